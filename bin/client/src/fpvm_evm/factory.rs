@@ -1,8 +1,13 @@
 //! [`EvmFactory`] implementation for the EVM in the FPVM environment.
 
-use super::precompiles::OpFpvmPrecompiles;
+use super::{
+    AcceleratedPrecompileFn,
+    precompiles::{OpFpvmPrecompiles, fpvm_precompile_overrides},
+};
+use alloc::sync::Arc;
 use alloy_evm::{Database, EvmEnv, EvmFactory};
 use alloy_op_evm::OpEvm;
+use kona_executor::PrecompileOverrides;
 use kona_preimage::{HintWriterClient, PreimageOracleClient};
 use op_revm::{
     DefaultOp, OpContext, OpEvm as RevmOpEvm, OpHaltReason, OpSpecId, OpTransaction,
@@ -22,6 +27,8 @@ pub struct FpvmOpEvmFactory<H, O> {
     hint_writer: H,
     /// The oracle reader.
     oracle_reader: O,
+    /// The registry of precompile overrides.
+    overrides: Arc<PrecompileOverrides<AcceleratedPrecompileFn<H, O>>>,
 }
 
 impl<H, O> FpvmOpEvmFactory<H, O>
@@ -29,9 +36,23 @@ where
     H: HintWriterClient + Clone + Send + Sync,
     O: PreimageOracleClient + Clone + Send + Sync,
 {
-    /// Creates a new [`FpvmOpEvmFactory`].
+    /// Creates a new [`FpvmOpEvmFactory`] with the default FPVM-accelerated precompiles.
     pub fn new(hint_writer: H, oracle_reader: O) -> Self {
-        Self { hint_writer, oracle_reader }
+        Self { hint_writer, oracle_reader, overrides: Arc::new(fpvm_precompile_overrides()) }
+    }
+
+    /// Replaces the registry of precompile overrides used by the produced EVMs.
+    pub fn with_precompile_overrides(
+        mut self,
+        overrides: PrecompileOverrides<AcceleratedPrecompileFn<H, O>>,
+    ) -> Self {
+        self.overrides = Arc::new(overrides);
+        self
+    }
+
+    /// Returns a reference to the registry of precompile overrides.
+    pub fn precompile_overrides(&self) -> &PrecompileOverrides<AcceleratedPrecompileFn<H, O>> {
+        &self.overrides
     }
 
     /// Returns a reference to the inner [`HintWriterClient`].
@@ -73,6 +94,7 @@ where
             instruction: EthInstructions::new_mainnet(),
             precompiles: OpFpvmPrecompiles::new_with_spec(
                 spec_id,
+                self.overrides.clone(),
                 self.hint_writer.clone(),
                 self.oracle_reader.clone(),
             ),
@@ -96,6 +118,7 @@ where
             instruction: EthInstructions::new_mainnet(),
             precompiles: OpFpvmPrecompiles::new_with_spec(
                 spec_id,
+                self.overrides.clone(),
                 self.hint_writer.clone(),
                 self.oracle_reader.clone(),
             ),
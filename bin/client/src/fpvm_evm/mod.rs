@@ -3,6 +3,7 @@
 //! [`EvmFactory`]: alloy_evm::EvmFactory
 
 mod precompiles;
+pub use precompiles::AcceleratedPrecompileFn;
 
 mod factory;
 pub use factory::FpvmOpEvmFactory;
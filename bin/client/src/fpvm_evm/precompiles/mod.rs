@@ -4,7 +4,8 @@
 //! [`PrecompileProvider`]: revm::handler::PrecompileProvider
 
 mod provider;
-pub(crate) use provider::OpFpvmPrecompiles;
+pub use provider::AcceleratedPrecompileFn;
+pub(crate) use provider::{OpFpvmPrecompiles, fpvm_precompile_overrides};
 
 mod bls12_g1_add;
 mod bls12_g1_msm;
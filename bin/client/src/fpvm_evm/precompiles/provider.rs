@@ -3,8 +3,9 @@
 use crate::fpvm_evm::precompiles::{
     ecrecover::ECRECOVER_ADDR, kzg_point_eval::KZG_POINT_EVAL_ADDR,
 };
-use alloc::{boxed::Box, string::String, vec, vec::Vec};
+use alloc::{boxed::Box, string::String, sync::Arc};
 use alloy_primitives::{Address, Bytes};
+use kona_executor::PrecompileOverrides;
 use kona_preimage::{HintWriterClient, PreimageOracleClient};
 use op_revm::{
     OpSpecId,
@@ -15,7 +16,7 @@ use revm::{
     handler::{EthPrecompiles, PrecompileProvider},
     interpreter::{CallInputs, Gas, InstructionResult, InterpreterResult},
     precompile::{PrecompileError, PrecompileResult, Precompiles, bls12_381_const, bn254},
-    primitives::{hardfork::SpecId, hash_map::HashMap},
+    primitives::{HashMap, hardfork::SpecId},
 };
 
 /// The FPVM-accelerated precompiles.
@@ -23,6 +24,8 @@ use revm::{
 pub struct OpFpvmPrecompiles<H, O> {
     /// The default [`EthPrecompiles`] provider.
     inner: EthPrecompiles,
+    /// The registry of precompile overrides, across all hardforks.
+    overrides: Arc<PrecompileOverrides<AcceleratedPrecompileFn<H, O>>>,
     /// The accelerated precompiles for the current [`OpSpecId`].
     accelerated_precompiles: HashMap<Address, AcceleratedPrecompileFn<H, O>>,
     /// The [`OpSpecId`] of the precompiles.
@@ -38,9 +41,15 @@ where
     H: HintWriterClient + Clone + Send + Sync + 'static,
     O: PreimageOracleClient + Clone + Send + Sync + 'static,
 {
-    /// Create a new precompile provider with the given [`OpSpecId`].
+    /// Create a new precompile provider with the given [`OpSpecId`] and
+    /// [`PrecompileOverrides`].
     #[inline]
-    pub fn new_with_spec(spec: OpSpecId, hint_writer: H, oracle_reader: O) -> Self {
+    pub fn new_with_spec(
+        spec: OpSpecId,
+        overrides: Arc<PrecompileOverrides<AcceleratedPrecompileFn<H, O>>>,
+        hint_writer: H,
+        oracle_reader: O,
+    ) -> Self {
         let precompiles = match spec {
             spec @ (OpSpecId::BEDROCK |
             OpSpecId::REGOLITH |
@@ -51,23 +60,12 @@ where
             OpSpecId::ISTHMUS | OpSpecId::INTEROP | OpSpecId::OSAKA | OpSpecId::JOVIAN => isthmus(),
         };
 
-        let accelerated_precompiles = match spec {
-            OpSpecId::BEDROCK | OpSpecId::REGOLITH | OpSpecId::CANYON => {
-                accelerated_bedrock::<H, O>()
-            }
-            OpSpecId::ECOTONE | OpSpecId::FJORD => accelerated_ecotone::<H, O>(),
-            OpSpecId::GRANITE | OpSpecId::HOLOCENE => accelerated_granite::<H, O>(),
-            OpSpecId::ISTHMUS | OpSpecId::INTEROP | OpSpecId::OSAKA | OpSpecId::JOVIAN => {
-                accelerated_isthmus::<H, O>()
-            }
-        };
+        let accelerated_precompiles = overrides.active(spec);
 
         Self {
             inner: EthPrecompiles { precompiles, spec: SpecId::default() },
-            accelerated_precompiles: accelerated_precompiles
-                .into_iter()
-                .map(|p| (p.address, p.precompile))
-                .collect(),
+            overrides,
+            accelerated_precompiles,
             spec,
             hint_writer,
             oracle_reader,
@@ -88,7 +86,12 @@ where
         if spec == self.spec {
             return false;
         }
-        *self = Self::new_with_spec(spec, self.hint_writer.clone(), self.oracle_reader.clone());
+        *self = Self::new_with_spec(
+            spec,
+            self.overrides.clone(),
+            self.hint_writer.clone(),
+            self.oracle_reader.clone(),
+        );
         true
     }
 
@@ -159,100 +162,68 @@ where
 }
 
 /// A precompile function that can be accelerated by the FPVM.
-type AcceleratedPrecompileFn<H, O> = fn(&[u8], u64, &H, &O) -> PrecompileResult;
-
-/// A tuple type for accelerated precompiles with an associated [`Address`].
-struct AcceleratedPrecompile<H, O> {
-    /// The address of the precompile.
-    address: Address,
-    /// The precompile function.
-    precompile: AcceleratedPrecompileFn<H, O>,
-}
-
-impl<H, O> AcceleratedPrecompile<H, O> {
-    /// Create a new accelerated precompile.
-    fn new(address: Address, precompile: AcceleratedPrecompileFn<H, O>) -> Self {
-        Self { address, precompile }
-    }
-}
+pub type AcceleratedPrecompileFn<H, O> = fn(&[u8], u64, &H, &O) -> PrecompileResult;
 
-/// The accelerated precompiles for the bedrock spec.
-fn accelerated_bedrock<H, O>() -> Vec<AcceleratedPrecompile<H, O>>
+/// Returns the default registry of FPVM-accelerated precompile overrides.
+pub(crate) fn fpvm_precompile_overrides<H, O>() -> PrecompileOverrides<AcceleratedPrecompileFn<H, O>>
 where
     H: HintWriterClient + Send + Sync,
     O: PreimageOracleClient + Send + Sync,
 {
-    vec![
-        AcceleratedPrecompile::new(ECRECOVER_ADDR, super::ecrecover::fpvm_ec_recover::<H, O>),
-        AcceleratedPrecompile::new(
+    PrecompileOverrides::new()
+        // Bedrock
+        .with_override(ECRECOVER_ADDR, OpSpecId::BEDROCK, super::ecrecover::fpvm_ec_recover::<H, O>)
+        .with_override(
             bn254::pair::ADDRESS,
+            OpSpecId::BEDROCK,
             super::bn128_pair::fpvm_bn128_pair::<H, O>,
-        ),
-    ]
-}
-
-/// The accelerated precompiles for the ecotone spec.
-fn accelerated_ecotone<H, O>() -> Vec<AcceleratedPrecompile<H, O>>
-where
-    H: HintWriterClient + Send + Sync,
-    O: PreimageOracleClient + Send + Sync,
-{
-    let mut base = accelerated_bedrock::<H, O>();
-    base.push(AcceleratedPrecompile::new(
-        KZG_POINT_EVAL_ADDR,
-        super::kzg_point_eval::fpvm_kzg_point_eval::<H, O>,
-    ));
-    base
-}
-
-/// The accelerated precompiles for the granite spec.
-fn accelerated_granite<H, O>() -> Vec<AcceleratedPrecompile<H, O>>
-where
-    H: HintWriterClient + Send + Sync,
-    O: PreimageOracleClient + Send + Sync,
-{
-    let mut base = accelerated_ecotone::<H, O>();
-    base.push(AcceleratedPrecompile::new(
-        bn254::pair::ADDRESS,
-        super::bn128_pair::fpvm_bn128_pair_granite::<H, O>,
-    ));
-    base
-}
-
-/// The accelerated precompiles for the isthmus spec.
-fn accelerated_isthmus<H, O>() -> Vec<AcceleratedPrecompile<H, O>>
-where
-    H: HintWriterClient + Send + Sync,
-    O: PreimageOracleClient + Send + Sync,
-{
-    let mut base = accelerated_granite::<H, O>();
-    base.push(AcceleratedPrecompile::new(
-        bls12_381_const::G1_ADD_ADDRESS,
-        super::bls12_g1_add::fpvm_bls12_g1_add::<H, O>,
-    ));
-    base.push(AcceleratedPrecompile::new(
-        bls12_381_const::G1_MSM_ADDRESS,
-        super::bls12_g1_msm::fpvm_bls12_g1_msm::<H, O>,
-    ));
-    base.push(AcceleratedPrecompile::new(
-        bls12_381_const::G2_ADD_ADDRESS,
-        super::bls12_g2_add::fpvm_bls12_g2_add::<H, O>,
-    ));
-    base.push(AcceleratedPrecompile::new(
-        bls12_381_const::G2_MSM_ADDRESS,
-        super::bls12_g2_msm::fpvm_bls12_g2_msm::<H, O>,
-    ));
-    base.push(AcceleratedPrecompile::new(
-        bls12_381_const::MAP_FP_TO_G1_ADDRESS,
-        super::bls12_map_fp::fpvm_bls12_map_fp::<H, O>,
-    ));
-    base.push(AcceleratedPrecompile::new(
-        bls12_381_const::MAP_FP2_TO_G2_ADDRESS,
-        super::bls12_map_fp2::fpvm_bls12_map_fp2::<H, O>,
-    ));
-    base.push(AcceleratedPrecompile::new(
-        bls12_381_const::PAIRING_ADDRESS,
-        super::bls12_pair::fpvm_bls12_pairing::<H, O>,
-    ));
-    base
+        )
+        // Ecotone
+        .with_override(
+            KZG_POINT_EVAL_ADDR,
+            OpSpecId::ECOTONE,
+            super::kzg_point_eval::fpvm_kzg_point_eval::<H, O>,
+        )
+        // Granite
+        .with_override(
+            bn254::pair::ADDRESS,
+            OpSpecId::GRANITE,
+            super::bn128_pair::fpvm_bn128_pair_granite::<H, O>,
+        )
+        // Isthmus
+        .with_override(
+            bls12_381_const::G1_ADD_ADDRESS,
+            OpSpecId::ISTHMUS,
+            super::bls12_g1_add::fpvm_bls12_g1_add::<H, O>,
+        )
+        .with_override(
+            bls12_381_const::G1_MSM_ADDRESS,
+            OpSpecId::ISTHMUS,
+            super::bls12_g1_msm::fpvm_bls12_g1_msm::<H, O>,
+        )
+        .with_override(
+            bls12_381_const::G2_ADD_ADDRESS,
+            OpSpecId::ISTHMUS,
+            super::bls12_g2_add::fpvm_bls12_g2_add::<H, O>,
+        )
+        .with_override(
+            bls12_381_const::G2_MSM_ADDRESS,
+            OpSpecId::ISTHMUS,
+            super::bls12_g2_msm::fpvm_bls12_g2_msm::<H, O>,
+        )
+        .with_override(
+            bls12_381_const::MAP_FP_TO_G1_ADDRESS,
+            OpSpecId::ISTHMUS,
+            super::bls12_map_fp::fpvm_bls12_map_fp::<H, O>,
+        )
+        .with_override(
+            bls12_381_const::MAP_FP2_TO_G2_ADDRESS,
+            OpSpecId::ISTHMUS,
+            super::bls12_map_fp2::fpvm_bls12_map_fp2::<H, O>,
+        )
+        .with_override(
+            bls12_381_const::PAIRING_ADDRESS,
+            OpSpecId::ISTHMUS,
+            super::bls12_pair::fpvm_bls12_pairing::<H, O>,
+        )
 }
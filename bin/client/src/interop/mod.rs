@@ -16,7 +16,7 @@ use thiserror::Error;
 use tracing::{error, info};
 use transition::sub_transition;
 
-use crate::fpvm_evm::FpvmOpEvmFactory;
+use crate::{fpvm_evm::FpvmOpEvmFactory, report::ExitOutcome};
 
 pub(crate) mod consolidate;
 pub(crate) mod transition;
@@ -51,6 +51,25 @@ pub enum FaultProofProgramError {
     MissingRollupConfig(u64),
 }
 
+impl From<&FaultProofProgramError> for ExitOutcome {
+    fn from(err: &FaultProofProgramError) -> Self {
+        match err {
+            FaultProofProgramError::InvalidClaim(_, _) |
+            FaultProofProgramError::Bootstrap(
+                BootstrapError::InvalidPostState(_) | BootstrapError::InvalidToInvalid,
+            ) => Self::ClaimInvalid,
+            FaultProofProgramError::OracleProvider(e) |
+            FaultProofProgramError::Bootstrap(BootstrapError::Oracle(e)) => e.into(),
+            FaultProofProgramError::Bootstrap(BootstrapError::InvalidL1Config) |
+            FaultProofProgramError::MissingRollupConfig(_) => Self::ConfigError,
+            FaultProofProgramError::Driver(_) |
+            FaultProofProgramError::PipelineError(_) |
+            FaultProofProgramError::Consolidation(_) |
+            FaultProofProgramError::StateTransitionFailed => Self::ExecutionError,
+        }
+    }
+}
+
 /// Executes the interop fault proof program with the given [PreimageOracleClient] and
 /// [HintWriterClient].
 #[inline]
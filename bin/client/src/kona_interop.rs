@@ -8,6 +8,7 @@
 extern crate alloc;
 
 use alloc::string::String;
+use kona_client::report::ExitReport;
 use kona_preimage::{HintWriter, OracleReader};
 use kona_std_fpvm::{FileChannel, FileDescriptor};
 use kona_std_fpvm_proc::client_entry;
@@ -37,5 +38,14 @@ fn main() -> Result<(), String> {
             .expect("Failed to set tracing subscriber");
     }
 
-    kona_proof::block_on(kona_client::interop::run(ORACLE_READER, HINT_WRITER))
+    // Natively built client programs may connect to a host serving the channels on a socket.
+    #[cfg(feature = "client-socket")]
+    let result = match kona_client::socket::server_addr()? {
+        Some(addr) => kona_client::socket::run(&addr, kona_client::interop::run)?,
+        None => kona_proof::block_on(kona_client::interop::run(ORACLE_READER, HINT_WRITER)),
+    };
+    #[cfg(not(feature = "client-socket"))]
+    let result = kona_proof::block_on(kona_client::interop::run(ORACLE_READER, HINT_WRITER));
+    kona_std_fpvm::io::print_err(&ExitReport::new(&result).to_line());
+    result
 }
@@ -7,6 +7,13 @@
 
 extern crate alloc;
 
+#[cfg(feature = "client-socket")]
+extern crate std;
+
 pub mod fpvm_evm;
 pub mod interop;
+pub mod report;
 pub mod single;
+
+#[cfg(feature = "client-socket")]
+pub mod socket;
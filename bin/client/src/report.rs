@@ -0,0 +1,135 @@
+//! Structured exit reports of the fault proof program.
+//!
+//! When the program exits, it writes an [ExitReport] to stderr as a single line prefixed with
+//! [EXIT_REPORT_PREFIX], classifying the outcome of the run. The host consumes the report to set
+//! its exit code, and operators can tell an invalid claim apart from missing data.
+
+use alloc::{
+    format,
+    string::{String, ToString},
+};
+use core::fmt::Display;
+use kona_proof::errors::OracleProviderError;
+use serde::{Deserialize, Serialize};
+
+/// The prefix of the stderr line holding the serialized [ExitReport].
+pub const EXIT_REPORT_PREFIX: &str = "kona-exit-report: ";
+
+/// The classified outcome of a run of the fault proof program.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ExitOutcome {
+    /// The claim is valid.
+    Valid,
+    /// The claim is invalid.
+    ClaimInvalid,
+    /// Data could not be read from the preimage oracle.
+    OracleMissingData,
+    /// Derivation or execution failed.
+    ExecutionError,
+    /// The boot information or the chain configuration is invalid.
+    ConfigError,
+}
+
+impl ExitOutcome {
+    /// Returns the exit code of the host for the outcome.
+    pub const fn exit_code(&self) -> i32 {
+        match self {
+            Self::Valid => 0,
+            Self::ClaimInvalid => 1,
+            Self::OracleMissingData => 2,
+            Self::ExecutionError => 3,
+            Self::ConfigError => 4,
+        }
+    }
+}
+
+impl From<&OracleProviderError> for ExitOutcome {
+    fn from(err: &OracleProviderError) -> Self {
+        match err {
+            OracleProviderError::Preimage(_) => Self::OracleMissingData,
+            OracleProviderError::UnknownChainId(_) | OracleProviderError::Serde(_) => {
+                Self::ConfigError
+            }
+            _ => Self::ExecutionError,
+        }
+    }
+}
+
+/// The structured exit report of a run of the fault proof program.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExitReport {
+    /// The classified outcome.
+    pub outcome: ExitOutcome,
+    /// The error that the program failed with, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl ExitReport {
+    /// Creates an [ExitReport] from the result of a run, given the [ExitOutcome] of its error.
+    pub fn new<E>(result: &Result<(), E>) -> Self
+    where
+        E: Display,
+        for<'a> &'a E: Into<ExitOutcome>,
+    {
+        match result {
+            Ok(()) => Self { outcome: ExitOutcome::Valid, error: None },
+            Err(e) => Self { outcome: e.into(), error: Some(e.to_string()) },
+        }
+    }
+
+    /// Serializes the report into a line, prefixed with [EXIT_REPORT_PREFIX].
+    pub fn to_line(&self) -> String {
+        let report = serde_json::to_string(self).unwrap_or_default();
+        format!("{EXIT_REPORT_PREFIX}{report}\n")
+    }
+
+    /// Parses a report from a line written by [ExitReport::to_line]. Returns [None] if the line
+    /// does not hold a report.
+    pub fn from_line(line: &str) -> Option<Self> {
+        serde_json::from_str(line.trim_end().strip_prefix(EXIT_REPORT_PREFIX)?).ok()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::single::FaultProofProgramError;
+    use alloy_primitives::B256;
+    use kona_preimage::errors::PreimageOracleError;
+
+    #[test]
+    fn test_report_roundtrip() {
+        let result = Err(FaultProofProgramError::InvalidClaim(B256::ZERO, B256::ZERO));
+        let report = ExitReport::new(&result);
+        assert_eq!(report.outcome, ExitOutcome::ClaimInvalid);
+        assert_eq!(report.outcome.exit_code(), 1);
+
+        let line = report.to_line();
+        assert!(line.starts_with("kona-exit-report: {\"outcome\":\"claim-invalid\""));
+        assert_eq!(ExitReport::from_line(&line), Some(report));
+        assert_eq!(ExitReport::from_line("Program encountered fatal error"), None);
+    }
+
+    #[test]
+    fn test_valid_report() {
+        let report = ExitReport::new::<FaultProofProgramError>(&Ok(()));
+        assert_eq!(report, ExitReport { outcome: ExitOutcome::Valid, error: None });
+        assert_eq!(report.to_line(), "kona-exit-report: {\"outcome\":\"valid\"}\n");
+    }
+
+    #[test]
+    fn test_classify_oracle_errors() {
+        let missing = OracleProviderError::Preimage(PreimageOracleError::KeyNotFound);
+        assert_eq!(ExitOutcome::from(&missing), ExitOutcome::OracleMissingData);
+        assert_eq!(
+            ExitOutcome::from(&OracleProviderError::UnknownChainId(1)),
+            ExitOutcome::ConfigError
+        );
+        assert_eq!(
+            ExitOutcome::from(&OracleProviderError::BlockNumberPastHead(2, 1)),
+            ExitOutcome::ExecutionError
+        );
+    }
+}
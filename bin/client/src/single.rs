@@ -1,12 +1,14 @@
 //! Single-chain fault proof program entrypoint.
 
-use crate::fpvm_evm::FpvmOpEvmFactory;
+use crate::{fpvm_evm::FpvmOpEvmFactory, report::ExitOutcome};
 use alloc::sync::Arc;
 use alloy_consensus::Sealed;
 use alloy_primitives::B256;
 use core::fmt::Debug;
 use kona_derive::{EthereumDataSource, PipelineErrorKind};
 use kona_driver::{Driver, DriverError};
+#[cfg(feature = "client-trie-node-cache")]
+use kona_executor::{DEFAULT_TRIE_NODE_CACHE_CAPACITY, TrieNodeCache};
 use kona_executor::{ExecutorError, TrieDBProvider};
 use kona_preimage::{CommsClient, HintWriterClient, PreimageKey, PreimageOracleClient};
 use kona_proof::{
@@ -26,6 +28,10 @@ pub enum FaultProofProgramError {
     /// The claim is invalid.
     #[error("Invalid claim. Expected {0}, actual {1}")]
     InvalidClaim(B256, B256),
+    /// The intermediate claims are not strictly ascending between the agreed and claimed L2
+    /// blocks.
+    #[error("Intermediate claim at L2 block #{0} is out of order or out of range")]
+    InvalidIntermediateClaim(u64),
     /// An error occurred in the Oracle provider.
     #[error(transparent)]
     OracleProviderError(#[from] OracleProviderError),
@@ -37,6 +43,19 @@ pub enum FaultProofProgramError {
     Driver(#[from] DriverError<ExecutorError>),
 }
 
+impl From<&FaultProofProgramError> for ExitOutcome {
+    fn from(err: &FaultProofProgramError) -> Self {
+        match err {
+            FaultProofProgramError::InvalidClaim(_, _) => Self::ClaimInvalid,
+            FaultProofProgramError::InvalidIntermediateClaim(_) => Self::ConfigError,
+            FaultProofProgramError::OracleProviderError(e) => e.into(),
+            FaultProofProgramError::PipelineError(_) | FaultProofProgramError::Driver(_) => {
+                Self::ExecutionError
+            }
+        }
+    }
+}
+
 /// Executes the fault proof program with the given [PreimageOracleClient] and [HintWriterClient].
 #[inline]
 pub async fn run<P, H>(oracle_client: P, hint_client: H) -> Result<(), FaultProofProgramError>
@@ -53,6 +72,9 @@ where
     let oracle =
         Arc::new(CachingOracle::new(ORACLE_LRU_SIZE, oracle_client.clone(), hint_client.clone()));
     let boot = BootInfo::load(oracle.as_ref()).await?;
+    if boot.preimage_batching {
+        oracle.enable_batching();
+    }
     let l1_config = boot.l1_config;
     let rollup_config = Arc::new(boot.rollup_config);
     let safe_head_hash = fetch_safe_head_hash(oracle.as_ref(), boot.agreed_l2_output_root).await?;
@@ -92,6 +114,18 @@ where
         return Ok(());
     }
 
+    // Intermediate claims must be strictly ascending, and lie between the agreed and claimed L2
+    // blocks.
+    let mut last_claimed = safe_head.number;
+    for claim in &boot.intermediate_claims {
+        if claim.l2_block_number <= last_claimed ||
+            claim.l2_block_number >= boot.claimed_l2_block_number
+        {
+            return Err(FaultProofProgramError::InvalidIntermediateClaim(claim.l2_block_number));
+        }
+        last_claimed = claim.l2_block_number;
+    }
+
     ////////////////////////////////////////////////////////////////
     //                   DERIVATION & EXECUTION                   //
     ////////////////////////////////////////////////////////////////
@@ -124,21 +158,68 @@ where
     )
     .await?;
 
+    // Keep the decoded trie nodes across the blocks of the disputed range. The cache is opt-in, as
+    // it holds up to `DEFAULT_TRIE_NODE_CACHE_CAPACITY` decoded nodes in the memory of the VM.
+    #[cfg(feature = "client-trie-node-cache")]
+    let trie_provider = TrieNodeCache::new(l2_provider.clone(), DEFAULT_TRIE_NODE_CACHE_CAPACITY);
+    #[cfg(not(feature = "client-trie-node-cache"))]
+    let trie_provider = l2_provider.clone();
     let executor = KonaExecutor::new(
         rollup_config.as_ref(),
-        l2_provider.clone(),
+        trie_provider.clone(),
         l2_provider,
         evm_factory,
         None,
-    );
+    )
+    // Prefetching reveals trie nodes in batches, which only saves round trips to the host if it
+    // serves batched preimage requests.
+    .with_state_prefetch(boot.preimage_batching);
     let mut driver = Driver::new(cursor, executor, pipeline);
 
+    // Validate each intermediate claim as the derivation pipeline reaches its L2 block. If the
+    // data source is exhausted early, the intermediate claims are validated against the final
+    // safe head, as with the disputed claim.
+    for claim in &boot.intermediate_claims {
+        let (safe_head, output_root) =
+            driver.advance_to_target(rollup_config.as_ref(), Some(claim.l2_block_number)).await?;
+
+        if output_root != claim.output_root {
+            error!(
+                target: "client",
+                number = safe_head.block_info.number,
+                output_root = ?output_root,
+                claimed_output_root = ?claim.output_root,
+                "Failed to validate intermediate L2 block",
+            );
+            return Err(FaultProofProgramError::InvalidClaim(output_root, claim.output_root));
+        }
+
+        info!(
+            target: "client",
+            number = safe_head.block_info.number,
+            output_root = ?output_root,
+            "Successfully validated intermediate L2 block",
+        );
+    }
+
     // Run the derivation pipeline until we are able to produce the output root of the claimed
     // L2 block.
     let (safe_head, output_root) = driver
         .advance_to_target(rollup_config.as_ref(), Some(boot.claimed_l2_block_number))
         .await?;
 
+    #[cfg(feature = "client-trie-node-cache")]
+    {
+        let cache_stats = trie_provider.stats();
+        info!(
+            target: "client",
+            hits = cache_stats.hits,
+            misses = cache_stats.misses,
+            hit_rate = cache_stats.hit_rate(),
+            "Trie node cache statistics",
+        );
+    }
+
     ////////////////////////////////////////////////////////////////
     //                          EPILOGUE                          //
     ////////////////////////////////////////////////////////////////
@@ -0,0 +1,40 @@
+//! Runs the client program over hint and preimage channels served by the host on a socket, so
+//! that a natively built client program can run in a separate container or on a separate machine
+//! from the host.
+
+use alloc::string::{String, ToString};
+use core::future::Future;
+use kona_preimage::{ChannelAddr, ChannelKind, HintWriter, OracleReader, SocketChannel};
+
+/// The environment variable holding the address that the host serves the channels on, formatted
+/// as `tcp://<host>:<port>` or `unix://<path>`. If unset, the client program communicates with
+/// the host over the file descriptors inherited from its parent process.
+pub const SERVER_ADDR_ENV: &str = "KONA_CLIENT_SERVER_ADDR";
+
+/// Returns the address that the host serves the channels on, read from [SERVER_ADDR_ENV].
+pub fn server_addr() -> Result<Option<ChannelAddr>, String> {
+    std::env::var(SERVER_ADDR_ENV).ok().map(|addr| addr.parse()).transpose()
+}
+
+/// Connects to the host serving the channels on the given address, and runs the client program
+/// over them to completion.
+pub fn run<F>(
+    addr: &ChannelAddr,
+    program: impl FnOnce(OracleReader<SocketChannel>, HintWriter<SocketChannel>) -> F,
+) -> Result<F::Output, String>
+where
+    F: Future,
+{
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .map_err(|err| err.to_string())?;
+    runtime.block_on(async {
+        let preimage = SocketChannel::connect(addr, ChannelKind::Preimage)
+            .await
+            .map_err(|err| err.to_string())?;
+        let hint =
+            SocketChannel::connect(addr, ChannelKind::Hint).await.map_err(|err| err.to_string())?;
+        Ok(program(OracleReader::new(preimage), HintWriter::new(hint)).await)
+    })
+}
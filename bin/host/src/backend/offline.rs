@@ -8,14 +8,21 @@ use kona_preimage::{
 };
 use std::sync::Arc;
 use tokio::sync::RwLock;
+use tracing::error;
 
 /// A [KeyValueStore]-backed implementation of the [PreimageFetcher] trait.
+///
+/// The [OfflineHostBackend] never reaches out to remote sources. If a requested preimage is not
+/// present within the [KeyValueStore], the request fails with
+/// [PreimageOracleError::MissingPreimage], and the missing key is reported alongside the last hint
+/// received from the client.
 #[derive(Debug)]
 pub struct OfflineHostBackend<KV>
 where
     KV: KeyValueStore + ?Sized,
 {
     inner: Arc<RwLock<KV>>,
+    last_hint: RwLock<Option<String>>,
 }
 
 impl<KV> OfflineHostBackend<KV>
@@ -24,7 +31,7 @@ where
 {
     /// Create a new [OfflineHostBackend] from the given [KeyValueStore].
     pub const fn new(kv_store: Arc<RwLock<KV>>) -> Self {
-        Self { inner: kv_store }
+        Self { inner: kv_store, last_hint: RwLock::const_new(None) }
     }
 }
 
@@ -35,7 +42,18 @@ where
 {
     async fn get_preimage(&self, key: PreimageKey) -> PreimageOracleResult<Vec<u8>> {
         let kv_store = self.inner.read().await;
-        kv_store.get(key.into()).ok_or(PreimageOracleError::KeyNotFound)
+        if let Some(preimage) = kv_store.get(key.into()) {
+            return Ok(preimage);
+        }
+
+        error!(
+            target: "offline_host_backend",
+            key = %key,
+            key_type = ?key.key_type(),
+            last_hint = ?self.last_hint.read().await.as_deref(),
+            "Missing preimage key in offline preimage store"
+        );
+        Err(PreimageOracleError::MissingPreimage(key))
     }
 }
 
@@ -44,7 +62,32 @@ impl<KV> HintRouter for OfflineHostBackend<KV>
 where
     KV: KeyValueStore + Send + Sync + ?Sized,
 {
-    async fn route_hint(&self, _hint: String) -> PreimageOracleResult<()> {
+    async fn route_hint(&self, hint: String) -> PreimageOracleResult<()> {
+        self.last_hint.write().await.replace(hint);
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::MemoryKeyValueStore;
+    use alloy_primitives::keccak256;
+
+    #[tokio::test]
+    async fn test_offline_backend_reports_missing_preimage() {
+        let present = PreimageKey::new_keccak256(*keccak256(b"present"));
+        let missing = PreimageKey::new_keccak256(*keccak256(b"missing"));
+
+        let mut kv = MemoryKeyValueStore::new();
+        kv.set(present.into(), b"present".to_vec()).unwrap();
+        let backend = OfflineHostBackend::new(Arc::new(RwLock::new(kv)));
+
+        backend.route_hint("l2-state-node 0xfacade".to_string()).await.unwrap();
+        assert_eq!(backend.get_preimage(present).await.unwrap(), b"present");
+        assert!(matches!(
+            backend.get_preimage(missing).await,
+            Err(PreimageOracleError::MissingPreimage(key)) if key == missing
+        ));
+    }
+}
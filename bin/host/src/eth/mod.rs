@@ -3,6 +3,7 @@
 use alloy_provider::{Network, RootProvider};
 use alloy_rpc_client::RpcClient;
 use alloy_transport_http::Http;
+use kona_providers_alloy::{OnlineBlobProvider, RedundantBeaconClient};
 use reqwest::Client;
 
 mod precompiles;
@@ -14,3 +15,15 @@ pub fn http_provider<N: Network>(url: &str) -> RootProvider<N> {
     let http = Http::<Client>::new(url);
     RootProvider::new(RpcClient::new(http, true))
 }
+
+/// The blob provider used by the host, which falls back to an optional blob archive for blobs that
+/// the beacon node cannot serve, e.g. because they expired.
+pub type HostBlobProvider = OnlineBlobProvider<RedundantBeaconClient>;
+
+/// Returns a [HostBlobProvider] for the given beacon node URL and optional blob archive URL.
+pub async fn blob_provider(beacon_url: String, archive_url: Option<String>) -> HostBlobProvider {
+    OnlineBlobProvider::init(RedundantBeaconClient::new_http(
+        core::iter::once(beacon_url).chain(archive_url),
+    ))
+    .await
+}
@@ -3,19 +3,22 @@
 use super::{InteropHintHandler, InteropLocalInputs};
 use crate::{
     DiskKeyValueStore, MemoryKeyValueStore, OfflineHostBackend, OnlineHostBackend,
-    OnlineHostBackendCfg, PreimageServer, SharedKeyValueStore, SplitKeyValueStore,
-    eth::http_provider, server::PreimageServerError,
+    OnlineHostBackendCfg, PreimageServer, SharedDiskKeyValueStore, SharedKeyValueStore,
+    SplitKeyValueStore,
+    eth::{HostBlobProvider, blob_provider, http_provider},
+    server::PreimageServerError,
 };
 use alloy_primitives::{B256, Bytes};
 use alloy_provider::{Provider, RootProvider};
 use clap::Parser;
 use kona_cli::cli_styles;
+use kona_client::report::ExitReport;
 use kona_genesis::{L1ChainConfig, RollupConfig};
 use kona_preimage::{
-    BidirectionalChannel, Channel, HintReader, HintWriter, OracleReader, OracleServer,
+    BidirectionalChannel, Channel, ChannelAddr, HintReader, HintWriter, OracleReader, OracleServer,
+    SocketChannelListener,
 };
 use kona_proof_interop::HintType;
-use kona_providers_alloy::{OnlineBeaconClient, OnlineBlobProvider};
 use kona_std_fpvm::{FileChannel, FileDescriptor};
 use op_alloy_network::Optimism;
 use serde::Serialize;
@@ -24,6 +27,7 @@ use tokio::{
     sync::RwLock,
     task::{self, JoinHandle},
 };
+use tracing::info;
 
 /// The interop host application.
 #[derive(Default, Parser, Serialize, Clone, Debug)]
@@ -72,15 +76,31 @@ pub struct InteropHost {
         env
     )]
     pub l1_beacon_address: Option<String>,
+    /// Address of a blob archive serving the L1 Beacon API blob sidecar endpoints. Used as a
+    /// fallback for blobs that have expired from the L1 Beacon API endpoint.
+    #[arg(long, visible_alias = "blob-archive", requires = "l1_beacon_address", env)]
+    pub l1_blob_archive_address: Option<String>,
     /// The Data Directory for preimage data storage. Optional if running in online mode,
     /// required if running in offline mode.
     #[arg(
         long,
         visible_alias = "db",
         required_unless_present_all = ["l2_node_addresses", "l1_node_address", "l1_beacon_address"],
+        required_unless_present_any = ["shared_data_dir"],
         env
     )]
     pub data_dir: Option<PathBuf>,
+    /// The data directory of a persistent, content-addressed preimage store shared across runs.
+    /// Preimages fetched by prior runs are served from this store rather than being refetched.
+    #[arg(long, conflicts_with = "data_dir", env)]
+    pub shared_data_dir: Option<PathBuf>,
+    /// Compact the shared preimage store when the host exits.
+    #[arg(long, requires = "shared_data_dir", env)]
+    pub compact_shared_data_dir: bool,
+    /// Run entirely from the preimage store at `--data-dir` or `--shared-data-dir`, refusing all
+    /// RPC access. Fails with a report of the missing preimage key if the store is incomplete.
+    #[arg(long, conflicts_with_all = ["l2_node_addresses", "l1_node_address", "l1_beacon_address"], env)]
+    pub offline: bool,
     /// Run the client program natively.
     #[arg(long, conflicts_with = "server", required_unless_present = "server")]
     pub native: bool,
@@ -88,6 +108,13 @@ pub struct InteropHost {
     /// host will run the client program in the host process.
     #[arg(long, conflicts_with = "native", required_unless_present = "native")]
     pub server: bool,
+    /// Address to serve the hint and preimage channels on in pre-image server mode, formatted as
+    /// `tcp://<host>:<port>` or `unix://<path>`. If not provided, the channels are served over the
+    /// file descriptors inherited from the parent process. Allows the client program to run in a
+    /// separate container or on a separate machine from the host. The client program, built with
+    /// the `client-socket` feature, connects to the address set in `KONA_CLIENT_SERVER_ADDR`.
+    #[arg(long, requires = "server", env)]
+    pub server_addr: Option<ChannelAddr>,
     /// Path to rollup configs. If provided, the host will use this config instead of attempting to
     /// look up the configs in the superchain registry.
     /// The rollup configs should be stored as serde-JSON serialized files.
@@ -124,6 +151,9 @@ pub enum InteropHostError {
     /// An error when no provider found for chain ID.
     #[error("No provider found for chain ID: {0}")]
     RootProviderError(u64),
+    /// The key-value store could not be opened.
+    #[error("Key-value store error: {0}")]
+    KeyValueStoreError(anyhow::Error),
     /// Any other error.
     #[error("Error: {0}")]
     Other(&'static str),
@@ -132,7 +162,13 @@ pub enum InteropHostError {
 impl InteropHost {
     /// Starts the [InteropHost] application.
     pub async fn start(self) -> Result<(), InteropHostError> {
-        if self.server {
+        if let Some(addr) = &self.server_addr {
+            let listener = SocketChannelListener::bind(addr).await?;
+            info!(target: "host", %addr, "Waiting for the client program to connect");
+            let (hint, preimage) = listener.accept().await;
+
+            self.start_server(hint, preimage).await?.await?
+        } else if self.server {
             let hint = FileChannel::new(FileDescriptor::HintRead, FileDescriptor::HintWrite);
             let preimage =
                 FileChannel::new(FileDescriptor::PreimageRead, FileDescriptor::PreimageWrite);
@@ -204,16 +240,20 @@ impl InteropHost {
 
         let (_, client_result) = tokio::try_join!(server_task, client_task)?;
 
-        // Bubble up the exit status of the client program if execution completes.
-        std::process::exit(client_result.is_err() as i32)
+        // Report the outcome of the client program, and bubble up its exit status if execution
+        // completes.
+        let report = ExitReport::new(&client_result);
+        eprint!("{}", report.to_line());
+        std::process::exit(report.outcome.exit_code())
     }
 
     /// Returns `true` if the host is running in offline mode.
     pub const fn is_offline(&self) -> bool {
-        self.l1_node_address.is_none() &&
-            self.l2_node_addresses.is_none() &&
-            self.l1_beacon_address.is_none() &&
-            self.data_dir.is_some()
+        self.offline ||
+            (self.l1_node_address.is_none() &&
+                self.l2_node_addresses.is_none() &&
+                self.l1_beacon_address.is_none() &&
+                (self.data_dir.is_some() || self.shared_data_dir.is_some()))
     }
 
     /// Reads the [RollupConfig]s from the file system and returns a map of L2 chain ID ->
@@ -256,7 +296,14 @@ impl InteropHost {
     fn create_key_value_store(&self) -> Result<SharedKeyValueStore, InteropHostError> {
         let local_kv_store = InteropLocalInputs::new(self.clone());
 
-        let kv_store: SharedKeyValueStore = if let Some(ref data_dir) = self.data_dir {
+        let kv_store: SharedKeyValueStore = if let Some(ref shared_data_dir) = self.shared_data_dir
+        {
+            let shared_kv_store = SharedDiskKeyValueStore::new(shared_data_dir.clone())
+                .map_err(InteropHostError::KeyValueStoreError)?
+                .with_compaction(self.compact_shared_data_dir);
+            let split_kv_store = SplitKeyValueStore::new(local_kv_store, shared_kv_store);
+            Arc::new(RwLock::new(split_kv_store))
+        } else if let Some(ref data_dir) = self.data_dir {
             let disk_kv_store = DiskKeyValueStore::new(data_dir.clone());
             let split_kv_store = SplitKeyValueStore::new(local_kv_store, disk_kv_store);
             Arc::new(RwLock::new(split_kv_store))
@@ -275,11 +322,12 @@ impl InteropHost {
             self.l1_node_address.as_ref().ok_or(InteropHostError::Other("Provider must be set"))?,
         );
 
-        let blob_provider = OnlineBlobProvider::init(OnlineBeaconClient::new_http(
+        let blob_provider = blob_provider(
             self.l1_beacon_address
                 .clone()
                 .ok_or(InteropHostError::Other("Beacon API URL must be set"))?,
-        ))
+            self.l1_blob_archive_address.clone(),
+        )
         .await;
 
         // Resolve all chain IDs to their corresponding providers.
@@ -309,7 +357,7 @@ pub struct InteropProviders {
     /// The L1 EL provider.
     pub l1: RootProvider,
     /// The L1 beacon node provider.
-    pub blobs: OnlineBlobProvider<OnlineBeaconClient>,
+    pub blobs: HostBlobProvider,
     /// The L2 EL providers, keyed by chain ID.
     pub l2s: HashMap<u64, RootProvider<Optimism>>,
 }
@@ -16,7 +16,6 @@ use alloy_provider::Provider;
 use alloy_rlp::{Decodable, Encodable};
 use alloy_rpc_types::Block;
 use anyhow::{Result, anyhow, ensure};
-use ark_ff::{BigInteger, PrimeField};
 use async_trait::async_trait;
 use kona_derive::EthereumDataSource;
 use kona_driver::Driver;
@@ -28,7 +27,7 @@ use kona_preimage::{
 use kona_proof::{
     CachingOracle, Hint,
     executor::KonaExecutor,
-    l1::{OracleBlobProvider, OracleL1ChainProvider, OraclePipeline, ROOTS_OF_UNITY},
+    l1::{OracleBlobProvider, OracleL1ChainProvider, OraclePipeline, root_of_unity_be},
     l2::OracleL2ChainProvider,
     sync::new_oracle_pipeline_cursor,
 };
@@ -137,9 +136,7 @@ impl HintHandler for InteropHintHandler {
                 let mut blob_key = [0u8; 80];
                 blob_key[..48].copy_from_slice(commitment.as_ref());
                 for i in 0..FIELD_ELEMENTS_PER_BLOB {
-                    blob_key[48..].copy_from_slice(
-                        ROOTS_OF_UNITY[i as usize].into_bigint().to_bytes_be().as_ref(),
-                    );
+                    blob_key[48..].copy_from_slice(root_of_unity_be(i as usize));
                     let blob_key_hash = keccak256(blob_key.as_ref());
 
                     kv_lock
@@ -243,7 +240,7 @@ impl HintHandler for InteropHintHandler {
 
                 // Fetch the storage root for the L2 head block.
                 let l2_to_l1_message_passer = l2_provider
-                    .get_proof(Predeploys::L2_TO_L1_MESSAGE_PASSER, Default::default())
+                    .get_proof(Predeploys::message_passer(&rollup_config), Default::default())
                     .block_id(block_number.into())
                     .await?;
 
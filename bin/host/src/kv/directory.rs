@@ -0,0 +1,77 @@
+//! Contains a concrete implementation of the [KeyValueStore] trait that stores data on disk in the
+//! directory format of op-program.
+
+use super::KeyValueStore;
+use alloy_primitives::{B256, hex};
+use anyhow::{Result, anyhow};
+use std::{fs, path::PathBuf};
+
+/// A simple, synchronous key-value store that stores each preimage in its own file, in the
+/// directory format of op-program's `DirectoryKV`.
+///
+/// The preimage of a key is stored hex-encoded in `<dir>/<key[0..2]>/<key[2..32]>.txt`, where the
+/// key is hex-encoded without a `0x` prefix. Preimage directories written by op-program can be read
+/// by kona and vice versa, so both programs can be run on identical inputs. Unlike the
+/// [DiskKeyValueStore], the directory is kept when the store is dropped.
+///
+/// [DiskKeyValueStore]: super::DiskKeyValueStore
+#[derive(Debug, Clone)]
+pub struct DirectoryKeyValueStore {
+    data_directory: PathBuf,
+}
+
+impl DirectoryKeyValueStore {
+    /// Create a new [DirectoryKeyValueStore] in the given data directory.
+    pub const fn new(data_directory: PathBuf) -> Self {
+        Self { data_directory }
+    }
+
+    /// Returns the path of the file holding the preimage of the given key.
+    fn path(&self, key: B256) -> PathBuf {
+        let key = hex::encode(key);
+        let (dir, name) = key.split_at(4);
+        self.data_directory.join(dir).join(format!("{name}.txt"))
+    }
+}
+
+impl KeyValueStore for DirectoryKeyValueStore {
+    fn get(&self, key: B256) -> Option<Vec<u8>> {
+        let encoded = fs::read_to_string(self.path(key)).ok()?;
+        hex::decode(encoded.trim()).ok()
+    }
+
+    fn set(&mut self, key: B256, value: Vec<u8>) -> Result<()> {
+        let path = self.path(key);
+        let dir = path.parent().ok_or_else(|| anyhow!("Invalid preimage path: {path:?}"))?;
+        fs::create_dir_all(dir)?;
+
+        // Write to a temporary file first, so that a partially written preimage is never read.
+        let tmp = path.with_extension("txt.tmp");
+        fs::write(&tmp, hex::encode(value))?;
+        fs::rename(tmp, path)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::env::temp_dir;
+
+    #[test]
+    fn test_directory_kv_roundtrip() {
+        let dir = temp_dir().join(format!("kona-directory-kv-{}", std::process::id()));
+        let mut kv = DirectoryKeyValueStore::new(dir.clone());
+
+        let key = B256::repeat_byte(0xab);
+        assert!(kv.get(key).is_none());
+        kv.set(key, vec![1, 2, 3]).unwrap();
+        assert_eq!(kv.get(key), Some(vec![1, 2, 3]));
+
+        // The preimage is stored in op-program's format.
+        let path = dir.join("abab").join(format!("{}.txt", "ab".repeat(30)));
+        assert_eq!(fs::read_to_string(path).unwrap(), "010203");
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
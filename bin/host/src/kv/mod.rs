@@ -11,6 +11,12 @@ pub use mem::MemoryKeyValueStore;
 mod disk;
 pub use disk::DiskKeyValueStore;
 
+mod directory;
+pub use directory::DirectoryKeyValueStore;
+
+mod shared;
+pub use shared::SharedDiskKeyValueStore;
+
 mod split;
 pub use split::SplitKeyValueStore;
 
@@ -0,0 +1,140 @@
+//! Contains a concrete implementation of the [KeyValueStore] trait that persists content-addressed
+//! preimages on disk using [rocksdb], allowing them to be shared across host runs.
+
+use super::KeyValueStore;
+use alloy_primitives::{B256, keccak256};
+use anyhow::{Result, anyhow, bail};
+use kona_preimage::PreimageKeyType;
+use rocksdb::{DB, Options};
+use sha2::{Digest, Sha256};
+use std::path::PathBuf;
+
+/// A synchronous, persistent key-value store that stores content-addressed preimages on disk.
+///
+/// Unlike the [DiskKeyValueStore], the [SharedDiskKeyValueStore] is not destroyed when dropped,
+/// so that repeated proof runs for nearby blocks are able to reuse preimages that were already
+/// fetched by prior runs. Only preimages whose keys are valid independently of the run they were
+/// fetched in are persisted; [PreimageKeyType::Local] keys are rejected, and
+/// [PreimageKeyType::Keccak256] and [PreimageKeyType::Sha256] preimages are verified against their
+/// keys before being written.
+///
+/// [DiskKeyValueStore]: super::DiskKeyValueStore
+#[derive(Debug)]
+pub struct SharedDiskKeyValueStore {
+    data_directory: PathBuf,
+    db: DB,
+    compact_on_drop: bool,
+}
+
+impl SharedDiskKeyValueStore {
+    /// Opens the [SharedDiskKeyValueStore] in the given data directory, creating it if it does not
+    /// yet exist.
+    ///
+    /// Returns an error if the database cannot be opened, for example because it is locked by
+    /// another host run.
+    pub fn new(data_directory: PathBuf) -> Result<Self> {
+        let db = DB::open(&Self::get_db_options(), data_directory.as_path())
+            .map_err(|e| anyhow!("Failed to open shared database at {data_directory:?}: {e}"))?;
+
+        Ok(Self { data_directory, db, compact_on_drop: false })
+    }
+
+    /// Compacts the underlying database when the [SharedDiskKeyValueStore] is dropped.
+    pub const fn with_compaction(mut self, compact_on_drop: bool) -> Self {
+        self.compact_on_drop = compact_on_drop;
+        self
+    }
+
+    /// Returns the data directory of the [SharedDiskKeyValueStore].
+    pub const fn data_directory(&self) -> &PathBuf {
+        &self.data_directory
+    }
+
+    /// Compacts the full key range of the underlying database, reclaiming space from overwritten
+    /// entries and merging on-disk files for faster reads in later runs.
+    pub fn compact(&self) {
+        self.db.compact_range(None::<&[u8]>, None::<&[u8]>);
+    }
+
+    /// Gets the [Options] for the underlying RocksDB instance.
+    fn get_db_options() -> Options {
+        let mut options = Options::default();
+        options.set_compression_type(rocksdb::DBCompressionType::Snappy);
+        options.create_if_missing(true);
+        options
+    }
+
+    /// Checks that the preimage is addressed by the given key, and is therefore safe to share
+    /// across runs.
+    fn check_content_addressed(key: B256, value: &[u8]) -> Result<()> {
+        let key_type = PreimageKeyType::try_from(key[0])
+            .map_err(|e| anyhow!("Invalid preimage key type: {e}"))?;
+
+        let digest: B256 = match key_type {
+            PreimageKeyType::Local => bail!("Local preimages cannot be shared across runs"),
+            PreimageKeyType::Keccak256 => keccak256(value),
+            PreimageKeyType::Sha256 => B256::from_slice(Sha256::digest(value).as_slice()),
+            // The remaining key types commit to more than the preimage itself, and are trusted
+            // as-is.
+            _ => return Ok(()),
+        };
+
+        if digest[1..] != key[1..] {
+            bail!("Preimage does not match its content-addressed key {key}");
+        }
+        Ok(())
+    }
+}
+
+impl KeyValueStore for SharedDiskKeyValueStore {
+    fn get(&self, key: B256) -> Option<Vec<u8>> {
+        self.db.get(*key).ok()?
+    }
+
+    fn set(&mut self, key: B256, value: Vec<u8>) -> Result<()> {
+        Self::check_content_addressed(key, &value)?;
+        self.db.put(*key, value).map_err(|e| anyhow!("Failed to set key-value pair: {e}"))
+    }
+}
+
+impl Drop for SharedDiskKeyValueStore {
+    fn drop(&mut self) {
+        if self.compact_on_drop {
+            self.compact();
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::SharedDiskKeyValueStore;
+    use crate::kv::KeyValueStore;
+    use alloy_primitives::keccak256;
+    use kona_preimage::{PreimageKey, PreimageKeyType};
+
+    #[test]
+    fn test_shared_kv_persists_across_runs() {
+        let dir = std::env::temp_dir().join(format!("kona-shared-kv-{}", std::process::id()));
+
+        let value = b"preimage".to_vec();
+        let key = PreimageKey::new(*keccak256(&value), PreimageKeyType::Keccak256);
+
+        {
+            let mut kv = SharedDiskKeyValueStore::new(dir.clone()).unwrap().with_compaction(true);
+            kv.set(key.into(), value.clone()).unwrap();
+
+            // Local and mismatched keccak preimages are rejected.
+            let local = PreimageKey::new_local(1);
+            assert!(kv.set(local.into(), value.clone()).is_err());
+            assert!(kv.set(key.into(), b"other".to_vec()).is_err());
+        }
+
+        let kv = SharedDiskKeyValueStore::new(dir.clone()).unwrap();
+        // The database is locked while open.
+        assert!(SharedDiskKeyValueStore::new(dir.clone()).is_err());
+        assert_eq!(kv.get(key.into()), Some(value));
+        drop(kv);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -6,7 +6,8 @@ pub use server::{PreimageServer, PreimageServerError};
 
 mod kv;
 pub use kv::{
-    DiskKeyValueStore, KeyValueStore, MemoryKeyValueStore, SharedKeyValueStore, SplitKeyValueStore,
+    DirectoryKeyValueStore, DiskKeyValueStore, KeyValueStore, MemoryKeyValueStore,
+    SharedDiskKeyValueStore, SharedKeyValueStore, SplitKeyValueStore,
 };
 
 mod backend;
@@ -14,6 +15,9 @@ pub use backend::{HintHandler, OfflineHostBackend, OnlineHostBackend, OnlineHost
 
 pub mod eth;
 
+mod witness;
+pub use witness::{RecordingBackend, Witness, WitnessEntry, WitnessError, WitnessGenerator};
+
 #[cfg(feature = "single")]
 pub mod single;
 
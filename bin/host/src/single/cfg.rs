@@ -1,21 +1,25 @@
 //! This module contains all CLI-specific code for the single chain entrypoint.
 
-use super::{SingleChainHintHandler, SingleChainLocalInputs};
+use super::{ProofFixture, SingleChainHintHandler, SingleChainLocalInputs};
 use crate::{
-    DiskKeyValueStore, MemoryKeyValueStore, OfflineHostBackend, OnlineHostBackend,
-    OnlineHostBackendCfg, PreimageServer, SharedKeyValueStore, SplitKeyValueStore,
-    eth::http_provider, server::PreimageServerError,
+    DirectoryKeyValueStore, DiskKeyValueStore, MemoryKeyValueStore, OfflineHostBackend,
+    OnlineHostBackend, OnlineHostBackendCfg, PreimageServer, SharedDiskKeyValueStore,
+    SharedKeyValueStore, SplitKeyValueStore,
+    eth::{HostBlobProvider, blob_provider, http_provider},
+    server::PreimageServerError,
 };
 use alloy_primitives::B256;
 use alloy_provider::RootProvider;
 use clap::Parser;
 use kona_cli::cli_styles;
+use kona_client::report::ExitReport;
 use kona_genesis::{L1ChainConfig, RollupConfig};
 use kona_preimage::{
-    BidirectionalChannel, Channel, HintReader, HintWriter, OracleReader, OracleServer,
+    BidirectionalChannel, Channel, ChannelAddr, HintReader, HintWriter, OracleReader, OracleServer,
+    SocketChannelListener,
 };
-use kona_proof::HintType;
-use kona_providers_alloy::{OnlineBeaconClient, OnlineBlobProvider};
+use kona_proof::{HintType, boot::L2OutputClaim};
+use kona_registry::ROLLUP_CONFIGS;
 use kona_std_fpvm::{FileChannel, FileDescriptor};
 use op_alloy_network::Optimism;
 use serde::Serialize;
@@ -24,26 +28,56 @@ use tokio::{
     sync::RwLock,
     task::{self, JoinHandle},
 };
+use tracing::info;
 
 /// The host binary CLI application arguments.
 #[derive(Default, Parser, Serialize, Clone, Debug)]
 #[command(styles = cli_styles())]
 pub struct SingleChainHost {
     /// Hash of the L1 head block. Derivation stops after this block is processed.
-    #[arg(long, env)]
+    #[arg(long, required_unless_present = "fixture_in", default_value_t, env)]
     pub l1_head: B256,
     /// Hash of the agreed upon safe L2 block committed to by `--agreed-l2-output-root`.
-    #[arg(long, visible_alias = "l2-head", env)]
+    #[arg(
+        long,
+        visible_alias = "l2-head",
+        required_unless_present = "fixture_in",
+        default_value_t,
+        env
+    )]
     pub agreed_l2_head_hash: B256,
     /// Agreed safe L2 Output Root to start derivation from.
-    #[arg(long, visible_alias = "l2-output-root", env)]
+    #[arg(
+        long,
+        visible_alias = "l2-output-root",
+        required_unless_present = "fixture_in",
+        default_value_t,
+        env
+    )]
     pub agreed_l2_output_root: B256,
     /// Claimed L2 output root at block # `--claimed-l2-block-number` to validate.
-    #[arg(long, visible_alias = "l2-claim", env)]
+    #[arg(
+        long,
+        visible_alias = "l2-claim",
+        required_unless_present = "fixture_in",
+        default_value_t,
+        env
+    )]
     pub claimed_l2_output_root: B256,
     /// Number of the L2 block that the claimed output root commits to.
-    #[arg(long, visible_alias = "l2-block-number", env)]
+    #[arg(
+        long,
+        visible_alias = "l2-block-number",
+        required_unless_present = "fixture_in",
+        default_value_t,
+        env
+    )]
     pub claimed_l2_block_number: u64,
+    /// Output roots claimed at intermediate L2 blocks between the agreed and claimed L2 blocks,
+    /// formatted as `<block number>:<output root>`. Each intermediate claim is validated as the
+    /// client program derives past its block.
+    #[arg(long, value_delimiter = ',', value_parser = parse_output_claim, env)]
+    pub l2_intermediate_claims: Vec<L2OutputClaim>,
     /// Address of L2 JSON-RPC endpoint to use (eth and debug namespace required).
     #[arg(
         long,
@@ -71,15 +105,61 @@ pub struct SingleChainHost {
         env
     )]
     pub l1_beacon_address: Option<String>,
+    /// Address of a blob archive serving the L1 Beacon API blob sidecar endpoints. Used as a
+    /// fallback for blobs that have expired from the L1 Beacon API endpoint.
+    #[arg(long, visible_alias = "blob-archive", requires = "l1_beacon_address", env)]
+    pub l1_blob_archive_address: Option<String>,
     /// The Data Directory for preimage data storage. Optional if running in online mode,
     /// required if running in offline mode.
     #[arg(
         long,
         visible_alias = "db",
         required_unless_present_all = ["l2_node_address", "l1_node_address", "l1_beacon_address"],
+        required_unless_present_any = ["shared_data_dir", "fixture_out", "fixture_in"],
         env
     )]
     pub data_dir: Option<PathBuf>,
+    /// The data directory of a persistent, content-addressed preimage store shared across runs.
+    /// Preimages fetched by prior runs are served from this store rather than being refetched.
+    #[arg(long, conflicts_with = "data_dir", env)]
+    pub shared_data_dir: Option<PathBuf>,
+    /// Compact the shared preimage store when the host exits.
+    #[arg(long, requires = "shared_data_dir", env)]
+    pub compact_shared_data_dir: bool,
+    /// Run entirely from the preimage store at `--data-dir` or `--shared-data-dir`, refusing all
+    /// RPC access. Fails with a report of the missing preimage key if the store is incomplete.
+    #[arg(long, conflicts_with_all = ["l2_node_address", "l1_node_address", "l1_beacon_address"], env)]
+    pub offline: bool,
+    /// Directory to export a proof fixture to, in the format shared with op-program: the boot
+    /// inputs in `fixture.json`, and all fetched preimages in `preimages/`.
+    #[arg(
+        long,
+        conflicts_with_all = ["data_dir", "shared_data_dir", "offline", "fixture_in"],
+        env
+    )]
+    pub fixture_out: Option<PathBuf>,
+    /// Directory of a proof fixture to run from, exported by `--fixture-out` or op-program. The
+    /// run is offline, and the boot inputs, rollup config and L1 config are loaded from the
+    /// fixture. `--rollup-config-path` and `--l1-config-path` are used if the fixture does not
+    /// hold the corresponding config.
+    #[arg(
+        long,
+        conflicts_with_all = [
+            "data_dir",
+            "shared_data_dir",
+            "l2_node_address",
+            "l1_node_address",
+            "l1_beacon_address",
+            "l1_head",
+            "agreed_l2_head_hash",
+            "agreed_l2_output_root",
+            "claimed_l2_output_root",
+            "claimed_l2_block_number",
+            "l2_chain_id",
+        ],
+        env
+    )]
+    pub fixture_in: Option<PathBuf>,
     /// Run the client program natively.
     #[arg(long, conflicts_with = "server", required_unless_present = "server")]
     pub native: bool,
@@ -87,12 +167,19 @@ pub struct SingleChainHost {
     /// host will run the client program in the host process.
     #[arg(long, conflicts_with = "native", required_unless_present = "native")]
     pub server: bool,
+    /// Address to serve the hint and preimage channels on in pre-image server mode, formatted as
+    /// `tcp://<host>:<port>` or `unix://<path>`. If not provided, the channels are served over the
+    /// file descriptors inherited from the parent process. Allows the client program to run in a
+    /// separate container or on a separate machine from the host. The client program, built with
+    /// the `client-socket` feature, connects to the address set in `KONA_CLIENT_SERVER_ADDR`.
+    #[arg(long, requires = "server", env)]
+    pub server_addr: Option<ChannelAddr>,
     /// The L2 chain ID of a supported chain. If provided, the host will look for the corresponding
     /// rollup config in the superchain registry.
     #[arg(
         long,
         conflicts_with = "rollup_config_path",
-        required_unless_present = "rollup_config_path",
+        required_unless_present_any = ["rollup_config_path", "fixture_in"],
         env
     )]
     pub l2_chain_id: Option<u64>,
@@ -102,7 +189,7 @@ pub struct SingleChainHost {
         long,
         alias = "rollup-cfg",
         conflicts_with = "l2_chain_id",
-        required_unless_present = "l2_chain_id",
+        required_unless_present_any = ["l2_chain_id", "fixture_in"],
         env
     )]
     pub rollup_config_path: Option<PathBuf>,
@@ -137,6 +224,9 @@ pub enum SingleChainHostError {
     /// No l1 config found.
     #[error("No l1 config found")]
     NoL1Config,
+    /// The key-value store could not be opened.
+    #[error("Key-value store error: {0}")]
+    KeyValueStoreError(anyhow::Error),
     /// Any other error.
     #[error("Error: {0}")]
     Other(&'static str),
@@ -145,7 +235,22 @@ pub enum SingleChainHostError {
 impl SingleChainHost {
     /// Starts the [SingleChainHost] application.
     pub async fn start(self) -> Result<(), SingleChainHostError> {
-        if self.server {
+        let cfg = match &self.fixture_in {
+            Some(fixture_in) => ProofFixture::load(fixture_in, self.clone())?,
+            None => self,
+        };
+        cfg.run().await
+    }
+
+    /// Runs the [SingleChainHost] application with its final boot inputs.
+    async fn run(self) -> Result<(), SingleChainHostError> {
+        if let Some(addr) = &self.server_addr {
+            let listener = SocketChannelListener::bind(addr).await?;
+            info!(target: "host", %addr, "Waiting for the client program to connect");
+            let (hint, preimage) = listener.accept().await;
+
+            self.start_server(hint, preimage).await?.await?
+        } else if self.server {
             let hint = FileChannel::new(FileDescriptor::HintRead, FileDescriptor::HintWrite);
             let preimage =
                 FileChannel::new(FileDescriptor::PreimageRead, FileDescriptor::PreimageWrite);
@@ -217,16 +322,21 @@ impl SingleChainHost {
 
         let (_, client_result) = tokio::try_join!(server_task, client_task)?;
 
-        // Bubble up the exit status of the client program if execution completes.
-        std::process::exit(client_result.is_err() as i32)
+        // Report the outcome of the client program, and bubble up its exit status if execution
+        // completes.
+        let report = ExitReport::new(&client_result);
+        eprint!("{}", report.to_line());
+        std::process::exit(report.outcome.exit_code())
     }
 
     /// Returns `true` if the host is running in offline mode.
     pub const fn is_offline(&self) -> bool {
-        self.l1_node_address.is_none() &&
-            self.l2_node_address.is_none() &&
-            self.l1_beacon_address.is_none() &&
-            self.data_dir.is_some()
+        self.offline ||
+            self.fixture_in.is_some() ||
+            (self.l1_node_address.is_none() &&
+                self.l2_node_address.is_none() &&
+                self.l1_beacon_address.is_none() &&
+                (self.data_dir.is_some() || self.shared_data_dir.is_some()))
     }
 
     /// Reads the [RollupConfig] from the file system and returns the deserialized configuration.
@@ -241,6 +351,18 @@ impl SingleChainHost {
         serde_json::from_str(&ser_config).map_err(SingleChainHostError::ParseError)
     }
 
+    /// Returns the [RollupConfig] of the chain, read from the file system if a path is set, or
+    /// looked up in the superchain registry by the L2 chain ID otherwise.
+    pub fn resolve_rollup_config(&self) -> Result<RollupConfig, SingleChainHostError> {
+        if self.rollup_config_path.is_some() {
+            return self.read_rollup_config();
+        }
+
+        self.l2_chain_id
+            .and_then(|chain_id| ROLLUP_CONFIGS.get(&chain_id).cloned())
+            .ok_or(SingleChainHostError::NoRollupConfig)
+    }
+
     /// Reads the [L1ChainConfig] from the file system and returns the deserialized configuration.
     pub fn read_l1_config(&self) -> Result<L1ChainConfig, SingleChainHostError> {
         let path = self.l1_config_path.as_ref().ok_or_else(|| SingleChainHostError::NoL1Config)?;
@@ -256,7 +378,24 @@ impl SingleChainHost {
     pub fn create_key_value_store(&self) -> Result<SharedKeyValueStore, SingleChainHostError> {
         let local_kv_store = SingleChainLocalInputs::new(self.clone());
 
-        let kv_store: SharedKeyValueStore = if let Some(ref data_dir) = self.data_dir {
+        let kv_store: SharedKeyValueStore = if let Some(ref fixture_out) = self.fixture_out {
+            ProofFixture::export(self, fixture_out)?;
+            let fixture_kv_store =
+                DirectoryKeyValueStore::new(ProofFixture::preimages_dir(fixture_out));
+            let split_kv_store = SplitKeyValueStore::new(local_kv_store, fixture_kv_store);
+            Arc::new(RwLock::new(split_kv_store))
+        } else if let Some(ref fixture_in) = self.fixture_in {
+            let fixture_kv_store =
+                DirectoryKeyValueStore::new(ProofFixture::preimages_dir(fixture_in));
+            let split_kv_store = SplitKeyValueStore::new(local_kv_store, fixture_kv_store);
+            Arc::new(RwLock::new(split_kv_store))
+        } else if let Some(ref shared_data_dir) = self.shared_data_dir {
+            let shared_kv_store = SharedDiskKeyValueStore::new(shared_data_dir.clone())
+                .map_err(SingleChainHostError::KeyValueStoreError)?
+                .with_compaction(self.compact_shared_data_dir);
+            let split_kv_store = SplitKeyValueStore::new(local_kv_store, shared_kv_store);
+            Arc::new(RwLock::new(split_kv_store))
+        } else if let Some(ref data_dir) = self.data_dir {
             let disk_kv_store = DiskKeyValueStore::new(data_dir.clone());
             let split_kv_store = SplitKeyValueStore::new(local_kv_store, disk_kv_store);
             Arc::new(RwLock::new(split_kv_store))
@@ -276,11 +415,12 @@ impl SingleChainHost {
                 .as_ref()
                 .ok_or(SingleChainHostError::Other("Provider must be set"))?,
         );
-        let blob_provider = OnlineBlobProvider::init(OnlineBeaconClient::new_http(
+        let blob_provider = blob_provider(
             self.l1_beacon_address
                 .clone()
                 .ok_or(SingleChainHostError::Other("Beacon API URL must be set"))?,
-        ))
+            self.l1_blob_archive_address.clone(),
+        )
         .await;
         let l2_provider = http_provider::<Optimism>(
             self.l2_node_address
@@ -292,6 +432,17 @@ impl SingleChainHost {
     }
 }
 
+/// Parses an [L2OutputClaim] formatted as `<block number>:<output root>`.
+fn parse_output_claim(s: &str) -> Result<L2OutputClaim, String> {
+    let (number, root) = s
+        .split_once(':')
+        .ok_or_else(|| format!("Expected <block number>:<output root>, got {s}"))?;
+    Ok(L2OutputClaim {
+        l2_block_number: number.parse().map_err(|e| format!("Invalid block number: {e}"))?,
+        output_root: root.parse().map_err(|e| format!("Invalid output root: {e}"))?,
+    })
+}
+
 impl OnlineHostBackendCfg for SingleChainHost {
     type HintType = HintType;
     type Providers = SingleChainProviders;
@@ -303,7 +454,7 @@ pub struct SingleChainProviders {
     /// The L1 EL provider.
     pub l1: RootProvider,
     /// The L1 beacon node provider.
-    pub blobs: OnlineBlobProvider<OnlineBeaconClient>,
+    pub blobs: HostBlobProvider,
     /// The L2 EL provider.
     pub l2: RootProvider<Optimism>,
 }
@@ -352,6 +503,23 @@ mod test {
                 .as_slice(),
                 true,
             ),
+            (
+                [
+                    "--l1-node-address",
+                    "dummy",
+                    "--l2-node-address",
+                    "dummy",
+                    "--l1-beacon-address",
+                    "dummy",
+                    "--l1-blob-archive-address",
+                    "dummy",
+                    "--server",
+                    "--l2-chain-id",
+                    "0",
+                ]
+                .as_slice(),
+                true,
+            ),
             (
                 [
                     "--server",
@@ -364,8 +532,105 @@ mod test {
                 .as_slice(),
                 true,
             ),
+            (
+                ["--server", "--l2-chain-id", "0", "--data-dir", "dummy", "--offline"].as_slice(),
+                true,
+            ),
+            (
+                ["--native", "--l2-chain-id", "0", "--shared-data-dir", "dummy", "--offline"]
+                    .as_slice(),
+                true,
+            ),
+            (
+                [
+                    "--server",
+                    "--l2-chain-id",
+                    "0",
+                    "--data-dir",
+                    "dummy",
+                    "--l2-intermediate-claims",
+                    "1:0x0000000000000000000000000000000000000000000000000000000000000000",
+                ]
+                .as_slice(),
+                true,
+            ),
+            (
+                [
+                    "--server",
+                    "--l2-chain-id",
+                    "0",
+                    "--data-dir",
+                    "dummy",
+                    "--server-addr",
+                    "tcp://127.0.0.1:7300",
+                ]
+                .as_slice(),
+                true,
+            ),
+            (
+                [
+                    "--l1-node-address",
+                    "dummy",
+                    "--l2-node-address",
+                    "dummy",
+                    "--l1-beacon-address",
+                    "dummy",
+                    "--server",
+                    "--l2-chain-id",
+                    "0",
+                    "--fixture-out",
+                    "dummy",
+                ]
+                .as_slice(),
+                true,
+            ),
             // invalid
+            (["--native", "--fixture-in", "dummy"].as_slice(), false),
             (["--server", "--native", "--l2-chain-id", "0"].as_slice(), false),
+            (
+                ["--native", "--l2-chain-id", "0", "--fixture-in", "dummy", "--data-dir", "dummy"]
+                    .as_slice(),
+                false,
+            ),
+            (
+                [
+                    "--native",
+                    "--l2-chain-id",
+                    "0",
+                    "--fixture-in",
+                    "dummy",
+                    "--fixture-out",
+                    "dummy",
+                ]
+                .as_slice(),
+                false,
+            ),
+            (
+                [
+                    "--native",
+                    "--l2-chain-id",
+                    "0",
+                    "--data-dir",
+                    "dummy",
+                    "--server-addr",
+                    "tcp://127.0.0.1:7300",
+                ]
+                .as_slice(),
+                false,
+            ),
+            (
+                [
+                    "--server",
+                    "--l2-chain-id",
+                    "0",
+                    "--data-dir",
+                    "dummy",
+                    "--server-addr",
+                    "127.0.0.1:7300",
+                ]
+                .as_slice(),
+                false,
+            ),
             (["--l2-chain-id", "0", "--rollup-config-path", "dummy", "--server"].as_slice(), false),
             (["--server"].as_slice(), false),
             (["--native"].as_slice(), false),
@@ -374,6 +639,49 @@ mod test {
             (["--l1-node-address", "dummy", "--server", "--l2-chain-id", "0"].as_slice(), false),
             (["--l2-node-address", "dummy", "--server", "--l2-chain-id", "0"].as_slice(), false),
             (["--l1-beacon-address", "dummy", "--server", "--l2-chain-id", "0"].as_slice(), false),
+            (
+                [
+                    "--server",
+                    "--l2-chain-id",
+                    "0",
+                    "--data-dir",
+                    "dummy",
+                    "--blob-archive",
+                    "dummy",
+                ]
+                .as_slice(),
+                false,
+            ),
+            (["--server", "--l2-chain-id", "0", "--offline"].as_slice(), false),
+            (
+                [
+                    "--server",
+                    "--l2-chain-id",
+                    "0",
+                    "--data-dir",
+                    "dummy",
+                    "--l2-intermediate-claims",
+                    "0x0000000000000000000000000000000000000000000000000000000000000000",
+                ]
+                .as_slice(),
+                false,
+            ),
+            (
+                [
+                    "--l1-node-address",
+                    "dummy",
+                    "--l2-node-address",
+                    "dummy",
+                    "--l1-beacon-address",
+                    "dummy",
+                    "--server",
+                    "--l2-chain-id",
+                    "0",
+                    "--offline",
+                ]
+                .as_slice(),
+                false,
+            ),
             ([].as_slice(), false),
         ];
 
@@ -384,4 +692,39 @@ mod test {
             assert_eq!(parsed.is_ok(), valid);
         }
     }
+
+    #[test]
+    fn test_fixture_in_flags() {
+        let cases = [
+            // valid
+            (["--native", "--fixture-in", "dummy"].as_slice(), true),
+            (
+                ["--server", "--fixture-in", "dummy", "--rollup-config-path", "dummy"].as_slice(),
+                true,
+            ),
+            (["--native", "--fixture-in", "dummy", "--l1-config-path", "dummy"].as_slice(), true),
+            // invalid
+            (["--native", "--fixture-in", "dummy", "--l2-chain-id", "10"].as_slice(), false),
+            (["--native", "--fixture-in", "dummy", "--l2-block-number", "1"].as_slice(), false),
+            (["--native", "--l2-chain-id", "10"].as_slice(), false),
+        ];
+
+        for (args_ext, valid) in cases.into_iter() {
+            let args = ["single"].iter().chain(args_ext.iter()).cloned().collect::<Vec<_>>();
+
+            let parsed = SingleChainHost::try_parse_from(args);
+            assert_eq!(parsed.is_ok(), valid);
+        }
+    }
+
+    #[test]
+    fn test_resolve_rollup_config() {
+        let mut host =
+            SingleChainHost::try_parse_from(["single", "--native", "--fixture-in", "dummy"])
+                .unwrap();
+        assert!(host.resolve_rollup_config().is_err());
+
+        host.l2_chain_id = Some(10);
+        assert_eq!(host.resolve_rollup_config().unwrap().l2_chain_id.id(), 10);
+    }
 }
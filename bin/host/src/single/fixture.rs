@@ -0,0 +1,204 @@
+//! Contains the [ProofFixture] format, shared with op-program, to run kona and op-program on
+//! identical inputs.
+
+use super::{SingleChainHost, SingleChainHostError};
+use alloy_primitives::B256;
+use serde::{Deserialize, Serialize};
+use std::{
+    fs::{self, File},
+    path::{Path, PathBuf},
+};
+
+/// The name of the file holding the [ProofFixture] inputs in a fixture directory.
+pub const FIXTURE_INPUTS_FILE: &str = "fixture.json";
+
+/// The name of the preimage directory in a fixture directory, in the format of the
+/// [DirectoryKeyValueStore].
+///
+/// [DirectoryKeyValueStore]: crate::DirectoryKeyValueStore
+pub const FIXTURE_PREIMAGES_DIR: &str = "preimages";
+
+/// The name of the file holding the rollup config in a fixture directory, if the fixture does not
+/// run on a chain of the superchain registry.
+pub const FIXTURE_ROLLUP_CONFIG_FILE: &str = "rollup.json";
+
+/// The name of the file holding the L1 chain config in a fixture directory, if the fixture does
+/// not run on a known L1 chain.
+pub const FIXTURE_L1_CONFIG_FILE: &str = "l1-chain-config.json";
+
+/// The boot inputs of a proof fixture.
+///
+/// A fixture directory holds the inputs in [FIXTURE_INPUTS_FILE], the rollup and L1 configs of
+/// the run, if any, in [FIXTURE_ROLLUP_CONFIG_FILE] and [FIXTURE_L1_CONFIG_FILE], and all
+/// preimages fetched by the run in [FIXTURE_PREIMAGES_DIR]. The inputs are named after the
+/// op-program flags, so that op-program can be run on the fixture with `--datadir
+/// <dir>/preimages`, the flags from the inputs and the configs. Local preimages are not stored, as
+/// they are derived from the inputs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct ProofFixture {
+    /// Hash of the L1 head block.
+    pub l1_head: B256,
+    /// Hash of the agreed upon safe L2 block.
+    pub l2_head: B256,
+    /// Agreed safe L2 output root.
+    pub l2_output_root: B256,
+    /// Claimed L2 output root.
+    pub l2_claim: B256,
+    /// Number of the L2 block that the claimed output root commits to.
+    pub l2_block_number: u64,
+    /// The L2 chain ID, or `0` if a custom rollup config is used.
+    pub l2_chain_id: u64,
+}
+
+impl From<&SingleChainHost> for ProofFixture {
+    fn from(cfg: &SingleChainHost) -> Self {
+        Self {
+            l1_head: cfg.l1_head,
+            l2_head: cfg.agreed_l2_head_hash,
+            l2_output_root: cfg.agreed_l2_output_root,
+            l2_claim: cfg.claimed_l2_output_root,
+            l2_block_number: cfg.claimed_l2_block_number,
+            l2_chain_id: cfg.l2_chain_id.unwrap_or_default(),
+        }
+    }
+}
+
+impl ProofFixture {
+    /// Writes the fixture inputs to the given fixture directory, creating it if needed.
+    pub fn write(&self, dir: &Path) -> Result<(), SingleChainHostError> {
+        fs::create_dir_all(dir)?;
+        serde_json::to_writer_pretty(File::create(dir.join(FIXTURE_INPUTS_FILE))?, self)?;
+        Ok(())
+    }
+
+    /// Reads the fixture inputs from the given fixture directory.
+    pub fn read(dir: &Path) -> Result<Self, SingleChainHostError> {
+        Ok(serde_json::from_reader(File::open(dir.join(FIXTURE_INPUTS_FILE))?)?)
+    }
+
+    /// Returns the preimage directory of the given fixture directory.
+    pub fn preimages_dir(dir: &Path) -> PathBuf {
+        dir.join(FIXTURE_PREIMAGES_DIR)
+    }
+
+    /// Exports the boot inputs of the given host to the given fixture directory, along with its
+    /// rollup and L1 configs, if any.
+    pub fn export(cfg: &SingleChainHost, dir: &Path) -> Result<(), SingleChainHostError> {
+        Self::from(cfg).write(dir)?;
+        if let Some(path) = &cfg.rollup_config_path {
+            fs::copy(path, dir.join(FIXTURE_ROLLUP_CONFIG_FILE))?;
+        }
+        if let Some(path) = &cfg.l1_config_path {
+            fs::copy(path, dir.join(FIXTURE_L1_CONFIG_FILE))?;
+        }
+        Ok(())
+    }
+
+    /// Loads the boot inputs of the fixture in the given directory into the given host, along
+    /// with the rollup and L1 configs of the fixture. The configs of the host are kept if the
+    /// fixture does not hold them.
+    pub fn load(dir: &Path, cfg: SingleChainHost) -> Result<SingleChainHost, SingleChainHostError> {
+        let fixture = Self::read(dir)?;
+        let rollup_config_path = Some(dir.join(FIXTURE_ROLLUP_CONFIG_FILE))
+            .filter(|path| path.exists())
+            .or(cfg.rollup_config_path);
+        let l1_config_path = Some(dir.join(FIXTURE_L1_CONFIG_FILE))
+            .filter(|path| path.exists())
+            .or(cfg.l1_config_path);
+        let l2_chain_id = Some(fixture.l2_chain_id).filter(|id| *id != 0);
+        if l2_chain_id.is_none() && rollup_config_path.is_none() {
+            return Err(SingleChainHostError::NoRollupConfig);
+        }
+
+        Ok(SingleChainHost {
+            l1_head: fixture.l1_head,
+            agreed_l2_head_hash: fixture.l2_head,
+            agreed_l2_output_root: fixture.l2_output_root,
+            claimed_l2_output_root: fixture.l2_claim,
+            claimed_l2_block_number: fixture.l2_block_number,
+            l2_chain_id,
+            rollup_config_path,
+            l1_config_path,
+            ..cfg
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::env::temp_dir;
+
+    #[test]
+    fn test_fixture_load() {
+        let dir = temp_dir().join(format!("kona-fixture-load-{}", std::process::id()));
+        let config_dir = dir.join("configs");
+        fs::create_dir_all(&config_dir).unwrap();
+        let rollup_config_path = config_dir.join("rollup.json");
+        fs::write(&rollup_config_path, b"{}").unwrap();
+
+        let host = SingleChainHost {
+            l1_head: B256::repeat_byte(1),
+            agreed_l2_head_hash: B256::repeat_byte(2),
+            agreed_l2_output_root: B256::repeat_byte(3),
+            claimed_l2_output_root: B256::repeat_byte(4),
+            claimed_l2_block_number: 5,
+            rollup_config_path: Some(rollup_config_path),
+            ..Default::default()
+        };
+        let fixture_dir = dir.join("fixture");
+        ProofFixture::export(&host, &fixture_dir).unwrap();
+        assert!(fixture_dir.join(FIXTURE_ROLLUP_CONFIG_FILE).exists());
+        assert!(!fixture_dir.join(FIXTURE_L1_CONFIG_FILE).exists());
+
+        let loaded = ProofFixture::load(
+            &fixture_dir,
+            SingleChainHost { fixture_in: Some(fixture_dir.clone()), ..Default::default() },
+        )
+        .unwrap();
+        assert_eq!(ProofFixture::from(&loaded), ProofFixture::from(&host));
+        assert_eq!(loaded.l2_chain_id, None);
+        assert_eq!(loaded.rollup_config_path, Some(fixture_dir.join(FIXTURE_ROLLUP_CONFIG_FILE)));
+        assert_eq!(loaded.l1_config_path, None);
+        assert_eq!(loaded.fixture_in, Some(fixture_dir.clone()));
+
+        // A fixture of a custom chain without its rollup config cannot be loaded.
+        fs::remove_file(fixture_dir.join(FIXTURE_ROLLUP_CONFIG_FILE)).unwrap();
+        assert!(matches!(
+            ProofFixture::load(&fixture_dir, SingleChainHost::default()),
+            Err(SingleChainHostError::NoRollupConfig)
+        ));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_fixture_roundtrip() {
+        let host = SingleChainHost {
+            l1_head: B256::repeat_byte(1),
+            agreed_l2_head_hash: B256::repeat_byte(2),
+            agreed_l2_output_root: B256::repeat_byte(3),
+            claimed_l2_output_root: B256::repeat_byte(4),
+            claimed_l2_block_number: 5,
+            l2_chain_id: Some(10),
+            ..Default::default()
+        };
+        let fixture = ProofFixture::from(&host);
+
+        let dir = temp_dir().join(format!("kona-fixture-{}", std::process::id()));
+        fixture.write(&dir).unwrap();
+        let json = fs::read_to_string(dir.join(FIXTURE_INPUTS_FILE)).unwrap();
+        assert!(json.contains("\"l2-output-root\""));
+        assert!(json.contains("\"l2-chain-id\": 10"));
+
+        let read = ProofFixture::read(&dir).unwrap();
+        assert_eq!(read, fixture);
+
+        let loaded = ProofFixture::load(&dir, SingleChainHost::default()).unwrap();
+        assert_eq!(ProofFixture::from(&loaded), fixture);
+        assert_eq!(loaded.l2_chain_id, Some(10));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
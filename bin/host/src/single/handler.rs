@@ -14,10 +14,9 @@ use alloy_provider::Provider;
 use alloy_rlp::Decodable;
 use alloy_rpc_types::{Block, debug::ExecutionWitness};
 use anyhow::{Result, anyhow, ensure};
-use ark_ff::{BigInteger, PrimeField};
 use async_trait::async_trait;
 use kona_preimage::{PreimageKey, PreimageKeyType};
-use kona_proof::{Hint, HintType, l1::ROOTS_OF_UNITY};
+use kona_proof::{Hint, HintType, l1::root_of_unity_be};
 use kona_protocol::{BlockInfo, OutputRoot, Predeploys};
 use op_alloy_rpc_types_engine::OpPayloadAttributes;
 use tracing::warn;
@@ -118,9 +117,7 @@ impl HintHandler for SingleChainHintHandler {
                 let mut blob_key = [0u8; 80];
                 blob_key[..48].copy_from_slice(commitment.as_ref());
                 for i in 0..FIELD_ELEMENTS_PER_BLOB {
-                    blob_key[48..].copy_from_slice(
-                        ROOTS_OF_UNITY[i as usize].into_bigint().to_bytes_be().as_ref(),
-                    );
+                    blob_key[48..].copy_from_slice(root_of_unity_be(i as usize));
                     let blob_key_hash = keccak256(blob_key.as_ref());
 
                     kv_lock
@@ -209,9 +206,10 @@ impl HintHandler for SingleChainHintHandler {
                 let header = Header::decode(&mut raw_header.as_ref())?;
 
                 // Fetch the storage root for the L2 head block.
+                let message_passer = Predeploys::message_passer(&cfg.resolve_rollup_config()?);
                 let l2_to_l1_message_passer = providers
                     .l2
-                    .get_proof(Predeploys::L2_TO_L1_MESSAGE_PASSER, Default::default())
+                    .get_proof(message_passer, Default::default())
                     .block_id(cfg.agreed_l2_head_hash.into())
                     .await?;
 
@@ -7,8 +7,9 @@ use alloy_primitives::B256;
 use anyhow::Result;
 use kona_preimage::PreimageKey;
 use kona_proof::boot::{
-    L1_CONFIG_KEY, L1_HEAD_KEY, L2_CHAIN_ID_KEY, L2_CLAIM_BLOCK_NUMBER_KEY, L2_CLAIM_KEY,
-    L2_OUTPUT_ROOT_KEY, L2_ROLLUP_CONFIG_KEY,
+    INTERMEDIATE_CLAIMS_FLAG, L1_CONFIG_KEY, L1_HEAD_KEY, L2_CHAIN_ID_KEY,
+    L2_CLAIM_BLOCK_NUMBER_KEY, L2_CLAIM_KEY, L2_INTERMEDIATE_CLAIMS_KEY, L2_OUTPUT_ROOT_KEY,
+    L2_ROLLUP_CONFIG_KEY, L2OutputClaim, PREIMAGE_BATCHING_FLAG,
 };
 
 /// A simple, synchronous key-value store that returns data from a [SingleChainHost] config.
@@ -32,7 +33,25 @@ impl KeyValueStore for SingleChainLocalInputs {
             L2_OUTPUT_ROOT_KEY => Some(self.cfg.agreed_l2_output_root.to_vec()),
             L2_CLAIM_KEY => Some(self.cfg.claimed_l2_output_root.to_vec()),
             L2_CLAIM_BLOCK_NUMBER_KEY => {
-                Some(self.cfg.claimed_l2_block_number.to_be_bytes().to_vec())
+                // Only advertise the intermediate claims if there are any, and batched preimage
+                // requests if the client runs natively, so that the preimage matches the one
+                // served by the on-chain oracle otherwise.
+                let mut flags = 0;
+                if !self.cfg.l2_intermediate_claims.is_empty() {
+                    flags |= INTERMEDIATE_CLAIMS_FLAG;
+                }
+                if self.cfg.native {
+                    flags |= PREIMAGE_BATCHING_FLAG;
+                }
+
+                let mut preimage = self.cfg.claimed_l2_block_number.to_be_bytes().to_vec();
+                if flags != 0 {
+                    preimage.push(flags);
+                }
+                Some(preimage)
+            }
+            L2_INTERMEDIATE_CLAIMS_KEY => {
+                Some(L2OutputClaim::encode_list(&self.cfg.l2_intermediate_claims))
             }
             L2_CHAIN_ID_KEY => {
                 Some(self.cfg.l2_chain_id.unwrap_or_default().to_be_bytes().to_vec())
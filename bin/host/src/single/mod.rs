@@ -3,6 +3,12 @@
 mod cfg;
 pub use cfg::{SingleChainHost, SingleChainHostError, SingleChainProviders};
 
+mod fixture;
+pub use fixture::{
+    FIXTURE_INPUTS_FILE, FIXTURE_L1_CONFIG_FILE, FIXTURE_PREIMAGES_DIR, FIXTURE_ROLLUP_CONFIG_FILE,
+    ProofFixture,
+};
+
 mod local_kv;
 pub use local_kv::SingleChainLocalInputs;
 
@@ -0,0 +1,264 @@
+//! Contains the [WitnessGenerator], which runs a client program natively while recording every
+//! preimage served to it, producing a self-contained [Witness].
+
+use crate::{MemoryKeyValueStore, PreimageServer, PreimageServerError};
+use alloy_primitives::B256;
+use async_trait::async_trait;
+use kona_preimage::{
+    BidirectionalChannel, HintReader, HintRouter, HintWriter, NativeChannel, OracleReader,
+    OracleServer, PreimageFetcher, PreimageKey, PreimageServerBackend,
+    errors::PreimageOracleResult,
+};
+use std::{collections::HashSet, future::Future, sync::Arc};
+use tokio::{sync::Mutex, task};
+
+/// An error that can occur while generating or decoding a [Witness].
+#[derive(Debug, thiserror::Error)]
+pub enum WitnessError {
+    /// The preimage server failed.
+    #[error(transparent)]
+    Server(#[from] PreimageServerError),
+    /// The client program failed.
+    #[error("Client program failed: {0}")]
+    Client(String),
+    /// A task failed to execute to completion.
+    #[error("Join error: {0}")]
+    Join(#[from] task::JoinError),
+    /// An I/O error occurred while setting up the native channels.
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+    /// The encoded witness is malformed.
+    #[error("Malformed witness: {0}")]
+    Malformed(&'static str),
+}
+
+/// A single preimage served to the client program.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WitnessEntry {
+    /// The [PreimageKey] of the preimage, in its 32-byte form.
+    pub key: B256,
+    /// The preimage.
+    pub value: Vec<u8>,
+}
+
+/// A self-contained witness of every preimage served to a client program, in the order in which
+/// the preimages were first requested.
+///
+/// A [Witness] can be consumed by zkVM provers that are unable to run the interactive preimage
+/// server, or loaded into a [MemoryKeyValueStore] to replay the client program offline.
+///
+/// **Encoding:**
+/// ```text
+/// witness = entry_count (u64 BE) .. entry_0 .. entry_1 .. ..
+/// entry   = key (32 bytes) .. value_length (u64 BE) .. value
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Witness {
+    /// The preimages served to the client program, in order of first request.
+    pub entries: Vec<WitnessEntry>,
+}
+
+impl Witness {
+    /// Returns the number of preimages in the witness.
+    pub const fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if the witness contains no preimages.
+    pub const fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Encodes the witness into its binary form.
+    pub fn encode(&self) -> Vec<u8> {
+        let size = 8 + self.entries.iter().map(|e| 40 + e.value.len()).sum::<usize>();
+        let mut buf = Vec::with_capacity(size);
+        buf.extend_from_slice(&(self.entries.len() as u64).to_be_bytes());
+        for entry in &self.entries {
+            buf.extend_from_slice(entry.key.as_slice());
+            buf.extend_from_slice(&(entry.value.len() as u64).to_be_bytes());
+            buf.extend_from_slice(&entry.value);
+        }
+        buf
+    }
+
+    /// Decodes a witness from its binary form.
+    pub fn decode(mut buf: &[u8]) -> Result<Self, WitnessError> {
+        let count = read_u64(&mut buf)?;
+        let mut entries = Vec::new();
+        for _ in 0..count {
+            let key = B256::from_slice(take(&mut buf, 32)?);
+            let len = read_u64(&mut buf)? as usize;
+            let value = take(&mut buf, len)?.to_vec();
+            entries.push(WitnessEntry { key, value });
+        }
+        if !buf.is_empty() {
+            return Err(WitnessError::Malformed("trailing bytes"));
+        }
+        Ok(Self { entries })
+    }
+
+    /// Loads the witness into a [MemoryKeyValueStore], for use with the [OfflineHostBackend].
+    ///
+    /// [OfflineHostBackend]: crate::OfflineHostBackend
+    pub fn into_kv_store(self) -> MemoryKeyValueStore {
+        MemoryKeyValueStore { store: self.entries.into_iter().map(|e| (e.key, e.value)).collect() }
+    }
+}
+
+/// Takes `len` bytes from the front of `buf`.
+fn take<'a>(buf: &mut &'a [u8], len: usize) -> Result<&'a [u8], WitnessError> {
+    if buf.len() < len {
+        return Err(WitnessError::Malformed("unexpected end of witness"));
+    }
+    let (head, tail) = buf.split_at(len);
+    *buf = tail;
+    Ok(head)
+}
+
+/// Reads a big-endian [u64] from the front of `buf`.
+fn read_u64(buf: &mut &[u8]) -> Result<u64, WitnessError> {
+    let bytes = take(buf, 8)?;
+    Ok(u64::from_be_bytes(bytes.try_into().expect("slice has length 8")))
+}
+
+/// A [PreimageServerBackend] that records every preimage served by the inner backend.
+#[derive(Debug)]
+pub struct RecordingBackend<B> {
+    /// The inner backend.
+    inner: Arc<B>,
+    /// The recorded witness, alongside the set of keys already recorded.
+    recorded: Mutex<(Witness, HashSet<B256>)>,
+}
+
+impl<B> RecordingBackend<B> {
+    /// Creates a new [RecordingBackend] wrapping the given backend.
+    pub fn new(inner: Arc<B>) -> Self {
+        Self { inner, recorded: Mutex::new(Default::default()) }
+    }
+
+    /// Returns a copy of the [Witness] recorded so far.
+    pub async fn witness(&self) -> Witness {
+        self.recorded.lock().await.0.clone()
+    }
+}
+
+#[async_trait]
+impl<B> PreimageFetcher for RecordingBackend<B>
+where
+    B: PreimageFetcher + Send + Sync,
+{
+    async fn get_preimage(&self, key: PreimageKey) -> PreimageOracleResult<Vec<u8>> {
+        let value = self.inner.get_preimage(key).await?;
+
+        let key: B256 = key.into();
+        let mut recorded = self.recorded.lock().await;
+        if recorded.1.insert(key) {
+            recorded.0.entries.push(WitnessEntry { key, value: value.clone() });
+        }
+
+        Ok(value)
+    }
+}
+
+#[async_trait]
+impl<B> HintRouter for RecordingBackend<B>
+where
+    B: HintRouter + Send + Sync,
+{
+    async fn route_hint(&self, hint: String) -> PreimageOracleResult<()> {
+        self.inner.route_hint(hint).await
+    }
+}
+
+/// Generates a [Witness] by running a client program natively against a preimage server backend,
+/// recording every preimage served to the client.
+#[derive(Debug)]
+pub struct WitnessGenerator<B> {
+    /// The backend serving preimages to the client program.
+    backend: Arc<B>,
+}
+
+impl<B> WitnessGenerator<B>
+where
+    B: PreimageServerBackend + Send + Sync + 'static,
+{
+    /// Creates a new [WitnessGenerator] with the given backend.
+    pub const fn new(backend: Arc<B>) -> Self {
+        Self { backend }
+    }
+
+    /// Runs the client program natively, returning the [Witness] of all preimages it requested.
+    ///
+    /// The client program is passed the [OracleReader] and [HintWriter] it should communicate
+    /// with the host over.
+    pub async fn generate<F, Fut, E>(self, client: F) -> Result<Witness, WitnessError>
+    where
+        F: FnOnce(OracleReader<NativeChannel>, HintWriter<NativeChannel>) -> Fut,
+        Fut: Future<Output = Result<(), E>> + Send + 'static,
+        E: std::fmt::Display,
+    {
+        let hint = BidirectionalChannel::new()?;
+        let preimage = BidirectionalChannel::new()?;
+        let backend = Arc::new(RecordingBackend::new(self.backend));
+
+        let server = PreimageServer::new(
+            OracleServer::new(preimage.host),
+            HintReader::new(hint.host),
+            backend.clone(),
+        );
+        let server_task = task::spawn(server.start());
+        let client_task =
+            task::spawn(client(OracleReader::new(preimage.client), HintWriter::new(hint.client)));
+
+        let (server_result, client_result) = tokio::try_join!(server_task, client_task)?;
+        server_result?;
+        client_result.map_err(|e| WitnessError::Client(e.to_string()))?;
+
+        Ok(backend.witness().await)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{KeyValueStore, OfflineHostBackend};
+    use kona_preimage::{PreimageKeyType, PreimageOracleClient};
+    use tokio::sync::RwLock;
+
+    #[test]
+    fn test_witness_roundtrip() {
+        let witness = Witness {
+            entries: vec![
+                WitnessEntry { key: B256::repeat_byte(1), value: vec![1, 2, 3] },
+                WitnessEntry { key: B256::repeat_byte(2), value: vec![] },
+            ],
+        };
+        let encoded = witness.encode();
+        assert_eq!(Witness::decode(&encoded).unwrap(), witness);
+        assert!(Witness::decode(&encoded[..encoded.len() - 1]).is_err());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_witness_generator_records_preimages() {
+        let key = PreimageKey::new(*B256::repeat_byte(0xaa), PreimageKeyType::Keccak256);
+        let mut kv = MemoryKeyValueStore::new();
+        kv.store.insert(key.into(), vec![0xbe, 0xef]);
+        let backend = Arc::new(OfflineHostBackend::new(Arc::new(RwLock::new(kv))));
+
+        let witness = WitnessGenerator::new(backend)
+            .generate(move |oracle, _hints| async move {
+                // Request the same preimage twice; it must only be recorded once.
+                oracle.get(key).await?;
+                oracle.get(key).await.map(|_| ())
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(
+            witness.entries,
+            vec![WitnessEntry { key: key.into(), value: vec![0xbe, 0xef] }]
+        );
+        assert_eq!(witness.into_kv_store().get(key.into()), Some(vec![0xbe, 0xef]));
+    }
+}
@@ -1,7 +1,10 @@
 //! Contains the node CLI.
 
 use crate::{
-    commands::{BootstoreCommand, InfoCommand, NetCommand, NodeCommand, RegistryCommand},
+    commands::{
+        BootstoreCommand, DeriveCommand, GenesisCommand, InfoCommand, NetCommand, NodeCommand,
+        RegistryCommand,
+    },
     flags::{GlobalArgs, init_unified_metrics},
     version,
 };
@@ -27,6 +30,12 @@ pub enum Commands {
     Bootstore(BootstoreCommand),
     /// Get info about op chain.
     Info(InfoCommand),
+    /// Derives a range of L2 blocks from L1 data without an execution engine.
+    #[command(alias = "d")]
+    Derive(DeriveCommand),
+    /// Generates the L2 genesis and rollup configuration of a new chain.
+    #[command(alias = "g")]
+    Genesis(GenesisCommand),
 }
 
 /// The node CLI.
@@ -61,6 +70,8 @@ impl Cli {
             Commands::Registry(ref registry) => registry.init_logs(&self.global)?,
             Commands::Bootstore(ref bootstore) => bootstore.init_logs(&self.global)?,
             Commands::Info(ref info) => info.init_logs(&self.global)?,
+            Commands::Derive(ref derive) => derive.init_logs(&self.global)?,
+            Commands::Genesis(ref genesis) => genesis.init_logs(&self.global)?,
         }
 
         // Allow subcommands to initialize cli metrics.
@@ -73,11 +84,13 @@ impl Cli {
 
         // Run the subcommand.
         match self.subcommand {
-            Commands::Node(node) => Self::run_until_ctrl_c(node.run(&self.global)),
+            Commands::Node(node) => Self::run_until_complete(node.run(&self.global)),
             Commands::Net(net) => Self::run_until_ctrl_c(net.run(&self.global)),
             Commands::Registry(registry) => registry.run(&self.global),
             Commands::Bootstore(bootstore) => bootstore.run(&self.global),
-            Commands::Info(info) => info.run(&self.global),
+            Commands::Info(info) => Self::run_until_ctrl_c(info.run(&self.global)),
+            Commands::Derive(derive) => Self::run_until_ctrl_c(derive.run(&self.global)),
+            Commands::Genesis(genesis) => Self::run_until_ctrl_c(genesis.run(&self.global)),
         }
     }
 
@@ -98,6 +111,16 @@ impl Cli {
         })
     }
 
+    /// Run until the future completes. Used by subcommands that handle the shutdown signals
+    /// themselves, to shut down gracefully.
+    pub fn run_until_complete<F>(fut: F) -> Result<()>
+    where
+        F: std::future::Future<Output = Result<()>>,
+    {
+        let rt = Self::tokio_runtime().map_err(|e| anyhow::anyhow!(e))?;
+        rt.block_on(fut)
+    }
+
     /// Creates a new default tokio multi-thread [Runtime](tokio::runtime::Runtime) with all
     /// features enabled
     pub fn tokio_runtime() -> Result<tokio::runtime::Runtime, std::io::Error> {
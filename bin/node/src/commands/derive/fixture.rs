@@ -0,0 +1,460 @@
+//! Contains the [DerivationFixture] recorded and replayed by the `derive` subcommand, and the
+//! providers serving the derivation pipeline from it.
+
+use alloy_consensus::{Header, Receipt, TxEnvelope};
+use alloy_eips::eip4844::{Blob, IndexedBlobHash};
+use alloy_primitives::B256;
+use alloy_provider::{Provider, RootProvider};
+use alloy_rpc_types_eth::Block;
+use async_trait::async_trait;
+use kona_derive::{BlobProvider, ChainProvider, L2ChainProvider, PipelineError, PipelineErrorKind};
+use kona_genesis::{RollupConfig, SystemConfig};
+use kona_protocol::{BatchValidationProvider, BlockInfo, L2BlockInfo, to_system_config};
+use kona_providers_alloy::{AlloyChainProvider, OnlineBeaconClient, OnlineBlobProvider};
+use op_alloy_consensus::OpBlock;
+use op_alloy_network::Optimism;
+use op_alloy_rpc_types::Transaction;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    fs::{self, File},
+    io::{BufReader, BufWriter},
+    path::Path,
+    sync::{Arc, Mutex, MutexGuard, PoisonError},
+};
+
+/// The name of the file holding the [DerivationFixture] in a fixture directory.
+pub(crate) const FIXTURE_FILE: &str = "derivation.json";
+
+/// The L1 and L2 data used to derive a range of L2 blocks.
+///
+/// The `derive` subcommand serves the data held by the fixture without fetching it, and records
+/// the data it fetches from the RPC endpoints into the fixture. A fixture written by a run can
+/// therefore derive the same range again without any endpoint. Blobs are checked against their
+/// versioned hashes when they are fetched, and are served as-is from the fixture.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DerivationFixture {
+    /// The number of the L1 head block when the fixture was recorded.
+    pub(crate) l1_head: u64,
+    /// The hashes of the L1 blocks, keyed by block number.
+    pub(crate) l1_hashes: BTreeMap<u64, B256>,
+    /// The L1 blocks, keyed by block hash.
+    pub(crate) l1_blocks: BTreeMap<B256, BlockInfo>,
+    /// The L1 headers, keyed by block hash.
+    pub(crate) l1_headers: BTreeMap<B256, Header>,
+    /// The L1 receipts, keyed by block hash.
+    pub(crate) l1_receipts: BTreeMap<B256, Vec<Receipt>>,
+    /// The L1 transactions, keyed by block hash.
+    pub(crate) l1_transactions: BTreeMap<B256, Vec<TxEnvelope>>,
+    /// The blobs, keyed by versioned hash.
+    pub(crate) blobs: BTreeMap<B256, Box<Blob>>,
+    /// The canonical L2 blocks, keyed by block number.
+    pub(crate) l2_blocks: BTreeMap<u64, Block<Transaction>>,
+}
+
+impl DerivationFixture {
+    /// Reads the fixture from the given fixture directory, or returns an empty fixture if the
+    /// directory does not hold one yet.
+    pub(crate) fn read(dir: &Path) -> anyhow::Result<Self> {
+        let path = dir.join(FIXTURE_FILE);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        Ok(serde_json::from_reader(BufReader::new(File::open(path)?))?)
+    }
+
+    /// Writes the fixture to the given fixture directory, creating it if needed.
+    pub(crate) fn write(&self, dir: &Path) -> anyhow::Result<()> {
+        fs::create_dir_all(dir)?;
+        serde_json::to_writer(BufWriter::new(File::create(dir.join(FIXTURE_FILE))?), self)?;
+        Ok(())
+    }
+}
+
+/// A [DerivationFixture] shared between the providers of the pipeline.
+#[derive(Debug, Default, Clone)]
+pub(crate) struct SharedFixture(Arc<Mutex<DerivationFixture>>);
+
+impl SharedFixture {
+    /// Creates a new [SharedFixture] holding the given fixture.
+    pub(crate) fn new(fixture: DerivationFixture) -> Self {
+        Self(Arc::new(Mutex::new(fixture)))
+    }
+
+    /// Locks the fixture. The fixture only holds plain data, so it is still consistent if a
+    /// thread panicked while holding the lock.
+    pub(crate) fn lock(&self) -> MutexGuard<'_, DerivationFixture> {
+        self.0.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// An error returned by the providers serving a [DerivationFixture].
+#[derive(Debug, thiserror::Error)]
+pub(crate) enum FixtureProviderError {
+    /// The data is not held by the fixture, and no RPC endpoint is configured to fetch it.
+    #[error("{0} is missing from the derivation fixture")]
+    Missing(String),
+    /// The RPC endpoint failed to serve the data.
+    #[error(transparent)]
+    Provider(PipelineErrorKind),
+}
+
+impl FixtureProviderError {
+    /// Creates a [FixtureProviderError::Provider] from the error of an RPC-backed provider.
+    fn provider(err: impl Into<PipelineErrorKind>) -> Self {
+        Self::Provider(err.into())
+    }
+}
+
+impl From<FixtureProviderError> for PipelineErrorKind {
+    fn from(e: FixtureProviderError) -> Self {
+        match e {
+            // Data missing from a fixture never becomes available, so derivation cannot proceed.
+            FixtureProviderError::Missing(_) => PipelineError::Provider(e.to_string()).crit(),
+            FixtureProviderError::Provider(e) => e,
+        }
+    }
+}
+
+/// A [ChainProvider] serving the L1 data of a [DerivationFixture], and fetching the remaining
+/// data from an [AlloyChainProvider], if any.
+#[derive(Debug, Clone)]
+pub(crate) struct FixtureChainProvider {
+    /// The RPC-backed provider, if an L1 endpoint is configured.
+    inner: Option<AlloyChainProvider>,
+    /// The fixture.
+    fixture: SharedFixture,
+}
+
+impl FixtureChainProvider {
+    /// Creates a new [FixtureChainProvider].
+    pub(crate) const fn new(inner: Option<AlloyChainProvider>, fixture: SharedFixture) -> Self {
+        Self { inner, fixture }
+    }
+
+    /// Returns the RPC-backed provider, or a [FixtureProviderError::Missing] error for the
+    /// described data if there is none.
+    fn inner(
+        &mut self,
+        missing: impl FnOnce() -> String,
+    ) -> Result<&mut AlloyChainProvider, FixtureProviderError> {
+        self.inner.as_mut().ok_or_else(|| FixtureProviderError::Missing(missing()))
+    }
+
+    /// Returns the number of the L1 head block, recording it into the fixture. Without an L1
+    /// endpoint, the L1 head recorded by the fixture is returned.
+    pub(crate) async fn latest_block_number(&mut self) -> Result<u64, FixtureProviderError> {
+        let Some(inner) = self.inner.as_mut() else {
+            let l1_head = self.fixture.lock().l1_head;
+            return Some(l1_head)
+                .filter(|head| *head != 0)
+                .ok_or_else(|| FixtureProviderError::Missing("The L1 head".to_string()));
+        };
+
+        let l1_head = inner
+            .latest_block_number()
+            .await
+            .map_err(|e| PipelineError::Provider(format!("Transport error: {e}")).temp())
+            .map_err(FixtureProviderError::Provider)?;
+        self.fixture.lock().l1_head = l1_head;
+        Ok(l1_head)
+    }
+}
+
+#[async_trait]
+impl ChainProvider for FixtureChainProvider {
+    type Error = FixtureProviderError;
+
+    async fn header_by_hash(&mut self, hash: B256) -> Result<Header, Self::Error> {
+        let held = self.fixture.lock().l1_headers.get(&hash).cloned();
+        if let Some(header) = held {
+            return Ok(header);
+        }
+
+        let header = self
+            .inner(|| format!("L1 header {hash}"))?
+            .header_by_hash(hash)
+            .await
+            .map_err(FixtureProviderError::provider)?;
+        self.fixture.lock().l1_headers.insert(hash, header.clone());
+        Ok(header)
+    }
+
+    async fn block_info_by_number(&mut self, number: u64) -> Result<BlockInfo, Self::Error> {
+        let held = {
+            let fixture = self.fixture.lock();
+            fixture.l1_hashes.get(&number).and_then(|hash| fixture.l1_blocks.get(hash)).copied()
+        };
+        if let Some(block) = held {
+            return Ok(block);
+        }
+
+        let block = self
+            .inner(|| format!("L1 block {number}"))?
+            .block_info_by_number(number)
+            .await
+            .map_err(FixtureProviderError::provider)?;
+        let mut fixture = self.fixture.lock();
+        fixture.l1_hashes.insert(number, block.hash);
+        fixture.l1_blocks.insert(block.hash, block);
+        Ok(block)
+    }
+
+    async fn receipts_by_hash(&mut self, hash: B256) -> Result<Vec<Receipt>, Self::Error> {
+        let held = self.fixture.lock().l1_receipts.get(&hash).cloned();
+        if let Some(receipts) = held {
+            return Ok(receipts);
+        }
+
+        let receipts = self
+            .inner(|| format!("Receipts of L1 block {hash}"))?
+            .receipts_by_hash(hash)
+            .await
+            .map_err(FixtureProviderError::provider)?;
+        self.fixture.lock().l1_receipts.insert(hash, receipts.clone());
+        Ok(receipts)
+    }
+
+    async fn block_info_and_transactions_by_hash(
+        &mut self,
+        hash: B256,
+    ) -> Result<(BlockInfo, Vec<TxEnvelope>), Self::Error> {
+        let held = {
+            let fixture = self.fixture.lock();
+            fixture.l1_blocks.get(&hash).copied().zip(fixture.l1_transactions.get(&hash).cloned())
+        };
+        if let Some(block_and_txs) = held {
+            return Ok(block_and_txs);
+        }
+
+        let (block, txs) = self
+            .inner(|| format!("Transactions of L1 block {hash}"))?
+            .block_info_and_transactions_by_hash(hash)
+            .await
+            .map_err(FixtureProviderError::provider)?;
+        let mut fixture = self.fixture.lock();
+        fixture.l1_blocks.insert(hash, block);
+        fixture.l1_transactions.insert(hash, txs.clone());
+        Ok((block, txs))
+    }
+}
+
+/// A [BlobProvider] serving the blobs of a [DerivationFixture], and fetching the remaining blobs
+/// from an [OnlineBlobProvider], if any.
+#[derive(Debug, Clone)]
+pub(crate) struct FixtureBlobProvider {
+    /// The beacon-backed provider, if a beacon endpoint is configured.
+    inner: Option<OnlineBlobProvider<OnlineBeaconClient>>,
+    /// The fixture.
+    fixture: SharedFixture,
+}
+
+impl FixtureBlobProvider {
+    /// Creates a new [FixtureBlobProvider].
+    pub(crate) const fn new(
+        inner: Option<OnlineBlobProvider<OnlineBeaconClient>>,
+        fixture: SharedFixture,
+    ) -> Self {
+        Self { inner, fixture }
+    }
+}
+
+#[async_trait]
+impl BlobProvider for FixtureBlobProvider {
+    type Error = FixtureProviderError;
+
+    async fn get_and_validate_blobs(
+        &mut self,
+        block_ref: &BlockInfo,
+        blob_hashes: &[IndexedBlobHash],
+    ) -> Result<Vec<Box<Blob>>, Self::Error> {
+        let held: Option<Vec<_>> = {
+            let fixture = self.fixture.lock();
+            blob_hashes.iter().map(|hash| fixture.blobs.get(&hash.hash).cloned()).collect()
+        };
+        if let Some(blobs) = held {
+            return Ok(blobs);
+        }
+
+        let blobs = self
+            .inner
+            .as_mut()
+            .ok_or_else(|| {
+                FixtureProviderError::Missing(format!("Blobs of L1 block {}", block_ref.number))
+            })?
+            .get_and_validate_blobs(block_ref, blob_hashes)
+            .await
+            .map_err(FixtureProviderError::provider)?;
+        self.fixture
+            .lock()
+            .blobs
+            .extend(blob_hashes.iter().map(|hash| hash.hash).zip(blobs.iter().cloned()));
+        Ok(blobs)
+    }
+}
+
+/// An [L2ChainProvider] serving the canonical L2 blocks of a [DerivationFixture], and fetching
+/// the remaining blocks from an L2 endpoint, if any.
+#[derive(Debug, Clone)]
+pub(crate) struct FixtureL2ChainProvider {
+    /// The L2 endpoint, if any.
+    inner: Option<RootProvider<Optimism>>,
+    /// The rollup configuration.
+    rollup_config: Arc<RollupConfig>,
+    /// The fixture.
+    fixture: SharedFixture,
+}
+
+impl FixtureL2ChainProvider {
+    /// Creates a new [FixtureL2ChainProvider].
+    pub(crate) const fn new(
+        inner: Option<RootProvider<Optimism>>,
+        rollup_config: Arc<RollupConfig>,
+        fixture: SharedFixture,
+    ) -> Self {
+        Self { inner, rollup_config, fixture }
+    }
+
+    /// Returns the canonical L2 block with the given number.
+    pub(crate) async fn canonical_block(
+        &mut self,
+        number: u64,
+    ) -> Result<Block<Transaction>, FixtureProviderError> {
+        let held = self.fixture.lock().l2_blocks.get(&number).cloned();
+        if let Some(block) = held {
+            return Ok(block);
+        }
+
+        let block = self
+            .inner
+            .as_ref()
+            .ok_or_else(|| FixtureProviderError::Missing(format!("L2 block {number}")))?
+            .get_block_by_number(number.into())
+            .full()
+            .await
+            .map_err(|e| PipelineError::Provider(format!("Transport error: {e}")).temp())
+            .and_then(|block| {
+                block.ok_or_else(|| {
+                    PipelineError::Provider(format!("L2 block {number} not found")).temp()
+                })
+            })
+            .map_err(FixtureProviderError::Provider)?;
+        self.fixture.lock().l2_blocks.insert(number, block.clone());
+        Ok(block)
+    }
+}
+
+#[async_trait]
+impl BatchValidationProvider for FixtureL2ChainProvider {
+    type Error = FixtureProviderError;
+
+    async fn l2_block_info_by_number(&mut self, number: u64) -> Result<L2BlockInfo, Self::Error> {
+        let block = self.block_by_number(number).await?;
+        L2BlockInfo::from_block_and_genesis(&block, &self.rollup_config.genesis).map_err(|e| {
+            FixtureProviderError::provider(PipelineError::Provider(e.to_string()).crit())
+        })
+    }
+
+    async fn block_by_number(&mut self, number: u64) -> Result<OpBlock, Self::Error> {
+        let block = self.canonical_block(number).await?;
+        Ok(block.into_consensus().map_transactions(|t| t.inner.inner.into_inner()))
+    }
+}
+
+#[async_trait]
+impl L2ChainProvider for FixtureL2ChainProvider {
+    type Error = FixtureProviderError;
+
+    async fn system_config_by_number(
+        &mut self,
+        number: u64,
+        rollup_config: Arc<RollupConfig>,
+    ) -> Result<SystemConfig, <Self as BatchValidationProvider>::Error> {
+        let block = self.block_by_number(number).await?;
+        to_system_config(&block, &rollup_config).map_err(|e| {
+            FixtureProviderError::provider(PipelineError::Provider(e.to_string()).crit())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_rpc_client::RpcClient;
+    use alloy_rpc_types_eth::BlockTransactions;
+    use alloy_transport::mock::{Asserter, MockTransport};
+
+    /// Returns an L2 RPC block with the given number.
+    fn l2_block(number: u64) -> Block<Transaction> {
+        Block {
+            header: alloy_rpc_types_eth::Header::new(Header { number, ..Default::default() }),
+            uncles: Vec::new(),
+            transactions: BlockTransactions::Full(Vec::new()),
+            withdrawals: None,
+        }
+    }
+
+    #[test]
+    fn test_fixture_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(DerivationFixture::read(dir.path()).unwrap().l1_head, 0);
+
+        let block = BlockInfo::new(B256::repeat_byte(1), 10, B256::repeat_byte(2), 100);
+        let mut fixture = DerivationFixture { l1_head: 12, ..Default::default() };
+        fixture.l1_hashes.insert(block.number, block.hash);
+        fixture.l1_blocks.insert(block.hash, block);
+        fixture.blobs.insert(B256::repeat_byte(3), Box::default());
+
+        fixture.write(dir.path()).unwrap();
+        let read = DerivationFixture::read(dir.path()).unwrap();
+        assert_eq!(read.l1_head, fixture.l1_head);
+        assert_eq!(read.l1_hashes, fixture.l1_hashes);
+        assert_eq!(read.l1_blocks, fixture.l1_blocks);
+        assert_eq!(read.blobs, fixture.blobs);
+    }
+
+    #[tokio::test]
+    async fn test_chain_provider_serves_fixture() {
+        let block = BlockInfo::new(B256::repeat_byte(1), 10, B256::repeat_byte(2), 100);
+        let mut fixture = DerivationFixture { l1_head: 12, ..Default::default() };
+        fixture.l1_hashes.insert(block.number, block.hash);
+        fixture.l1_blocks.insert(block.hash, block);
+        let mut provider = FixtureChainProvider::new(None, SharedFixture::new(fixture));
+
+        assert_eq!(provider.latest_block_number().await.unwrap(), 12);
+        assert_eq!(provider.block_info_by_number(10).await.unwrap(), block);
+
+        // Data missing from the fixture halts derivation without an L1 endpoint.
+        let err = provider.block_info_by_number(11).await.unwrap_err();
+        assert!(matches!(err, FixtureProviderError::Missing(_)));
+        assert!(matches!(PipelineErrorKind::from(err), PipelineErrorKind::Critical(_)));
+        assert!(provider.receipts_by_hash(block.hash).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_l2_chain_provider_records_fetched_blocks() {
+        let asserter = Asserter::new();
+        let inner = RootProvider::new(RpcClient::new(MockTransport::new(asserter.clone()), false));
+        let fixture = SharedFixture::default();
+        let mut provider = FixtureL2ChainProvider::new(
+            Some(inner),
+            Arc::new(RollupConfig::default()),
+            fixture.clone(),
+        );
+        asserter.push_success(&l2_block(1));
+
+        assert_eq!(provider.canonical_block(1).await.unwrap().header.number, 1);
+        assert!(fixture.lock().l2_blocks.contains_key(&1));
+
+        // The recorded block is served without fetching it again.
+        assert_eq!(provider.block_by_number(1).await.unwrap().header.number, 1);
+        assert!(asserter.read_q().is_empty());
+
+        // Without an L2 endpoint, only the recorded blocks are served.
+        let mut offline =
+            FixtureL2ChainProvider::new(None, Arc::new(RollupConfig::default()), fixture);
+        assert_eq!(offline.canonical_block(1).await.unwrap().header.number, 1);
+        assert!(matches!(offline.canonical_block(2).await, Err(FixtureProviderError::Missing(_))));
+    }
+}
@@ -0,0 +1,423 @@
+//! Derive Subcommand
+
+use crate::{
+    commands::node::{load_l1_config, load_l2_config},
+    flags::GlobalArgs,
+};
+use alloy_provider::RootProvider;
+use anyhow::{Result, bail};
+use clap::Parser;
+use kona_cli::LogConfig;
+use kona_derive::{
+    ActivationSignal, ChainProvider, DerivationPipeline, EthereumDataSource, L2ChainProvider,
+    OriginProvider, Pipeline, PipelineBuilder, PipelineErrorKind, PolledAttributesQueueStage,
+    ResetError, ResetSignal, SignalReceiver, StatefulAttributesBuilder, StepResult,
+};
+use kona_engine::AttributesMatch;
+use kona_genesis::{L1ChainConfig, RollupConfig};
+use kona_protocol::{BatchValidationProvider, BlockInfo, L2BlockInfo, OpAttributesWithParent};
+use kona_providers_alloy::{AlloyChainProvider, OnlineBeaconClient, OnlineBlobProvider};
+use std::{path::PathBuf, sync::Arc};
+use tracing::{debug, info, warn};
+use url::Url;
+
+mod fixture;
+use fixture::{
+    DerivationFixture, FixtureBlobProvider, FixtureChainProvider, FixtureL2ChainProvider,
+    SharedFixture,
+};
+
+/// The size of the L1 provider caches.
+const PROVIDER_CACHE_SIZE: usize = 1024;
+
+/// The derivation pipeline of the `derive` subcommand, served by the fixture providers.
+type DerivePipeline = DerivationPipeline<
+    PolledAttributesQueueStage<
+        EthereumDataSource<FixtureChainProvider, FixtureBlobProvider>,
+        FixtureChainProvider,
+        FixtureL2ChainProvider,
+        StatefulAttributesBuilder<FixtureChainProvider, FixtureL2ChainProvider>,
+    >,
+    FixtureL2ChainProvider,
+>;
+
+/// The `derive` Subcommand
+///
+/// The `derive` subcommand derives the payload attributes of a range of L2 blocks from L1 data,
+/// without an execution engine, and prints them as JSON lines, along with the hash of the
+/// canonical block at the same height and whether the attributes match it. This is useful to debug
+/// batch posting issues, or to verify that the batches posted for a chain derive the canonical
+/// blocks. The command fails if any derived block does not match the canonical chain.
+///
+/// Derivation starts from the parent of the first block in the range. Without an engine, the
+/// derived attributes are not executed: the canonical L2 blocks are used as the parents and system
+/// configs of the following blocks.
+///
+/// The L1 and L2 data are fetched from the RPC endpoints, or read from a fixture directory. A run
+/// with `--fixture-dir` records the fetched data into the directory, so that the same range can
+/// later be derived without any endpoint.
+///
+/// # Usage
+///
+/// ```sh
+/// kona-node derive --l1-eth-rpc http://localhost:8545 \
+///                  --l1-beacon http://localhost:5052 \
+///                  --l2-eth-rpc http://localhost:9545 \
+///                  --fixture-dir ./fixture \
+///                  --from 1000 --to 1010
+///
+/// kona-node derive --fixture-dir ./fixture --from 1000 --to 1010
+/// ```
+#[derive(Parser, PartialEq, Debug, Clone)]
+#[command(about = "Derives a range of L2 blocks from L1 data without an execution engine")]
+pub struct DeriveCommand {
+    /// URL of the L1 execution client RPC API.
+    /// Required unless the L1 data is read from `--fixture-dir`.
+    #[arg(
+        long,
+        visible_alias = "l1",
+        env = "KONA_NODE_L1_ETH_RPC",
+        required_unless_present = "fixture_dir"
+    )]
+    pub l1_eth_rpc: Option<Url>,
+    /// URL of the L1 beacon API.
+    /// Required unless the blobs are read from `--fixture-dir`.
+    #[arg(
+        long,
+        visible_alias = "l1.beacon",
+        env = "KONA_NODE_L1_BEACON",
+        required_unless_present = "fixture_dir"
+    )]
+    pub l1_beacon: Option<Url>,
+    /// URL of an L2 execution client RPC API, serving the canonical blocks.
+    /// Required unless the canonical blocks are read from `--fixture-dir`.
+    #[arg(
+        long,
+        visible_alias = "l2",
+        env = "KONA_NODE_L2_ETH_RPC",
+        required_unless_present = "fixture_dir"
+    )]
+    pub l2_eth_rpc: Option<Url>,
+    /// Path to a fixture directory. The data held by the fixture is not fetched from the RPC
+    /// endpoints, and the fetched data is recorded into the fixture once derivation ends.
+    #[arg(long, visible_alias = "fixture")]
+    pub fixture_dir: Option<PathBuf>,
+    /// The number of the first L2 block to derive.
+    #[arg(long)]
+    pub from: u64,
+    /// The number of the last L2 block to derive, inclusive. Defaults to `--from`.
+    #[arg(long)]
+    pub to: Option<u64>,
+    /// Path to a custom L2 rollup configuration file
+    /// (overrides the default rollup configuration from the registry)
+    #[arg(long, visible_alias = "rollup-cfg", env = "KONA_NODE_ROLLUP_CONFIG")]
+    pub l2_config_file: Option<PathBuf>,
+    /// Path to a custom L1 rollup configuration file
+    /// (overrides the default rollup configuration from the registry)
+    #[arg(long, visible_alias = "rollup-l1-cfg", env = "KONA_NODE_L1_CHAIN_CONFIG")]
+    pub l1_config_file: Option<PathBuf>,
+}
+
+impl DeriveCommand {
+    /// Initializes the logging system based on global arguments.
+    pub fn init_logs(&self, args: &GlobalArgs) -> anyhow::Result<()> {
+        LogConfig::new(args.log_args.clone()).init_tracing_subscriber(None)?;
+        Ok(())
+    }
+
+    /// Runs the subcommand.
+    pub async fn run(self, args: &GlobalArgs) -> anyhow::Result<()> {
+        let to = self.to.unwrap_or(self.from);
+        if self.from == 0 || to < self.from {
+            bail!("Invalid block range: {}..={to}", self.from);
+        }
+
+        let cfg =
+            Arc::new(args.apply_overrides(load_l2_config(self.l2_config_file.as_ref(), args)?));
+        let l1_cfg = Arc::new(load_l1_config(self.l1_config_file.as_ref(), cfg.l1_chain_id)?);
+
+        let fixture = match &self.fixture_dir {
+            Some(dir) => DerivationFixture::read(dir)?,
+            None => DerivationFixture::default(),
+        };
+        let fixture = SharedFixture::new(fixture);
+
+        let l1_provider = FixtureChainProvider::new(
+            self.l1_eth_rpc.map(|url| AlloyChainProvider::new_http(url, PROVIDER_CACHE_SIZE)),
+            fixture.clone(),
+        );
+        let blob_provider = match self.l1_beacon {
+            Some(url) => {
+                Some(OnlineBlobProvider::init(OnlineBeaconClient::new_http(url.to_string())).await)
+            }
+            None => None,
+        };
+        let blob_provider = FixtureBlobProvider::new(blob_provider, fixture.clone());
+        let l2_provider = FixtureL2ChainProvider::new(
+            self.l2_eth_rpc.map(RootProvider::new_http),
+            cfg.clone(),
+            fixture.clone(),
+        );
+
+        let result =
+            Self::derive(cfg, l1_cfg, l1_provider, blob_provider, l2_provider, self.from, to).await;
+
+        // Record the fetched data even if derivation failed, to replay the failure.
+        if let Some(dir) = &self.fixture_dir {
+            fixture.lock().write(dir)?;
+            info!(target: "derive", dir = %dir.display(), "Recorded derivation fixture");
+        }
+        result
+    }
+
+    /// Derives the L2 blocks `from..=to`, and prints them along with their match against the
+    /// canonical chain.
+    async fn derive(
+        cfg: Arc<RollupConfig>,
+        l1_cfg: Arc<L1ChainConfig>,
+        mut l1_provider: FixtureChainProvider,
+        blob_provider: FixtureBlobProvider,
+        mut l2_provider: FixtureL2ChainProvider,
+        from: u64,
+        to: u64,
+    ) -> Result<()> {
+        // Start derivation from the parent of the first block.
+        let mut cursor = l2_provider.l2_block_info_by_number(from - 1).await?;
+        let l1_origin = Self::reset_origin(&mut l1_provider, &cfg, cursor).await?;
+        let l1_head = l1_provider.latest_block_number().await?;
+
+        info!(
+            target: "derive",
+            parent = cursor.block_info.number,
+            l1_origin = l1_origin.number,
+            "Starting derivation"
+        );
+
+        let attributes = StatefulAttributesBuilder::new(
+            cfg.clone(),
+            l1_cfg,
+            l2_provider.clone(),
+            l1_provider.clone(),
+        );
+        let dap = EthereumDataSource::new_from_parts(l1_provider.clone(), blob_provider, &cfg);
+        let mut pipeline = PipelineBuilder::new()
+            .rollup_config(cfg.clone())
+            .dap_source(dap)
+            .l2_chain_provider(l2_provider.clone())
+            .chain_provider(l1_provider.clone())
+            .builder(attributes)
+            .origin(BlockInfo::default())
+            .build_polled();
+
+        let system_config =
+            l2_provider.system_config_by_number(cursor.block_info.number, cfg.clone()).await.ok();
+        pipeline
+            .signal(ResetSignal { l2_safe_head: cursor, l1_origin, system_config }.signal())
+            .await?;
+
+        let mut mismatches = 0;
+        while cursor.block_info.number < to {
+            let attributes = Self::next_attributes(
+                &mut pipeline,
+                &mut l1_provider,
+                &mut l2_provider,
+                &cfg,
+                cursor,
+                l1_head,
+            )
+            .await?;
+            let number = attributes.parent.block_info.number + 1;
+
+            let canonical = l2_provider.canonical_block(number).await?;
+            let mismatch = match AttributesMatch::check(&cfg, &attributes, &canonical) {
+                AttributesMatch::Match => None,
+                AttributesMatch::Mismatch(mismatch) => {
+                    warn!(target: "derive", number, ?mismatch, "Derived block is not canonical");
+                    mismatches += 1;
+                    Some(format!("{mismatch:?}"))
+                }
+            };
+
+            println!(
+                "{}",
+                serde_json::json!({
+                    "number": number,
+                    "parent": attributes.parent.block_info.hash,
+                    "derivedFrom": attributes.derived_from,
+                    "canonical": canonical.header.hash,
+                    "mismatch": mismatch,
+                    "attributes": attributes.inner,
+                })
+            );
+
+            // Without an engine, the derived attributes are not executed. Continue derivation
+            // from the canonical block at the same height.
+            cursor = l2_provider.l2_block_info_by_number(number).await?;
+        }
+
+        if mismatches > 0 {
+            bail!("{mismatches} derived blocks do not match the canonical chain");
+        }
+        Ok(())
+    }
+
+    /// Returns the L1 origin to reset the pipeline to for the given L2 safe head: the L1 origin of
+    /// the safe head, rewound by the channel timeout so that the channels opened before it are
+    /// read again. This is the L1 origin the engine resets the node's pipeline to.
+    async fn reset_origin(
+        l1_provider: &mut FixtureChainProvider,
+        cfg: &RollupConfig,
+        safe_head: L2BlockInfo,
+    ) -> Result<BlockInfo> {
+        let number = safe_head
+            .l1_origin
+            .number
+            .saturating_sub(cfg.channel_timeout(safe_head.block_info.timestamp));
+        Ok(l1_provider.block_info_by_number(number).await?)
+    }
+
+    /// Steps the pipeline until it produces the attributes of the block following `cursor`.
+    ///
+    /// Fails if the pipeline runs out of L1 data, i.e. its origin reaches `l1_head`.
+    async fn next_attributes(
+        pipeline: &mut DerivePipeline,
+        l1_provider: &mut FixtureChainProvider,
+        l2_provider: &mut FixtureL2ChainProvider,
+        cfg: &Arc<RollupConfig>,
+        cursor: L2BlockInfo,
+        l1_head: u64,
+    ) -> Result<OpAttributesWithParent> {
+        loop {
+            match pipeline.step(cursor).await {
+                StepResult::PreparedAttributes => {
+                    debug!(target: "derive", "Prepared attributes");
+                }
+                StepResult::AdvancedOrigin => {
+                    debug!(target: "derive", origin = ?pipeline.origin(), "Advanced origin");
+                }
+                StepResult::OriginAdvanceErr(PipelineErrorKind::Temporary(_))
+                    if pipeline.origin().is_some_and(|origin| origin.number >= l1_head) =>
+                {
+                    bail!(
+                        "Reached the L1 head before deriving block {}",
+                        cursor.block_info.number + 1
+                    );
+                }
+                StepResult::OriginAdvanceErr(e) | StepResult::StepFailed(e) => match e {
+                    PipelineErrorKind::Temporary(_) => continue,
+                    PipelineErrorKind::Reset(e) => {
+                        warn!(target: "derive", err = ?e, "Resetting derivation pipeline");
+                        let system_config = l2_provider
+                            .system_config_by_number(cursor.block_info.number, cfg.clone())
+                            .await
+                            .ok();
+                        // Like the node, the Holocene activation keeps the current L1 origin,
+                        // while other resets restart from the L1 origin of the safe head.
+                        let signal = if matches!(e, ResetError::HoloceneActivation) {
+                            let l1_origin = pipeline
+                                .origin()
+                                .ok_or_else(|| anyhow::anyhow!("Pipeline has no L1 origin"))?;
+                            ActivationSignal { l2_safe_head: cursor, l1_origin, system_config }
+                                .signal()
+                        } else {
+                            let l1_origin = Self::reset_origin(l1_provider, cfg, cursor).await?;
+                            ResetSignal { l2_safe_head: cursor, l1_origin, system_config }.signal()
+                        };
+                        pipeline.signal(signal).await?;
+                    }
+                    PipelineErrorKind::Critical(e) => bail!("Derivation failed: {e}"),
+                },
+            }
+
+            if let Some(attributes) = pipeline.next() {
+                return Ok(attributes);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_eips::BlockNumHash;
+    use alloy_primitives::B256;
+
+    #[test]
+    fn test_derive_cli_flags() {
+        let args = DeriveCommand::parse_from([
+            "derive",
+            "--l1-eth-rpc",
+            "http://localhost:8545",
+            "--l1-beacon",
+            "http://localhost:5052",
+            "--l2-eth-rpc",
+            "http://localhost:9545",
+            "--from",
+            "1000",
+        ]);
+        assert_eq!(args.from, 1000);
+        assert_eq!(args.to, None);
+
+        let args = DeriveCommand::parse_from([
+            "derive",
+            "--l1",
+            "http://localhost:8545",
+            "--l1.beacon",
+            "http://localhost:5052",
+            "--l2",
+            "http://localhost:9545",
+            "--from",
+            "1000",
+            "--to",
+            "1010",
+        ]);
+        assert_eq!(args.to, Some(1010));
+        assert_eq!(args.fixture_dir, None);
+    }
+
+    #[test]
+    fn test_derive_cli_fixture_dir() {
+        // The endpoints are optional when the data is read from a fixture directory.
+        let args =
+            DeriveCommand::parse_from(["derive", "--fixture-dir", "./fixture", "--from", "1000"]);
+        assert_eq!(args.fixture_dir, Some(PathBuf::from("./fixture")));
+        assert_eq!(args.l1_eth_rpc, None);
+        assert_eq!(args.l1_beacon, None);
+        assert_eq!(args.l2_eth_rpc, None);
+
+        assert!(DeriveCommand::try_parse_from(["derive", "--from", "1000"]).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_reset_origin_rewinds_channel_timeout() {
+        let cfg = RollupConfig { channel_timeout: 50, ..Default::default() };
+        let genesis = BlockInfo::new(B256::repeat_byte(4), 0, B256::ZERO, 0);
+        let origin = BlockInfo::new(B256::repeat_byte(1), 50, B256::repeat_byte(2), 600);
+        let mut fixture = DerivationFixture::default();
+        for block in [genesis, origin] {
+            fixture.l1_hashes.insert(block.number, block.hash);
+            fixture.l1_blocks.insert(block.hash, block);
+        }
+        let mut l1_provider = FixtureChainProvider::new(None, SharedFixture::new(fixture));
+
+        let safe_head = L2BlockInfo::new(
+            BlockInfo { number: 10, timestamp: 1_000, ..Default::default() },
+            BlockNumHash { number: 100, hash: B256::repeat_byte(3) },
+            0,
+        );
+        assert_eq!(
+            DeriveCommand::reset_origin(&mut l1_provider, &cfg, safe_head).await.unwrap(),
+            origin
+        );
+
+        // The L1 origin is not rewound past the L1 genesis.
+        let safe_head = L2BlockInfo {
+            l1_origin: BlockNumHash { number: 20, hash: B256::repeat_byte(5) },
+            ..safe_head
+        };
+        assert_eq!(
+            DeriveCommand::reset_origin(&mut l1_provider, &cfg, safe_head).await.unwrap(),
+            genesis
+        );
+    }
+}
@@ -0,0 +1,140 @@
+//! Genesis Subcommand
+
+use crate::flags::GlobalArgs;
+use alloy_eips::BlockId;
+use alloy_genesis::GenesisAccount;
+use alloy_primitives::Address;
+use alloy_provider::{Provider, RootProvider};
+use anyhow::{Context, Result};
+use clap::Parser;
+use kona_cli::LogConfig;
+use kona_genesis::{DeployConfig, L1Deployments};
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+use tracing::info;
+use url::Url;
+
+/// The `genesis` Subcommand
+///
+/// The `genesis` subcommand generates the L2 genesis and the rollup configuration of a new chain,
+/// like `op-node genesis l2`, from:
+///
+/// - the deploy configuration of the chain.
+/// - the L1 deployments, the addresses of the L1 contracts of the chain.
+/// - the L2 allocations, the state dump of the predeploys and prefunded accounts produced by the
+///   contracts-bedrock `L2Genesis` script.
+///
+/// The chain starts from the L1 block of the `l1StartingBlockTag` of the deploy configuration, or
+/// from the latest L1 block if it is unset.
+///
+/// # Usage
+///
+/// ```sh
+/// kona-node genesis --l1-eth-rpc http://localhost:8545 \
+///                   --deploy-config deploy-config.json \
+///                   --l1-deployments l1-deployments.json \
+///                   --l2-allocs allocs-l2.json \
+///                   --outfile.l2 genesis.json \
+///                   --outfile.rollup rollup.json
+/// ```
+#[derive(Parser, PartialEq, Debug, Clone)]
+#[command(about = "Generates the L2 genesis and rollup configuration of a new chain")]
+pub struct GenesisCommand {
+    /// URL of the L1 execution client RPC API, used to fetch the L1 starting block.
+    #[arg(long, visible_alias = "l1", env = "KONA_NODE_L1_ETH_RPC")]
+    pub l1_eth_rpc: Url,
+    /// Path to the deploy configuration of the chain.
+    #[arg(long)]
+    pub deploy_config: PathBuf,
+    /// Path to the addresses of the L1 contracts of the chain.
+    #[arg(long)]
+    pub l1_deployments: PathBuf,
+    /// Path to the allocations of the L2 genesis.
+    #[arg(long)]
+    pub l2_allocs: PathBuf,
+    /// Path to write the L2 genesis to.
+    #[arg(long = "outfile.l2")]
+    pub outfile_l2: PathBuf,
+    /// Path to write the rollup configuration to.
+    #[arg(long = "outfile.rollup")]
+    pub outfile_rollup: PathBuf,
+}
+
+impl GenesisCommand {
+    /// Initializes the logging system based on global arguments.
+    pub fn init_logs(&self, args: &GlobalArgs) -> anyhow::Result<()> {
+        LogConfig::new(args.log_args.clone()).init_tracing_subscriber(None)?;
+        Ok(())
+    }
+
+    /// Runs the subcommand.
+    pub async fn run(self, _args: &GlobalArgs) -> anyhow::Result<()> {
+        let deploy_config: DeployConfig = serde_json::from_str(&read(&self.deploy_config)?)
+            .context("Failed to parse the deploy configuration")?;
+        let deployments: L1Deployments = serde_json::from_str(&read(&self.l1_deployments)?)
+            .context("Failed to parse the L1 deployments")?;
+        let alloc: BTreeMap<Address, GenesisAccount> =
+            serde_json::from_str(&read(&self.l2_allocs)?)
+                .context("Failed to parse the L2 allocations")?;
+
+        let block_id = deploy_config.l1_starting_block_tag.unwrap_or(BlockId::latest());
+        let provider = RootProvider::new_http(self.l1_eth_rpc);
+        let l1_start = provider
+            .get_block(block_id)
+            .await?
+            .with_context(|| format!("L1 starting block {block_id} not found"))?
+            .header
+            .inner;
+
+        let genesis = deploy_config.l2_genesis(&l1_start, alloc)?;
+        let rollup_config = deploy_config.rollup_config(&l1_start, &genesis, &deployments);
+        info!(
+            target: "genesis",
+            l1_start = l1_start.number,
+            l2_genesis = %rollup_config.genesis.l2.hash,
+            "Generated the L2 genesis"
+        );
+
+        fs::write(&self.outfile_l2, serde_json::to_string_pretty(&genesis)?)
+            .with_context(|| format!("Failed to write {}", self.outfile_l2.display()))?;
+        fs::write(&self.outfile_rollup, serde_json::to_string_pretty(&rollup_config)?)
+            .with_context(|| format!("Failed to write {}", self.outfile_rollup.display()))?;
+        Ok(())
+    }
+}
+
+/// Reads the file at the given path.
+fn read(path: &Path) -> Result<String> {
+    fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_genesis_command_flags() {
+        let command = GenesisCommand::try_parse_from([
+            "genesis",
+            "--l1-eth-rpc",
+            "http://localhost:8545",
+            "--deploy-config",
+            "deploy-config.json",
+            "--l1-deployments",
+            "l1-deployments.json",
+            "--l2-allocs",
+            "allocs-l2.json",
+            "--outfile.l2",
+            "genesis.json",
+            "--outfile.rollup",
+            "rollup.json",
+        ])
+        .unwrap();
+        assert_eq!(command.l2_allocs, PathBuf::from("allocs-l2.json"));
+        assert_eq!(command.outfile_l2, PathBuf::from("genesis.json"));
+        assert_eq!(command.outfile_rollup, PathBuf::from("rollup.json"));
+    }
+}
@@ -1,24 +1,57 @@
 //! Info Subcommand
 
-use crate::flags::GlobalArgs;
+use crate::{commands::node::load_l2_config, flags::GlobalArgs};
+use alloy_consensus::Transaction;
+use alloy_eips::eip4844::IndexedBlobHash;
+use alloy_primitives::{B256, Bytes, hex};
+use alloy_provider::Provider;
+use anyhow::{Context, Result, bail};
 use clap::Parser;
 use kona_cli::LogConfig;
+use kona_derive::{BlobData, BlobProvider, ChainProvider};
+use kona_genesis::RollupConfig;
+use kona_protocol::{Batch, BatchReader, BlockInfo, Channel, ChannelId, Frame};
+use kona_providers_alloy::{AlloyChainProvider, OnlineBeaconClient, OnlineBlobProvider};
 use kona_registry::{OPCHAINS, ROLLUP_CONFIGS};
+use std::{collections::BTreeMap, path::PathBuf};
 use tracing::info;
+use url::Url;
 
 /// The `info` Subcommand
 ///
 /// The `info` subcommand is used to run the information stack for the `kona-node`.
 ///
+/// When a batcher transaction is passed with `--tx`, the subcommand instead decodes the frames
+/// it carries, reassembles and decompresses the channels they complete, and prints the batches
+/// they contain. The transaction can be given as an L1 transaction hash, in which case its data
+/// is fetched from the L1 RPC (and the L1 beacon API for blob transactions), or as the raw
+/// hex-encoded batcher data.
+///
 /// # Usage
 ///
 /// ```sh
 /// kona-node info
+/// kona-node info --tx 0x00... # raw batcher data
+/// kona-node info --tx <tx-hash> --l1-eth-rpc http://localhost:8545 --l1-beacon http://localhost:5052
 /// ```
-
 #[derive(Parser, Default, PartialEq, Debug, Clone)]
 #[command(about = "Runs the information stack for the kona-node.")]
-pub struct InfoCommand;
+pub struct InfoCommand {
+    /// A batcher transaction to decode, given either as an L1 transaction hash or as the raw
+    /// hex-encoded batcher data (the derivation version byte followed by frames).
+    #[arg(long, value_name = "HASH|HEX")]
+    pub tx: Option<String>,
+    /// URL of the L1 execution client RPC API, used to fetch the transaction by hash.
+    #[arg(long, visible_alias = "l1", env = "KONA_NODE_L1_ETH_RPC")]
+    pub l1_eth_rpc: Option<Url>,
+    /// URL of the L1 beacon API, used to fetch the blobs of a blob transaction.
+    #[arg(long, visible_alias = "l1.beacon", env = "KONA_NODE_L1_BEACON")]
+    pub l1_beacon: Option<Url>,
+    /// Path to a custom L2 rollup configuration file
+    /// (overrides the default rollup configuration from the registry)
+    #[arg(long, visible_alias = "rollup-cfg", env = "KONA_NODE_ROLLUP_CONFIG")]
+    pub l2_config_file: Option<PathBuf>,
+}
 
 impl InfoCommand {
     /// Initializes the logging system based on global arguments.
@@ -28,9 +61,27 @@ impl InfoCommand {
     }
 
     /// Runs the information stack for the kona-node.
-    pub fn run(&self, args: &GlobalArgs) -> anyhow::Result<()> {
+    pub async fn run(self, args: &GlobalArgs) -> anyhow::Result<()> {
         info!(target: "node_info", "Running info command");
 
+        let Some(tx) = self.tx.as_deref() else {
+            return Self::print_chain_info(args);
+        };
+
+        let cfg = args.apply_overrides(load_l2_config(self.l2_config_file.as_ref(), args)?);
+        let raw = hex::decode(tx.trim()).context("Transaction must be a hash or hex data")?;
+        let (data, l1_block) = if raw.len() == B256::len_bytes() {
+            let (data, block) = self.fetch_batcher_data(B256::from_slice(&raw)).await?;
+            (data, Some(block))
+        } else {
+            (vec![Bytes::from(raw)], None)
+        };
+
+        Self::print_batcher_data(&cfg, data, l1_block)
+    }
+
+    /// Prints the registry information of the configured L2 chain.
+    fn print_chain_info(args: &GlobalArgs) -> anyhow::Result<()> {
         let op_chain_config = OPCHAINS.get(&args.l2_chain_id.id()).expect("No Chain config found");
         let op_rollup_config =
             ROLLUP_CONFIGS.get(&args.l2_chain_id.id()).expect("No Rollup config found");
@@ -46,4 +97,171 @@ impl InfoCommand {
 
         Ok(())
     }
+
+    /// Fetches the batcher data carried by the L1 transaction with the given hash, along with the
+    /// L1 block that includes it.
+    ///
+    /// Calldata transactions carry a single data item. Blob transactions carry one data item per
+    /// blob, fetched from the L1 beacon API.
+    async fn fetch_batcher_data(&self, hash: B256) -> Result<(Vec<Bytes>, BlockInfo)> {
+        let Some(l1_eth_rpc) = self.l1_eth_rpc.clone() else {
+            bail!("--l1-eth-rpc is required to decode a transaction by hash");
+        };
+        let mut l1_provider = AlloyChainProvider::new_http(l1_eth_rpc, 1);
+
+        let block_hash = l1_provider
+            .inner
+            .get_transaction_by_hash(hash)
+            .await?
+            .with_context(|| format!("Transaction {hash} not found"))?
+            .block_hash
+            .with_context(|| format!("Transaction {hash} is pending"))?;
+        let (block, txs) = l1_provider.block_info_and_transactions_by_hash(block_hash).await?;
+
+        // Blob hashes are indexed by their position among all blobs in the block.
+        let mut blob_index = 0;
+        let Some(tx) = txs.into_iter().find(|tx| {
+            let found = *tx.tx_hash() == hash;
+            if !found {
+                blob_index += tx.blob_versioned_hashes().map_or(0, |h| h.len() as u64);
+            }
+            found
+        }) else {
+            bail!("Transaction {hash} not found in block {block_hash}");
+        };
+
+        let Some(blob_hashes) = tx.blob_versioned_hashes() else {
+            return Ok((vec![tx.input().clone()], block));
+        };
+        let Some(l1_beacon) = self.l1_beacon.as_ref() else {
+            bail!("--l1-beacon is required to decode a blob transaction");
+        };
+
+        let blob_hashes = blob_hashes
+            .iter()
+            .zip(blob_index..)
+            .map(|(hash, index)| IndexedBlobHash { index, hash: *hash })
+            .collect::<Vec<_>>();
+        let mut blob_provider =
+            OnlineBlobProvider::init(OnlineBeaconClient::new_http(l1_beacon.to_string())).await;
+        let blobs = blob_provider
+            .get_and_validate_blobs(&block, &blob_hashes)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to fetch blobs: {e}"))?;
+
+        let data = blobs
+            .iter()
+            .map(|blob| BlobData::from(&**blob).decode())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| anyhow::anyhow!("Failed to decode blob: {e}"))?;
+        Ok((data, block))
+    }
+
+    /// Prints the frames contained in the given batcher data, and the batches of every channel
+    /// they complete.
+    fn print_batcher_data(
+        cfg: &RollupConfig,
+        data: Vec<Bytes>,
+        l1_block: Option<BlockInfo>,
+    ) -> Result<()> {
+        let block = l1_block.unwrap_or_default();
+        let mut channels = BTreeMap::<ChannelId, Channel>::new();
+
+        for item in data {
+            let frames = Frame::parse_frames(&item)
+                .map_err(|e| anyhow::anyhow!("Failed to parse frames: {e}"))?;
+            for frame in frames {
+                println!(
+                    "Frame {} of channel {}: {} bytes{}",
+                    frame.number,
+                    hex::encode_prefixed(frame.id),
+                    frame.data.len(),
+                    if frame.is_last { " (last)" } else { "" }
+                );
+                channels
+                    .entry(frame.id)
+                    .or_insert_with(|| Channel::new(frame.id, block))
+                    .add_frame(frame, block)
+                    .map_err(|e| anyhow::anyhow!("Invalid frame: {e}"))?;
+            }
+        }
+
+        // Without an L1 block, the channel size limit of the latest hardfork applies.
+        let timestamp = l1_block.map_or(u64::MAX, |block| block.timestamp);
+        let max_rlp_bytes = cfg.max_rlp_bytes_per_channel(timestamp) as usize;
+
+        for (id, channel) in channels {
+            println!("-------------");
+            let Some(channel_data) = channel.is_ready().then(|| channel.frame_data()).flatten()
+            else {
+                println!(
+                    "Channel {}: incomplete, {} frame(s) in this transaction",
+                    hex::encode_prefixed(id),
+                    channel.len()
+                );
+                continue;
+            };
+
+            println!("Channel {}: {} frame(s)", hex::encode_prefixed(id), channel.len());
+            let mut reader = BatchReader::new(channel_data, max_rlp_bytes);
+            if let Err(e) = reader.decompress() {
+                println!("  Failed to decompress channel: {e}");
+                continue;
+            }
+            let mut count = 0;
+            while let Some(batch) = reader.next_batch(cfg) {
+                Self::print_batch(count, &batch);
+                count += 1;
+            }
+            println!("  {count} batch(es)");
+        }
+
+        Ok(())
+    }
+
+    /// Prints a summary of a decoded batch.
+    fn print_batch(index: usize, batch: &Batch) {
+        match batch {
+            Batch::Single(single) => println!(
+                "  Batch {index} (single): timestamp {}, epoch {} ({}), parent {}, {} tx(s)",
+                single.timestamp,
+                single.epoch_num,
+                single.epoch_hash,
+                single.parent_hash,
+                single.transactions.len()
+            ),
+            Batch::Span(span) => println!(
+                "  Batch {index} (span): timestamps {}..={}, starting epoch {}, {} block(s), {} tx(s)",
+                span.starting_timestamp(),
+                span.final_timestamp(),
+                span.starting_epoch_num(),
+                span.batches.len(),
+                span.batches.iter().map(|b| b.transactions.len()).sum::<usize>()
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_info_cli_flags() {
+        let args = InfoCommand::parse_from(["info"]);
+        assert_eq!(args, InfoCommand::default());
+
+        let args = InfoCommand::parse_from([
+            "info",
+            "--tx",
+            "0x00",
+            "--l1",
+            "http://localhost:8545",
+            "--l1.beacon",
+            "http://localhost:5052",
+        ]);
+        assert_eq!(args.tx.as_deref(), Some("0x00"));
+        assert_eq!(args.l1_eth_rpc, Some(Url::parse("http://localhost:8545").unwrap()));
+        assert_eq!(args.l1_beacon, Some(Url::parse("http://localhost:5052").unwrap()));
+    }
 }
@@ -3,6 +3,9 @@
 mod info;
 pub use info::InfoCommand;
 
+mod derive;
+pub use derive::DeriveCommand;
+
 mod node;
 pub use node::NodeCommand;
 
@@ -12,5 +15,8 @@ pub use bootstore::BootstoreCommand;
 mod net;
 pub use net::NetCommand;
 
+mod genesis;
+pub use genesis::GenesisCommand;
+
 mod registry;
 pub use registry::RegistryCommand;
@@ -5,6 +5,7 @@ use clap::Parser;
 use futures::future::OptionFuture;
 use jsonrpsee::{RpcModule, server::Server};
 use kona_cli::LogConfig;
+use kona_disc::Discv5Driver;
 use kona_gossip::P2pRpcRequest;
 use kona_node_service::{
     NetworkActor, NetworkBuilder, NetworkContext, NetworkInboundData, NodeActor,
@@ -55,6 +56,10 @@ impl NetCommand {
 
     /// Run the Net subcommand.
     pub async fn run(self, args: &GlobalArgs) -> anyhow::Result<()> {
+        if self.p2p.bootnode_mode {
+            return run_bootnode(self.p2p.bootnode_driver(args)?, CancellationToken::new()).await;
+        }
+
         let signer = args.genesis_signer()?;
         info!(target: "net", "Genesis block signer: {:?}", signer);
 
@@ -72,7 +77,13 @@ impl NetCommand {
             NetworkActor::new(NetworkBuilder::from(p2p_config));
 
         let (blocks, mut blocks_rx) = tokio::sync::mpsc::channel(1024);
-        network.start(NetworkContext { blocks, cancellation: CancellationToken::new() }).await?;
+        network
+            .start(NetworkContext {
+                blocks,
+                import_backpressure: None,
+                cancellation: CancellationToken::new(),
+            })
+            .await?;
 
         info!(target: "net", "Network started, receiving blocks.");
 
@@ -130,3 +141,40 @@ impl NetCommand {
         }
     }
 }
+
+/// The interval at which a bootnode logs the size of its discovery table.
+const BOOTNODE_STATUS_INTERVAL: tokio::time::Duration = tokio::time::Duration::from_secs(60);
+
+/// Runs the discovery service of a dedicated bootnode until the given cancellation token is
+/// cancelled. No gossip connections are made: discovered ENRs are only kept in the discovery table
+/// and the bootstore, to be served to the nodes joining the network.
+pub(crate) async fn run_bootnode(
+    driver: Discv5Driver,
+    cancellation: CancellationToken,
+) -> anyhow::Result<()> {
+    // Discovered ENRs are not dialed, so the receiver is dropped right away.
+    let (handler, _) = driver.start();
+
+    let enr = handler.local_enr().await?;
+    info!(target: "bootnode", enr = %enr.to_base64(), node_id = %enr.node_id(), "Bootnode started");
+
+    let mut interval = tokio::time::interval(BOOTNODE_STATUS_INTERVAL);
+    loop {
+        tokio::select! {
+            _ = cancellation.cancelled() => {
+                info!(target: "bootnode", "Received shutdown signal. Stopping the bootnode.");
+                // Dropping the handler shuts the discovery service down, which persists the
+                // bootstore.
+                return Ok(());
+            }
+            _ = interval.tick() => {
+                let table = handler.table_enrs().await;
+                let peers = handler.peer_count().await;
+                let (Ok(table), Ok(peers)) = (table, peers) else {
+                    anyhow::bail!("The discovery service stopped unexpectedly");
+                };
+                info!(target: "bootnode", table = table.len(), peers, "Discovery table status");
+            }
+        }
+    }
+}
@@ -1,22 +1,29 @@
 //! Node Subcommand.
 
 use crate::{
+    commands::net::run_bootnode,
     flags::{GlobalArgs, P2PArgs, RpcArgs, SequencerArgs},
     metrics::{CliMetrics, init_rollup_config_metrics},
 };
+use alloy_eips::BlockNumHash;
+use alloy_primitives::B256;
+use alloy_provider::Provider;
 use alloy_rpc_types_engine::JwtSecret;
 use anyhow::{Result, bail};
 use backon::{ExponentialBuilder, Retryable};
 use clap::Parser;
 use kona_cli::{LogConfig, MetricsArgs};
-use kona_genesis::{L1ChainConfig, RollupConfig};
-use kona_node_service::{NodeMode, RollupNode, RollupNodeService};
+use kona_genesis::{L1ChainConfig, L1StorageProvider, RollupConfig, SystemConfig};
+use kona_node_service::{NodeMode, RollupNode, RollupNodeService, SyncMode};
+use kona_providers_alloy::{AlloyChainProvider, BeaconRedundancy};
 use kona_registry::{L1Config, scr_rollup_config_by_alloy_ident};
+use kona_sources::TrustedCheckpoint;
 use op_alloy_provider::ext::engine::OpEngineApi;
 use serde_json::from_reader;
-use std::{fs::File, path::PathBuf, sync::Arc};
+use std::{fmt::Debug, fs::File, num::NonZeroU32, path::PathBuf, sync::Arc, time::Duration};
 use strum::IntoEnumIterator;
-use tracing::{debug, error, info};
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, error, info, warn};
 use url::Url;
 
 /// A JWT token validation error.
@@ -28,6 +35,37 @@ pub(super) enum JwtValidationError {
     CapabilityExchange(String),
 }
 
+/// A mismatch between the rollup configuration and the chains served by the L1 RPC and the
+/// execution client.
+#[derive(Debug, thiserror::Error)]
+pub(super) enum GenesisVerificationError {
+    #[error(
+        "The rollup config expects L1 chain ID {expected}, but the L1 RPC serves chain ID {found}"
+    )]
+    L1ChainId { expected: u64, found: u64 },
+    #[error(
+        "The rollup config expects L1 genesis block {} to be {}, but the L1 RPC reports {found}",
+        expected.number,
+        expected.hash
+    )]
+    L1Genesis { expected: BlockNumHash, found: B256 },
+    #[error(
+        "The rollup config expects L2 chain ID {expected}, but the execution client serves chain ID {found}"
+    )]
+    L2ChainId { expected: u64, found: u64 },
+    #[error(
+        "The rollup config expects L2 genesis block {} to be {}, but the execution client reports {found}",
+        expected.number,
+        expected.hash
+    )]
+    L2Genesis { expected: BlockNumHash, found: B256 },
+    #[error(
+        "The rollup config expects the genesis system config {expected:?}, but the L1 \
+         SystemConfig contract holds {found:?} at the L1 genesis block"
+    )]
+    L1SystemConfig { expected: Box<SystemConfig>, found: Box<SystemConfig> },
+}
+
 /// Command-line interface for running a Kona rollup node.
 ///
 /// The `NodeCommand` struct defines all the configuration options needed to start and run
@@ -39,6 +77,10 @@ pub(super) enum JwtValidationError {
 /// The node can operate in different modes:
 /// - **Validator**: Validates L2 blocks and participates in consensus
 /// - **Sequencer**: Sequences transactions and produces L2 blocks
+/// - **p2p-only**: Relays unsafe L2 blocks over gossip and tracks the unsafe head, without an
+///   engine. The L2 engine is not called, and the L2 RPC is only used to serve payloads to peers.
+/// - **derive-only**: Derives the L2 chain from L1 without joining the P2P network. The execution
+///   layer is synced by derivation, as with `--syncmode consensus-layer`.
 ///
 /// # Configuration Sources
 ///
@@ -79,9 +121,54 @@ pub struct NodeCommand {
         )
     )]
     pub node_mode: NodeMode,
+    /// The mode to sync the execution layer in.
+    #[arg(
+        long = "syncmode",
+        default_value_t = SyncMode::ExecutionLayer,
+        env = "KONA_NODE_SYNCMODE",
+        help = format!(
+            "The mode to sync the execution layer in. Supported modes are: {}",
+            SyncMode::iter()
+                .map(|mode| format!("\"{}\"", mode.to_string()))
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+    )]
+    pub sync_mode: SyncMode,
     /// URL of the L1 execution client RPC API.
     #[arg(long, visible_alias = "l1", env = "KONA_NODE_L1_ETH_RPC")]
     pub l1_eth_rpc: Url,
+    /// URLs of L1 execution client RPC APIs to fail over to when `--l1-eth-rpc` is unavailable,
+    /// in order of priority.
+    #[arg(
+        long,
+        visible_alias = "l1.fallback",
+        value_delimiter = ',',
+        env = "KONA_NODE_L1_ETH_RPC_FALLBACK"
+    )]
+    pub l1_eth_rpc_fallback: Vec<Url>,
+    /// The maximum number of requests per second sent to the L1 execution client RPC API.
+    /// Identical concurrent requests are coalesced into a single request when set.
+    #[arg(long, visible_alias = "l1.rps", env = "KONA_NODE_L1_ETH_RPC_RATE_LIMIT")]
+    pub l1_eth_rpc_rate_limit: Option<NonZeroU32>,
+    /// WebSocket or IPC endpoint of the L1 execution client, used to subscribe to new heads and
+    /// system config logs. Falls back to polling `--l1-eth-rpc` if unset or unavailable.
+    #[arg(long, visible_alias = "l1.subscribe", env = "KONA_NODE_L1_ETH_SUBSCRIPTION_RPC")]
+    pub l1_eth_subscription_rpc: Option<String>,
+    /// Number of confirmations after which an L1 block is considered finalized, for L1 chains
+    /// without a finality gadget. L2 blocks derived from such L1 blocks are finalized. If unset,
+    /// the L1 `finalized` block tag is used.
+    #[arg(long, visible_alias = "l1.finality-depth", env = "KONA_NODE_L1_FINALITY_DEPTH")]
+    pub l1_finality_depth: Option<u64>,
+    /// Number of L1 blocks ahead of the derivation pipeline whose receipts, transactions and
+    /// batcher blobs are prefetched. Prefetching is disabled if set to 0.
+    #[arg(
+        long,
+        visible_alias = "l1.prefetch-lookahead",
+        env = "KONA_NODE_L1_PREFETCH_LOOKAHEAD",
+        default_value = "0"
+    )]
+    pub l1_prefetch_lookahead: u64,
     /// Whether to trust the L1 RPC.
     /// If false, block hash verification is performed for all retrieved blocks.
     #[arg(
@@ -94,9 +181,39 @@ pub struct NodeCommand {
     /// URL of the L1 beacon API.
     #[arg(long, visible_alias = "l1.beacon", env = "KONA_NODE_L1_BEACON")]
     pub l1_beacon: Url,
+    /// URLs of L1 beacon APIs queried when `--l1-beacon` cannot serve valid blobs, in order of
+    /// priority. Blobs are always checked against their versioned hashes, and beacon APIs serving
+    /// mismatched blobs are queried last.
+    #[arg(
+        long,
+        visible_alias = "l1.beacon-fallbacks",
+        value_delimiter = ',',
+        env = "KONA_NODE_L1_BEACON_FALLBACKS"
+    )]
+    pub l1_beacon_fallbacks: Vec<Url>,
+    /// Queries all L1 beacon APIs for every blob, to detect every beacon API serving mismatched
+    /// blobs, instead of only failing over when a beacon API cannot serve valid blobs.
+    #[arg(long, visible_alias = "l1.beacon-cross-check", env = "KONA_NODE_L1_BEACON_CROSS_CHECK")]
+    pub l1_beacon_cross_check: bool,
     /// URL of the engine API endpoint of an L2 execution client.
     #[arg(long, visible_alias = "l2", env = "KONA_NODE_L2_ENGINE_RPC")]
     pub l2_engine_rpc: Url,
+    /// URLs of engine API endpoints of standby L2 execution clients to fail over to when
+    /// `--l2-engine-rpc` is unavailable, in order of priority. Standby execution clients must
+    /// share the JWT secret of the primary.
+    #[arg(
+        long,
+        visible_alias = "l2.standby",
+        value_delimiter = ',',
+        env = "KONA_NODE_L2_ENGINE_RPC_STANDBY"
+    )]
+    pub l2_engine_rpc_standby: Vec<Url>,
+    /// URL of the engine API endpoint of a secondary L2 execution client, used to cross-check
+    /// the inserted payloads. Every payload inserted into the primary is sent to it with
+    /// `engine_newPayload`, its head follows the unsafe chain with forkchoice updates, and
+    /// divergent verdicts are reported. The verifier must share the JWT secret of the primary.
+    #[arg(long, visible_alias = "l2.verifier", env = "KONA_NODE_L2_ENGINE_RPC_VERIFIER")]
+    pub l2_engine_rpc_verifier: Option<Url>,
     /// Whether to trust the L2 RPC.
     /// If false, block hash verification is performed for all retrieved blocks.
     #[arg(
@@ -106,8 +223,47 @@ pub struct NodeCommand {
         default_value = "true"
     )]
     pub l2_trust_rpc: bool,
+    /// Interval, in milliseconds, within which forkchoice updates of unsafe blocks are batched
+    /// during rapid unsafe block import. The latest heads are always sent to the execution
+    /// client once the interval elapses. A value of 0 sends a forkchoice update for every block.
+    #[arg(
+        long,
+        visible_alias = "l2.fcu-debounce",
+        env = "KONA_NODE_L2_ENGINE_FCU_DEBOUNCE",
+        default_value = "100",
+        value_parser = |arg: &str| -> Result<Duration, std::num::ParseIntError> {Ok(Duration::from_millis(arg.parse()?))}
+    )]
+    pub l2_engine_fcu_debounce: Duration,
+    /// Maximum number of unsafe L2 blocks that may be reorged out when the safe chain diverges
+    /// from the unsafe chain. Deeper reorgs halt the node for the operator to investigate. If
+    /// unset, reorgs of any depth are applied.
+    #[arg(long, visible_alias = "l2.max-reorg-depth", env = "KONA_NODE_L2_MAX_REORG_DEPTH")]
+    pub l2_max_reorg_depth: Option<u64>,
+    /// Hash of a trusted L2 block to start the sync from, instead of searching the execution
+    /// client for a safe block. The execution client must hold the checkpoint block. The L2 chain
+    /// up to the checkpoint is not verified against L1, so this requires `--l2.trust-checkpoint`.
+    #[arg(
+        long,
+        visible_alias = "l2.checkpoint",
+        env = "KONA_NODE_L2_CHECKPOINT",
+        requires_all = ["l2_checkpoint_l1_origin", "l2_trust_checkpoint"]
+    )]
+    pub l2_checkpoint: Option<B256>,
+    /// Hash of the L1 origin of the trusted L2 checkpoint block.
+    #[arg(
+        long,
+        visible_alias = "l2.checkpoint-l1-origin",
+        env = "KONA_NODE_L2_CHECKPOINT_L1_ORIGIN",
+        requires = "l2_checkpoint"
+    )]
+    pub l2_checkpoint_l1_origin: Option<B256>,
+    /// Acknowledges that the L2 chain up to `--l2.checkpoint` is trusted rather than verified
+    /// against L1.
+    #[arg(long, visible_alias = "l2.trust-checkpoint", env = "KONA_NODE_L2_TRUST_CHECKPOINT")]
+    pub l2_trust_checkpoint: bool,
     /// JWT secret for the auth-rpc endpoint of the execution client.
     /// This MUST be a valid path to a file containing the hex-encoded JWT secret.
+    /// The file is watched, and the secret is reloaded whenever it changes.
     #[arg(long, visible_alias = "l2.jwt-secret", env = "KONA_NODE_L2_ENGINE_AUTH")]
     pub l2_engine_jwt_secret: Option<PathBuf>,
     /// Path to a custom L2 rollup configuration file
@@ -118,6 +274,37 @@ pub struct NodeCommand {
     /// (overrides the default rollup configuration from the registry)
     #[arg(long, visible_alias = "rollup-l1-cfg", env = "KONA_NODE_L1_CHAIN_CONFIG")]
     pub l1_config_file: Option<PathBuf>,
+    /// Path of the database recording the L2 safe head derived from each L1 block, served by the
+    /// `optimism_safeHeadAtL1Block` RPC method. If unset, safe heads are not recorded.
+    #[arg(long, visible_alias = "safedb.path", env = "KONA_NODE_SAFEDB_PATH")]
+    pub safedb_path: Option<PathBuf>,
+    /// Path of the file persisting the last known L2 safe and finalized blocks. On startup, the
+    /// execution client must still contain them, protecting against a rolled back execution
+    /// client or the execution client of another chain. If unset, no check is performed.
+    #[arg(long, visible_alias = "l2.anchor-path", env = "KONA_NODE_L2_ANCHOR_PATH")]
+    pub l2_anchor_path: Option<PathBuf>,
+    /// Starts the node even though the execution client does not contain the blocks of
+    /// `--l2.anchor-path`, reorging the node to the chain of the execution client.
+    #[arg(
+        long,
+        visible_alias = "l2.anchor-reorg",
+        env = "KONA_NODE_L2_ANCHOR_REORG",
+        requires = "l2_anchor_path"
+    )]
+    pub l2_anchor_reorg: bool,
+    /// Skips the verification, at startup, that the chain IDs and genesis blocks of the rollup
+    /// configuration match the chains served by the L1 RPC and the execution client.
+    #[arg(long, visible_alias = "rollup.skip-genesis-check", env = "KONA_NODE_SKIP_GENESIS_CHECK")]
+    pub skip_genesis_check: bool,
+    /// The time, in seconds, given to the node to finish its in-flight work and persist its state
+    /// when shutting down, after which the remaining tasks are aborted.
+    #[arg(
+        long,
+        env = "KONA_NODE_SHUTDOWN_TIMEOUT",
+        default_value = "30",
+        value_parser = |arg: &str| -> Result<Duration, std::num::ParseIntError> {Ok(Duration::from_secs(arg.parse()?))}
+    )]
+    pub shutdown_timeout: Duration,
     /// P2P CLI arguments.
     #[command(flatten)]
     pub p2p_flags: P2PArgs,
@@ -133,14 +320,34 @@ impl Default for NodeCommand {
     fn default() -> Self {
         Self {
             l1_eth_rpc: Url::parse("http://localhost:8545").unwrap(),
+            l1_eth_rpc_fallback: Vec::new(),
+            l1_eth_rpc_rate_limit: None,
+            l1_eth_subscription_rpc: None,
+            l1_finality_depth: None,
+            l1_prefetch_lookahead: 0,
             l1_trust_rpc: true,
             l1_beacon: Url::parse("http://localhost:5052").unwrap(),
+            l1_beacon_fallbacks: Vec::new(),
+            l1_beacon_cross_check: false,
             l2_engine_rpc: Url::parse("http://localhost:8551").unwrap(),
+            l2_engine_rpc_standby: Vec::new(),
+            l2_engine_rpc_verifier: None,
             l2_trust_rpc: true,
+            l2_engine_fcu_debounce: Duration::from_millis(100),
+            l2_max_reorg_depth: None,
+            l2_checkpoint: None,
+            l2_checkpoint_l1_origin: None,
+            l2_trust_checkpoint: false,
             l2_engine_jwt_secret: None,
             l2_config_file: None,
             l1_config_file: None,
+            safedb_path: None,
+            l2_anchor_path: None,
+            l2_anchor_reorg: false,
+            skip_genesis_check: false,
+            shutdown_timeout: Duration::from_secs(30),
             node_mode: NodeMode::Validator,
+            sync_mode: SyncMode::ExecutionLayer,
             p2p_flags: P2PArgs::default(),
             rpc_flags: RpcArgs::default(),
             sequencer_flags: SequencerArgs::default(),
@@ -231,6 +438,34 @@ impl NodeCommand {
         Self::is_jwt_signature_error(error.as_ref() as &dyn std::error::Error)
     }
 
+    /// Returns the [`NodeMode`] to run the node in. The node runs in sequencer mode if either
+    /// `--mode Sequencer` or `--sequencer.enabled` is set.
+    pub fn mode(&self) -> NodeMode {
+        if self.sequencer_flags.enabled { NodeMode::Sequencer } else { self.node_mode }
+    }
+
+    /// Returns the [`SyncMode`] to sync the execution layer in. Without P2P, the execution layer
+    /// cannot sync from gossiped unsafe blocks, so the node always syncs it by derivation in
+    /// derive-only mode.
+    pub fn sync_mode(&self) -> SyncMode {
+        if self.mode().is_derive_only() { SyncMode::ConsensusLayer } else { self.sync_mode }
+    }
+
+    /// Checks that `--sequencer.enabled` is not combined with a mode that cannot sequence.
+    pub fn validate_mode(&self) -> anyhow::Result<()> {
+        if self.sequencer_flags.enabled &&
+            !matches!(self.node_mode, NodeMode::Validator | NodeMode::Sequencer)
+        {
+            anyhow::bail!("--sequencer.enabled cannot be used in {} mode", self.node_mode);
+        }
+        Ok(())
+    }
+
+    /// Returns the [`TrustedCheckpoint`] to start the sync from, if `--l2.checkpoint` is set.
+    pub fn trusted_checkpoint(&self) -> Option<TrustedCheckpoint> {
+        Some(TrustedCheckpoint::new(self.l2_checkpoint?, self.l2_checkpoint_l1_origin?))
+    }
+
     /// Validate the jwt secret if specified by exchanging capabilities with the engine.
     /// Since the engine client will fail if the jwt token is invalid, this allows to ensure
     /// that the jwt token passed as a cli arg is correct.
@@ -273,23 +508,171 @@ impl NodeCommand {
             .await
     }
 
+    /// Verifies that the chain IDs and genesis blocks of the rollup configuration match the
+    /// chains served by the L1 RPC and the execution client, to fail fast instead of deriving
+    /// an invalid chain when the node is pointed at the wrong network.
+    ///
+    /// The L1 genesis block is only checked if the L1 RPC still serves it, as its history may have
+    /// been pruned or expired. In that case, the genesis system config is also checked against the
+    /// L1 `SystemConfig` contract, see [`verify_genesis_system_config`]. The L2 genesis block is
+    /// only checked if the execution client already holds it.
+    pub async fn verify_genesis(
+        &self,
+        config: &RollupConfig,
+        jwt_secret: JwtSecret,
+    ) -> anyhow::Result<()> {
+        let engine_client = kona_engine::EngineClient::new_http(
+            self.l2_engine_rpc.clone(),
+            self.l1_eth_rpc.clone(),
+            Arc::new(config.clone()),
+            jwt_secret,
+        );
+        let (l1, l2) = (engine_client.l1_provider(), engine_client.l2_engine());
+
+        let verify = || async {
+            let found = l1.get_chain_id().await?;
+            if found != config.l1_chain_id {
+                bail!(GenesisVerificationError::L1ChainId { expected: config.l1_chain_id, found });
+            }
+
+            let expected = config.genesis.l1;
+            match l1.get_block_by_number(expected.number.into()).await? {
+                Some(block) if block.header.hash != expected.hash => {
+                    bail!(GenesisVerificationError::L1Genesis {
+                        expected,
+                        found: block.header.hash
+                    });
+                }
+                Some(_) => {
+                    let mut provider = AlloyChainProvider::new(l1.clone(), 1);
+                    verify_genesis_system_config(config, &mut provider).await?;
+                }
+                None => warn!(
+                    target: "rollup_node",
+                    number = expected.number,
+                    "L1 RPC does not serve the L1 genesis block, skipping its verification"
+                ),
+            }
+
+            let found = l2.get_chain_id().await?;
+            if found != config.l2_chain_id.id() {
+                bail!(GenesisVerificationError::L2ChainId {
+                    expected: config.l2_chain_id.id(),
+                    found
+                });
+            }
+
+            let expected = config.genesis.l2;
+            match l2.get_block_by_number(expected.number.into()).await? {
+                Some(block) if block.header.hash != expected.hash => {
+                    bail!(GenesisVerificationError::L2Genesis {
+                        expected,
+                        found: block.header.hash
+                    });
+                }
+                Some(_) => {}
+                None => warn!(
+                    target: "rollup_node",
+                    number = expected.number,
+                    "Execution client does not hold the L2 genesis block, skipping its verification"
+                ),
+            }
+            Ok(())
+        };
+
+        verify
+            .retry(ExponentialBuilder::default())
+            .when(|e| !e.is::<GenesisVerificationError>())
+            .notify(|err, duration| {
+                debug!(target: "rollup_node", ?err, "Retrying genesis verification after {duration:?}");
+            })
+            .await?;
+
+        info!(
+            target: "rollup_node",
+            l1_genesis = %config.genesis.l1.hash,
+            l2_genesis = %config.genesis.l2.hash,
+            "Verified the genesis of the L1 and L2 chains"
+        );
+        Ok(())
+    }
+
+    /// Waits for a shutdown signal (`SIGINT`, or `SIGTERM` on unix), then cancels the given
+    /// token to gracefully shut the node down. A second signal exits the process immediately.
+    async fn cancel_on_shutdown_signal(cancellation: CancellationToken) {
+        async fn shutdown_signal() {
+            #[cfg(unix)]
+            {
+                use tokio::signal::unix::{SignalKind, signal};
+                match signal(SignalKind::terminate()) {
+                    Ok(mut sigterm) => tokio::select! {
+                        _ = tokio::signal::ctrl_c() => {}
+                        _ = sigterm.recv() => {}
+                    },
+                    Err(err) => {
+                        warn!(target: "rollup_node", ?err, "Failed to listen for SIGTERM");
+                        let _ = tokio::signal::ctrl_c().await;
+                    }
+                }
+            }
+            #[cfg(not(unix))]
+            let _ = tokio::signal::ctrl_c().await;
+        }
+
+        shutdown_signal().await;
+        info!(target: "rollup_node", "Received shutdown signal, shutting down gracefully...");
+        cancellation.cancel();
+
+        shutdown_signal().await;
+        warn!(target: "rollup_node", "Received a second shutdown signal, exiting immediately");
+        std::process::exit(130);
+    }
+
     /// Run the Node subcommand.
     pub async fn run(self, args: &GlobalArgs) -> anyhow::Result<()> {
+        self.validate_mode()?;
+        let mode = self.mode();
         let cfg = self.get_l2_config(args)?;
         let l1_cfg = self.get_l1_config(cfg.l1_chain_id)?;
 
         // If metrics are enabled, initialize the global cli metrics.
         args.metrics.enabled.then(|| init_rollup_config_metrics(&cfg));
 
-        let jwt_secret = self.validate_jwt(&cfg).await?;
+        let cancellation = CancellationToken::new();
+        tokio::spawn(Self::cancel_on_shutdown_signal(cancellation.clone()));
+
+        if self.p2p_flags.bootnode_mode {
+            info!(target: "rollup_node", "Running in bootnode mode, only discovery is started");
+            return run_bootnode(self.p2p_flags.bootnode_driver(args)?, cancellation).await;
+        }
+
+        // The engine is not run in p2p-only mode, so the execution layer is not checked.
+        let jwt_secret = if mode.runs_derivation() {
+            tokio::select! {
+                jwt_secret = self.validate_jwt(&cfg) => jwt_secret?,
+                _ = cancellation.cancelled() => return Ok(()),
+            }
+        } else {
+            self.jwt_secret().unwrap_or_else(JwtSecret::random)
+        };
+
+        if !self.skip_genesis_check && mode.runs_derivation() {
+            tokio::select! {
+                result = self.verify_genesis(&cfg, jwt_secret) => result?,
+                _ = cancellation.cancelled() => return Ok(()),
+            }
+        }
 
-        self.p2p_flags.check_ports()?;
+        if mode.runs_p2p() {
+            self.p2p_flags.check_ports()?;
+        }
         let p2p_config = self.p2p_flags.config(&cfg, args, Some(self.l1_eth_rpc.clone())).await?;
         let rpc_config = self.rpc_flags.into();
 
         info!(
             target: "rollup_node",
             chain_id = cfg.l2_chain_id.id(),
+            %mode,
             "Starting rollup node services"
         );
         for hf in cfg.hardforks.to_string().lines() {
@@ -297,18 +680,42 @@ impl NodeCommand {
         }
 
         RollupNode::builder(cfg, l1_cfg)
-            .with_mode(self.node_mode)
+            .with_mode(mode)
+            .with_sync_mode(self.sync_mode())
             .with_jwt_secret(jwt_secret)
+            .with_jwt_secret_file(self.l2_engine_jwt_secret.clone())
             .with_l1_provider_rpc_url(self.l1_eth_rpc)
+            .with_l1_provider_fallback_rpc_urls(self.l1_eth_rpc_fallback)
+            .with_l1_provider_rate_limit(self.l1_eth_rpc_rate_limit)
+            .with_l1_provider_subscription_rpc(self.l1_eth_subscription_rpc)
+            .with_l1_finality_depth(self.l1_finality_depth)
+            .with_l1_prefetch_lookahead(self.l1_prefetch_lookahead)
             .with_l1_trust_rpc(self.l1_trust_rpc)
             .with_l1_beacon_api_url(self.l1_beacon)
+            .with_l1_beacon_fallback_api_urls(self.l1_beacon_fallbacks)
+            .with_l1_beacon_redundancy(if self.l1_beacon_cross_check {
+                BeaconRedundancy::CrossCheck
+            } else {
+                BeaconRedundancy::Failover
+            })
             .with_l2_engine_rpc_url(self.l2_engine_rpc)
+            .with_l2_standby_engine_rpc_urls(self.l2_engine_rpc_standby)
+            .with_l2_verifier_engine_rpc_url(self.l2_engine_rpc_verifier)
             .with_l2_trust_rpc(self.l2_trust_rpc)
+            .with_safe_db_path(self.safedb_path)
+            .with_max_reorg_depth(self.l2_max_reorg_depth)
+            .with_trusted_checkpoint(self.trusted_checkpoint())
+            .with_anchor_path(self.l2_anchor_path)
+            .with_anchor_reorg(self.l2_anchor_reorg)
+            .with_forkchoice_debounce(
+                (!self.l2_engine_fcu_debounce.is_zero()).then_some(self.l2_engine_fcu_debounce),
+            )
             .with_p2p_config(p2p_config)
             .with_rpc_config(rpc_config)
             .with_sequencer_config(self.sequencer_flags.config())
+            .with_shutdown_timeout(self.shutdown_timeout)
             .build()
-            .start()
+            .start(cancellation)
             .await
             .map_err(|e| {
                 error!(target: "rollup_node", "Failed to start rollup node service: {e}");
@@ -320,40 +727,12 @@ impl NodeCommand {
 
     /// Get the L1 config, either from a file or the known chains.
     pub fn get_l1_config(&self, l1_chain_id: u64) -> Result<L1ChainConfig> {
-        match &self.l1_config_file {
-            Some(path) => {
-                debug!("Loading l1 config from file: {:?}", path);
-                let file = File::open(path)
-                    .map_err(|e| anyhow::anyhow!("Failed to open l1 config file: {e}"))?;
-                from_reader(file).map_err(|e| anyhow::anyhow!("Failed to parse l1 config: {e}"))
-            }
-            None => {
-                debug!("Loading l1 config from known chains");
-                let cfg = L1Config::get_l1_genesis(l1_chain_id).map_err(|e| {
-                    anyhow::anyhow!("Failed to find l1 config for chain ID {l1_chain_id}: {e}")
-                })?;
-                Ok(cfg.into())
-            }
-        }
+        load_l1_config(self.l1_config_file.as_ref(), l1_chain_id)
     }
 
     /// Get the L2 rollup config, either from a file or the superchain registry.
     pub fn get_l2_config(&self, args: &GlobalArgs) -> Result<RollupConfig> {
-        match &self.l2_config_file {
-            Some(path) => {
-                debug!("Loading l2 config from file: {:?}", path);
-                let file = File::open(path)
-                    .map_err(|e| anyhow::anyhow!("Failed to open l2 config file: {e}"))?;
-                from_reader(file).map_err(|e| anyhow::anyhow!("Failed to parse l2 config: {e}"))
-            }
-            None => {
-                debug!("Loading l2 config from superchain registry");
-                let Some(cfg) = scr_rollup_config_by_alloy_ident(&args.l2_chain_id) else {
-                    bail!("Failed to find l2 config for chain ID {}", args.l2_chain_id);
-                };
-                Ok(cfg.clone())
-            }
-        }
+        load_l2_config(self.l2_config_file.as_ref(), args)
     }
 
     /// Returns the JWT secret for the engine API
@@ -391,10 +770,190 @@ impl NodeCommand {
     }
 }
 
+/// Loads the L1 chain config, either from the file at `path` or the known chains.
+/// Verifies the genesis system config of the rollup config against the storage of the L1
+/// `SystemConfig` contract at the L1 genesis block.
+///
+/// Only the batcher address, overhead, scalar and gas limit are compared, as the fields introduced
+/// by later hardforks are usually omitted from the genesis system config. Reading the storage of
+/// the L1 genesis block requires an archive L1 RPC, so the verification is skipped if it fails.
+async fn verify_genesis_system_config<P>(
+    config: &RollupConfig,
+    provider: &mut P,
+) -> Result<(), GenesisVerificationError>
+where
+    P: L1StorageProvider + Send,
+    P::Error: Debug,
+{
+    let Some(expected) = config.genesis.system_config else {
+        return Ok(());
+    };
+
+    let found = match SystemConfig::from_l1_storage(
+        config.l1_system_config_address,
+        config.genesis.l1.hash,
+        provider,
+    )
+    .await
+    {
+        Ok(found) => found,
+        Err(err) => {
+            warn!(
+                target: "rollup_node",
+                ?err,
+                "Failed to read the L1 SystemConfig contract at genesis, skipping its verification"
+            );
+            return Ok(());
+        }
+    };
+
+    let fields = |c: &SystemConfig| (c.batcher_address, c.overhead, c.scalar, c.gas_limit);
+    if fields(&found) != fields(&expected) {
+        return Err(GenesisVerificationError::L1SystemConfig {
+            expected: Box::new(expected),
+            found: Box::new(found),
+        });
+    }
+    Ok(())
+}
+
+pub(crate) fn load_l1_config(path: Option<&PathBuf>, l1_chain_id: u64) -> Result<L1ChainConfig> {
+    match path {
+        Some(path) => {
+            debug!("Loading l1 config from file: {:?}", path);
+            let file = File::open(path)
+                .map_err(|e| anyhow::anyhow!("Failed to open l1 config file: {e}"))?;
+            from_reader(file).map_err(|e| anyhow::anyhow!("Failed to parse l1 config: {e}"))
+        }
+        None => {
+            debug!("Loading l1 config from known chains");
+            let cfg = L1Config::get_l1_genesis(l1_chain_id).map_err(|e| {
+                anyhow::anyhow!("Failed to find l1 config for chain ID {l1_chain_id}: {e}")
+            })?;
+            Ok(cfg.into())
+        }
+    }
+}
+
+/// Loads the L2 rollup config, either from the file at `path` or the superchain registry.
+pub(crate) fn load_l2_config(path: Option<&PathBuf>, args: &GlobalArgs) -> Result<RollupConfig> {
+    match path {
+        Some(path) => {
+            debug!("Loading l2 config from file: {:?}", path);
+            let file = File::open(path)
+                .map_err(|e| anyhow::anyhow!("Failed to open l2 config file: {e}"))?;
+            from_reader(file).map_err(|e| anyhow::anyhow!("Failed to parse l2 config: {e}"))
+        }
+        None => {
+            debug!("Loading l2 config from superchain registry");
+            let Some(cfg) = scr_rollup_config_by_alloy_ident(&args.l2_chain_id) else {
+                bail!("Failed to find l2 config for chain ID {}", args.l2_chain_id);
+            };
+            Ok(cfg.clone())
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use alloy_primitives::{Address, U256, address};
     use anyhow::anyhow;
+    use kona_genesis::{ChainGenesis, SystemConfigSlots};
+    use std::collections::HashMap;
+
+    /// The storage of the L1 `SystemConfig` contract at the L1 genesis block, or an unavailable
+    /// state if `None`.
+    struct MockStorage(Option<HashMap<U256, U256>>);
+
+    #[async_trait::async_trait]
+    impl L1StorageProvider for MockStorage {
+        type Error = &'static str;
+
+        async fn storage_at(
+            &mut self,
+            _: Address,
+            slot: U256,
+            _: B256,
+        ) -> Result<U256, Self::Error> {
+            let storage = self.0.as_ref().ok_or("missing trie node")?;
+            Ok(storage.get(&slot).copied().unwrap_or_default())
+        }
+    }
+
+    fn genesis_system_config() -> SystemConfig {
+        SystemConfig {
+            batcher_address: address!("6887246668a3b87f54deb3b94ba47a6f63f32985"),
+            overhead: U256::from(0xbc),
+            scalar: U256::from(0xa6fe0),
+            gas_limit: 30_000_000,
+            ..Default::default()
+        }
+    }
+
+    fn storage_of(system_config: &SystemConfig) -> HashMap<U256, U256> {
+        HashMap::from([
+            (SystemConfigSlots::OVERHEAD, system_config.overhead),
+            (SystemConfigSlots::SCALAR, system_config.scalar),
+            (
+                SystemConfigSlots::BATCHER_HASH,
+                U256::from_be_slice(system_config.batcher_address.as_slice()),
+            ),
+            (SystemConfigSlots::GAS_LIMIT_FEE_SCALARS, U256::from(system_config.gas_limit)),
+        ])
+    }
+
+    fn rollup_config() -> RollupConfig {
+        RollupConfig {
+            genesis: ChainGenesis {
+                system_config: Some(genesis_system_config()),
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_verify_genesis_system_config() {
+        let config = rollup_config();
+        let mut storage = MockStorage(Some(storage_of(&genesis_system_config())));
+        verify_genesis_system_config(&config, &mut storage).await.unwrap();
+
+        // Fields introduced by later hardforks are not compared.
+        let upgraded = SystemConfig { base_fee_scalar: Some(1), ..genesis_system_config() };
+        let mut storage = MockStorage(Some(storage_of(&upgraded)));
+        storage.0.as_mut().unwrap().insert(
+            SystemConfigSlots::GAS_LIMIT_FEE_SCALARS,
+            U256::from(upgraded.gas_limit) | (U256::from(1) << 64),
+        );
+        verify_genesis_system_config(&config, &mut storage).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_verify_genesis_system_config_mismatch() {
+        let config = rollup_config();
+        let other =
+            SystemConfig { batcher_address: Address::repeat_byte(1), ..genesis_system_config() };
+        let mut storage = MockStorage(Some(storage_of(&other)));
+
+        let err = verify_genesis_system_config(&config, &mut storage).await.unwrap_err();
+        let GenesisVerificationError::L1SystemConfig { expected, found } = err else {
+            panic!("unexpected error: {err:?}");
+        };
+        assert_eq!(*expected, genesis_system_config());
+        assert_eq!(found.batcher_address, other.batcher_address);
+    }
+
+    #[tokio::test]
+    async fn test_verify_genesis_system_config_skipped() {
+        // The L1 RPC does not serve the state of the L1 genesis block.
+        let mut storage = MockStorage(None);
+        verify_genesis_system_config(&rollup_config(), &mut storage).await.unwrap();
+
+        // The rollup config has no genesis system config.
+        let mut storage = MockStorage(Some(HashMap::new()));
+        verify_genesis_system_config(&RollupConfig::default(), &mut storage).await.unwrap();
+    }
 
     #[derive(Debug)]
     struct MockError {
@@ -424,6 +983,280 @@ mod tests {
     fn test_node_cli_defaults() {
         let args = NodeCommand::parse_from(["node"].iter().chain(default_flags().iter()).copied());
         assert_eq!(args.node_mode, NodeMode::Validator);
+        assert_eq!(args.sync_mode, SyncMode::ExecutionLayer);
+    }
+
+    #[test]
+    fn test_node_cli_sequencer_enabled() {
+        let args = NodeCommand::parse_from(["node"].iter().chain(default_flags().iter()).copied());
+        assert_eq!(args.mode(), NodeMode::Validator);
+
+        let args = NodeCommand::parse_from(
+            ["node", "--sequencer.enabled"].iter().chain(default_flags().iter()).copied(),
+        );
+        assert_eq!(args.node_mode, NodeMode::Validator);
+        assert_eq!(args.mode(), NodeMode::Sequencer);
+    }
+
+    #[test]
+    fn test_node_cli_p2p_only_and_derive_only() {
+        let args = NodeCommand::parse_from(
+            ["node", "--mode", "p2p-only"].iter().chain(default_flags().iter()).copied(),
+        );
+        assert_eq!(args.mode(), NodeMode::P2pOnly);
+        assert_eq!(args.sync_mode(), SyncMode::ExecutionLayer);
+        assert!(args.validate_mode().is_ok());
+
+        let args = NodeCommand::parse_from(
+            ["node", "--mode", "derive-only"].iter().chain(default_flags().iter()).copied(),
+        );
+        assert_eq!(args.mode(), NodeMode::DeriveOnly);
+        assert_eq!(args.sync_mode(), SyncMode::ConsensusLayer);
+
+        let args = NodeCommand::parse_from(
+            ["node", "--mode", "derive-only", "--sequencer.enabled"]
+                .iter()
+                .chain(default_flags().iter())
+                .copied(),
+        );
+        assert!(args.validate_mode().is_err());
+
+        let args = NodeCommand::parse_from(
+            ["node", "--mode", "sequencer"].iter().chain(default_flags().iter()).copied(),
+        );
+        assert_eq!(args.mode(), NodeMode::Sequencer);
+    }
+
+    #[test]
+    fn test_node_cli_syncmode() {
+        let args = NodeCommand::parse_from(
+            ["node", "--syncmode", "consensus-layer"].iter().chain(default_flags().iter()).copied(),
+        );
+        assert_eq!(args.sync_mode, SyncMode::ConsensusLayer);
+
+        let err = NodeCommand::try_parse_from(
+            ["node", "--syncmode", "snap"].iter().chain(default_flags().iter()).copied(),
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("--syncmode"));
+    }
+
+    #[test]
+    fn test_node_cli_l1_eth_rpc_fallback() {
+        let args = NodeCommand::parse_from(
+            ["node", "--l1.fallback", "http://localhost:8546,http://localhost:8547"]
+                .iter()
+                .chain(default_flags().iter())
+                .copied(),
+        );
+        assert_eq!(
+            args.l1_eth_rpc_fallback,
+            vec![
+                Url::parse("http://localhost:8546").unwrap(),
+                Url::parse("http://localhost:8547").unwrap()
+            ]
+        );
+    }
+
+    #[test]
+    fn test_node_cli_l1_beacon_fallbacks() {
+        let args = NodeCommand::parse_from(
+            ["node", "--l1.beacon-fallbacks", "http://localhost:5053", "--l1.beacon-cross-check"]
+                .iter()
+                .chain(default_flags().iter())
+                .copied(),
+        );
+        assert_eq!(args.l1_beacon_fallbacks, vec![Url::parse("http://localhost:5053").unwrap()]);
+        assert!(args.l1_beacon_cross_check);
+    }
+
+    #[test]
+    fn test_node_cli_l2_engine_rpc_standby() {
+        let args = NodeCommand::parse_from(
+            ["node", "--l2.standby", "http://localhost:8552,http://localhost:8553"]
+                .iter()
+                .chain(default_flags().iter())
+                .copied(),
+        );
+        assert_eq!(
+            args.l2_engine_rpc_standby,
+            vec![
+                Url::parse("http://localhost:8552").unwrap(),
+                Url::parse("http://localhost:8553").unwrap()
+            ]
+        );
+    }
+
+    #[test]
+    fn test_node_cli_l2_engine_rpc_verifier() {
+        let args = NodeCommand::parse_from(["node"].iter().chain(default_flags().iter()).copied());
+        assert_eq!(args.l2_engine_rpc_verifier, None);
+
+        let args = NodeCommand::parse_from(
+            ["node", "--l2.verifier", "http://localhost:8552"]
+                .iter()
+                .chain(default_flags().iter())
+                .copied(),
+        );
+        assert_eq!(args.l2_engine_rpc_verifier, Some(Url::parse("http://localhost:8552").unwrap()));
+    }
+
+    #[test]
+    fn test_node_cli_l2_engine_fcu_debounce() {
+        let args = NodeCommand::parse_from(["node"].iter().chain(default_flags().iter()).copied());
+        assert_eq!(args.l2_engine_fcu_debounce, Duration::from_millis(100));
+
+        let args = NodeCommand::parse_from(
+            ["node", "--l2.fcu-debounce", "0"].iter().chain(default_flags().iter()).copied(),
+        );
+        assert!(args.l2_engine_fcu_debounce.is_zero());
+    }
+
+    #[test]
+    fn test_node_cli_safedb_path() {
+        let args = NodeCommand::parse_from(
+            ["node", "--safedb.path", "/tmp/safedb"].iter().chain(default_flags().iter()).copied(),
+        );
+        assert_eq!(args.safedb_path, Some(PathBuf::from("/tmp/safedb")));
+    }
+
+    #[test]
+    fn test_node_cli_anchor() {
+        let args = NodeCommand::parse_from(
+            ["node", "--l2.anchor-path", "/tmp/anchor.json", "--l2.anchor-reorg"]
+                .iter()
+                .chain(default_flags().iter())
+                .copied(),
+        );
+        assert_eq!(args.l2_anchor_path, Some(PathBuf::from("/tmp/anchor.json")));
+        assert!(args.l2_anchor_reorg);
+
+        let err = NodeCommand::try_parse_from(
+            ["node", "--l2.anchor-reorg"].iter().chain(default_flags().iter()).copied(),
+        )
+        .unwrap_err();
+        assert_eq!(err.kind(), clap::error::ErrorKind::MissingRequiredArgument);
+    }
+
+    #[test]
+    fn test_node_cli_skip_genesis_check() {
+        let args = NodeCommand::parse_from(["node"].iter().chain(default_flags().iter()).copied());
+        assert!(!args.skip_genesis_check);
+
+        let args = NodeCommand::parse_from(
+            ["node", "--rollup.skip-genesis-check"].iter().chain(default_flags().iter()).copied(),
+        );
+        assert!(args.skip_genesis_check);
+    }
+
+    #[test]
+    fn test_node_cli_shutdown_timeout() {
+        let args = NodeCommand::parse_from(["node"].iter().chain(default_flags().iter()).copied());
+        assert_eq!(args.shutdown_timeout, Duration::from_secs(30));
+
+        let args = NodeCommand::parse_from(
+            ["node", "--shutdown-timeout", "5"].iter().chain(default_flags().iter()).copied(),
+        );
+        assert_eq!(args.shutdown_timeout, Duration::from_secs(5));
+    }
+
+    #[test]
+    fn test_node_cli_l1_finality_depth() {
+        let args = NodeCommand::parse_from(
+            ["node", "--l1.finality-depth", "64"].iter().chain(default_flags().iter()).copied(),
+        );
+        assert_eq!(args.l1_finality_depth, Some(64));
+    }
+
+    #[test]
+    fn test_node_cli_l1_prefetch_lookahead() {
+        let args = NodeCommand::parse_from(["node"].iter().chain(default_flags().iter()).copied());
+        assert_eq!(args.l1_prefetch_lookahead, 0);
+
+        let args = NodeCommand::parse_from(
+            ["node", "--l1.prefetch-lookahead", "8"].iter().chain(default_flags().iter()).copied(),
+        );
+        assert_eq!(args.l1_prefetch_lookahead, 8);
+    }
+
+    #[test]
+    fn test_node_cli_l2_max_reorg_depth() {
+        let args = NodeCommand::parse_from(
+            ["node", "--l2.max-reorg-depth", "32"].iter().chain(default_flags().iter()).copied(),
+        );
+        assert_eq!(args.l2_max_reorg_depth, Some(32));
+    }
+
+    #[test]
+    fn test_node_cli_l2_checkpoint() {
+        let l2_hash = B256::repeat_byte(1).to_string();
+        let l1_hash = B256::repeat_byte(2).to_string();
+
+        let args = NodeCommand::parse_from(
+            [
+                "node",
+                "--l2.checkpoint",
+                &l2_hash,
+                "--l2.checkpoint-l1-origin",
+                &l1_hash,
+                "--l2.trust-checkpoint",
+            ]
+            .iter()
+            .chain(default_flags().iter())
+            .copied(),
+        );
+        assert_eq!(
+            args.trusted_checkpoint(),
+            Some(TrustedCheckpoint::new(B256::repeat_byte(1), B256::repeat_byte(2)))
+        );
+
+        // The checkpoint requires its L1 origin and the explicit trust flag.
+        assert!(
+            NodeCommand::try_parse_from(
+                ["node", "--l2.checkpoint", &l2_hash, "--l2.checkpoint-l1-origin", &l1_hash]
+                    .iter()
+                    .chain(default_flags().iter())
+                    .copied(),
+            )
+            .is_err()
+        );
+        assert!(
+            NodeCommand::try_parse_from(
+                ["node", "--l2.checkpoint", &l2_hash, "--l2.trust-checkpoint"]
+                    .iter()
+                    .chain(default_flags().iter())
+                    .copied(),
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn test_node_cli_l1_eth_rpc_rate_limit() {
+        let args = NodeCommand::parse_from(
+            ["node", "--l1.rps", "25"].iter().chain(default_flags().iter()).copied(),
+        );
+        assert_eq!(args.l1_eth_rpc_rate_limit, NonZeroU32::new(25));
+
+        let err = NodeCommand::try_parse_from(
+            ["node", "--l1.rps", "0"].iter().chain(default_flags().iter()).copied(),
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("--l1-eth-rpc-rate-limit"));
+    }
+
+    #[test]
+    fn test_node_cli_l1_eth_subscription_rpc() {
+        let args = NodeCommand::parse_from(["node"].iter().chain(default_flags().iter()).copied());
+        assert_eq!(args.l1_eth_subscription_rpc, None);
+
+        let args = NodeCommand::parse_from(
+            ["node", "--l1.subscribe", "ws://localhost:8546"]
+                .iter()
+                .chain(default_flags().iter())
+                .copied(),
+        );
+        assert_eq!(args.l1_eth_subscription_rpc.as_deref(), Some("ws://localhost:8546"));
     }
 
     #[test]
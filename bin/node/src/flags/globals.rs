@@ -4,7 +4,7 @@ use alloy_primitives::Address;
 use clap::Parser;
 use kona_cli::{LogArgs, MetricsArgs};
 use kona_genesis::RollupConfig;
-use kona_registry::OPCHAINS;
+use kona_registry::{CHAINS, OPCHAINS};
 
 /// Global arguments for the CLI.
 #[derive(Parser, Default, Clone, Debug)]
@@ -12,15 +12,20 @@ pub struct GlobalArgs {
     /// Logging arguments.
     #[command(flatten)]
     pub log_args: LogArgs,
-    /// The L2 chain ID to use.
+    /// The L2 chain to use.
+    ///
+    /// Known chains are bootstrapped from the superchain registry: their rollup config and L1
+    /// config are used unless config files are passed, and their bootnodes are always dialed.
     #[arg(
         long = "chain",
         alias = "l2-chain-id",
+        visible_alias = "network",
         short = 'c',
         global = true,
         default_value = "10",
         env = "KONA_NODE_L2_CHAIN_ID",
-        help = "The L2 chain ID to use"
+        value_parser = parse_l2_chain,
+        help = "The L2 chain to use, as a chain ID, a network name (e.g. `op-mainnet`, `base-sepolia`) or a registry identifier (e.g. `mainnet/op`)"
     )]
     pub l2_chain_id: alloy_chains::Chain,
     /// Embed the override flags globally to provide override values adjacent to the configs.
@@ -31,6 +36,17 @@ pub struct GlobalArgs {
     pub metrics: MetricsArgs,
 }
 
+/// Parses an L2 chain from a superchain registry network name (e.g. `op-mainnet`) or identifier
+/// (e.g. `mainnet/op`), falling back to the chain names and IDs known to [`alloy_chains::Chain`].
+fn parse_l2_chain(value: &str) -> Result<alloy_chains::Chain, String> {
+    if let Some(chain) =
+        CHAINS.get_chain_by_network_name(value).or_else(|| CHAINS.get_chain_by_ident(value))
+    {
+        return Ok(alloy_chains::Chain::from_id(chain.chain_id));
+    }
+    value.parse::<alloy_chains::Chain>().map_err(|e| e.to_string())
+}
+
 impl GlobalArgs {
     /// Applies the specified overrides to the given rollup config.
     ///
@@ -81,6 +97,16 @@ mod tests {
         assert_eq!(args.l2_chain_id.id(), expected_id);
     }
 
+    #[rstest]
+    #[case::network_op_mainnet("op-mainnet", 10)]
+    #[case::network_base_sepolia("base-sepolia", 84532)]
+    #[case::registry_identifier("mainnet/base", 8453)]
+    #[case::numeric("8453", 8453)]
+    fn test_network_parse_valid(#[case] value: &str, #[case] expected_id: u64) {
+        let args = GlobalArgs::try_parse_from(["test", "--network", value]).unwrap();
+        assert_eq!(args.l2_chain_id.id(), expected_id);
+    }
+
     #[rstest]
     #[case::invalid_string("invalid_chain")]
     fn test_l2_chain_id_parse_invalid(#[case] invalid_value: &str) {
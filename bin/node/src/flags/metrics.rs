@@ -18,6 +18,8 @@ pub fn init_unified_metrics(args: &MetricsArgs) -> anyhow::Result<()> {
         kona_node_service::Metrics::init();
         kona_derive::Metrics::init();
         kona_providers_alloy::Metrics::init();
+        kona_rpc::Metrics::init();
+        kona_sources::Metrics::init();
         VersionInfo::from_build().register_version_metrics();
     }
     Ok(())
@@ -12,13 +12,13 @@ use anyhow::Result;
 use clap::Parser;
 use discv5::{Enr, enr::k256};
 use kona_derive::ChainProvider;
-use kona_disc::LocalNode;
+use kona_disc::{Discv5Driver, LocalNode, Nat};
 use kona_genesis::RollupConfig;
-use kona_gossip::GaterConfig;
+use kona_gossip::{ConnectionGater, DEFAULT_PUBLISH_RATE_LIMIT, GaterConfig};
 use kona_node_service::NetworkConfig;
-use kona_peers::{BootStoreFile, PeerMonitoring, PeerScoreLevel};
+use kona_peers::{BanList, BootStoreFile, PeerMonitoring, PeerScoreLevel, TopicScoreWeights};
 use kona_providers_alloy::AlloyChainProvider;
-use libp2p::identity::Keypair;
+use libp2p::{Multiaddr, PeerId, identity::Keypair};
 use std::{
     net::{IpAddr, SocketAddr},
     num::ParseIntError,
@@ -28,12 +28,28 @@ use std::{
 use tokio::time::Duration;
 use url::Url;
 
+/// The number of discovery sessions kept by a bootnode. Bootnodes are contacted by every node
+/// joining the network, so they keep far more sessions than the discv5 default of 1000.
+pub const BOOTNODE_SESSION_CACHE_CAPACITY: usize = 10_000;
+
 /// P2P CLI Flags
-#[derive(Parser, Clone, Debug, PartialEq, Eq)]
+#[derive(Parser, Clone, Debug, PartialEq)]
 pub struct P2PArgs {
     /// Disable Discv5 (node discovery).
     #[arg(long = "p2p.no-discovery", default_value = "false", env = "KONA_NODE_P2P_NO_DISCOVERY")]
     pub no_discovery: bool,
+    /// Run as a dedicated bootnode: only the discovery service is run, with a static ENR and a
+    /// large session cache, and no gossip connections are dialed or accepted.
+    ///
+    /// The ENR is derived from the `p2p.priv.path` or `p2p.priv.raw` key, which must be set, and
+    /// advertises `p2p.advertise.ip` or `p2p.listen.ip`, which must be a routable address.
+    #[arg(
+        long = "p2p.bootnode-mode",
+        default_value = "false",
+        env = "KONA_NODE_P2P_BOOTNODE_MODE",
+        conflicts_with = "no_discovery"
+    )]
+    pub bootnode_mode: bool,
     /// Read the hex-encoded 32-byte private key for the peer ID from this txt file.
     /// Created if not already exists. Important to persist to keep the same network identity after
     /// restarting, maintaining the previous advertised identity.
@@ -60,6 +76,14 @@ pub struct P2PArgs {
     #[arg(long = "p2p.advertise.udp", env = "KONA_NODE_P2P_ADVERTISE_UDP_PORT")]
     pub advertise_udp_port: Option<u16>,
 
+    /// NAT traversal mechanism, used to map the gossip and discovery ports on the NAT gateway and
+    /// advertise the resulting external address in the ENR.
+    ///
+    /// One of `none`, `any` (UPnP, then NAT-PMP), `upnp`, `pmp`, `pmp:<gateway ip>` or
+    /// `extip:<ip>`. Port mappings are renewed, and the external address re-checked, periodically.
+    #[arg(long = "p2p.nat", default_value = "none", env = "KONA_NODE_P2P_NAT")]
+    pub nat: Nat,
+
     /// IP to bind LibP2P/Discv5 to.
     #[arg(long = "p2p.listen.ip", default_value = "0.0.0.0", env = "KONA_NODE_P2P_LISTEN_IP")]
     pub listen_ip: IpAddr,
@@ -144,6 +168,23 @@ pub struct P2PArgs {
     #[arg(long = "p2p.ban.duration", default_value = "60", env = "KONA_NODE_P2P_BAN_DURATION")]
     pub ban_duration: u64,
 
+    /// The number of payloads with invalid signatures a peer may send before it is banned for
+    /// `p2p.ban.duration` minutes. If set to 0, peers are not banned for invalid signatures.
+    ///
+    /// For peers to be banned, the `p2p.ban.peers` flag must be set to `true`.
+    #[arg(
+        long = "p2p.ban.invalid-signatures",
+        default_value = "3",
+        env = "KONA_NODE_P2P_BAN_INVALID_SIGNATURES"
+    )]
+    pub ban_invalid_signatures: u32,
+
+    /// The file used to persist banned peers across restarts.
+    ///
+    /// Defaults to `~/.kona/<chain_id>/banlist.json`.
+    #[arg(long = "p2p.ban.store", env = "KONA_NODE_P2P_BAN_STORE")]
+    pub ban_store: Option<PathBuf>,
+
     /// The interval in seconds to find peers using the discovery service.
     /// Defaults to 5 seconds.
     #[arg(
@@ -174,6 +215,20 @@ pub struct P2PArgs {
     #[arg(long = "p2p.bootnodes", value_delimiter = ',', env = "KONA_NODE_P2P_BOOTNODES")]
     pub bootnodes: Vec<Enr>,
 
+    /// An optional list of static peers, as multiaddrs including the peer id.
+    ///
+    /// Static peers are dialed on startup and redialed with backoff, indefinitely, whenever they
+    /// disconnect. Like trusted peers, they are never disconnected for their score.
+    #[arg(long = "p2p.static", value_delimiter = ',', env = "KONA_NODE_P2P_STATIC")]
+    pub static_peers: Vec<Multiaddr>,
+
+    /// An optional list of trusted peer ids.
+    ///
+    /// Trusted peers are never disconnected for their score, and are not subject to the
+    /// redialing threshold.
+    #[arg(long = "p2p.trusted", value_delimiter = ',', env = "KONA_NODE_P2P_TRUSTED")]
+    pub trusted_peers: Vec<PeerId>,
+
     /// Optionally enable topic scoring.
     ///
     /// Topic scoring is a mechanism to score peers based on their behavior in the gossip network.
@@ -191,6 +246,23 @@ pub struct P2PArgs {
     )]
     pub topic_scoring: bool,
 
+    /// The weight of each topic score in the peer score, when `p2p.topic-scoring` is enabled.
+    #[arg(
+        long = "p2p.scoring.topic-weight",
+        default_value = "0.8",
+        env = "KONA_NODE_P2P_SCORING_TOPIC_WEIGHT"
+    )]
+    pub topic_weight: f64,
+
+    /// The weight of the penalty applied to peers that deliver invalid messages on a topic, when
+    /// `p2p.topic-scoring` is enabled. This must be negative.
+    #[arg(
+        long = "p2p.scoring.invalid-message-weight",
+        default_value = "-140.4475",
+        env = "KONA_NODE_P2P_SCORING_INVALID_MESSAGE_WEIGHT"
+    )]
+    pub invalid_message_weight: f64,
+
     /// An optional unsafe block signer address.
     ///
     /// By default, this is fetched from the chain config in the superchain-registry using the
@@ -207,6 +279,25 @@ pub struct P2PArgs {
     #[arg(long = "p2p.discovery.randomize", env = "KONA_NODE_P2P_DISCOVERY_RANDOMIZE")]
     pub discovery_randomize: Option<u64>,
 
+    /// The maximum number of locally built unsafe blocks published per second. `0` disables
+    /// rate limiting.
+    #[arg(
+        long = "p2p.publish.rate-limit",
+        default_value_t = DEFAULT_PUBLISH_RATE_LIMIT,
+        env = "KONA_NODE_P2P_PUBLISH_RATE_LIMIT"
+    )]
+    pub publish_rate_limit: u32,
+
+    /// The number of seconds during which blocks signed by the previous unsafe block signer are
+    /// still accepted after a signer rotation. `0` rejects them as soon as the rotation is
+    /// observed.
+    #[arg(
+        long = "p2p.signer-rotation-grace",
+        default_value_t = 0,
+        env = "KONA_NODE_P2P_SIGNER_ROTATION_GRACE"
+    )]
+    pub signer_rotation_grace: u64,
+
     /// Specify optional remote signer configuration. Note that this argument is mutually exclusive
     /// with `p2p.sequencer.key` that specifies a local sequencer signer.
     #[command(flatten)]
@@ -376,8 +467,23 @@ impl P2PArgs {
         let monitor_peers = self.ban_enabled.then_some(PeerMonitoring {
             ban_duration: Duration::from_secs(60 * self.ban_duration),
             ban_threshold: self.ban_threshold as f64,
+            invalid_signature_threshold: self.ban_invalid_signatures,
         });
 
+        if self.invalid_message_weight > 0.0 {
+            anyhow::bail!("p2p.scoring.invalid-message-weight must not be positive");
+        }
+        let topic_score_weights = TopicScoreWeights {
+            topic_weight: self.topic_weight,
+            invalid_message_deliveries_weight: self.invalid_message_weight,
+        };
+        if let Some(addr) =
+            self.static_peers.iter().find(|addr| ConnectionGater::peer_id_from_addr(addr).is_none())
+        {
+            anyhow::bail!("p2p.static peer {addr} is missing a /p2p/<peer id> component");
+        }
+        let ban_list = self.ban_store.or_else(|| BanList::default_path(args.l2_chain_id.into()));
+
         let discovery_listening_address = SocketAddr::new(self.listen_ip, self.listen_udp_port);
         let discovery_config =
             NetworkConfig::discv5_config(discovery_listening_address.into(), static_ip);
@@ -402,6 +508,7 @@ impl P2PArgs {
             discovery_address,
             discovery_randomize: self.discovery_randomize.map(Duration::from_secs),
             enr_update: !static_ip,
+            nat: self.nat,
             gossip_address,
             keypair,
             unsafe_block_signer,
@@ -410,16 +517,80 @@ impl P2PArgs {
             monitor_peers,
             bootstore,
             topic_scoring: self.topic_scoring,
+            topic_score_weights,
+            ban_list,
             gater_config: GaterConfig {
                 peer_redialing: self.peer_redial,
                 dial_period: Duration::from_secs(60 * self.redial_period),
             },
             bootnodes: self.bootnodes,
+            static_peers: self.static_peers,
+            trusted_peers: self.trusted_peers,
             rollup_config: config.clone(),
             gossip_signer: self.signer.config(args)?,
+            publish_rate_limit: self.publish_rate_limit,
+            signer_rotation_grace: Duration::from_secs(self.signer_rotation_grace),
         })
     }
 
+    /// Builds the [`Discv5Driver`] of a dedicated bootnode, for `p2p.bootnode-mode`.
+    ///
+    /// The local ENR is static, so that it can be published as a bootnode of the chain: it is
+    /// signed with the configured private key, advertises the configured address and is never
+    /// updated. Discovered ENRs are not forwarded, since the bootnode does not dial peers.
+    ///
+    /// ## Errors
+    ///
+    /// - If no private key is configured.
+    /// - If the advertised IP address is unspecified.
+    pub fn bootnode_driver(&self, args: &GlobalArgs) -> anyhow::Result<Discv5Driver> {
+        let keypair = self.keypair().map_err(|e| {
+            anyhow::anyhow!("p2p.bootnode-mode requires a persistent p2p private key: {e}")
+        })?;
+        let advertise_ip = self.advertise_ip.unwrap_or(self.listen_ip);
+        if advertise_ip.is_unspecified() {
+            anyhow::bail!(
+                "p2p.bootnode-mode requires p2p.advertise.ip or p2p.listen.ip to be a routable address"
+            );
+        }
+
+        let secp256k1_key = keypair
+            .try_into_secp256k1()
+            .map_err(|e| anyhow::anyhow!("Only secp256k1 keys are supported: {e}"))?
+            .secret()
+            .to_bytes();
+        let local_node_key = k256::ecdsa::SigningKey::from_bytes(&secp256k1_key.into())
+            .map_err(|e| anyhow::anyhow!("Invalid secp256k1 key: {e}"))?;
+        let local_node = LocalNode::new(
+            local_node_key,
+            advertise_ip,
+            self.advertise_tcp_port.unwrap_or(self.listen_tcp_port),
+            self.advertise_udp_port.unwrap_or(self.listen_udp_port),
+        );
+
+        let listen_address = SocketAddr::new(self.listen_ip, self.listen_udp_port);
+        let discovery_config = discv5::ConfigBuilder::new(listen_address.into())
+            .disable_enr_update()
+            .auto_nat_listen_duration(None)
+            .session_cache_capacity(BOOTNODE_SESSION_CACHE_CAPACITY)
+            .build();
+
+        let bootstore = (!self.disable_bootstore).then(|| {
+            self.bootstore.clone().map_or(
+                BootStoreFile::Default { chain_id: args.l2_chain_id.into() },
+                BootStoreFile::Custom,
+            )
+        });
+
+        Discv5Driver::builder(local_node, args.l2_chain_id.into(), discovery_config)
+            .with_interval(Duration::from_secs(self.discovery_interval))
+            .with_bootstore_file(bootstore)
+            .with_bootnodes(self.bootnodes.clone())
+            .disable_forward()
+            .build()
+            .map_err(|e| anyhow::anyhow!("Failed to build the bootnode discovery service: {e}"))
+    }
+
     /// Returns the [`Keypair`] from the cli inputs.
     ///
     /// If the raw private key is empty and the specified file is empty,
@@ -473,6 +644,40 @@ mod tests {
         assert!(args.p2p.keypair().is_ok());
     }
 
+    #[test]
+    fn test_p2p_args_bootnode_driver() {
+        let global = GlobalArgs { l2_chain_id: 10.into(), ..Default::default() };
+        let args = MockCommand::parse_from(["test", "--p2p.bootnode-mode"]);
+        assert!(args.p2p.bootnode_mode);
+        assert!(args.p2p.bootnode_driver(&global).is_err());
+
+        let args = MockCommand::parse_from([
+            "test",
+            "--p2p.bootnode-mode",
+            "--p2p.no-bootstore",
+            "--p2p.priv.raw",
+            "1d2b0bda21d56b8bd12d4f94ebacffdfb35f5e226f84b461103bb8beab6353be",
+        ]);
+        assert!(args.p2p.bootnode_driver(&global).is_err());
+
+        let args = MockCommand::parse_from([
+            "test",
+            "--p2p.bootnode-mode",
+            "--p2p.no-bootstore",
+            "--p2p.priv.raw",
+            "1d2b0bda21d56b8bd12d4f94ebacffdfb35f5e226f84b461103bb8beab6353be",
+            "--p2p.advertise.ip",
+            "10.0.0.1",
+        ]);
+        let driver = args.p2p.bootnode_driver(&global).unwrap();
+        assert!(!driver.forward);
+        assert_eq!(driver.disc.local_enr().ip4(), Some("10.0.0.1".parse().unwrap()));
+
+        let err =
+            MockCommand::try_parse_from(["test", "--p2p.bootnode-mode", "--p2p.no-discovery"]);
+        assert!(err.is_err());
+    }
+
     #[test]
     fn test_p2p_args_keypair_from_path() {
         // Create a temporary directory.
@@ -540,6 +745,36 @@ mod tests {
         assert_eq!(args.p2p.signer.sequencer_key, Some(key));
     }
 
+    #[test]
+    fn test_p2p_args_sequencer_key_path() {
+        let path = std::env::temp_dir().join("sequencer.key");
+        std::fs::write(
+            &path,
+            "0xbcc617ea05150ff60490d3c6058630ba94ae9f12a02a87efd291349ca0e54e0a\n",
+        )
+        .unwrap();
+
+        let args =
+            MockCommand::parse_from(["test", "--p2p.sequencer.key.path", path.to_str().unwrap()]);
+        assert_eq!(args.p2p.signer.sequencer_key_path, Some(path.clone()));
+        let global = GlobalArgs { l2_chain_id: 10.into(), ..Default::default() };
+        assert!(matches!(
+            args.p2p.signer.config(&global),
+            Ok(Some(kona_sources::BlockSigner::Local(_)))
+        ));
+
+        assert!(
+            MockCommand::try_parse_from([
+                "test",
+                "--p2p.sequencer.key.path",
+                path.to_str().unwrap(),
+                "--p2p.sequencer.key",
+                "bcc617ea05150ff60490d3c6058630ba94ae9f12a02a87efd291349ca0e54e0a",
+            ])
+            .is_err()
+        );
+    }
+
     #[test]
     fn test_p2p_args_listen_ip() {
         let args = MockCommand::parse_from(["test", "--p2p.listen.ip", "127.0.0.1"]);
@@ -558,4 +793,85 @@ mod tests {
         let args = MockCommand::parse_from(["test", "--p2p.listen.udp", "1234"]);
         assert_eq!(args.p2p.listen_udp_port, 1234);
     }
+
+    #[test]
+    fn test_p2p_args_ban_invalid_signatures() {
+        let args = MockCommand::parse_from(["test"]);
+        assert_eq!(args.p2p.ban_invalid_signatures, 3);
+        assert_eq!(args.p2p.ban_store, None);
+        let args = MockCommand::parse_from([
+            "test",
+            "--p2p.ban.invalid-signatures",
+            "5",
+            "--p2p.ban.store",
+            "/tmp/banlist.json",
+        ]);
+        assert_eq!(args.p2p.ban_invalid_signatures, 5);
+        assert_eq!(args.p2p.ban_store, Some(PathBuf::from("/tmp/banlist.json")));
+    }
+
+    #[test]
+    fn test_p2p_args_nat() {
+        let args = MockCommand::parse_from(["test"]);
+        assert_eq!(args.p2p.nat, Nat::None);
+        let args = MockCommand::parse_from(["test", "--p2p.nat", "extip:1.2.3.4"]);
+        assert_eq!(args.p2p.nat, Nat::ExtIp("1.2.3.4".parse().unwrap()));
+        assert!(MockCommand::try_parse_from(["test", "--p2p.nat", "stun"]).is_err());
+    }
+
+    #[test]
+    fn test_p2p_args_static_and_trusted_peers() {
+        let args = MockCommand::parse_from(["test"]);
+        assert!(args.p2p.static_peers.is_empty());
+        assert!(args.p2p.trusted_peers.is_empty());
+
+        let peer_id = Keypair::generate_secp256k1().public().to_peer_id();
+        let addr = format!("/ip4/127.0.0.1/tcp/9222/p2p/{peer_id}");
+        let args = MockCommand::parse_from([
+            "test",
+            "--p2p.static",
+            &format!("{addr},/ip4/127.0.0.2/tcp/9222"),
+            "--p2p.trusted",
+            &peer_id.to_string(),
+        ]);
+        assert_eq!(args.p2p.static_peers.len(), 2);
+        assert_eq!(args.p2p.static_peers[0], addr.parse::<Multiaddr>().unwrap());
+        assert_eq!(args.p2p.trusted_peers, vec![peer_id]);
+    }
+
+    #[test]
+    fn test_p2p_args_publish_rate_limit() {
+        let args = MockCommand::parse_from(["test"]);
+        assert_eq!(args.p2p.publish_rate_limit, DEFAULT_PUBLISH_RATE_LIMIT);
+
+        let args = MockCommand::parse_from(["test", "--p2p.publish.rate-limit", "0"]);
+        assert_eq!(args.p2p.publish_rate_limit, 0);
+    }
+
+    #[test]
+    fn test_p2p_args_signer_rotation_grace() {
+        let args = MockCommand::parse_from(["test"]);
+        assert_eq!(args.p2p.signer_rotation_grace, 0);
+
+        let args = MockCommand::parse_from(["test", "--p2p.signer-rotation-grace", "300"]);
+        assert_eq!(args.p2p.signer_rotation_grace, 300);
+    }
+
+    #[test]
+    fn test_p2p_args_topic_score_weights() {
+        let args = MockCommand::parse_from(["test"]);
+        assert_eq!(args.p2p.topic_weight, TopicScoreWeights::DEFAULT.topic_weight);
+        assert_eq!(
+            args.p2p.invalid_message_weight,
+            TopicScoreWeights::DEFAULT.invalid_message_deliveries_weight
+        );
+        let args = MockCommand::parse_from([
+            "test",
+            "--p2p.scoring.topic-weight",
+            "0.5",
+            "--p2p.scoring.invalid-message-weight=-10",
+        ]);
+        assert_eq!(args.p2p.topic_weight, 0.5);
+        assert_eq!(args.p2p.invalid_message_weight, -10.0);
+    }
 }
@@ -3,10 +3,12 @@
 //! Flags for configuring the RPC server.
 
 use clap::Parser;
-use kona_rpc::RpcBuilder;
+use kona_rpc::{LogFilterReloader, ReadinessCriteria, RpcBuilder};
 use std::{
     net::{IpAddr, SocketAddr},
     path::PathBuf,
+    sync::Arc,
+    time::Duration,
 };
 
 /// RPC CLI Arguments
@@ -24,6 +26,18 @@ pub struct RpcArgs {
     /// RPC listening port.
     #[arg(long = "port", alias = "rpc.port", default_value = "9545", env = "KONA_NODE_RPC_PORT")]
     pub listen_port: u16,
+    /// Listening address of the separate RPC server for the admin, debug, dev and p2p management
+    /// namespaces. Only used if `--rpc.admin-port` is set.
+    #[arg(long = "rpc.admin-addr", default_value = "127.0.0.1", env = "KONA_NODE_RPC_ADMIN_ADDR")]
+    pub admin_addr: IpAddr,
+    /// Listening port of the separate RPC server for the admin, debug, dev and p2p management
+    /// namespaces. If set, these namespaces are no longer served on the main RPC port.
+    #[arg(long = "rpc.admin-port", env = "KONA_NODE_RPC_ADMIN_PORT")]
+    pub admin_port: Option<u16>,
+    /// Bearer token required in the `Authorization` header to call the methods of the admin,
+    /// debug, dev and p2p management namespaces. Read-only rollup methods stay public.
+    #[arg(long = "rpc.admin-token", env = "KONA_NODE_RPC_ADMIN_TOKEN")]
+    pub admin_token: Option<String>,
     /// Enable the admin API.
     #[arg(long = "rpc.enable-admin", env = "KONA_NODE_RPC_ENABLE_ADMIN")]
     pub enable_admin: bool,
@@ -37,6 +51,32 @@ pub struct RpcArgs {
     /// Enables development RPC endpoints for engine state introspection
     #[arg(long = "rpc.dev-enabled", default_value = "false", env = "KONA_NODE_RPC_DEV_ENABLED")]
     pub dev_enabled: bool,
+    /// The minimum number of connected peers for the node to pass the `/readyz` readiness check.
+    #[arg(
+        long = "rpc.readyz.min-peers",
+        default_value = "0",
+        env = "KONA_NODE_RPC_READYZ_MIN_PEERS"
+    )]
+    pub readyz_min_peers: usize,
+    /// Do not require the execution layer to be reachable to pass the `/readyz` readiness check.
+    #[arg(long = "rpc.readyz.skip-engine", env = "KONA_NODE_RPC_READYZ_SKIP_ENGINE")]
+    pub readyz_skip_engine: bool,
+    /// The maximum age of the L1 head, in seconds, for the node to pass the `/readyz` readiness
+    /// check. `0` disables the check.
+    #[arg(
+        long = "rpc.readyz.max-l1-head-age",
+        default_value = "120",
+        env = "KONA_NODE_RPC_READYZ_MAX_L1_HEAD_AGE"
+    )]
+    pub readyz_max_l1_head_age: u64,
+    /// The maximum age of the unsafe head, in seconds, for the node to pass the `/readyz`
+    /// readiness check. `0` disables the check.
+    #[arg(
+        long = "rpc.readyz.max-unsafe-head-age",
+        default_value = "60",
+        env = "KONA_NODE_RPC_READYZ_MAX_UNSAFE_HEAD_AGE"
+    )]
+    pub readyz_max_unsafe_head_age: u64,
 }
 
 impl Default for RpcArgs {
@@ -47,6 +87,16 @@ impl Default for RpcArgs {
     }
 }
 
+/// Reloads the filter of the tracing subscriber installed by [`kona_cli::LogConfig`].
+#[derive(Debug, Clone, Copy)]
+struct CliLogFilter;
+
+impl LogFilterReloader for CliLogFilter {
+    fn reload(&self, directives: &str) -> Result<(), String> {
+        kona_cli::reload_log_filter(directives).map_err(|e| e.to_string())
+    }
+}
+
 impl From<RpcArgs> for Option<RpcBuilder> {
     fn from(args: RpcArgs) -> Self {
         if args.rpc_disabled {
@@ -55,10 +105,22 @@ impl From<RpcArgs> for Option<RpcBuilder> {
         Some(RpcBuilder {
             no_restart: args.no_restart,
             socket: SocketAddr::new(args.listen_addr, args.listen_port),
+            admin_socket: args.admin_port.map(|port| SocketAddr::new(args.admin_addr, port)),
+            admin_token: args.admin_token,
             enable_admin: args.enable_admin,
             admin_persistence: args.admin_persistence,
             ws_enabled: args.ws_enabled,
             dev_enabled: args.dev_enabled,
+            version: crate::version::RPC_VERSION.to_string(),
+            readiness: ReadinessCriteria {
+                min_peers: args.readyz_min_peers,
+                require_engine: !args.readyz_skip_engine,
+                max_l1_head_age: (args.readyz_max_l1_head_age > 0)
+                    .then(|| Duration::from_secs(args.readyz_max_l1_head_age)),
+                max_unsafe_head_age: (args.readyz_max_unsafe_head_age > 0)
+                    .then(|| Duration::from_secs(args.readyz_max_unsafe_head_age)),
+            },
+            log_filter: Some(Arc::new(CliLogFilter)),
         })
     }
 }
@@ -77,6 +139,12 @@ mod tests {
     #[case::disable_rpc_alias(&["--rpc.port", "8743"], |args: &mut RpcArgs| { args.listen_port = 8743; })]
     #[case::disable_rpc(&["--rpc.enable-admin"], |args: &mut RpcArgs| { args.enable_admin = true; })]
     #[case::disable_rpc(&["--rpc.admin-state", "/"], |args: &mut RpcArgs| { args.admin_persistence = Some(PathBuf::from("/")); })]
+    #[case::admin_addr(&["--rpc.admin-addr", "1.1.1.1"], |args: &mut RpcArgs| { args.admin_addr = IpAddr::V4(Ipv4Addr::new(1, 1, 1, 1)); })]
+    #[case::admin_port(&["--rpc.admin-port", "9546"], |args: &mut RpcArgs| { args.admin_port = Some(9546); })]
+    #[case::admin_token(&["--rpc.admin-token", "secret"], |args: &mut RpcArgs| { args.admin_token = Some("secret".to_string()); })]
+    #[case::readyz_min_peers(&["--rpc.readyz.min-peers", "5"], |args: &mut RpcArgs| { args.readyz_min_peers = 5; })]
+    #[case::readyz_skip_engine(&["--rpc.readyz.skip-engine"], |args: &mut RpcArgs| { args.readyz_skip_engine = true; })]
+    #[case::readyz_max_unsafe_head_age(&["--rpc.readyz.max-unsafe-head-age", "0"], |args: &mut RpcArgs| { args.readyz_max_unsafe_head_age = 0; })]
     fn test_parse_rpc_args(#[case] args: &[&str], #[case] mutate: impl Fn(&mut RpcArgs)) {
         let args = [&["kona-node"], args].concat();
         let cli = RpcArgs::parse_from(args);
@@ -12,6 +12,15 @@ use url::Url;
 /// Sequencer CLI Flags
 #[derive(Parser, Clone, Debug, PartialEq, Eq)]
 pub struct SequencerArgs {
+    /// Enable sequencing of new L2 blocks. Equivalent to running the node with `--mode
+    /// Sequencer`.
+    #[arg(
+        long = "sequencer.enabled",
+        default_value = "false",
+        env = "KONA_NODE_SEQUENCER_ENABLED"
+    )]
+    pub enabled: bool,
+
     /// Initialize the sequencer in a stopped state. The sequencer can be started using the
     /// admin_startSequencer RPC.
     #[arg(
@@ -72,6 +81,7 @@ impl SequencerArgs {
             sequencer_stopped: self.stopped,
             sequencer_recovery_mode: self.recover,
             conductor_rpc_url: self.conductor_rpc.clone(),
+            conductor_rpc_timeout: self.conductor_rpc_timeout,
             l1_conf_delay: self.l1_confs,
         }
     }
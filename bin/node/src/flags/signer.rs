@@ -1,4 +1,4 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use alloy_primitives::{Address, B256};
 use alloy_signer::{Signer, k256::ecdsa};
@@ -21,6 +21,15 @@ pub struct SignerArgs {
         conflicts_with = "endpoint"
     )]
     pub sequencer_key: Option<B256>,
+    /// An optional path to a file containing the hex-encoded private key for the sequencer to
+    /// sign unsafe blocks. This is mutually exclusive with `p2p.sequencer.key` and
+    /// `p2p.signer.endpoint`.
+    #[arg(
+        long = "p2p.sequencer.key.path",
+        env = "KONA_NODE_P2P_SEQUENCER_KEY_PATH",
+        conflicts_with_all = ["sequencer_key", "endpoint"]
+    )]
+    pub sequencer_key_path: Option<PathBuf>,
     /// The URL of the remote signer endpoint. If not provided, remote signer will be disabled.
     /// This is mutually exclusive with `p2p.sequencer.key`.
     /// This is required if any of the other signer flags are provided.
@@ -74,6 +83,12 @@ pub enum SignerArgsParseError {
     /// The sequencer key is invalid.
     #[error("The sequencer key is invalid.")]
     SequencerKeyInvalid(#[from] ecdsa::Error),
+    /// The sequencer key file could not be read.
+    #[error("Failed to read the sequencer key file: {0}")]
+    SequencerKeyFile(#[from] std::io::Error),
+    /// The sequencer key file does not contain a hex-encoded 32-byte key.
+    #[error("The sequencer key file does not contain a hex-encoded 32-byte key.")]
+    SequencerKeyFileInvalid(#[from] alloy_primitives::hex::FromHexError),
     /// The address is required if `signer.endpoint` is provided.
     #[error("The address is required if `signer.endpoint` is provided.")]
     AddressRequired,
@@ -95,7 +110,12 @@ impl SignerArgs {
     /// Creates a [`BlockSigner`] from the [`SignerArgs`].
     pub fn config(self, args: &GlobalArgs) -> Result<Option<BlockSigner>, SignerArgsParseError> {
         // The sequencer signer obtained from the CLI arguments.
-        let gossip_signer: Option<BlockSigner> = match (self.sequencer_key, self.config_remote()?) {
+        let sequencer_key = match (self.sequencer_key, self.sequencer_key_path.as_ref()) {
+            (Some(key), _) => Some(key),
+            (None, Some(path)) => Some(Self::read_sequencer_key(path)?),
+            (None, None) => None,
+        };
+        let gossip_signer: Option<BlockSigner> = match (sequencer_key, self.config_remote()?) {
             (Some(_), Some(_)) => return Err(SignerArgsParseError::LocalAndRemoteSigner),
             (Some(key), None) => {
                 let signer: BlockSigner = PrivateKeySigner::from_bytes(&key)?
@@ -110,6 +130,12 @@ impl SignerArgs {
         Ok(gossip_signer)
     }
 
+    /// Reads the hex-encoded sequencer key from the file at the given path.
+    fn read_sequencer_key(path: &Path) -> Result<B256, SignerArgsParseError> {
+        let contents = std::fs::read_to_string(path)?;
+        Ok(B256::from_str(contents.trim())?)
+    }
+
     /// Creates a [`RemoteSigner`] from the [`SignerArgs`].
     fn config_remote(self) -> Result<Option<RemoteSigner>, SignerArgsParseError> {
         let Some(endpoint) = self.endpoint else {
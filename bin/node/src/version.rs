@@ -15,6 +15,11 @@ pub(crate) const VERGEN_CARGO_TARGET_TRIPLE: &str = env!("VERGEN_CARGO_TARGET_TR
 /// The build features.
 pub(crate) const VERGEN_CARGO_FEATURES: &str = env!("VERGEN_CARGO_FEATURES");
 
+/// The version served by the `optimism_version` RPC method, in the `v<version>-<short sha>` format
+/// of op-node.
+pub(crate) const RPC_VERSION: &str =
+    concat!("v", env!("CARGO_PKG_VERSION"), "-", env!("VERGEN_GIT_SHA_SHORT"));
+
 /// The short version information for kona-node.
 pub(crate) const SHORT_VERSION: &str = env!("KONA_NODE_SHORT_VERSION");
 
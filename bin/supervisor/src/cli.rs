@@ -5,6 +5,7 @@ use anyhow::Result;
 use clap::Parser;
 use kona_cli::{LogArgs, LogConfig, MetricsArgs, cli_styles};
 use kona_supervisor_service::Service;
+use std::sync::Arc;
 use tracing::{error, info};
 
 /// CLI for the Rust implementation of the OP Supervisor.
@@ -34,8 +35,9 @@ impl Cli {
         self.init_logs(&self.global)?;
 
         Self::run_until_ctrl_c(async move {
-            let config = self.supervisor.init_config().await?;
-            let mut service = Service::new(config);
+            let args = Arc::new(self.supervisor);
+            let config = args.init_config().await?;
+            let mut service = Service::new(config).with_config_loader(args);
 
             tokio::select! {
                 res = service.run() => {
@@ -2,6 +2,7 @@ use alloy_network::Ethereum;
 use alloy_provider::{Provider, RootProvider};
 use alloy_rpc_types_engine::JwtSecret;
 use anyhow::{Context as _, Ok, Result, anyhow};
+use async_trait::async_trait;
 use clap::Args;
 use glob::glob;
 use kona_genesis::RollupConfig;
@@ -11,6 +12,7 @@ use kona_supervisor_core::{
     config::{Config, RollupConfigSet},
     syncnode::ClientConfig,
 };
+use kona_supervisor_service::ConfigLoader;
 use serde::de::DeserializeOwned;
 use std::{
     net::{IpAddr, SocketAddr},
@@ -45,7 +47,7 @@ pub struct SupervisorArgs {
     #[arg(long = "datadir.sync-endpoint", env = "DATADIR_SYNC_ENDPOINT")]
     pub datadir_sync_endpoint: Option<String>,
 
-    /// Path to the dependency-set JSON config file.
+    /// Path to the dependency-set config file, in JSON or, with a `.toml` extension, TOML.
     #[arg(long = "dependency-set", env = "DEPENDENCY_SET")]
     pub dependency_set: PathBuf,
 
@@ -69,7 +71,7 @@ pub struct SupervisorArgs {
 }
 
 impl SupervisorArgs {
-    async fn read_json_file<T: DeserializeOwned>(path: &Path) -> Result<T> {
+    async fn read_file(path: &Path) -> Result<String> {
         let mut file = File::open(path)
             .await
             .with_context(|| format!("Failed to open '{}'", path.display()))?;
@@ -77,6 +79,11 @@ impl SupervisorArgs {
         file.read_to_string(&mut contents)
             .await
             .with_context(|| format!("Failed to read '{}'", path.display()))?;
+        Ok(contents)
+    }
+
+    async fn read_json_file<T: DeserializeOwned>(path: &Path) -> Result<T> {
+        let contents = Self::read_file(path).await?;
         let value = serde_json::from_str(&contents)
             .with_context(|| format!("Failed to parse JSON from '{}'", path.display()))?;
         Ok(value)
@@ -84,7 +91,15 @@ impl SupervisorArgs {
 
     /// initialise and return the [`DependencySet`].
     pub async fn init_dependency_set(&self) -> Result<DependencySet> {
-        Self::read_json_file(&self.dependency_set).await
+        let path = &self.dependency_set;
+        let contents = Self::read_file(path).await?;
+        let dependency_set = if path.extension().is_some_and(|extension| extension == "toml") {
+            DependencySet::from_toml(&contents)
+        } else {
+            DependencySet::from_json(&contents)
+        };
+        dependency_set
+            .with_context(|| format!("Failed to load dependency set '{}'", path.display()))
     }
 
     async fn get_rollup_configs(&self) -> Result<Vec<RollupConfig>> {
@@ -194,6 +209,13 @@ impl SupervisorArgs {
         let dependency_set = self.init_dependency_set().await?;
         let rollup_config_set = self.init_rollup_config_set().await?;
 
+        let interop_times = rollup_config_set
+            .rollups
+            .iter()
+            .map(|(chain_id, config)| (*chain_id, config.interop_time))
+            .collect();
+        dependency_set.validate(&interop_times).context("Invalid dependency set")?;
+
         let rpc_addr = SocketAddr::new(self.rpc_address, self.rpc_port);
         let managed_nodes_config = self.init_managed_nodes_config()?;
 
@@ -209,11 +231,19 @@ impl SupervisorArgs {
     }
 }
 
+/// Reloads the dependency set and rollup configs from the files the supervisor was started with.
+#[async_trait]
+impl ConfigLoader for SupervisorArgs {
+    async fn load_config(&self) -> Result<Config> {
+        self.init_config().await
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use clap::Parser;
-    use kona_interop::{ChainDependency, DependencySet};
+    use kona_interop::{ChainDependency, DependencySet, DependencySetError};
     use kona_registry::HashMap;
     use std::{fs::File, io::Write, net::Ipv4Addr};
     use tempfile::{NamedTempFile, tempdir};
@@ -338,8 +368,14 @@ mod tests {
 
         let loaded_depset = result.unwrap();
         let mut expected_dependencies = HashMap::default();
-        expected_dependencies.insert(1, ChainDependency {});
-        expected_dependencies.insert(2, ChainDependency {});
+        expected_dependencies.insert(
+            1,
+            ChainDependency { chain_index: Some(10), activation_time: Some(1678886400) },
+        );
+        expected_dependencies.insert(
+            2,
+            ChainDependency { chain_index: Some(20), activation_time: Some(1678886401) },
+        );
 
         let expected_depset = DependencySet {
             dependencies: expected_dependencies,
@@ -393,8 +429,11 @@ mod tests {
 
         let result = args.init_dependency_set().await;
         let err = result.expect_err("init_dependency_set should have failed due to invalid JSON");
-        let json_error = err.downcast_ref::<serde_json::Error>();
-        assert!(json_error.is_some(), "Error should be a serde_json::Error, but was: {err:?}");
+        let parse_error = err.downcast_ref::<DependencySetError>();
+        assert!(
+            matches!(parse_error, Some(DependencySetError::Parse(_))),
+            "Error should be a DependencySetError::Parse, but was: {err:?}"
+        );
         Ok(())
     }
 
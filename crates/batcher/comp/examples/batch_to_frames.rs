@@ -6,7 +6,7 @@
 //! Notice, the raw batch is first _encoded_.
 //! Once encoded, it is compressed into raw data that the channel is constructed with.
 //!
-//! The [ChannelOut] then outputs frames individually using the maximum frame size,
+//! The [ChannelOut] is then closed, and outputs its frames in order using the maximum frame size,
 //! in this case hardcoded to 100, to construct the frames.
 //!
 //! Finally, once [Frame]s are built from the [ChannelOut], they are encoded and ready
@@ -39,16 +39,14 @@ fn main() {
     // Add the compressed batch to the `ChannelOut`.
     channel_out.add_batch(batch).unwrap();
 
-    // Output frames
-    while channel_out.ready_bytes() > 0 {
-        let frame = channel_out.output_frame(100).expect("outputs frame");
+    // Close the channel and output its frames.
+    let frames = channel_out.frames(100).expect("outputs frames");
+    for frame in &frames {
         println!("Frame: {}", alloy_primitives::hex::encode(frame.encode()));
-        if channel_out.ready_bytes() <= 100 {
-            channel_out.close();
-        }
     }
 
     assert!(channel_out.closed);
+    assert!(frames.last().is_some_and(|frame| frame.is_last));
     println!("Successfully encoded Batch to frames");
 }
 
@@ -1,6 +1,7 @@
 //! Contains brotli compression utilities.
 
 use crate::{ChannelCompressor, CompressorError, CompressorResult, CompressorWriter};
+use kona_protocol::BatchReader;
 use std::vec::Vec;
 
 /// The brotli encoding level used in Optimism.
@@ -125,8 +126,12 @@ impl CompressorWriter for BrotliCompressor {
 }
 
 impl ChannelCompressor for BrotliCompressor {
-    fn get_compressed(&self) -> Vec<u8> {
-        self.compressed.clone()
+    fn compressed(&self) -> &[u8] {
+        &self.compressed
+    }
+
+    fn channel_version(&self) -> Option<u8> {
+        Some(BatchReader::CHANNEL_VERSION_BROTLI)
     }
 }
 
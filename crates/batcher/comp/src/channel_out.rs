@@ -1,10 +1,15 @@
 //! Contains the `ChannelOut` primitive for Optimism.
 
 use crate::{ChannelCompressor, CompressorError};
-use alloc::{vec, vec::Vec};
+use alloc::{
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
 use kona_genesis::RollupConfig;
-use kona_protocol::{Batch, ChannelId, Frame};
-use rand::{RngCore, SeedableRng, rngs::SmallRng};
+use kona_protocol::{Batch, ChannelId, Frame, SingleBatch, random_channel_id};
+use op_alloy_consensus::OpBlock;
+use rand::{SeedableRng, rngs::SmallRng};
 
 /// The frame overhead.
 const FRAME_V0_OVERHEAD: usize = 23;
@@ -30,9 +35,21 @@ pub enum ChannelOutError {
     /// The encoded batch exceeds the max RLP bytes per channel.
     #[error("The encoded batch exceeds the max RLP bytes per channel")]
     ExceedsMaxRlpBytesPerChannel,
+    /// The L2 block could not be converted to a batch.
+    #[error("Error converting the block to a batch: {0}")]
+    BlockConversion(String),
 }
 
 /// [ChannelOut] constructs a channel from compressed, encoded batch data.
+///
+/// L2 blocks or batches are added to the channel, which RLP encodes and compresses them exactly as
+/// the [BatchReader] of the derivation pipeline expects to decode them. The channel is then split
+/// into ordered [Frame]s, the last of which is marked once the channel is closed.
+///
+/// Compressors re-compress their whole input on every write, so the contents of a channel are
+/// fixed once its first frame is output: batches can no longer be added after that.
+///
+/// [BatchReader]: kona_protocol::BatchReader
 #[allow(missing_debug_implementations)]
 pub struct ChannelOut<'a, C>
 where
@@ -50,6 +67,8 @@ where
     pub closed: bool,
     /// The frame number.
     pub frame_number: u16,
+    /// The number of bytes of channel data already output to frames.
+    pub emitted: usize,
     /// The compressor.
     pub compressor: C,
 }
@@ -60,32 +79,42 @@ where
 {
     /// Creates a new [ChannelOut] with the given [ChannelId].
     pub const fn new(id: ChannelId, config: &'a RollupConfig, compressor: C) -> Self {
-        Self { id, config, rlp_length: 0, frame_number: 0, closed: false, compressor }
+        Self { id, config, rlp_length: 0, frame_number: 0, emitted: 0, closed: false, compressor }
     }
 
     /// Resets the [ChannelOut] to its initial state.
     pub fn reset(&mut self) {
         self.rlp_length = 0;
         self.frame_number = 0;
+        self.emitted = 0;
         self.closed = false;
         self.compressor.reset();
         // `getrandom` isn't available for wasm and risc targets
         // Thread-based RNGs are not available for no_std
         // So we must use a seeded RNG.
         let mut small_rng = SmallRng::seed_from_u64(43);
-        SmallRng::fill_bytes(&mut small_rng, &mut self.id);
+        self.id = random_channel_id(&mut small_rng);
+    }
+
+    /// Accepts the given L2 [OpBlock] into the [ChannelOut], as the [SingleBatch] it is derived
+    /// from.
+    pub fn add_block(&mut self, block: &OpBlock) -> Result<(), ChannelOutError> {
+        let batch = SingleBatch::from_block(block)
+            .map_err(|err| ChannelOutError::BlockConversion(err.to_string()))?;
+        self.add_batch(Batch::Single(batch))
     }
 
     /// Accepts the given [Batch] data into the [ChannelOut], compressing it
     /// into frames.
     pub fn add_batch(&mut self, batch: Batch) -> Result<(), ChannelOutError> {
-        if self.closed {
+        if self.closed || self.frame_number > 0 {
             return Err(ChannelOutError::ChannelClosed);
         }
 
-        // Encode the batch.
-        let mut buf = vec![];
-        batch.encode(&mut buf).map_err(|_| ChannelOutError::BatchEncoding)?;
+        // Encode the batch, as an RLP string of its typed encoding.
+        let mut encoded = vec![];
+        batch.encode(&mut encoded).map_err(|_| ChannelOutError::BatchEncoding)?;
+        let buf = alloy_rlp::encode(encoded.as_slice());
 
         // Validate that the RLP length is within the channel's limits.
         let max_rlp_bytes_per_channel = self.config.max_rlp_bytes_per_channel(batch.timestamp());
@@ -94,6 +123,7 @@ where
         }
 
         self.compressor.write(&buf)?;
+        self.rlp_length += buf.len() as u64;
 
         Ok(())
     }
@@ -105,7 +135,16 @@ where
 
     /// Returns the number of bytes ready to be output to a frame.
     pub fn ready_bytes(&self) -> usize {
-        self.compressor.len()
+        self.data_len().saturating_sub(self.emitted)
+    }
+
+    /// Returns the length of the channel data: the compressed batches, prefixed with the channel
+    /// version of the compressor, if any.
+    fn data_len(&self) -> usize {
+        match self.compressor.len() {
+            0 => 0,
+            len => len + self.compressor.channel_version().map_or(0, |_| 1),
+        }
     }
 
     /// Flush the internal compressor.
@@ -119,37 +158,73 @@ where
         self.closed = true;
     }
 
-    /// Outputs a [Frame] from the [ChannelOut].
+    /// Outputs the next [Frame] of the [ChannelOut], of at most `max_size` encoded bytes.
+    ///
+    /// Once the channel is closed, the frame that outputs the remaining channel data is marked
+    /// as the last frame.
     pub fn output_frame(&mut self, max_size: usize) -> Result<Frame, ChannelOutError> {
         if max_size < FRAME_V0_OVERHEAD {
             return Err(ChannelOutError::MaxFrameSizeTooSmall);
         }
 
-        // Construct an empty frame.
-        let mut frame =
-            Frame { id: self.id, number: self.frame_number, is_last: self.closed, data: vec![] };
-
-        let mut max_size = max_size - FRAME_V0_OVERHEAD;
-        if max_size > self.ready_bytes() {
-            max_size = self.ready_bytes();
+        // Slice the next bytes of the channel data out of the compressor buffer, after the
+        // channel version prefix if any.
+        let data_len = self.data_len();
+        let start = self.emitted.min(data_len);
+        let end = data_len.min(start + max_size - FRAME_V0_OVERHEAD);
+        let compressed = self.compressor.compressed();
+        let prefix = data_len.saturating_sub(compressed.len());
+
+        let mut data = Vec::with_capacity(end - start);
+        if start < prefix && start < end {
+            data.extend(self.compressor.channel_version());
         }
+        data.extend_from_slice(
+            &compressed[start.saturating_sub(prefix)..end.saturating_sub(prefix)],
+        );
+        let frame = Frame {
+            id: self.id,
+            number: self.frame_number,
+            is_last: self.closed && end == data_len,
+            data,
+        };
 
-        // Read `max_size` bytes from the compressed data.
-        let mut data = Vec::with_capacity(max_size);
-        self.compressor.read(&mut data).map_err(ChannelOutError::Compression)?;
-        frame.data.extend_from_slice(data.as_slice());
-
-        // Update the compressed data.
+        self.emitted = end;
         self.frame_number += 1;
         Ok(frame)
     }
+
+    /// Closes the channel and outputs all of its remaining [Frame]s, in order, each of at most
+    /// `max_size` encoded bytes. The last frame is marked as such.
+    pub fn frames(&mut self, max_size: usize) -> Result<Vec<Frame>, ChannelOutError> {
+        // Frames must carry data for the channel to be drained.
+        if max_size <= FRAME_V0_OVERHEAD {
+            return Err(ChannelOutError::MaxFrameSizeTooSmall);
+        }
+
+        self.close();
+        let mut frames = Vec::new();
+        loop {
+            let frame = self.output_frame(max_size)?;
+            let is_last = frame.is_last;
+            frames.push(frame);
+            if is_last {
+                return Ok(frames);
+            }
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{CompressorWriter, test_utils::MockCompressor};
-    use kona_protocol::{SingleBatch, SpanBatch};
+    use crate::{CompressorWriter, ZlibCompressor, test_utils::MockCompressor};
+    use alloy_consensus::{BlockBody, Header, SignableTransaction, TxEip1559};
+    use alloy_primitives::{B256, Bytes, Sealed, Signature};
+    use kona_protocol::{
+        BatchReader, BlockInfo, Channel, L1BlockInfoBedrock, L1BlockInfoTx, SpanBatch,
+    };
+    use op_alloy_consensus::{OpTxEnvelope, TxDeposit};
 
     #[test]
     fn test_output_frame_max_size_too_small() {
@@ -166,8 +241,41 @@ mod tests {
             &config,
             MockCompressor { read_error: true, compressed: Some(Default::default()) },
         );
-        let err = channel.output_frame(FRAME_V0_OVERHEAD).unwrap_err();
-        assert_eq!(err, ChannelOutError::Compression(CompressorError::Full));
+        let frame = channel.output_frame(FRAME_V0_OVERHEAD + 8).unwrap();
+        assert!(frame.data.is_empty());
+        assert_eq!(channel.emitted, 0);
+    }
+
+    #[test]
+    fn test_channel_out_output_frame_slices_compressed_data() {
+        let config = RollupConfig::default();
+        // The frames are sliced out of the compressor buffer, which is never read.
+        let mut channel = ChannelOut::new(
+            ChannelId::default(),
+            &config,
+            MockCompressor { read_error: true, compressed: Some(Bytes::from_static(&[1, 2, 3])) },
+        );
+        channel.close();
+
+        let frame = channel.output_frame(FRAME_V0_OVERHEAD + 2).unwrap();
+        assert_eq!((frame.data.as_slice(), frame.is_last), (&[1, 2][..], false));
+        let frame = channel.output_frame(FRAME_V0_OVERHEAD + 2).unwrap();
+        assert_eq!((frame.data.as_slice(), frame.is_last), (&[3][..], true));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_channel_out_output_frame_versioned() {
+        let config = RollupConfig::default();
+        let mut compressor = crate::BrotliCompressor::new(crate::BrotliLevel::Brotli10);
+        compressor.write(&[0xab; 64]).unwrap();
+        let mut data = vec![BatchReader::CHANNEL_VERSION_BROTLI];
+        data.extend_from_slice(compressor.compressed());
+
+        let mut channel = ChannelOut::new(ChannelId::default(), &config, compressor);
+        let frames = channel.frames(FRAME_V0_OVERHEAD + 3).unwrap();
+        assert!(frames.iter().all(|frame| frame.data.len() <= 3));
+        assert_eq!(frames.into_iter().flat_map(|frame| frame.data).collect::<Vec<_>>(), data);
     }
 
     #[test]
@@ -193,11 +301,13 @@ mod tests {
             rlp_length: 10,
             closed: true,
             frame_number: 11,
+            emitted: 12,
             compressor: MockCompressor::default(),
         };
         channel.reset();
         assert_eq!(channel.rlp_length, 0);
         assert_eq!(channel.frame_number, 0);
+        assert_eq!(channel.emitted, 0);
         // The odds of a randomized channel id being equal to the
         // default are so astronomically low, this test will always pass.
         // The randomized [u8; 16] is about 1/255^16.
@@ -268,4 +378,91 @@ mod tests {
         let batch = Batch::Single(SingleBatch::default());
         assert_eq!(channel.add_batch(batch), Ok(()));
     }
+
+    #[test]
+    fn test_channel_out_add_batch_after_output() {
+        let config = RollupConfig::default();
+        let mut channel = ChannelOut::new(ChannelId::default(), &config, ZlibCompressor::new());
+        channel.add_batch(Batch::Single(SingleBatch::default())).unwrap();
+        channel.output_frame(FRAME_V0_OVERHEAD + 1).unwrap();
+
+        let batch = Batch::Single(SingleBatch::default());
+        assert_eq!(channel.add_batch(batch), Err(ChannelOutError::ChannelClosed));
+    }
+
+    #[test]
+    fn test_channel_out_frames_max_size_too_small() {
+        let config = RollupConfig::default();
+        let mut channel = ChannelOut::new(ChannelId::default(), &config, ZlibCompressor::new());
+        assert_eq!(channel.frames(FRAME_V0_OVERHEAD), Err(ChannelOutError::MaxFrameSizeTooSmall));
+    }
+
+    #[test]
+    fn test_channel_out_empty_frames() {
+        let config = RollupConfig::default();
+        let mut channel = ChannelOut::new(ChannelId::default(), &config, ZlibCompressor::new());
+        let frames = channel.frames(100).unwrap();
+        assert_eq!(frames.len(), 1);
+        assert!(frames[0].is_last);
+        assert!(frames[0].data.is_empty());
+    }
+
+    fn l2_block(number: u64, timestamp: u64) -> OpBlock {
+        let l1_info = L1BlockInfoTx::Bedrock(L1BlockInfoBedrock {
+            number,
+            block_hash: B256::with_last_byte(number as u8),
+            ..Default::default()
+        });
+        let deposit = OpTxEnvelope::Deposit(Sealed::new(TxDeposit {
+            input: l1_info.encode_calldata(),
+            ..Default::default()
+        }));
+        let tx = TxEip1559 { chain_id: 10, nonce: number, ..Default::default() };
+        let tx = OpTxEnvelope::Eip1559(tx.into_signed(Signature::test_signature()));
+        OpBlock {
+            header: Header { timestamp, ..Default::default() },
+            body: BlockBody { transactions: vec![deposit, tx], ..Default::default() },
+        }
+    }
+
+    /// Submits L2 blocks through a [ChannelOut], and reads them back from its frames as the
+    /// derivation pipeline does.
+    fn round_trip<C: ChannelCompressor>(compressor: C) {
+        let config = RollupConfig::default();
+        let blocks = (1..=8).map(|number| l2_block(number, number * 2)).collect::<Vec<_>>();
+
+        let mut channel_out = ChannelOut::new(ChannelId::from([7; 16]), &config, compressor);
+        for block in &blocks {
+            channel_out.add_block(block).unwrap();
+        }
+        let frames = channel_out.frames(FRAME_V0_OVERHEAD + 64).unwrap();
+        assert!(frames.len() > 1);
+        assert!(frames.iter().enumerate().all(|(i, frame)| frame.number == i as u16));
+        assert!(frames.iter().rev().skip(1).all(|frame| !frame.is_last));
+
+        let mut channel = Channel::new(ChannelId::from([7; 16]), BlockInfo::default());
+        for frame in frames {
+            channel.add_frame(frame, BlockInfo::default()).unwrap();
+        }
+        assert!(channel.is_ready());
+
+        let max_rlp_bytes = config.max_rlp_bytes_per_channel(0) as usize;
+        let mut reader = BatchReader::new(channel.frame_data().unwrap(), max_rlp_bytes);
+        for block in &blocks {
+            let expected = SingleBatch::from_block(block).unwrap();
+            assert_eq!(reader.next_batch(&config), Some(Batch::Single(expected)));
+        }
+        assert_eq!(reader.next_batch(&config), None);
+    }
+
+    #[test]
+    fn test_channel_out_round_trip_zlib() {
+        round_trip(ZlibCompressor::new());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_channel_out_round_trip_brotli() {
+        round_trip(crate::BrotliCompressor::new(crate::BrotliLevel::Brotli10));
+    }
 }
@@ -44,5 +44,10 @@ mod ratio;
 #[cfg(feature = "std")]
 pub use ratio::RatioCompressor;
 
+#[cfg(feature = "std")]
+mod sizer;
+#[cfg(feature = "std")]
+pub use sizer::{BatchSizer, SizeEstimate};
+
 #[cfg(any(test, feature = "test-utils"))]
 pub mod test_utils;
@@ -0,0 +1,202 @@
+//! Contains the [BatchSizer], which estimates the compressed size of a channel incrementally.
+
+use crate::{CompressionAlgo, CompressorError, CompressorResult, compress_brotli, compress_zlib};
+use std::vec::Vec;
+
+/// Bounds on the compressed size of the data appended to a [BatchSizer].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SizeEstimate {
+    /// A lower bound on the compressed size.
+    pub lower: usize,
+    /// An upper bound on the compressed size.
+    pub upper: usize,
+}
+
+impl SizeEstimate {
+    /// Returns whether the estimate is the exact compressed size.
+    pub const fn is_exact(&self) -> bool {
+        self.lower == self.upper
+    }
+}
+
+/// Batch Sizer
+///
+/// Estimates the compressed size of a channel as encoded batches (or any other data) are appended
+/// to it, without compressing the whole channel on every append.
+///
+/// Every appended chunk is compressed on its own, which costs time proportional to the chunk
+/// rather than to the channel. The compressed size of the channel is then bounded by:
+///
+/// - below, by the exact compressed size of the data covered by the last full compression, since
+///   appending data does not shrink the compressed output.
+/// - above, by that exact size plus the sizes of the chunks compressed on their own since, since
+///   the chunks compress at least as well alongside the preceding data, and each chunk compressed
+///   on its own carries its own stream header and trailer.
+///
+/// The channel is only compressed in full when the bounds are too loose to decide whether it fits
+/// a target size, see [BatchSizer::fits], which tightens the bounds to the exact size.
+#[derive(Debug, Clone)]
+pub struct BatchSizer {
+    /// The compression algorithm.
+    algo: CompressionAlgo,
+    /// The uncompressed data.
+    raw: Vec<u8>,
+    /// The exact compressed size of the data covered by the last full compression.
+    compressed_len: usize,
+    /// The sum of the compressed sizes of the chunks appended since the last full compression.
+    pending_len: usize,
+}
+
+impl BatchSizer {
+    /// Creates a new [BatchSizer] for the given [CompressionAlgo].
+    pub const fn new(algo: CompressionAlgo) -> Self {
+        Self { algo, raw: Vec::new(), compressed_len: 0, pending_len: 0 }
+    }
+
+    /// Appends the given data, such as an encoded [Batch][kona_protocol::Batch], and returns the
+    /// updated [SizeEstimate].
+    pub fn append(&mut self, data: &[u8]) -> CompressorResult<SizeEstimate> {
+        self.pending_len += self.compressed_size(data)?;
+        self.raw.extend_from_slice(data);
+        Ok(self.estimate())
+    }
+
+    /// Returns the current bounds on the compressed size of the appended data.
+    pub const fn estimate(&self) -> SizeEstimate {
+        SizeEstimate { lower: self.compressed_len, upper: self.compressed_len + self.pending_len }
+    }
+
+    /// Compresses the appended data in full, returning its exact compressed size. The bounds are
+    /// tightened to the exact size until more data is appended.
+    pub fn exact_size(&mut self) -> CompressorResult<usize> {
+        if self.pending_len > 0 {
+            self.compressed_len = self.compressed_size(&self.raw)?;
+            self.pending_len = 0;
+        }
+        Ok(self.compressed_len)
+    }
+
+    /// Returns whether the compressed data fits the given target size.
+    ///
+    /// The appended data is only compressed in full if the target lies between the bounds of the
+    /// current [SizeEstimate].
+    pub fn fits(&mut self, target: usize) -> CompressorResult<bool> {
+        let estimate = self.estimate();
+        if estimate.upper <= target {
+            return Ok(true);
+        }
+        if estimate.lower > target {
+            return Ok(false);
+        }
+        Ok(self.exact_size()? <= target)
+    }
+
+    /// Returns the length of the uncompressed data.
+    pub fn raw_len(&self) -> usize {
+        self.raw.len()
+    }
+
+    /// Resets the [BatchSizer], discarding the appended data.
+    pub fn reset(&mut self) {
+        self.raw.clear();
+        self.compressed_len = 0;
+        self.pending_len = 0;
+    }
+
+    /// Returns the compressed size of the given data.
+    fn compressed_size(&self, data: &[u8]) -> CompressorResult<usize> {
+        match self.algo {
+            CompressionAlgo::Zlib => Ok(compress_zlib(data).len()),
+            level => compress_brotli(data, level.into())
+                .map(|compressed| compressed.len())
+                .map_err(|_| CompressorError::Brotli),
+        }
+    }
+}
+
+impl From<CompressionAlgo> for BatchSizer {
+    fn from(algo: CompressionAlgo) -> Self {
+        Self::new(algo)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BrotliLevel;
+    use rand::{Rng, SeedableRng, rngs::StdRng};
+
+    /// Returns chunks of partially compressible data, resembling encoded transactions.
+    fn chunks() -> Vec<Vec<u8>> {
+        let mut rng = StdRng::seed_from_u64(42);
+        (0..64)
+            .map(|i| {
+                let mut chunk = vec![i as u8; 64];
+                chunk.extend((0..128).map(|_| rng.random::<u8>()));
+                chunk
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_estimate_bounds_exact_size() {
+        for algo in [CompressionAlgo::Zlib, CompressionAlgo::Brotli10] {
+            let mut sizer = BatchSizer::new(algo);
+            let mut raw = Vec::new();
+            for chunk in chunks() {
+                let estimate = sizer.append(&chunk).unwrap();
+                raw.extend_from_slice(&chunk);
+
+                let exact = match algo {
+                    CompressionAlgo::Zlib => compress_zlib(&raw).len(),
+                    _ => compress_brotli(&raw, BrotliLevel::Brotli10).unwrap().len(),
+                };
+                assert!(estimate.lower <= exact && exact <= estimate.upper);
+            }
+            assert_eq!(sizer.raw_len(), raw.len());
+        }
+    }
+
+    #[test]
+    fn test_exact_size_tightens_bounds() {
+        let mut sizer = BatchSizer::new(CompressionAlgo::Zlib);
+        for chunk in chunks() {
+            sizer.append(&chunk).unwrap();
+        }
+        assert!(!sizer.estimate().is_exact());
+
+        let exact = sizer.exact_size().unwrap();
+        assert_eq!(sizer.estimate(), SizeEstimate { lower: exact, upper: exact });
+
+        let estimate = sizer.append(&[0; 32]).unwrap();
+        assert_eq!(estimate.lower, exact);
+        assert!(estimate.upper > exact);
+    }
+
+    #[test]
+    fn test_fits() {
+        let mut sizer = BatchSizer::new(CompressionAlgo::Zlib);
+        for chunk in chunks() {
+            sizer.append(&chunk).unwrap();
+        }
+
+        let estimate = sizer.estimate();
+        assert!(sizer.fits(estimate.upper).unwrap());
+        assert!(!sizer.estimate().is_exact());
+
+        let exact = sizer.exact_size().unwrap();
+        sizer.append(&[0; 32]).unwrap();
+        assert!(!sizer.fits(exact - 1).unwrap());
+        assert!(!sizer.estimate().is_exact());
+        assert!(sizer.fits(exact + 32).unwrap());
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut sizer = BatchSizer::from(CompressionAlgo::Zlib);
+        sizer.append(&[1; 128]).unwrap();
+        sizer.reset();
+        assert_eq!(sizer.raw_len(), 0);
+        assert_eq!(sizer.estimate(), SizeEstimate::default());
+    }
+}
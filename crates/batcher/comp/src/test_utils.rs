@@ -1,7 +1,6 @@
 //! Test Utilities for the compression crate.
 
 use crate::{ChannelCompressor, CompressorError, CompressorResult, CompressorWriter};
-use alloc::vec::Vec;
 use alloy_primitives::Bytes;
 
 /// A Mock compressor for testing.
@@ -48,7 +47,7 @@ impl CompressorWriter for MockCompressor {
 }
 
 impl ChannelCompressor for MockCompressor {
-    fn get_compressed(&self) -> Vec<u8> {
-        self.compressed.as_ref().unwrap().to_vec()
+    fn compressed(&self) -> &[u8] {
+        self.compressed.as_deref().unwrap_or_default()
     }
 }
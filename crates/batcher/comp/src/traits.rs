@@ -34,6 +34,21 @@ pub trait CompressorWriter {
 ///
 /// A compressor for channels.
 pub trait ChannelCompressor: CompressorWriter {
-    /// Returns the compressed data buffer.
-    fn get_compressed(&self) -> Vec<u8>;
+    /// Returns a view of the compressed data buffer.
+    fn compressed(&self) -> &[u8];
+
+    /// Returns a copy of the compressed data buffer.
+    fn get_compressed(&self) -> Vec<u8> {
+        self.compressed().to_vec()
+    }
+
+    /// Returns the version byte that prefixes the compressed data in a channel, if any.
+    ///
+    /// Zlib streams are identified by their own header, while brotli streams are prefixed with
+    /// [BatchReader::CHANNEL_VERSION_BROTLI].
+    ///
+    /// [BatchReader::CHANNEL_VERSION_BROTLI]: kona_protocol::BatchReader::CHANNEL_VERSION_BROTLI
+    fn channel_version(&self) -> Option<u8> {
+        None
+    }
 }
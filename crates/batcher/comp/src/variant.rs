@@ -72,10 +72,17 @@ impl CompressorWriter for VariantCompressor {
 }
 
 impl ChannelCompressor for VariantCompressor {
-    fn get_compressed(&self) -> Vec<u8> {
+    fn compressed(&self) -> &[u8] {
         match self {
-            Self::Brotli(compressor) => compressor.get_compressed(),
-            Self::Zlib(compressor) => compressor.get_compressed(),
+            Self::Brotli(compressor) => compressor.compressed(),
+            Self::Zlib(compressor) => compressor.compressed(),
+        }
+    }
+
+    fn channel_version(&self) -> Option<u8> {
+        match self {
+            Self::Brotli(compressor) => compressor.channel_version(),
+            Self::Zlib(compressor) => compressor.channel_version(),
         }
     }
 }
@@ -67,7 +67,7 @@ impl CompressorWriter for ZlibCompressor {
 }
 
 impl ChannelCompressor for ZlibCompressor {
-    fn get_compressed(&self) -> Vec<u8> {
-        self.compressed.clone()
+    fn compressed(&self) -> &[u8] {
+        &self.compressed
     }
 }
@@ -1,6 +1,6 @@
 //! Contains a builder for the discovery service.
 
-use discv5::{Config, Discv5, Enr, enr::k256};
+use discv5::{Config, Discv5, Enr, ListenConfig, enr::k256};
 use kona_peers::{BootStoreFile, OpStackEnr};
 use std::net::IpAddr;
 use tokio::time::Duration;
@@ -155,6 +155,15 @@ impl Discv5Builder {
         self
     }
 
+    /// Returns the UDP port the discovery service listens on. For dual-stack configs, this is the
+    /// IPv4 port.
+    pub const fn listen_port(&self) -> u16 {
+        match &self.discovery_config.listen_config {
+            ListenConfig::Ipv4 { port, .. } | ListenConfig::Ipv6 { port, .. } => *port,
+            ListenConfig::DualStack { ipv4_port, .. } => *ipv4_port,
+        }
+    }
+
     /// Disables forwarding of the initial set of valid ENRs to the gossip layer.
     pub const fn disable_forward(mut self) -> Self {
         self.forward = false;
@@ -188,7 +197,7 @@ impl Discv5Builder {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use discv5::{ConfigBuilder, ListenConfig, enr::CombinedKey};
+    use discv5::{ConfigBuilder, enr::CombinedKey};
     use kona_peers::EnrValidation;
     use std::net::{IpAddr, Ipv4Addr};
 
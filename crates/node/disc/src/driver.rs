@@ -5,6 +5,7 @@ use derive_more::Debug;
 use discv5::{Config, Discv5, Enr, enr::NodeId};
 use kona_peers::{BootNode, BootNodes, BootStore, BootStoreFile, EnrValidation, enr_to_multiaddr};
 use libp2p::Multiaddr;
+use std::net::SocketAddr;
 use tokio::{
     sync::mpsc::channel,
     time::{Duration, sleep},
@@ -349,9 +350,23 @@ impl Discv5Driver {
                                         }
                                     }
                                 },
+                                HandlerRequest::SetExternalAddr(external) => {
+                                    let tcp = SocketAddr::new(external.ip, external.tcp_port);
+                                    let udp = SocketAddr::new(external.ip, external.udp_port);
+                                    let tcp_updated = self.disc.update_local_enr_socket(tcp, true);
+                                    let udp_updated = self.disc.update_local_enr_socket(udp, false);
+                                    debug!(target: "discovery", ?external, tcp_updated, udp_updated, "Updated the local ENR with the external address");
+                                },
                             }
                             None => {
-                                trace!(target: "discovery", "Receiver `None` peer enr");
+                                // The handler was dropped: the node is shutting down.
+                                info!(target: "discovery", "Discovery handler dropped, shutting down discovery");
+                                self.store.merge(self.disc.table_entries_enr());
+                                if let Err(e) = self.store.sync() {
+                                    warn!(target: "discovery", "Failed to sync bootstore: {:?}", e);
+                                }
+                                self.disc.shutdown();
+                                return;
                             }
                         }
                     }
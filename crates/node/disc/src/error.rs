@@ -13,3 +13,32 @@ pub enum Discv5BuilderError {
     #[error("failed to build ENR")]
     EnrBuildFailed,
 }
+
+/// An error that can occur while traversing the NAT.
+#[derive(Debug, Error)]
+pub enum NatError {
+    /// The NAT traversal mode could not be parsed.
+    #[error("invalid NAT mode {0}, expected one of none, any, upnp, pmp[:<gateway>] or extip:<ip>")]
+    InvalidMode(String),
+    /// NAT traversal is disabled.
+    #[error("NAT traversal is disabled")]
+    Disabled,
+    /// No gateway could be found for the local network.
+    #[error("no NAT gateway found")]
+    NoGateway,
+    /// A UPnP request failed.
+    #[error("UPnP error: {0}")]
+    Upnp(String),
+    /// The NAT-PMP gateway returned an error result code.
+    #[error("NAT-PMP error result code {0}")]
+    Pmp(u16),
+    /// The NAT-PMP gateway returned a malformed response.
+    #[error("invalid NAT-PMP response")]
+    InvalidResponse,
+    /// The NAT-PMP gateway did not respond.
+    #[error("NAT-PMP request timed out")]
+    Timeout,
+    /// An I/O error occurred.
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+}
@@ -1,5 +1,6 @@
 //! Handler to the [`discv5::Discv5`] service spawned in a thread.
 
+use crate::ExternalAddr;
 use discv5::{Enr, RequestError, enr::NodeId, kbucket::NodeStatus, metrics::Metrics};
 use libp2p::Multiaddr;
 use std::{collections::HashSet, string::String, sync::Arc, time::Duration};
@@ -69,6 +70,12 @@ pub enum HandlerRequest {
         /// Duration for which the addresses should be banned.
         ban_duration: Duration,
     },
+
+    /// Set the external address advertised in the local ENR.
+    ///
+    /// Sent by the [`crate::NatService`] once the gossip and discovery ports are mapped on the
+    /// NAT gateway, so that peers outside the NAT can dial the node.
+    SetExternalAddr(ExternalAddr),
 }
 
 /// Handler to the spawned [`discv5::Discv5`] service.
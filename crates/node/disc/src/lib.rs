@@ -16,6 +16,7 @@
 //! - [`Discv5Builder`]: Builder pattern for configuring discovery service parameters
 //! - [`Discv5Handler`]: Handle for interacting with the discovery service
 //! - [`LocalNode`]: Represents the local node's discovery information
+//! - [`NatService`]: Maps ports on the NAT gateway and advertises the external address
 //!
 //! ## Discovery Process
 //!
@@ -60,7 +61,7 @@ mod builder;
 pub use builder::{Discv5Builder, LocalNode};
 
 mod error;
-pub use error::Discv5BuilderError;
+pub use error::{Discv5BuilderError, NatError};
 
 mod driver;
 pub use driver::Discv5Driver;
@@ -68,5 +69,8 @@ pub use driver::Discv5Driver;
 mod handler;
 pub use handler::{Discv5Handler, HandlerRequest};
 
+mod nat;
+pub use nat::{ExternalAddr, NAT_MAPPING_LIFETIME, NAT_RECHECK_INTERVAL, Nat, NatService};
+
 mod metrics;
 pub use metrics::Metrics;
@@ -0,0 +1,355 @@
+//! NAT traversal: port mapping with UPnP or NAT-PMP, and external address detection.
+
+use crate::{Discv5Handler, HandlerRequest, NatError};
+use igd_next::{PortMappingProtocol, SearchOptions};
+use std::{
+    fmt,
+    net::{IpAddr, Ipv4Addr, SocketAddr},
+    str::FromStr,
+    time::Duration,
+};
+use tokio::{net::UdpSocket, task::JoinHandle};
+
+/// The interval at which port mappings are renewed and the external address is re-checked.
+pub const NAT_RECHECK_INTERVAL: Duration = Duration::from_secs(15 * 60);
+
+/// The lifetime requested for port mappings. This outlives [`NAT_RECHECK_INTERVAL`], so that
+/// mappings are renewed before they expire.
+pub const NAT_MAPPING_LIFETIME: Duration = Duration::from_secs(20 * 60);
+
+/// The description attached to UPnP port mappings.
+const UPNP_MAPPING_DESCRIPTION: &str = "kona-node";
+
+/// The port NAT-PMP gateways listen on.
+const NAT_PMP_PORT: u16 = 5351;
+
+/// The number of times a NAT-PMP request is sent before giving up. The first retransmission
+/// happens after 250ms, and the delay doubles on every retransmission.
+const NAT_PMP_ATTEMPTS: u32 = 4;
+
+/// The NAT traversal mechanism used to make the node dialable from behind a NAT.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Nat {
+    /// No NAT traversal. The advertised address is left untouched.
+    #[default]
+    None,
+    /// Tries UPnP, then NAT-PMP.
+    Any,
+    /// Maps ports with UPnP.
+    Upnp,
+    /// Maps ports with NAT-PMP, using the given gateway. If no gateway is given, the `.1` address
+    /// of the local network is assumed.
+    Pmp(Option<Ipv4Addr>),
+    /// Advertises the given external IP address, without mapping ports.
+    ExtIp(IpAddr),
+}
+
+impl FromStr for Nat {
+    type Err = NatError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (mechanism, arg) = s.split_once(':').map_or((s, None), |(m, a)| (m, Some(a)));
+        let parse_ip = |ip: &str| ip.parse().map_err(|_| NatError::InvalidMode(s.to_string()));
+        match (mechanism.to_lowercase().as_str(), arg) {
+            ("none", None) => Ok(Self::None),
+            ("any", None) => Ok(Self::Any),
+            ("upnp", None) => Ok(Self::Upnp),
+            ("pmp", None) => Ok(Self::Pmp(None)),
+            ("pmp", Some(gateway)) => Ok(Self::Pmp(Some(parse_ip(gateway)?))),
+            ("extip", Some(ip)) => Ok(Self::ExtIp(parse_ip(ip)?)),
+            _ => Err(NatError::InvalidMode(s.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for Nat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::None => write!(f, "none"),
+            Self::Any => write!(f, "any"),
+            Self::Upnp => write!(f, "upnp"),
+            Self::Pmp(None) => write!(f, "pmp"),
+            Self::Pmp(Some(gateway)) => write!(f, "pmp:{gateway}"),
+            Self::ExtIp(ip) => write!(f, "extip:{ip}"),
+        }
+    }
+}
+
+/// The external address of the node, as advertised in its ENR.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExternalAddr {
+    /// The external IP address.
+    pub ip: IpAddr,
+    /// The external TCP port, mapped to the gossip port.
+    pub tcp_port: u16,
+    /// The external UDP port, mapped to the discovery port.
+    pub udp_port: u16,
+}
+
+/// A service that maps the gossip and discovery ports on the NAT gateway, and updates the local
+/// ENR with the resulting external address.
+///
+/// Mappings are renewed, and the external address re-checked, every [`NAT_RECHECK_INTERVAL`], so
+/// that the ENR follows changes of the external IP address.
+#[derive(Debug, Clone)]
+pub struct NatService {
+    /// The NAT traversal mechanism.
+    nat: Nat,
+    /// The local gossip (TCP) port.
+    tcp_port: u16,
+    /// The local discovery (UDP) port.
+    udp_port: u16,
+    /// The handler of the discovery service, whose ENR is updated.
+    discovery: Discv5Handler,
+}
+
+impl NatService {
+    /// Creates a new [`NatService`].
+    pub const fn new(nat: Nat, tcp_port: u16, udp_port: u16, discovery: Discv5Handler) -> Self {
+        Self { nat, tcp_port, udp_port, discovery }
+    }
+
+    /// Spawns the [`NatService`] in a new tokio task. Returns `None` if NAT traversal is
+    /// disabled.
+    pub fn spawn(self) -> Option<JoinHandle<()>> {
+        if self.nat == Nat::None {
+            return None;
+        }
+        Some(tokio::spawn(self.run()))
+    }
+
+    /// Runs the service until the discovery service shuts down.
+    async fn run(self) {
+        let mut interval = tokio::time::interval(NAT_RECHECK_INTERVAL);
+        let mut current = None;
+        loop {
+            interval.tick().await;
+            let external = match self.map_ports().await {
+                Ok(external) => external,
+                Err(e) => {
+                    warn!(target: "nat", nat = %self.nat, ?e, "Failed to map ports");
+                    continue;
+                }
+            };
+            if current == Some(external) {
+                continue;
+            }
+            info!(target: "nat", nat = %self.nat, ?external, "Detected external address");
+            if self.discovery.sender.send(HandlerRequest::SetExternalAddr(external)).await.is_err()
+            {
+                debug!(target: "nat", "Discovery service stopped, stopping NAT service");
+                return;
+            }
+            current = Some(external);
+
+            // A fixed external IP does not need to be re-checked.
+            if matches!(self.nat, Nat::ExtIp(_)) {
+                return;
+            }
+        }
+    }
+
+    /// Maps the gossip and discovery ports with the configured mechanism, returning the external
+    /// address of the node.
+    pub async fn map_ports(&self) -> Result<ExternalAddr, NatError> {
+        match self.nat {
+            Nat::None => Err(NatError::Disabled),
+            Nat::ExtIp(ip) => {
+                Ok(ExternalAddr { ip, tcp_port: self.tcp_port, udp_port: self.udp_port })
+            }
+            Nat::Upnp => self.map_upnp().await,
+            Nat::Pmp(gateway) => self.map_pmp(gateway).await,
+            Nat::Any => match self.map_upnp().await {
+                Ok(external) => Ok(external),
+                Err(e) => {
+                    debug!(target: "nat", ?e, "UPnP port mapping failed, trying NAT-PMP");
+                    self.map_pmp(None).await
+                }
+            },
+        }
+    }
+
+    /// Maps the ports on the UPnP gateway of the local network.
+    async fn map_upnp(&self) -> Result<ExternalAddr, NatError> {
+        let gateway = igd_next::aio::tokio::search_gateway(SearchOptions::default())
+            .await
+            .map_err(|e| NatError::Upnp(e.to_string()))?;
+        let local_ip = local_ip_towards(gateway.addr.ip()).await?;
+        let lifetime = NAT_MAPPING_LIFETIME.as_secs() as u32;
+        for (protocol, port) in
+            [(PortMappingProtocol::TCP, self.tcp_port), (PortMappingProtocol::UDP, self.udp_port)]
+        {
+            gateway
+                .add_port(
+                    protocol,
+                    port,
+                    SocketAddr::new(local_ip, port),
+                    lifetime,
+                    UPNP_MAPPING_DESCRIPTION,
+                )
+                .await
+                .map_err(|e| NatError::Upnp(e.to_string()))?;
+        }
+        let ip = gateway.get_external_ip().await.map_err(|e| NatError::Upnp(e.to_string()))?;
+        Ok(ExternalAddr { ip, tcp_port: self.tcp_port, udp_port: self.udp_port })
+    }
+
+    /// Maps the ports on the given NAT-PMP gateway, or on the assumed gateway of the local
+    /// network.
+    async fn map_pmp(&self, gateway: Option<Ipv4Addr>) -> Result<ExternalAddr, NatError> {
+        let gateway = match gateway {
+            Some(gateway) => gateway,
+            None => match local_ip_towards(IpAddr::V4(Ipv4Addr::new(1, 1, 1, 1))).await? {
+                IpAddr::V4(ip) => {
+                    let [a, b, c, _] = ip.octets();
+                    Ipv4Addr::new(a, b, c, 1)
+                }
+                IpAddr::V6(_) => return Err(NatError::NoGateway),
+            },
+        };
+        let lifetime = NAT_MAPPING_LIFETIME.as_secs() as u32;
+        let response = pmp_request(gateway, &pmp::external_address_request()).await?;
+        let ip = pmp::decode_external_address(&response)?;
+        let response =
+            pmp_request(gateway, &pmp::map_request(false, self.tcp_port, lifetime)).await?;
+        let tcp_port = pmp::decode_mapping(&response, false)?;
+        let response =
+            pmp_request(gateway, &pmp::map_request(true, self.udp_port, lifetime)).await?;
+        let udp_port = pmp::decode_mapping(&response, true)?;
+        Ok(ExternalAddr { ip: IpAddr::V4(ip), tcp_port, udp_port })
+    }
+}
+
+/// Returns the local IP address used to reach the given address. No packet is sent.
+async fn local_ip_towards(target: IpAddr) -> Result<IpAddr, NatError> {
+    let bind: IpAddr = if target.is_ipv4() {
+        Ipv4Addr::UNSPECIFIED.into()
+    } else {
+        std::net::Ipv6Addr::UNSPECIFIED.into()
+    };
+    let socket = UdpSocket::bind((bind, 0)).await?;
+    socket.connect((target, NAT_PMP_PORT)).await?;
+    Ok(socket.local_addr()?.ip())
+}
+
+/// Sends the given NAT-PMP request to the gateway, retransmitting it until a response is received.
+async fn pmp_request(gateway: Ipv4Addr, request: &[u8]) -> Result<Vec<u8>, NatError> {
+    let socket = UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0)).await?;
+    socket.connect((gateway, NAT_PMP_PORT)).await?;
+    let mut timeout = Duration::from_millis(250);
+    let mut buf = [0u8; 16];
+    for _ in 0..NAT_PMP_ATTEMPTS {
+        socket.send(request).await?;
+        if let Ok(received) = tokio::time::timeout(timeout, socket.recv(&mut buf)).await {
+            return Ok(buf[..received?].to_vec());
+        }
+        timeout *= 2;
+    }
+    Err(NatError::Timeout)
+}
+
+/// Encoding and decoding of NAT-PMP messages, as specified by
+/// [RFC 6886](https://datatracker.ietf.org/doc/html/rfc6886).
+mod pmp {
+    use crate::NatError;
+    use std::net::Ipv4Addr;
+
+    /// The NAT-PMP protocol version.
+    const VERSION: u8 = 0;
+    /// The opcode of external address requests.
+    const OP_EXTERNAL_ADDRESS: u8 = 0;
+    /// The opcode of UDP mapping requests.
+    const OP_MAP_UDP: u8 = 1;
+    /// The opcode of TCP mapping requests.
+    const OP_MAP_TCP: u8 = 2;
+    /// The offset added to the request opcode in responses.
+    const OP_RESPONSE: u8 = 128;
+
+    /// Encodes an external address request.
+    pub(super) const fn external_address_request() -> [u8; 2] {
+        [VERSION, OP_EXTERNAL_ADDRESS]
+    }
+
+    /// Encodes a request mapping the given port to the same external port.
+    pub(super) fn map_request(udp: bool, port: u16, lifetime: u32) -> [u8; 12] {
+        let mut request = [0u8; 12];
+        request[0] = VERSION;
+        request[1] = if udp { OP_MAP_UDP } else { OP_MAP_TCP };
+        request[4..6].copy_from_slice(&port.to_be_bytes());
+        request[6..8].copy_from_slice(&port.to_be_bytes());
+        request[8..12].copy_from_slice(&lifetime.to_be_bytes());
+        request
+    }
+
+    /// Checks the header of a response to a request with the given opcode.
+    const fn check_header(response: &[u8], len: usize, op: u8) -> Result<(), NatError> {
+        if response.len() < len || response[0] != VERSION || response[1] != OP_RESPONSE + op {
+            return Err(NatError::InvalidResponse);
+        }
+        match u16::from_be_bytes([response[2], response[3]]) {
+            0 => Ok(()),
+            code => Err(NatError::Pmp(code)),
+        }
+    }
+
+    /// Decodes the external IP address from a response to an external address request.
+    pub(super) fn decode_external_address(response: &[u8]) -> Result<Ipv4Addr, NatError> {
+        check_header(response, 12, OP_EXTERNAL_ADDRESS)?;
+        Ok(Ipv4Addr::new(response[8], response[9], response[10], response[11]))
+    }
+
+    /// Decodes the mapped external port from a response to a mapping request.
+    pub(super) fn decode_mapping(response: &[u8], udp: bool) -> Result<u16, NatError> {
+        check_header(response, 16, if udp { OP_MAP_UDP } else { OP_MAP_TCP })?;
+        Ok(u16::from_be_bytes([response[10], response[11]]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nat_from_str() {
+        assert_eq!("none".parse::<Nat>().unwrap(), Nat::None);
+        assert_eq!("any".parse::<Nat>().unwrap(), Nat::Any);
+        assert_eq!("UPnP".parse::<Nat>().unwrap(), Nat::Upnp);
+        assert_eq!("pmp".parse::<Nat>().unwrap(), Nat::Pmp(None));
+        assert_eq!(
+            "pmp:192.168.1.254".parse::<Nat>().unwrap(),
+            Nat::Pmp(Some(Ipv4Addr::new(192, 168, 1, 254)))
+        );
+        assert_eq!(
+            "extip:1.2.3.4".parse::<Nat>().unwrap(),
+            Nat::ExtIp(IpAddr::V4(Ipv4Addr::new(1, 2, 3, 4)))
+        );
+        assert!("extip".parse::<Nat>().is_err());
+        assert!("extip:foo".parse::<Nat>().is_err());
+        assert!("stun".parse::<Nat>().is_err());
+
+        for nat in [Nat::None, Nat::Any, Nat::Pmp(Some(Ipv4Addr::LOCALHOST))] {
+            assert_eq!(nat.to_string().parse::<Nat>().unwrap(), nat);
+        }
+    }
+
+    #[test]
+    fn test_pmp_map_request() {
+        let request = pmp::map_request(false, 9222, 1200);
+        assert_eq!(request, [0, 2, 0, 0, 0x24, 0x06, 0x24, 0x06, 0, 0, 0x04, 0xb0]);
+        assert_eq!(pmp::map_request(true, 9223, 1200)[1], 1);
+    }
+
+    #[test]
+    fn test_pmp_decode_responses() {
+        let response = [0, 128, 0, 0, 0, 0, 0, 1, 1, 2, 3, 4];
+        assert_eq!(pmp::decode_external_address(&response).unwrap(), Ipv4Addr::new(1, 2, 3, 4));
+
+        let response = [0, 130, 0, 0, 0, 0, 0, 1, 0x24, 0x06, 0x24, 0x07, 0, 0, 0x04, 0xb0];
+        assert_eq!(pmp::decode_mapping(&response, false).unwrap(), 9223);
+        assert!(matches!(pmp::decode_mapping(&response, true), Err(NatError::InvalidResponse)));
+
+        let response = [0, 128, 0, 3, 0, 0, 0, 1, 0, 0, 0, 0];
+        assert!(matches!(pmp::decode_external_address(&response), Err(NatError::Pmp(3))));
+        assert!(matches!(pmp::decode_external_address(&[0, 128]), Err(NatError::InvalidResponse)));
+    }
+}
@@ -0,0 +1,189 @@
+//! Contains the [`EngineAnchor`], which persists the last known safe and finalized L2 blocks to
+//! detect an execution layer that was rolled back or replaced while the node was stopped.
+
+use crate::EngineClientError;
+use alloy_eips::{BlockNumHash, BlockNumberOrTag};
+use alloy_primitives::B256;
+use alloy_provider::{Provider, RootProvider};
+use op_alloy_network::Optimism;
+use std::{fs, io, path::Path};
+use thiserror::Error;
+
+/// The last known safe and finalized L2 blocks of the node.
+///
+/// The anchor is written to disk, along with the safe head, as the finalized head advances. On
+/// startup, the execution layer's canonical chain must still contain both blocks. Otherwise, the
+/// execution layer was rolled back, or the node was pointed at the execution layer of another
+/// chain.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct EngineAnchor {
+    /// The last known L2 safe head.
+    pub safe: BlockNumHash,
+    /// The last known L2 finalized head.
+    pub finalized: BlockNumHash,
+}
+
+/// An error that occurred while loading, storing or verifying an [`EngineAnchor`].
+#[derive(Debug, Error)]
+pub enum EngineAnchorError {
+    /// The anchor file could not be read or written.
+    #[error("engine anchor file error: {0}")]
+    Io(#[from] io::Error),
+    /// The anchor file could not be decoded.
+    #[error("engine anchor file is corrupt: {0}")]
+    Corrupt(#[from] serde_json::Error),
+    /// The execution layer could not be queried.
+    #[error(transparent)]
+    Client(#[from] EngineClientError),
+    /// The canonical chain of the execution layer does not contain an anchor block.
+    #[error(
+        "execution layer does not contain the anchor block {} ({}), found {found:?}",
+        expected.number,
+        expected.hash
+    )]
+    NotCanonical {
+        /// The anchor block.
+        expected: BlockNumHash,
+        /// The hash of the canonical block at the anchor's height, if any.
+        found: Option<B256>,
+    },
+}
+
+impl EngineAnchor {
+    /// Loads the anchor from the given file. Returns [`None`] if the file does not exist.
+    pub fn load(path: impl AsRef<Path>) -> Result<Option<Self>, EngineAnchorError> {
+        match fs::read(path) {
+            Ok(contents) => Ok(Some(serde_json::from_slice(&contents)?)),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    /// Writes the anchor to the given file. The file is replaced atomically, so that a crash
+    /// while writing does not leave a corrupt anchor behind.
+    pub fn store(&self, path: impl AsRef<Path>) -> Result<(), EngineAnchorError> {
+        let path = path.as_ref();
+        let tmp = path.with_extension("tmp");
+        fs::write(&tmp, serde_json::to_vec(self)?)?;
+        fs::rename(tmp, path)?;
+        Ok(())
+    }
+
+    /// Checks that the canonical chain of the execution layer contains the finalized and safe
+    /// blocks of the anchor. Blocks that were never set are skipped.
+    pub async fn verify(&self, l2: &RootProvider<Optimism>) -> Result<(), EngineAnchorError> {
+        for expected in [self.finalized, self.safe] {
+            if expected.hash.is_zero() {
+                continue;
+            }
+            let found = l2
+                .get_block_by_number(BlockNumberOrTag::Number(expected.number))
+                .await
+                .map_err(EngineClientError::from)?
+                .map(|block| block.header.hash);
+            if found != Some(expected.hash) {
+                return Err(EngineAnchorError::NotCanonical { expected, found });
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_rpc_client::RpcClient;
+    use alloy_rpc_types_eth::{Block, Header};
+    use alloy_transport::mock::{Asserter, MockTransport};
+    use op_alloy_rpc_types::Transaction;
+
+    fn provider(asserter: &Asserter) -> RootProvider<Optimism> {
+        RootProvider::new(RpcClient::new(MockTransport::new(asserter.clone()), false))
+    }
+
+    fn push_block(asserter: &Asserter, hash: B256) {
+        let block = Block::<Transaction> {
+            header: Header { hash, ..Default::default() },
+            ..Default::default()
+        };
+        asserter.push_success(&block);
+    }
+
+    fn anchor() -> EngineAnchor {
+        EngineAnchor {
+            safe: BlockNumHash { number: 110, hash: B256::with_last_byte(110) },
+            finalized: BlockNumHash { number: 100, hash: B256::with_last_byte(100) },
+        }
+    }
+
+    #[test]
+    fn test_anchor_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("anchor.json");
+        assert_eq!(EngineAnchor::load(&path).unwrap(), None);
+
+        let anchor = anchor();
+        anchor.store(&path).unwrap();
+        assert_eq!(EngineAnchor::load(&path).unwrap(), Some(anchor));
+        assert!(!path.with_extension("tmp").exists());
+    }
+
+    #[test]
+    fn test_anchor_corrupt() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("anchor.json");
+        fs::write(&path, b"not json").unwrap();
+        assert!(matches!(EngineAnchor::load(&path), Err(EngineAnchorError::Corrupt(_))));
+    }
+
+    #[tokio::test]
+    async fn test_anchor_verify_canonical() {
+        let asserter = Asserter::new();
+        push_block(&asserter, B256::with_last_byte(100));
+        push_block(&asserter, B256::with_last_byte(110));
+        anchor().verify(&provider(&asserter)).await.unwrap();
+        assert!(asserter.read_q().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_anchor_verify_mismatch() {
+        let asserter = Asserter::new();
+        push_block(&asserter, B256::with_last_byte(100));
+        push_block(&asserter, B256::with_last_byte(111));
+        let err = anchor().verify(&provider(&asserter)).await.unwrap_err();
+        assert!(matches!(
+            err,
+            EngineAnchorError::NotCanonical { expected, found }
+                if expected == anchor().safe && found == Some(B256::with_last_byte(111))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_anchor_verify_missing_block() {
+        let asserter = Asserter::new();
+        asserter.push_success(&Option::<Block<Transaction>>::None);
+        let finalized = anchor().finalized;
+        let err = anchor().verify(&provider(&asserter)).await.unwrap_err();
+        assert!(matches!(
+            err,
+            EngineAnchorError::NotCanonical { expected, found: None } if expected == finalized
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_anchor_verify_skips_unset_blocks() {
+        let asserter = Asserter::new();
+        push_block(&asserter, B256::with_last_byte(110));
+        let anchor = EngineAnchor { finalized: BlockNumHash::default(), ..anchor() };
+        anchor.verify(&provider(&asserter)).await.unwrap();
+        assert!(asserter.read_q().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_anchor_verify_client_error() {
+        let asserter = Asserter::new();
+        asserter.push_failure_msg("unavailable");
+        let err = anchor().verify(&provider(&asserter)).await.unwrap_err();
+        assert!(matches!(err, EngineAnchorError::Client(_)));
+    }
+}
@@ -1,6 +1,6 @@
 //! An Engine API Client.
 
-use crate::Metrics;
+use crate::{Metrics, ReloadableAuthLayer, ReloadableAuthService, ReloadableJwtSecret};
 use alloy_eips::eip1898::BlockNumberOrTag;
 use alloy_network::Network;
 use alloy_primitives::{B256, BlockHash, Bytes};
@@ -13,7 +13,7 @@ use alloy_rpc_types_engine::{
 use alloy_rpc_types_eth::Block;
 use alloy_transport::{RpcError, TransportErrorKind, TransportResult};
 use alloy_transport_http::{
-    AuthLayer, AuthService, Http, HyperClient,
+    Http, HyperClient,
     hyper_util::{
         client::legacy::{Client, connect::HttpConnector},
         rt::TokioExecutor,
@@ -23,15 +23,17 @@ use derive_more::Deref;
 use http_body_util::Full;
 use kona_genesis::RollupConfig;
 use kona_protocol::{FromBlockError, L2BlockInfo};
+use kona_providers_alloy::{FailoverConfig, FailoverTransport};
 use op_alloy_network::Optimism;
 use op_alloy_provider::ext::engine::OpEngineApi;
 use op_alloy_rpc_types::Transaction;
 use op_alloy_rpc_types_engine::{
-    OpExecutionPayloadEnvelopeV3, OpExecutionPayloadEnvelopeV4, OpExecutionPayloadV4,
-    OpPayloadAttributes, ProtocolVersion,
+    OpExecutionPayloadEnvelope, OpExecutionPayloadEnvelopeV3, OpExecutionPayloadEnvelopeV4,
+    OpExecutionPayloadV4, OpPayloadAttributes, ProtocolVersion,
 };
 use std::{sync::Arc, time::Instant};
 use thiserror::Error;
+use tokio::sync::mpsc;
 use tower::ServiceBuilder;
 use url::Url;
 
@@ -47,7 +49,11 @@ pub enum EngineClientError {
     BlockInfoDecodeError(#[from] FromBlockError),
 }
 /// A Hyper HTTP client with a JWT authentication layer.
-type HyperAuthClient<B = Full<Bytes>> = HyperClient<B, AuthService<Client<HttpConnector, B>>>;
+pub(crate) type HyperAuthClient<B = Full<Bytes>> =
+    HyperClient<B, ReloadableAuthService<Client<HttpConnector, B>>>;
+
+/// The transport of the Engine API, failing over across the configured engine endpoints.
+type EngineTransport = FailoverTransport<Http<HyperAuthClient>>;
 
 /// An Engine API client that provides authenticated HTTP communication with an execution layer.
 ///
@@ -55,6 +61,10 @@ type HyperAuthClient<B = Full<Bytes>> = HyperClient<B, AuthService<Client<HttpCo
 /// execution layers. It automatically selects the appropriate Engine API version based on the
 /// rollup configuration and block timestamps.
 ///
+/// The client may be configured with standby engine endpoints, to which requests fail over when
+/// the active endpoint is unavailable. The JWT secret is shared through a [`ReloadableJwtSecret`],
+/// allowing it to be rotated without restarting the client.
+///
 /// # Examples
 ///
 /// ```rust,no_run
@@ -79,23 +89,32 @@ pub struct EngineClient {
     /// The L2 engine provider for Engine API calls.
     #[deref]
     engine: RootProvider<Optimism>,
+    /// The transport of the L2 engine provider, used to track the active engine endpoint.
+    transport: EngineTransport,
     /// The L1 chain provider for reading L1 data.
     l1_provider: RootProvider,
     /// The [`RollupConfig`] for determining Engine API versions based on hardfork activations.
     cfg: Arc<RollupConfig>,
+    /// The channel to the [`PayloadVerifier`] that cross-checks the inserted payloads, if any.
+    ///
+    /// [`PayloadVerifier`]: crate::PayloadVerifier
+    payload_verifier: Option<mpsc::Sender<OpExecutionPayloadEnvelope>>,
 }
 
 impl EngineClient {
-    /// Creates a new RPC client for the given address and JWT secret.
-    fn rpc_client<T: Network>(addr: Url, jwt: JwtSecret) -> RootProvider<T> {
+    /// Creates a new authenticated HTTP transport for the given address and JWT secret.
+    fn http_transport(addr: Url, jwt: ReloadableJwtSecret) -> Http<HyperAuthClient> {
         let hyper_client = Client::builder(TokioExecutor::new()).build_http::<Full<Bytes>>();
-        let auth_layer = AuthLayer::new(jwt);
+        let auth_layer = ReloadableAuthLayer::new(jwt);
         let service = ServiceBuilder::new().layer(auth_layer).service(hyper_client);
         let layer_transport = HyperClient::with_service(service);
 
-        let http_hyper = Http::with_client(layer_transport, addr);
-        let rpc_client = RpcClient::new(http_hyper, false);
-        RootProvider::<T>::new(rpc_client)
+        Http::with_client(layer_transport, addr)
+    }
+
+    /// Creates a new RPC client over the given transport.
+    fn rpc_client<T: Network>(transport: EngineTransport) -> RootProvider<T> {
+        RootProvider::<T>::new(RpcClient::new(transport, false))
     }
 
     /// Creates a new [`EngineClient`] with authenticated HTTP connections.
@@ -110,10 +129,70 @@ impl EngineClient {
     /// * `cfg` - Rollup configuration for version selection
     /// * `jwt` - JWT secret for authentication
     pub fn new_http(engine: Url, l1_rpc: Url, cfg: Arc<RollupConfig>, jwt: JwtSecret) -> Self {
-        let engine = Self::rpc_client::<Optimism>(engine, jwt);
+        Self::new_http_with_standby(engine, Vec::new(), l1_rpc, cfg, jwt.into())
+    }
+
+    /// Creates a new [`EngineClient`] with authenticated HTTP connections to a primary engine
+    /// endpoint and the given standby engine endpoints, in order of priority.
+    ///
+    /// Requests fail over to the next healthy standby endpoint once the active endpoint fails
+    /// several requests in a row, and stay on the new endpoint afterwards. After a switchover, the
+    /// new endpoint must be resynchronized with the current forkchoice state; see
+    /// [`EngineClient::active_engine`].
+    pub fn new_http_with_standby(
+        engine: Url,
+        standby: Vec<Url>,
+        l1_rpc: Url,
+        cfg: Arc<RollupConfig>,
+        jwt: ReloadableJwtSecret,
+    ) -> Self {
+        let transports = core::iter::once(engine)
+            .chain(standby)
+            .map(|url| Self::http_transport(url, jwt.clone()))
+            .collect();
+        let transport = FailoverTransport::new(transports, FailoverConfig::default());
+        let engine = Self::rpc_client::<Optimism>(transport.clone());
         let l1_provider = RootProvider::new_http(l1_rpc);
 
-        Self { engine, l1_provider, cfg }
+        Self { engine, transport, l1_provider, cfg, payload_verifier: None }
+    }
+
+    /// Sets the channel to the [`PayloadVerifier`] that cross-checks the payloads inserted through
+    /// this client, as returned by [`PayloadVerifier::spawn`].
+    ///
+    /// [`PayloadVerifier`]: crate::PayloadVerifier
+    /// [`PayloadVerifier::spawn`]: crate::PayloadVerifier::spawn
+    pub fn with_payload_verifier(
+        mut self,
+        payload_verifier: mpsc::Sender<OpExecutionPayloadEnvelope>,
+    ) -> Self {
+        self.payload_verifier = Some(payload_verifier);
+        self
+    }
+
+    /// Sends an inserted payload to the [`PayloadVerifier`], if any. Payloads are skipped while
+    /// the verifier lags behind, rather than delaying their insertion.
+    ///
+    /// [`PayloadVerifier`]: crate::PayloadVerifier
+    pub fn verify_payload(&self, envelope: &OpExecutionPayloadEnvelope) {
+        let Some(payload_verifier) = &self.payload_verifier else {
+            return;
+        };
+        if let Err(err) = payload_verifier.try_send(envelope.clone()) {
+            debug!(
+                target: "engine",
+                ?err,
+                number = envelope.execution_payload.block_number(),
+                "Skipping the verification of unsafe payload"
+            );
+            kona_macros::inc!(counter, Metrics::PAYLOAD_VERIFICATION, "verdict" => "skipped");
+        }
+    }
+
+    /// Returns the index of the engine endpoint that requests are currently routed to. The
+    /// primary endpoint has index `0`, followed by the standby endpoints in order of priority.
+    pub fn active_engine(&self) -> usize {
+        self.transport.active()
     }
 
     /// Returns a reference to the inner L2 [`RootProvider`].
@@ -153,6 +232,30 @@ impl EngineClient {
     }
 }
 
+#[cfg(test)]
+impl EngineClient {
+    /// Creates a new [`EngineClient`] whose L2 requests are answered by the given [`Asserter`].
+    ///
+    /// [`Asserter`]: alloy_transport::mock::Asserter
+    pub(crate) fn new_mocked(
+        asserter: &alloy_transport::mock::Asserter,
+        cfg: Arc<RollupConfig>,
+    ) -> Self {
+        let url: Url = "http://127.0.0.1:8551".parse().unwrap();
+        let transport = FailoverTransport::new(
+            vec![Self::http_transport(url.clone(), JwtSecret::random().into())],
+            FailoverConfig::default(),
+        );
+        let engine = RootProvider::new(RpcClient::new(
+            alloy_transport::mock::MockTransport::new(asserter.clone()),
+            false,
+        ));
+        let l1_provider = RootProvider::new_http(url);
+
+        Self { engine, transport, l1_provider, cfg, payload_verifier: None }
+    }
+}
+
 #[async_trait::async_trait]
 impl OpEngineApi<Optimism, Http<HyperAuthClient>> for EngineClient {
     async fn new_payload_v2(
@@ -0,0 +1,52 @@
+//! Contains the [`DerivationEvent`]s published by the engine as the safe chain is derived.
+
+use kona_protocol::{BlockInfo, L2BlockInfo};
+use serde::{Deserialize, Serialize};
+
+/// The default capacity of the [`DerivationEvent`] broadcast channel. Subscribers that fall
+/// further behind miss the oldest events.
+pub const DEFAULT_DERIVATION_EVENTS_CAPACITY: usize = 256;
+
+/// An event in the derivation of the safe chain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum DerivationEvent {
+    /// The safe head was updated.
+    #[serde(rename_all = "camelCase")]
+    SafeHeadUpdated {
+        /// The L1 block that the safe head was derived from, if known.
+        l1_block: Option<BlockInfo>,
+        /// The new L2 safe head.
+        safe_head: L2BlockInfo,
+    },
+    /// The derivation pipeline was reset.
+    #[serde(rename_all = "camelCase")]
+    Reset {
+        /// The L1 origin of the safe head that derivation restarts from.
+        l1_origin: BlockInfo,
+        /// The L2 safe head that derivation restarts from.
+        safe_head: L2BlockInfo,
+    },
+    /// The channel being derived was flushed, after a derived payload was found to be invalid.
+    Flushed,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_derivation_event_serde() {
+        let event = DerivationEvent::SafeHeadUpdated {
+            l1_block: Some(BlockInfo { number: 10, ..Default::default() }),
+            safe_head: L2BlockInfo::default(),
+        };
+        let json = serde_json::to_value(event).unwrap();
+        assert_eq!(json["type"], "safeHeadUpdated");
+        assert_eq!(json["l1Block"]["number"], 10);
+        assert_eq!(serde_json::from_value::<DerivationEvent>(json).unwrap(), event);
+
+        let json = serde_json::to_value(DerivationEvent::Flushed).unwrap();
+        assert_eq!(json, serde_json::json!({ "type": "flushed" }));
+    }
+}
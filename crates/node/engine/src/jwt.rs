@@ -0,0 +1,149 @@
+//! Contains a JWT authentication layer for the Engine API whose secret can be rotated at runtime.
+
+use alloy_rpc_types_engine::{Claims, JwtSecret};
+use alloy_transport_http::hyper::{
+    Request,
+    header::{AUTHORIZATION, HeaderValue},
+};
+use std::{
+    path::PathBuf,
+    sync::{Arc, RwLock},
+    task::{Context, Poll},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use tokio::task::JoinHandle;
+use tower::{Layer, Service};
+
+/// The lifetime of the JWT tokens issued for Engine API requests, in seconds.
+const TOKEN_LIFETIME_SECS: u64 = 60;
+
+/// A [`JwtSecret`] that can be replaced at runtime, shared between every client authenticated
+/// with it.
+#[derive(Debug, Clone)]
+pub struct ReloadableJwtSecret {
+    /// The current secret.
+    inner: Arc<RwLock<JwtSecret>>,
+}
+
+impl From<JwtSecret> for ReloadableJwtSecret {
+    fn from(secret: JwtSecret) -> Self {
+        Self { inner: Arc::new(RwLock::new(secret)) }
+    }
+}
+
+impl ReloadableJwtSecret {
+    /// Returns the current [`JwtSecret`].
+    pub fn get(&self) -> JwtSecret {
+        *self.inner.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Replaces the current [`JwtSecret`].
+    pub fn set(&self, secret: JwtSecret) {
+        *self.inner.write().unwrap_or_else(|e| e.into_inner()) = secret;
+    }
+
+    /// Spawns a task that reloads the secret from the hex-encoded file at the given path whenever
+    /// its contents change, checking at the given interval. Files that fail to parse are ignored,
+    /// and the current secret is kept.
+    pub fn watch_file(&self, path: PathBuf, interval: Duration) -> JoinHandle<()> {
+        let secret = self.clone();
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                let Ok(contents) = tokio::fs::read_to_string(&path).await else {
+                    continue;
+                };
+                match JwtSecret::from_hex(contents.trim()) {
+                    Ok(new) if new != secret.get() => {
+                        info!(target: "engine", path = %path.display(), "Reloaded JWT secret");
+                        secret.set(new);
+                    }
+                    Ok(_) => {}
+                    Err(err) => {
+                        warn!(target: "engine", ?err, path = %path.display(), "Failed to parse JWT secret, keeping the current secret");
+                    }
+                }
+            }
+        })
+    }
+
+    /// Issues a bearer token signed with the current secret.
+    fn bearer_token(&self) -> Option<HeaderValue> {
+        let iat = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+        let claims = Claims { iat, exp: Some(iat + TOKEN_LIFETIME_SECS) };
+        let token = self.get().encode(&claims).ok()?;
+        HeaderValue::from_str(&format!("Bearer {token}")).ok()
+    }
+}
+
+/// A [`Layer`] that authenticates requests with a [`ReloadableJwtSecret`].
+#[derive(Debug, Clone)]
+pub struct ReloadableAuthLayer {
+    /// The shared secret.
+    secret: ReloadableJwtSecret,
+}
+
+impl ReloadableAuthLayer {
+    /// Creates a new [`ReloadableAuthLayer`] with the given secret.
+    pub const fn new(secret: ReloadableJwtSecret) -> Self {
+        Self { secret }
+    }
+}
+
+impl<S> Layer<S> for ReloadableAuthLayer {
+    type Service = ReloadableAuthService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        ReloadableAuthService { inner, secret: self.secret.clone() }
+    }
+}
+
+/// A [`Service`] that adds a bearer token, signed with the current [`ReloadableJwtSecret`], to
+/// every request.
+#[derive(Debug, Clone)]
+pub struct ReloadableAuthService<S> {
+    /// The inner service.
+    inner: S,
+    /// The shared secret.
+    secret: ReloadableJwtSecret,
+}
+
+impl<S, B> Service<Request<B>> for ReloadableAuthService<S>
+where
+    S: Service<Request<B>>,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = S::Future;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut request: Request<B>) -> Self::Future {
+        match self.secret.bearer_token() {
+            Some(token) => {
+                request.headers_mut().insert(AUTHORIZATION, token);
+            }
+            None => error!(target: "engine", "Failed to issue a JWT for the Engine API request"),
+        }
+        self.inner.call(request)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reloadable_secret_is_shared() {
+        let secret = ReloadableJwtSecret::from(JwtSecret::random());
+        let shared = secret.clone();
+
+        let new = JwtSecret::random();
+        secret.set(new);
+        assert_eq!(shared.get(), new);
+        assert!(shared.bearer_token().is_some());
+    }
+}
@@ -33,6 +33,12 @@
 //! - **Attributes** - Payload attribute validation via [`AttributesMatch`]
 //! - **Kinds** - Engine client type identification via [`EngineKind`]
 //! - **Query** - Engine query interface via [`EngineQueries`]
+//! - **Safe DB** - Persistent history of the safe head derived from each L1 block via
+//!   [`SafeHeadDb`]
+//! - **Anchor** - Persisted safe and finalized heads, checked against the execution layer on
+//!   startup via [`EngineAnchor`]
+//! - **Verifier** - Cross-checks of unsafe payloads against a secondary execution layer via
+//!   [`PayloadVerifier`]
 //! - **Metrics** - Optional Prometheus metrics collection via [`Metrics`]
 
 #[macro_use]
@@ -42,8 +48,8 @@ mod task_queue;
 pub use task_queue::{
     BuildTask, BuildTaskError, ConsolidateTask, ConsolidateTaskError, Engine, EngineBuildError,
     EngineResetError, EngineTask, EngineTaskError, EngineTaskErrorSeverity, EngineTaskErrors,
-    EngineTaskExt, FinalizeTask, FinalizeTaskError, InsertTask, InsertTaskError, SynchronizeTask,
-    SynchronizeTaskError,
+    EngineTaskExt, FinalizeTask, FinalizeTaskError, InsertTask, InsertTaskError,
+    PayloadCommitRequest, SynchronizeTask, SynchronizeTaskError,
 };
 
 mod attributes;
@@ -52,6 +58,9 @@ pub use attributes::{AttributesMatch, AttributesMismatch};
 mod client;
 pub use client::{EngineClient, EngineClientError};
 
+mod jwt;
+pub use jwt::{ReloadableAuthLayer, ReloadableAuthService, ReloadableJwtSecret};
+
 mod versions;
 pub use versions::{EngineForkchoiceVersion, EngineGetPayloadVersion, EngineNewPayloadVersion};
 
@@ -61,8 +70,24 @@ pub use state::{EngineState, EngineSyncState, EngineSyncStateUpdate};
 mod kinds;
 pub use kinds::EngineKind;
 
+mod safe_db;
+pub use safe_db::{SafeDbError, SafeHeadDb, SafeHeadEntry};
+
+mod anchor;
+pub use anchor::{EngineAnchor, EngineAnchorError};
+
+mod verifier;
+pub use verifier::{PayloadVerdict, PayloadVerifier};
+
+mod events;
+pub use events::{DEFAULT_DERIVATION_EVENTS_CAPACITY, DerivationEvent};
+
 mod query;
-pub use query::{EngineQueries, EngineQueriesError, EngineQuerySender};
+pub use query::{
+    DEFAULT_OUTPUT_ROOT_CACHE_SIZE, EngineQueries, EngineQueriesError, EngineQueryContext,
+    EngineQuerySender, MAX_CONCURRENT_RANGE_QUERIES, MAX_OUTPUT_RANGE, OUTPUT_RANGE_CONCURRENCY,
+    OutputRootCache,
+};
 
 mod metrics;
 pub use metrics::Metrics;
@@ -72,6 +72,21 @@ impl Metrics {
     /// Identifier for the counter that tracks the number of times the engine has been reset.
     pub const ENGINE_RESET_COUNT: &str = "kona_node_engine_reset_count";
 
+    /// Identifier for the counter that tracks the number of times the unsafe chain was reorged
+    /// because it diverged from the safe chain.
+    pub const UNSAFE_REORG_COUNT: &str = "kona_node_unsafe_reorg_count";
+    /// Identifier for the gauge that tracks the depth of the last unsafe chain reorg.
+    pub const UNSAFE_REORG_DEPTH: &str = "kona_node_unsafe_reorg_depth";
+
+    /// Identifier for the counter that tracks the verdicts of the secondary execution layer on
+    /// the unsafe payloads cross-checked by the [`crate::PayloadVerifier`].
+    pub const PAYLOAD_VERIFICATION: &str = "kona_node_payload_verification";
+
+    /// Identifier for the gauge that tracks the number of consecutive unsafe payloads that the
+    /// secondary execution layer of the [`crate::PayloadVerifier`] could not verify.
+    pub const PAYLOAD_VERIFIER_UNVERIFIED_STREAK: &str =
+        "kona_node_payload_verifier_unverified_streak";
+
     /// Initializes metrics for the engine.
     ///
     /// This does two things:
@@ -106,6 +121,30 @@ impl Metrics {
             metrics::Unit::Count,
             "Engine reset count"
         );
+
+        // Unsafe chain reorgs
+        metrics::describe_counter!(
+            Self::UNSAFE_REORG_COUNT,
+            metrics::Unit::Count,
+            "Reorgs of the unsafe chain after diverging from the safe chain"
+        );
+        metrics::describe_gauge!(
+            Self::UNSAFE_REORG_DEPTH,
+            metrics::Unit::Count,
+            "Depth of the last unsafe chain reorg"
+        );
+
+        // Payload verification
+        metrics::describe_counter!(
+            Self::PAYLOAD_VERIFICATION,
+            metrics::Unit::Count,
+            "Verdicts of the secondary execution layer on unsafe payloads"
+        );
+        metrics::describe_gauge!(
+            Self::PAYLOAD_VERIFIER_UNVERIFIED_STREAK,
+            metrics::Unit::Count,
+            "Consecutive unsafe payloads that the secondary execution layer could not verify"
+        );
     }
 
     /// Initializes metrics to `0` so they can be queried immediately by consumers of prometheus
@@ -125,5 +164,9 @@ impl Metrics {
 
         // Engine reset count
         kona_macros::set!(counter, Self::ENGINE_RESET_COUNT, 0);
+
+        // Unsafe chain reorgs
+        kona_macros::set!(counter, Self::UNSAFE_REORG_COUNT, 0);
+        kona_macros::set!(gauge, Self::UNSAFE_REORG_DEPTH, 0.0);
     }
 }
@@ -4,20 +4,47 @@
 //! from external actors. Uses oneshot channels for responses to maintain
 //! clean async communication patterns.
 
-use std::sync::Arc;
+use std::{
+    num::NonZeroUsize,
+    sync::{Arc, Mutex},
+};
 
-use alloy_eips::BlockNumberOrTag;
-use alloy_provider::Provider;
+use alloy_eips::{BlockId, BlockNumberOrTag};
+use alloy_primitives::B256;
+use alloy_provider::{Provider, RootProvider};
+use alloy_rpc_types_eth::Block;
 use alloy_transport::{RpcError, TransportErrorKind};
+use futures::{StreamExt, TryStreamExt};
 use kona_genesis::RollupConfig;
 use kona_protocol::{L2BlockInfo, OutputRoot, Predeploys};
-use tokio::sync::oneshot::Sender;
+use lru::LruCache;
+use op_alloy_network::Optimism;
+use op_alloy_rpc_types::Transaction;
+use tokio::sync::{Semaphore, oneshot::Sender};
 
-use crate::{EngineClient, EngineClientError, EngineState};
+use crate::{
+    DerivationEvent, EngineClient, EngineClientError, EngineState, SafeDbError, SafeHeadDb,
+    SafeHeadEntry,
+};
 
 /// Channel sender for submitting [`EngineQueries`] to the engine.
 pub type EngineQuerySender = tokio::sync::mpsc::Sender<EngineQueries>;
 
+/// The maximum number of blocks whose output roots can be requested at once with
+/// [`EngineQueries::OutputsAtBlockRange`].
+pub const MAX_OUTPUT_RANGE: u64 = 1024;
+
+/// The maximum number of [`EngineQueries::OutputsAtBlockRange`] queries answered at once. Further
+/// range queries wait for one of them to complete.
+pub const MAX_CONCURRENT_RANGE_QUERIES: usize = 2;
+
+/// The number of blocks of an [`EngineQueries::OutputsAtBlockRange`] query whose output roots are
+/// computed concurrently.
+pub const OUTPUT_RANGE_CONCURRENCY: usize = 8;
+
+/// The default number of output roots held by the [`OutputRootCache`].
+pub const DEFAULT_OUTPUT_ROOT_CACHE_SIZE: usize = 2048;
+
 /// Query types supported by the engine for external communication.
 ///
 /// Each variant includes a oneshot sender for the response, enabling
@@ -31,19 +58,47 @@ pub enum EngineQueries {
     State(Sender<EngineState>),
     /// Request the L2 output root for a specific block.
     ///
-    /// Returns a tuple of block info, output root, and engine state at the requested block.
+    /// Returns a tuple of block info, output root, and engine state at the requested block, or
+    /// the error that prevented the output root from being computed.
     OutputAtBlock {
         /// The block number or tag to retrieve the output for.
         block: BlockNumberOrTag,
         /// Response channel for (block_info, output_root, engine_state).
-        sender: Sender<(L2BlockInfo, OutputRoot, EngineState)>,
+        sender: Sender<Result<(L2BlockInfo, OutputRoot, EngineState), EngineQueriesError>>,
+    },
+    /// Request the L2 output roots for an inclusive range of block numbers.
+    ///
+    /// Returns the block info and output root of every block in the range, in ascending order,
+    /// along with the engine state, or the error that prevented an output root from being
+    /// computed. The range may span at most [`MAX_OUTPUT_RANGE`] blocks.
+    OutputsAtBlockRange {
+        /// The number of the first block of the range.
+        start: u64,
+        /// The number of the last block of the range.
+        end: u64,
+        /// Response channel for (outputs, engine_state).
+        sender: Sender<Result<(Vec<(L2BlockInfo, OutputRoot)>, EngineState), EngineQueriesError>>,
     },
     /// Subscribe to engine state updates via a watch channel receiver.
     StateReceiver(Sender<tokio::sync::watch::Receiver<EngineState>>),
+    /// Request whether the execution layer is reachable.
+    EngineReachable(Sender<bool>),
+    /// Request the next buffered unsafe payload to be inserted into the unsafe chain, if any.
+    QueuedUnsafeHead(Sender<Option<L2BlockInfo>>),
+    /// Subscribe to [`DerivationEvent`]s via a broadcast channel receiver.
+    DerivationEventsReceiver(Sender<tokio::sync::broadcast::Receiver<DerivationEvent>>),
     /// Development API: Subscribe to task queue length updates.
     QueueLengthReceiver(Sender<tokio::sync::watch::Receiver<usize>>),
     /// Development API: Get the current number of pending tasks in the queue.
     TaskQueueLength(Sender<usize>),
+    /// Request the most recent safe head derived from an L1 block at or before the given L1 block
+    /// number, from the [`SafeHeadDb`].
+    SafeHeadAtL1Block {
+        /// The L1 block number.
+        l1_block_number: u64,
+        /// Response channel for the safe head, if any was recorded.
+        sender: Sender<Result<Option<SafeHeadEntry>, SafeDbError>>,
+    },
 }
 
 /// An error that can occur when querying the engine.
@@ -58,6 +113,19 @@ pub enum EngineQueriesError {
     /// No block withdrawals root while Isthmus is active.
     #[error("No block withdrawals root while Isthmus is active")]
     NoWithdrawalsRoot,
+    /// The requested block range is empty or spans more than [`MAX_OUTPUT_RANGE`] blocks.
+    #[error(
+        "Invalid block range {start}..={end}, at most {MAX_OUTPUT_RANGE} blocks may be requested"
+    )]
+    InvalidBlockRange {
+        /// The number of the first block of the range.
+        start: u64,
+        /// The number of the last block of the range.
+        end: u64,
+    },
+    /// Failed to fetch an L2 block of a requested range.
+    #[error("Failed to fetch L2 block: {0}")]
+    BlockFetchFailed(RpcError<TransportErrorKind>),
     /// No L2 block found for block number or tag.
     #[error("No L2 block found for block number or tag: {0}")]
     NoL2BlockFound(BlockNumberOrTag),
@@ -66,15 +134,80 @@ pub enum EngineQueriesError {
     FailedToRetrieveWithdrawalsRoot(#[from] RpcError<TransportErrorKind>),
 }
 
+/// A cache of the computed [`OutputRoot`]s of L2 blocks, keyed by block hash.
+///
+/// Output roots are immutable for a given block hash, so cached entries stay valid across
+/// reorgs. Computing an output root before Isthmus requires an `eth_getProof` call, which the
+/// cache saves when proposers repeatedly request the same outputs.
+#[derive(Debug, Clone)]
+pub struct OutputRootCache(Arc<Mutex<LruCache<B256, (L2BlockInfo, OutputRoot)>>>);
+
+impl Default for OutputRootCache {
+    fn default() -> Self {
+        Self::new(
+            NonZeroUsize::new(DEFAULT_OUTPUT_ROOT_CACHE_SIZE)
+                .expect("the default cache size is non-zero"),
+        )
+    }
+}
+
+impl OutputRootCache {
+    /// Creates a new, empty [`OutputRootCache`] with the given capacity.
+    pub fn new(capacity: NonZeroUsize) -> Self {
+        Self(Arc::new(Mutex::new(LruCache::new(capacity))))
+    }
+
+    /// Returns the cached output of the block with the given hash, if any.
+    pub fn get(&self, block_hash: &B256) -> Option<(L2BlockInfo, OutputRoot)> {
+        self.0.lock().ok()?.get(block_hash).copied()
+    }
+
+    /// Caches the output of the block with the given hash.
+    pub fn insert(&self, block_hash: B256, output: (L2BlockInfo, OutputRoot)) {
+        if let Ok(mut cache) = self.0.lock() {
+            cache.put(block_hash, output);
+        }
+    }
+}
+
+/// The handles to the engine that [`EngineQueries`] are answered from.
+#[derive(Debug, Clone)]
+pub struct EngineQueryContext {
+    /// A receiver of [`EngineState`] updates.
+    pub state_recv: tokio::sync::watch::Receiver<EngineState>,
+    /// A receiver of task queue length updates.
+    pub queue_length_recv: tokio::sync::watch::Receiver<usize>,
+    /// A receiver of the next buffered unsafe payload to be inserted into the unsafe chain.
+    pub queued_unsafe_head_recv: tokio::sync::watch::Receiver<Option<L2BlockInfo>>,
+    /// The sender of [`DerivationEvent`]s, which queries subscribe to.
+    pub derivation_events: tokio::sync::broadcast::Sender<DerivationEvent>,
+    /// The [`EngineClient`], used to query the execution layer.
+    pub client: Arc<EngineClient>,
+    /// The [`RollupConfig`].
+    pub rollup_config: Arc<RollupConfig>,
+    /// The [`SafeHeadDb`], if the safe head derived from each L1 block is recorded.
+    pub safe_db: Option<Arc<SafeHeadDb>>,
+    /// The cache of computed output roots.
+    pub output_cache: OutputRootCache,
+    /// The permits of the [`EngineQueries::OutputsAtBlockRange`] queries being answered, at most
+    /// [`MAX_CONCURRENT_RANGE_QUERIES`].
+    pub range_query_permits: Arc<Semaphore>,
+}
+
 impl EngineQueries {
     /// Handles the engine query request.
-    pub async fn handle(
-        self,
-        state_recv: &tokio::sync::watch::Receiver<EngineState>,
-        queue_length_recv: &tokio::sync::watch::Receiver<usize>,
-        client: &Arc<EngineClient>,
-        rollup_config: &Arc<RollupConfig>,
-    ) -> Result<(), EngineQueriesError> {
+    pub async fn handle(self, ctx: &EngineQueryContext) -> Result<(), EngineQueriesError> {
+        let EngineQueryContext {
+            state_recv,
+            queue_length_recv,
+            queued_unsafe_head_recv,
+            derivation_events,
+            client,
+            rollup_config,
+            safe_db,
+            output_cache,
+            range_query_permits,
+        } = ctx;
         let state = *state_recv.borrow();
 
         match self {
@@ -85,50 +218,57 @@ impl EngineQueries {
                 sender.send(state).map_err(|_| EngineQueriesError::OutputChannelClosed)
             }
             Self::OutputAtBlock { block, sender } => {
-                let output_block = client.l2_block_by_label(block).await?;
-                let output_block = output_block.ok_or(EngineQueriesError::NoL2BlockFound(block))?;
-                // Cloning the l2 block below is cheaper than sending a network request to get the
-                // l2 block info. Querying the `L2BlockInfo` from the client ends up
-                // fetching the full l2 block again.
-                let consensus_block = output_block.clone().into_consensus();
-                let output_block_info =
-                    L2BlockInfo::from_block_and_genesis::<op_alloy_consensus::OpTxEnvelope>(
-                        &consensus_block.map_transactions(|tx| tx.inner.inner.into_inner()),
-                        &rollup_config.genesis,
-                    )
-                    .map_err(|_| EngineQueriesError::NoL2BlockFound(block))?;
-
-                let state_root = output_block.header.state_root;
-
-                let message_passer_storage_root =
-                    if rollup_config.is_isthmus_active(output_block.header.timestamp) {
-                        output_block
-                            .header
-                            .withdrawals_root
-                            .ok_or(EngineQueriesError::NoWithdrawalsRoot)?
-                    } else {
-                        // Fetch the storage root for the L2 head block.
-                        let l2_to_l1_message_passer = client
-                            .get_proof(Predeploys::L2_TO_L1_MESSAGE_PASSER, Default::default())
-                            .block_id(block.into())
-                            .await?;
-
-                        l2_to_l1_message_passer.storage_hash
+                let output = Self::output_at_block(block, client, rollup_config, output_cache)
+                    .await
+                    .map(|(block_info, output_root)| (block_info, output_root, state));
+                if let Err(err) = &output {
+                    debug!(target: "engine", ?block, ?err, "Failed to compute output root");
+                }
+                sender.send(output).map_err(|_| EngineQueriesError::OutputChannelClosed)
+            }
+            Self::OutputsAtBlockRange { start, end, sender } => {
+                // A range query makes several RPCs per block, so it is answered on its own task
+                // rather than delaying the queries that follow it.
+                let permits = Arc::clone(range_query_permits);
+                let client = Arc::clone(client);
+                let rollup_config = Arc::clone(rollup_config);
+                let output_cache = output_cache.clone();
+                tokio::spawn(async move {
+                    let Ok(_permit) = permits.acquire_owned().await else {
+                        return;
                     };
-
-                let output_response_v0 = OutputRoot::from_parts(
-                    state_root,
-                    message_passer_storage_root,
-                    output_block.header.hash,
-                );
-
-                sender
-                    .send((output_block_info, output_response_v0, state))
-                    .map_err(|_| EngineQueriesError::OutputChannelClosed)
+                    let outputs = Self::outputs_at_block_range(
+                        start,
+                        end,
+                        &client,
+                        &rollup_config,
+                        &output_cache,
+                        OUTPUT_RANGE_CONCURRENCY,
+                    )
+                    .await
+                    .map(|outputs| (outputs, state));
+                    if let Err(err) = &outputs {
+                        debug!(target: "engine", start, end, ?err, "Failed to compute output roots");
+                    }
+                    if sender.send(outputs).is_err() {
+                        warn!(target: "engine", "Failed to send output roots response");
+                    }
+                });
+                Ok(())
             }
+            Self::EngineReachable(sender) => {
+                let reachable = client.get_block_number().await.is_ok();
+                sender.send(reachable).map_err(|_| EngineQueriesError::OutputChannelClosed)
+            }
+            Self::QueuedUnsafeHead(sender) => sender
+                .send(*queued_unsafe_head_recv.borrow())
+                .map_err(|_| EngineQueriesError::OutputChannelClosed),
             Self::StateReceiver(subscription) => subscription
                 .send(state_recv.clone())
                 .map_err(|_| EngineQueriesError::OutputChannelClosed),
+            Self::DerivationEventsReceiver(subscription) => subscription
+                .send(derivation_events.subscribe())
+                .map_err(|_| EngineQueriesError::OutputChannelClosed),
             Self::QueueLengthReceiver(subscription) => subscription
                 .send(queue_length_recv.clone())
                 .map_err(|_| EngineQueriesError::OutputChannelClosed),
@@ -139,6 +279,255 @@ impl EngineQueries {
                 }
                 Ok(())
             }
+            Self::SafeHeadAtL1Block { l1_block_number, sender } => {
+                let safe_head = safe_db.as_deref().map_or(Err(SafeDbError::Disabled), |db| {
+                    db.safe_head_at_l1_block(l1_block_number)
+                });
+                sender.send(safe_head).map_err(|_| EngineQueriesError::OutputChannelClosed)
+            }
+        }
+    }
+
+    /// Computes the [`OutputRoot`] of the L2 block with the given number or tag, or returns it
+    /// from the cache.
+    async fn output_at_block(
+        block: BlockNumberOrTag,
+        client: &EngineClient,
+        rollup_config: &RollupConfig,
+        output_cache: &OutputRootCache,
+    ) -> Result<(L2BlockInfo, OutputRoot), EngineQueriesError> {
+        let output_block = client.l2_block_by_label(block).await?;
+        let output_block = output_block.ok_or(EngineQueriesError::NoL2BlockFound(block))?;
+        let block_hash = output_block.header.hash;
+        if let Some(output) = output_cache.get(&block_hash) {
+            return Ok(output);
+        }
+
+        let output = Self::output_of_block(output_block, block, client, rollup_config).await?;
+        output_cache.insert(block_hash, output);
+        Ok(output)
+    }
+
+    /// Computes the [`OutputRoot`]s of the L2 blocks in the given inclusive range of numbers, in
+    /// ascending order, computing the given number of them concurrently.
+    async fn outputs_at_block_range(
+        start: u64,
+        end: u64,
+        provider: &RootProvider<Optimism>,
+        rollup_config: &RollupConfig,
+        output_cache: &OutputRootCache,
+        concurrency: usize,
+    ) -> Result<Vec<(L2BlockInfo, OutputRoot)>, EngineQueriesError> {
+        if end < start || end - start >= MAX_OUTPUT_RANGE {
+            return Err(EngineQueriesError::InvalidBlockRange { start, end });
+        }
+
+        futures::stream::iter(start..=end)
+            .map(|number| Self::output_at_number(number, provider, rollup_config, output_cache))
+            .buffered(concurrency.max(1))
+            .try_collect()
+            .await
+    }
+
+    /// Computes the [`OutputRoot`] of the L2 block with the given number, or returns it from the
+    /// cache.
+    ///
+    /// Only the header of the block is fetched to look its output up in the cache. The full block
+    /// is fetched by hash on a cache miss, so that the output matches the looked up block even if
+    /// the chain reorgs in the meantime.
+    async fn output_at_number(
+        number: u64,
+        provider: &RootProvider<Optimism>,
+        rollup_config: &RollupConfig,
+        output_cache: &OutputRootCache,
+    ) -> Result<(L2BlockInfo, OutputRoot), EngineQueriesError> {
+        let block = BlockNumberOrTag::Number(number);
+        let header = provider
+            .get_block_by_number(block)
+            .await
+            .map_err(EngineQueriesError::BlockFetchFailed)?
+            .ok_or(EngineQueriesError::NoL2BlockFound(block))?;
+        let block_hash = header.header.hash;
+        if let Some(output) = output_cache.get(&block_hash) {
+            return Ok(output);
+        }
+
+        let output_block = provider
+            .get_block_by_hash(block_hash)
+            .full()
+            .await
+            .map_err(EngineQueriesError::BlockFetchFailed)?
+            .ok_or(EngineQueriesError::NoL2BlockFound(block))?;
+        let output = Self::output_of_block(output_block, block, provider, rollup_config).await?;
+        output_cache.insert(block_hash, output);
+        Ok(output)
+    }
+
+    /// Computes the [`OutputRoot`] of the given L2 block, fetched for the given number or tag.
+    ///
+    /// The output root commits to the block's state root, the storage root of the
+    /// `L2ToL1MessagePasser` predeploy and the block hash. Since Isthmus, the message passer
+    /// storage root is the block's withdrawals root. Before Isthmus, it is fetched with an
+    /// `eth_getProof` pinned to the block hash, so that a moving tag cannot yield a storage root
+    /// from a different block.
+    async fn output_of_block(
+        output_block: Block<Transaction>,
+        block: BlockNumberOrTag,
+        provider: &RootProvider<Optimism>,
+        rollup_config: &RollupConfig,
+    ) -> Result<(L2BlockInfo, OutputRoot), EngineQueriesError> {
+        // Cloning the l2 block below is cheaper than sending a network request to get the
+        // l2 block info. Querying the `L2BlockInfo` from the client ends up
+        // fetching the full l2 block again.
+        let consensus_block = output_block.clone().into_consensus();
+        let output_block_info =
+            L2BlockInfo::from_block_and_genesis::<op_alloy_consensus::OpTxEnvelope>(
+                &consensus_block.map_transactions(|tx| tx.inner.inner.into_inner()),
+                &rollup_config.genesis,
+            )
+            .map_err(|_| EngineQueriesError::NoL2BlockFound(block))?;
+
+        let state_root = output_block.header.state_root;
+        let block_hash = output_block.header.hash;
+
+        let message_passer_storage_root =
+            if rollup_config.is_isthmus_active(output_block.header.timestamp) {
+                output_block.header.withdrawals_root.ok_or(EngineQueriesError::NoWithdrawalsRoot)?
+            } else {
+                // Fetch the storage root of the message passer at the resolved block.
+                let l2_to_l1_message_passer = provider
+                    .get_proof(Predeploys::message_passer(rollup_config), Default::default())
+                    .block_id(BlockId::hash(block_hash))
+                    .await?;
+
+                l2_to_l1_message_passer.storage_hash
+            };
+
+        Ok((
+            output_block_info,
+            OutputRoot::from_parts(state_root, message_passer_storage_root, block_hash),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::Header;
+    use alloy_eips::BlockNumHash;
+    use alloy_rpc_client::RpcClient;
+    use alloy_rpc_types_eth::BlockTransactions;
+    use alloy_transport::mock::{Asserter, MockTransport};
+    use kona_genesis::{ChainGenesis, HardForkConfig};
+    use kona_protocol::BlockInfo;
+
+    /// Returns an empty Isthmus block with the given number, and its hash.
+    fn block(number: u64) -> (Block<Transaction>, B256) {
+        let header = Header {
+            number,
+            state_root: B256::repeat_byte(0x01),
+            withdrawals_root: Some(B256::repeat_byte(0x02)),
+            ..Default::default()
+        };
+        let hash = header.hash_slow();
+        let block = Block {
+            header: alloy_rpc_types_eth::Header::new(header),
+            uncles: Vec::new(),
+            transactions: BlockTransactions::Full(Vec::new()),
+            withdrawals: None,
+        };
+        (block, hash)
+    }
+
+    fn provider(asserter: &Asserter) -> RootProvider<Optimism> {
+        RootProvider::new(RpcClient::new(MockTransport::new(asserter.clone()), false))
+    }
+
+    /// Returns an Isthmus rollup config whose genesis is the given block.
+    fn rollup_config(genesis_hash: B256) -> RollupConfig {
+        RollupConfig {
+            genesis: ChainGenesis {
+                l2: BlockNumHash { number: 0, hash: genesis_hash },
+                ..Default::default()
+            },
+            hardforks: HardForkConfig { isthmus_time: Some(0), ..Default::default() },
+            ..Default::default()
+        }
+    }
+
+    fn output(number: u64, hash: u8) -> (L2BlockInfo, OutputRoot) {
+        let block_info =
+            BlockInfo { number, hash: B256::with_last_byte(hash), ..Default::default() };
+        (
+            L2BlockInfo { block_info, ..Default::default() },
+            OutputRoot::from_parts(B256::ZERO, B256::ZERO, B256::with_last_byte(hash)),
+        )
+    }
+
+    #[test]
+    fn test_output_root_cache() {
+        let cache = OutputRootCache::new(NonZeroUsize::new(1).unwrap());
+        cache.insert(B256::with_last_byte(1), output(1, 1));
+        assert_eq!(cache.get(&B256::with_last_byte(1)), Some(output(1, 1)));
+
+        // The least recently used output is evicted, and clones share the cache.
+        cache.clone().insert(B256::with_last_byte(2), output(2, 2));
+        assert_eq!(cache.get(&B256::with_last_byte(1)), None);
+        assert_eq!(cache.get(&B256::with_last_byte(2)), Some(output(2, 2)));
+    }
+
+    #[tokio::test]
+    async fn test_outputs_at_block_range_invalid() {
+        let asserter = Asserter::new();
+        let (_, genesis_hash) = block(0);
+        let cfg = rollup_config(genesis_hash);
+        let cache = OutputRootCache::default();
+
+        for (start, end) in [(2, 1), (0, MAX_OUTPUT_RANGE)] {
+            let result = EngineQueries::outputs_at_block_range(
+                start,
+                end,
+                &provider(&asserter),
+                &cfg,
+                &cache,
+                1,
+            )
+            .await;
+            assert!(matches!(result, Err(EngineQueriesError::InvalidBlockRange { .. })));
         }
+        assert!(asserter.read_q().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_outputs_at_block_range() {
+        let asserter = Asserter::new();
+        let (genesis, genesis_hash) = block(0);
+        let cfg = rollup_config(genesis_hash);
+        let cache = OutputRootCache::default();
+
+        // Block 1 is cached, so only its header is fetched.
+        let (mut cached, _) = block(1);
+        cached.header.hash = B256::with_last_byte(1);
+        cache.insert(B256::with_last_byte(1), output(1, 1));
+
+        asserter.push_success(&genesis);
+        asserter.push_success(&genesis);
+        asserter.push_success(&cached);
+
+        let outputs =
+            EngineQueries::outputs_at_block_range(0, 1, &provider(&asserter), &cfg, &cache, 1)
+                .await
+                .unwrap();
+        assert!(asserter.read_q().is_empty());
+
+        let genesis_output = (
+            L2BlockInfo {
+                block_info: BlockInfo { hash: genesis_hash, ..Default::default() },
+                ..Default::default()
+            },
+            OutputRoot::from_parts(B256::repeat_byte(0x01), B256::repeat_byte(0x02), genesis_hash),
+        );
+        assert_eq!(outputs, vec![genesis_output, output(1, 1)]);
+        assert_eq!(cache.get(&genesis_hash), Some(genesis_output));
     }
 }
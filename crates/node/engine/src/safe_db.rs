@@ -0,0 +1,181 @@
+//! Contains the [`SafeHeadDb`], which persists the L2 safe head derived from each L1 block.
+
+use alloy_eips::BlockNumHash;
+use alloy_primitives::B256;
+use rocksdb::{DB, Direction, IteratorMode, Options, WriteBatch};
+use std::path::Path;
+use thiserror::Error;
+
+/// The length of an encoded [`SafeHeadEntry`] value: the L1 block hash, followed by the L2 safe
+/// head number and hash.
+const ENTRY_VALUE_LEN: usize = 32 + 8 + 32;
+
+/// An L2 safe head, and the L1 block that it was derived from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SafeHeadEntry {
+    /// The L1 block that the safe head was derived from.
+    pub l1_block: BlockNumHash,
+    /// The L2 safe head.
+    pub safe_head: BlockNumHash,
+}
+
+impl SafeHeadEntry {
+    /// Encodes the database key of the entry: the big-endian L1 block number, so that entries are
+    /// ordered by L1 block number.
+    const fn key(l1_block_number: u64) -> [u8; 8] {
+        l1_block_number.to_be_bytes()
+    }
+
+    /// Encodes the database value of the entry.
+    fn value(&self) -> [u8; ENTRY_VALUE_LEN] {
+        let mut value = [0u8; ENTRY_VALUE_LEN];
+        value[..32].copy_from_slice(self.l1_block.hash.as_slice());
+        value[32..40].copy_from_slice(&self.safe_head.number.to_be_bytes());
+        value[40..].copy_from_slice(self.safe_head.hash.as_slice());
+        value
+    }
+
+    /// Decodes an entry from its database key and value.
+    fn decode(key: &[u8], value: &[u8]) -> Result<Self, SafeDbError> {
+        let key: [u8; 8] = key.try_into().map_err(|_| SafeDbError::Corrupt)?;
+        if value.len() != ENTRY_VALUE_LEN {
+            return Err(SafeDbError::Corrupt);
+        }
+        let safe_head_number: [u8; 8] =
+            value[32..40].try_into().map_err(|_| SafeDbError::Corrupt)?;
+        Ok(Self {
+            l1_block: BlockNumHash {
+                number: u64::from_be_bytes(key),
+                hash: B256::from_slice(&value[..32]),
+            },
+            safe_head: BlockNumHash {
+                number: u64::from_be_bytes(safe_head_number),
+                hash: B256::from_slice(&value[40..]),
+            },
+        })
+    }
+}
+
+/// An error that occurred while accessing the [`SafeHeadDb`].
+#[derive(Debug, Error)]
+pub enum SafeDbError {
+    /// Safe head tracking is disabled.
+    #[error("safe head tracking is disabled")]
+    Disabled,
+    /// The underlying database returned an error.
+    #[error("safe head database error: {0}")]
+    Database(#[from] rocksdb::Error),
+    /// An entry in the database could not be decoded.
+    #[error("safe head database entry is corrupt")]
+    Corrupt,
+}
+
+/// A database that persists the L2 safe head derived from each L1 block, allowing the safe head
+/// at any L1 block to be queried without re-deriving the chain.
+///
+/// Entries are keyed by L1 block number. When the safe head advances multiple times while
+/// deriving from the same L1 block, only the latest safe head is kept.
+#[derive(Debug)]
+pub struct SafeHeadDb {
+    /// The underlying database.
+    db: DB,
+}
+
+impl SafeHeadDb {
+    /// Opens the [`SafeHeadDb`] at the given path, creating it if it does not exist.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, SafeDbError> {
+        let mut options = Options::default();
+        options.create_if_missing(true);
+        Ok(Self { db: DB::open(&options, path)? })
+    }
+
+    /// Records that the given L2 safe head was derived from the given L1 block.
+    pub fn record(&self, entry: SafeHeadEntry) -> Result<(), SafeDbError> {
+        self.db.put(SafeHeadEntry::key(entry.l1_block.number), entry.value())?;
+        Ok(())
+    }
+
+    /// Returns the most recent safe head derived from an L1 block at or before the given L1
+    /// block number, if any.
+    pub fn safe_head_at_l1_block(
+        &self,
+        l1_block_number: u64,
+    ) -> Result<Option<SafeHeadEntry>, SafeDbError> {
+        let key = SafeHeadEntry::key(l1_block_number);
+        let mut iter = self.db.iterator(IteratorMode::From(&key, Direction::Reverse));
+        iter.next().transpose()?.map(|(key, value)| SafeHeadEntry::decode(&key, &value)).transpose()
+    }
+
+    /// Flushes the recorded entries to disk.
+    pub fn flush(&self) -> Result<(), SafeDbError> {
+        self.db.flush_wal(true)?;
+        self.db.flush()?;
+        Ok(())
+    }
+
+    /// Removes the entries whose safe head is ahead of the given L2 block number. This must be
+    /// called when the safe head is reset, since the removed safe heads may no longer be
+    /// canonical.
+    pub fn truncate(&self, safe_head_number: u64) -> Result<(), SafeDbError> {
+        let mut batch = WriteBatch::default();
+        for item in self.db.iterator(IteratorMode::End) {
+            let (key, value) = item?;
+            let entry = SafeHeadEntry::decode(&key, &value)?;
+            if entry.safe_head.number <= safe_head_number {
+                break;
+            }
+            batch.delete(key);
+        }
+        self.db.write(batch)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(l1: u64, l2: u64) -> SafeHeadEntry {
+        SafeHeadEntry {
+            l1_block: BlockNumHash { number: l1, hash: B256::with_last_byte(l1 as u8) },
+            safe_head: BlockNumHash { number: l2, hash: B256::with_last_byte(l2 as u8) },
+        }
+    }
+
+    #[test]
+    fn test_safe_head_at_l1_block() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = SafeHeadDb::open(dir.path()).unwrap();
+
+        db.record(entry(10, 100)).unwrap();
+        db.record(entry(12, 110)).unwrap();
+        db.record(entry(12, 115)).unwrap();
+
+        assert_eq!(db.safe_head_at_l1_block(9).unwrap(), None);
+        assert_eq!(db.safe_head_at_l1_block(10).unwrap(), Some(entry(10, 100)));
+        assert_eq!(db.safe_head_at_l1_block(11).unwrap(), Some(entry(10, 100)));
+        assert_eq!(db.safe_head_at_l1_block(20).unwrap(), Some(entry(12, 115)));
+    }
+
+    #[test]
+    fn test_safe_head_db_truncate() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = SafeHeadDb::open(dir.path()).unwrap();
+
+        db.record(entry(10, 100)).unwrap();
+        db.record(entry(11, 105)).unwrap();
+        db.record(entry(12, 110)).unwrap();
+        db.truncate(105).unwrap();
+
+        assert_eq!(db.safe_head_at_l1_block(20).unwrap(), Some(entry(11, 105)));
+    }
+
+    #[test]
+    fn test_safe_head_db_persists() {
+        let dir = tempfile::tempdir().unwrap();
+        SafeHeadDb::open(dir.path()).unwrap().record(entry(10, 100)).unwrap();
+
+        let db = SafeHeadDb::open(dir.path()).unwrap();
+        assert_eq!(db.safe_head_at_l1_block(10).unwrap(), Some(entry(10, 100)));
+    }
+}
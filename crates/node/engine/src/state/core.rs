@@ -4,6 +4,7 @@ use crate::Metrics;
 use alloy_rpc_types_engine::ForkchoiceState;
 use kona_protocol::L2BlockInfo;
 use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
 
 /// The synchronization state of the execution layer across different safety levels.
 ///
@@ -158,6 +159,14 @@ pub struct EngineState {
     /// because engine may forgot backupUnsafeHead or backupUnsafeHead is not part
     /// of the chain.
     pub need_fcu_call_backup_unsafe_reorg: bool,
+
+    /// Whether the sync state is ahead of the forkchoice state last sent to the EL, because the
+    /// forkchoice update of an inserted payload was deferred. See
+    /// [`crate::InsertTask::with_forkchoice_debounce`].
+    pub forkchoice_update_pending: bool,
+
+    /// The instant at which the last forkchoice update was sent to the EL, if any.
+    pub last_forkchoice_update: Option<Instant>,
 }
 
 impl EngineState {
@@ -171,6 +180,11 @@ impl EngineState {
     pub fn needs_consolidation(&self) -> bool {
         self.sync_state.safe_head() != self.sync_state.unsafe_head()
     }
+
+    /// Returns `true` if a forkchoice update was sent to the EL within the given interval.
+    pub fn forkchoice_updated_within(&self, interval: Duration) -> bool {
+        self.last_forkchoice_update.is_some_and(|last| last.elapsed() < interval)
+    }
 }
 
 #[cfg(test)]
@@ -255,4 +269,14 @@ mod test {
             format!("kona_node_block_labels{{label=\"{label_name}\"}} {number}").as_str()
         ));
     }
+
+    #[test]
+    fn test_forkchoice_updated_within() {
+        let mut state = EngineState::default();
+        assert!(!state.forkchoice_updated_within(Duration::from_secs(60)));
+
+        state.last_forkchoice_update = Some(Instant::now());
+        assert!(state.forkchoice_updated_within(Duration::from_secs(60)));
+        assert!(!state.forkchoice_updated_within(Duration::ZERO));
+    }
 }
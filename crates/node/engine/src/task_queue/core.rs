@@ -10,7 +10,7 @@ use alloy_provider::Provider;
 use alloy_rpc_types_eth::Transaction;
 use kona_genesis::{RollupConfig, SystemConfig};
 use kona_protocol::{BlockInfo, L2BlockInfo, OpBlockConversionError, to_system_config};
-use kona_sources::{SyncStartError, find_starting_forkchoice};
+use kona_sources::{SyncStartError, TrustedCheckpoint, find_starting_forkchoice};
 use op_alloy_consensus::OpTxEnvelope;
 use std::{collections::BinaryHeap, sync::Arc};
 use thiserror::Error;
@@ -39,6 +39,8 @@ pub struct Engine {
     task_queue_length: Sender<usize>,
     /// The task queue.
     tasks: BinaryHeap<EngineTask>,
+    /// The trusted L2 checkpoint that resets start from, if any.
+    checkpoint: Option<TrustedCheckpoint>,
 }
 
 impl Engine {
@@ -48,7 +50,20 @@ impl Engine {
         state_sender: Sender<EngineState>,
         task_queue_length: Sender<usize>,
     ) -> Self {
-        Self { state: initial_state, state_sender, task_queue_length, tasks: BinaryHeap::default() }
+        Self {
+            state: initial_state,
+            state_sender,
+            task_queue_length,
+            tasks: BinaryHeap::default(),
+            checkpoint: None,
+        }
+    }
+
+    /// Sets the [`TrustedCheckpoint`] that resets start from while the safe head of the execution
+    /// layer is behind it.
+    pub const fn with_trusted_checkpoint(mut self, checkpoint: Option<TrustedCheckpoint>) -> Self {
+        self.checkpoint = checkpoint;
+        self
     }
 
     /// Returns a reference to the inner [`EngineState`].
@@ -74,8 +89,9 @@ impl Engine {
     }
 
     /// Resets the engine by finding a plausible sync starting point via
-    /// [`find_starting_forkchoice`]. The state will be updated to the starting point, and a
-    /// forkchoice update will be enqueued in order to reorg the execution layer.
+    /// [`find_starting_forkchoice`], or via [`TrustedCheckpoint::starting_forkchoice`] if a
+    /// checkpoint is set. The state will be updated to the starting point, and a forkchoice update
+    /// will be enqueued in order to reorg the execution layer.
     pub async fn reset(
         &mut self,
         client: Arc<EngineClient>,
@@ -84,8 +100,16 @@ impl Engine {
         // Clear any outstanding tasks to prepare for the reset.
         self.clear();
 
-        let start =
-            find_starting_forkchoice(&config, client.l1_provider(), client.l2_engine()).await?;
+        let start = match &self.checkpoint {
+            Some(checkpoint) => {
+                checkpoint
+                    .starting_forkchoice(&config, client.l1_provider(), client.l2_engine())
+                    .await?
+            }
+            None => {
+                find_starting_forkchoice(&config, client.l1_provider(), client.l2_engine()).await?
+            }
+        };
 
         // Retry to synchronize the engine until we succeeds or a critical error occurs.
         while let Err(err) = SynchronizeTask::new(
@@ -144,6 +168,22 @@ impl Engine {
         Ok((start.safe, l1_origin_info, system_config))
     }
 
+    /// Sends a deferred forkchoice update to the EL with the latest heads, if one is pending.
+    pub async fn flush_forkchoice(
+        &mut self,
+        client: Arc<EngineClient>,
+        config: Arc<RollupConfig>,
+    ) -> Result<(), SynchronizeTaskError> {
+        if !self.state.forkchoice_update_pending {
+            return Ok(());
+        }
+
+        SynchronizeTask::flush_pending(client, config, &mut self.state).await?;
+        self.state_sender.send_replace(self.state);
+
+        Ok(())
+    }
+
     /// Clears the task queue.
     pub fn clear(&mut self) {
         self.tasks.clear();
@@ -17,7 +17,14 @@ use std::{
     sync::Arc,
     time::{Duration, Instant, SystemTime},
 };
-use tokio::{sync::mpsc, time::sleep};
+use tokio::{
+    sync::{mpsc, oneshot},
+    time::sleep,
+};
+
+/// A request to commit a sealed payload before it is inserted into the engine, along with the
+/// channel to acknowledge whether the payload was committed on.
+pub type PayloadCommitRequest = (OpExecutionPayloadEnvelope, oneshot::Sender<bool>);
 
 /// Task for building new blocks with automatic forkchoice synchronization.
 ///
@@ -26,7 +33,9 @@ use tokio::{sync::mpsc, time::sleep};
 /// 1. **Automatic Forkchoice Updates**: Performs initial `engine_forkchoiceUpdated` call with
 ///    payload attributes to initiate block building on the execution layer
 /// 2. **Payload Construction**: Retrieves the built payload using `engine_getPayload`
-/// 3. **Block Import**: Imports the payload using [`InsertTask`] for canonicalization
+/// 3. **Payload Commitment**: Optionally commits the sealed payload, e.g. to the conductor, before
+///    it is imported. A payload that is not committed is dropped.
+/// 4. **Block Import**: Imports the payload using [`InsertTask`] for canonicalization
 ///
 /// ## Forkchoice Integration
 ///
@@ -56,6 +65,9 @@ pub struct BuildTask {
     /// An optional channel to send the built [`OpExecutionPayloadEnvelope`] to, after the block
     /// has been built, imported, and canonicalized.
     pub payload_tx: Option<mpsc::Sender<OpExecutionPayloadEnvelope>>,
+    /// An optional channel to commit the sealed payload to before it is imported. The payload is
+    /// only imported if the commitment is acknowledged.
+    pub commit_tx: Option<mpsc::Sender<PayloadCommitRequest>>,
 }
 
 impl BuildTask {
@@ -67,7 +79,26 @@ impl BuildTask {
         is_attributes_derived: bool,
         payload_tx: Option<mpsc::Sender<OpExecutionPayloadEnvelope>>,
     ) -> Self {
-        Self { engine, cfg, attributes, is_attributes_derived, payload_tx }
+        Self { engine, cfg, attributes, is_attributes_derived, payload_tx, commit_tx: None }
+    }
+
+    /// Sets the channel to commit the sealed payload to before it is imported.
+    pub fn with_commit_tx(mut self, commit_tx: mpsc::Sender<PayloadCommitRequest>) -> Self {
+        self.commit_tx = Some(commit_tx);
+        self
+    }
+
+    /// Commits the sealed payload through the given channel, and returns whether the commitment
+    /// was acknowledged.
+    async fn commit_payload(
+        commit_tx: &mpsc::Sender<PayloadCommitRequest>,
+        payload: OpExecutionPayloadEnvelope,
+    ) -> bool {
+        let (ack_tx, ack_rx) = oneshot::channel();
+        if commit_tx.send((payload, ack_tx)).await.is_err() {
+            return false;
+        }
+        ack_rx.await.unwrap_or(false)
     }
 
     /// Starts the block building process by sending an initial `engine_forkchoiceUpdate` call with
@@ -294,6 +325,20 @@ impl EngineTaskExt for BuildTask {
         )
         .map_err(BuildTaskError::FromBlock)?;
 
+        // Commit the sealed payload before inserting it, if required.
+        let committed = match &self.commit_tx {
+            Some(commit_tx) => Self::commit_payload(commit_tx, new_payload.clone()).await,
+            None => true,
+        };
+        if !committed {
+            warn!(
+                target: "engine_builder",
+                l2_number = new_block_ref.block_info.number,
+                "Built payload was not committed, dropping it"
+            );
+            return Ok(());
+        }
+
         // Insert the new block into the engine.
         match InsertTask::new(
             Arc::clone(&self.engine),
@@ -318,15 +363,9 @@ impl EngineTaskExt for BuildTask {
             {
                 warn!(target: "engine_builder", error = ?e, "Re-attempting payload import with deposits only.");
                 // HOLOCENE: Re-attempt payload import with deposits only
-                match Self::new(
-                    self.engine.clone(),
-                    self.cfg.clone(),
-                    self.attributes.as_deposits_only(),
-                    self.is_attributes_derived,
-                    self.payload_tx.clone(),
-                )
-                .execute(state)
-                .await
+                match (Self { attributes: self.attributes.as_deposits_only(), ..self.clone() })
+                    .execute(state)
+                    .await
                 {
                     Ok(_) => {
                         info!(target: "engine_builder", "Successfully imported deposits-only payload")
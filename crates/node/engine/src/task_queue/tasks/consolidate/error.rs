@@ -21,6 +21,14 @@ pub enum ConsolidateTaskError {
     /// The consolidation forkchoice update call to the engine api failed.
     #[error(transparent)]
     ForkchoiceUpdateFailed(#[from] SynchronizeTaskError),
+    /// The safe chain diverged from the unsafe chain deeper than the maximum reorg depth.
+    #[error("Unsafe chain reorg of depth {depth} exceeds the maximum reorg depth of {max}")]
+    ReorgTooDeep {
+        /// The number of unsafe blocks that would be reorged out.
+        depth: u64,
+        /// The maximum reorg depth.
+        max: u64,
+    },
 }
 
 impl EngineTaskError for ConsolidateTaskError {
@@ -30,6 +38,7 @@ impl EngineTaskError for ConsolidateTaskError {
             Self::FailedToFetchUnsafeL2Block => EngineTaskErrorSeverity::Temporary,
             Self::BuildTaskFailed(inner) => inner.severity(),
             Self::ForkchoiceUpdateFailed(inner) => inner.severity(),
+            Self::ReorgTooDeep { .. } => EngineTaskErrorSeverity::Critical,
         }
     }
 }
@@ -23,6 +23,9 @@ pub struct ConsolidateTask {
     pub attributes: OpAttributesWithParent,
     /// Whether or not the payload was derived, or created by the sequencer.
     pub is_attributes_derived: bool,
+    /// The maximum number of unsafe blocks that may be reorged out when the safe chain diverges
+    /// from the unsafe chain, if any.
+    pub max_reorg_depth: Option<u64>,
 }
 
 impl ConsolidateTask {
@@ -33,7 +36,44 @@ impl ConsolidateTask {
         attributes: OpAttributesWithParent,
         is_attributes_derived: bool,
     ) -> Self {
-        Self { client, cfg: config, attributes, is_attributes_derived }
+        Self { client, cfg: config, attributes, is_attributes_derived, max_reorg_depth: None }
+    }
+
+    /// Sets the maximum number of unsafe blocks that may be reorged out when the safe chain
+    /// diverges from the unsafe chain. Deeper reorgs fail with a critical error rather than
+    /// being applied, so that they can be investigated by the operator.
+    pub const fn with_max_reorg_depth(mut self, max_reorg_depth: Option<u64>) -> Self {
+        self.max_reorg_depth = max_reorg_depth;
+        self
+    }
+
+    /// Checks the depth of the unsafe chain reorg caused by the attributes diverging from the
+    /// unsafe chain, against the maximum reorg depth.
+    fn check_reorg_depth(&self, state: &EngineState) -> Result<(), ConsolidateTaskError> {
+        let unsafe_head = state.sync_state.unsafe_head().block_info.number;
+        let depth = unsafe_head.saturating_sub(self.attributes.parent.block_info.number);
+
+        if let Some(max) = self.max_reorg_depth.filter(|&max| depth > max) {
+            error!(
+                target: "engine",
+                depth,
+                max,
+                unsafe_head,
+                "Safe chain diverged from the unsafe chain beyond the maximum reorg depth"
+            );
+            return Err(ConsolidateTaskError::ReorgTooDeep { depth, max });
+        }
+
+        kona_macros::inc!(counter, crate::Metrics::UNSAFE_REORG_COUNT);
+        kona_macros::set!(gauge, crate::Metrics::UNSAFE_REORG_DEPTH, depth as f64);
+
+        warn!(
+            target: "engine",
+            depth,
+            unsafe_head,
+            "Safe chain diverged from the unsafe chain, reorging the unsafe chain"
+        );
+        Ok(())
     }
 
     /// Executes a new [`BuildTask`].
@@ -158,6 +198,7 @@ impl ConsolidateTask {
             block_hash = %block_hash,
             "Attributes mismatch! Executing build task to initiate reorg",
         );
+        self.check_reorg_depth(state)?;
         self.execute_build_task(state).await
     }
 }
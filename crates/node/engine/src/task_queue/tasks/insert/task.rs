@@ -17,7 +17,10 @@ use op_alloy_provider::ext::engine::OpEngineApi;
 use op_alloy_rpc_types_engine::{
     OpExecutionPayload, OpExecutionPayloadEnvelope, OpExecutionPayloadSidecar,
 };
-use std::{sync::Arc, time::Instant};
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 /// The task to insert a payload into the execution engine.
 #[derive(Debug, Clone)]
@@ -31,6 +34,8 @@ pub struct InsertTask {
     /// If the payload is safe this is true.
     /// A payload is safe if it is derived from a safe block.
     is_payload_safe: bool,
+    /// The interval within which forkchoice updates are debounced, if any.
+    forkchoice_debounce: Option<Duration>,
 }
 
 impl InsertTask {
@@ -41,7 +46,33 @@ impl InsertTask {
         envelope: OpExecutionPayloadEnvelope,
         is_attributes_derived: bool,
     ) -> Self {
-        Self { client, rollup_config, envelope, is_payload_safe: is_attributes_derived }
+        Self {
+            client,
+            rollup_config,
+            envelope,
+            is_payload_safe: is_attributes_derived,
+            forkchoice_debounce: None,
+        }
+    }
+
+    /// Debounces the forkchoice update that canonicalizes the inserted payload.
+    ///
+    /// If a forkchoice update was sent to the EL within the given interval, the forkchoice update
+    /// of an unsafe payload is deferred: the sync state is advanced to the payload, and the
+    /// latest heads are sent to the EL by the next forkchoice update, before the next task other
+    /// than an insert is executed, or when the pending update is flushed via
+    /// [`crate::Engine::flush_forkchoice`]. This batches forkchoice updates during rapid unsafe
+    /// block import.
+    pub const fn with_forkchoice_debounce(mut self, interval: Option<Duration>) -> Self {
+        self.forkchoice_debounce = interval;
+        self
+    }
+
+    /// Returns `true` if the forkchoice update of the payload should be deferred.
+    fn should_defer_forkchoice(&self, state: &EngineState) -> bool {
+        !self.is_payload_safe &&
+            self.forkchoice_debounce
+                .is_some_and(|interval| state.forkchoice_updated_within(interval))
     }
 
     /// Checks the response of the `engine_newPayload` call.
@@ -117,26 +148,41 @@ impl EngineTaskExt for InsertTask {
         if !self.check_new_payload_status(&response.status) {
             return Err(InsertTaskError::UnexpectedPayloadStatus(response.status));
         }
+        self.client.verify_payload(&self.envelope);
         let insert_duration = insert_time_start.elapsed();
 
         let new_unsafe_ref =
             L2BlockInfo::from_block_and_genesis(&block, &self.rollup_config.genesis)
                 .map_err(InsertTaskError::L2BlockInfoConstruction)?;
 
-        // Send a FCU to canonicalize the imported block.
-        SynchronizeTask::new(
-            Arc::clone(&self.client),
-            self.rollup_config.clone(),
-            EngineSyncStateUpdate {
-                cross_unsafe_head: Some(new_unsafe_ref),
-                unsafe_head: Some(new_unsafe_ref),
-                local_safe_head: self.is_payload_safe.then_some(new_unsafe_ref),
-                safe_head: self.is_payload_safe.then_some(new_unsafe_ref),
-                ..Default::default()
-            },
-        )
-        .execute(state)
-        .await?;
+        let state_update = EngineSyncStateUpdate {
+            cross_unsafe_head: Some(new_unsafe_ref),
+            unsafe_head: Some(new_unsafe_ref),
+            local_safe_head: self.is_payload_safe.then_some(new_unsafe_ref),
+            safe_head: self.is_payload_safe.then_some(new_unsafe_ref),
+            ..Default::default()
+        };
+
+        if self.should_defer_forkchoice(state) {
+            // Defer the FCU, advancing the sync state so that the latest heads are sent to the EL
+            // with the next forkchoice update.
+            state.sync_state = state.sync_state.apply_update(state_update);
+            state.forkchoice_update_pending = true;
+            trace!(
+                target: "engine",
+                number = new_unsafe_ref.block_info.number,
+                "Deferred forkchoice update of inserted block"
+            );
+        } else {
+            // Send a FCU to canonicalize the imported block.
+            SynchronizeTask::new(
+                Arc::clone(&self.client),
+                self.rollup_config.clone(),
+                state_update,
+            )
+            .execute(state)
+            .await?;
+        }
 
         let total_duration = time_start.elapsed();
 
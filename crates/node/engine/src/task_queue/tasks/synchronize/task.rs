@@ -54,6 +54,22 @@ impl SynchronizeTask {
         Self { client, rollup, state_update }
     }
 
+    /// Sends the forkchoice update deferred by an [`InsertTask`] to the EL with the latest heads,
+    /// if one is pending.
+    ///
+    /// [`InsertTask`]: crate::InsertTask
+    pub async fn flush_pending(
+        client: Arc<EngineClient>,
+        rollup: Arc<RollupConfig>,
+        state: &mut EngineState,
+    ) -> Result<(), SynchronizeTaskError> {
+        if !state.forkchoice_update_pending {
+            return Ok(());
+        }
+
+        Self::new(client, rollup, EngineSyncStateUpdate::default()).execute(state).await
+    }
+
     /// Checks the response of the `engine_forkchoiceUpdated` call, and updates the sync status if
     /// necessary.
     fn check_forkchoice_updated_status(
@@ -99,13 +115,17 @@ impl EngineTaskExt for SynchronizeTask {
         // A forkchoice update is not needed if...
         // 1. The engine state is not default (initial forkchoice state has been emitted), and
         // 2. The new sync state is the same as the current sync state (no changes to the sync
-        //    state).
+        //    state), and
+        // 3. No deferred forkchoice update is pending.
         //
         // NOTE:
         // We shouldn't retry the synchronize task there. Since the `sync_state` is only updated
         // inside the `SynchronizeTask` (except inside the ConsolidateTask, when the block is not
         // the last in the batch) - the engine will get stuck retrying the `SynchronizeTask`
-        if state.sync_state != Default::default() && state.sync_state == new_sync_state {
+        if state.sync_state != Default::default() &&
+            state.sync_state == new_sync_state &&
+            !state.forkchoice_update_pending
+        {
             debug!(target: "engine", ?new_sync_state, "No forkchoice update needed");
             return Ok(());
         }
@@ -145,6 +165,8 @@ impl EngineTaskExt for SynchronizeTask {
 
         // Apply the new sync state to the engine state.
         state.sync_state = new_sync_state;
+        state.forkchoice_update_pending = false;
+        state.last_forkchoice_update = Some(std::time::Instant::now());
 
         let fcu_duration = fcu_time_start.elapsed();
         debug!(
@@ -154,6 +176,16 @@ impl EngineTaskExt for SynchronizeTask {
             response = ?valid_response,
             "Forkchoice updated"
         );
+        trace!(
+            target: "journal",
+            event = "forkchoice_updated",
+            unsafe_number = new_sync_state.unsafe_head().block_info.number,
+            unsafe_hash = %forkchoice.head_block_hash,
+            safe_number = new_sync_state.safe_head().block_info.number,
+            safe_hash = %forkchoice.safe_block_hash,
+            finalized_number = new_sync_state.finalized_head().block_info.number,
+            finalized_hash = %forkchoice.finalized_block_hash,
+        );
 
         Ok(())
     }
@@ -2,7 +2,7 @@
 //!
 //! [`Engine`]: crate::Engine
 
-use super::{BuildTask, ConsolidateTask, FinalizeTask, InsertTask};
+use super::{BuildTask, ConsolidateTask, FinalizeTask, InsertTask, SynchronizeTask};
 use crate::{
     BuildTaskError, ConsolidateTaskError, EngineState, FinalizeTaskError, InsertTaskError,
 };
@@ -99,12 +99,30 @@ pub enum EngineTask {
 
 impl EngineTask {
     /// Executes the task without consuming it.
+    ///
+    /// A forkchoice update deferred by an [`InsertTask`] is sent before any other task, as the
+    /// inserted blocks are not canonical in the EL until then.
     async fn execute_inner(&self, state: &mut EngineState) -> Result<(), EngineTaskErrors> {
         match self.clone() {
             Self::Insert(task) => task.execute(state).await?,
-            Self::Build(task) => task.execute(state).await?,
-            Self::Consolidate(task) => task.execute(state).await?,
-            Self::Finalize(task) => task.execute(state).await?,
+            Self::Build(task) => {
+                SynchronizeTask::flush_pending(task.engine.clone(), task.cfg.clone(), state)
+                    .await
+                    .map_err(BuildTaskError::from)?;
+                task.execute(state).await?
+            }
+            Self::Consolidate(task) => {
+                SynchronizeTask::flush_pending(task.client.clone(), task.cfg.clone(), state)
+                    .await
+                    .map_err(ConsolidateTaskError::from)?;
+                task.execute(state).await?
+            }
+            Self::Finalize(task) => {
+                SynchronizeTask::flush_pending(task.client.clone(), task.cfg.clone(), state)
+                    .await
+                    .map_err(FinalizeTaskError::from)?;
+                task.execute(state).await?
+            }
         };
 
         Ok(())
@@ -221,3 +239,81 @@ impl EngineTaskExt for EngineTask {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::EngineClient;
+    use alloy_eips::BlockNumHash;
+    use alloy_rpc_types_engine::{
+        ExecutionPayloadV1, ForkchoiceUpdated, PayloadStatus, PayloadStatusEnum,
+    };
+    use alloy_transport::mock::Asserter;
+    use arbitrary::Arbitrary;
+    use kona_genesis::RollupConfig;
+    use kona_protocol::{L2BlockInfo, OpAttributesWithParent};
+    use op_alloy_consensus::OpBlock;
+    use op_alloy_rpc_types_engine::{
+        OpExecutionPayload, OpExecutionPayloadEnvelope, OpPayloadAttributes,
+    };
+    use std::{
+        sync::Arc,
+        time::{Duration, Instant},
+    };
+
+    #[tokio::test]
+    async fn test_consolidate_flushes_deferred_forkchoice() {
+        let mut payload =
+            ExecutionPayloadV1::arbitrary(&mut arbitrary::Unstructured::new(&[])).unwrap();
+        payload.block_number = 1;
+        let block: OpBlock = OpExecutionPayload::V1(payload.clone()).try_into_block().unwrap();
+
+        // Insert the payload as the first block after genesis, skipping the L1 info deposit.
+        let mut cfg = RollupConfig::default();
+        cfg.genesis.l2 = BlockNumHash { number: 1, hash: block.header.hash_slow() };
+        let cfg = Arc::new(cfg);
+
+        let asserter = Asserter::new();
+        let client = Arc::new(EngineClient::new_mocked(&asserter, cfg.clone()));
+        let mut state =
+            EngineState { last_forkchoice_update: Some(Instant::now()), ..Default::default() };
+
+        // The forkchoice update of the inserted block is deferred.
+        asserter.push_success(&PayloadStatus::new(PayloadStatusEnum::Valid, None));
+        let envelope = OpExecutionPayloadEnvelope {
+            execution_payload: OpExecutionPayload::V1(payload),
+            parent_beacon_block_root: None,
+        };
+        let insert = InsertTask::new(client.clone(), cfg.clone(), envelope, false)
+            .with_forkchoice_debounce(Some(Duration::from_secs(60)));
+        EngineTask::Insert(Box::new(insert)).execute_inner(&mut state).await.unwrap();
+        assert!(state.forkchoice_update_pending);
+        assert_eq!(state.sync_state.unsafe_head().block_info.number, 1);
+
+        // The deferred forkchoice update is sent before the unsafe block is fetched for
+        // consolidation.
+        asserter.push_success(&ForkchoiceUpdated::new(PayloadStatus::new(
+            PayloadStatusEnum::Valid,
+            None,
+        )));
+        asserter.push_success(&serde_json::Value::Null);
+        let attributes = OpAttributesWithParent::new(
+            OpPayloadAttributes::default(),
+            L2BlockInfo::default(),
+            None,
+            true,
+        );
+        let consolidate = ConsolidateTask::new(client, cfg, attributes, true);
+        let err = EngineTask::Consolidate(Box::new(consolidate))
+            .execute_inner(&mut state)
+            .await
+            .unwrap_err();
+
+        assert!(matches!(
+            err,
+            EngineTaskErrors::Consolidate(ConsolidateTaskError::MissingUnsafeL2Block(1))
+        ));
+        assert!(!state.forkchoice_update_pending);
+        assert!(asserter.read_q().is_empty());
+    }
+}
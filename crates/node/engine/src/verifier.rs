@@ -0,0 +1,336 @@
+//! Contains the [`PayloadVerifier`], which cross-checks unsafe payloads against a secondary
+//! execution layer.
+
+use crate::client::HyperAuthClient;
+use alloy_primitives::B256;
+use alloy_provider::{RootProvider, ext::EngineApi};
+use alloy_rpc_types_engine::{ExecutionPayloadInputV2, ForkchoiceState, PayloadStatusEnum};
+use alloy_transport::{RpcError, TransportErrorKind};
+use alloy_transport_http::Http;
+use op_alloy_network::Optimism;
+use op_alloy_provider::ext::engine::OpEngineApi;
+use op_alloy_rpc_types_engine::{OpExecutionPayload, OpExecutionPayloadEnvelope};
+use tokio::sync::mpsc;
+
+/// The verdict of the secondary execution layer on an unsafe payload.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PayloadVerdict {
+    /// The payload was executed and is valid.
+    Valid,
+    /// The payload is invalid, diverging from the payloads accepted by the node. Contains the
+    /// validation error reported by the secondary execution layer.
+    Invalid(String),
+    /// The payload could not be executed, because the secondary execution layer does not hold the
+    /// state of its parent.
+    Unverified,
+}
+
+impl PayloadVerdict {
+    /// Returns the metrics label of the verdict.
+    pub const fn label(&self) -> &'static str {
+        match self {
+            Self::Valid => "valid",
+            Self::Invalid(_) => "invalid",
+            Self::Unverified => "unverified",
+        }
+    }
+}
+
+impl From<PayloadStatusEnum> for PayloadVerdict {
+    fn from(status: PayloadStatusEnum) -> Self {
+        match status {
+            PayloadStatusEnum::Valid => Self::Valid,
+            PayloadStatusEnum::Invalid { validation_error } => Self::Invalid(validation_error),
+            PayloadStatusEnum::Syncing | PayloadStatusEnum::Accepted => Self::Unverified,
+        }
+    }
+}
+
+/// Cross-checks the payloads inserted by the node, whether received over gossip, built by the
+/// sequencer or derived, by submitting them to a secondary execution layer with
+/// `engine_newPayload`.
+///
+/// The secondary execution layer follows the unsafe chain of the node: its head is moved to each
+/// payload that is not invalid with `engine_forkchoiceUpdated`. A payload whose parent state is
+/// missing is unverified, and moving the head to it lets the secondary execution layer sync the
+/// missing blocks on its own.
+///
+/// This allows monitoring the integrity of the sequencer: a payload found invalid by the
+/// secondary execution layer is reported as a divergence, and a streak of unverified payloads as a
+/// secondary execution layer out of sync, through logs and metrics.
+#[derive(Debug, Clone)]
+pub struct PayloadVerifier {
+    /// The Engine API provider of the secondary execution layer.
+    provider: RootProvider<Optimism>,
+    /// The number of consecutive payloads that could not be verified.
+    unverified_streak: u64,
+}
+
+impl PayloadVerifier {
+    /// The number of payloads waiting to be verified, past which new payloads are skipped.
+    pub const QUEUE_CAPACITY: usize = 256;
+
+    /// The number of consecutive unverified payloads past which the secondary execution layer is
+    /// reported as out of sync, and again for each multiple of it.
+    pub const UNVERIFIED_STREAK_WARNING: u64 = 32;
+
+    /// Creates a new [`PayloadVerifier`] submitting payloads to the secondary execution layer
+    /// behind the given Engine API provider.
+    pub const fn new(provider: RootProvider<Optimism>) -> Self {
+        Self { provider, unverified_streak: 0 }
+    }
+
+    /// Submits the payload to the secondary execution layer, and returns its verdict.
+    pub async fn verify(
+        &self,
+        envelope: &OpExecutionPayloadEnvelope,
+    ) -> Result<PayloadVerdict, RpcError<TransportErrorKind>> {
+        let parent_beacon_block_root = envelope.parent_beacon_block_root.unwrap_or_default();
+        let status = match envelope.execution_payload.clone() {
+            OpExecutionPayload::V1(payload) => self.provider.new_payload_v1(payload).await?,
+            OpExecutionPayload::V2(payload) => {
+                let payload_input = ExecutionPayloadInputV2 {
+                    execution_payload: payload.payload_inner,
+                    withdrawals: Some(payload.withdrawals),
+                };
+                <RootProvider<Optimism> as OpEngineApi<Optimism, Http<HyperAuthClient>>>::new_payload_v2(
+                    &self.provider,
+                    payload_input,
+                )
+                .await?
+            }
+            OpExecutionPayload::V3(payload) => <RootProvider<Optimism> as OpEngineApi<
+                Optimism,
+                Http<HyperAuthClient>,
+            >>::new_payload_v3(
+                &self.provider, payload, parent_beacon_block_root
+            )
+            .await?,
+            OpExecutionPayload::V4(payload) => <RootProvider<Optimism> as OpEngineApi<
+                Optimism,
+                Http<HyperAuthClient>,
+            >>::new_payload_v4(
+                &self.provider, payload, parent_beacon_block_root
+            )
+            .await?,
+        };
+        Ok(status.status.into())
+    }
+
+    /// Moves the head of the secondary execution layer to the given payload. The safe and
+    /// finalized blocks are left unset, as the secondary execution layer only tracks the unsafe
+    /// chain.
+    async fn update_forkchoice(
+        &self,
+        envelope: &OpExecutionPayloadEnvelope,
+    ) -> Result<(), RpcError<TransportErrorKind>> {
+        let forkchoice = ForkchoiceState {
+            head_block_hash: envelope.execution_payload.block_hash(),
+            safe_block_hash: B256::ZERO,
+            finalized_block_hash: B256::ZERO,
+        };
+        match envelope.execution_payload {
+            OpExecutionPayload::V1(_) | OpExecutionPayload::V2(_) => {
+                <RootProvider<Optimism> as OpEngineApi<Optimism, Http<HyperAuthClient>>>::fork_choice_updated_v2(
+                    &self.provider,
+                    forkchoice,
+                    None,
+                )
+                .await?;
+            }
+            OpExecutionPayload::V3(_) | OpExecutionPayload::V4(_) => {
+                <RootProvider<Optimism> as OpEngineApi<Optimism, Http<HyperAuthClient>>>::fork_choice_updated_v3(
+                    &self.provider,
+                    forkchoice,
+                    None,
+                )
+                .await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Spawns a task verifying the payloads sent through the returned channel, in order. The task
+    /// exits once the channel is closed.
+    pub fn spawn(mut self) -> mpsc::Sender<OpExecutionPayloadEnvelope> {
+        let (tx, mut rx) = mpsc::channel::<OpExecutionPayloadEnvelope>(Self::QUEUE_CAPACITY);
+        tokio::spawn(async move {
+            while let Some(envelope) = rx.recv().await {
+                self.process(&envelope).await;
+            }
+        });
+        tx
+    }
+
+    /// Verifies the payload, moves the head of the secondary execution layer to it unless it is
+    /// invalid, and reports the verdict through logs and metrics. Returns the verdict, if the
+    /// payload could be submitted.
+    async fn process(&mut self, envelope: &OpExecutionPayloadEnvelope) -> Option<PayloadVerdict> {
+        let number = envelope.execution_payload.block_number();
+        let hash = envelope.execution_payload.block_hash();
+        let verdict = match self.verify(envelope).await {
+            Ok(verdict) => verdict,
+            Err(err) => {
+                warn!(target: "engine::verifier", number, %hash, %err, "Failed to verify payload");
+                kona_macros::inc!(counter, crate::Metrics::PAYLOAD_VERIFICATION, "verdict" => "error");
+                return None;
+            }
+        };
+        kona_macros::inc!(counter, crate::Metrics::PAYLOAD_VERIFICATION, "verdict" => verdict.label());
+
+        match &verdict {
+            PayloadVerdict::Valid => {
+                debug!(target: "engine::verifier", number, %hash, "Verified payload");
+                self.unverified_streak = 0;
+            }
+            PayloadVerdict::Invalid(validation_error) => {
+                error!(
+                    target: "engine::verifier",
+                    number,
+                    %hash,
+                    %validation_error,
+                    "Secondary execution layer found an unsafe payload invalid"
+                );
+                self.unverified_streak = 0;
+            }
+            PayloadVerdict::Unverified => {
+                debug!(
+                    target: "engine::verifier",
+                    number,
+                    %hash,
+                    "Secondary execution layer is missing the parent state, payload not verified"
+                );
+                self.unverified_streak += 1;
+                if self.unverified_streak % Self::UNVERIFIED_STREAK_WARNING == 0 {
+                    warn!(
+                        target: "engine::verifier",
+                        streak = self.unverified_streak,
+                        "Secondary execution layer could not verify the latest payloads, it may be out of sync"
+                    );
+                }
+            }
+        }
+        kona_macros::set!(
+            gauge,
+            crate::Metrics::PAYLOAD_VERIFIER_UNVERIFIED_STREAK,
+            self.unverified_streak as f64
+        );
+
+        if matches!(verdict, PayloadVerdict::Invalid(_)) {
+            return Some(verdict);
+        }
+        if let Err(err) = self.update_forkchoice(envelope).await {
+            warn!(target: "engine::verifier", number, %hash, %err, "Failed to update the forkchoice of the secondary execution layer");
+        }
+        Some(verdict)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_rpc_client::RpcClient;
+    use alloy_rpc_types_engine::{ExecutionPayloadV1, ForkchoiceUpdated, PayloadStatus};
+    use alloy_transport::mock::{Asserter, MockTransport};
+    use arbitrary::Arbitrary;
+
+    fn verifier(asserter: &Asserter) -> PayloadVerifier {
+        PayloadVerifier::new(RootProvider::new(RpcClient::new(
+            MockTransport::new(asserter.clone()),
+            false,
+        )))
+    }
+
+    fn payload() -> OpExecutionPayloadEnvelope {
+        let payload =
+            ExecutionPayloadV1::arbitrary(&mut arbitrary::Unstructured::new(&[])).unwrap();
+        OpExecutionPayloadEnvelope {
+            execution_payload: OpExecutionPayload::V1(payload),
+            parent_beacon_block_root: None,
+        }
+    }
+
+    fn push_status(asserter: &Asserter, status: PayloadStatusEnum) {
+        asserter.push_success(&PayloadStatus::new(status, None));
+    }
+
+    fn push_forkchoice_updated(asserter: &Asserter) {
+        asserter.push_success(&ForkchoiceUpdated::new(PayloadStatus::new(
+            PayloadStatusEnum::Valid,
+            None,
+        )));
+    }
+
+    #[test]
+    fn test_payload_verdict_from_status() {
+        assert_eq!(PayloadVerdict::from(PayloadStatusEnum::Valid), PayloadVerdict::Valid);
+        assert_eq!(
+            PayloadVerdict::from(PayloadStatusEnum::Invalid {
+                validation_error: "bad state root".to_string()
+            }),
+            PayloadVerdict::Invalid("bad state root".to_string())
+        );
+        assert_eq!(PayloadVerdict::from(PayloadStatusEnum::Syncing), PayloadVerdict::Unverified);
+        assert_eq!(PayloadVerdict::from(PayloadStatusEnum::Accepted), PayloadVerdict::Unverified);
+        assert_eq!(PayloadVerdict::Unverified.label(), "unverified");
+    }
+
+    #[tokio::test]
+    async fn test_process_valid_payload_updates_forkchoice() {
+        let asserter = Asserter::new();
+        let mut verifier = verifier(&asserter);
+
+        push_status(&asserter, PayloadStatusEnum::Valid);
+        push_forkchoice_updated(&asserter);
+        assert_eq!(verifier.process(&payload()).await, Some(PayloadVerdict::Valid));
+        assert!(asserter.read_q().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_process_invalid_payload_keeps_forkchoice() {
+        let asserter = Asserter::new();
+        let mut verifier = verifier(&asserter);
+
+        push_status(
+            &asserter,
+            PayloadStatusEnum::Invalid { validation_error: "bad state root".to_string() },
+        );
+        push_forkchoice_updated(&asserter);
+        assert_eq!(
+            verifier.process(&payload()).await,
+            Some(PayloadVerdict::Invalid("bad state root".to_string()))
+        );
+        // The forkchoice update is left unconsumed.
+        assert_eq!(asserter.read_q().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_process_unverified_streak() {
+        let asserter = Asserter::new();
+        let mut verifier = verifier(&asserter);
+
+        for streak in 1..=3 {
+            push_status(&asserter, PayloadStatusEnum::Syncing);
+            push_forkchoice_updated(&asserter);
+            assert_eq!(verifier.process(&payload()).await, Some(PayloadVerdict::Unverified));
+            assert_eq!(verifier.unverified_streak, streak);
+        }
+
+        // A verified payload ends the streak.
+        push_status(&asserter, PayloadStatusEnum::Valid);
+        push_forkchoice_updated(&asserter);
+        assert_eq!(verifier.process(&payload()).await, Some(PayloadVerdict::Valid));
+        assert_eq!(verifier.unverified_streak, 0);
+        assert!(asserter.read_q().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_process_rpc_error() {
+        let asserter = Asserter::new();
+        let mut verifier = verifier(&asserter);
+
+        asserter.push_failure_msg("connection refused");
+        assert_eq!(verifier.process(&payload()).await, None);
+        assert!(asserter.read_q().is_empty());
+    }
+}
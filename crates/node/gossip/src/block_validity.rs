@@ -71,6 +71,15 @@ pub enum BlockInvalidError {
         /// The height of the block.
         height: u64,
     },
+    /// The payload version is older than the version required by the hardforks active at the
+    /// block timestamp.
+    #[error("Outdated payload version. Required: v{required}, Received: v{received}")]
+    OutdatedVersion {
+        /// The payload version required at the block timestamp.
+        required: u8,
+        /// The received payload version.
+        received: u8,
+    },
     /// The block has already been seen.
     #[error("Block seen before")]
     BlockSeen {
@@ -176,6 +185,7 @@ impl BlockHandler {
                         BlockInvalidError::BlobGasUsed => "blob_gas_used",
                         BlockInvalidError::ExcessBlobGas => "excess_blob_gas",
                         BlockInvalidError::WithdrawalsRoot => "withdrawals_root",
+                        BlockInvalidError::OutdatedVersion { .. } => "outdated_version",
                         BlockInvalidError::BaseFeePerGasOverflow(_) => "invalid_block",
                     };
                     kona_macros::inc!(counter, Metrics::BLOCK_VALIDATION_FAILED, "reason" => reason);
@@ -244,27 +254,24 @@ impl BlockHandler {
 
         // CHECK: The signature is valid.
         let msg = envelope.payload_hash.signature_message(self.rollup_config.l2_chain_id.id());
-        let block_signer = *self.signer_recv.borrow();
 
         // The block has a valid signature.
         let Ok(msg_signer) = envelope.signature.recover_address_from_prehash(&msg) else {
             return Err(BlockInvalidError::Signature);
         };
 
-        // The block is signed by the expected signer (the unsafe block signer).
-        if msg_signer != block_signer {
-            return Err(BlockInvalidError::Signer { expected: msg_signer, received: block_signer });
+        // The block is signed by the expected signer (the unsafe block signer), or by the previous
+        // signer within the rotation grace window.
+        if !self.is_accepted_signer(msg_signer) {
+            return Err(BlockInvalidError::Signer {
+                expected: self.current_signer,
+                received: msg_signer,
+            });
         }
 
-        self.seen_hashes
-            .entry(envelope.payload.block_number())
-            .or_default()
-            .insert(envelope.payload.block_hash());
-
-        // Mark the block as seen.
-        if self.seen_hashes.len() >= Self::SEEN_HASH_CACHE_SIZE {
-            self.seen_hashes.pop_first();
-        }
+        // Mark the block as seen. Blocks are keyed by hash rather than by topic, so the same
+        // block received on another block topic is ignored.
+        self.mark_seen(envelope);
 
         Ok(())
     }
@@ -323,6 +330,20 @@ impl BlockHandler {
             validate_v3(rollup_config, &block.payload_inner, parent_beacon_block_root)
         }
 
+        // CHECK: The payload version is not older than the version required by the hardforks
+        // active at the block timestamp. Payloads gossiped on a newer topic are accepted, so that
+        // blocks published ahead of a fork activation by peers with skewed clocks are not dropped.
+        let required = self.payload_version(envelope.payload.timestamp());
+        let received = match &envelope.payload {
+            OpExecutionPayload::V1(_) => 1,
+            OpExecutionPayload::V2(_) => 2,
+            OpExecutionPayload::V3(_) => 3,
+            OpExecutionPayload::V4(_) => 4,
+        };
+        if received < required {
+            return Err(BlockInvalidError::OutdatedVersion { required, received });
+        }
+
         match &envelope.payload {
             OpExecutionPayload::V1(_) => Ok(()),
             OpExecutionPayload::V2(_) => Ok(()),
@@ -347,7 +368,7 @@ pub(crate) mod tests {
     use alloy_rlp::BufMut;
     use alloy_rpc_types_engine::{ExecutionPayloadV1, ExecutionPayloadV2, ExecutionPayloadV3};
     use arbitrary::{Arbitrary, Unstructured};
-    use kona_genesis::RollupConfig;
+    use kona_genesis::{HardForkConfig, RollupConfig};
     use op_alloy_consensus::OpTxEnvelope;
     use op_alloy_rpc_types_engine::{OpExecutionPayload, OpExecutionPayloadV4, PayloadHash};
 
@@ -688,6 +709,65 @@ pub(crate) mod tests {
         assert!(matches!(handler.block_valid(&envelope), Err(BlockInvalidError::Signer { .. })));
     }
 
+    /// Blocks signed by the previous unsafe block signer are accepted until the rotation grace
+    /// window elapses.
+    #[test]
+    fn test_signer_rotation_grace() {
+        let envelope = || OpNetworkPayloadEnvelope {
+            payload: OpExecutionPayload::V1(ExecutionPayloadV1::from_block_slow(&v1_valid_block())),
+            signature: Signature::test_signature(),
+            payload_hash: PayloadHash(B256::ZERO),
+            parent_beacon_block_root: None,
+        };
+
+        let msg = PayloadHash(B256::ZERO).signature_message(10);
+        let signer = Signature::test_signature().recover_address_from_prehash(&msg).unwrap();
+        let (signer_tx, unsafe_signer) = tokio::sync::watch::channel(signer);
+        let mut handler = BlockHandler::new(
+            RollupConfig { l2_chain_id: Chain::optimism_mainnet(), ..Default::default() },
+            unsafe_signer,
+        )
+        .with_signer_rotation_grace(std::time::Duration::from_secs(300));
+
+        // Rotate the signer: the previous signer is accepted within the grace window.
+        signer_tx.send(Address::with_last_byte(1)).unwrap();
+        assert!(handler.block_valid(&envelope()).is_ok());
+        assert_eq!(handler.current_signer, Address::with_last_byte(1));
+
+        // Once the grace window elapses, the previous signer is rejected.
+        handler.retired_signer = Some((signer, std::time::Instant::now()));
+        assert!(matches!(
+            handler.block_valid(&envelope()),
+            Err(BlockInvalidError::Signer { received, .. }) if received == signer
+        ));
+    }
+
+    /// Without a rotation grace window, blocks signed by the previous unsafe block signer are
+    /// rejected as soon as the rotation is observed.
+    #[test]
+    fn test_signer_rotation_without_grace() {
+        let envelope = OpNetworkPayloadEnvelope {
+            payload: OpExecutionPayload::V1(ExecutionPayloadV1::from_block_slow(&v1_valid_block())),
+            signature: Signature::test_signature(),
+            payload_hash: PayloadHash(B256::ZERO),
+            parent_beacon_block_root: None,
+        };
+
+        let msg = PayloadHash(B256::ZERO).signature_message(10);
+        let signer = Signature::test_signature().recover_address_from_prehash(&msg).unwrap();
+        let (signer_tx, unsafe_signer) = tokio::sync::watch::channel(signer);
+        let mut handler = BlockHandler::new(
+            RollupConfig { l2_chain_id: Chain::optimism_mainnet(), ..Default::default() },
+            unsafe_signer,
+        );
+
+        signer_tx.send(Address::with_last_byte(1)).unwrap();
+        assert!(matches!(
+            handler.block_valid(&envelope),
+            Err(BlockInvalidError::Signer { received, .. }) if received == signer
+        ));
+    }
+
     /// If we specify a non empty parent beacon block root for blocks with v1/v2 payloads we
     /// get a hash mismatch error because the decoder enforces that these versions of the execution
     /// payload don't contain the parent beacon block root.
@@ -763,6 +843,64 @@ pub(crate) mod tests {
         assert!(handler.block_valid(&envelope).is_ok());
     }
 
+    #[test]
+    fn test_v2_block_outdated_after_ecotone() {
+        let block = v2_valid_block();
+
+        let v2 = ExecutionPayloadV2::from_block_slow(&block);
+
+        let payload = OpExecutionPayload::V2(v2);
+        let envelope = OpNetworkPayloadEnvelope {
+            payload,
+            signature: Signature::test_signature(),
+            payload_hash: PayloadHash(B256::ZERO),
+            parent_beacon_block_root: None,
+        };
+
+        let msg = envelope.payload_hash.signature_message(10);
+        let signer = envelope.signature.recover_address_from_prehash(&msg).unwrap();
+        let (_, unsafe_signer) = tokio::sync::watch::channel(signer);
+        let mut handler = BlockHandler::new(
+            RollupConfig {
+                l2_chain_id: Chain::optimism_mainnet(),
+                hardforks: HardForkConfig { ecotone_time: Some(0), ..Default::default() },
+                ..Default::default()
+            },
+            unsafe_signer,
+        );
+
+        assert!(matches!(
+            handler.block_valid(&envelope),
+            Err(BlockInvalidError::OutdatedVersion { required: 3, received: 2 })
+        ));
+    }
+
+    #[test]
+    fn test_published_block_seen() {
+        let block = v2_valid_block();
+
+        let v2 = ExecutionPayloadV2::from_block_slow(&block);
+
+        let payload = OpExecutionPayload::V2(v2);
+        let envelope = OpNetworkPayloadEnvelope {
+            payload,
+            signature: Signature::test_signature(),
+            payload_hash: PayloadHash(B256::ZERO),
+            parent_beacon_block_root: None,
+        };
+
+        let msg = envelope.payload_hash.signature_message(10);
+        let signer = envelope.signature.recover_address_from_prehash(&msg).unwrap();
+        let (_, unsafe_signer) = tokio::sync::watch::channel(signer);
+        let mut handler = BlockHandler::new(
+            RollupConfig { l2_chain_id: Chain::optimism_mainnet(), ..Default::default() },
+            unsafe_signer,
+        );
+
+        handler.mark_seen(&envelope);
+        assert!(matches!(handler.block_valid(&envelope), Err(BlockInvalidError::BlockSeen { .. })));
+    }
+
     #[test]
     fn test_v2_non_empty_withdrawals() {
         let mut block = v2_valid_block();
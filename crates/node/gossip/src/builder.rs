@@ -2,15 +2,19 @@
 
 use alloy_primitives::Address;
 use kona_genesis::RollupConfig;
-use kona_peers::{PeerMonitoring, PeerScoreLevel};
+use kona_peers::{BanList, PeerMonitoring, PeerScoreLevel, TopicScoreWeights};
 use libp2p::{
-    Multiaddr, StreamProtocol, SwarmBuilder, gossipsub::Config, identity::Keypair,
+    Multiaddr, PeerId, SwarmBuilder, gossipsub::Config, identity::Keypair,
     noise::Config as NoiseConfig, tcp::Config as TcpConfig, yamux::Config as YamuxConfig,
 };
-use std::time::Duration;
+use std::{sync::Arc, time::Duration};
 use tokio::sync::watch::{self};
 
-use crate::{Behaviour, BlockHandler, GaterConfig, GossipDriver, GossipDriverBuilderError};
+use crate::{
+    Behaviour, BlockHandler, ConnectionGate, DEFAULT_PUBLISH_RATE_LIMIT,
+    DEFAULT_SIGNER_ROTATION_GRACE, GaterConfig, GossipDriver, GossipDriverBuilderError,
+    PayloadPublisher, PayloadSource, StaticPeers, SyncClient,
+};
 
 /// A builder for the [`GossipDriver`].
 #[derive(Debug)]
@@ -36,6 +40,21 @@ pub struct GossipDriverBuilder {
     gater_config: Option<GaterConfig>,
     /// Topic scoring. Disabled by default.
     topic_scoring: bool,
+    /// The [`TopicScoreWeights`] applied when topic scoring is enabled.
+    topic_score_weights: TopicScoreWeights,
+    /// The persisted [`BanList`], if any.
+    ban_list: Option<BanList>,
+    /// The [`PayloadSource`] serving sync requests, if any.
+    payload_source: Option<Arc<dyn PayloadSource>>,
+    /// The addresses of the static peers, which are kept connected to.
+    static_peers: Vec<Multiaddr>,
+    /// The trusted peers, which are protected from scoring-based disconnects and dial limits.
+    trusted_peers: Vec<PeerId>,
+    /// The maximum number of unsafe blocks published per second. Zero disables rate limiting.
+    publish_rate_limit: u32,
+    /// How long payloads signed by the previous unsafe block signer are accepted after a signer
+    /// rotation.
+    signer_rotation_grace: Duration,
 }
 
 impl GossipDriverBuilder {
@@ -57,6 +76,13 @@ impl GossipDriverBuilder {
             gater_config: None,
             rollup_config,
             topic_scoring: false,
+            topic_score_weights: TopicScoreWeights::DEFAULT,
+            ban_list: None,
+            payload_source: None,
+            static_peers: Vec::new(),
+            trusted_peers: Vec::new(),
+            publish_rate_limit: DEFAULT_PUBLISH_RATE_LIMIT,
+            signer_rotation_grace: DEFAULT_SIGNER_ROTATION_GRACE,
         }
     }
 
@@ -80,6 +106,54 @@ impl GossipDriverBuilder {
         self
     }
 
+    /// Sets the [`TopicScoreWeights`] applied when topic scoring is enabled.
+    pub const fn with_topic_score_weights(mut self, weights: TopicScoreWeights) -> Self {
+        self.topic_score_weights = weights;
+        self
+    }
+
+    /// Sets the [`BanList`] for the gossip driver. Peers in the list are blocked when the driver
+    /// is built.
+    pub fn with_ban_list(mut self, ban_list: BanList) -> Self {
+        self.ban_list = Some(ban_list);
+        self
+    }
+
+    /// Sets the [`PayloadSource`] used to serve the payloads requested by peers over the
+    /// `payload_by_number` sync protocol.
+    pub fn with_payload_source(mut self, source: Arc<dyn PayloadSource>) -> Self {
+        self.payload_source = Some(source);
+        self
+    }
+
+    /// Sets the static peers. Static peers are dialed on startup and redialed with backoff whenever
+    /// they disconnect. They are also protected, like trusted peers.
+    pub fn with_static_peers(mut self, peers: Vec<Multiaddr>) -> Self {
+        self.static_peers = peers;
+        self
+    }
+
+    /// Sets the trusted peers. Trusted peers are protected by the connection gate: they are never
+    /// disconnected for their score and are not subject to the dial limits.
+    pub fn with_trusted_peers(mut self, peers: Vec<PeerId>) -> Self {
+        self.trusted_peers = peers;
+        self
+    }
+
+    /// Sets the maximum number of unsafe blocks published per second. Zero disables rate
+    /// limiting.
+    pub const fn with_publish_rate_limit(mut self, rate_limit: u32) -> Self {
+        self.publish_rate_limit = rate_limit;
+        self
+    }
+
+    /// Sets how long payloads signed by the previous unsafe block signer are accepted after a
+    /// signer rotation. Zero by default.
+    pub const fn with_signer_rotation_grace(mut self, grace: Duration) -> Self {
+        self.signer_rotation_grace = grace;
+        self
+    }
+
     /// Sets the [`PeerScoreLevel`] for the [`Behaviour`].
     pub const fn with_peer_scoring(mut self, level: PeerScoreLevel) -> Self {
         self.scoring = Some(level);
@@ -135,13 +209,15 @@ impl GossipDriverBuilder {
         let addr = self.gossip_addr;
         let signer_recv = self.signer;
         let rollup_config = self.rollup_config;
-        let l2_chain_id = rollup_config.l2_chain_id;
+        let l2_chain_id = rollup_config.l2_chain_id.id();
         let block_time = rollup_config.block_time;
+        let sync_rollup_config = Arc::new(rollup_config.clone());
 
         let (signer_tx, signer_rx) = watch::channel(signer_recv);
 
         // Block Handler setup
-        let handler = BlockHandler::new(rollup_config, signer_rx);
+        let handler = BlockHandler::new(rollup_config, signer_rx)
+            .with_signer_rotation_grace(self.signer_rotation_grace);
 
         // Construct the gossip behaviour
         let config = self.config.unwrap_or(crate::default_config());
@@ -173,7 +249,12 @@ impl GossipDriverBuilder {
             Some(level) => {
                 use crate::handler::Handler;
                 let params = level
-                    .to_params(handler.topics(), self.topic_scoring, block_time)
+                    .to_params(
+                        handler.topics(),
+                        self.topic_scoring,
+                        block_time,
+                        self.topic_score_weights,
+                    )
                     .unwrap_or_default();
                 match behaviour.gossipsub.with_peer_score(params, PeerScoreLevel::thresholds()) {
                     Ok(_) => debug!(target: "scoring", "Peer scoring enabled successfully"),
@@ -185,12 +266,12 @@ impl GossipDriverBuilder {
         // Let's setup the sync request/response protocol stream.
         let mut sync_handler = behaviour.sync_req_resp.new_control();
 
-        let protocol = format!("/opstack/req/payload_by_number/{l2_chain_id}/0/");
-        let sync_protocol_name = StreamProtocol::try_from_owned(protocol)
-            .map_err(|_| GossipDriverBuilderError::SetupSyncReqRespError)?;
+        let sync_protocol_name = crate::sync_protocol(l2_chain_id)
+            .ok_or(GossipDriverBuilderError::SetupSyncReqRespError)?;
         let sync_protocol = sync_handler
-            .accept(sync_protocol_name)
+            .accept(sync_protocol_name.clone())
             .map_err(|_| GossipDriverBuilderError::SyncReqRespAlreadyAccepted)?;
+        let sync_client = SyncClient::new(sync_handler, sync_protocol_name, sync_rollup_config);
 
         // Build the swarm.
         debug!(target: "gossip", "Building Swarm with Peer ID: {}", keypair.public().to_peer_id());
@@ -211,8 +292,30 @@ impl GossipDriverBuilder {
             .build();
 
         let gater_config = self.gater_config.take().unwrap_or_default();
-        let gate = crate::ConnectionGater::new(gater_config);
+        let mut gate = crate::ConnectionGater::new(gater_config);
+
+        // Block the peers that were banned in a previous run.
+        let ban_list = self.ban_list.take().unwrap_or_default();
+        for peer_id in ban_list.banned() {
+            gate.block_peer(&peer_id);
+        }
+        if !ban_list.peers.is_empty() {
+            info!(target: "gossip", count = ban_list.peers.len(), "Loaded banned peers");
+        }
+
+        // Protect the static and trusted peers.
+        let static_peers = StaticPeers::new(self.static_peers);
+        for peer_id in static_peers.peer_ids().chain(self.trusted_peers.iter()) {
+            gate.protect_peer(*peer_id);
+        }
+
+        let mut driver = GossipDriver::new(swarm, addr, handler, sync_client, sync_protocol, gate);
+        driver.ban_list = ban_list;
+        driver.static_peers = static_peers;
+        driver.peer_monitoring = self.peer_monitoring;
+        driver.payload_source = self.payload_source;
+        driver.publisher = PayloadPublisher::new(self.publish_rate_limit);
 
-        Ok((GossipDriver::new(swarm, addr, handler, sync_handler, sync_protocol, gate), signer_tx))
+        Ok((driver, signer_tx))
     }
 }
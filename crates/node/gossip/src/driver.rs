@@ -1,29 +1,32 @@
 //! Consensus-layer gossipsub driver for Optimism.
 
-use alloy_primitives::{Address, hex};
+use alloy_primitives::Address;
 use derive_more::Debug;
 use discv5::Enr;
-use futures::{AsyncReadExt, AsyncWriteExt, stream::StreamExt};
+use futures::stream::StreamExt;
 use kona_genesis::RollupConfig;
-use kona_peers::{EnrValidation, PeerMonitoring, enr_to_multiaddr};
+use kona_peers::{BanList, EnrValidation, PeerMonitoring, enr_to_multiaddr};
 use libp2p::{
     Multiaddr, PeerId, Swarm, TransportError,
-    gossipsub::{IdentTopic, MessageId},
+    gossipsub::{IdentTopic, MessageAcceptance, MessageId},
     swarm::SwarmEvent,
 };
 use libp2p_identity::Keypair;
 use libp2p_stream::IncomingStreams;
-use op_alloy_rpc_types_engine::OpNetworkPayloadEnvelope;
+use lru::LruCache;
+use op_alloy_rpc_types_engine::{OpExecutionPayloadEnvelope, OpNetworkPayloadEnvelope};
 use std::{
     collections::HashMap,
+    num::NonZeroUsize,
     sync::Arc,
-    time::{Duration, Instant},
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
 };
 use tokio::sync::Mutex;
 
 use crate::{
     Behaviour, BlockHandler, ConnectionGate, ConnectionGater, Event, GossipDriverBuilder, Handler,
-    PublishError,
+    PayloadPublisher, PayloadSource, PendingPublication, PublishError, StaticPeers, SyncClient,
+    SyncRequest, SyncResponse,
 };
 
 /// A driver for a [`Swarm`] instance.
@@ -39,9 +42,9 @@ pub struct GossipDriver<G: ConnectionGate> {
     pub addr: Multiaddr,
     /// The [`BlockHandler`].
     pub handler: BlockHandler,
-    /// A [`libp2p_stream::Control`] instance. Can be used to control the sync request/response
-    #[debug(skip)]
-    pub sync_handler: libp2p_stream::Control,
+    /// The client of the sync request/response protocol, used to request missing payloads from
+    /// peers.
+    pub sync_client: SyncClient,
     /// The inbound streams for the sync request/response protocol.
     ///
     /// This is an option to allow to take the underlying value when the gossip driver gets
@@ -50,6 +53,9 @@ pub struct GossipDriver<G: ConnectionGate> {
     /// TODO(@theochap, `<https://github.com/op-rs/kona/issues/2141>`): remove the sync-req-resp protocol once the `op-node` phases it out.
     #[debug(skip)]
     pub sync_protocol: Option<IncomingStreams>,
+    /// The source of the payloads served over the sync request/response protocol. If unset,
+    /// every sync request is answered with not found.
+    pub payload_source: Option<Arc<dyn PayloadSource>>,
     /// A mapping from [`PeerId`] to [`Multiaddr`].
     pub peerstore: HashMap<PeerId, libp2p::identify::Info>,
     /// If set, the gossip layer will monitor peer scores and ban peers that are below a given
@@ -61,12 +67,37 @@ pub struct GossipDriver<G: ConnectionGate> {
     pub connection_gate: G,
     /// Tracks ping times for peers.
     pub ping: Arc<Mutex<HashMap<PeerId, Duration>>>,
+    /// The persisted list of banned peers.
+    pub ban_list: BanList,
+    /// The number of times the [`BanList`] was modified, used to order its writes to disk.
+    pub ban_list_version: u64,
+    /// The version of the [`BanList`] most recently written to disk. Writes run on the blocking
+    /// thread pool, and a write is skipped if a more recent version was already written.
+    pub ban_list_written: Arc<std::sync::Mutex<u64>>,
+    /// The number of payloads with invalid signatures received from the most recently offending
+    /// peers, bounded to [`Self::INVALID_SIGNATURES_CACHE_SIZE`] peers.
+    pub invalid_signatures: LruCache<PeerId, u32>,
+    /// The static peers, which are kept connected to.
+    pub static_peers: StaticPeers,
+    /// The maximum number of connected peers above which discovered peers are no longer dialed.
+    /// Static peers are dialed regardless. If unset, discovered peers are always dialed.
+    pub max_peers: Option<usize>,
+    /// Paces the publication of the unsafe blocks built by the sequencer.
+    pub publisher: PayloadPublisher,
+    /// The number of the unsafe head of the node while it is congested with unsafe blocks to
+    /// import. While set, valid blocks received over gossip are still accepted and propagated, but
+    /// only the block extending the unsafe head is forwarded to the node.
+    pub import_backpressure: Option<u64>,
 }
 
 impl<G> GossipDriver<G>
 where
     G: ConnectionGate,
 {
+    /// The maximum number of peers whose invalid signatures are counted. The least recently
+    /// offending peers are forgotten first.
+    pub const INVALID_SIGNATURES_CACHE_SIZE: NonZeroUsize = NonZeroUsize::new(1024).unwrap();
+
     /// Returns the [`GossipDriverBuilder`] that can be used to construct the [`GossipDriver`].
     pub const fn builder(
         rollup_config: RollupConfig,
@@ -82,7 +113,7 @@ where
         swarm: Swarm<Behaviour>,
         addr: Multiaddr,
         handler: BlockHandler,
-        sync_handler: libp2p_stream::Control,
+        sync_client: SyncClient,
         sync_protocol: IncomingStreams,
         gate: G,
     ) -> Self {
@@ -93,10 +124,104 @@ where
             peerstore: Default::default(),
             peer_monitoring: None,
             peer_connection_start: Default::default(),
-            sync_handler,
+            sync_client,
             sync_protocol: Some(sync_protocol),
+            payload_source: None,
             connection_gate: gate,
             ping: Arc::new(Mutex::new(Default::default())),
+            ban_list: Default::default(),
+            ban_list_version: 0,
+            ban_list_written: Default::default(),
+            invalid_signatures: LruCache::new(Self::INVALID_SIGNATURES_CACHE_SIZE),
+            static_peers: Default::default(),
+            max_peers: None,
+            publisher: Default::default(),
+            import_backpressure: None,
+        }
+    }
+
+    /// Dials the static peers that are disconnected and whose redial backoff has elapsed.
+    pub fn redial_static_peers(&mut self) {
+        let swarm = &self.swarm;
+        let due = self.static_peers.take_due(Instant::now(), |peer_id| swarm.is_connected(peer_id));
+        for addr in due {
+            debug!(target: "gossip", ?addr, "Dialing static peer");
+            self.dial_multiaddr(addr);
+        }
+    }
+
+    /// Bans the given peer for the given duration: the peer is blocked by the connection gate,
+    /// and the ban is persisted to the [`BanList`]. Protected peers cannot be banned.
+    pub fn record_ban(&mut self, peer_id: PeerId, duration: Duration) {
+        if self.connection_gate.list_protected_peers().contains(&peer_id) {
+            debug!(target: "gossip", ?peer_id, "Not banning protected peer");
+            return;
+        }
+        self.connection_gate.block_peer(&peer_id);
+        self.ban_list.ban(peer_id, duration);
+        self.persist_ban_list();
+    }
+
+    /// Lifts the bans that have expired, unblocking the peers in the connection gate.
+    pub fn expire_bans(&mut self) {
+        let expired = self.ban_list.remove_expired();
+        if expired.is_empty() {
+            return;
+        }
+        for peer_id in &expired {
+            debug!(target: "gossip", ?peer_id, "Peer ban expired");
+            self.connection_gate.unblock_peer(peer_id);
+        }
+        self.persist_ban_list();
+    }
+
+    /// Writes the [`BanList`] to disk on the blocking thread pool, so that the file IO does not
+    /// stall the swarm. Concurrent writes are ordered by version, so that an older list never
+    /// overwrites a newer one.
+    fn persist_ban_list(&mut self) {
+        if self.ban_list.path.is_none() {
+            return;
+        }
+        self.ban_list_version += 1;
+        let version = self.ban_list_version;
+        let ban_list = self.ban_list.clone();
+        let written = Arc::clone(&self.ban_list_written);
+        tokio::task::spawn_blocking(move || {
+            let mut written = written.lock().unwrap_or_else(|e| e.into_inner());
+            if *written >= version {
+                return;
+            }
+            match ban_list.sync() {
+                Ok(()) => *written = version,
+                Err(e) => warn!(target: "gossip", ?e, "Failed to persist the ban list"),
+            }
+        });
+    }
+
+    /// Records that the given peer sent a payload with an invalid signature, banning and
+    /// disconnecting the peer once it reaches the configured invalid signature threshold.
+    fn record_invalid_signature(&mut self, peer_id: PeerId) {
+        let Some((threshold, duration)) = self
+            .peer_monitoring
+            .as_ref()
+            .map(|m| (m.invalid_signature_threshold, m.ban_duration))
+            .filter(|(threshold, _)| *threshold > 0)
+        else {
+            return;
+        };
+
+        let count = self.invalid_signatures.get_or_insert_mut(peer_id, || 0);
+        *count += 1;
+        if *count < threshold {
+            return;
+        }
+        self.invalid_signatures.pop(&peer_id);
+
+        warn!(target: "gossip", ?peer_id, threshold, "Banning peer for sending payloads with invalid signatures");
+        self.record_ban(peer_id, duration);
+        kona_macros::inc!(gauge, crate::Metrics::BANNED_PEERS, "peer_id" => peer_id.to_string(), "score" => "invalid_signature");
+        if self.swarm.disconnect_peer_id(peer_id).is_err() {
+            debug!(target: "gossip", ?peer_id, "Banned peer was not connected");
         }
     }
 
@@ -112,6 +237,9 @@ where
     ///
     /// Returns the [`MessageId`] of the published message or a [`PublishError`]
     /// if the message could not be published.
+    ///
+    /// Publications are paced by the [`PayloadPublisher`]. If no peer is subscribed to the topic
+    /// of the block, the block is republished once a peer subscribes to it.
     pub fn publish(
         &mut self,
         selector: impl FnOnce(&BlockHandler) -> IdentTopic,
@@ -120,63 +248,98 @@ where
         let Some(payload) = payload else {
             return Ok(None);
         };
+        let number = payload.payload.block_number();
+        let timestamp = payload.payload.timestamp();
+        if !self.publisher.try_acquire(number, Instant::now()) {
+            return Err(PublishError::RateLimited(number));
+        }
         let topic = selector(&self.handler);
         let topic_hash = topic.hash();
+        // Peers may relay the block back on another block topic, which must be ignored.
+        self.handler.mark_seen(&payload);
         let data = self.handler.encode(topic, payload)?;
-        let id = self.swarm.behaviour_mut().gossipsub.publish(topic_hash, data)?;
-        kona_macros::inc!(gauge, crate::Metrics::UNSAFE_BLOCK_PUBLISHED);
-        Ok(Some(id))
+        match self.swarm.behaviour_mut().gossipsub.publish(topic_hash.clone(), data.clone()) {
+            Ok(id) => {
+                self.publisher.published(number);
+                kona_macros::inc!(gauge, crate::Metrics::UNSAFE_BLOCK_PUBLISHED);
+                Ok(Some(id))
+            }
+            Err(err @ libp2p::gossipsub::PublishError::NoPeersSubscribedToTopic) => {
+                self.publisher.set_pending(PendingPublication {
+                    topic: topic_hash,
+                    data,
+                    number,
+                    timestamp,
+                });
+                Err(err.into())
+            }
+            Err(err) => Err(err.into()),
+        }
     }
 
-    /// Handles the sync request/response protocol.
-    ///
-    /// This is a mock handler that supports the `payload_by_number` protocol.
-    /// It always returns: not found (1), version (0). `<https://specs.optimism.io/protocol/rollup-node-p2p.html#payload_by_number>`
-    ///
-    /// ## Note
+    /// Republishes the pending block of the [`PayloadPublisher`] if it was published on the
+    /// given topic, which a peer just subscribed to.
+    fn republish_pending(&mut self, topic: &libp2p::gossipsub::TopicHash) {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+        let Some(pending) = self.publisher.take_pending(topic, now) else {
+            return;
+        };
+        let number = pending.number;
+        match self.swarm.behaviour_mut().gossipsub.publish(topic.clone(), pending.data.clone()) {
+            Ok(id) => {
+                info!(target: "gossip", number, ?id, "Republished unsafe block to new peer");
+                kona_macros::inc!(gauge, crate::Metrics::UNSAFE_BLOCK_PUBLISHED);
+            }
+            Err(libp2p::gossipsub::PublishError::NoPeersSubscribedToTopic) => {
+                self.publisher.set_pending(pending);
+            }
+            Err(err) => {
+                debug!(target: "gossip", number, ?err, "Failed to republish unsafe block");
+            }
+        }
+    }
+
+    /// Starts serving the sync request/response protocol, with the payloads of the
+    /// [`PayloadSource`].
     ///
-    /// This is used to ensure op-nodes are not penalizing kona-nodes for not supporting it.
-    /// This feature is being deprecated by the op-node team. Once it is fully removed from the
-    /// op-node's implementation we will remove this handler.
+    /// TODO(@theochap, `<https://github.com/op-rs/kona/issues/2141>`): remove the sync-req-resp protocol once the `op-node` phases it out.
     pub(super) fn sync_protocol_handler(&mut self) {
-        let Some(mut sync_protocol) = self.sync_protocol.take() else {
+        let Some(sync_protocol) = self.sync_protocol.take() else {
             return;
         };
+        let rollup_config = Arc::new(self.handler.rollup_config.clone());
+        tokio::spawn(crate::sync::serve_sync_requests(
+            sync_protocol,
+            self.payload_source.clone(),
+            rollup_config,
+        ));
+    }
 
-        // Spawn a new task to handle the sync request/response protocol.
-        tokio::spawn(async move {
-            loop {
-                let Some((peer_id, mut inbound_stream)) = sync_protocol.next().await else {
-                    warn!(target: "gossip", "The sync protocol stream has ended");
-                    return;
-                };
+    /// Returns the connected peers that support the sync request/response protocol.
+    fn sync_peers(&self) -> Vec<PeerId> {
+        let protocol = self.sync_client.protocol();
+        self.swarm
+            .connected_peers()
+            .filter(|peer_id| {
+                self.peerstore.get(peer_id).is_some_and(|info| info.protocols.contains(protocol))
+            })
+            .copied()
+            .collect()
+    }
 
-                info!(target: "gossip", "Received a sync request from {peer_id}, spawning a new task to handle it");
+    /// Requests the payload of the given [`SyncRequest`] from a peer.
+    pub fn request_payload(&mut self, request: SyncRequest) {
+        let peers = self.sync_peers();
+        self.sync_client.request(request, &peers);
+    }
 
-                tokio::spawn(async move {
-                    let mut buffer = Vec::new();
-                    let Ok(bytes_received) = inbound_stream.read_to_end(&mut buffer).await else {
-                        error!(target: "gossip", "Failed to read the sync request from {peer_id}");
-                        return;
-                    };
-
-                    debug!(target: "gossip", bytes_received = bytes_received, peer_id = ?peer_id, payload = ?buffer, "Received inbound sync request");
-
-                    // We return: not found (1), version (0). `<https://specs.optimism.io/protocol/rollup-node-p2p.html#payload_by_number>`
-                    // Response format: <response> = <res><version><payload>
-                    // No payload is returned.
-                    const OUTPUT: [u8; 2] = hex!("0100");
-
-                    // We only write that we're not supporting the sync request.
-                    if let Err(e) = inbound_stream.write_all(&OUTPUT).await {
-                        error!(target: "gossip", err = ?e, "Failed to write the sync response to {peer_id}");
-                        return;
-                    };
-
-                    debug!(target: "gossip", bytes_sent = OUTPUT.len(), peer_id = ?peer_id, "Sent outbound sync response");
-                });
-            }
-        });
+    /// Handles the response to a sync request, returning the requested payload if it is valid.
+    pub fn handle_sync_response(
+        &mut self,
+        response: SyncResponse,
+    ) -> Option<OpExecutionPayloadEnvelope> {
+        let peers = self.sync_peers();
+        self.sync_client.handle_response(response, &peers)
     }
 
     /// Starts the libp2p Swarm.
@@ -230,8 +393,12 @@ where
         self.swarm.connected_peers().count()
     }
 
-    /// Dials the given [`Enr`].
+    /// Dials the given [`Enr`], unless the [`Self::max_peers`] limit is reached.
     pub fn dial(&mut self, enr: Enr) {
+        if self.max_peers.is_some_and(|max_peers| self.connected_peers() >= max_peers) {
+            trace!(target: "gossip", "Peer limit reached, not dialing discovered peer");
+            return;
+        }
         let validation = EnrValidation::validate(&enr, self.handler.rollup_config.l2_chain_id.id());
         if validation.is_invalid() {
             trace!(target: "gossip", "Invalid OP Stack ENR for chain id {}: {}", self.handler.rollup_config.l2_chain_id.id(), validation);
@@ -359,18 +526,42 @@ where
                 trace!(target: "gossip", "Received message with topic: {}", message.topic);
                 kona_macros::inc!(gauge, crate::Metrics::GOSSIP_EVENT, "type" => "message", "topic" => message.topic.to_string());
                 if self.handler.topics().contains(&message.topic) {
-                    let (status, payload) = self.handler.handle(message);
+                    let (status, payload, invalid_signature) =
+                        match self.handler.handle_block(message) {
+                            Ok(envelope) => {
+                                let number = envelope.payload.block_number();
+                                let forward = self.import_backpressure.is_none_or(|unsafe_head| {
+                                    number == unsafe_head.saturating_add(1)
+                                });
+                                if !forward {
+                                    debug!(
+                                        target: "gossip",
+                                        number,
+                                        "Not forwarding unsafe block under import backpressure"
+                                    );
+                                }
+                                (MessageAcceptance::Accept, forward.then_some(envelope), false)
+                            }
+                            Err(err) => {
+                                let invalid_signature = err.is_invalid_signature();
+                                (err.into(), None, invalid_signature)
+                            }
+                        };
                     _ = self
                         .swarm
                         .behaviour_mut()
                         .gossipsub
                         .report_message_validation_result(&id, &src, status);
+                    if invalid_signature {
+                        self.record_invalid_signature(src);
+                    }
                     return payload;
                 }
             }
             libp2p::gossipsub::Event::Subscribed { peer_id, topic } => {
                 trace!(target: "gossip", "Peer: {:?} subscribed to topic: {:?}", peer_id, topic);
                 kona_macros::inc!(gauge, crate::Metrics::GOSSIP_EVENT, "type" => "subscribed", "topic" => topic.to_string());
+                self.republish_pending(&topic);
             }
             libp2p::gossipsub::Event::Unsubscribed { peer_id, topic } => {
                 trace!(target: "gossip", "Peer: {:?} unsubscribed from topic: {:?}", peer_id, topic);
@@ -406,6 +597,7 @@ where
                 kona_macros::set!(gauge, crate::Metrics::GOSSIP_PEER_COUNT, peer_count as f64);
 
                 self.peer_connection_start.insert(peer_id, Instant::now());
+                self.static_peers.connected(&peer_id);
             }
             SwarmEvent::OutgoingConnectionError { peer_id: _peer_id, error, .. } => {
                 debug!(target: "gossip", "Outgoing connection error: {:?}", error);
@@ -415,6 +607,11 @@ where
                     "type" => "outgoing_error",
                     "peer" => _peer_id.map(|p| p.to_string()).unwrap_or_default()
                 );
+
+                // The dial failed, so the peer may be dialed again.
+                if let Some(peer_id) = _peer_id {
+                    self.connection_gate.remove_dial(&peer_id);
+                }
             }
             SwarmEvent::IncomingConnectionError {
                 error, connection_id: _connection_id, ..
@@ -437,6 +634,8 @@ where
                     "peer" => peer_id.to_string()
                 );
                 kona_macros::set!(gauge, crate::Metrics::GOSSIP_PEER_COUNT, peer_count as f64);
+                self.sync_client.remove_peer(&peer_id);
+                self.static_peers.disconnected(Instant::now(), &peer_id);
 
                 // Record the total connection duration.
                 if let Some(start_time) = self.peer_connection_start.remove(&peer_id) {
@@ -1,8 +1,11 @@
 //! Error types for the gossip networking module.
 
-use crate::BehaviourError;
+use crate::{BehaviourError, BlockInvalidError};
 use derive_more::From;
-use libp2p::{Multiaddr, PeerId};
+use libp2p::{
+    Multiaddr, PeerId,
+    gossipsub::{MessageAcceptance, TopicHash},
+};
 use std::net::IpAddr;
 use thiserror::Error;
 
@@ -25,6 +28,13 @@ pub enum PublishError {
     /// into the binary format expected by the network protocol.
     #[error("Failed to encode payload: {0}")]
     EncodeError(#[from] HandlerEncodeError),
+
+    /// The payload was not published because of the publication rate limit.
+    ///
+    /// Either too many payloads were published in the last second, or too many distinct
+    /// payloads were published at the height of the payload.
+    #[error("Rate limited the publication of block {0}")]
+    RateLimited(u64),
 }
 
 /// Error encountered when encoding payloads in the block handler.
@@ -48,6 +58,73 @@ pub enum HandlerEncodeError {
     UnknownTopic(libp2p::gossipsub::TopicHash),
 }
 
+/// Error encountered when handling a block received over gossip.
+#[derive(Debug, Error)]
+pub enum BlockHandleError {
+    /// The block was received on a topic that is not a block topic.
+    #[error("Received block with unknown topic: {0}")]
+    UnknownTopic(TopicHash),
+    /// The block could not be decoded for the version of its topic.
+    #[error("Failed to decode block")]
+    Decode,
+    /// The block is invalid.
+    #[error(transparent)]
+    Invalid(#[from] BlockInvalidError),
+}
+
+impl BlockHandleError {
+    /// Returns `true` if the block was rejected because its payload signature is invalid.
+    ///
+    /// Blocks correctly signed by another address than the unsafe block signer are not counted:
+    /// honest peers relay blocks of the previous signer while they observe a signer rotation.
+    pub const fn is_invalid_signature(&self) -> bool {
+        matches!(self, Self::Invalid(BlockInvalidError::Signature))
+    }
+}
+
+impl From<BlockHandleError> for MessageAcceptance {
+    fn from(value: BlockHandleError) -> Self {
+        match value {
+            BlockHandleError::Invalid(err) => err.into(),
+            _ => Self::Reject,
+        }
+    }
+}
+
+/// An error encountered by a request of the `payload_by_number` sync protocol.
+#[derive(Debug, Error)]
+pub enum SyncError {
+    /// Failed to open a stream to the peer.
+    #[error("failed to open a sync stream: {0}")]
+    OpenStream(String),
+    /// An I/O error on the sync stream.
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    /// The request timed out.
+    #[error("sync request timed out")]
+    Timeout,
+    /// The peer responded with an unsuccessful result code.
+    #[error("peer responded with result code {0}")]
+    ResultCode(u8),
+    /// The peer responded with an unsupported response version.
+    #[error("unsupported sync response version {0}")]
+    UnsupportedVersion(u32),
+    /// The payload could not be encoded.
+    #[error("failed to encode the sync payload")]
+    Encode,
+    /// The response could not be decoded.
+    #[error("failed to decode the sync response")]
+    Decode,
+    /// The returned payload does not have the expected block hash.
+    #[error("unexpected block hash: expected {expected}, received {received}")]
+    UnexpectedHash {
+        /// The expected block hash.
+        expected: alloy_primitives::B256,
+        /// The received block hash.
+        received: alloy_primitives::B256,
+    },
+}
+
 /// An error type for the [`crate::GossipDriverBuilder`].
 #[derive(Debug, Clone, PartialEq, Eq, From, Error)]
 pub enum GossipDriverBuilderError {
@@ -114,3 +191,23 @@ pub enum DialError {
         ip: IpAddr,
     },
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::Address;
+
+    #[test]
+    fn test_is_invalid_signature() {
+        assert!(BlockHandleError::Invalid(BlockInvalidError::Signature).is_invalid_signature());
+
+        // Blocks of another signer are not counted, as honest peers relay the blocks of the
+        // previous signer during a signer rotation.
+        let signer = BlockInvalidError::Signer {
+            expected: Address::with_last_byte(1),
+            received: Address::with_last_byte(2),
+        };
+        assert!(!BlockHandleError::Invalid(signer).is_invalid_signature());
+        assert!(!BlockHandleError::Decode.is_invalid_signature());
+    }
+}
@@ -1,13 +1,21 @@
 //! Block Handler
 
-use crate::HandlerEncodeError;
+use crate::{BlockHandleError, HandlerEncodeError};
 use alloy_primitives::{Address, B256};
 use kona_genesis::RollupConfig;
 use libp2p::gossipsub::{IdentTopic, Message, MessageAcceptance, TopicHash};
 use op_alloy_rpc_types_engine::OpNetworkPayloadEnvelope;
-use std::collections::{BTreeMap, HashSet};
+use std::{
+    collections::{BTreeMap, HashSet},
+    time::{Duration, Instant},
+};
 use tokio::sync::watch::Receiver;
 
+/// The default duration during which payloads signed by the previous unsafe block signer are still
+/// accepted after a signer rotation. Zero, so that the previous signer is rejected as soon as the
+/// rotation is observed, unless a grace period is configured.
+pub const DEFAULT_SIGNER_ROTATION_GRACE: Duration = Duration::ZERO;
+
 /// This trait defines the functionality required to process incoming messages
 /// and determine their acceptance within the network.
 ///
@@ -29,6 +37,15 @@ pub struct BlockHandler {
     pub rollup_config: RollupConfig,
     /// A [`Receiver`] to monitor changes to the unsafe block signer.
     pub signer_recv: Receiver<Address>,
+    /// The unsafe block signer that payloads are currently validated against.
+    pub current_signer: Address,
+    /// The previous unsafe block signer, and the time until which payloads signed by it are still
+    /// accepted, if the signer was rotated.
+    pub retired_signer: Option<(Address, Instant)>,
+    /// How long payloads signed by the previous unsafe block signer are accepted after a signer
+    /// rotation, so that the network is not partitioned while peers observe the rotation on L1 at
+    /// slightly different times. Defaults to [`DEFAULT_SIGNER_ROTATION_GRACE`].
+    pub signer_rotation_grace: Duration,
     /// The libp2p topic for pre Canyon/Shangai blocks.
     pub blocks_v1_topic: IdentTopic,
     /// The libp2p topic for Canyon/Delta blocks.
@@ -46,31 +63,9 @@ impl Handler for BlockHandler {
     /// Checks validity of a [`OpNetworkPayloadEnvelope`] received over P2P gossip.
     /// If valid, sends the [`OpNetworkPayloadEnvelope`] to the block update channel.
     fn handle(&mut self, msg: Message) -> (MessageAcceptance, Option<OpNetworkPayloadEnvelope>) {
-        let decoded = if msg.topic == self.blocks_v1_topic.hash() {
-            OpNetworkPayloadEnvelope::decode_v1(&msg.data)
-        } else if msg.topic == self.blocks_v2_topic.hash() {
-            OpNetworkPayloadEnvelope::decode_v2(&msg.data)
-        } else if msg.topic == self.blocks_v3_topic.hash() {
-            OpNetworkPayloadEnvelope::decode_v3(&msg.data)
-        } else if msg.topic == self.blocks_v4_topic.hash() {
-            OpNetworkPayloadEnvelope::decode_v4(&msg.data)
-        } else {
-            warn!(target: "gossip", topic = ?msg.topic, "Received block with unknown topic");
-            return (MessageAcceptance::Reject, None);
-        };
-
-        match decoded {
-            Ok(envelope) => match self.block_valid(&envelope) {
-                Ok(()) => (MessageAcceptance::Accept, Some(envelope)),
-                Err(err) => {
-                    warn!(target: "gossip", ?err, hash = ?envelope.payload_hash, "Received invalid block");
-                    (err.into(), None)
-                }
-            },
-            Err(err) => {
-                warn!(target: "gossip", ?err, "Failed to decode block");
-                (MessageAcceptance::Reject, None)
-            }
+        match self.handle_block(msg) {
+            Ok(envelope) => (MessageAcceptance::Accept, Some(envelope)),
+            Err(err) => (err.into(), None),
         }
     }
 
@@ -85,15 +80,33 @@ impl Handler for BlockHandler {
     }
 }
 
+/// Returns the version of the payloads of blocks with the specified timestamp, according to the
+/// hardforks active at that timestamp.
+pub(crate) fn payload_version(rollup_config: &RollupConfig, timestamp: u64) -> u8 {
+    if rollup_config.is_isthmus_active(timestamp) {
+        4
+    } else if rollup_config.is_ecotone_active(timestamp) {
+        3
+    } else if rollup_config.is_canyon_active(timestamp) {
+        2
+    } else {
+        1
+    }
+}
+
 impl BlockHandler {
     /// Creates a new [`BlockHandler`].
     ///
     /// Requires the chain ID and a receiver channel for the unsafe block signer.
     pub fn new(rollup_config: RollupConfig, signer_recv: Receiver<Address>) -> Self {
         let chain_id = rollup_config.l2_chain_id.id();
+        let current_signer = *signer_recv.borrow();
         Self {
             rollup_config,
             signer_recv,
+            current_signer,
+            retired_signer: None,
+            signer_rotation_grace: DEFAULT_SIGNER_ROTATION_GRACE,
             blocks_v1_topic: IdentTopic::new(format!("/optimism/{chain_id}/0/blocks")),
             blocks_v2_topic: IdentTopic::new(format!("/optimism/{chain_id}/1/blocks")),
             blocks_v3_topic: IdentTopic::new(format!("/optimism/{chain_id}/2/blocks")),
@@ -102,18 +115,90 @@ impl BlockHandler {
         }
     }
 
+    /// Sets how long payloads signed by the previous unsafe block signer are accepted after a
+    /// signer rotation.
+    pub const fn with_signer_rotation_grace(mut self, grace: Duration) -> Self {
+        self.signer_rotation_grace = grace;
+        self
+    }
+
+    /// Returns `true` if payloads signed by the given address are accepted: it is either the
+    /// current unsafe block signer, or the previous one and the rotation grace window has not
+    /// elapsed.
+    ///
+    /// Picks up any rotation of the unsafe block signer, retiring the previous signer.
+    pub fn is_accepted_signer(&mut self, signer: Address) -> bool {
+        let latest = *self.signer_recv.borrow_and_update();
+        if latest != self.current_signer {
+            info!(target: "gossip", previous = ?self.current_signer, current = ?latest, "Unsafe block signer rotated");
+            self.retired_signer =
+                Some((self.current_signer, Instant::now() + self.signer_rotation_grace));
+            self.current_signer = latest;
+        }
+        if signer == self.current_signer {
+            return true;
+        }
+        self.retired_signer
+            .is_some_and(|(retired, deadline)| retired == signer && Instant::now() < deadline)
+    }
+
+    /// Decodes and validates a [`OpNetworkPayloadEnvelope`] received over P2P gossip, returning
+    /// the reason it was rejected if it is invalid.
+    pub fn handle_block(
+        &mut self,
+        msg: Message,
+    ) -> Result<OpNetworkPayloadEnvelope, BlockHandleError> {
+        let decoded = if msg.topic == self.blocks_v1_topic.hash() {
+            OpNetworkPayloadEnvelope::decode_v1(&msg.data)
+        } else if msg.topic == self.blocks_v2_topic.hash() {
+            OpNetworkPayloadEnvelope::decode_v2(&msg.data)
+        } else if msg.topic == self.blocks_v3_topic.hash() {
+            OpNetworkPayloadEnvelope::decode_v3(&msg.data)
+        } else if msg.topic == self.blocks_v4_topic.hash() {
+            OpNetworkPayloadEnvelope::decode_v4(&msg.data)
+        } else {
+            warn!(target: "gossip", topic = ?msg.topic, "Received block with unknown topic");
+            return Err(BlockHandleError::UnknownTopic(msg.topic));
+        };
+
+        let envelope = decoded.map_err(|err| {
+            warn!(target: "gossip", ?err, "Failed to decode block");
+            BlockHandleError::Decode
+        })?;
+        self.block_valid(&envelope).map_err(|err| {
+            warn!(target: "gossip", ?err, hash = ?envelope.payload_hash, "Received invalid block");
+            err
+        })?;
+        Ok(envelope)
+    }
+
     /// Returns the topic using the specified timestamp and optional [`RollupConfig`].
     ///
     /// Reference: <https://github.com/ethereum-optimism/optimism/blob/0bc5fe8d16155dc68bcdf1fa5733abc58689a618/op-node/p2p/gossip.go#L604C1-L612C3>
     pub fn topic(&self, timestamp: u64) -> IdentTopic {
-        if self.rollup_config.is_isthmus_active(timestamp) {
-            self.blocks_v4_topic.clone()
-        } else if self.rollup_config.is_ecotone_active(timestamp) {
-            self.blocks_v3_topic.clone()
-        } else if self.rollup_config.is_canyon_active(timestamp) {
-            self.blocks_v2_topic.clone()
-        } else {
-            self.blocks_v1_topic.clone()
+        match self.payload_version(timestamp) {
+            4 => self.blocks_v4_topic.clone(),
+            3 => self.blocks_v3_topic.clone(),
+            2 => self.blocks_v2_topic.clone(),
+            _ => self.blocks_v1_topic.clone(),
+        }
+    }
+
+    /// Returns the version of the payloads gossiped for blocks with the specified timestamp,
+    /// according to the hardforks active at that timestamp.
+    pub fn payload_version(&self, timestamp: u64) -> u8 {
+        payload_version(&self.rollup_config, timestamp)
+    }
+
+    /// Marks the block of the given [`OpNetworkPayloadEnvelope`] as seen, so that the same block
+    /// received from peers on any of the block topics is ignored.
+    pub fn mark_seen(&mut self, envelope: &OpNetworkPayloadEnvelope) {
+        self.seen_hashes
+            .entry(envelope.payload.block_number())
+            .or_default()
+            .insert(envelope.payload.block_hash());
+        if self.seen_hashes.len() >= Self::SEEN_HASH_CACHE_SIZE {
+            self.seen_hashes.pop_first();
         }
     }
 
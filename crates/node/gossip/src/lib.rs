@@ -58,17 +58,35 @@ mod builder;
 pub use builder::GossipDriverBuilder;
 
 mod error;
-pub use error::{DialError, GossipDriverBuilderError, HandlerEncodeError, PublishError};
+pub use error::{
+    BlockHandleError, DialError, GossipDriverBuilderError, HandlerEncodeError, PublishError,
+    SyncError,
+};
 
 mod event;
 pub use event::Event;
 
 mod handler;
-pub use handler::{BlockHandler, Handler};
+pub use handler::{BlockHandler, DEFAULT_SIGNER_ROTATION_GRACE, Handler};
 
 mod driver;
 pub use driver::GossipDriver;
 
+mod publisher;
+pub use publisher::{
+    DEFAULT_PUBLISH_RATE_LIMIT, MAX_PENDING_PUBLICATION_AGE, MAX_PUBLISHED_BLOCKS_PER_HEIGHT,
+    PayloadPublisher, PendingPublication,
+};
+
+mod static_peers;
+pub use static_peers::{STATIC_PEER_MAX_BACKOFF, STATIC_PEER_MIN_BACKOFF, StaticPeers};
+
+mod sync;
+pub use sync::{
+    MAX_SYNC_RESPONSE_SIZE, PayloadSource, SYNC_REQUEST_TIMEOUT, SyncClient, SyncRequest,
+    SyncResponse, SyncResultCode, sync_protocol,
+};
+
 mod block_validity;
 pub use block_validity::BlockInvalidError;
 
@@ -0,0 +1,212 @@
+//! Contains the [`PayloadPublisher`], which paces the publication of locally built unsafe blocks.
+
+use libp2p::gossipsub::TopicHash;
+use std::{collections::BTreeMap, time::Instant};
+
+/// The default maximum number of unsafe blocks published per second.
+pub const DEFAULT_PUBLISH_RATE_LIMIT: u32 = 10;
+
+/// The maximum number of distinct blocks published at the same height. Peers reject more than
+/// [`BlockHandler::MAX_BLOCKS_TO_KEEP`] blocks at the same height, and penalize their sender.
+///
+/// [`BlockHandler::MAX_BLOCKS_TO_KEEP`]: crate::BlockHandler
+pub const MAX_PUBLISHED_BLOCKS_PER_HEIGHT: usize = 5;
+
+/// The maximum age, in seconds, of a pending block to be republished. Peers reject blocks whose
+/// timestamp is more than 60 seconds in the past, and penalize their sender.
+pub const MAX_PENDING_PUBLICATION_AGE: u64 = 60;
+
+/// The number of heights for which published blocks are counted.
+const PUBLISHED_HEIGHTS_CACHE_SIZE: usize = 1_000;
+
+/// An encoded unsafe block waiting for a peer to be published to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PendingPublication {
+    /// The topic of the block.
+    pub topic: TopicHash,
+    /// The encoded block.
+    pub data: Vec<u8>,
+    /// The number of the block.
+    pub number: u64,
+    /// The timestamp of the block.
+    pub timestamp: u64,
+}
+
+/// Paces the publication of the unsafe blocks built by the sequencer.
+///
+/// Publications are rate limited with a token bucket refilled at the configured rate, and capped
+/// at [`MAX_PUBLISHED_BLOCKS_PER_HEIGHT`] blocks per height, so that a misbehaving sequencer does
+/// not get its node penalized by its peers.
+///
+/// The latest block that could not be published because no peer was subscribed to its topic is
+/// retained, and republished as soon as a peer subscribes to the topic. This lets a sequencer
+/// that lost all of its peers share its head again once it reconnects. Blocks older than
+/// [`MAX_PENDING_PUBLICATION_AGE`] are dropped instead.
+#[derive(Debug, Clone)]
+pub struct PayloadPublisher {
+    /// The maximum number of blocks published per second. Zero disables rate limiting.
+    rate_limit: u32,
+    /// The number of publications available.
+    tokens: f64,
+    /// The last time the tokens were refilled.
+    refilled_at: Instant,
+    /// The number of blocks published at each height.
+    published: BTreeMap<u64, usize>,
+    /// The latest block waiting for a peer to subscribe to its topic.
+    pending: Option<PendingPublication>,
+}
+
+impl Default for PayloadPublisher {
+    fn default() -> Self {
+        Self::new(DEFAULT_PUBLISH_RATE_LIMIT)
+    }
+}
+
+impl PayloadPublisher {
+    /// Creates a new [`PayloadPublisher`] publishing at most `rate_limit` blocks per second.
+    /// A `rate_limit` of zero disables rate limiting.
+    pub fn new(rate_limit: u32) -> Self {
+        Self {
+            rate_limit,
+            tokens: rate_limit as f64,
+            refilled_at: Instant::now(),
+            published: BTreeMap::new(),
+            pending: None,
+        }
+    }
+
+    /// Returns the maximum number of blocks published per second.
+    pub const fn rate_limit(&self) -> u32 {
+        self.rate_limit
+    }
+
+    /// Returns whether a block at the given height may be published at the given time, consuming
+    /// a publication if so.
+    pub fn try_acquire(&mut self, number: u64, now: Instant) -> bool {
+        if self.published.get(&number).copied().unwrap_or_default() >=
+            MAX_PUBLISHED_BLOCKS_PER_HEIGHT
+        {
+            return false;
+        }
+
+        if self.rate_limit > 0 {
+            let rate = self.rate_limit as f64;
+            let elapsed = now.saturating_duration_since(self.refilled_at);
+            self.tokens = (self.tokens + elapsed.as_secs_f64() * rate).min(rate);
+            self.refilled_at = now;
+            if self.tokens < 1.0 {
+                return false;
+            }
+            self.tokens -= 1.0;
+        }
+
+        *self.published.entry(number).or_default() += 1;
+        if self.published.len() > PUBLISHED_HEIGHTS_CACHE_SIZE {
+            self.published.pop_first();
+        }
+        true
+    }
+
+    /// Retains the given block to be republished when a peer subscribes to its topic. Replaces
+    /// the pending block, unless it is higher than the given one.
+    pub fn set_pending(&mut self, publication: PendingPublication) {
+        if self.pending.as_ref().is_some_and(|pending| pending.number > publication.number) {
+            return;
+        }
+        self.pending = Some(publication);
+    }
+
+    /// Takes the pending block if it was published on the given topic. The pending block is
+    /// dropped if it is older than [`MAX_PENDING_PUBLICATION_AGE`] at the given unix timestamp.
+    pub fn take_pending(&mut self, topic: &TopicHash, now: u64) -> Option<PendingPublication> {
+        self.pending
+            .take_if(|pending| pending.timestamp.saturating_add(MAX_PENDING_PUBLICATION_AGE) < now);
+        self.pending.take_if(|pending| &pending.topic == topic)
+    }
+
+    /// Clears the pending block if it is not higher than the given published block.
+    pub fn published(&mut self, number: u64) {
+        self.pending.take_if(|pending| pending.number <= number);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn pending(topic: &str, number: u64) -> PendingPublication {
+        PendingPublication {
+            topic: TopicHash::from_raw(topic),
+            data: vec![1, 2, 3],
+            number,
+            timestamp: 1_000 + number,
+        }
+    }
+
+    #[test]
+    fn test_rate_limit() {
+        let now = Instant::now();
+        let mut publisher = PayloadPublisher::new(2);
+        assert!(publisher.try_acquire(1, now));
+        assert!(publisher.try_acquire(2, now));
+        assert!(!publisher.try_acquire(3, now));
+
+        let later = now + Duration::from_millis(500);
+        assert!(publisher.try_acquire(3, later));
+        assert!(!publisher.try_acquire(4, later));
+    }
+
+    #[test]
+    fn test_rate_limit_disabled() {
+        let now = Instant::now();
+        let mut publisher = PayloadPublisher::new(0);
+        assert!((0..100).all(|number| publisher.try_acquire(number, now)));
+    }
+
+    #[test]
+    fn test_blocks_per_height() {
+        let now = Instant::now();
+        let mut publisher = PayloadPublisher::new(0);
+        for _ in 0..MAX_PUBLISHED_BLOCKS_PER_HEIGHT {
+            assert!(publisher.try_acquire(7, now));
+        }
+        assert!(!publisher.try_acquire(7, now));
+        assert!(publisher.try_acquire(8, now));
+    }
+
+    #[test]
+    fn test_pending_publication() {
+        let mut publisher = PayloadPublisher::default();
+        publisher.set_pending(pending("blocks/v3", 10));
+        publisher.set_pending(pending("blocks/v3", 9));
+        assert_eq!(publisher.take_pending(&TopicHash::from_raw("blocks/v4"), 1_010), None);
+        assert_eq!(
+            publisher.take_pending(&TopicHash::from_raw("blocks/v3"), 1_010),
+            Some(pending("blocks/v3", 10))
+        );
+        assert_eq!(publisher.take_pending(&TopicHash::from_raw("blocks/v3"), 1_010), None);
+
+        publisher.set_pending(pending("blocks/v3", 11));
+        publisher.published(10);
+        assert!(publisher.pending.is_some());
+        publisher.published(11);
+        assert!(publisher.pending.is_none());
+    }
+
+    #[test]
+    fn test_stale_pending_publication() {
+        let topic = TopicHash::from_raw("blocks/v3");
+        let mut publisher = PayloadPublisher::default();
+        publisher.set_pending(pending("blocks/v3", 10));
+        let stale = 1_010 + MAX_PENDING_PUBLICATION_AGE + 1;
+        assert_eq!(publisher.take_pending(&TopicHash::from_raw("blocks/v4"), stale), None);
+        assert!(publisher.pending.is_none());
+
+        publisher.set_pending(pending("blocks/v3", 10));
+        assert_eq!(
+            publisher.take_pending(&topic, 1_010 + MAX_PENDING_PUBLICATION_AGE),
+            Some(pending("blocks/v3", 10))
+        );
+    }
+}
@@ -140,6 +140,7 @@ impl P2pRpcRequest {
 
     fn block_addr<G: ConnectionGate>(address: IpAddr, gossip: &mut GossipDriver<G>) {
         gossip.connection_gate.block_addr(address);
+        Self::disconnect_peers_on(gossip, |ip| ip == address);
     }
 
     fn unblock_addr<G: ConnectionGate>(address: IpAddr, gossip: &mut GossipDriver<G>) {
@@ -156,11 +157,21 @@ impl P2pRpcRequest {
     fn block_peer<G: ConnectionGate>(id: PeerId, gossip: &mut GossipDriver<G>) {
         gossip.connection_gate.block_peer(&id);
         gossip.swarm.behaviour_mut().gossipsub.blacklist_peer(&id);
+        if gossip.swarm.is_connected(&id) {
+            Self::disconnect_peer(id, gossip);
+        }
     }
 
     fn unblock_peer<G: ConnectionGate>(id: PeerId, gossip: &mut GossipDriver<G>) {
         gossip.connection_gate.unblock_peer(&id);
         gossip.swarm.behaviour_mut().gossipsub.remove_blacklisted_peer(&id);
+        // Lift any persisted ban as well, so that the peer is not blocked again on restart.
+        if !gossip.ban_list.unban(&id) {
+            return;
+        }
+        if let Err(e) = gossip.ban_list.sync() {
+            warn!(target: "p2p::rpc", "Failed to persist the ban list: {:?}", e);
+        }
     }
 
     fn list_blocked_peers<G: ConnectionGate>(s: Sender<Vec<PeerId>>, gossip: &GossipDriver<G>) {
@@ -172,6 +183,32 @@ impl P2pRpcRequest {
 
     fn block_subnet<G: ConnectionGate>(address: IpNet, gossip: &mut GossipDriver<G>) {
         gossip.connection_gate.block_subnet(address);
+        Self::disconnect_peers_on(gossip, |ip| address.contains(&ip));
+    }
+
+    /// Disconnects the connected peers that listen on an IP address matching the given predicate.
+    /// Protected peers are left connected.
+    fn disconnect_peers_on<G: ConnectionGate>(
+        gossip: &mut GossipDriver<G>,
+        matches: impl Fn(IpAddr) -> bool,
+    ) {
+        let protected = gossip.connection_gate.list_protected_peers();
+        let peers = gossip
+            .peerstore
+            .iter()
+            .filter(|(id, _)| gossip.swarm.is_connected(id) && !protected.contains(id))
+            .filter(|(_, info)| {
+                info.listen_addrs.iter().flat_map(|addr| addr.iter()).any(|p| match p {
+                    Protocol::Ip4(ip) => matches(IpAddr::V4(ip)),
+                    Protocol::Ip6(ip) => matches(IpAddr::V6(ip)),
+                    _ => false,
+                })
+            })
+            .map(|(id, _)| *id)
+            .collect::<Vec<_>>();
+        for peer_id in peers {
+            Self::disconnect_peer(peer_id, gossip);
+        }
     }
 
     fn unblock_subnet<G: ConnectionGate>(address: IpNet, gossip: &mut GossipDriver<G>) {
@@ -0,0 +1,151 @@
+//! Contains the [`StaticPeers`], the peers that the gossip driver always keeps connected to.
+
+use libp2p::{Multiaddr, PeerId};
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+use crate::ConnectionGater;
+
+/// The delay before the first redial of a disconnected static peer.
+pub const STATIC_PEER_MIN_BACKOFF: Duration = Duration::from_secs(1);
+
+/// The maximum delay between two redials of a disconnected static peer.
+pub const STATIC_PEER_MAX_BACKOFF: Duration = Duration::from_secs(5 * 60);
+
+/// The dial state of a static peer.
+#[derive(Debug, Clone)]
+struct StaticPeer {
+    /// The address the peer is dialed at.
+    addr: Multiaddr,
+    /// The delay before the next redial, doubled on every dial until the peer connects.
+    backoff: Duration,
+    /// The earliest time at which the peer may be dialed again.
+    next_dial: Instant,
+}
+
+/// The set of static peers.
+///
+/// Static peers are dialed on startup and redialed with an exponential backoff, capped at
+/// [`STATIC_PEER_MAX_BACKOFF`], whenever they are disconnected. Unlike peers found through
+/// discovery, they are never given up on.
+#[derive(Debug, Clone, Default)]
+pub struct StaticPeers {
+    /// The static peers, by peer id.
+    peers: HashMap<PeerId, StaticPeer>,
+}
+
+impl StaticPeers {
+    /// Creates the [`StaticPeers`] from the given addresses. Addresses that do not contain a peer
+    /// id are ignored.
+    pub fn new(addrs: impl IntoIterator<Item = Multiaddr>) -> Self {
+        let now = Instant::now();
+        let peers = addrs
+            .into_iter()
+            .filter_map(|addr| {
+                let Some(peer_id) = ConnectionGater::peer_id_from_addr(&addr) else {
+                    warn!(target: "gossip", ?addr, "Ignoring static peer without a peer id");
+                    return None;
+                };
+                Some((
+                    peer_id,
+                    StaticPeer { addr, backoff: STATIC_PEER_MIN_BACKOFF, next_dial: now },
+                ))
+            })
+            .collect();
+        Self { peers }
+    }
+
+    /// Returns `true` if there are no static peers.
+    pub fn is_empty(&self) -> bool {
+        self.peers.is_empty()
+    }
+
+    /// Returns `true` if the given peer is a static peer.
+    pub fn contains(&self, peer_id: &PeerId) -> bool {
+        self.peers.contains_key(peer_id)
+    }
+
+    /// Returns the ids of the static peers.
+    pub fn peer_ids(&self) -> impl Iterator<Item = &PeerId> {
+        self.peers.keys()
+    }
+
+    /// Returns the addresses of the static peers that are not connected and are due to be
+    /// dialed at the given time, backing off their next dial.
+    pub fn take_due(
+        &mut self,
+        now: Instant,
+        is_connected: impl Fn(&PeerId) -> bool,
+    ) -> Vec<Multiaddr> {
+        self.peers
+            .iter_mut()
+            .filter(|(peer_id, peer)| peer.next_dial <= now && !is_connected(peer_id))
+            .map(|(_, peer)| {
+                peer.next_dial = now + peer.backoff;
+                peer.backoff = (peer.backoff * 2).min(STATIC_PEER_MAX_BACKOFF);
+                peer.addr.clone()
+            })
+            .collect()
+    }
+
+    /// Resets the backoff of the given peer, once it is connected.
+    pub fn connected(&mut self, peer_id: &PeerId) {
+        if let Some(peer) = self.peers.get_mut(peer_id) {
+            peer.backoff = STATIC_PEER_MIN_BACKOFF;
+        }
+    }
+
+    /// Schedules a prompt redial of the given peer, once it is disconnected at the given time.
+    pub fn disconnected(&mut self, now: Instant, peer_id: &PeerId) {
+        if let Some(peer) = self.peers.get_mut(peer_id) {
+            peer.next_dial = peer.next_dial.min(now + peer.backoff);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use libp2p::{identity::Keypair, multiaddr::Protocol};
+
+    fn static_addr() -> (PeerId, Multiaddr) {
+        let peer_id = Keypair::generate_secp256k1().public().to_peer_id();
+        let addr: Multiaddr = "/ip4/127.0.0.1/tcp/9222".parse().unwrap();
+        (peer_id, addr.with(Protocol::P2p(peer_id)))
+    }
+
+    #[test]
+    fn test_static_peers_backoff() {
+        let (peer_id, addr) = static_addr();
+        let mut peers = StaticPeers::new([addr.clone(), "/ip4/127.0.0.1/tcp/1".parse().unwrap()]);
+        assert!(peers.contains(&peer_id));
+        assert_eq!(peers.peer_ids().count(), 1);
+
+        let now = Instant::now();
+        assert_eq!(peers.take_due(now, |_| false), vec![addr.clone()]);
+        assert!(peers.take_due(now, |_| false).is_empty());
+
+        let now = now + STATIC_PEER_MIN_BACKOFF;
+        assert_eq!(peers.take_due(now, |_| false), vec![addr.clone()]);
+
+        // The backoff doubled, so the peer is not due yet.
+        let now = now + STATIC_PEER_MIN_BACKOFF;
+        assert!(peers.take_due(now, |_| false).is_empty());
+        let now = now + STATIC_PEER_MIN_BACKOFF;
+        assert_eq!(peers.take_due(now, |_| false), vec![addr]);
+    }
+
+    #[test]
+    fn test_static_peers_skip_connected() {
+        let (peer_id, addr) = static_addr();
+        let mut peers = StaticPeers::new([addr.clone()]);
+        let now = Instant::now();
+        assert!(peers.take_due(now, |id| id == &peer_id).is_empty());
+
+        peers.connected(&peer_id);
+        peers.disconnected(now, &peer_id);
+        assert_eq!(peers.take_due(now, |_| false), vec![addr]);
+    }
+}
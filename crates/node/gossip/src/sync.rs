@@ -0,0 +1,585 @@
+//! The `payload_by_number` request/response sync protocol, used to backfill unsafe blocks that
+//! were missed on gossip.
+//!
+//! A request is the little-endian encoded number of the requested block. A response is a single
+//! result byte, followed for successful responses by the little-endian `u32` response version
+//! and the snappy-framed SSZ encoded payload.
+//!
+//! Reference: <https://specs.optimism.io/protocol/rollup-node-p2p.html#payload_by_number>
+
+use alloy_primitives::{B256, Signature, U256};
+use derive_more::Debug;
+use futures::{AsyncReadExt, AsyncWriteExt, StreamExt, future::BoxFuture};
+use kona_genesis::RollupConfig;
+use libp2p::{PeerId, StreamProtocol};
+use libp2p_stream::IncomingStreams;
+use op_alloy_rpc_types_engine::{
+    OpExecutionPayloadEnvelope, OpNetworkPayloadEnvelope, PayloadHash,
+};
+use std::{
+    cmp::Reverse,
+    collections::{HashMap, HashSet},
+    io::{Read, Write},
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use tokio::sync::mpsc;
+
+use crate::{SyncError, handler::payload_version};
+
+/// The maximum duration of a sync request, from opening the stream to reading the response.
+pub const SYNC_REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The maximum size of a sync response.
+pub const MAX_SYNC_RESPONSE_SIZE: u64 = 10 * 1024 * 1024;
+
+/// The length of the signature prefixing gossiped payloads, which sync responses do not carry.
+const SIGNATURE_LEN: usize = 65;
+
+/// The rate at which the server serves sync requests across all peers, per second.
+const GLOBAL_SERVER_RATE: f64 = 50.0;
+
+/// The number of sync requests the server serves at once across all peers.
+const GLOBAL_SERVER_BURST: f64 = 2.0;
+
+/// The rate at which the server serves sync requests of a single peer, per second.
+const PEER_SERVER_RATE: f64 = 4.0;
+
+/// The number of sync requests the server serves at once for a single peer.
+const PEER_SERVER_BURST: f64 = 2.0;
+
+/// The number of peers whose rate limits are tracked before idle peers are pruned.
+const MAX_TRACKED_PEERS: usize = 1024;
+
+/// The number of peers a sync request is sent to before it is abandoned.
+const MAX_REQUEST_ATTEMPTS: usize = 3;
+
+/// The duration during which a peer that failed a sync request is not selected.
+const PEER_FAILURE_COOLDOWN: Duration = Duration::from_secs(30);
+
+/// Returns the [`StreamProtocol`] of the `payload_by_number` protocol for the given l2 chain id.
+pub fn sync_protocol(l2_chain_id: u64) -> Option<StreamProtocol> {
+    StreamProtocol::try_from_owned(format!("/opstack/req/payload_by_number/{l2_chain_id}/0/")).ok()
+}
+
+/// The result code of a sync response.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum SyncResultCode {
+    /// The payload is returned.
+    Success = 0,
+    /// The requested payload is not known.
+    NotFound = 1,
+    /// The request is malformed.
+    InvalidRequest = 2,
+    /// The request could not be served.
+    Unknown = 3,
+}
+
+/// A request for the payload of the block with the given number, which is expected to have the
+/// given hash.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SyncRequest {
+    /// The number of the requested block.
+    pub number: u64,
+    /// The expected hash of the requested block.
+    pub hash: B256,
+}
+
+/// The outcome of a [`SyncRequest`] sent to a peer.
+#[derive(Debug)]
+pub struct SyncResponse {
+    /// The request.
+    pub request: SyncRequest,
+    /// The peer that the request was sent to.
+    pub peer_id: PeerId,
+    /// The returned payload, or the reason the request failed.
+    pub result: Result<OpExecutionPayloadEnvelope, SyncError>,
+}
+
+/// A source of the unsafe payloads served to peers over the sync protocol.
+pub trait PayloadSource: std::fmt::Debug + Send + Sync {
+    /// Returns the canonical payload with the given block number, if it is known.
+    fn payload_by_number(&self, number: u64) -> BoxFuture<'_, Option<OpExecutionPayloadEnvelope>>;
+}
+
+/// A token bucket rate limiter. Requests that exceed the rate are not rejected, but delayed until
+/// the bucket has refilled.
+#[derive(Debug, Clone, Copy)]
+struct RateLimiter {
+    /// The number of tokens added per second.
+    rate: f64,
+    /// The maximum number of tokens.
+    burst: f64,
+    /// The current number of tokens, which is negative when requests are delayed.
+    tokens: f64,
+    /// The last time the tokens were refilled.
+    last: Instant,
+}
+
+impl RateLimiter {
+    /// Creates a new, full [`RateLimiter`].
+    fn new(rate: f64, burst: f64) -> Self {
+        Self { rate, burst, tokens: burst, last: Instant::now() }
+    }
+
+    /// Refills the bucket up to the given instant.
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.last).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.rate).min(self.burst);
+        self.last = now;
+    }
+
+    /// Reserves a token, returning the delay after which the request may be served.
+    fn reserve(&mut self, now: Instant) -> Duration {
+        self.refill(now);
+        self.tokens -= 1.0;
+        if self.tokens >= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(-self.tokens / self.rate)
+        }
+    }
+
+    /// Cancels a reservation whose request is not served.
+    fn cancel(&mut self) {
+        self.tokens += 1.0;
+    }
+
+    /// Returns `true` if the bucket is full at the given instant.
+    fn is_idle(&mut self, now: Instant) -> bool {
+        self.refill(now);
+        self.tokens >= self.burst
+    }
+}
+
+/// Serves the sync requests received on the given streams, with the payloads of the given
+/// [`PayloadSource`]. If there is no source, every request is answered with
+/// [`SyncResultCode::NotFound`].
+///
+/// Requests are rate limited across all peers and per peer. Requests that cannot be served
+/// before they time out are dropped.
+pub(crate) async fn serve_sync_requests(
+    mut streams: IncomingStreams,
+    source: Option<Arc<dyn PayloadSource>>,
+    rollup_config: Arc<RollupConfig>,
+) {
+    let mut global = RateLimiter::new(GLOBAL_SERVER_RATE, GLOBAL_SERVER_BURST);
+    let mut peers = HashMap::<PeerId, RateLimiter>::new();
+
+    while let Some((peer_id, mut stream)) = streams.next().await {
+        let now = Instant::now();
+        if peers.len() >= MAX_TRACKED_PEERS {
+            peers.retain(|_, limiter| !limiter.is_idle(now));
+        }
+        let peer = peers
+            .entry(peer_id)
+            .or_insert_with(|| RateLimiter::new(PEER_SERVER_RATE, PEER_SERVER_BURST));
+        let delay = global.reserve(now).max(peer.reserve(now));
+        if delay >= SYNC_REQUEST_TIMEOUT {
+            debug!(target: "gossip::sync", ?peer_id, "Dropping rate limited sync request");
+            global.cancel();
+            peer.cancel();
+            continue;
+        }
+
+        let source = source.clone();
+        let rollup_config = rollup_config.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(delay).await;
+            let response = tokio::time::timeout(SYNC_REQUEST_TIMEOUT, async {
+                let mut request = [0u8; 8];
+                stream.read_exact(&mut request).await?;
+                let number = u64::from_le_bytes(request);
+                let response = sync_response(number, source.as_deref(), &rollup_config).await;
+                stream.write_all(&response).await?;
+                stream.close().await
+            })
+            .await;
+            match response {
+                Ok(Ok(())) => trace!(target: "gossip::sync", ?peer_id, "Served sync request"),
+                Ok(Err(err)) => {
+                    debug!(target: "gossip::sync", ?peer_id, ?err, "Failed to serve sync request")
+                }
+                Err(_) => debug!(target: "gossip::sync", ?peer_id, "Sync request timed out"),
+            }
+        });
+    }
+
+    warn!(target: "gossip::sync", "The sync protocol stream has ended");
+}
+
+/// Returns the encoded response to a request for the payload with the given number.
+async fn sync_response(
+    number: u64,
+    source: Option<&dyn PayloadSource>,
+    rollup_config: &RollupConfig,
+) -> Vec<u8> {
+    let Some(source) = source else {
+        return vec![SyncResultCode::NotFound as u8];
+    };
+    let Some(envelope) = source.payload_by_number(number).await else {
+        return vec![SyncResultCode::NotFound as u8];
+    };
+    let version = payload_version(rollup_config, envelope.execution_payload.timestamp());
+    match encode_payload(version, envelope) {
+        Ok(payload) => {
+            let mut response = Vec::with_capacity(5 + payload.len());
+            response.push(SyncResultCode::Success as u8);
+            response.extend_from_slice(&0u32.to_le_bytes());
+            response.extend_from_slice(&payload);
+            response
+        }
+        Err(err) => {
+            warn!(target: "gossip::sync", number, ?err, "Failed to encode sync payload");
+            vec![SyncResultCode::Unknown as u8]
+        }
+    }
+}
+
+/// Returns the placeholder signature used to reuse the gossip payload encoding, which is not
+/// part of sync responses.
+const fn placeholder_signature() -> Signature {
+    Signature::new(U256::ZERO, U256::ZERO, false)
+}
+
+/// Encodes the payload of a sync response, using the SSZ encoding of the given payload version.
+fn encode_payload(version: u8, envelope: OpExecutionPayloadEnvelope) -> Result<Vec<u8>, SyncError> {
+    let envelope = OpNetworkPayloadEnvelope {
+        payload: envelope.execution_payload,
+        parent_beacon_block_root: envelope.parent_beacon_block_root,
+        signature: placeholder_signature(),
+        payload_hash: PayloadHash(B256::ZERO),
+    };
+    let encoded = match version {
+        1 => envelope.encode_v1(),
+        2 => envelope.encode_v2(),
+        3 => envelope.encode_v3(),
+        _ => envelope.encode_v4(),
+    }
+    .map_err(|_| SyncError::Encode)?;
+    let decompressed =
+        snap::raw::Decoder::new().decompress_vec(&encoded).map_err(|_| SyncError::Encode)?;
+
+    let mut encoder = snap::write::FrameEncoder::new(Vec::new());
+    encoder.write_all(&decompressed[SIGNATURE_LEN..])?;
+    encoder.into_inner().map_err(|_| SyncError::Encode)
+}
+
+/// Decodes the payload of a sync response, using the SSZ encoding of the given payload version.
+fn decode_payload(version: u8, data: &[u8]) -> Result<OpExecutionPayloadEnvelope, SyncError> {
+    let mut payload = placeholder_signature().as_bytes().to_vec();
+    snap::read::FrameDecoder::new(data).take(MAX_SYNC_RESPONSE_SIZE).read_to_end(&mut payload)?;
+    let encoded =
+        snap::raw::Encoder::new().compress_vec(&payload).map_err(|_| SyncError::Decode)?;
+    let envelope = match version {
+        1 => OpNetworkPayloadEnvelope::decode_v1(&encoded),
+        2 => OpNetworkPayloadEnvelope::decode_v2(&encoded),
+        3 => OpNetworkPayloadEnvelope::decode_v3(&encoded),
+        _ => OpNetworkPayloadEnvelope::decode_v4(&encoded),
+    }
+    .map_err(|_| SyncError::Decode)?;
+    Ok(OpExecutionPayloadEnvelope {
+        execution_payload: envelope.payload,
+        parent_beacon_block_root: envelope.parent_beacon_block_root,
+    })
+}
+
+/// Decodes a sync response.
+fn decode_response(version: u8, response: &[u8]) -> Result<OpExecutionPayloadEnvelope, SyncError> {
+    let (&code, rest) = response.split_first().ok_or(SyncError::Decode)?;
+    if code != SyncResultCode::Success as u8 {
+        return Err(SyncError::ResultCode(code));
+    }
+    let (response_version, payload) = rest.split_first_chunk::<4>().ok_or(SyncError::Decode)?;
+    let response_version = u32::from_le_bytes(*response_version);
+    if response_version != 0 {
+        return Err(SyncError::UnsupportedVersion(response_version));
+    }
+    decode_payload(version, payload)
+}
+
+/// The record of a peer's sync responses, used to select the peers that requests are sent to.
+#[derive(Debug, Default, Clone, Copy)]
+struct SyncPeer {
+    /// The number of valid responses.
+    successes: u64,
+    /// The number of failed requests.
+    failures: u64,
+    /// The time of the last failed request.
+    last_failure: Option<Instant>,
+    /// The number of requests in flight.
+    in_flight: usize,
+}
+
+impl SyncPeer {
+    /// Returns `true` if the peer failed a request within the [`PEER_FAILURE_COOLDOWN`].
+    fn cooling_down(&self, now: Instant) -> bool {
+        self.last_failure.is_some_and(|t| now.saturating_duration_since(t) < PEER_FAILURE_COOLDOWN)
+    }
+}
+
+/// An in flight [`SyncRequest`].
+#[derive(Debug, Clone)]
+struct InFlight {
+    /// The expected hash of the requested block.
+    hash: B256,
+    /// The peers that the request was sent to.
+    tried: HashSet<PeerId>,
+}
+
+/// The client side of the sync protocol, which requests missing payloads from peers.
+///
+/// Each request is sent to a single peer at a time. Peers are selected among the peers that
+/// support the protocol, preferring peers with the fewest requests in flight and the best record
+/// of valid responses, and skipping peers that recently failed a request. Failed requests are
+/// retried with another peer, up to a maximum number of attempts.
+#[derive(Debug)]
+pub struct SyncClient {
+    /// The stream control used to open request streams.
+    #[debug(skip)]
+    control: libp2p_stream::Control,
+    /// The sync protocol.
+    protocol: StreamProtocol,
+    /// The rollup config, used to determine the version of requested payloads.
+    rollup_config: Arc<RollupConfig>,
+    /// The requests in flight, by block number.
+    in_flight: HashMap<u64, InFlight>,
+    /// The record of each peer's responses.
+    peers: HashMap<PeerId, SyncPeer>,
+    /// The sender of the responses of the requests in flight.
+    responses: mpsc::Sender<SyncResponse>,
+    /// The receiver of the responses of the requests in flight.
+    ///
+    /// This is an option to allow to take the receiver when the network is started.
+    responses_rx: Option<mpsc::Receiver<SyncResponse>>,
+}
+
+impl SyncClient {
+    /// Creates a new [`SyncClient`].
+    pub fn new(
+        control: libp2p_stream::Control,
+        protocol: StreamProtocol,
+        rollup_config: Arc<RollupConfig>,
+    ) -> Self {
+        let (responses, responses_rx) = mpsc::channel(256);
+        Self {
+            control,
+            protocol,
+            rollup_config,
+            in_flight: HashMap::new(),
+            peers: HashMap::new(),
+            responses,
+            responses_rx: Some(responses_rx),
+        }
+    }
+
+    /// Takes the receiver of the responses to the client's requests. Each response must be passed
+    /// to [`SyncClient::handle_response`].
+    pub const fn take_responses(&mut self) -> Option<mpsc::Receiver<SyncResponse>> {
+        self.responses_rx.take()
+    }
+
+    /// Returns the sync protocol.
+    pub const fn protocol(&self) -> &StreamProtocol {
+        &self.protocol
+    }
+
+    /// Sends the given request to one of the given candidate peers, unless it is already in
+    /// flight. Returns `false` if no peer is available.
+    pub fn request(&mut self, request: SyncRequest, candidates: &[PeerId]) -> bool {
+        if self.in_flight.contains_key(&request.number) {
+            return true;
+        }
+        let in_flight = InFlight { hash: request.hash, tried: HashSet::new() };
+        self.send(request, in_flight, candidates)
+    }
+
+    /// Handles the response to a request, returning the payload if it is valid. Failed requests
+    /// are retried with another of the given candidate peers.
+    pub fn handle_response(
+        &mut self,
+        response: SyncResponse,
+        candidates: &[PeerId],
+    ) -> Option<OpExecutionPayloadEnvelope> {
+        let SyncResponse { request, peer_id, result } = response;
+        let in_flight = self.in_flight.remove(&request.number)?;
+        let result = result.and_then(|envelope| {
+            let received = envelope.execution_payload.block_hash();
+            if received == in_flight.hash {
+                Ok(envelope)
+            } else {
+                Err(SyncError::UnexpectedHash { expected: in_flight.hash, received })
+            }
+        });
+
+        let peer = self.peers.entry(peer_id).or_default();
+        peer.in_flight = peer.in_flight.saturating_sub(1);
+        match result {
+            Ok(envelope) => {
+                peer.successes += 1;
+                debug!(target: "gossip::sync", ?peer_id, number = request.number, "Received payload from sync request");
+                Some(envelope)
+            }
+            Err(err) => {
+                peer.failures += 1;
+                peer.last_failure = Some(Instant::now());
+                debug!(target: "gossip::sync", ?peer_id, number = request.number, ?err, "Sync request failed");
+                if in_flight.tried.len() < MAX_REQUEST_ATTEMPTS {
+                    self.send(request, in_flight, candidates);
+                }
+                None
+            }
+        }
+    }
+
+    /// Forgets the record of the given peer, once it has disconnected.
+    pub fn remove_peer(&mut self, peer_id: &PeerId) {
+        self.peers.remove(peer_id);
+    }
+
+    /// Selects the peer that the given request should be sent to.
+    fn select_peer(&self, in_flight: &InFlight, candidates: &[PeerId]) -> Option<PeerId> {
+        let now = Instant::now();
+        candidates
+            .iter()
+            .filter(|peer_id| !in_flight.tried.contains(peer_id))
+            .map(|peer_id| (peer_id, self.peers.get(peer_id).copied().unwrap_or_default()))
+            .filter(|(_, peer)| !peer.cooling_down(now))
+            .min_by_key(|(_, peer)| (peer.in_flight, peer.failures, Reverse(peer.successes)))
+            .map(|(peer_id, _)| *peer_id)
+    }
+
+    /// Sends the given request to a selected peer.
+    fn send(
+        &mut self,
+        request: SyncRequest,
+        mut in_flight: InFlight,
+        candidates: &[PeerId],
+    ) -> bool {
+        let Some(peer_id) = self.select_peer(&in_flight, candidates) else {
+            debug!(target: "gossip::sync", number = request.number, "No peer available for sync request");
+            return false;
+        };
+        let Some(version) = self.payload_version(request.number) else {
+            return false;
+        };
+
+        in_flight.tried.insert(peer_id);
+        self.in_flight.insert(request.number, in_flight);
+        self.peers.entry(peer_id).or_default().in_flight += 1;
+
+        let mut control = self.control.clone();
+        let protocol = self.protocol.clone();
+        let responses = self.responses.clone();
+        tokio::spawn(async move {
+            let result = tokio::time::timeout(SYNC_REQUEST_TIMEOUT, async {
+                let mut stream = control
+                    .open_stream(peer_id, protocol)
+                    .await
+                    .map_err(|e| SyncError::OpenStream(e.to_string()))?;
+                stream.write_all(&request.number.to_le_bytes()).await?;
+                stream.close().await?;
+                let mut response = Vec::new();
+                stream.take(MAX_SYNC_RESPONSE_SIZE).read_to_end(&mut response).await?;
+                decode_response(version, &response)
+            })
+            .await
+            .unwrap_or(Err(SyncError::Timeout));
+            _ = responses.send(SyncResponse { request, peer_id, result }).await;
+        });
+        true
+    }
+
+    /// Returns the payload version of the block with the given number, based on its expected
+    /// timestamp.
+    fn payload_version(&self, number: u64) -> Option<u8> {
+        let genesis = &self.rollup_config.genesis;
+        let blocks = number.checked_sub(genesis.l2.number)?;
+        let timestamp = genesis.l2_time + blocks * self.rollup_config.block_time;
+        Some(payload_version(&self.rollup_config, timestamp))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_rpc_types_engine::ExecutionPayloadV1;
+    use arbitrary::Arbitrary;
+    use libp2p::identity::Keypair;
+    use op_alloy_rpc_types_engine::OpExecutionPayload;
+
+    fn random_peer() -> PeerId {
+        Keypair::generate_secp256k1().public().to_peer_id()
+    }
+
+    #[test]
+    fn test_rate_limiter_delays_over_burst() {
+        let now = Instant::now();
+        let mut limiter = RateLimiter::new(4.0, 2.0);
+        assert_eq!(limiter.reserve(now), Duration::ZERO);
+        assert_eq!(limiter.reserve(now), Duration::ZERO);
+        assert_eq!(limiter.reserve(now), Duration::from_millis(250));
+        assert_eq!(limiter.reserve(now), Duration::from_millis(500));
+
+        limiter.cancel();
+        assert_eq!(limiter.reserve(now + Duration::from_millis(500)), Duration::ZERO);
+        assert!(limiter.is_idle(now + Duration::from_secs(1)));
+    }
+
+    #[test]
+    fn test_sync_response_roundtrip() {
+        let mut payload =
+            ExecutionPayloadV1::arbitrary(&mut arbitrary::Unstructured::new(&[])).unwrap();
+        payload.block_number = 10;
+        payload.block_hash = B256::with_last_byte(10);
+        let envelope = OpExecutionPayloadEnvelope {
+            execution_payload: OpExecutionPayload::V1(payload),
+            parent_beacon_block_root: None,
+        };
+
+        let mut response = vec![SyncResultCode::Success as u8, 0, 0, 0, 0];
+        response.extend(encode_payload(1, envelope.clone()).unwrap());
+        let decoded = decode_response(1, &response).unwrap();
+        assert_eq!(decoded, envelope);
+    }
+
+    #[test]
+    fn test_sync_response_errors() {
+        assert!(matches!(
+            decode_response(1, &[SyncResultCode::NotFound as u8]),
+            Err(SyncError::ResultCode(1))
+        ));
+        assert!(matches!(
+            decode_response(1, &[SyncResultCode::Success as u8, 1, 0, 0, 0]),
+            Err(SyncError::UnsupportedVersion(1))
+        ));
+        assert!(matches!(decode_response(1, &[]), Err(SyncError::Decode)));
+    }
+
+    #[test]
+    fn test_sync_client_peer_selection() {
+        let control = libp2p_stream::Behaviour::new().new_control();
+        let protocol = sync_protocol(10).unwrap();
+        let mut client = SyncClient::new(control, protocol, Arc::new(RollupConfig::default()));
+
+        let (busy, failing, cooling, best) =
+            (random_peer(), random_peer(), random_peer(), random_peer());
+        client.peers.insert(busy, SyncPeer { in_flight: 1, ..Default::default() });
+        client.peers.insert(failing, SyncPeer { failures: 2, ..Default::default() });
+        client
+            .peers
+            .insert(cooling, SyncPeer { last_failure: Some(Instant::now()), ..Default::default() });
+        client.peers.insert(best, SyncPeer { successes: 3, ..Default::default() });
+
+        let candidates = [busy, failing, cooling, best];
+        let mut in_flight = InFlight { hash: B256::ZERO, tried: HashSet::new() };
+        assert_eq!(client.select_peer(&in_flight, &candidates), Some(best));
+
+        in_flight.tried.insert(best);
+        assert_eq!(client.select_peer(&in_flight, &candidates), Some(failing));
+
+        in_flight.tried.extend([failing, busy]);
+        assert_eq!(client.select_peer(&in_flight, &candidates), None);
+    }
+}
@@ -0,0 +1,163 @@
+//! Contains the [`BanList`], a persisted list of banned peers.
+
+use libp2p::PeerId;
+use std::{
+    collections::HashMap,
+    path::PathBuf,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+/// A list of banned peers and the time at which their bans expire.
+///
+/// If the [`BanList`] is backed by a file, the list is a simple JSON object mapping each banned
+/// peer id to the unix timestamp, in seconds, at which its ban expires. The file is read when the
+/// list is opened, so that bans persist across restarts.
+#[derive(Debug, Default, Clone)]
+pub struct BanList {
+    /// The file backing the [`BanList`], if any.
+    pub path: Option<PathBuf>,
+    /// The banned peers, mapped to the unix timestamp at which their ban expires.
+    pub peers: HashMap<PeerId, u64>,
+}
+
+impl BanList {
+    /// The default file name of the [`BanList`], within the `~/.kona/<chain_id>` directory.
+    pub const DEFAULT_FILE_NAME: &str = "banlist.json";
+
+    /// Returns the default path of the [`BanList`] for the given l2 chain id, ie
+    /// `~/.kona/<chain_id>/banlist.json`.
+    pub fn default_path(chain_id: u64) -> Option<PathBuf> {
+        let mut path = dirs::home_dir()?;
+        path.push(".kona");
+        path.push(chain_id.to_string());
+        path.push(Self::DEFAULT_FILE_NAME);
+        Some(path)
+    }
+
+    /// Opens the [`BanList`] backed by the file at the given path. Expired bans, and entries that
+    /// fail to parse, are dropped. If the file does not exist or cannot be read, the list starts
+    /// empty.
+    pub fn open(path: PathBuf) -> Self {
+        let now = unix_now();
+        let peers = match std::fs::read(&path) {
+            Ok(contents) => serde_json::from_slice::<HashMap<String, u64>>(&contents)
+                .inspect_err(|e| warn!(target: "banlist", ?e, "Failed to parse ban list"))
+                .unwrap_or_default()
+                .into_iter()
+                .filter(|(_, expiry)| *expiry > now)
+                .filter_map(|(peer, expiry)| Some((peer.parse().ok()?, expiry)))
+                .collect(),
+            Err(e) => {
+                debug!(target: "banlist", ?e, ?path, "No ban list read from disk");
+                HashMap::new()
+            }
+        };
+        Self { path: Some(path), peers }
+    }
+
+    /// Bans the given peer for the given duration, extending any existing ban.
+    ///
+    /// The ban is not written to disk until [`BanList::sync`] is called.
+    pub fn ban(&mut self, peer_id: PeerId, duration: Duration) {
+        let expiry = unix_now().saturating_add(duration.as_secs());
+        let entry = self.peers.entry(peer_id).or_default();
+        *entry = (*entry).max(expiry);
+    }
+
+    /// Lifts the ban of the given peer. Returns `true` if the peer was banned.
+    pub fn unban(&mut self, peer_id: &PeerId) -> bool {
+        self.peers.remove(peer_id).is_some()
+    }
+
+    /// Returns `true` if the given peer is banned.
+    pub fn is_banned(&self, peer_id: &PeerId) -> bool {
+        self.peers.get(peer_id).is_some_and(|expiry| *expiry > unix_now())
+    }
+
+    /// Returns the peers whose bans have not expired.
+    pub fn banned(&self) -> Vec<PeerId> {
+        let now = unix_now();
+        self.peers.iter().filter(|(_, expiry)| **expiry > now).map(|(peer, _)| *peer).collect()
+    }
+
+    /// Removes the expired bans, returning the peers whose bans expired.
+    pub fn remove_expired(&mut self) -> Vec<PeerId> {
+        let now = unix_now();
+        let expired = self
+            .peers
+            .iter()
+            .filter(|(_, expiry)| **expiry <= now)
+            .map(|(peer, _)| *peer)
+            .collect::<Vec<_>>();
+        for peer in &expired {
+            self.peers.remove(peer);
+        }
+        expired
+    }
+
+    /// Writes the [`BanList`] to its backing file, if any.
+    pub fn sync(&self) -> Result<(), std::io::Error> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let peers = self
+            .peers
+            .iter()
+            .map(|(peer, expiry)| (peer.to_string(), *expiry))
+            .collect::<HashMap<_, _>>();
+        std::fs::write(path, serde_json::to_vec(&peers)?)
+    }
+}
+
+/// Returns the current unix timestamp, in seconds.
+fn unix_now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use libp2p::identity::Keypair;
+
+    fn random_peer() -> PeerId {
+        Keypair::generate_secp256k1().public().to_peer_id()
+    }
+
+    #[test]
+    fn test_ban_list_ban_and_expire() {
+        let mut list = BanList::default();
+        let banned = random_peer();
+        let expired = random_peer();
+
+        list.ban(banned, Duration::from_secs(60));
+        list.ban(expired, Duration::ZERO);
+
+        assert!(list.is_banned(&banned));
+        assert!(!list.is_banned(&expired));
+        assert_eq!(list.banned(), vec![banned]);
+        assert_eq!(list.remove_expired(), vec![expired]);
+
+        assert!(list.unban(&banned));
+        assert!(!list.is_banned(&banned));
+    }
+
+    #[test]
+    fn test_ban_list_persists() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(BanList::DEFAULT_FILE_NAME);
+        let peer = random_peer();
+
+        let mut list = BanList::open(path.clone());
+        assert!(list.peers.is_empty());
+        list.ban(peer, Duration::from_secs(60));
+        list.ban(random_peer(), Duration::ZERO);
+        list.sync().unwrap();
+
+        let list = BanList::open(path);
+        assert_eq!(list.banned(), vec![peer]);
+        assert_eq!(list.peers.len(), 1);
+    }
+}
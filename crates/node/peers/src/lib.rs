@@ -22,7 +22,7 @@ mod store;
 pub use store::{BootStore, BootStoreFile};
 
 mod score;
-pub use score::PeerScoreLevel;
+pub use score::{PeerScoreLevel, TopicScoreWeights};
 
 mod enr;
 pub use enr::{EnrValidation, OpStackEnr, OpStackEnrError};
@@ -43,3 +43,6 @@ pub use utils::{
 
 mod monitoring;
 pub use monitoring::PeerMonitoring;
+
+mod ban;
+pub use ban::BanList;
@@ -9,4 +9,7 @@ pub struct PeerMonitoring {
     pub ban_threshold: f64,
     /// The duration of a peer's ban.
     pub ban_duration: Duration,
+    /// The number of payloads with invalid signatures a peer may send before it is banned. If
+    /// zero, peers are not banned for sending payloads with invalid signatures.
+    pub invalid_signature_threshold: u32,
 }
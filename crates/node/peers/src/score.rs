@@ -4,6 +4,28 @@ use derive_more::{Display, FromStr};
 use libp2p::gossipsub::{PeerScoreParams, PeerScoreThresholds, TopicHash, TopicScoreParams};
 use std::collections::HashMap;
 
+/// The configurable weights of the gossipsub topic scores.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TopicScoreWeights {
+    /// The weight of each topic score in the peer score.
+    pub topic_weight: f64,
+    /// The weight of the penalty applied for invalid messages delivered on a topic. This must be
+    /// negative.
+    pub invalid_message_deliveries_weight: f64,
+}
+
+impl TopicScoreWeights {
+    /// The default [`TopicScoreWeights`], matching the op-node defaults.
+    pub const DEFAULT: Self =
+        Self { topic_weight: 0.8, invalid_message_deliveries_weight: -140.4475 };
+}
+
+impl Default for TopicScoreWeights {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
 /// The peer scoring level is used to determine
 /// how peers are scored based on their behavior.
 #[derive(Debug, FromStr, Display, Default, Clone, Copy, PartialEq, Eq)]
@@ -53,15 +75,15 @@ impl PeerScoreLevel {
         (3600 * std::time::Duration::from_secs(1)).as_secs_f64() / slot.as_secs_f64()
     }
 
-    /// Returns the topic score parameters given the block time.
-    pub fn topic_score_params(block_time: u64) -> TopicScoreParams {
+    /// Returns the topic score parameters given the block time and [`TopicScoreWeights`].
+    pub fn topic_score_params(block_time: u64, weights: TopicScoreWeights) -> TopicScoreParams {
         let slot = std::time::Duration::from_secs(block_time);
         let epoch = slot * 6;
         let invalid_decay_period = 50 * epoch;
         let decay_epoch =
             std::time::Duration::from_secs(Self::DECAY_EPOCH as u64 * epoch.as_secs());
         TopicScoreParams {
-            topic_weight: 0.8,
+            topic_weight: weights.topic_weight,
             time_in_mesh_weight: Self::MAX_IN_MESH_SCORE / Self::in_mesh_cap(slot),
             time_in_mesh_quantum: slot,
             time_in_mesh_cap: Self::in_mesh_cap(slot),
@@ -79,7 +101,7 @@ impl PeerScoreLevel {
             mesh_message_deliveries_activation: epoch * 4,
             mesh_failure_penalty_weight: Self::MESH_WEIGHT,
             mesh_failure_penalty_decay: Self::score_decay(decay_epoch, slot),
-            invalid_message_deliveries_weight: -140.4475,
+            invalid_message_deliveries_weight: weights.invalid_message_deliveries_weight,
             invalid_message_deliveries_decay: Self::score_decay(invalid_decay_period, slot),
         }
     }
@@ -88,11 +110,12 @@ impl PeerScoreLevel {
     pub fn topic_scores(
         topics: Vec<TopicHash>,
         block_time: u64,
+        weights: TopicScoreWeights,
     ) -> HashMap<TopicHash, TopicScoreParams> {
         let mut topic_scores = HashMap::with_capacity(topics.len());
         for topic in topics {
             debug!(target: "scoring", "Topic scoring enabled on topic: {}", topic);
-            topic_scores.insert(topic, Self::topic_score_params(block_time));
+            topic_scores.insert(topic, Self::topic_score_params(block_time, weights));
         }
         topic_scores
     }
@@ -101,11 +124,13 @@ impl PeerScoreLevel {
     ///
     /// # Arguments
     /// * `block_time` - The block time in seconds.
+    /// * `weights` - The [`TopicScoreWeights`], applied if topic scoring is enabled.
     pub fn to_params(
         &self,
         topics: Vec<TopicHash>,
         topic_scoring: bool,
         block_time: u64,
+        weights: TopicScoreWeights,
     ) -> Option<PeerScoreParams> {
         let slot = std::time::Duration::from_secs(block_time);
         debug!(target: "scoring", "Slot duration: {:?}", slot);
@@ -113,8 +138,11 @@ impl PeerScoreLevel {
         let ten_epochs = epoch * 10;
         let one_hundred_epochs = epoch * 100;
         let penalty_decay = Self::score_decay(ten_epochs, slot);
-        let topics =
-            if topic_scoring { Self::topic_scores(topics, block_time) } else { Default::default() };
+        let topics = if topic_scoring {
+            Self::topic_scores(topics, block_time, weights)
+        } else {
+            Default::default()
+        };
         match self {
             Self::Off => None,
             Self::Light => Some(PeerScoreParams {
@@ -1,13 +1,17 @@
 //! Admin RPC Module
 
-use crate::AdminApiServer;
-use alloy_primitives::B256;
+use crate::{AdminApiServer, RpcNamespaceError, RpcNamespaces};
+use alloy_eips::eip2718::Decodable2718;
+use alloy_primitives::{B256, Bytes};
+use alloy_rpc_types_eth::erc4337::TransactionConditional;
 use async_trait::async_trait;
 use jsonrpsee::{
     core::RpcResult,
-    types::{ErrorCode, ErrorObject},
+    types::{ErrorCode, ErrorObject, ErrorObjectOwned},
 };
+use op_alloy_consensus::OpTxEnvelope;
 use op_alloy_rpc_types_engine::OpExecutionPayloadEnvelope;
+use std::{fmt::Debug, sync::Arc};
 use tokio::sync::oneshot;
 
 /// The query types to the sequencer actor for the admin api.
@@ -16,15 +20,45 @@ pub enum SequencerAdminQuery {
     /// A query to check if the sequencer is active.
     SequencerActive(oneshot::Sender<bool>),
     /// A query to start the sequencer.
-    StartSequencer,
-    /// A query to stop the sequencer.
-    StopSequencer(oneshot::Sender<B256>),
+    StartSequencer(oneshot::Sender<Result<(), SequencerAdminError>>),
+    /// A query to stop the sequencer. Responds with the hash of the unsafe head at which the
+    /// sequencer stopped.
+    StopSequencer(oneshot::Sender<Result<B256, SequencerAdminError>>),
     /// A query to check if the conductor is enabled.
     ConductorEnabled(oneshot::Sender<bool>),
     /// A query to set the recover mode.
     SetRecoveryMode(bool),
     /// A query to override the leader.
-    OverrideLeader,
+    OverrideLeader(oneshot::Sender<Result<(), SequencerAdminError>>),
+}
+
+/// An error returned by the sequencer actor in response to a [`SequencerAdminQuery`].
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum SequencerAdminError {
+    /// The sequencer is already running.
+    #[error("Sequencer already running")]
+    AlreadyRunning,
+    /// The sequencer is not running.
+    #[error("Sequencer not running")]
+    NotRunning,
+    /// The conductor service is not enabled.
+    #[error("Conductor not enabled")]
+    ConductorDisabled,
+    /// The conductor service returned an error.
+    #[error("Conductor error: {0}")]
+    Conductor(String),
+}
+
+impl From<SequencerAdminError> for ErrorObjectOwned {
+    fn from(err: SequencerAdminError) -> Self {
+        let code = match err {
+            SequencerAdminError::AlreadyRunning |
+            SequencerAdminError::NotRunning |
+            SequencerAdminError::ConductorDisabled => ErrorCode::InvalidRequest,
+            SequencerAdminError::Conductor(_) => ErrorCode::InternalError,
+        };
+        Self::owned(code.code(), err.to_string(), None::<()>)
+    }
 }
 
 /// The query types to the network actor for the admin api.
@@ -35,9 +69,77 @@ pub enum NetworkAdminQuery {
         /// The payload to post.
         payload: OpExecutionPayloadEnvelope,
     },
+    /// An admin rpc request to set the maximum number of connected peers above which no new
+    /// discovered peers are dialed. `None` removes the limit.
+    SetMaxPeers {
+        /// The maximum number of connected peers.
+        max_peers: Option<usize>,
+    },
+}
+
+/// Replaces the log filter of the node at runtime.
+///
+/// Implemented by the binary that owns the tracing subscriber, and used by the admin api to
+/// adjust the log levels without restarting the node.
+pub trait LogFilterReloader: Debug + Send + Sync {
+    /// Replaces the log filter with the given directives, using the `RUST_LOG` syntax.
+    fn reload(&self, directives: &str) -> Result<(), String>;
+}
+
+impl From<RpcNamespaceError> for ErrorObjectOwned {
+    fn from(err: RpcNamespaceError) -> Self {
+        Self::owned(ErrorCode::InvalidParams.code(), err.to_string(), None::<()>)
+    }
+}
+
+/// A raw transaction submitted to the sequencer with conditions on its inclusion.
+#[derive(Debug)]
+pub struct ConditionalTransaction {
+    /// The EIP-2718 encoded transaction.
+    pub raw: Bytes,
+    /// The conditions that must hold for the block the transaction is included in.
+    pub conditional: TransactionConditional,
+    /// Responds with the hash of the transaction once the execution layer accepted it into its
+    /// transaction pool.
+    pub result: oneshot::Sender<Result<B256, ConditionalTransactionError>>,
+}
+
+/// The JSON-RPC error code of a transaction whose conditions do not hold, as returned by the
+/// execution layer.
+pub const TRANSACTION_CONDITIONAL_REJECTED_CODE: i32 = -32003;
+
+/// An error returned in response to a [`ConditionalTransaction`].
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ConditionalTransactionError {
+    /// The conditions can no longer hold for the next block built by the sequencer.
+    #[error("Transaction conditions cannot be met by the next block")]
+    Stale,
+    /// The execution layer rejected the transaction.
+    #[error("{message}")]
+    Rejected {
+        /// The JSON-RPC error code returned by the execution layer.
+        code: i32,
+        /// The error message returned by the execution layer.
+        message: String,
+    },
+    /// The request to the execution layer failed.
+    #[error("Execution layer error: {0}")]
+    ExecutionLayer(String),
+}
+
+impl From<ConditionalTransactionError> for ErrorObjectOwned {
+    fn from(err: ConditionalTransactionError) -> Self {
+        let code = match &err {
+            ConditionalTransactionError::Stale => TRANSACTION_CONDITIONAL_REJECTED_CODE,
+            ConditionalTransactionError::Rejected { code, .. } => *code,
+            ConditionalTransactionError::ExecutionLayer(_) => ErrorCode::InternalError.code(),
+        };
+        Self::owned(code, err.to_string(), None::<()>)
+    }
 }
 
 type SequencerQuerySender = tokio::sync::mpsc::Sender<SequencerAdminQuery>;
+type ConditionalTransactionSender = tokio::sync::mpsc::Sender<ConditionalTransaction>;
 type NetworkAdminQuerySender = tokio::sync::mpsc::Sender<NetworkAdminQuery>;
 
 /// The admin rpc server.
@@ -47,6 +149,12 @@ pub struct AdminRpc {
     pub sequencer_sender: Option<SequencerQuerySender>,
     /// The sender to the network actor.
     pub network_sender: NetworkAdminQuerySender,
+    /// The sender of conditional transactions to the engine actor.
+    pub conditional_tx_sender: Option<ConditionalTransactionSender>,
+    /// The reloader of the log filter. If unset, the log filter cannot be changed at runtime.
+    pub log_filter: Option<Arc<dyn LogFilterReloader>>,
+    /// The RPC namespaces toggled at runtime.
+    pub namespaces: RpcNamespaces,
 }
 
 #[async_trait]
@@ -82,10 +190,13 @@ impl AdminApiServer for AdminRpc {
             return Err(ErrorObject::from(ErrorCode::MethodNotFound));
         };
 
+        let (tx, rx) = oneshot::channel();
+
         sequencer_sender
-            .send(SequencerAdminQuery::StartSequencer)
+            .send(SequencerAdminQuery::StartSequencer(tx))
             .await
-            .map_err(|_| ErrorObject::from(ErrorCode::InternalError))
+            .map_err(|_| ErrorObject::from(ErrorCode::InternalError))?;
+        rx.await.map_err(|_| ErrorObject::from(ErrorCode::InternalError))?.map_err(Into::into)
     }
 
     async fn admin_stop_sequencer(&self) -> RpcResult<B256> {
@@ -100,7 +211,7 @@ impl AdminApiServer for AdminRpc {
             .send(SequencerAdminQuery::StopSequencer(tx))
             .await
             .map_err(|_| ErrorObject::from(ErrorCode::InternalError))?;
-        rx.await.map_err(|_| ErrorObject::from(ErrorCode::InternalError))
+        rx.await.map_err(|_| ErrorObject::from(ErrorCode::InternalError))?.map_err(Into::into)
     }
 
     async fn admin_conductor_enabled(&self) -> RpcResult<bool> {
@@ -136,9 +247,68 @@ impl AdminApiServer for AdminRpc {
             return Err(ErrorObject::from(ErrorCode::MethodNotFound));
         };
 
+        let (tx, rx) = oneshot::channel();
+
         sequencer_sender
-            .send(SequencerAdminQuery::OverrideLeader)
+            .send(SequencerAdminQuery::OverrideLeader(tx))
+            .await
+            .map_err(|_| ErrorObject::from(ErrorCode::InternalError))?;
+        rx.await.map_err(|_| ErrorObject::from(ErrorCode::InternalError))?.map_err(Into::into)
+    }
+
+    async fn admin_send_raw_transaction_conditional(
+        &self,
+        bytes: Bytes,
+        conditional: TransactionConditional,
+    ) -> RpcResult<B256> {
+        // If the sequencer is not enabled (mode runs in validator mode), return an error.
+        let Some(ref conditional_tx_sender) = self.conditional_tx_sender else {
+            return Err(ErrorObject::from(ErrorCode::MethodNotFound));
+        };
+
+        // Deposit transactions can only be derived from L1.
+        match OpTxEnvelope::decode_2718(&mut bytes.as_ref()) {
+            Ok(OpTxEnvelope::Deposit(_)) | Err(_) => {
+                return Err(ErrorObject::from(ErrorCode::InvalidParams));
+            }
+            Ok(_) => {}
+        }
+
+        let (tx, rx) = oneshot::channel();
+
+        conditional_tx_sender
+            .send(ConditionalTransaction { raw: bytes, conditional, result: tx })
+            .await
+            .map_err(|_| ErrorObject::from(ErrorCode::InternalError))?;
+        rx.await.map_err(|_| ErrorObject::from(ErrorCode::InternalError))?.map_err(Into::into)
+    }
+
+    async fn admin_set_log_filter(&self, directives: String) -> RpcResult<()> {
+        let Some(ref log_filter) = self.log_filter else {
+            return Err(ErrorObject::from(ErrorCode::MethodNotFound));
+        };
+
+        log_filter
+            .reload(&directives)
+            .map_err(|e| ErrorObject::owned(ErrorCode::InvalidParams.code(), e, None::<()>))?;
+        info!(target: "rpc", %directives, "Log filter updated from admin api");
+        Ok(())
+    }
+
+    async fn admin_set_max_peers(&self, max_peers: Option<usize>) -> RpcResult<()> {
+        self.network_sender
+            .send(NetworkAdminQuery::SetMaxPeers { max_peers })
             .await
             .map_err(|_| ErrorObject::from(ErrorCode::InternalError))
     }
+
+    async fn admin_set_rpc_namespace(&self, namespace: String, enabled: bool) -> RpcResult<()> {
+        self.namespaces.set_enabled(&namespace, enabled)?;
+        info!(target: "rpc", %namespace, enabled, "RPC namespace toggled from admin api");
+        Ok(())
+    }
+
+    async fn admin_disabled_rpc_namespaces(&self) -> RpcResult<Vec<String>> {
+        Ok(self.namespaces.disabled())
+    }
 }
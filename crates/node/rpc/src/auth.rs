@@ -0,0 +1,257 @@
+//! Access control for the RPC namespaces that manage the node.
+
+use jsonrpsee::{
+    MethodResponse,
+    core::middleware::{Batch, BatchEntry, Notification, RpcServiceT},
+    server::HttpRequest,
+    types::{ErrorCode, ErrorObject, Request},
+};
+use std::{
+    borrow::Cow,
+    future::Future,
+    sync::Arc,
+    task::{Context, Poll},
+};
+use tower::{Layer, Service};
+
+/// The namespaces whose methods manage the node rather than read the rollup state. They can be
+/// served on a separate port, or to the callers authenticated with a bearer token only.
+pub const PROTECTED_NAMESPACES: &[&str] = &["admin", "debug", "dev", "interop", "opp2p"];
+
+/// The error code returned for the calls to protected methods without a valid bearer token.
+pub const UNAUTHORIZED_ERROR_CODE: i32 = -32001;
+
+/// Returns whether the method with the given name belongs to one of the
+/// [`PROTECTED_NAMESPACES`].
+pub fn is_protected_method(method: &str) -> bool {
+    method.split_once('_').is_some_and(|(namespace, _)| PROTECTED_NAMESPACES.contains(&namespace))
+}
+
+/// How a server handles the calls to the methods of the [`PROTECTED_NAMESPACES`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProtectedAccess {
+    /// The methods are served to every caller.
+    Open,
+    /// The methods are served to the callers authenticated with a bearer token.
+    Authenticated,
+    /// The methods are not served, and rejected with a method not found error.
+    Denied,
+}
+
+/// The marker inserted in the extensions of the requests that carry a valid bearer token.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Authenticated;
+
+/// An HTTP middleware layer that marks the requests carrying the configured bearer token in their
+/// `Authorization` header as [`Authenticated`].
+///
+/// The layer does not reject any request: the [`RpcAccessGate`] decides which methods require the
+/// caller to be authenticated. Without a token, no request is ever authenticated.
+#[derive(Debug, Clone, Default)]
+pub struct BearerAuthLayer {
+    /// The expected bearer token.
+    token: Option<Arc<str>>,
+}
+
+impl BearerAuthLayer {
+    /// Creates a new [`BearerAuthLayer`] authenticating the requests with the given token.
+    pub fn new(token: Option<&str>) -> Self {
+        Self { token: token.map(Arc::from) }
+    }
+}
+
+impl<S> Layer<S> for BearerAuthLayer {
+    type Service = BearerAuth<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        BearerAuth { inner, token: self.token.clone() }
+    }
+}
+
+/// The HTTP middleware built by the [`BearerAuthLayer`].
+#[derive(Debug, Clone)]
+pub struct BearerAuth<S> {
+    /// The inner service.
+    inner: S,
+    /// The expected bearer token.
+    token: Option<Arc<str>>,
+}
+
+impl<S> BearerAuth<S> {
+    /// Returns whether the given `Authorization` header value carries the expected token.
+    fn is_authorized(&self, header: &[u8]) -> bool {
+        let Some(token) = self.token.as_deref() else {
+            return false;
+        };
+        header
+            .strip_prefix(b"Bearer ")
+            .is_some_and(|given| constant_time_eq(given, token.as_bytes()))
+    }
+}
+
+impl<S, B> Service<HttpRequest<B>> for BearerAuth<S>
+where
+    S: Service<HttpRequest<B>>,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = S::Future;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut request: HttpRequest<B>) -> Self::Future {
+        let authorized = request
+            .headers()
+            .get("authorization")
+            .is_some_and(|header| self.is_authorized(header.as_bytes()));
+        if authorized {
+            request.extensions_mut().insert(Authenticated);
+        }
+        self.inner.call(request)
+    }
+}
+
+/// Compares the two byte strings in a time that only depends on their lengths.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+/// An RPC middleware that handles the calls to the methods of the [`PROTECTED_NAMESPACES`]
+/// according to the [`ProtectedAccess`] of the server.
+#[derive(Debug, Clone)]
+pub struct RpcAccessGate<S> {
+    /// The inner service.
+    inner: S,
+    /// The access to the protected methods.
+    access: ProtectedAccess,
+}
+
+impl<S> RpcAccessGate<S> {
+    /// Wraps the given service, handling its calls to protected methods with the given access.
+    pub const fn new(inner: S, access: ProtectedAccess) -> Self {
+        Self { inner, access }
+    }
+
+    /// Returns the error to answer the given request with, if it is not allowed.
+    fn check(&self, request: &Request<'_>) -> Option<ErrorObject<'static>> {
+        if !is_protected_method(request.method_name()) {
+            return None;
+        }
+        match self.access {
+            ProtectedAccess::Open => None,
+            ProtectedAccess::Authenticated
+                if request.extensions().get::<Authenticated>().is_some() =>
+            {
+                None
+            }
+            ProtectedAccess::Authenticated => {
+                Some(ErrorObject::owned(UNAUTHORIZED_ERROR_CODE, "Unauthorized", None::<()>))
+            }
+            ProtectedAccess::Denied => Some(ErrorObject::from(ErrorCode::MethodNotFound)),
+        }
+    }
+}
+
+impl<S> RpcServiceT for RpcAccessGate<S>
+where
+    S: RpcServiceT<MethodResponse = MethodResponse> + Send + Sync + Clone + 'static,
+{
+    type MethodResponse = S::MethodResponse;
+    type NotificationResponse = S::NotificationResponse;
+    type BatchResponse = S::BatchResponse;
+
+    fn call<'a>(
+        &self,
+        request: Request<'a>,
+    ) -> impl Future<Output = Self::MethodResponse> + Send + 'a {
+        let error = self.check(&request);
+        let inner = self.inner.clone();
+
+        async move {
+            if let Some(error) = error {
+                return MethodResponse::error(request.id(), error);
+            }
+            inner.call(request).await
+        }
+    }
+
+    fn batch<'a>(
+        &self,
+        mut requests: Batch<'a>,
+    ) -> impl Future<Output = Self::BatchResponse> + Send + 'a {
+        // As in the `RpcNamespaceGate`, the calls of a batch that are not allowed are renamed to a
+        // method that is never served, which the server rejects as not found.
+        for entry in requests.iter_mut() {
+            let Ok(BatchEntry::Call(request)) = entry else {
+                continue;
+            };
+            if self.check(request).is_some() {
+                request.method = Cow::Borrowed("");
+            }
+        }
+        self.inner.batch(requests)
+    }
+
+    fn notification<'a>(
+        &self,
+        notification: Notification<'a>,
+    ) -> impl Future<Output = Self::NotificationResponse> + Send + 'a {
+        self.inner.notification(notification)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use jsonrpsee::types::Id;
+
+    fn request(method: &'static str, authenticated: bool) -> Request<'static> {
+        let mut request = Request::new(method.into(), None, Id::Number(1));
+        if authenticated {
+            request.extensions_mut().insert(Authenticated);
+        }
+        request
+    }
+
+    #[test]
+    fn test_protected_methods() {
+        assert!(is_protected_method("admin_startSequencer"));
+        assert!(is_protected_method("opp2p_blockPeer"));
+        assert!(is_protected_method("debug_derivationPipeline"));
+        assert!(is_protected_method("interop_reset"));
+        assert!(is_protected_method("interop_resetPreInterop"));
+        assert!(!is_protected_method("optimism_syncStatus"));
+        assert!(!is_protected_method("healthz"));
+    }
+
+    #[test]
+    fn test_access_gate() {
+        let open = RpcAccessGate::new((), ProtectedAccess::Open);
+        assert!(open.check(&request("admin_startSequencer", false)).is_none());
+
+        let authenticated = RpcAccessGate::new((), ProtectedAccess::Authenticated);
+        assert!(authenticated.check(&request("optimism_syncStatus", false)).is_none());
+        assert!(authenticated.check(&request("admin_startSequencer", true)).is_none());
+        let error = authenticated.check(&request("admin_startSequencer", false)).unwrap();
+        assert_eq!(error.code(), UNAUTHORIZED_ERROR_CODE);
+
+        let denied = RpcAccessGate::new((), ProtectedAccess::Denied);
+        assert!(denied.check(&request("optimism_syncStatus", false)).is_none());
+        let error = denied.check(&request("admin_startSequencer", true)).unwrap();
+        assert_eq!(error.code(), ErrorCode::MethodNotFound.code());
+    }
+
+    #[test]
+    fn test_bearer_token() {
+        let auth = BearerAuthLayer::new(Some("secret")).layer(());
+        assert!(auth.is_authorized(b"Bearer secret"));
+        assert!(!auth.is_authorized(b"Bearer secrets"));
+        assert!(!auth.is_authorized(b"Basic secret"));
+        assert!(!auth.is_authorized(b"secret"));
+
+        let auth = BearerAuthLayer::new(None).layer(());
+        assert!(!auth.is_authorized(b"Bearer "));
+    }
+}
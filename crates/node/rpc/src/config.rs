@@ -1,6 +1,8 @@
 //! Contains the RPC Configuration.
 
-use std::{net::SocketAddr, path::PathBuf};
+use std::{net::SocketAddr, path::PathBuf, sync::Arc};
+
+use crate::{LogFilterReloader, ProtectedAccess, ReadinessCriteria};
 
 /// The RPC configuration.
 #[derive(Debug, Clone)]
@@ -9,6 +11,12 @@ pub struct RpcBuilder {
     pub no_restart: bool,
     /// The RPC socket address.
     pub socket: SocketAddr,
+    /// The socket address of a separate server for the methods of the protected namespaces. If
+    /// set, these methods are no longer served on the main [`socket`](Self::socket).
+    pub admin_socket: Option<SocketAddr>,
+    /// The bearer token required to call the methods of the protected namespaces. If unset, the
+    /// methods are served to every caller of the server they are exposed on.
+    pub admin_token: Option<String>,
     /// Enable the admin API.
     pub enable_admin: bool,
     /// File path used to persist state changes made via the admin API so they persist across
@@ -18,6 +26,13 @@ pub struct RpcBuilder {
     pub ws_enabled: bool,
     /// Enable development RPC endpoints
     pub dev_enabled: bool,
+    /// The node version, served by `optimism_version` and the healthcheck.
+    pub version: String,
+    /// The criteria that the node must meet to pass the `readyz` readiness check.
+    pub readiness: ReadinessCriteria,
+    /// The reloader of the log filter, used by the admin API to change the log filter at
+    /// runtime.
+    pub log_filter: Option<Arc<dyn LogFilterReloader>>,
 }
 
 impl RpcBuilder {
@@ -36,6 +51,20 @@ impl RpcBuilder {
         self.socket
     }
 
+    /// Returns the access to the protected methods on the main socket.
+    pub const fn public_access(&self) -> ProtectedAccess {
+        if self.admin_socket.is_some() { ProtectedAccess::Denied } else { self.admin_access() }
+    }
+
+    /// Returns the access to the protected methods on the server exposing them.
+    pub const fn admin_access(&self) -> ProtectedAccess {
+        if self.admin_token.is_some() {
+            ProtectedAccess::Authenticated
+        } else {
+            ProtectedAccess::Open
+        }
+    }
+
     /// Returns the number of times the RPC server will attempt to restart if it stops.
     pub const fn restart_count(&self) -> u32 {
         if self.no_restart { 0 } else { 3 }
@@ -0,0 +1,96 @@
+//! Debug RPC API for dumping the internal state of the derivation pipeline.
+
+use async_trait::async_trait;
+use jsonrpsee::{
+    core::RpcResult,
+    types::{ErrorCode, ErrorObjectOwned},
+};
+use kona_derive::PipelineSnapshot;
+use tokio::sync::{mpsc, oneshot};
+
+use crate::DebugApiServer;
+
+/// A sender for derivation queries.
+pub type DerivationQuerySender = mpsc::Sender<DerivationQueries>;
+
+/// The inbound queries to the derivation actor.
+#[derive(Debug)]
+pub enum DerivationQueries {
+    /// Get a snapshot of the internal state of the derivation pipeline.
+    PipelineSnapshot(oneshot::Sender<PipelineSnapshot>),
+}
+
+/// Implementation of the debug RPC API.
+#[derive(Debug)]
+pub struct DebugRpc {
+    /// The derivation query sender.
+    derivation_query_sender: DerivationQuerySender,
+}
+
+impl DebugRpc {
+    /// Creates a new [`DebugRpc`] instance.
+    pub const fn new(derivation_query_sender: DerivationQuerySender) -> Self {
+        Self { derivation_query_sender }
+    }
+}
+
+#[async_trait]
+impl DebugApiServer for DebugRpc {
+    async fn debug_derivation_state(&self) -> RpcResult<PipelineSnapshot> {
+        let (query_tx, query_rx) = oneshot::channel();
+
+        self.derivation_query_sender
+            .send(DerivationQueries::PipelineSnapshot(query_tx))
+            .await
+            .map_err(|_| {
+                ErrorObjectOwned::owned(
+                    ErrorCode::InternalError.code(),
+                    "Derivation query channel closed",
+                    None::<()>,
+                )
+            })?;
+
+        query_rx.await.map_err(|_| {
+            ErrorObjectOwned::owned(
+                ErrorCode::InternalError.code(),
+                "Failed to receive derivation pipeline snapshot",
+                None::<()>,
+            )
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kona_protocol::BlockInfo;
+
+    #[tokio::test]
+    async fn test_debug_derivation_state() {
+        let (tx, mut rx) = mpsc::channel(1);
+        let rpc = DebugRpc::new(tx);
+
+        let snapshot = PipelineSnapshot {
+            origin: Some(BlockInfo { number: 10, ..Default::default() }),
+            frame_queue: 3,
+            ..Default::default()
+        };
+        let expected = snapshot.clone();
+        tokio::spawn(async move {
+            let Some(DerivationQueries::PipelineSnapshot(sender)) = rx.recv().await else {
+                panic!("Expected a pipeline snapshot query");
+            };
+            sender.send(snapshot).unwrap();
+        });
+
+        assert_eq!(rpc.debug_derivation_state().await.unwrap(), expected);
+    }
+
+    #[tokio::test]
+    async fn test_debug_derivation_state_closed() {
+        let (tx, rx) = mpsc::channel(1);
+        drop(rx);
+        let rpc = DebugRpc::new(tx);
+        assert!(rpc.debug_derivation_state().await.is_err());
+    }
+}
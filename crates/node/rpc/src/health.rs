@@ -0,0 +1,197 @@
+//! Contains the [`ReadinessCheck`], which serves the `readyz` readiness check of the node.
+
+use jsonrpsee::{
+    core::RpcResult,
+    types::{ErrorCode, ErrorObject},
+};
+use kona_engine::{EngineQueries, EngineQuerySender};
+use kona_gossip::P2pRpcRequest;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::{L1WatcherQueries, L1WatcherQuerySender};
+
+/// The default maximum age of the L1 head for the node to be ready.
+pub const DEFAULT_READY_MAX_L1_HEAD_AGE: Duration = Duration::from_secs(2 * 60);
+
+/// The default maximum age of the unsafe head for the node to be ready.
+pub const DEFAULT_READY_MAX_UNSAFE_HEAD_AGE: Duration = Duration::from_secs(60);
+
+/// The criteria that the node must meet to be ready.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReadinessCriteria {
+    /// The minimum number of connected peers.
+    pub min_peers: usize,
+    /// Whether the execution layer must be reachable.
+    pub require_engine: bool,
+    /// The maximum age of the L1 head, if bounded. The L1 head is only advanced while the L1
+    /// provider is live.
+    pub max_l1_head_age: Option<Duration>,
+    /// The maximum age of the unsafe head, if bounded.
+    pub max_unsafe_head_age: Option<Duration>,
+}
+
+impl Default for ReadinessCriteria {
+    fn default() -> Self {
+        Self {
+            min_peers: 0,
+            require_engine: true,
+            max_l1_head_age: Some(DEFAULT_READY_MAX_L1_HEAD_AGE),
+            max_unsafe_head_age: Some(DEFAULT_READY_MAX_UNSAFE_HEAD_AGE),
+        }
+    }
+}
+
+impl ReadinessCriteria {
+    /// Returns a description of each criterion that the given status does not meet.
+    pub fn unmet(&self, status: &ReadyzResponse) -> Vec<String> {
+        let mut unmet = Vec::new();
+        if status.peers < self.min_peers {
+            unmet.push(format!("{} connected peers, need {}", status.peers, self.min_peers));
+        }
+        if self.require_engine && !status.engine_reachable {
+            unmet.push("execution layer unreachable".to_string());
+        }
+        if let Some(max_age) = self.max_l1_head_age {
+            match status.l1_head_age {
+                Some(age) if age > max_age.as_secs() => {
+                    unmet.push(format!("L1 head is {age}s old, max {}s", max_age.as_secs()))
+                }
+                Some(_) => {}
+                None => unmet.push("no L1 head".to_string()),
+            }
+        }
+        if let Some(max_age) = self.max_unsafe_head_age {
+            if status.unsafe_head_age > max_age.as_secs() {
+                unmet.push(format!(
+                    "unsafe head is {}s old, max {}s",
+                    status.unsafe_head_age,
+                    max_age.as_secs()
+                ));
+            }
+        }
+        unmet
+    }
+}
+
+/// The status of the node, as measured by the readiness check.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReadyzResponse {
+    /// The number of connected peers.
+    pub peers: usize,
+    /// Whether the execution layer is reachable.
+    pub engine_reachable: bool,
+    /// The age of the L1 head, in seconds, if there is one.
+    pub l1_head_age: Option<u64>,
+    /// The age of the unsafe head, in seconds.
+    pub unsafe_head_age: u64,
+}
+
+/// Serves the `readyz` readiness check, which succeeds if the node meets the
+/// [`ReadinessCriteria`], and fails with a description of the unmet criteria otherwise.
+#[derive(Debug, Clone)]
+pub struct ReadinessCheck {
+    /// The [`ReadinessCriteria`] of the node.
+    pub criteria: ReadinessCriteria,
+    /// The channel to send [`P2pRpcRequest`]s.
+    pub p2p_sender: tokio::sync::mpsc::Sender<P2pRpcRequest>,
+    /// The channel to send [`EngineQueries`].
+    pub engine_sender: EngineQuerySender,
+    /// The channel to send [`L1WatcherQueries`].
+    pub l1_watcher_sender: L1WatcherQuerySender,
+}
+
+impl ReadinessCheck {
+    /// The timeout of each query made by the readiness check.
+    const QUERY_TIMEOUT: Duration = Duration::from_secs(1);
+
+    /// Measures the status of the node and checks it against the [`ReadinessCriteria`].
+    pub async fn readyz(&self) -> RpcResult<ReadyzResponse> {
+        kona_macros::inc!(gauge, crate::RollupRpc::RPC_IDENT, "method" => "readyz");
+
+        let (peers, engine_reachable, l1_state, engine_state) = tokio::join!(
+            self.query(|tx| self.p2p_sender.try_send(P2pRpcRequest::PeerCount(tx)).is_ok()),
+            self.query(|tx| self
+                .engine_sender
+                .try_send(EngineQueries::EngineReachable(tx))
+                .is_ok()),
+            self.query(|tx| self.l1_watcher_sender.try_send(L1WatcherQueries::L1State(tx)).is_ok()),
+            self.query(|tx| self.engine_sender.try_send(EngineQueries::State(tx)).is_ok()),
+        );
+
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+        let unsafe_head =
+            engine_state.map(|state| state.sync_state.unsafe_head()).unwrap_or_default();
+        let status = ReadyzResponse {
+            peers: peers.map_or(0, |(_, gossip_peers)| gossip_peers),
+            engine_reachable: engine_reachable.unwrap_or_default(),
+            l1_head_age: l1_state
+                .and_then(|state| state.head_l1)
+                .map(|head| now.saturating_sub(head.timestamp)),
+            unsafe_head_age: now.saturating_sub(unsafe_head.block_info.timestamp),
+        };
+
+        let unmet = self.criteria.unmet(&status);
+        if unmet.is_empty() {
+            return Ok(status);
+        }
+        debug!(target: "rpc", ?unmet, "Node is not ready");
+        Err(ErrorObject::owned(
+            ErrorCode::InternalError.code(),
+            format!("not ready: {}", unmet.join(", ")),
+            Some(status),
+        ))
+    }
+
+    /// Sends a query with the given function and awaits its response, if the query could be sent.
+    async fn query<T>(
+        &self,
+        send: impl FnOnce(tokio::sync::oneshot::Sender<T>) -> bool,
+    ) -> Option<T> {
+        let (tx, rx) = tokio::sync::oneshot::channel();
+        if !send(tx) {
+            return None;
+        }
+        tokio::time::timeout(Self::QUERY_TIMEOUT, rx).await.ok()?.ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ready_status() -> ReadyzResponse {
+        ReadyzResponse {
+            peers: 3,
+            engine_reachable: true,
+            l1_head_age: Some(12),
+            unsafe_head_age: 2,
+        }
+    }
+
+    #[test]
+    fn test_readiness_criteria_met() {
+        let criteria = ReadinessCriteria { min_peers: 3, ..Default::default() };
+        assert!(criteria.unmet(&ready_status()).is_empty());
+    }
+
+    #[test]
+    fn test_readiness_criteria_unmet() {
+        let criteria = ReadinessCriteria { min_peers: 4, ..Default::default() };
+        let status = ReadyzResponse {
+            engine_reachable: false,
+            l1_head_age: None,
+            unsafe_head_age: 600,
+            ..ready_status()
+        };
+        assert_eq!(criteria.unmet(&status).len(), 4);
+
+        let criteria = ReadinessCriteria {
+            min_peers: 0,
+            require_engine: false,
+            max_l1_head_age: None,
+            max_unsafe_head_age: None,
+        };
+        assert!(criteria.unmet(&status).is_empty());
+    }
+}
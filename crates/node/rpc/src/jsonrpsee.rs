@@ -2,7 +2,8 @@
 
 use crate::{OutputResponse, SafeHeadResponse};
 use alloy_eips::BlockNumberOrTag;
-use alloy_primitives::B256;
+use alloy_primitives::{B256, Bytes};
+use alloy_rpc_types_eth::erc4337::TransactionConditional;
 use core::net::IpAddr;
 use ipnet::IpNet;
 use jsonrpsee::{
@@ -32,6 +33,14 @@ pub trait RollupNodeApi {
     async fn op_output_at_block(&self, block_number: BlockNumberOrTag)
     -> RpcResult<OutputResponse>;
 
+    /// Get the output roots of an inclusive range of blocks, in ascending order.
+    #[method(name = "outputsAtBlockRange")]
+    async fn op_outputs_at_block_range(
+        &self,
+        start: u64,
+        end: u64,
+    ) -> RpcResult<Vec<OutputResponse>>;
+
     /// Gets the safe head at an L1 block height.
     #[method(name = "safeHeadAtL1Block")]
     async fn op_safe_head_at_l1_block(
@@ -89,7 +98,7 @@ pub trait OpP2PApi {
     async fn opp2p_list_blocked_peers(&self) -> RpcResult<Vec<String>>;
 
     /// Blocks the given address
-    #[method(name = "blocAddr")]
+    #[method(name = "blockAddr", aliases = ["opp2p_blocAddr"])]
     async fn opp2p_block_addr(&self, ip: IpAddr) -> RpcResult<()>;
 
     /// Unblocks the given address
@@ -147,6 +156,20 @@ pub trait Ws {
     async fn ws_unsafe_head_updates(&self) -> SubscriptionResult;
 }
 
+/// The rollup subscription API of the node.
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "rollup"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "rollup"))]
+#[async_trait]
+pub trait RollupSubscriptionApi {
+    /// Subscribes to the given kind of node events.
+    #[subscription(
+        name = "subscribe" => "subscription",
+        unsubscribe = "unsubscribe",
+        item = crate::RollupSubscriptionItem
+    )]
+    async fn rollup_subscribe(&self, kind: crate::RollupSubscriptionKind) -> SubscriptionResult;
+}
+
 /// Development RPC API for engine state introspection.
 #[cfg_attr(not(feature = "client"), rpc(server, namespace = "dev"))]
 #[cfg_attr(feature = "client", rpc(server, client, namespace = "dev"))]
@@ -161,6 +184,16 @@ pub trait DevEngineApi {
     async fn dev_task_queue_length(&self) -> RpcResult<usize>;
 }
 
+/// Debug RPC API for derivation pipeline introspection.
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "debug"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "debug"))]
+#[async_trait]
+pub trait DebugApi {
+    /// Get a snapshot of the internal state of the derivation pipeline.
+    #[method(name = "derivationState")]
+    async fn debug_derivation_state(&self) -> RpcResult<kona_derive::PipelineSnapshot>;
+}
+
 /// The admin namespace for the consensus node.
 #[cfg_attr(not(feature = "client"), rpc(server, namespace = "admin"))]
 #[cfg_attr(feature = "client", rpc(server, client, namespace = "admin"))]
@@ -193,4 +226,32 @@ pub trait AdminApi {
     /// Overrides the leader in the conductor.
     #[method(name = "overrideLeader")]
     async fn admin_override_leader(&self) -> RpcResult<()>;
+
+    /// Submits a raw transaction to be included by the sequencer, as long as the given inclusion
+    /// conditions hold for the block it is included in. Returns the transaction hash once the
+    /// execution layer accepted the transaction into its pool, or the error it was rejected with.
+    #[method(name = "sendRawTransactionConditional")]
+    async fn admin_send_raw_transaction_conditional(
+        &self,
+        bytes: Bytes,
+        conditional: TransactionConditional,
+    ) -> RpcResult<B256>;
+
+    /// Replaces the log filter of the node with the given directives, using the `RUST_LOG`
+    /// syntax (e.g. `info,engine=debug`).
+    #[method(name = "setLogFilter")]
+    async fn admin_set_log_filter(&self, directives: String) -> RpcResult<()>;
+
+    /// Sets the maximum number of connected peers above which no new discovered peers are
+    /// dialed. Static peers are always dialed. Passing no limit removes it.
+    #[method(name = "setMaxPeers")]
+    async fn admin_set_max_peers(&self, max_peers: Option<usize>) -> RpcResult<()>;
+
+    /// Enables or disables the given RPC namespace. The `admin` namespace cannot be disabled.
+    #[method(name = "setRpcNamespace")]
+    async fn admin_set_rpc_namespace(&self, namespace: String, enabled: bool) -> RpcResult<()>;
+
+    /// Returns the RPC namespaces disabled at runtime.
+    #[method(name = "disabledRpcNamespaces")]
+    async fn admin_disabled_rpc_namespaces(&self) -> RpcResult<Vec<String>>;
 }
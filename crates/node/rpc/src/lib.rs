@@ -10,7 +10,17 @@
 extern crate tracing;
 
 mod admin;
-pub use admin::{AdminRpc, NetworkAdminQuery, SequencerAdminQuery};
+pub use admin::{
+    AdminRpc, ConditionalTransaction, ConditionalTransactionError, LogFilterReloader,
+    NetworkAdminQuery, SequencerAdminError, SequencerAdminQuery,
+    TRANSACTION_CONDITIONAL_REJECTED_CODE,
+};
+
+mod auth;
+pub use auth::{
+    Authenticated, BearerAuth, BearerAuthLayer, PROTECTED_NAMESPACES, ProtectedAccess,
+    RpcAccessGate, UNAUTHORIZED_ERROR_CODE, is_protected_method,
+};
 
 mod config;
 pub use config::RpcBuilder;
@@ -26,13 +36,22 @@ pub use response::SafeHeadResponse;
 mod output;
 pub use output::OutputResponse;
 
+mod health;
+pub use health::{
+    DEFAULT_READY_MAX_L1_HEAD_AGE, DEFAULT_READY_MAX_UNSAFE_HEAD_AGE, ReadinessCheck,
+    ReadinessCriteria, ReadyzResponse,
+};
+
 mod dev;
 pub use dev::DevEngineRpc;
 
+mod debug;
+pub use debug::{DebugRpc, DerivationQueries, DerivationQuerySender};
+
 mod jsonrpsee;
 pub use jsonrpsee::{
-    AdminApiServer, DevEngineApiServer, MinerApiExtServer, OpAdminApiServer, OpP2PApiServer,
-    RollupNodeApiServer, WsServer,
+    AdminApiServer, DebugApiServer, DevEngineApiServer, MinerApiExtServer, OpAdminApiServer,
+    OpP2PApiServer, RollupNodeApiServer, RollupSubscriptionApiServer, WsServer,
 };
 
 mod rollup;
@@ -42,7 +61,13 @@ mod l1_watcher;
 pub use l1_watcher::{L1State, L1WatcherQueries, L1WatcherQuerySender};
 
 mod ws;
-pub use ws::WsRPC;
+pub use ws::{RollupSubscriptionItem, RollupSubscriptionKind, WsRPC};
+
+mod metrics;
+pub use metrics::{Metrics, RpcMetricsService};
+
+mod namespaces;
+pub use namespaces::{ADMIN_NAMESPACE, RpcNamespaceError, RpcNamespaceGate, RpcNamespaces};
 
 /// A healthcheck response for the RPC server.
 #[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
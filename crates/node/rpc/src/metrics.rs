@@ -0,0 +1,104 @@
+//! Metrics for the RPC server.
+
+use jsonrpsee::{
+    core::middleware::{Batch, Notification, RpcServiceT},
+    types::Request,
+};
+use std::{collections::HashSet, future::Future, sync::Arc, time::Instant};
+
+/// Container for metrics.
+#[derive(Debug, Clone)]
+pub struct Metrics;
+
+impl Metrics {
+    /// Identifier for the histogram of RPC request durations. Labels: `method`.
+    pub const RPC_REQUEST_DURATION: &str = "kona_node_rpc_request_duration_seconds";
+
+    /// Label for requests to methods that are not served by the RPC server.
+    pub const UNKNOWN_METHOD_LABEL: &str = "unknown";
+
+    /// Initializes metrics for the RPC server.
+    #[cfg(feature = "metrics")]
+    pub fn init() {
+        Self::describe();
+    }
+
+    /// Describes metrics used in [`kona_rpc`][crate].
+    #[cfg(feature = "metrics")]
+    pub fn describe() {
+        metrics::describe_histogram!(
+            Self::RPC_REQUEST_DURATION,
+            metrics::Unit::Seconds,
+            "Duration of RPC requests served by the node"
+        );
+    }
+}
+
+/// An RPC middleware that records the duration of every method call in the
+/// [`Metrics::RPC_REQUEST_DURATION`] histogram.
+///
+/// Calls are labeled with their method name. To keep the label cardinality bounded, calls to
+/// methods that are not in the served set are labeled [`Metrics::UNKNOWN_METHOD_LABEL`]. Batch
+/// requests are forwarded as-is and not recorded.
+#[derive(Debug, Clone)]
+pub struct RpcMetricsService<S> {
+    /// The inner service.
+    inner: S,
+    /// The names of the methods served by the RPC server.
+    methods: Arc<HashSet<&'static str>>,
+}
+
+impl<S> RpcMetricsService<S> {
+    /// Wraps the given service, labeling the calls to any of the given `methods`.
+    pub const fn new(inner: S, methods: Arc<HashSet<&'static str>>) -> Self {
+        Self { inner, methods }
+    }
+}
+
+impl<S> RpcServiceT for RpcMetricsService<S>
+where
+    S: RpcServiceT + Send + Sync + Clone + 'static,
+{
+    type MethodResponse = S::MethodResponse;
+    type NotificationResponse = S::NotificationResponse;
+    type BatchResponse = S::BatchResponse;
+
+    fn call<'a>(
+        &self,
+        request: Request<'a>,
+    ) -> impl Future<Output = Self::MethodResponse> + Send + 'a {
+        let method = self
+            .methods
+            .get(request.method_name())
+            .copied()
+            .unwrap_or(Metrics::UNKNOWN_METHOD_LABEL);
+        let inner = self.inner.clone();
+
+        async move {
+            let start = Instant::now();
+            let response = inner.call(request).await;
+            kona_macros::record!(
+                histogram,
+                Metrics::RPC_REQUEST_DURATION,
+                "method",
+                method,
+                start.elapsed().as_secs_f64()
+            );
+            response
+        }
+    }
+
+    fn batch<'a>(
+        &self,
+        requests: Batch<'a>,
+    ) -> impl Future<Output = Self::BatchResponse> + Send + 'a {
+        self.inner.batch(requests)
+    }
+
+    fn notification<'a>(
+        &self,
+        notification: Notification<'a>,
+    ) -> impl Future<Output = Self::NotificationResponse> + Send + 'a {
+        self.inner.notification(notification)
+    }
+}
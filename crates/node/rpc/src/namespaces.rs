@@ -0,0 +1,165 @@
+//! Runtime toggles for the RPC namespaces.
+
+use jsonrpsee::{
+    MethodResponse,
+    core::middleware::{Batch, BatchEntry, Notification, RpcServiceT},
+    types::{ErrorCode, ErrorObject, Request},
+};
+use std::{
+    borrow::Cow,
+    collections::HashSet,
+    future::Future,
+    sync::{Arc, RwLock},
+};
+
+/// The namespace of the admin API, which cannot be disabled since it is used to re-enable the
+/// other namespaces.
+pub const ADMIN_NAMESPACE: &str = "admin";
+
+/// An error returned when toggling an RPC namespace.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum RpcNamespaceError {
+    /// The admin namespace cannot be disabled.
+    #[error("The admin namespace cannot be disabled")]
+    AdminNamespace,
+}
+
+/// The set of RPC namespaces that are disabled at runtime.
+///
+/// The set is shared between the [`RpcNamespaceGate`] middleware, which rejects the calls to the
+/// methods of disabled namespaces, and the admin API, which toggles the namespaces. Methods are
+/// matched on the prefix of their name before the first `_`, so methods without a namespace (like
+/// `healthz`) are always served.
+#[derive(Debug, Clone, Default)]
+pub struct RpcNamespaces {
+    /// The disabled namespaces.
+    disabled: Arc<RwLock<HashSet<String>>>,
+}
+
+impl RpcNamespaces {
+    /// Enables or disables the given namespace.
+    pub fn set_enabled(&self, namespace: &str, enabled: bool) -> Result<(), RpcNamespaceError> {
+        if namespace == ADMIN_NAMESPACE {
+            return Err(RpcNamespaceError::AdminNamespace);
+        }
+
+        let mut disabled = self.disabled.write().unwrap_or_else(|e| e.into_inner());
+        if enabled {
+            disabled.remove(namespace);
+        } else {
+            disabled.insert(namespace.to_string());
+        }
+        Ok(())
+    }
+
+    /// Returns the disabled namespaces, sorted by name.
+    pub fn disabled(&self) -> Vec<String> {
+        let disabled = self.disabled.read().unwrap_or_else(|e| e.into_inner());
+        let mut namespaces = disabled.iter().cloned().collect::<Vec<_>>();
+        namespaces.sort();
+        namespaces
+    }
+
+    /// Returns whether the method with the given name is served.
+    pub fn is_method_enabled(&self, method: &str) -> bool {
+        let Some((namespace, _)) = method.split_once('_') else {
+            return true;
+        };
+        !self.disabled.read().unwrap_or_else(|e| e.into_inner()).contains(namespace)
+    }
+}
+
+/// An RPC middleware that rejects the calls to the methods of the namespaces disabled in
+/// [`RpcNamespaces`] with a method not found error.
+#[derive(Debug, Clone)]
+pub struct RpcNamespaceGate<S> {
+    /// The inner service.
+    inner: S,
+    /// The namespaces toggled at runtime.
+    namespaces: RpcNamespaces,
+}
+
+impl<S> RpcNamespaceGate<S> {
+    /// Wraps the given service, gating its calls on the given [`RpcNamespaces`].
+    pub const fn new(inner: S, namespaces: RpcNamespaces) -> Self {
+        Self { inner, namespaces }
+    }
+}
+
+impl<S> RpcServiceT for RpcNamespaceGate<S>
+where
+    S: RpcServiceT<MethodResponse = MethodResponse> + Send + Sync + Clone + 'static,
+{
+    type MethodResponse = S::MethodResponse;
+    type NotificationResponse = S::NotificationResponse;
+    type BatchResponse = S::BatchResponse;
+
+    fn call<'a>(
+        &self,
+        request: Request<'a>,
+    ) -> impl Future<Output = Self::MethodResponse> + Send + 'a {
+        let enabled = self.namespaces.is_method_enabled(request.method_name());
+        let inner = self.inner.clone();
+
+        async move {
+            if !enabled {
+                return MethodResponse::error(
+                    request.id(),
+                    ErrorObject::from(ErrorCode::MethodNotFound),
+                );
+            }
+            inner.call(request).await
+        }
+    }
+
+    fn batch<'a>(
+        &self,
+        mut requests: Batch<'a>,
+    ) -> impl Future<Output = Self::BatchResponse> + Send + 'a {
+        // The calls of a batch are not routed through `call`, so the calls to disabled methods
+        // are renamed to a method that is never served, which the server rejects as not found.
+        for entry in requests.iter_mut() {
+            if let Ok(BatchEntry::Call(request)) = entry {
+                if !self.namespaces.is_method_enabled(request.method_name()) {
+                    request.method = Cow::Borrowed("");
+                }
+            }
+        }
+        self.inner.batch(requests)
+    }
+
+    fn notification<'a>(
+        &self,
+        notification: Notification<'a>,
+    ) -> impl Future<Output = Self::NotificationResponse> + Send + 'a {
+        self.inner.notification(notification)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_namespace_toggle() {
+        let namespaces = RpcNamespaces::default();
+        assert!(namespaces.is_method_enabled("optimism_syncStatus"));
+
+        namespaces.set_enabled("optimism", false).unwrap();
+        assert!(!namespaces.is_method_enabled("optimism_syncStatus"));
+        assert!(namespaces.is_method_enabled("opp2p_peers"));
+        assert!(namespaces.is_method_enabled("healthz"));
+        assert_eq!(namespaces.disabled(), vec!["optimism".to_string()]);
+
+        namespaces.set_enabled("optimism", true).unwrap();
+        assert!(namespaces.is_method_enabled("optimism_syncStatus"));
+        assert!(namespaces.disabled().is_empty());
+    }
+
+    #[test]
+    fn test_admin_namespace_cannot_be_disabled() {
+        let namespaces = RpcNamespaces::default();
+        assert_eq!(namespaces.set_enabled("admin", false), Err(RpcNamespaceError::AdminNamespace));
+        assert!(namespaces.is_method_enabled("admin_setRpcNamespace"));
+    }
+}
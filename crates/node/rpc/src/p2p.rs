@@ -58,6 +58,7 @@ impl OpP2PApiServer for P2pRpc {
     }
 
     async fn opp2p_peer_stats(&self) -> RpcResult<PeerStats> {
+        kona_macros::inc!(gauge, kona_gossip::Metrics::RPC_CALLS, "method" => "opp2p_peerStats");
         let (tx, rx) = tokio::sync::oneshot::channel();
         self.sender
             .send(P2pRpcRequest::PeerStats(tx))
@@ -192,10 +193,9 @@ impl OpP2PApiServer for P2pRpc {
             .map_err(|_| ErrorObject::from(ErrorCode::InternalError))
     }
 
-    async fn opp2p_connect_peer(&self, _peer: String) -> RpcResult<()> {
-        use std::str::FromStr;
+    async fn opp2p_connect_peer(&self, peer: String) -> RpcResult<()> {
         kona_macros::inc!(gauge, kona_gossip::Metrics::RPC_CALLS, "method" => "opp2p_connectPeer");
-        let ma = libp2p::Multiaddr::from_str(&_peer).map_err(|_| {
+        let ma = libp2p::Multiaddr::from_str(&peer).map_err(|_| {
             ErrorObject::borrowed(ErrorCode::InvalidParams.code(), "Invalid multiaddr", None)
         })?;
 
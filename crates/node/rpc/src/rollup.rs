@@ -8,9 +8,9 @@ use jsonrpsee::{
     core::RpcResult,
     types::{ErrorCode, ErrorObject},
 };
-use kona_engine::{EngineQueries, EngineQuerySender, EngineState};
+use kona_engine::{EngineQueries, EngineQueriesError, EngineQuerySender, EngineState};
 use kona_genesis::RollupConfig;
-use kona_protocol::SyncStatus;
+use kona_protocol::{L2BlockInfo, SyncStatus};
 
 use crate::{
     L1State, L1WatcherQueries, OutputResponse, RollupNodeApiServer, SafeHeadResponse,
@@ -26,25 +26,53 @@ pub struct RollupRpc {
     pub engine_sender: EngineQuerySender,
     /// The channel to send [`crate::L1WatcherQueries`]s.
     pub l1_watcher_sender: L1WatcherQuerySender,
+    /// The node version, served by `optimism_version`.
+    pub version: String,
 }
 
 impl RollupRpc {
     /// The identifier for the Metric that tracks rollup RPC calls.
     pub const RPC_IDENT: &'static str = "rollup_rpc";
 
-    /// Constructs a new [`RollupRpc`] given a sender channel.
+    /// Constructs a new [`RollupRpc`] given a sender channel and the node version.
     pub const fn new(
         engine_sender: EngineQuerySender,
         l1_watcher_sender: L1WatcherQuerySender,
+        version: String,
     ) -> Self {
-        Self { engine_sender, l1_watcher_sender }
+        Self { engine_sender, l1_watcher_sender, version }
+    }
+
+    /// Queries the engine for the next queued unsafe payload, if any.
+    async fn queued_unsafe_head(&self) -> RpcResult<Option<L2BlockInfo>> {
+        let (queued_send, queued_recv) = tokio::sync::oneshot::channel();
+        self.engine_sender
+            .send(EngineQueries::QueuedUnsafeHead(queued_send))
+            .await
+            .map_err(|_| ErrorObject::from(ErrorCode::InternalError))?;
+        queued_recv.await.map_err(|_| ErrorObject::from(ErrorCode::InternalError))
+    }
+
+    /// Maps an error computing output roots to an RPC error. Requests for missing blocks or for
+    /// invalid ranges are invalid parameters.
+    fn output_error(err: EngineQueriesError) -> ErrorObject<'static> {
+        let code = match err {
+            EngineQueriesError::NoL2BlockFound(_) |
+            EngineQueriesError::InvalidBlockRange { .. } => ErrorCode::InvalidParams,
+            _ => ErrorCode::InternalError,
+        };
+        ErrorObject::owned(code.code(), err.to_string(), None::<()>)
     }
 
     // Important note: we zero-out the fields that can't be derived yet to follow op-node's
     // behaviour.
+    //
+    // The engine consolidates derived attributes block by block, so the pending safe head is the
+    // local safe head.
     fn sync_status_from_actor_queries(
         l1_sync_status: L1State,
         l2_sync_status: EngineState,
+        queued_unsafe_l2: Option<L2BlockInfo>,
     ) -> SyncStatus {
         SyncStatus {
             current_l1: l1_sync_status.current_l1.unwrap_or_default(),
@@ -57,6 +85,8 @@ impl RollupRpc {
             local_safe_l2: l2_sync_status.sync_state.local_safe_head(),
             safe_l2: l2_sync_status.sync_state.safe_head(),
             finalized_l2: l2_sync_status.sync_state.finalized_head(),
+            pending_safe_l2: l2_sync_status.sync_state.local_safe_head(),
+            queued_unsafe_l2: queued_unsafe_l2.unwrap_or_default(),
         }
     }
 }
@@ -69,14 +99,17 @@ impl RollupNodeApiServer for RollupRpc {
         let (output_send, output_recv) = tokio::sync::oneshot::channel();
         let (l1_sync_status_send, l1_sync_status_recv) = tokio::sync::oneshot::channel();
 
-        let ((l2_block_info, output_root, l2_sync_status), l1_sync_status) = tokio::try_join!(
+        let ((l2_block_info, output_root, l2_sync_status), l1_sync_status, queued_unsafe_l2) = tokio::try_join!(
             async {
                 self.engine_sender
                     .send(EngineQueries::OutputAtBlock { block: block_num, sender: output_send })
                     .await
                     .map_err(|_| ErrorObject::from(ErrorCode::InternalError))?;
 
-                output_recv.await.map_err(|_| ErrorObject::from(ErrorCode::InternalError))
+                output_recv
+                    .await
+                    .map_err(|_| ErrorObject::from(ErrorCode::InternalError))?
+                    .map_err(Self::output_error)
             },
             async {
                 self.l1_watcher_sender
@@ -85,22 +118,91 @@ impl RollupNodeApiServer for RollupRpc {
                     .map_err(|_| ErrorObject::from(ErrorCode::InternalError))?;
 
                 l1_sync_status_recv.await.map_err(|_| ErrorObject::from(ErrorCode::InternalError))
-            }
+            },
+            self.queued_unsafe_head()
         )?;
 
-        let sync_status = Self::sync_status_from_actor_queries(l1_sync_status, l2_sync_status);
+        let sync_status =
+            Self::sync_status_from_actor_queries(l1_sync_status, l2_sync_status, queued_unsafe_l2);
 
         Ok(OutputResponse::from_v0(output_root, sync_status, l2_block_info))
     }
 
-    /// This RPC endpoint is not supported. It is not necessary to track the safe head for every L1
-    /// block post-interop anymore so we can remove this method from the rpc interface.
+    async fn op_outputs_at_block_range(
+        &self,
+        start: u64,
+        end: u64,
+    ) -> RpcResult<Vec<OutputResponse>> {
+        kona_macros::inc!(gauge, Self::RPC_IDENT, "method" => "op_outputsAtBlockRange");
+
+        let (outputs_send, outputs_recv) = tokio::sync::oneshot::channel();
+        let (l1_sync_status_send, l1_sync_status_recv) = tokio::sync::oneshot::channel();
+
+        let ((outputs, l2_sync_status), l1_sync_status, queued_unsafe_l2) = tokio::try_join!(
+            async {
+                self.engine_sender
+                    .send(EngineQueries::OutputsAtBlockRange { start, end, sender: outputs_send })
+                    .await
+                    .map_err(|_| ErrorObject::from(ErrorCode::InternalError))?;
+
+                outputs_recv
+                    .await
+                    .map_err(|_| ErrorObject::from(ErrorCode::InternalError))?
+                    .map_err(Self::output_error)
+            },
+            async {
+                self.l1_watcher_sender
+                    .send(L1WatcherQueries::L1State(l1_sync_status_send))
+                    .await
+                    .map_err(|_| ErrorObject::from(ErrorCode::InternalError))?;
+
+                l1_sync_status_recv.await.map_err(|_| ErrorObject::from(ErrorCode::InternalError))
+            },
+            self.queued_unsafe_head()
+        )?;
+
+        let sync_status =
+            Self::sync_status_from_actor_queries(l1_sync_status, l2_sync_status, queued_unsafe_l2);
+
+        Ok(outputs
+            .into_iter()
+            .map(|(l2_block_info, output_root)| {
+                OutputResponse::from_v0(output_root, sync_status.clone(), l2_block_info)
+            })
+            .collect())
+    }
+
     async fn op_safe_head_at_l1_block(
         &self,
-        _block_num: BlockNumberOrTag,
+        block_num: BlockNumberOrTag,
     ) -> RpcResult<SafeHeadResponse> {
         kona_macros::inc!(gauge, Self::RPC_IDENT, "method" => "op_safeHeadAtL1Block");
-        return Err(ErrorObject::from(ErrorCode::MethodNotFound));
+
+        let Some(l1_block_number) = block_num.as_number() else {
+            return Err(ErrorObject::from(ErrorCode::InvalidParams));
+        };
+
+        let (safe_head_send, safe_head_recv) = tokio::sync::oneshot::channel();
+        self.engine_sender
+            .send(EngineQueries::SafeHeadAtL1Block { l1_block_number, sender: safe_head_send })
+            .await
+            .map_err(|_| ErrorObject::from(ErrorCode::InternalError))?;
+
+        match safe_head_recv.await.map_err(|_| ErrorObject::from(ErrorCode::InternalError))? {
+            Ok(Some(entry)) => {
+                Ok(SafeHeadResponse { l1_block: entry.l1_block, safe_head: entry.safe_head })
+            }
+            Ok(None) => Err(ErrorObject::owned(
+                ErrorCode::InvalidParams.code(),
+                "no safe head recorded at or before the L1 block",
+                None::<()>,
+            )),
+            Err(err) => Err(ErrorObject::owned(
+                ErrorCode::InternalError.code(),
+                err.to_string(),
+                None::<()>,
+            )),
+        }
     }
 
     async fn op_sync_status(&self) -> RpcResult<SyncStatus> {
@@ -109,7 +211,7 @@ impl RollupNodeApiServer for RollupRpc {
         let (l1_sync_status_send, l1_sync_status_recv) = tokio::sync::oneshot::channel();
         let (l2_sync_status_send, l2_sync_status_recv) = tokio::sync::oneshot::channel();
 
-        let (l1_sync_status, l2_sync_status) = tokio::try_join!(
+        let (l1_sync_status, l2_sync_status, queued_unsafe_l2) = tokio::try_join!(
             async {
                 self.l1_watcher_sender
                     .send(L1WatcherQueries::L1State(l1_sync_status_send))
@@ -123,11 +225,16 @@ impl RollupNodeApiServer for RollupRpc {
                     .await
                     .map_err(|_| ErrorObject::from(ErrorCode::InternalError))?;
                 l2_sync_status_recv.await.map_err(|_| ErrorObject::from(ErrorCode::InternalError))
-            }
+            },
+            self.queued_unsafe_head()
         )
         .map_err(|_| ErrorObject::from(ErrorCode::InternalError))?;
 
-        return Ok(Self::sync_status_from_actor_queries(l1_sync_status, l2_sync_status));
+        return Ok(Self::sync_status_from_actor_queries(
+            l1_sync_status,
+            l2_sync_status,
+            queued_unsafe_l2,
+        ));
     }
 
     async fn op_rollup_config(&self) -> RpcResult<RollupConfig> {
@@ -145,8 +252,6 @@ impl RollupNodeApiServer for RollupRpc {
     async fn op_version(&self) -> RpcResult<String> {
         kona_macros::inc!(gauge, Self::RPC_IDENT, "method" => "op_version");
 
-        const RPC_VERSION: &str = env!("CARGO_PKG_VERSION");
-
-        return Ok(RPC_VERSION.to_string());
+        Ok(self.version.clone())
     }
 }
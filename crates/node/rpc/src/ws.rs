@@ -3,12 +3,41 @@
 use jsonrpsee::{
     PendingSubscriptionSink, SubscriptionSink, core::SubscriptionResult, tracing::warn,
 };
-use kona_engine::{EngineQueries, EngineQuerySender, EngineState};
+use kona_engine::{DerivationEvent, EngineQueries, EngineQuerySender, EngineState};
 use kona_protocol::L2BlockInfo;
+use serde::{Deserialize, Serialize};
+use tokio::sync::{
+    broadcast::{self, error::RecvError},
+    watch,
+};
 
 use jsonrpsee::core::to_json_raw_value;
 
-use crate::jsonrpsee::WsServer;
+use crate::jsonrpsee::{RollupSubscriptionApiServer, WsServer};
+
+/// The kind of node events to subscribe to with `rollup_subscribe`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RollupSubscriptionKind {
+    /// The unsafe head, sent as an [`L2BlockInfo`] whenever it changes.
+    UnsafeHead,
+    /// The safe head, sent as an [`L2BlockInfo`] whenever it changes.
+    SafeHead,
+    /// The finalized head, sent as an [`L2BlockInfo`] whenever it changes.
+    FinalizedHead,
+    /// The [`DerivationEvent`]s of the node.
+    DerivationEvents,
+}
+
+/// An item sent to a `rollup_subscribe` subscriber.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum RollupSubscriptionItem {
+    /// A head update, for the head subscriptions.
+    Head(L2BlockInfo),
+    /// A derivation event, for the [`RollupSubscriptionKind::DerivationEvents`] subscription.
+    Derivation(DerivationEvent),
+}
 
 /// An RPC server that handles subscriptions to the node's state.
 #[derive(Debug)]
@@ -39,13 +68,50 @@ impl WsRPC {
         query_rx.await.map_err(|_| jsonrpsee::core::SubscriptionError::from("Internal error. Failed to receive engine state receiver query. The engine query handler is likely closed."))
     }
 
+    async fn derivation_events_receiver(
+        &self,
+    ) -> Result<broadcast::Receiver<DerivationEvent>, jsonrpsee::core::SubscriptionError> {
+        let (query_tx, query_rx) = tokio::sync::oneshot::channel();
+
+        if let Err(e) =
+            self.engine_query_sender.send(EngineQueries::DerivationEventsReceiver(query_tx)).await
+        {
+            warn!(target: "rpc::ws", ?e, "Failed to send derivation events receiver query. The engine query handler is likely closed.");
+            return Err(jsonrpsee::core::SubscriptionError::from(
+                "Internal error. Failed to send derivation events receiver query. The engine query handler is likely closed.",
+            ));
+        }
+
+        query_rx.await.map_err(|_| jsonrpsee::core::SubscriptionError::from("Internal error. Failed to receive derivation events receiver query. The engine query handler is likely closed."))
+    }
+
+    /// Sends the given head of the engine state whenever it changes, until the engine state
+    /// channel is closed.
+    async fn stream_head_updates(
+        sink: &SubscriptionSink,
+        mut subscription: watch::Receiver<EngineState>,
+        head: impl Fn(&EngineState) -> L2BlockInfo,
+    ) -> Result<(), jsonrpsee::core::SubscriptionError> {
+        let mut current_head = head(&subscription.borrow());
+
+        while let Ok(new_head) = subscription
+            .wait_for(|state| head(state) != current_head)
+            .await
+            .map(|state| head(&state))
+        {
+            current_head = new_head;
+            Self::send_state_update(sink, current_head).await?;
+        }
+        Ok(())
+    }
+
     async fn send_state_update(
         sink: &SubscriptionSink,
-        state: L2BlockInfo,
+        update: impl Serialize,
     ) -> Result<(), jsonrpsee::core::SubscriptionError> {
-        sink.send(to_json_raw_value(&state).map_err(|_| {
+        sink.send(to_json_raw_value(&update).map_err(|_| {
             jsonrpsee::core::SubscriptionError::from(
-                "Internal error. Impossible to convert l2 block info to json",
+                "Internal error. Impossible to convert subscription update to json",
             )
         })?)
         .await
@@ -61,20 +127,9 @@ impl WsRPC {
 impl WsServer for WsRPC {
     async fn ws_safe_head_updates(&self, sink: PendingSubscriptionSink) -> SubscriptionResult {
         let sink = sink.accept().await?;
-
-        let mut subscription = self.engine_state_watcher().await?;
-
-        let mut current_safe_head = subscription.borrow().sync_state.safe_head();
-
-        while let Ok(new_state) = subscription
-            .wait_for(|state| state.sync_state.safe_head() != current_safe_head)
-            .await
-            .map(|state| *state)
-        {
-            info!(target: "rpc::ws", "Sending safe head update: {:?}", new_state.sync_state.safe_head());
-            current_safe_head = new_state.sync_state.safe_head();
-            Self::send_state_update(&sink, current_safe_head).await?;
-        }
+        let subscription = self.engine_state_watcher().await?;
+        Self::stream_head_updates(&sink, subscription, |state| state.sync_state.safe_head())
+            .await?;
 
         warn!(target: "rpc::ws", "Subscription to safe head updates has been closed.");
         Ok(())
@@ -82,19 +137,9 @@ impl WsServer for WsRPC {
 
     async fn ws_finalized_head_updates(&self, sink: PendingSubscriptionSink) -> SubscriptionResult {
         let sink = sink.accept().await?;
-
-        let mut subscription = self.engine_state_watcher().await?;
-
-        let mut current_finalized_head = subscription.borrow().sync_state.finalized_head();
-
-        while let Ok(new_state) = subscription
-            .wait_for(|state| state.sync_state.finalized_head() != current_finalized_head)
-            .await
-            .map(|state| *state)
-        {
-            current_finalized_head = new_state.sync_state.finalized_head();
-            Self::send_state_update(&sink, current_finalized_head).await?;
-        }
+        let subscription = self.engine_state_watcher().await?;
+        Self::stream_head_updates(&sink, subscription, |state| state.sync_state.finalized_head())
+            .await?;
 
         warn!(target: "rpc::ws", "Subscription to finalized head updates has been closed.");
         Ok(())
@@ -102,21 +147,49 @@ impl WsServer for WsRPC {
 
     async fn ws_unsafe_head_updates(&self, sink: PendingSubscriptionSink) -> SubscriptionResult {
         let sink = sink.accept().await?;
+        let subscription = self.engine_state_watcher().await?;
+        Self::stream_head_updates(&sink, subscription, |state| state.sync_state.unsafe_head())
+            .await?;
 
-        let mut subscription = self.engine_state_watcher().await?;
-
-        let mut current_unsafe_head = subscription.borrow().sync_state.unsafe_head();
+        warn!(target: "rpc::ws", "Subscription to unsafe head updates has been closed.");
+        Ok(())
+    }
+}
 
-        while let Ok(new_state) = subscription
-            .wait_for(|state| state.sync_state.unsafe_head() != current_unsafe_head)
-            .await
-            .map(|state| *state)
-        {
-            current_unsafe_head = new_state.sync_state.unsafe_head();
-            Self::send_state_update(&sink, current_unsafe_head).await?;
-        }
+#[async_trait::async_trait]
+impl RollupSubscriptionApiServer for WsRPC {
+    async fn rollup_subscribe(
+        &self,
+        sink: PendingSubscriptionSink,
+        kind: RollupSubscriptionKind,
+    ) -> SubscriptionResult {
+        let head: fn(&EngineState) -> L2BlockInfo = match kind {
+            RollupSubscriptionKind::UnsafeHead => |state| state.sync_state.unsafe_head(),
+            RollupSubscriptionKind::SafeHead => |state| state.sync_state.safe_head(),
+            RollupSubscriptionKind::FinalizedHead => |state| state.sync_state.finalized_head(),
+            RollupSubscriptionKind::DerivationEvents => {
+                let mut events = self.derivation_events_receiver().await?;
+                let sink = sink.accept().await?;
+                loop {
+                    match events.recv().await {
+                        Ok(event) => Self::send_state_update(&sink, event).await?,
+                        Err(RecvError::Lagged(skipped)) => {
+                            warn!(target: "rpc::ws", skipped, "Derivation events subscriber lagged behind");
+                        }
+                        Err(RecvError::Closed) => break,
+                    }
+                }
+
+                warn!(target: "rpc::ws", "Subscription to derivation events has been closed.");
+                return Ok(());
+            }
+        };
+
+        let subscription = self.engine_state_watcher().await?;
+        let sink = sink.accept().await?;
+        Self::stream_head_updates(&sink, subscription, head).await?;
 
-        warn!(target: "rpc::ws", "Subscription to unsafe head updates has been closed.");
+        warn!(target: "rpc::ws", ?kind, "Rollup subscription has been closed.");
         Ok(())
     }
 }
@@ -12,9 +12,10 @@ use kona_derive::{
 use kona_genesis::{L1ChainConfig, RollupConfig};
 use kona_protocol::{BlockInfo, L2BlockInfo, OpAttributesWithParent};
 use kona_providers_alloy::{
-    AlloyChainProvider, AlloyL2ChainProvider, OnlineBeaconClient, OnlineBlobProvider,
-    OnlinePipeline,
+    AlloyChainProvider, AlloyL2ChainProvider, L1PrefetchCache, OnlineBlobProvider, OnlinePipeline,
+    RedundantBeaconClient,
 };
+use kona_rpc::DerivationQueries;
 use op_alloy_network::Optimism;
 use thiserror::Error;
 use tokio::{
@@ -62,6 +63,8 @@ where
     ///
     /// Specs: <https://specs.optimism.io/protocol/derivation.html#l1-sync-payload-attributes-processing>
     derivation_signal_rx: mpsc::Receiver<Signal>,
+    /// The receiver for queries on the derivation pipeline, from the RPC server.
+    inbound_queries: mpsc::Receiver<DerivationQueries>,
 }
 
 /// The state for the derivation actor.
@@ -101,7 +104,7 @@ pub struct DerivationBuilder {
     /// Whether to trust the L1 RPC.
     pub l1_trust_rpc: bool,
     /// The L1 beacon client.
-    pub l1_beacon: OnlineBeaconClient,
+    pub l1_beacon: RedundantBeaconClient,
     /// The L2 provider.
     pub l2_provider: RootProvider<Optimism>,
     /// Whether to trust the L2 RPC.
@@ -112,19 +115,34 @@ pub struct DerivationBuilder {
     pub l1_config: Arc<L1ChainConfig>,
     /// The interop mode.
     pub interop_mode: InteropMode,
+    /// The L1 data prefetched ahead of the pipeline by the L1 watcher, if enabled.
+    pub l1_prefetch_cache: Option<L1PrefetchCache>,
 }
 
 #[async_trait]
 impl PipelineBuilder for DerivationBuilder {
-    type Pipeline = OnlinePipeline;
+    type Pipeline = OnlinePipeline<RedundantBeaconClient>;
 
-    async fn build(self) -> DerivationState<OnlinePipeline> {
+    async fn build(self) -> DerivationState<Self::Pipeline> {
         // Create the caching L1/L2 EL providers for derivation.
-        let l1_derivation_provider = AlloyChainProvider::new_with_trust(
+        let mut l1_derivation_provider = AlloyChainProvider::new_with_trust(
             self.l1_provider.clone(),
             DERIVATION_PROVIDER_CACHE_SIZE,
             self.l1_trust_rpc,
         );
+        match l1_derivation_provider.probe_receipts_strategy().await {
+            Ok(strategy) => {
+                info!(target: "derivation", %strategy, "Selected the L1 receipts method");
+            }
+            Err(err) => {
+                warn!(target: "derivation", ?err, "Failed to probe the L1 receipts methods");
+            }
+        }
+        let mut blob_provider = OnlineBlobProvider::init(self.l1_beacon.clone()).await;
+        if let Some(cache) = self.l1_prefetch_cache {
+            l1_derivation_provider = l1_derivation_provider.with_prefetch_cache(cache.clone());
+            blob_provider = blob_provider.with_prefetch_cache(cache);
+        }
         let l2_derivation_provider = AlloyL2ChainProvider::new_with_trust(
             self.l2_provider.clone(),
             self.rollup_config.clone(),
@@ -136,14 +154,14 @@ impl PipelineBuilder for DerivationBuilder {
             InteropMode::Polled => OnlinePipeline::new_polled(
                 self.rollup_config.clone(),
                 self.l1_config.clone(),
-                OnlineBlobProvider::init(self.l1_beacon.clone()).await,
+                blob_provider,
                 l1_derivation_provider,
                 l2_derivation_provider,
             ),
             InteropMode::Indexed => OnlinePipeline::new_indexed(
                 self.rollup_config.clone(),
                 self.l1_config.clone(),
-                OnlineBlobProvider::init(self.l1_beacon.clone()).await,
+                blob_provider,
                 l1_derivation_provider,
                 l2_derivation_provider,
             ),
@@ -169,6 +187,8 @@ pub struct DerivationInboundChannels {
     /// This channel should be used by the engine actor to send [`Signal`]s to the derivation
     /// pipeline. The signals are received by `DerivationActor::derivation_signal_rx`.
     pub derivation_signal_tx: mpsc::Sender<Signal>,
+    /// A sender for queries on the derivation pipeline, used by the RPC server.
+    pub inbound_queries: mpsc::Sender<DerivationQueries>,
 }
 
 /// The communication context used by the derivation actor.
@@ -212,6 +232,17 @@ where
         }
     }
 
+    /// Records how many blocks the L1 origin of the pipeline lags behind the given L1 head.
+    fn record_origin_lag(&self, l1_head: Option<BlockInfo>) {
+        if let (Some(l1_head), Some(origin)) = (l1_head, self.pipeline.origin()) {
+            kona_macros::set!(
+                gauge,
+                Metrics::DERIVATION_L1_ORIGIN_LAG,
+                l1_head.number.saturating_sub(origin.number) as f64
+            );
+        }
+    }
+
     /// Attempts to step the derivation pipeline forward as much as possible in order to produce the
     /// next safe payload.
     async fn produce_next_attributes(
@@ -278,6 +309,14 @@ where
                                         target: "derivation",
                                         "L1 reorg detected! Expected: {expected} | New: {new}"
                                     );
+                                    trace!(
+                                        target: "journal",
+                                        event = "l1_reorg_detected",
+                                        expected = %expected,
+                                        new = %new,
+                                        l2_safe_head_number = l2_safe_head.block_info.number,
+                                        l2_safe_head_hash = %l2_safe_head.block_info.hash,
+                                    );
 
                                     kona_macros::inc!(counter, Metrics::L1_REORG_COUNT);
                                 }
@@ -408,12 +447,14 @@ where
             watch::channel(L2BlockInfo::default());
         let (el_sync_complete_tx, el_sync_complete_rx) = oneshot::channel();
         let (derivation_signal_tx, derivation_signal_rx) = mpsc::channel(16);
+        let (inbound_queries_tx, inbound_queries_rx) = mpsc::channel(1024);
         let actor = Self {
             state,
             l1_head_updates: l1_head_updates_rx,
             engine_l2_safe_head: engine_l2_safe_head_rx,
             el_sync_complete_rx,
             derivation_signal_rx,
+            inbound_queries: inbound_queries_rx,
         };
 
         (
@@ -422,6 +463,7 @@ where
                 engine_l2_safe_head_tx,
                 el_sync_complete_tx,
                 derivation_signal_tx,
+                inbound_queries: inbound_queries_tx,
             },
             actor,
         )
@@ -476,6 +518,15 @@ where
                     state.signal(signal).await;
                     state.waiting_for_signal = false;
                 }
+                Some(query) = self.inbound_queries.recv() => {
+                    match query {
+                        DerivationQueries::PipelineSnapshot(sender) => {
+                            if sender.send(state.pipeline.snapshot()).is_err() {
+                                warn!(target: "derivation", "Failed to send the pipeline snapshot, the receiver was dropped");
+                            }
+                        }
+                    }
+                }
                 msg = self.l1_head_updates.changed() => {
                     if let Err(err) = msg {
                         error!(
@@ -487,9 +538,11 @@ where
                     }
 
                     state.process(InboundDerivationMessage::NewDataAvailable, &mut self.engine_l2_safe_head, &self.el_sync_complete_rx, &derived_attributes_tx, &reset_request_tx).await?;
+                    state.record_origin_lag(*self.l1_head_updates.borrow());
                 }
                 _ = self.engine_l2_safe_head.changed() => {
                     state.process(InboundDerivationMessage::SafeHeadUpdated, &mut self.engine_l2_safe_head, &self.el_sync_complete_rx, &derived_attributes_tx, &reset_request_tx).await?;
+                    state.record_origin_lag(*self.l1_head_updates.borrow());
                 }
                 _ = &mut self.el_sync_complete_rx, if !self.el_sync_complete_rx.is_terminated() => {
                     info!(target: "derivation", "Engine finished syncing, starting derivation.");
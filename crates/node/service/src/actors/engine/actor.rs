@@ -1,27 +1,35 @@
 //! The [`EngineActor`].
 
-use super::{EngineError, L2Finalizer};
-use alloy_rpc_types_engine::JwtSecret;
+use super::{
+    EngineError, L2Finalizer, conditional::forward_conditional_txs,
+    import_queue::UnsafeImportQueue, unsafe_buffer::UnsafePayloadBuffer,
+};
+use alloy_provider::RootProvider;
 use async_trait::async_trait;
 use futures::future::OptionFuture;
 use kona_derive::{ResetSignal, Signal};
 use kona_engine::{
-    BuildTask, ConsolidateTask, Engine, EngineClient, EngineQueries,
+    BuildTask, ConsolidateTask, DEFAULT_DERIVATION_EVENTS_CAPACITY, DerivationEvent, Engine,
+    EngineAnchor, EngineAnchorError, EngineClient, EngineQueries, EngineQueryContext,
     EngineState as InnerEngineState, EngineTask, EngineTaskError, EngineTaskErrorSeverity,
-    InsertTask,
+    InsertTask, MAX_CONCURRENT_RANGE_QUERIES, OutputRootCache, PayloadCommitRequest,
+    PayloadVerifier, ReloadableJwtSecret, SafeHeadDb, SafeHeadEntry,
 };
 use kona_genesis::RollupConfig;
+use kona_gossip::SyncRequest;
 use kona_protocol::{BlockInfo, L2BlockInfo, OpAttributesWithParent};
+use kona_rpc::ConditionalTransaction;
+use kona_sources::TrustedCheckpoint;
 use op_alloy_rpc_types_engine::OpExecutionPayloadEnvelope;
-use std::sync::Arc;
+use std::{path::PathBuf, sync::Arc, time::Duration};
 use tokio::{
-    sync::{mpsc, oneshot, watch},
+    sync::{Semaphore, broadcast, mpsc, oneshot, watch},
     task::JoinHandle,
 };
 use tokio_util::sync::{CancellationToken, WaitForCancellationFuture};
 use url::Url;
 
-use crate::{NodeActor, NodeMode, actors::CancellableContext};
+use crate::{NodeActor, NodeMode, SyncMode, actors::CancellableContext};
 
 /// The [`EngineActor`] is responsible for managing the operations sent to the execution layer's
 /// Engine API. To accomplish this, it uses the [`Engine`] task queue to order Engine API
@@ -43,10 +51,31 @@ pub struct EngineActor {
     /// ## Note
     /// This is `Some` when the node is in sequencer mode, and `None` when the node is in validator
     /// mode.
-    build_request_rx:
-        Option<mpsc::Receiver<(OpAttributesWithParent, mpsc::Sender<OpExecutionPayloadEnvelope>)>>,
+    build_request_rx: Option<mpsc::Receiver<BuildRequest>>,
     /// The [`L2Finalizer`], used to finalize L2 blocks.
     finalizer: L2Finalizer,
+    /// A channel to receive conditional transactions submitted to the sequencer.
+    ///
+    /// ## Note
+    /// This is `Some` when the node is in sequencer mode, and `None` when the node is in validator
+    /// mode.
+    conditional_tx_rx: Option<mpsc::Receiver<ConditionalTransaction>>,
+    /// The unsafe payloads received from the network that are yet to be imported.
+    unsafe_imports: UnsafeImportQueue,
+    /// A channel that signals the network actor to only forward the unsafe payload extending the
+    /// unsafe head while the [`UnsafeImportQueue`] is congested.
+    unsafe_import_backpressure: watch::Sender<Option<u64>>,
+}
+
+/// A request from the sequencer actor to build a new block on top of the unsafe head.
+#[derive(Debug)]
+pub struct BuildRequest {
+    /// The attributes of the block to build.
+    pub attributes: OpAttributesWithParent,
+    /// The channel to send the built payload to, once it is imported.
+    pub payload_tx: mpsc::Sender<OpExecutionPayloadEnvelope>,
+    /// The channel to commit the sealed payload to before it is imported, if any.
+    pub commit_tx: Option<mpsc::Sender<PayloadCommitRequest>>,
 }
 
 /// The outbound data for the [`EngineActor`].
@@ -57,8 +86,7 @@ pub struct EngineInboundData {
     /// ## Note
     /// This is `Some` when the node is in sequencer mode, and `None` when the node is in validator
     /// mode.
-    pub build_request_tx:
-        Option<mpsc::Sender<(OpAttributesWithParent, mpsc::Sender<OpExecutionPayloadEnvelope>)>>,
+    pub build_request_tx: Option<mpsc::Sender<BuildRequest>>,
     /// A channel to send [`OpAttributesWithParent`] to the engine actor.
     pub attributes_tx: mpsc::Sender<OpAttributesWithParent>,
     /// A channel to send [`OpExecutionPayloadEnvelope`] to the engine actor.
@@ -69,12 +97,22 @@ pub struct EngineInboundData {
     /// trigger [`BuildTask`] tasks which should insert the block newly built to the engine
     /// state upon completion.
     pub unsafe_block_tx: mpsc::Sender<OpExecutionPayloadEnvelope>,
+    /// A channel that signals the number of the unsafe head while the engine actor is congested
+    /// with unsafe payloads to import, in which case the network actor should only forward the
+    /// payload extending it.
+    pub unsafe_import_backpressure: watch::Receiver<Option<u64>>,
     /// A channel to send reset requests.
     pub reset_request_tx: mpsc::Sender<()>,
     /// Handler to send inbound queries to the engine.
     pub inbound_queries_tx: mpsc::Sender<EngineQueries>,
     /// A channel that sends new finalized L1 blocks intermittently.
     pub finalized_l1_block_tx: watch::Sender<Option<BlockInfo>>,
+    /// A channel to send conditional transactions to be included by the sequencer.
+    ///
+    /// ## Note
+    /// This is `Some` when the node is in sequencer mode, and `None` when the node is in validator
+    /// mode.
+    pub conditional_tx_tx: Option<mpsc::Sender<ConditionalTransaction>>,
 }
 
 /// Configuration for the Engine Actor.
@@ -84,53 +122,179 @@ pub struct EngineBuilder {
     pub config: Arc<RollupConfig>,
     /// The engine rpc url.
     pub engine_url: Url,
+    /// The standby engine rpc urls, failed over to when the engine rpc is unavailable.
+    pub standby_engine_urls: Vec<Url>,
+    /// The engine rpc url of a secondary execution layer, used to cross-check the payloads
+    /// inserted by the node, if any.
+    pub verifier_engine_url: Option<Url>,
     /// The L1 rpc url.
     pub l1_rpc_url: Url,
     /// The engine jwt secret.
-    pub jwt_secret: JwtSecret,
+    pub jwt_secret: ReloadableJwtSecret,
+    /// The file the engine jwt secret was read from. If set, the secret is reloaded whenever the
+    /// file changes.
+    pub jwt_secret_file: Option<PathBuf>,
     /// The mode of operation for the node.
     /// When the node is in sequencer mode, the engine actor will receive requests to build blocks
     /// from the sequencer actor.
     pub mode: NodeMode,
+    /// The [`SyncMode`] used to bring the execution layer up to the tip of the L2 chain.
+    pub sync_mode: SyncMode,
+    /// The interval within which forkchoice updates of inserted unsafe blocks are batched, if
+    /// any.
+    pub forkchoice_debounce: Option<Duration>,
+    /// The path of the [`SafeHeadDb`]. If set, the safe head derived from each L1 block is
+    /// recorded.
+    pub safe_db_path: Option<PathBuf>,
+    /// The maximum number of unsafe blocks that may be reorged out when the safe chain diverges
+    /// from the unsafe chain, if any. Deeper reorgs halt the node.
+    pub max_reorg_depth: Option<u64>,
+    /// The trusted L2 checkpoint to start the sync from, if any.
+    pub trusted_checkpoint: Option<TrustedCheckpoint>,
+    /// The file of the [`EngineAnchor`]. If set, the safe and finalized heads are persisted, and
+    /// the execution layer must still contain them on startup.
+    pub anchor_path: Option<PathBuf>,
+    /// Whether to start even though the execution layer does not contain the [`EngineAnchor`],
+    /// reorging the node to the chain of the execution layer.
+    pub anchor_reorg: bool,
 }
 
 impl EngineBuilder {
     /// Launches the [`Engine`]. Returns the [`Engine`] and a channel to receive engine state
     /// updates.
-    fn build_state(self) -> EngineActorState {
-        let client = self.client();
-        let state = InnerEngineState::default();
+    fn build_state(self) -> Result<EngineActorState, EngineError> {
+        let client = self.verified_client();
+        let safe_db = self.safe_db_path.map(SafeHeadDb::open).transpose()?.map(Arc::new);
+        // In consensus-layer sync mode, the execution layer is not instructed to sync via its own
+        // P2P network, so derivation can start straight away.
+        let state = InnerEngineState {
+            el_sync_finished: self.sync_mode.is_consensus_layer(),
+            ..Default::default()
+        };
         let (engine_state_send, _) = tokio::sync::watch::channel(state);
         let (engine_queue_length_send, _) = tokio::sync::watch::channel(0);
-
-        EngineActorState {
+        let (queued_unsafe_head, _) = tokio::sync::watch::channel(None);
+        let (derivation_events, _) =
+            tokio::sync::broadcast::channel(DEFAULT_DERIVATION_EVENTS_CAPACITY);
+        Ok(EngineActorState {
             rollup: self.config,
+            sync_mode: self.sync_mode,
+            forkchoice_debounce: self.forkchoice_debounce,
+            max_reorg_depth: self.max_reorg_depth,
+            active_engine: client.active_engine(),
             client,
-            engine: Engine::new(state, engine_state_send, engine_queue_length_send),
-        }
+            engine: Engine::new(state, engine_state_send, engine_queue_length_send)
+                .with_trusted_checkpoint(self.trusted_checkpoint),
+            safe_db,
+            anchor_path: self.anchor_path,
+            anchor_reorg: self.anchor_reorg,
+            anchor: EngineAnchor::default(),
+            anchor_write: None,
+            safe_db_write: None,
+            derived_from: None,
+            unsafe_payloads: UnsafePayloadBuffer::default(),
+            queued_unsafe_head,
+            derivation_events,
+        })
     }
 
     /// Returns the [`EngineClient`].
     pub fn client(&self) -> Arc<EngineClient> {
-        EngineClient::new_http(
+        self.engine_client().into()
+    }
+
+    /// Returns the [`EngineClient`] of the engine actor, whose inserted payloads are cross-checked
+    /// by a [`PayloadVerifier`] if a secondary execution layer is configured.
+    fn verified_client(&self) -> Arc<EngineClient> {
+        let client = self.engine_client();
+        match self.payload_verifier() {
+            Some(payload_verifier) => client.with_payload_verifier(payload_verifier).into(),
+            None => client.into(),
+        }
+    }
+
+    /// Returns a new [`EngineClient`] of the execution layer.
+    fn engine_client(&self) -> EngineClient {
+        EngineClient::new_http_with_standby(
             self.engine_url.clone(),
+            self.standby_engine_urls.clone(),
             self.l1_rpc_url.clone(),
             self.config.clone(),
-            self.jwt_secret,
+            self.jwt_secret.clone(),
         )
-        .into()
+    }
+
+    /// Spawns the [`PayloadVerifier`] of the secondary execution layer, if one is configured.
+    fn payload_verifier(&self) -> Option<mpsc::Sender<OpExecutionPayloadEnvelope>> {
+        self.verifier_engine_url.clone().map(|url| {
+            let client = EngineClient::new_http_with_standby(
+                url,
+                Vec::new(),
+                self.l1_rpc_url.clone(),
+                self.config.clone(),
+                self.jwt_secret.clone(),
+            );
+            PayloadVerifier::new(client.l2_engine().clone()).spawn()
+        })
+    }
+
+    /// Spawns a task that reloads the engine jwt secret whenever its file changes, if the secret
+    /// was read from a file.
+    fn watch_jwt_secret(&self) -> Option<JoinHandle<()>> {
+        self.jwt_secret_file
+            .clone()
+            .map(|path| self.jwt_secret.watch_file(path, JWT_SECRET_RELOAD_INTERVAL))
     }
 }
 
+/// The interval at which the engine jwt secret file is checked for changes.
+const JWT_SECRET_RELOAD_INTERVAL: Duration = Duration::from_secs(10);
+
+/// The interval at which the [`EngineAnchor`] check is retried while the execution layer is
+/// unavailable.
+const ANCHOR_RETRY_INTERVAL: Duration = Duration::from_secs(5);
+
 /// The configuration for the [`EngineActor`].
 #[derive(Debug)]
 pub(super) struct EngineActorState {
     /// The [`RollupConfig`] used to build tasks.
     pub(super) rollup: Arc<RollupConfig>,
+    /// The [`SyncMode`] of the node.
+    pub(super) sync_mode: SyncMode,
+    /// The interval within which forkchoice updates of inserted unsafe blocks are batched, if
+    /// any.
+    pub(super) forkchoice_debounce: Option<Duration>,
+    /// The maximum number of unsafe blocks that may be reorged out when the safe chain diverges
+    /// from the unsafe chain, if any.
+    pub(super) max_reorg_depth: Option<u64>,
+    /// The index of the engine endpoint that the engine state was last synchronized with.
+    pub(super) active_engine: usize,
     /// An [`EngineClient`] used for creating engine tasks.
     pub(super) client: Arc<EngineClient>,
     /// The [`Engine`] task queue.
     pub(super) engine: Engine,
+    /// The [`SafeHeadDb`], if the safe head derived from each L1 block is recorded.
+    pub(super) safe_db: Option<Arc<SafeHeadDb>>,
+    /// The file of the [`EngineAnchor`], if the safe and finalized heads are persisted.
+    pub(super) anchor_path: Option<PathBuf>,
+    /// Whether to start even though the execution layer does not contain the [`EngineAnchor`].
+    pub(super) anchor_reorg: bool,
+    /// The most recently persisted [`EngineAnchor`].
+    pub(super) anchor: EngineAnchor,
+    /// The in-flight write of the [`EngineAnchor`] file, if any.
+    pub(super) anchor_write: Option<JoinHandle<()>>,
+    /// The last queued write to the [`SafeHeadDb`], if any. Each write waits for the previous
+    /// one, so that the safe heads are recorded in order.
+    pub(super) safe_db_write: Option<JoinHandle<()>>,
+    /// The L1 block that the most recently received attributes were derived from.
+    pub(super) derived_from: Option<BlockInfo>,
+    /// The recently gossiped unsafe payloads, used to rebuild the unsafe chain after a reorg.
+    pub(super) unsafe_payloads: UnsafePayloadBuffer,
+    /// A channel that sends the next buffered unsafe payload to be inserted into the unsafe chain,
+    /// if any.
+    pub(super) queued_unsafe_head: watch::Sender<Option<L2BlockInfo>>,
+    /// A channel that broadcasts [`DerivationEvent`]s to subscribers.
+    pub(super) derivation_events: broadcast::Sender<DerivationEvent>,
 }
 
 /// The communication context used by the engine actor.
@@ -150,6 +314,8 @@ pub struct EngineContext {
     pub sync_complete_tx: oneshot::Sender<()>,
     /// A way for the engine actor to send a [`Signal`] back to the derivation actor.
     pub derivation_signal_tx: mpsc::Sender<Signal>,
+    /// A channel to request missing unsafe payloads from peers.
+    pub sync_request_tx: mpsc::Sender<SyncRequest>,
 }
 
 impl CancellableContext for EngineContext {
@@ -166,6 +332,7 @@ impl EngineActor {
         let (attributes_tx, attributes_rx) = mpsc::channel(1024);
         let (unsafe_block_tx, unsafe_block_rx) = mpsc::channel(1024);
         let (reset_request_tx, reset_request_rx) = mpsc::channel(1024);
+        let (unsafe_import_backpressure_tx, unsafe_import_backpressure) = watch::channel(None);
 
         let (build_request_tx, build_request_rx) = if config.mode.is_sequencer() {
             let (tx, rx) = mpsc::channel(1024);
@@ -173,6 +340,12 @@ impl EngineActor {
         } else {
             (None, None)
         };
+        let (conditional_tx_tx, conditional_tx_rx) = if config.mode.is_sequencer() {
+            let (tx, rx) = mpsc::channel(1024);
+            (Some(tx), Some(rx))
+        } else {
+            (None, None)
+        };
 
         let actor = Self {
             builder: config,
@@ -182,6 +355,9 @@ impl EngineActor {
             inbound_queries: inbound_queries_rx,
             build_request_rx,
             finalizer: L2Finalizer::new(finalized_l1_block_rx),
+            conditional_tx_rx,
+            unsafe_imports: UnsafeImportQueue::default(),
+            unsafe_import_backpressure: unsafe_import_backpressure_tx,
         };
 
         let outbound_data = EngineInboundData {
@@ -190,33 +366,170 @@ impl EngineActor {
             inbound_queries_tx,
             attributes_tx,
             unsafe_block_tx,
+            unsafe_import_backpressure,
             reset_request_tx,
+            conditional_tx_tx,
         };
 
         (outbound_data, actor)
     }
+
+    /// Signals the network actor the number of the given unsafe head while the
+    /// [`UnsafeImportQueue`] is congested, if it changed.
+    fn signal_unsafe_import_backpressure(&self, unsafe_head: u64) {
+        let backpressure = self.unsafe_imports.is_congested().then_some(unsafe_head);
+        let mut toggled = false;
+        self.unsafe_import_backpressure.send_if_modified(|val| {
+            toggled = val.is_some() != backpressure.is_some();
+            (*val != backpressure).then(|| *val = backpressure).is_some()
+        });
+        if toggled {
+            let congested = backpressure.is_some();
+            info!(
+                target: "engine",
+                congested,
+                queued = self.unsafe_imports.len(),
+                "Unsafe payload import backpressure changed"
+            );
+        }
+    }
 }
 
 impl EngineActorState {
+    /// Flushes the [`SafeHeadDb`] to disk, if any, once the queued writes have completed. Called
+    /// on shutdown.
+    async fn flush_safe_db(&mut self) {
+        let Some(safe_db) = self.safe_db.clone() else {
+            return;
+        };
+        self.wait_safe_db_write().await;
+        match tokio::task::spawn_blocking(move || safe_db.flush()).await {
+            Ok(Ok(())) => {}
+            Ok(Err(err)) => warn!(target: "engine", ?err, "Failed to flush the safe head database"),
+            Err(err) => warn!(target: "engine", ?err, "Safe head database flush task failed"),
+        }
+    }
+
+    /// Waits for the queued writes to the [`SafeHeadDb`] to complete.
+    async fn wait_safe_db_write(&mut self) {
+        if let Some(write) = self.safe_db_write.take() {
+            if let Err(err) = write.await {
+                error!(target: "engine", ?err, "Safe head database write task failed");
+            }
+        }
+    }
+
+    /// Records the given entry in the [`SafeHeadDb`], if any. The write runs on the blocking
+    /// pool, after the previously queued writes.
+    fn record_safe_head(&mut self, entry: SafeHeadEntry) {
+        let Some(safe_db) = self.safe_db.clone() else {
+            return;
+        };
+        let previous = self.safe_db_write.take();
+        self.safe_db_write = Some(tokio::spawn(async move {
+            if let Some(previous) = previous {
+                let _ = previous.await;
+            }
+            match tokio::task::spawn_blocking(move || safe_db.record(entry)).await {
+                Ok(Ok(())) => {}
+                Ok(Err(err)) => error!(target: "engine", ?err, "Failed to record safe head"),
+                Err(err) => error!(target: "engine", ?err, "Safe head database write task failed"),
+            }
+        }));
+    }
+
+    /// Checks that the execution layer still contains the persisted [`EngineAnchor`], if any.
+    /// The check is retried until the execution layer is available, or the actor is cancelled.
+    async fn verify_anchor(&mut self, cancellation: &CancellationToken) -> Result<(), EngineError> {
+        let Some(path) = &self.anchor_path else {
+            return Ok(());
+        };
+        let Some(anchor) = EngineAnchor::load(path)? else {
+            return Ok(());
+        };
+
+        loop {
+            match anchor.verify(self.client.l2_engine()).await {
+                Ok(()) => {
+                    info!(target: "engine", ?anchor, "Execution layer contains the engine anchor");
+                    self.anchor = anchor;
+                    return Ok(());
+                }
+                Err(EngineAnchorError::Client(err)) => {
+                    warn!(target: "engine", ?err, "Failed to check the engine anchor, retrying");
+                    tokio::select! {
+                        _ = cancellation.cancelled() => return Ok(()),
+                        _ = tokio::time::sleep(ANCHOR_RETRY_INTERVAL) => {}
+                    }
+                }
+                Err(err @ EngineAnchorError::NotCanonical { .. }) if self.anchor_reorg => {
+                    warn!(target: "engine", %err, "Reorging to the chain of the execution layer");
+                    return Ok(());
+                }
+                Err(err) => {
+                    error!(
+                        target: "engine",
+                        %err,
+                        "The execution layer was rolled back or belongs to another chain. Refusing to start"
+                    );
+                    return Err(err.into());
+                }
+            }
+        }
+    }
+
+    /// Persists the safe and finalized heads to the [`EngineAnchor`] file when the finalized head
+    /// changed, rather than on every safe head update. The file is written on the blocking pool,
+    /// one write at a time; an update that arrives while a write is in flight is picked up by
+    /// the next call.
+    fn persist_anchor(&mut self) {
+        let Some(path) = &self.anchor_path else {
+            return;
+        };
+        if self.anchor_write.as_ref().is_some_and(|write| !write.is_finished()) {
+            return;
+        }
+        let sync_state = self.engine.state().sync_state;
+        let anchor = EngineAnchor {
+            safe: sync_state.safe_head().block_info.id(),
+            finalized: sync_state.finalized_head().block_info.id(),
+        };
+        if anchor.finalized == self.anchor.finalized || anchor.finalized.hash.is_zero() {
+            return;
+        }
+
+        self.anchor = anchor;
+        let path = path.clone();
+        self.anchor_write = Some(tokio::task::spawn_blocking(move || {
+            if let Err(err) = anchor.store(path) {
+                error!(target: "engine", ?err, "Failed to persist the engine anchor");
+            }
+        }));
+    }
+
     /// Starts a task to handle engine queries.
     fn start_query_task(
         &self,
         mut inbound_query_channel: tokio::sync::mpsc::Receiver<EngineQueries>,
     ) -> JoinHandle<()> {
-        let state_recv = self.engine.state_subscribe();
-        let queue_length_recv = self.engine.queue_length_subscribe();
-        let engine_client = self.client.clone();
-        let rollup_config = self.rollup.clone();
+        let ctx = EngineQueryContext {
+            state_recv: self.engine.state_subscribe(),
+            queue_length_recv: self.engine.queue_length_subscribe(),
+            queued_unsafe_head_recv: self.queued_unsafe_head.subscribe(),
+            derivation_events: self.derivation_events.clone(),
+            client: self.client.clone(),
+            rollup_config: self.rollup.clone(),
+            safe_db: self.safe_db.clone(),
+            output_cache: OutputRootCache::default(),
+            range_query_permits: Arc::new(Semaphore::new(MAX_CONCURRENT_RANGE_QUERIES)),
+        };
 
         tokio::spawn(async move {
             while let Some(req) = inbound_query_channel.recv().await {
                 {
                     trace!(target: "engine", ?req, "Received engine query request.");
 
-                    if let Err(e) = req
-                        .handle(&state_recv, &queue_length_recv, &engine_client, &rollup_config)
-                        .await
-                    {
+                    if let Err(e) = req.handle(&ctx).await {
                         warn!(target: "engine", err = ?e, "Failed to handle engine query request.");
                     }
                 }
@@ -235,6 +548,21 @@ impl EngineActorState {
         let (l2_safe_head, l1_origin, system_config) =
             self.engine.reset(self.client.clone(), self.rollup.clone()).await?;
 
+        // Safe heads recorded past the reset safe head may no longer be canonical. The queued
+        // writes must land first, so that they are truncated as well.
+        if let Some(safe_db) = self.safe_db.clone() {
+            self.wait_safe_db_write().await;
+            let number = l2_safe_head.block_info.number;
+            tokio::task::spawn_blocking(move || safe_db.truncate(number)).await??;
+        }
+        self.derived_from = None;
+
+        // Buffered payloads that were reorged out would be inserted again once their parent
+        // becomes the unsafe head, reorging the chain back and looping on resets.
+        let unsafe_head = self.engine.state().sync_state.unsafe_head().block_info;
+        self.unsafe_payloads.purge_non_canonical(unsafe_head.number, unsafe_head.hash);
+        self.update_queued_unsafe_head();
+
         // Attempt to update the safe head following the reset.
         // IMPORTANT NOTE: We need to update the safe head BEFORE sending the reset signal to the
         // derivation actor. Since the derivation actor receives the safe head via a watch
@@ -242,6 +570,10 @@ impl EngineActorState {
         // condition where the derivation actor receives the pre-reset safe head.
         self.maybe_update_safe_head(engine_l2_safe_head_tx);
 
+        let _ = self
+            .derivation_events
+            .send(DerivationEvent::Reset { l1_origin, safe_head: l2_safe_head });
+
         // Signal the derivation actor to reset.
         let signal = ResetSignal { l2_safe_head, l1_origin, system_config: Some(system_config) };
         match derivation_signal_tx.send(signal.signal()).await {
@@ -266,7 +598,14 @@ impl EngineActorState {
         engine_l2_safe_head_tx: &watch::Sender<L2BlockInfo>,
         finalizer: &mut L2Finalizer,
     ) -> Result<(), EngineError> {
-        match self.engine.drain().await {
+        // Insert any buffered unsafe payloads that extend the unsafe head, which rebuilds the
+        // unsafe chain after it has been reorged.
+        let mut result = self.engine.drain().await;
+        while result.is_ok() && self.enqueue_buffered_child() {
+            result = self.engine.drain().await;
+        }
+
+        match result {
             Ok(_) => {
                 trace!(target: "engine", "[ENGINE] tasks drained");
             }
@@ -288,7 +627,8 @@ impl EngineActorState {
                         warn!(target: "engine", ?err, "Invalid payload, Flushing derivation pipeline.");
                         match derivation_signal_tx.send(Signal::FlushChannel).await {
                             Ok(_) => {
-                                debug!(target: "engine", "Sent flush signal to derivation actor")
+                                debug!(target: "engine", "Sent flush signal to derivation actor");
+                                let _ = self.derivation_events.send(DerivationEvent::Flushed);
                             }
                             Err(err) => {
                                 error!(target: "engine", ?err, "Failed to send flush signal to the derivation actor.");
@@ -304,6 +644,11 @@ impl EngineActorState {
         }
 
         self.maybe_update_safe_head(engine_l2_safe_head_tx);
+        self.persist_anchor();
+        self.unsafe_payloads.prune(self.engine.state().sync_state.safe_head().block_info.number);
+        self.update_queued_unsafe_head();
+        self.check_engine_switchover(derivation_signal_tx, engine_l2_safe_head_tx, finalizer)
+            .await?;
         self.check_el_sync(
             derivation_signal_tx,
             engine_l2_safe_head_tx,
@@ -315,6 +660,144 @@ impl EngineActorState {
         Ok(())
     }
 
+    /// Publishes the next buffered unsafe payload above the unsafe head, if it has changed.
+    fn update_queued_unsafe_head(&self) {
+        let unsafe_head = self.engine.state().sync_state.unsafe_head().block_info.number;
+        let queued = self.unsafe_payloads.first_above(unsafe_head);
+        self.queued_unsafe_head.send_if_modified(|current| {
+            let queued_hash = queued.map(|envelope| envelope.execution_payload.block_hash());
+            if current.map(|head| head.block_info.hash) == queued_hash {
+                return false;
+            }
+            *current = queued.and_then(|envelope| {
+                L2BlockInfo::from_payload_and_genesis(
+                    envelope.execution_payload.clone(),
+                    envelope.parent_beacon_block_root,
+                    &self.rollup.genesis,
+                )
+                .inspect_err(|err| {
+                    debug!(target: "engine", ?err, "Failed to read queued unsafe payload");
+                })
+                .ok()
+            });
+            true
+        });
+    }
+
+    /// Enqueues an [`InsertTask`] for the buffered unsafe payload that extends the unsafe head,
+    /// if there is one. Returns `true` if a task was enqueued.
+    fn enqueue_buffered_child(&mut self) -> bool {
+        let unsafe_head = self.engine.state().sync_state.unsafe_head().block_info.hash;
+        let Some(envelope) = self.unsafe_payloads.take_child_of(unsafe_head) else {
+            return false;
+        };
+
+        debug!(
+            target: "engine",
+            block_number = envelope.execution_payload.block_number(),
+            "Inserting buffered unsafe block that extends the unsafe head"
+        );
+        let task = EngineTask::Insert(Box::new(
+            InsertTask::new(self.client.clone(), self.rollup.clone(), envelope, false)
+                .with_forkchoice_debounce(self.forkchoice_debounce),
+        ));
+        self.engine.enqueue(task);
+        true
+    }
+
+    /// Imports an unsafe payload received from the network, enqueuing an [`InsertTask`] for it.
+    ///
+    /// In consensus-layer sync mode, the gap between the unsafe head and the tip of the chain is
+    /// filled by derivation rather than by the execution layer. Payloads that do not extend the
+    /// unsafe head are left in the buffer, and inserted once their parent becomes the unsafe head.
+    fn import_unsafe_payload(
+        &mut self,
+        envelope: OpExecutionPayloadEnvelope,
+        sync_request_tx: &mpsc::Sender<SyncRequest>,
+    ) {
+        let unsafe_head = self.engine.state().sync_state.unsafe_head();
+        if self.sync_mode.is_consensus_layer() &&
+            envelope.execution_payload.parent_hash() != unsafe_head.block_info.hash
+        {
+            debug!(
+                target: "engine",
+                block_number = envelope.execution_payload.block_number(),
+                unsafe_head = unsafe_head.block_info.number,
+                "Buffering unsafe block that does not extend the unsafe head"
+            );
+            self.request_missing_parent(&envelope, sync_request_tx);
+            return;
+        }
+
+        let task = EngineTask::Insert(Box::new(
+            InsertTask::new(
+                self.client.clone(),
+                self.rollup.clone(),
+                envelope,
+                false, // The payload is not derived in this case. This is an unsafe block.
+            )
+            .with_forkchoice_debounce(self.forkchoice_debounce),
+        ));
+        self.engine.enqueue(task);
+    }
+
+    /// Requests the parent of the given buffered unsafe payload from peers, if it is missing
+    /// between the unsafe head and the payload. Payloads that are further ahead of the unsafe head
+    /// than the buffer can hold are left to derivation.
+    fn request_missing_parent(
+        &self,
+        envelope: &OpExecutionPayloadEnvelope,
+        sync_request_tx: &mpsc::Sender<SyncRequest>,
+    ) {
+        let unsafe_head = self.engine.state().sync_state.unsafe_head().block_info.number;
+        let number = envelope.execution_payload.block_number();
+        let parent_hash = envelope.execution_payload.parent_hash();
+        if number <= unsafe_head + 1 ||
+            number - unsafe_head > self.unsafe_payloads.capacity() as u64 ||
+            self.unsafe_payloads.contains(parent_hash)
+        {
+            return;
+        }
+
+        let request = SyncRequest { number: number - 1, hash: parent_hash };
+        if let Err(err) = sync_request_tx.try_send(request) {
+            debug!(target: "engine", ?err, number = request.number, "Failed to request missing unsafe payload");
+        }
+    }
+
+    /// Sends the deferred forkchoice update of batched unsafe blocks to the EL, if one is pending.
+    /// Failures are retried with the next flush, or the next forkchoice update.
+    async fn flush_forkchoice(&mut self) {
+        if let Err(err) =
+            self.engine.flush_forkchoice(self.client.clone(), self.rollup.clone()).await
+        {
+            warn!(target: "engine", ?err, "Failed to flush deferred forkchoice update");
+        }
+    }
+
+    /// Checks if the engine client has failed over to another engine endpoint, resetting the
+    /// engine if it has. The reset resynchronizes the new endpoint with the forkchoice state.
+    async fn check_engine_switchover(
+        &mut self,
+        derivation_signal_tx: &mpsc::Sender<Signal>,
+        engine_l2_safe_head_tx: &watch::Sender<L2BlockInfo>,
+        finalizer: &mut L2Finalizer,
+    ) -> Result<(), EngineError> {
+        let active_engine = self.client.active_engine();
+        if active_engine == self.active_engine {
+            return Ok(());
+        }
+
+        warn!(
+            target: "engine",
+            from = self.active_engine,
+            to = active_engine,
+            "Engine endpoint switched over, resynchronizing forkchoice state"
+        );
+        self.active_engine = active_engine;
+        self.reset(derivation_signal_tx, engine_l2_safe_head_tx, finalizer).await
+    }
+
     /// Checks if the EL has finished syncing, notifying the derivation actor if it has.
     async fn check_el_sync(
         &mut self,
@@ -344,7 +827,7 @@ impl EngineActorState {
     }
 
     /// Attempts to update the safe head via the watch channel.
-    fn maybe_update_safe_head(&self, engine_l2_safe_head_tx: &watch::Sender<L2BlockInfo>) {
+    fn maybe_update_safe_head(&mut self, engine_l2_safe_head_tx: &watch::Sender<L2BlockInfo>) {
         let state_safe_head = self.engine.state().sync_state.safe_head();
         let update = |head: &mut L2BlockInfo| {
             if head != &state_safe_head {
@@ -355,6 +838,21 @@ impl EngineActorState {
         };
         let sent = engine_l2_safe_head_tx.send_if_modified(update);
         info!(target: "engine", safe_head = ?state_safe_head, ?sent, "Attempted L2 Safe Head Update");
+
+        if sent {
+            // Sending only fails if there are no subscribers.
+            let _ = self.derivation_events.send(DerivationEvent::SafeHeadUpdated {
+                l1_block: self.derived_from,
+                safe_head: state_safe_head,
+            });
+        }
+
+        if let (true, Some(derived_from)) = (sent, self.derived_from) {
+            self.record_safe_head(SafeHeadEntry {
+                l1_block: derived_from.id(),
+                safe_head: state_safe_head.block_info.id(),
+            });
+        }
     }
 }
 
@@ -377,22 +875,63 @@ impl NodeActor for EngineActor {
             sync_complete_tx,
             derivation_signal_tx,
             mut engine_unsafe_head_tx,
+            sync_request_tx,
         }: Self::OutboundData,
     ) -> Result<(), Self::Error> {
-        let mut state = self.builder.build_state();
+        let jwt_watcher = self.builder.watch_jwt_secret();
+        let mut state = self.builder.build_state().inspect_err(|_| {
+            if let Some(watcher) = &jwt_watcher {
+                watcher.abort();
+            }
+        })?;
+
+        // Refuse to start on an execution layer that lost the persisted safe or finalized heads.
+        if let Err(err) = state.verify_anchor(&cancellation).await {
+            if let Some(watcher) = &jwt_watcher {
+                watcher.abort();
+            }
+            cancellation.cancel();
+            return Err(err);
+        }
 
         // Start the engine query server in a separate task to avoid blocking the main task.
         let handle = state.start_query_task(self.inbound_queries);
 
+        // Forward the conditional transactions submitted to the sequencer to the transaction pool
+        // of the execution layer, in a separate task to avoid blocking the main task.
+        let conditional_tx_handle = self.conditional_tx_rx.take().map(|rx| {
+            tokio::spawn(forward_conditional_txs(
+                RootProvider::clone(&state.client),
+                state.engine.state_subscribe(),
+                state.rollup.block_time,
+                rx,
+            ))
+        });
+
         // The sync complete tx is consumed after the first successful send. Hence we need to wrap
         // it in an `Option` to ensure we satisfy the borrow checker.
         let mut sync_complete_tx = Some(sync_complete_tx);
 
         loop {
+            // Import the next queued unsafe payload. Payloads are imported one at a time, so that
+            // the tasks of derivation are drained in between.
+            let unsafe_head = state.engine.state().sync_state.unsafe_head().block_info;
+            if let Some(envelope) = self.unsafe_imports.pop(unsafe_head.id()) {
+                state.import_unsafe_payload(envelope, &sync_request_tx);
+            }
+            self.signal_unsafe_import_backpressure(unsafe_head.number);
+
             tokio::select! {
                 _ = cancellation.cancelled() => {
                     warn!(target: "engine", "EngineActor received shutdown signal. Aborting engine query task.");
                     handle.abort();
+                    if let Some(conditional_tx_handle) = &conditional_tx_handle {
+                        conditional_tx_handle.abort();
+                    }
+                    if let Some(watcher) = &jwt_watcher {
+                        watcher.abort();
+                    }
+                    state.flush_safe_db().await;
                     return Ok(());
                 },
 
@@ -428,6 +967,13 @@ impl NodeActor for EngineActor {
                     warn!(target: "engine", "EngineActor received shutdown signal. Aborting engine query task.");
 
                     handle.abort();
+                    if let Some(conditional_tx_handle) = &conditional_tx_handle {
+                        conditional_tx_handle.abort();
+                    }
+                    if let Some(watcher) = &jwt_watcher {
+                        watcher.abort();
+                    }
+                    state.flush_safe_db().await;
 
                     return Ok(());
                 }
@@ -443,35 +989,23 @@ impl NodeActor for EngineActor {
                         .await?;
                 }
                 Some(res) = OptionFuture::from(self.build_request_rx.as_mut().map(|rx| rx.recv())), if self.build_request_rx.is_some() => {
-                    let Some((attributes, response_tx)) = res else {
+                    let Some(BuildRequest { attributes, payload_tx, commit_tx }) = res else {
                         error!(target: "engine", "Build request receiver closed unexpectedly while in sequencer mode");
                         cancellation.cancel();
                         return Err(EngineError::ChannelClosed);
                     };
-
-                    let task = EngineTask::Build(Box::new(BuildTask::new(
+                    let mut task = BuildTask::new(
                         state.client.clone(),
                         state.rollup.clone(),
                         attributes,
                         // The payload is not derived in this case.
                         false,
-                        Some(response_tx),
-                    )));
-                    state.engine.enqueue(task);
-                }
-                unsafe_block = self.unsafe_block_rx.recv() => {
-                    let Some(envelope) = unsafe_block else {
-                        error!(target: "engine", "Unsafe block receiver closed unexpectedly");
-                        cancellation.cancel();
-                        return Err(EngineError::ChannelClosed);
-                    };
-                    let task = EngineTask::Insert(Box::new(InsertTask::new(
-                        state.client.clone(),
-                        state.rollup.clone(),
-                        envelope,
-                        false, // The payload is not derived in this case. This is an unsafe block.
-                    )));
-                    state.engine.enqueue(task);
+                        Some(payload_tx),
+                    );
+                    if let Some(commit_tx) = commit_tx {
+                        task = task.with_commit_tx(commit_tx);
+                    }
+                    state.engine.enqueue(EngineTask::Build(Box::new(task)));
                 }
                 attributes = self.attributes_rx.recv() => {
                     let Some(attributes) = attributes else {
@@ -480,15 +1014,43 @@ impl NodeActor for EngineActor {
                         return Err(EngineError::ChannelClosed);
                     };
                     self.finalizer.enqueue_for_finalization(&attributes);
+                    if attributes.derived_from.is_some() {
+                        state.derived_from = attributes.derived_from;
+                    }
 
-                    let task = EngineTask::Consolidate(Box::new(ConsolidateTask::new(
-                        state.client.clone(),
-                        state.rollup.clone(),
-                        attributes,
-                        true,
-                    )));
+                    let task = EngineTask::Consolidate(Box::new(
+                        ConsolidateTask::new(
+                            state.client.clone(),
+                            state.rollup.clone(),
+                            attributes,
+                            true,
+                        )
+                        .with_max_reorg_depth(state.max_reorg_depth),
+                    ));
                     state.engine.enqueue(task);
                 }
+                // Unsafe payloads are received after derived attributes, so that a flood of
+                // gossiped payloads does not starve derivation.
+                unsafe_block = self.unsafe_block_rx.recv() => {
+                    let Some(envelope) = unsafe_block else {
+                        error!(target: "engine", "Unsafe block receiver closed unexpectedly");
+                        cancellation.cancel();
+                        return Err(EngineError::ChannelClosed);
+                    };
+
+                    // Queue every payload that has already been received, rather than one per
+                    // iteration. Stale payloads are dropped by the bounded queue.
+                    let unsafe_head = state.engine.state().sync_state.unsafe_head().block_info;
+                    let received = std::iter::once(envelope)
+                        .chain(std::iter::from_fn(|| self.unsafe_block_rx.try_recv().ok()));
+                    for envelope in received {
+                        // Retain the payload, so that the unsafe chain can be rebuilt if it is
+                        // reorged.
+                        state.unsafe_payloads.insert(envelope.clone());
+                        self.unsafe_imports.push(envelope, unsafe_head.number);
+                    }
+                    self.signal_unsafe_import_backpressure(unsafe_head.number);
+                }
                 msg = self.finalizer.new_finalized_block() => {
                     if let Err(err) = msg {
                         error!(target: "engine", ?err, "L1 finalized block receiver closed unexpectedly");
@@ -499,6 +1061,13 @@ impl NodeActor for EngineActor {
                     // chain.
                     self.finalizer.try_finalize_next(&mut state).await;
                 }
+                // Once unsafe blocks stop arriving, send the latest heads of the batched unsafe
+                // blocks to the EL.
+                _ = tokio::time::sleep(state.forkchoice_debounce.unwrap_or_default()), if state.engine.state().forkchoice_update_pending => {
+                    state.flush_forkchoice().await;
+                }
+                // Wake up to import the next queued unsafe payload.
+                _ = std::future::ready(()), if !self.unsafe_imports.is_empty() => {}
             }
         }
     }
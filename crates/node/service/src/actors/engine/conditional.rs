@@ -0,0 +1,163 @@
+//! Forwarding of the [`ConditionalTransaction`]s submitted to the sequencer to the transaction pool
+//! of the execution layer.
+
+use alloy_primitives::B256;
+use alloy_provider::{Provider, RootProvider};
+use alloy_rpc_types_eth::erc4337::TransactionConditional;
+use alloy_transport::TransportError;
+use jsonrpsee::types::ErrorCode;
+use kona_engine::EngineState;
+use kona_protocol::BlockInfo;
+use kona_rpc::{ConditionalTransaction, ConditionalTransactionError};
+use op_alloy_network::Optimism;
+use tokio::sync::{mpsc, watch};
+
+/// Forwards the [`ConditionalTransaction`]s received on the given channel to the transaction pool
+/// of the execution layer with `eth_sendRawTransactionConditional`, until the channel is closed.
+/// Each transaction is answered with the hash returned by the execution layer, or the error that
+/// it was rejected with.
+///
+/// Transactions whose conditions can no longer hold for the block built on top of the current
+/// unsafe head are rejected without being forwarded. The execution layer checks the conditions
+/// when the transaction is submitted, and again when building each block, so a transaction whose
+/// conditions no longer hold is evicted from the pool rather than failing the block being built.
+/// Transactions that are not included yet remain in the pool across failed block builds.
+pub(super) async fn forward_conditional_txs(
+    provider: RootProvider<Optimism>,
+    engine_state: watch::Receiver<EngineState>,
+    block_time: u64,
+    mut conditional_tx_rx: mpsc::Receiver<ConditionalTransaction>,
+) {
+    while let Some(ConditionalTransaction { raw, conditional, result }) =
+        conditional_tx_rx.recv().await
+    {
+        let unsafe_head = engine_state.borrow().sync_state.unsafe_head().block_info;
+        let response = if is_stale(&conditional, &unsafe_head, block_time) {
+            Err(ConditionalTransactionError::Stale)
+        } else {
+            provider
+                .raw_request::<_, B256>(
+                    "eth_sendRawTransactionConditional".into(),
+                    (raw, conditional),
+                )
+                .await
+                .map_err(rejection)
+        };
+
+        match &response {
+            Ok(hash) => {
+                debug!(target: "engine", %hash, "Forwarded conditional transaction to the execution layer");
+            }
+            Err(err) => {
+                warn!(target: "engine", ?err, "Rejected conditional transaction");
+            }
+        }
+        // The submitter may have stopped waiting for the response.
+        let _ = result.send(response);
+    }
+}
+
+/// Returns `true` if the conditions can no longer hold for the block built on top of the given
+/// unsafe head.
+fn is_stale(
+    conditional: &TransactionConditional,
+    unsafe_head: &BlockInfo,
+    block_time: u64,
+) -> bool {
+    let number = unsafe_head.number + 1;
+    let timestamp = unsafe_head.timestamp + block_time;
+    conditional.block_number_max.is_some_and(|max| number > max) ||
+        conditional.timestamp_max.is_some_and(|max| timestamp > max)
+}
+
+/// Converts the error of a `eth_sendRawTransactionConditional` request into a
+/// [`ConditionalTransactionError`], preserving the error responses of the execution layer.
+fn rejection(err: TransportError) -> ConditionalTransactionError {
+    match err.as_error_resp() {
+        Some(payload) => ConditionalTransactionError::Rejected {
+            code: i32::try_from(payload.code).unwrap_or(ErrorCode::InternalError.code()),
+            message: payload.message.to_string(),
+        },
+        None => ConditionalTransactionError::ExecutionLayer(err.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::Bytes;
+    use alloy_rpc_client::RpcClient;
+    use alloy_transport::mock::{Asserter, MockTransport};
+    use kona_engine::EngineSyncStateUpdate;
+    use kona_protocol::L2BlockInfo;
+    use tokio::sync::oneshot;
+
+    #[tokio::test]
+    async fn test_forward_conditional_txs() {
+        let asserter = Asserter::new();
+        let provider = RootProvider::<Optimism>::new(RpcClient::new(
+            MockTransport::new(asserter.clone()),
+            false,
+        ));
+        let mut state = EngineState::default();
+        state.sync_state = state.sync_state.apply_update(EngineSyncStateUpdate {
+            unsafe_head: Some(L2BlockInfo {
+                block_info: BlockInfo { number: 10, timestamp: 20, ..Default::default() },
+                ..Default::default()
+            }),
+            ..Default::default()
+        });
+        let (_state_tx, state_rx) = watch::channel(state);
+
+        // A rejected transaction does not stop the forwarding of the next ones.
+        asserter.push_failure_msg("conditional transactions are disabled");
+        asserter.push_success(&B256::repeat_byte(0x01));
+
+        let (conditional_tx_tx, conditional_tx_rx) = mpsc::channel(3);
+        let mut results = Vec::new();
+        // The first transaction cannot be included in the next block, and is not forwarded.
+        for block_number_max in [10, 11, 12] {
+            let conditional = TransactionConditional {
+                block_number_max: Some(block_number_max),
+                ..Default::default()
+            };
+            let (result, result_rx) = oneshot::channel();
+            conditional_tx_tx
+                .send(ConditionalTransaction {
+                    raw: Bytes::from_static(&[0x02]),
+                    conditional,
+                    result,
+                })
+                .await
+                .unwrap();
+            results.push(result_rx);
+        }
+        drop(conditional_tx_tx);
+
+        forward_conditional_txs(provider, state_rx, 2, conditional_tx_rx).await;
+        assert!(asserter.read_q().is_empty());
+
+        let mut results = results.into_iter();
+        assert_eq!(results.next().unwrap().await.unwrap(), Err(ConditionalTransactionError::Stale));
+        assert!(matches!(
+            results.next().unwrap().await.unwrap(),
+            Err(ConditionalTransactionError::Rejected { .. })
+        ));
+        assert_eq!(results.next().unwrap().await.unwrap(), Ok(B256::repeat_byte(0x01)));
+    }
+
+    #[test]
+    fn test_is_stale() {
+        let head = BlockInfo { number: 10, timestamp: 20, ..Default::default() };
+        let conditional = |block_number_max, timestamp_max| TransactionConditional {
+            block_number_max,
+            timestamp_max,
+            ..Default::default()
+        };
+
+        assert!(!is_stale(&conditional(None, None), &head, 2));
+        assert!(!is_stale(&conditional(Some(11), Some(22)), &head, 2));
+        assert!(is_stale(&conditional(Some(10), None), &head, 2));
+        assert!(is_stale(&conditional(None, Some(21)), &head, 2));
+    }
+}
@@ -2,7 +2,7 @@
 //!
 //! [`EngineActor`]: super::EngineActor
 
-use kona_engine::{EngineResetError, EngineTaskErrors};
+use kona_engine::{EngineAnchorError, EngineResetError, EngineTaskErrors, SafeDbError};
 
 /// An error from the [`EngineActor`].
 ///
@@ -18,4 +18,13 @@ pub enum EngineError {
     /// Engine task error.
     #[error(transparent)]
     EngineTask(#[from] EngineTaskErrors),
+    /// Safe head database error.
+    #[error(transparent)]
+    SafeDb(#[from] SafeDbError),
+    /// Engine anchor error.
+    #[error(transparent)]
+    Anchor(#[from] EngineAnchorError),
+    /// A blocking task failed to complete.
+    #[error("blocking task failed: {0}")]
+    BlockingTask(#[from] tokio::task::JoinError),
 }
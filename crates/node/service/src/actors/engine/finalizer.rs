@@ -65,21 +65,95 @@ impl L2Finalizer {
             return;
         };
 
-        // Find the highest safe L2 block that is contained within the finalized chain,
-        // that the finalizer is aware of.
-        let highest_safe = self.awaiting_finalization.range(..=new_finalized_l1.number).next_back();
+        // Safe heads derived before the node started, or before the last reset, are only known to
+        // the safe head database, if enabled.
+        let sync_state = engine_state.engine.state().sync_state;
+        let highest_recorded = engine_state
+            .safe_db
+            .as_ref()
+            .and_then(|safe_db| {
+                safe_db
+                    .safe_head_at_l1_block(new_finalized_l1.number)
+                    .inspect_err(|err| {
+                        warn!(target: "engine", ?err, "Failed to query the safe head database");
+                    })
+                    .ok()
+                    .flatten()
+            })
+            .map(|entry| entry.safe_head.number);
 
         // If the highest safe block is found, enqueue a finalization task and drain the
         // queue of all L1 blocks not contained in the finalized L1 chain.
-        if let Some((_, highest_safe_number)) = highest_safe {
-            let task = EngineTask::Finalize(Box::new(FinalizeTask::new(
-                engine_state.client.clone(),
-                engine_state.rollup.clone(),
-                *highest_safe_number,
-            )));
-            engine_state.engine.enqueue(task);
+        if let Some(highest_safe_number) = self.highest_finalizable(
+            new_finalized_l1.number,
+            highest_recorded,
+            sync_state.safe_head().block_info.number,
+        ) {
+            if highest_safe_number > sync_state.finalized_head().block_info.number {
+                let task = EngineTask::Finalize(Box::new(FinalizeTask::new(
+                    engine_state.client.clone(),
+                    engine_state.rollup.clone(),
+                    highest_safe_number,
+                )));
+                engine_state.engine.enqueue(task);
+            }
 
             self.awaiting_finalization.retain(|&number, _| number > new_finalized_l1.number);
         }
     }
+
+    /// Returns the highest safe L2 block contained within the finalized L1 chain up to
+    /// `finalized_l1`, if any.
+    ///
+    /// The candidates are the blocks awaiting finalization and the `recorded` safe head of the
+    /// safe head database. A recorded safe head ahead of the current `safe_head` is ignored, since
+    /// only safe blocks can be finalized.
+    fn highest_finalizable(
+        &self,
+        finalized_l1: L1BlockNumber,
+        recorded: Option<L2BlockNumber>,
+        safe_head: L2BlockNumber,
+    ) -> Option<L2BlockNumber> {
+        let highest_awaiting = self
+            .awaiting_finalization
+            .range(..=finalized_l1)
+            .next_back()
+            .map(|(_, l2_number)| *l2_number);
+        let highest_recorded = recorded.filter(|&number| number <= safe_head);
+        highest_awaiting.max(highest_recorded)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn finalizer(awaiting: &[(L1BlockNumber, L2BlockNumber)]) -> L2Finalizer {
+        let (_, rx) = watch::channel(None);
+        let mut finalizer = L2Finalizer::new(rx);
+        finalizer.awaiting_finalization.extend(awaiting.iter().copied());
+        finalizer
+    }
+
+    #[test]
+    fn test_highest_finalizable_awaiting() {
+        let finalizer = finalizer(&[(10, 100), (11, 105), (12, 110)]);
+
+        assert_eq!(finalizer.highest_finalizable(9, None, 110), None);
+        assert_eq!(finalizer.highest_finalizable(11, None, 110), Some(105));
+        assert_eq!(finalizer.highest_finalizable(20, None, 110), Some(110));
+    }
+
+    #[test]
+    fn test_highest_finalizable_recorded() {
+        let finalizer = finalizer(&[(11, 105)]);
+
+        // A recorded safe head from before a restart finalizes beyond the awaited blocks.
+        assert_eq!(finalizer.highest_finalizable(9, Some(90), 110), Some(90));
+        assert_eq!(finalizer.highest_finalizable(12, Some(108), 110), Some(108));
+        assert_eq!(finalizer.highest_finalizable(12, Some(102), 110), Some(105));
+        // A recorded safe head ahead of the current safe head is ignored.
+        assert_eq!(finalizer.highest_finalizable(12, Some(120), 110), Some(105));
+        assert_eq!(finalizer.highest_finalizable(9, Some(120), 110), None);
+    }
 }
@@ -0,0 +1,202 @@
+//! The [`UnsafeImportQueue`], which orders the unsafe payloads received from the network for
+//! insertion into the unsafe chain.
+
+use alloy_eips::BlockNumHash;
+use op_alloy_rpc_types_engine::OpExecutionPayloadEnvelope;
+use std::collections::VecDeque;
+
+/// The default maximum number of payloads held by the [`UnsafeImportQueue`].
+pub(super) const DEFAULT_UNSAFE_IMPORT_QUEUE_CAPACITY: usize = 256;
+
+/// The default number of blocks below the unsafe head beyond which queued payloads are dropped.
+pub(super) const DEFAULT_UNSAFE_IMPORT_MAX_DEPTH: u64 = 64;
+
+/// A bounded queue of the unsafe payloads received from the network that are yet to be imported.
+///
+/// Payloads are imported one at a time, so that a flood of gossiped payloads while catching up
+/// does not starve derivation. The payload that extends the unsafe head is imported first, then
+/// the payloads closest to it. Payloads more than the maximum depth below the unsafe head are
+/// stale, and dropped. When the queue is full, the payload furthest from the unsafe head is
+/// evicted.
+///
+/// Once the queue is half full, it is congested, and the gossip layer is signaled to stop
+/// forwarding payloads until it drains.
+#[derive(Debug)]
+pub(super) struct UnsafeImportQueue {
+    /// The queued payloads, in order of receipt.
+    payloads: VecDeque<OpExecutionPayloadEnvelope>,
+    /// The maximum number of queued payloads.
+    capacity: usize,
+    /// The number of blocks below the unsafe head beyond which payloads are dropped.
+    max_depth: u64,
+}
+
+impl Default for UnsafeImportQueue {
+    fn default() -> Self {
+        Self::new(DEFAULT_UNSAFE_IMPORT_QUEUE_CAPACITY, DEFAULT_UNSAFE_IMPORT_MAX_DEPTH)
+    }
+}
+
+impl UnsafeImportQueue {
+    /// Creates a new, empty [`UnsafeImportQueue`] with the given capacity and maximum depth.
+    pub(super) const fn new(capacity: usize, max_depth: u64) -> Self {
+        Self { payloads: VecDeque::new(), capacity, max_depth }
+    }
+
+    /// Returns the number of queued payloads.
+    pub(super) fn len(&self) -> usize {
+        self.payloads.len()
+    }
+
+    /// Returns `true` if no payloads are queued.
+    pub(super) fn is_empty(&self) -> bool {
+        self.payloads.is_empty()
+    }
+
+    /// Returns `true` if the queue is at least half full, and the gossip layer should stop
+    /// forwarding payloads.
+    pub(super) fn is_congested(&self) -> bool {
+        self.payloads.len() * 2 >= self.capacity.max(1)
+    }
+
+    /// Adds a payload to the queue, given the number of the unsafe head. Stale payloads and
+    /// payloads that are already queued are ignored. If the queue is full, the payload furthest
+    /// from the unsafe head is evicted, which may be the given payload.
+    pub(super) fn push(&mut self, envelope: OpExecutionPayloadEnvelope, unsafe_head: u64) {
+        let hash = envelope.execution_payload.block_hash();
+        if envelope.execution_payload.block_number() < self.min_number(unsafe_head) ||
+            self.payloads.iter().any(|p| p.execution_payload.block_hash() == hash)
+        {
+            return;
+        }
+
+        self.payloads.push_back(envelope);
+        if self.payloads.len() > self.capacity {
+            let furthest = self
+                .payloads
+                .iter()
+                .enumerate()
+                .max_by_key(|(_, p)| Self::distance(p, unsafe_head))
+                .map(|(index, _)| index);
+            if let Some(index) = furthest {
+                self.payloads.remove(index);
+            }
+        }
+    }
+
+    /// Takes the next payload to import, given the unsafe head. This is the payload that extends
+    /// the unsafe head if there is one, or else the payload closest to it, in order of receipt.
+    /// Stale payloads are dropped.
+    pub(super) fn pop(&mut self, unsafe_head: BlockNumHash) -> Option<OpExecutionPayloadEnvelope> {
+        let min_number = self.min_number(unsafe_head.number);
+        self.payloads.retain(|p| p.execution_payload.block_number() >= min_number);
+
+        let index = self
+            .payloads
+            .iter()
+            .position(|p| p.execution_payload.parent_hash() == unsafe_head.hash)
+            .or_else(|| {
+                self.payloads
+                    .iter()
+                    .enumerate()
+                    .min_by_key(|(_, p)| Self::distance(p, unsafe_head.number))
+                    .map(|(index, _)| index)
+            })?;
+        self.payloads.remove(index)
+    }
+
+    /// Returns the lowest block number of the payloads that are not stale, which are at most the
+    /// maximum depth below the unsafe head.
+    const fn min_number(&self, unsafe_head: u64) -> u64 {
+        unsafe_head.saturating_sub(self.max_depth)
+    }
+
+    /// Returns the distance of the payload from the block that extends the unsafe head.
+    fn distance(envelope: &OpExecutionPayloadEnvelope, unsafe_head: u64) -> u64 {
+        envelope.execution_payload.block_number().abs_diff(unsafe_head + 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::B256;
+    use alloy_rpc_types_engine::ExecutionPayloadV1;
+    use arbitrary::Arbitrary;
+    use op_alloy_rpc_types_engine::OpExecutionPayload;
+
+    fn payload(number: u64, parent: u8, hash: u8) -> OpExecutionPayloadEnvelope {
+        let mut payload =
+            ExecutionPayloadV1::arbitrary(&mut arbitrary::Unstructured::new(&[])).unwrap();
+        payload.block_number = number;
+        payload.parent_hash = B256::with_last_byte(parent);
+        payload.block_hash = B256::with_last_byte(hash);
+        OpExecutionPayloadEnvelope {
+            execution_payload: OpExecutionPayload::V1(payload),
+            parent_beacon_block_root: None,
+        }
+    }
+
+    fn head(number: u64, hash: u8) -> BlockNumHash {
+        BlockNumHash::new(number, B256::with_last_byte(hash))
+    }
+
+    fn pop_number(queue: &mut UnsafeImportQueue, unsafe_head: BlockNumHash) -> Option<u64> {
+        queue.pop(unsafe_head).map(|p| p.execution_payload.block_number())
+    }
+
+    #[test]
+    fn test_import_queue_prefers_child_of_unsafe_head() {
+        let mut queue = UnsafeImportQueue::default();
+        queue.push(payload(20, 19, 20), 10);
+        queue.push(payload(12, 11, 12), 10);
+        queue.push(payload(11, 9, 30), 10);
+        queue.push(payload(11, 10, 11), 10);
+        queue.push(payload(11, 10, 11), 10);
+        assert_eq!(queue.len(), 4);
+
+        // The child of the unsafe head is imported before the competing block at the same height.
+        let child = queue.pop(head(10, 10)).unwrap();
+        assert_eq!(child.execution_payload.block_hash(), B256::with_last_byte(11));
+        assert_eq!(pop_number(&mut queue, head(11, 11)), Some(12));
+        assert_eq!(pop_number(&mut queue, head(12, 12)), Some(11));
+        assert_eq!(pop_number(&mut queue, head(12, 12)), Some(20));
+        assert!(queue.pop(head(12, 12)).is_none());
+    }
+
+    #[test]
+    fn test_import_queue_drops_stale_payloads() {
+        let mut queue = UnsafeImportQueue::new(8, 2);
+        queue.push(payload(7, 6, 7), 10);
+        queue.push(payload(8, 7, 8), 10);
+        queue.push(payload(9, 8, 9), 10);
+        assert_eq!(queue.len(), 2);
+
+        // The unsafe head advanced, so the remaining payloads became stale.
+        assert!(queue.pop(head(20, 20)).is_none());
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn test_import_queue_evicts_furthest_payload() {
+        let mut queue = UnsafeImportQueue::new(4, 64);
+        for number in [11, 30, 12, 13] {
+            queue.push(payload(number, number as u8 - 1, number as u8), 10);
+        }
+        assert!(queue.is_congested());
+
+        queue.push(payload(14, 13, 14), 10);
+        queue.push(payload(40, 39, 40), 10);
+        assert_eq!(queue.len(), 4);
+
+        let mut numbers = Vec::new();
+        let mut unsafe_head = head(10, 10);
+        while let Some(envelope) = queue.pop(unsafe_head) {
+            let number = envelope.execution_payload.block_number();
+            unsafe_head = head(number, number as u8);
+            numbers.push(number);
+        }
+        assert_eq!(numbers, vec![11, 12, 13, 14]);
+        assert!(!queue.is_congested());
+    }
+}
@@ -1,10 +1,16 @@
 //! The [`EngineActor`] and its components.
 
 mod actor;
-pub use actor::{EngineActor, EngineBuilder, EngineContext, EngineInboundData};
+pub use actor::{BuildRequest, EngineActor, EngineBuilder, EngineContext, EngineInboundData};
 
 mod error;
 pub use error::EngineError;
 
+mod conditional;
+
+mod import_queue;
+
+mod unsafe_buffer;
+
 mod finalizer;
 pub use finalizer::L2Finalizer;
@@ -0,0 +1,179 @@
+//! The [`UnsafePayloadBuffer`], which retains recently gossiped unsafe payloads so that the unsafe
+//! chain can be rebuilt after a reorg.
+
+use alloy_primitives::B256;
+use op_alloy_rpc_types_engine::OpExecutionPayloadEnvelope;
+use std::collections::{HashSet, VecDeque};
+
+/// The default maximum number of payloads held by the [`UnsafePayloadBuffer`].
+pub(super) const DEFAULT_UNSAFE_PAYLOAD_BUFFER_CAPACITY: usize = 256;
+
+/// A buffer of recently gossiped [`OpExecutionPayloadEnvelope`]s that have not yet been made safe,
+/// in order of receipt.
+///
+/// Payloads that do not extend the unsafe head when they are received, or that are reorged out of
+/// the unsafe chain, are kept in the buffer so that they can be inserted once their parent becomes
+/// the unsafe head. Payloads at or below the safe head are pruned, payloads that can no longer
+/// extend the unsafe head are purged on reset, and when the buffer is full, the oldest payload is
+/// evicted.
+#[derive(Debug)]
+pub(super) struct UnsafePayloadBuffer {
+    /// The buffered payloads, in order of receipt.
+    payloads: VecDeque<OpExecutionPayloadEnvelope>,
+    /// The maximum number of buffered payloads.
+    capacity: usize,
+}
+
+impl Default for UnsafePayloadBuffer {
+    fn default() -> Self {
+        Self::new(DEFAULT_UNSAFE_PAYLOAD_BUFFER_CAPACITY)
+    }
+}
+
+impl UnsafePayloadBuffer {
+    /// Creates a new, empty [`UnsafePayloadBuffer`] with the given capacity.
+    pub(super) const fn new(capacity: usize) -> Self {
+        Self { payloads: VecDeque::new(), capacity }
+    }
+
+    /// Returns the maximum number of buffered payloads.
+    pub(super) const fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns `true` if the payload with the given block hash is buffered.
+    pub(super) fn contains(&self, hash: B256) -> bool {
+        self.payloads.iter().any(|p| p.execution_payload.block_hash() == hash)
+    }
+
+    /// Adds a payload to the buffer, evicting the oldest payload if the buffer is full. Payloads
+    /// that are already buffered are ignored.
+    pub(super) fn insert(&mut self, envelope: OpExecutionPayloadEnvelope) {
+        let hash = envelope.execution_payload.block_hash();
+        if self.capacity == 0 || self.contains(hash) {
+            return;
+        }
+        if self.payloads.len() >= self.capacity {
+            self.payloads.pop_front();
+        }
+        self.payloads.push_back(envelope);
+    }
+
+    /// Takes the most recently received buffered payload whose parent is the block with the given
+    /// hash, if any.
+    pub(super) fn take_child_of(
+        &mut self,
+        parent_hash: B256,
+    ) -> Option<OpExecutionPayloadEnvelope> {
+        let index =
+            self.payloads.iter().rposition(|p| p.execution_payload.parent_hash() == parent_hash)?;
+        self.payloads.remove(index)
+    }
+
+    /// Returns the lowest buffered payload above the given block number, which is the next queued
+    /// payload to be inserted into the unsafe chain, if any.
+    pub(super) fn first_above(&self, number: u64) -> Option<&OpExecutionPayloadEnvelope> {
+        self.payloads
+            .iter()
+            .filter(|p| p.execution_payload.block_number() > number)
+            .min_by_key(|p| p.execution_payload.block_number())
+    }
+
+    /// Removes the payloads at or below the given safe block number, which can no longer be
+    /// inserted into the unsafe chain.
+    pub(super) fn prune(&mut self, safe_number: u64) {
+        self.payloads.retain(|p| p.execution_payload.block_number() > safe_number);
+    }
+
+    /// Removes the payloads that can no longer extend the unsafe head with the given number and
+    /// hash, after a reset: the payloads at or below the unsafe head, the payloads right above it
+    /// that are not its children, and the buffered descendants of any of them. Payloads whose
+    /// parent is missing from the buffer further above the unsafe head are kept, as their
+    /// ancestors may still be received.
+    pub(super) fn purge_non_canonical(&mut self, unsafe_number: u64, unsafe_hash: B256) {
+        let mut by_number = self.payloads.iter().collect::<Vec<_>>();
+        by_number.sort_by_key(|p| p.execution_payload.block_number());
+
+        let mut purged = HashSet::new();
+        for payload in by_number {
+            let number = payload.execution_payload.block_number();
+            let parent_hash = payload.execution_payload.parent_hash();
+            let non_canonical = number <= unsafe_number ||
+                (number == unsafe_number + 1 && parent_hash != unsafe_hash) ||
+                purged.contains(&parent_hash);
+            if non_canonical {
+                purged.insert(payload.execution_payload.block_hash());
+            }
+        }
+        self.payloads.retain(|p| !purged.contains(&p.execution_payload.block_hash()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_rpc_types_engine::ExecutionPayloadV1;
+    use arbitrary::Arbitrary;
+    use op_alloy_rpc_types_engine::OpExecutionPayload;
+
+    fn payload(number: u64, parent: u8, hash: u8) -> OpExecutionPayloadEnvelope {
+        let mut payload =
+            ExecutionPayloadV1::arbitrary(&mut arbitrary::Unstructured::new(&[])).unwrap();
+        payload.block_number = number;
+        payload.parent_hash = B256::with_last_byte(parent);
+        payload.block_hash = B256::with_last_byte(hash);
+        OpExecutionPayloadEnvelope {
+            execution_payload: OpExecutionPayload::V1(payload),
+            parent_beacon_block_root: None,
+        }
+    }
+
+    #[test]
+    fn test_unsafe_buffer_take_child_of() {
+        let mut buffer = UnsafePayloadBuffer::default();
+        buffer.insert(payload(2, 1, 2));
+        buffer.insert(payload(2, 1, 2));
+        buffer.insert(payload(3, 2, 3));
+        assert_eq!(buffer.payloads.len(), 2);
+
+        let child = buffer.take_child_of(B256::with_last_byte(2)).unwrap();
+        assert_eq!(child.execution_payload.block_number(), 3);
+        assert!(buffer.take_child_of(B256::with_last_byte(2)).is_none());
+        assert_eq!(buffer.payloads.len(), 1);
+    }
+
+    #[test]
+    fn test_unsafe_buffer_prune_and_evict() {
+        let mut buffer = UnsafePayloadBuffer::new(2);
+        buffer.insert(payload(1, 0, 1));
+        buffer.insert(payload(2, 1, 2));
+        buffer.insert(payload(3, 2, 3));
+        assert!(buffer.take_child_of(B256::with_last_byte(0)).is_none());
+
+        assert_eq!(buffer.first_above(1).unwrap().execution_payload.block_number(), 2);
+        assert!(buffer.first_above(3).is_none());
+
+        buffer.prune(2);
+        assert_eq!(buffer.payloads.len(), 1);
+        assert_eq!(buffer.payloads[0].execution_payload.block_number(), 3);
+    }
+
+    #[test]
+    fn test_unsafe_buffer_purge_non_canonical() {
+        let mut buffer = UnsafePayloadBuffer::default();
+        // The unsafe head is block 2 with hash 2, and 0x12 was reorged out at the same height.
+        buffer.insert(payload(2, 1, 2));
+        buffer.insert(payload(2, 1, 0x12));
+        buffer.insert(payload(3, 0x12, 0x13));
+        buffer.insert(payload(4, 0x13, 0x14));
+        // The canonical child of the unsafe head, and a payload past a gap.
+        buffer.insert(payload(3, 2, 3));
+        buffer.insert(payload(6, 5, 6));
+
+        buffer.purge_non_canonical(2, B256::with_last_byte(2));
+        let mut remaining =
+            buffer.payloads.iter().map(|p| p.execution_payload.block_hash()).collect::<Vec<_>>();
+        remaining.sort();
+        assert_eq!(remaining, vec![B256::with_last_byte(3), B256::with_last_byte(6)]);
+    }
+}
@@ -1,20 +1,24 @@
 //! [`NodeActor`] implementation for an L1 chain watcher that polls for L1 block updates over HTTP
-//! RPC.
+//! RPC, or optionally subscribes to them over a WS or IPC RPC.
 
-use crate::{NodeActor, actors::CancellableContext};
+use crate::{Metrics, NodeActor, actors::CancellableContext};
 use alloy_eips::{BlockId, BlockNumberOrTag};
 use alloy_primitives::{Address, B256};
 use alloy_provider::{Provider, RootProvider};
+use alloy_pubsub::Subscription;
 use alloy_rpc_client::PollerBuilder;
-use alloy_rpc_types_eth::{Block, Log};
+use alloy_rpc_types_eth::{Block, Filter, Header, Log};
 use alloy_transport::TransportError;
 use async_stream::stream;
 use async_trait::async_trait;
-use futures::{Stream, StreamExt};
+use futures::{Stream, StreamExt, stream};
 use kona_genesis::{RollupConfig, SystemConfigLog, SystemConfigUpdate, UnsafeBlockSignerUpdate};
 use kona_protocol::BlockInfo;
+use kona_providers_alloy::{
+    AlloyChainProvider, L1PrefetchCache, L1Prefetcher, RedundantBeaconClient,
+};
 use kona_rpc::{L1State, L1WatcherQueries};
-use std::{sync::Arc, time::Duration};
+use std::{num::NonZeroUsize, pin::Pin, sync::Arc, time::Duration};
 use thiserror::Error;
 use tokio::{
     select,
@@ -42,6 +46,41 @@ pub struct L1WatcherRpcState {
     pub rollup: Arc<RollupConfig>,
     /// The L1 provider.
     pub l1_provider: RootProvider,
+    /// The WS or IPC endpoint of the L1 execution client, used to subscribe to new heads and
+    /// system config logs. If unset, or if the subscriptions end, the L1 watcher falls back to
+    /// polling the L1 provider.
+    pub l1_subscription_rpc: Option<String>,
+    /// The number of confirmations after which an L1 block is considered finalized, for L1 chains
+    /// without a finality gadget. If unset, the L1 `finalized` block tag is used.
+    pub l1_finality_depth: Option<u64>,
+    /// The L1 beacon API, used to prefetch blobs.
+    pub l1_beacon: RedundantBeaconClient,
+    /// The prefetching of L1 data ahead of the derivation cursor. If unset, L1 data is only
+    /// fetched on demand by the derivation pipeline.
+    pub l1_prefetch: Option<L1PrefetchConfig>,
+}
+
+/// The configuration of the prefetching of L1 data ahead of the derivation cursor.
+///
+/// The L1 watcher prefetches the blocks, receipts and batcher blobs of the L1 blocks following
+/// the L1 origin of the derivation pipeline into the [`L1PrefetchCache`], which is shared with the
+/// providers of the pipeline.
+#[derive(Debug, Clone)]
+pub struct L1PrefetchConfig {
+    /// The number of L1 blocks prefetched ahead of the derivation cursor.
+    pub lookahead: u64,
+    /// The cache shared with the providers of the derivation pipeline.
+    pub cache: L1PrefetchCache,
+}
+
+impl L1PrefetchConfig {
+    /// Creates a new [`L1PrefetchConfig`] with the given lookahead, and a cache large enough to
+    /// hold two lookahead windows.
+    pub fn new(lookahead: u64) -> Self {
+        let capacity =
+            NonZeroUsize::new((lookahead as usize).saturating_mul(2)).unwrap_or(NonZeroUsize::MIN);
+        Self { lookahead, cache: L1PrefetchCache::new(capacity) }
+    }
 }
 
 impl L1WatcherRpcState {
@@ -55,16 +94,156 @@ impl L1WatcherRpcState {
         Ok(logs)
     }
 
+    /// Subscribes to new heads and system config logs over the L1 subscription endpoint, if one
+    /// is configured. The returned provider must be kept alive for the subscriptions to remain
+    /// open.
+    async fn subscribe(
+        &self,
+    ) -> (Option<RootProvider>, Option<Subscription<Header>>, Option<Subscription<Log>>) {
+        let Some(endpoint) = self.l1_subscription_rpc.as_ref() else {
+            return (None, None, None);
+        };
+
+        let provider = match RootProvider::connect(endpoint).await {
+            Ok(provider) => provider,
+            Err(e) => {
+                warn!(target: "l1_watcher", error = ?e, "Failed to connect to the L1 subscription endpoint, polling instead");
+                return (None, None, None);
+            }
+        };
+
+        let heads = provider
+            .subscribe_blocks()
+            .await
+            .inspect_err(|e| {
+                warn!(target: "l1_watcher", error = ?e, "Failed to subscribe to L1 heads, polling instead");
+            })
+            .ok();
+        let logs = provider
+            .subscribe_logs(&Filter::new().address(self.rollup.l1_system_config_address))
+            .await
+            .inspect_err(|e| {
+                warn!(target: "l1_watcher", error = ?e, "Failed to subscribe to system config logs, fetching per block instead");
+            })
+            .ok();
+
+        (Some(provider), heads, logs)
+    }
+
+    /// Handles a system config log, sending unsafe block signer updates to the block signer
+    /// sender.
+    async fn handle_system_config_log(
+        &self,
+        log: Log,
+        ecotone_active: bool,
+        block_signer_sender: &mpsc::Sender<Address>,
+    ) {
+        if log.address() != self.rollup.l1_system_config_address {
+            return; // Skip logs not related to the system config.
+        }
+
+        let sys_cfg_log = SystemConfigLog::new(log.into(), ecotone_active);
+        if let Ok(SystemConfigUpdate::UnsafeBlockSigner(UnsafeBlockSignerUpdate {
+            unsafe_block_signer,
+        })) = sys_cfg_log.build()
+        {
+            info!(
+                target: "l1_watcher",
+                "Unsafe block signer update: {unsafe_block_signer}"
+            );
+            if let Err(e) = block_signer_sender.send(unsafe_block_signer).await {
+                error!(
+                    target: "l1_watcher",
+                    "Error sending unsafe block signer update: {e}"
+                );
+            }
+        }
+    }
+
+    /// Returns the L1 block that is finalized by the given head block, if finality is determined
+    /// by confirmation depth and the block is ahead of the current finalized block.
+    async fn finalized_at_depth(
+        &self,
+        head: BlockInfo,
+        current_finalized: Option<BlockInfo>,
+    ) -> Result<Option<BlockInfo>, L1WatcherRpcError<BlockInfo>> {
+        let Some(depth) = self.l1_finality_depth else {
+            return Ok(None);
+        };
+        let Some(number) =
+            finalized_number_at_depth(depth, head.number, current_finalized.map(|b| b.number))
+        else {
+            return Ok(None);
+        };
+
+        let block = self
+            .l1_provider
+            .get_block_by_number(number.into())
+            .await?
+            .ok_or(L1WatcherRpcError::L1BlockNotFound(BlockId::number(number)))?;
+        Ok(Some(block.into_consensus().into()))
+    }
+
+    /// Spins up a task prefetching the L1 blocks following the derivation cursor, up to the L1
+    /// head, if prefetching is enabled.
+    fn start_prefetcher(
+        &self,
+        mut head_updates_recv: watch::Receiver<Option<BlockInfo>>,
+    ) -> Option<JoinHandle<()>> {
+        let L1PrefetchConfig { lookahead, cache } = self.l1_prefetch.clone()?;
+        let l1_provider = self.l1_provider.clone();
+        let l1_beacon = self.l1_beacon.clone();
+        let batch_inbox_address = self.rollup.batch_inbox_address;
+
+        Some(tokio::spawn(async move {
+            let mut cursor_recv = cache.subscribe_cursor();
+            let prefetcher = L1Prefetcher::init(
+                AlloyChainProvider::new(l1_provider, 1),
+                l1_beacon,
+                batch_inbox_address,
+                cache,
+                lookahead,
+            )
+            .await;
+            let mut prefetcher = match prefetcher {
+                Ok(prefetcher) => prefetcher,
+                Err(e) => {
+                    // The pipeline fetches the L1 data itself.
+                    warn!(target: "l1_watcher", error = ?e, "Failed to start the L1 prefetcher");
+                    return;
+                }
+            };
+
+            loop {
+                let cursor = *cursor_recv.borrow_and_update();
+                let head = (*head_updates_recv.borrow_and_update()).map(|head| head.number);
+                if let (Some(cursor), Some(head)) = (cursor, head) {
+                    if let Err(e) = prefetcher.prefetch(cursor, head).await {
+                        // The pipeline fetches the missing data itself.
+                        debug!(target: "l1_watcher", error = ?e, cursor, "Failed to prefetch L1 data");
+                    }
+                }
+
+                select! {
+                    res = cursor_recv.changed() => if res.is_err() { return },
+                    res = head_updates_recv.changed() => if res.is_err() { return },
+                }
+            }
+        }))
+    }
+
     /// Spins up a task to process inbound queries.
     fn start_query_processor(
         &self,
         mut inbound_queries: tokio::sync::mpsc::Receiver<L1WatcherQueries>,
         head_updates_recv: watch::Receiver<Option<BlockInfo>>,
+        finalized_updates_recv: watch::Receiver<Option<BlockInfo>>,
     ) -> JoinHandle<()> {
         // Start the inbound query processor in a separate task to avoid blocking the main task.
         // We can cheaply clone the l1 provider here because it is an Arc.
         let l1_provider = self.l1_provider.clone();
         let rollup_config = self.rollup.clone();
+        let finality_by_depth = self.l1_finality_depth.is_some();
 
         tokio::spawn(async move {
             while let Some(query) = inbound_queries.recv().await {
@@ -84,12 +263,16 @@ impl L1WatcherRpcState {
                                     None
                                 }}.map(|block| block.into_consensus().into());
 
-                        let finalized_l1 = match l1_provider.get_block(BlockId::finalized()).await {
+                        let finalized_l1 = if finality_by_depth {
+                            *finalized_updates_recv.borrow()
+                        } else {
+                            match l1_provider.get_block(BlockId::finalized()).await {
                                 Ok(block) => block,
                                 Err(e) => {
                                     warn!(target: "l1_watcher", error = ?e, "failed to query l1 provider for latest finalized block");
                                     None
-                                }}.map(|block| block.into_consensus().into());
+                                }}.map(|block| block.into_consensus().into())
+                        };
 
                         let safe_l1 = match l1_provider.get_block(BlockId::safe()).await {
                                 Ok(block) => block,
@@ -167,21 +350,39 @@ impl NodeActor for L1WatcherRpc {
         mut self,
         L1WatcherRpcContext { latest_head, latest_finalized, block_signer_sender, cancellation }: Self::OutboundData,
     ) -> Result<(), Self::Error> {
-        let mut head_stream = BlockStream::new(
-            &self.state.l1_provider,
-            BlockNumberOrTag::Latest,
-            Duration::from_secs(13),
-        )
-        .into_stream();
-        let mut finalized_stream = BlockStream::new(
-            &self.state.l1_provider,
-            BlockNumberOrTag::Finalized,
-            Duration::from_secs(60),
-        )
-        .into_stream();
-
-        let inbound_query_processor =
-            self.state.start_query_processor(self.inbound_queries, latest_head.subscribe());
+        let (_subscription_provider, head_subscription, log_subscription) =
+            self.state.subscribe().await;
+
+        let mut head_stream = head_stream(self.state.l1_provider.clone(), head_subscription);
+        // If finality is determined by confirmation depth, finalized blocks are derived from new
+        // heads instead.
+        let mut finalized_stream: Pin<Box<dyn Stream<Item = BlockInfo> + Send>> =
+            if self.state.l1_finality_depth.is_some() {
+                Box::pin(stream::pending())
+            } else {
+                Box::pin(
+                    BlockStream::new(
+                        self.state.l1_provider.clone(),
+                        BlockNumberOrTag::Finalized,
+                        Duration::from_secs(60),
+                    )
+                    .into_stream(),
+                )
+            };
+
+        // If system config logs are not subscribed to, they are fetched for each new head.
+        let mut logs_subscribed = log_subscription.is_some();
+        let mut log_stream: Pin<Box<dyn Stream<Item = Log> + Send>> = match log_subscription {
+            Some(subscription) => Box::pin(subscription.into_stream()),
+            None => Box::pin(stream::pending()),
+        };
+
+        let inbound_query_processor = self.state.start_query_processor(
+            self.inbound_queries,
+            latest_head.subscribe(),
+            latest_finalized.subscribe(),
+        );
+        let prefetcher = self.state.start_prefetcher(latest_head.subscribe());
 
         // Start the main processing loop.
         loop {
@@ -193,8 +394,11 @@ impl NodeActor for L1WatcherRpc {
                         "Received shutdown signal. Exiting L1 watcher task."
                     );
 
-                    // Kill the inbound query processor.
+                    // Kill the inbound query processor and the prefetcher.
                     inbound_query_processor.abort();
+                    if let Some(prefetcher) = &prefetcher {
+                        prefetcher.abort();
+                    }
 
                     return Ok(());
                 },
@@ -205,34 +409,44 @@ impl NodeActor for L1WatcherRpc {
                     Some(head_block_info) => {
                         // Send the head update event to all consumers.
                         latest_head.send_replace(Some(head_block_info));
+                        kona_macros::set!(gauge, Metrics::L1_HEAD, head_block_info.number as f64);
+
+                        let current_finalized = *latest_finalized.borrow();
+                        if let Some(finalized) = self.state.finalized_at_depth(head_block_info, current_finalized).await? {
+                            latest_finalized.send_replace(Some(finalized));
+                        }
 
                         // For each log, attempt to construct a `SystemConfigLog`.
                         // Build the `SystemConfigUpdate` from the log.
                         // If the update is an Unsafe block signer update, send the address
-                        // to the block signer sender.
-                        let logs = self.state.fetch_logs(head_block_info.hash).await?;
-                        let ecotone_active = self.state.rollup.is_ecotone_active(head_block_info.timestamp);
-                        for log in logs {
-                            if log.address() != self.state.rollup.l1_system_config_address {
-                                continue; // Skip logs not related to the system config.
-                            }
-
-                            let sys_cfg_log = SystemConfigLog::new(log.into(), ecotone_active);
-                            if let Ok(SystemConfigUpdate::UnsafeBlockSigner(UnsafeBlockSignerUpdate { unsafe_block_signer })) = sys_cfg_log.build() {
-                                info!(
-                                    target: "l1_watcher",
-                                    "Unsafe block signer update: {unsafe_block_signer}"
-                                );
-                                if let Err(e) = block_signer_sender.send(unsafe_block_signer).await {
-                                    error!(
-                                        target: "l1_watcher",
-                                        "Error sending unsafe block signer update: {e}"
-                                    );
-                                }
+                        // to the block signer sender. Subscribed logs are handled as they arrive.
+                        if !logs_subscribed {
+                            let logs = self.state.fetch_logs(head_block_info.hash).await?;
+                            let ecotone_active = self.state.rollup.is_ecotone_active(head_block_info.timestamp);
+                            for log in logs {
+                                self.state.handle_system_config_log(log, ecotone_active, &block_signer_sender).await;
                             }
                         }
                     },
                 },
+                new_log = log_stream.next() => match new_log {
+                    None => {
+                        warn!(target: "l1_watcher", "System config log subscription ended, fetching per block instead");
+                        logs_subscribed = false;
+                        log_stream = Box::pin(stream::pending());
+                    }
+                    // Logs removed by a reorg are skipped, as they are re-emitted by the canonical
+                    // chain.
+                    Some(log) if !log.removed => {
+                        let timestamp = log
+                            .block_timestamp
+                            .or_else(|| (*latest_head.borrow()).map(|head| head.timestamp))
+                            .unwrap_or_default();
+                        let ecotone_active = self.state.rollup.is_ecotone_active(timestamp);
+                        self.state.handle_system_config_log(log, ecotone_active, &block_signer_sender).await;
+                    }
+                    Some(_) => {}
+                },
                 new_finalized = finalized_stream.next() => match new_finalized {
                     None => {
                         return Err(L1WatcherRpcError::StreamEnded);
@@ -246,26 +460,64 @@ impl NodeActor for L1WatcherRpc {
     }
 }
 
+/// Returns a stream of new L1 heads. If a `newHeads` subscription is given, heads are yielded from
+/// it until it ends, after which the stream falls back to polling the L1 provider.
+fn head_stream(
+    l1_provider: RootProvider,
+    subscription: Option<Subscription<Header>>,
+) -> impl Stream<Item = BlockInfo> + Unpin {
+    Box::pin(stream! {
+        if let Some(subscription) = subscription {
+            let mut heads = subscription.into_stream();
+            while let Some(header) = heads.next().await {
+                yield BlockInfo::new(header.hash, header.number, header.parent_hash, header.timestamp);
+            }
+            warn!(target: "l1_watcher", "L1 head subscription ended, polling instead");
+        }
+
+        let mut poll_stream =
+            BlockStream::new(l1_provider, BlockNumberOrTag::Latest, Duration::from_secs(13))
+                .into_stream();
+        while let Some(head) = poll_stream.next().await {
+            yield head;
+        }
+    })
+}
+
+/// Returns the number of the L1 block finalized at `depth` confirmations below the `head` block, if
+/// it is ahead of the `current_finalized` block. Finality never moves backwards, even if the L1
+/// head is reorged to a lower height.
+const fn finalized_number_at_depth(
+    depth: u64,
+    head: u64,
+    current_finalized: Option<u64>,
+) -> Option<u64> {
+    match (head.checked_sub(depth), current_finalized) {
+        (Some(number), Some(finalized)) if finalized >= number => None,
+        (number, _) => number,
+    }
+}
+
 /// A wrapper around a [`PollerBuilder`] that observes [`BlockId`] updates on a [`RootProvider`].
 ///
 /// Note that this stream is not guaranteed to be contiguous. It may miss certain blocks, and
 /// yielded items should only be considered to be the latest block matching the given
 /// [`BlockNumberOrTag`].
-struct BlockStream<'a> {
+struct BlockStream {
     /// The inner [`RootProvider`].
-    l1_provider: &'a RootProvider,
+    l1_provider: RootProvider,
     /// The block tag to poll for.
     tag: BlockNumberOrTag,
     /// The poll interval (in seconds).
     poll_interval: Duration,
 }
 
-impl<'a> BlockStream<'a> {
+impl BlockStream {
     /// Creates a new [`BlockStream`] instance.
     ///
     /// ## Panics
     /// Panics if the passed [`BlockNumberOrTag`] is of the [`BlockNumberOrTag::Number`] variant.
-    fn new(l1_provider: &'a RootProvider, tag: BlockNumberOrTag, poll_interval: Duration) -> Self {
+    fn new(l1_provider: RootProvider, tag: BlockNumberOrTag, poll_interval: Duration) -> Self {
         if matches!(tag, BlockNumberOrTag::Number(_)) {
             panic!("Invalid BlockNumberOrTag variant - Must be a tag");
         }
@@ -312,3 +564,20 @@ pub enum L1WatcherRpcError<T> {
     #[error("Stream ended unexpectedly")]
     StreamEnded,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_finalized_number_at_depth() {
+        assert_eq!(finalized_number_at_depth(64, 100, None), Some(36));
+        assert_eq!(finalized_number_at_depth(64, 100, Some(35)), Some(36));
+        assert_eq!(finalized_number_at_depth(0, 100, None), Some(100));
+        // Not enough confirmations yet.
+        assert_eq!(finalized_number_at_depth(64, 63, None), None);
+        // Finality does not move backwards, or repeat the current finalized block.
+        assert_eq!(finalized_number_at_depth(64, 100, Some(36)), None);
+        assert_eq!(finalized_number_at_depth(64, 90, Some(36)), None);
+    }
+}
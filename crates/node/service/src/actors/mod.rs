@@ -7,7 +7,8 @@ pub use traits::{CancellableContext, NodeActor};
 
 mod engine;
 pub use engine::{
-    EngineActor, EngineBuilder, EngineContext, EngineError, EngineInboundData, L2Finalizer,
+    BuildRequest, EngineActor, EngineBuilder, EngineContext, EngineError, EngineInboundData,
+    L2Finalizer,
 };
 
 mod rpc;
@@ -21,16 +22,20 @@ pub use derivation::{
 
 mod l1_watcher_rpc;
 pub use l1_watcher_rpc::{
-    L1WatcherRpc, L1WatcherRpcContext, L1WatcherRpcError, L1WatcherRpcInboundChannels,
-    L1WatcherRpcState,
+    L1PrefetchConfig, L1WatcherRpc, L1WatcherRpcContext, L1WatcherRpcError,
+    L1WatcherRpcInboundChannels, L1WatcherRpcState,
 };
 
 mod network;
 pub use network::{
-    NetworkActor, NetworkActorError, NetworkBuilder, NetworkBuilderError, NetworkConfig,
-    NetworkContext, NetworkDriver, NetworkDriverError, NetworkHandler, NetworkInboundData,
+    L2PayloadSource, NetworkActor, NetworkActorError, NetworkBuilder, NetworkBuilderError,
+    NetworkConfig, NetworkContext, NetworkDriver, NetworkDriverError, NetworkHandler,
+    NetworkInboundData,
 };
 
+mod unsafe_head;
+pub use unsafe_head::{UnsafeHeadTracker, UnsafeHeadTrackerContext, UnsafeHeadTrackerError};
+
 mod sequencer;
 pub use sequencer::{
     AttributesBuilderConfig, ConductorClient, ConductorError, DelayedL1OriginSelectorProvider,
@@ -1,12 +1,16 @@
 use alloy_primitives::Address;
 use async_trait::async_trait;
-use kona_gossip::P2pRpcRequest;
+use futures::future::OptionFuture;
+use kona_gossip::{P2pRpcRequest, SyncRequest};
 use kona_rpc::NetworkAdminQuery;
-use kona_sources::BlockSignerError;
+use kona_sources::{BlockSignerError, BlockSignerHandler, SIGNER_HEALTH_CHECK_TIMEOUT};
 use libp2p::TransportError;
 use op_alloy_rpc_types_engine::{OpExecutionPayloadEnvelope, OpNetworkPayloadEnvelope};
 use thiserror::Error;
-use tokio::{self, select, sync::mpsc};
+use tokio::{
+    self, select,
+    sync::{mpsc, watch},
+};
 use tokio_util::sync::{CancellationToken, WaitForCancellationFuture};
 
 use crate::{
@@ -55,6 +59,8 @@ pub struct NetworkActor {
     pub(super) admin_rpc: mpsc::Receiver<NetworkAdminQuery>,
     /// A channel to receive unsafe blocks and send them through the gossip layer.
     pub(super) publish_rx: mpsc::Receiver<OpExecutionPayloadEnvelope>,
+    /// A channel to receive requests for missing unsafe payloads, which are requested from peers.
+    pub(super) sync_request_rx: mpsc::Receiver<SyncRequest>,
 }
 
 /// The inbound data for the network actor.
@@ -70,6 +76,9 @@ pub struct NetworkInboundData {
     /// This channel should only be used by the sequencer actor/admin RPC api to forward their
     /// newly produced unsafe blocks to the network actor.
     pub gossip_payload_tx: mpsc::Sender<OpExecutionPayloadEnvelope>,
+    /// A channel to request missing unsafe payloads from peers. The payloads are sent through
+    /// the unsafe block channel of the [`NetworkContext`].
+    pub sync_request_tx: mpsc::Sender<SyncRequest>,
 }
 
 impl NetworkActor {
@@ -79,18 +88,21 @@ impl NetworkActor {
         let (rpc_tx, rpc_rx) = mpsc::channel(1024);
         let (admin_rpc_tx, admin_rpc_rx) = mpsc::channel(1024);
         let (publish_tx, publish_rx) = tokio::sync::mpsc::channel(256);
+        let (sync_request_tx, sync_request_rx) = mpsc::channel(256);
         let actor = Self {
             builder: driver,
             signer: signer_rx,
             p2p_rpc: rpc_rx,
             admin_rpc: admin_rpc_rx,
             publish_rx,
+            sync_request_rx,
         };
         let outbound_data = NetworkInboundData {
             signer: signer_tx,
             p2p_rpc: rpc_tx,
             admin_rpc: admin_rpc_tx,
             gossip_payload_tx: publish_tx,
+            sync_request_tx,
         };
         (outbound_data, actor)
     }
@@ -101,6 +113,10 @@ impl NetworkActor {
 pub struct NetworkContext {
     /// The channel used by the sequencer actor for sending unsafe blocks to the network.
     pub blocks: mpsc::Sender<OpExecutionPayloadEnvelope>,
+    /// A channel that signals the number of the unsafe head while the receiver of the unsafe
+    /// blocks is congested, in which case the gossip layer only forwards the block extending it,
+    /// if any.
+    pub import_backpressure: Option<watch::Receiver<Option<u64>>>,
     /// Cancels the network actor.
     pub cancellation: CancellationToken,
 }
@@ -150,7 +166,7 @@ impl NodeActor for NetworkActor {
 
     async fn start(
         mut self,
-        NetworkContext { blocks, cancellation }: Self::OutboundData,
+        NetworkContext { blocks, mut import_backpressure, cancellation }: Self::OutboundData,
     ) -> Result<(), Self::Error> {
         let mut handler = self.builder.build()?.start().await?;
 
@@ -164,6 +180,11 @@ impl NodeActor for NetworkActor {
                         target: "network",
                         "Received shutdown signal. Exiting network task."
                     );
+                    // Dropping the handler closes the swarm connections and shuts the discovery
+                    // service down, which persists the bootstore.
+                    if let Err(err) = handler.gossip.ban_list.sync() {
+                        warn!(target: "network", ?err, "Failed to persist the ban list");
+                    }
                     return Ok(());
                 }
                 block = unsafe_block_rx.recv() => {
@@ -177,6 +198,11 @@ impl NodeActor for NetworkActor {
                         return Err(NetworkActorError::ChannelClosed);
                     }
                 }
+                Some(Ok(())) = OptionFuture::from(import_backpressure.as_mut().map(|rx| rx.changed())), if import_backpressure.is_some() => {
+                    let unsafe_head = import_backpressure.as_ref().and_then(|rx| *rx.borrow());
+                    trace!(target: "network", ?unsafe_head, "Unsafe block import backpressure changed");
+                    handler.gossip.import_backpressure = unsafe_head;
+                }
                 signer = self.signer.recv() => {
                     let Some(signer) = signer else {
                         warn!(
@@ -243,15 +269,55 @@ impl NodeActor for NetworkActor {
                 _ = handler.peer_score_inspector.tick(), if handler.gossip.peer_monitoring.as_ref().is_some() => {
                     handler.handle_peer_monitoring().await;
                 },
-                Some(NetworkAdminQuery::PostUnsafePayload { payload }) = self.admin_rpc.recv(), if !self.admin_rpc.is_closed() => {
-                    debug!(target: "node::p2p", "Broadcasting unsafe payload from admin api");
-                    if unsafe_block_tx.send(payload).is_err() {
-                        warn!(target: "node::p2p", "Failed to send unsafe block to network handler");
+                _ = handler.signer_health_check.tick(), if handler.signer.as_ref().is_some_and(BlockSignerHandler::is_remote) => {
+                    if let Some(signer) = handler.signer.as_ref() {
+                        // A hung signer must not stall gossip handling.
+                        let health = tokio::time::timeout(SIGNER_HEALTH_CHECK_TIMEOUT, signer.health_check());
+                        match health.await {
+                            Ok(Ok(())) => {}
+                            Ok(Err(err)) => {
+                                warn!(target: "network", %err, "Remote block signer is unhealthy");
+                            }
+                            Err(_) => {
+                                warn!(
+                                    target: "network",
+                                    timeout = ?SIGNER_HEALTH_CHECK_TIMEOUT,
+                                    "Remote block signer health check timed out"
+                                );
+                            }
+                        }
+                    }
+                },
+                _ = handler.static_peer_dialer.tick(), if !handler.gossip.static_peers.is_empty() => {
+                    handler.gossip.redial_static_peers();
+                },
+                Some(query) = self.admin_rpc.recv(), if !self.admin_rpc.is_closed() => {
+                    match query {
+                        NetworkAdminQuery::PostUnsafePayload { payload } => {
+                            debug!(target: "node::p2p", "Broadcasting unsafe payload from admin api");
+                            if unsafe_block_tx.send(payload).is_err() {
+                                warn!(target: "node::p2p", "Failed to send unsafe block to network handler");
+                            }
+                        }
+                        NetworkAdminQuery::SetMaxPeers { max_peers } => {
+                            info!(target: "node::p2p", ?max_peers, "Peer limit updated from admin api");
+                            handler.gossip.max_peers = max_peers;
+                        }
                     }
                 },
                 Some(req) = self.p2p_rpc.recv(), if !self.p2p_rpc.is_closed() => {
                     req.handle(&mut handler.gossip, &handler.discovery);
                 },
+                Some(request) = self.sync_request_rx.recv(), if !self.sync_request_rx.is_closed() => {
+                    handler.gossip.request_payload(request);
+                },
+                Some(response) = handler.sync_responses.recv() => {
+                    if let Some(payload) = handler.gossip.handle_sync_response(response) {
+                        if unsafe_block_tx.send(payload).is_err() {
+                            warn!(target: "node::p2p", "Failed to send synced unsafe block to network handler");
+                        }
+                    }
+                },
             }
         }
     }
@@ -2,13 +2,13 @@
 
 use alloy_primitives::Address;
 use discv5::{Config as Discv5Config, Enr};
-use kona_disc::{Discv5Builder, LocalNode};
+use kona_disc::{Discv5Builder, LocalNode, Nat};
 use kona_genesis::RollupConfig;
-use kona_gossip::{GaterConfig, GossipDriverBuilder};
-use kona_peers::{BootStoreFile, PeerMonitoring, PeerScoreLevel};
+use kona_gossip::{GaterConfig, GossipDriverBuilder, PayloadSource};
+use kona_peers::{BanList, BootStoreFile, PeerMonitoring, PeerScoreLevel, TopicScoreWeights};
 use kona_sources::BlockSigner;
-use libp2p::{Multiaddr, identity::Keypair};
-use std::time::Duration;
+use libp2p::{Multiaddr, PeerId, identity::Keypair};
+use std::{path::PathBuf, sync::Arc, time::Duration};
 
 use crate::{
     NetworkBuilderError,
@@ -29,6 +29,8 @@ pub struct NetworkBuilder {
     /// This may be set to false if the node is configured to use a static advertised address (when
     /// used with a nat for example).
     pub(super) enr_update: bool,
+    /// The NAT traversal mechanism.
+    pub(super) nat: Nat,
 }
 
 impl From<NetworkConfig> for NetworkBuilder {
@@ -43,6 +45,7 @@ impl From<NetworkConfig> for NetworkBuilder {
             config.gossip_signer,
         )
         .with_enr_update(config.enr_update)
+        .with_nat(config.nat)
         .with_discovery_randomize(config.discovery_randomize)
         .with_bootstore(config.bootstore)
         .with_bootnodes(config.bootnodes)
@@ -51,7 +54,13 @@ impl From<NetworkConfig> for NetworkBuilder {
         .with_peer_scoring(config.scoring)
         .with_peer_monitoring(config.monitor_peers)
         .with_topic_scoring(config.topic_scoring)
+        .with_topic_score_weights(config.topic_score_weights)
+        .with_ban_list(config.ban_list)
+        .with_static_peers(config.static_peers)
+        .with_trusted_peers(config.trusted_peers)
         .with_gater_config(config.gater_config)
+        .with_publish_rate_limit(config.publish_rate_limit)
+        .with_signer_rotation_grace(config.signer_rotation_grace)
     }
 }
 
@@ -80,6 +89,7 @@ impl NetworkBuilder {
             ),
             signer,
             enr_update: true,
+            nat: Nat::None,
         }
     }
 
@@ -88,6 +98,11 @@ impl NetworkBuilder {
         Self { enr_update, ..self }
     }
 
+    /// Sets the NAT traversal mechanism for the [`NetworkBuilder`].
+    pub fn with_nat(self, nat: Nat) -> Self {
+        Self { nat, ..self }
+    }
+
     /// Sets the configuration for the connection gater.
     pub fn with_gater_config(self, config: GaterConfig) -> Self {
         Self { gossip: self.gossip.with_gater_config(config), ..self }
@@ -123,6 +138,48 @@ impl NetworkBuilder {
         Self { gossip: self.gossip.with_topic_scoring(topic_scoring), ..self }
     }
 
+    /// Sets the topic score weights for the [`GossipDriverBuilder`].
+    pub fn with_topic_score_weights(self, weights: TopicScoreWeights) -> Self {
+        Self { gossip: self.gossip.with_topic_score_weights(weights), ..self }
+    }
+
+    /// Sets the path of the persisted ban list for the [`GossipDriverBuilder`]. If no path is
+    /// given, bans are kept in memory only.
+    pub fn with_ban_list(self, path: Option<PathBuf>) -> Self {
+        let Some(path) = path else {
+            return self;
+        };
+        Self { gossip: self.gossip.with_ban_list(BanList::open(path)), ..self }
+    }
+
+    /// Sets the static peers for the [`GossipDriverBuilder`].
+    pub fn with_static_peers(self, peers: Vec<Multiaddr>) -> Self {
+        Self { gossip: self.gossip.with_static_peers(peers), ..self }
+    }
+
+    /// Sets the maximum number of unsafe blocks published per second for the
+    /// [`GossipDriverBuilder`].
+    pub fn with_publish_rate_limit(self, rate_limit: u32) -> Self {
+        Self { gossip: self.gossip.with_publish_rate_limit(rate_limit), ..self }
+    }
+
+    /// Sets how long payloads signed by the previous unsafe block signer are accepted after a
+    /// signer rotation for the [`GossipDriverBuilder`].
+    pub fn with_signer_rotation_grace(self, grace: Duration) -> Self {
+        Self { gossip: self.gossip.with_signer_rotation_grace(grace), ..self }
+    }
+
+    /// Sets the trusted peers for the [`GossipDriverBuilder`].
+    pub fn with_trusted_peers(self, peers: Vec<PeerId>) -> Self {
+        Self { gossip: self.gossip.with_trusted_peers(peers), ..self }
+    }
+
+    /// Sets the [`PayloadSource`] serving the payloads requested by peers over the sync
+    /// request/response protocol.
+    pub fn with_payload_source(self, source: Arc<dyn PayloadSource>) -> Self {
+        Self { gossip: self.gossip.with_payload_source(source), ..self }
+    }
+
     /// Sets the peer monitoring for the [`GossipDriverBuilder`].
     pub fn with_peer_monitoring(self, peer_monitoring: Option<PeerMonitoring>) -> Self {
         Self { gossip: self.gossip.with_peer_monitoring(peer_monitoring), ..self }
@@ -161,6 +218,7 @@ impl NetworkBuilder {
     /// Builds the [`NetworkDriver`].
     pub fn build(self) -> Result<NetworkDriver, NetworkBuilderError> {
         let (gossip, unsafe_block_signer_sender) = self.gossip.build()?;
+        let discovery_port = self.discovery.listen_port();
         let discovery = self.discovery.build()?;
 
         Ok(NetworkDriver {
@@ -169,6 +227,8 @@ impl NetworkBuilder {
             unsafe_block_signer_sender,
             signer: self.signer,
             enr_update: self.enr_update,
+            nat: self.nat,
+            discovery_port,
         })
     }
 }
@@ -2,12 +2,13 @@
 
 use alloy_primitives::Address;
 use discv5::Enr;
-use kona_disc::LocalNode;
+use kona_disc::{LocalNode, Nat};
 use kona_genesis::RollupConfig;
-use kona_gossip::GaterConfig;
-use kona_peers::{BootStoreFile, PeerMonitoring, PeerScoreLevel};
+use kona_gossip::{DEFAULT_PUBLISH_RATE_LIMIT, DEFAULT_SIGNER_ROTATION_GRACE, GaterConfig};
+use kona_peers::{BootStoreFile, PeerMonitoring, PeerScoreLevel, TopicScoreWeights};
 use kona_sources::BlockSigner;
-use libp2p::{Multiaddr, identity::Keypair};
+use libp2p::{Multiaddr, PeerId, identity::Keypair};
+use std::path::PathBuf;
 use tokio::time::Duration;
 
 /// Configuration for kona's P2P stack.
@@ -24,6 +25,8 @@ pub struct NetworkConfig {
     pub discovery_randomize: Option<Duration>,
     /// Whether to update the ENR socket when the gossip listen address changes.
     pub enr_update: bool,
+    /// The NAT traversal mechanism used to map ports and detect the external address.
+    pub nat: Nat,
     /// The gossip address.
     pub gossip_address: libp2p::Multiaddr,
     /// The unsafe block signer.
@@ -36,18 +39,31 @@ pub struct NetworkConfig {
     pub scoring: PeerScoreLevel,
     /// Whether to enable topic scoring.
     pub topic_scoring: bool,
+    /// The topic score weights, applied when topic scoring is enabled.
+    pub topic_score_weights: TopicScoreWeights,
     /// Peer score monitoring config.
     pub monitor_peers: Option<PeerMonitoring>,
     /// An optional path to the bootstore.
     pub bootstore: Option<BootStoreFile>,
+    /// An optional path to the file persisting the banned peers.
+    pub ban_list: Option<PathBuf>,
     /// The configuration for the connection gater.
     pub gater_config: GaterConfig,
     /// An optional list of bootnode ENRs to start the node with.
     pub bootnodes: Vec<Enr>,
+    /// The addresses of the static peers, which are kept connected to.
+    pub static_peers: Vec<Multiaddr>,
+    /// The trusted peers, which are exempt from scoring-based disconnects and dial limits.
+    pub trusted_peers: Vec<PeerId>,
     /// The [`RollupConfig`].
     pub rollup_config: RollupConfig,
     /// A signer for gossip payloads.
     pub gossip_signer: Option<BlockSigner>,
+    /// The maximum number of unsafe blocks published per second. Zero disables rate limiting.
+    pub publish_rate_limit: u32,
+    /// How long payloads signed by the previous unsafe block signer are accepted after a signer
+    /// rotation. Zero rejects them as soon as the rotation is observed.
+    pub signer_rotation_grace: Duration,
 }
 
 impl NetworkConfig {
@@ -87,15 +103,22 @@ impl NetworkConfig {
             gossip_address,
             unsafe_block_signer,
             enr_update: true,
+            nat: Nat::None,
             keypair: Keypair::generate_secp256k1(),
             bootnodes: Default::default(),
+            static_peers: Default::default(),
+            trusted_peers: Default::default(),
             bootstore: Default::default(),
+            ban_list: Default::default(),
             gater_config: Default::default(),
             gossip_config: Default::default(),
             scoring: Default::default(),
             topic_scoring: Default::default(),
+            topic_score_weights: Default::default(),
             monitor_peers: Default::default(),
             gossip_signer: Default::default(),
+            publish_rate_limit: DEFAULT_PUBLISH_RATE_LIMIT,
+            signer_rotation_grace: DEFAULT_SIGNER_ROTATION_GRACE,
         }
     }
 }
@@ -3,9 +3,11 @@ use std::net::{IpAddr, SocketAddr};
 use alloy_primitives::Address;
 use discv5::multiaddr::Protocol;
 use futures::future::OptionFuture;
-use kona_disc::Discv5Driver;
-use kona_gossip::{ConnectionGater, GossipDriver, PEER_SCORE_INSPECT_FREQUENCY};
-use kona_sources::{BlockSigner, BlockSignerStartError};
+use kona_disc::{Discv5Driver, Nat, NatService};
+use kona_gossip::{
+    ConnectionGater, GossipDriver, PEER_SCORE_INSPECT_FREQUENCY, STATIC_PEER_MIN_BACKOFF,
+};
+use kona_sources::{BlockSigner, BlockSignerStartError, SIGNER_HEALTH_CHECK_INTERVAL};
 use libp2p::{Multiaddr, TransportError};
 use tokio::sync::watch;
 
@@ -27,6 +29,10 @@ pub struct NetworkDriver {
     pub unsafe_block_signer_sender: watch::Sender<Address>,
     /// A block signer. This is optional and should be set if the node is configured to sign blocks
     pub signer: Option<BlockSigner>,
+    /// The NAT traversal mechanism used to map the gossip and discovery ports.
+    pub nat: Nat,
+    /// The UDP port the discovery service listens on.
+    pub discovery_port: u16,
 }
 
 /// An error from the [`NetworkDriver`].
@@ -41,17 +47,36 @@ pub enum NetworkDriverError {
     /// An error occurred parsing the gossip listen address.
     #[error("error parsing gossip listen address: {0}")]
     InvalidGossipListenAddr(Multiaddr),
+    /// The gossip driver was missing its sync response receiver.
+    #[error("missing sync response receiver in gossip driver")]
+    MissingSyncResponseReceiver,
 }
 
 impl NetworkDriver {
     /// Starts the network.
     pub async fn start(mut self) -> Result<NetworkHandler, NetworkDriverError> {
+        let sync_responses = self
+            .gossip
+            .sync_client
+            .take_responses()
+            .ok_or(NetworkDriverError::MissingSyncResponseReceiver)?;
+
         // Start the libp2p Swarm
         let gossip_listen_addr = self.gossip.start().await?;
 
+        // Parse the multiaddr to a socket address.
+        let port = gossip_listen_addr
+            .iter()
+            .find_map(|p| match p {
+                Protocol::Tcp(port) => Some(port),
+                _ => None,
+            })
+            .ok_or_else(|| {
+                NetworkDriverError::InvalidGossipListenAddr(gossip_listen_addr.clone())
+            })?;
+
         if self.enr_update {
             // Update the local ENR socket to the gossip listen address.
-            // Parse the multiaddr to a socket address.
             let ip_address = gossip_listen_addr
                 .iter()
                 .find_map(|p| match p {
@@ -62,15 +87,6 @@ impl NetworkDriver {
                 .ok_or_else(|| {
                     NetworkDriverError::InvalidGossipListenAddr(gossip_listen_addr.clone())
                 })?;
-            let port = gossip_listen_addr
-                .iter()
-                .find_map(|p| match p {
-                    Protocol::Tcp(port) => Some(port),
-                    _ => None,
-                })
-                .ok_or_else(|| {
-                    NetworkDriverError::InvalidGossipListenAddr(gossip_listen_addr.clone())
-                })?;
 
             self.discovery.disc.update_local_enr_socket(SocketAddr::new(ip_address, port), true);
         }
@@ -78,9 +94,20 @@ impl NetworkDriver {
         // Start the discovery service.
         let (handler, enr_receiver) = self.discovery.start();
 
+        // Map the gossip and discovery ports on the NAT gateway, if enabled. The service
+        // periodically re-checks the external address and updates the local ENR.
+        NatService::new(self.nat, port, self.discovery_port, handler.clone()).spawn();
+
         // We are checking the peer scores every [`PEER_SCORE_INSPECT_FREQUENCY`] seconds.
         let peer_score_inspector = tokio::time::interval(*PEER_SCORE_INSPECT_FREQUENCY);
 
+        // Static peers are checked for redials at the granularity of their minimum backoff.
+        let static_peer_dialer = tokio::time::interval(STATIC_PEER_MIN_BACKOFF);
+
+        // The health of remote block signers is checked periodically, to detect an unreachable
+        // signer before the sequencer needs it.
+        let signer_health_check = tokio::time::interval(SIGNER_HEALTH_CHECK_INTERVAL);
+
         // Start the block signer if it is configured.
         let signer =
             OptionFuture::from(self.signer.map(async |s| s.start().await)).await.transpose()?;
@@ -91,7 +118,10 @@ impl NetworkDriver {
             enr_receiver,
             unsafe_block_signer_sender: self.unsafe_block_signer_sender,
             peer_score_inspector,
+            static_peer_dialer,
             signer,
+            signer_health_check,
+            sync_responses,
         })
     }
 }
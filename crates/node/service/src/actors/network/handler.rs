@@ -3,7 +3,7 @@ use std::collections::HashSet;
 use alloy_primitives::Address;
 use discv5::Enr;
 use kona_disc::{Discv5Handler, HandlerRequest};
-use kona_gossip::{ConnectionGater, GossipDriver};
+use kona_gossip::{ConnectionGate, ConnectionGater, GossipDriver, SyncResponse};
 use kona_sources::BlockSignerHandler;
 use tokio::sync::{mpsc, watch};
 
@@ -20,17 +20,30 @@ pub struct NetworkHandler {
     pub unsafe_block_signer_sender: watch::Sender<Address>,
     /// The peer score inspector. Is used to ban peers that are below a given threshold.
     pub peer_score_inspector: tokio::time::Interval,
+    /// The interval at which the disconnected static peers are redialed.
+    pub static_peer_dialer: tokio::time::Interval,
     /// A handler for the block signer.
     pub signer: Option<BlockSignerHandler>,
+    /// The interval at which the health of a remote block signer is checked.
+    pub signer_health_check: tokio::time::Interval,
+    /// The receiver of the responses to the sync requests sent to peers.
+    pub sync_responses: mpsc::Receiver<SyncResponse>,
 }
 
 impl NetworkHandler {
     pub(super) async fn handle_peer_monitoring(&mut self) {
+        // Lift the bans that have expired.
+        self.gossip.expire_bans();
+
         // Inspect peer scores and ban peers that are below the threshold.
-        let Some(ban_peers) = self.gossip.peer_monitoring.as_ref() else {
+        let Some(ban_peers) = self.gossip.peer_monitoring.clone() else {
             return;
         };
 
+        // Protected peers, such as trusted and static peers, are never disconnected for their
+        // score.
+        let protected_peers = self.gossip.connection_gate.list_protected_peers();
+
         // We iterate over all connected peers and check their scores.
         // We collect a list of peers to remove
         let peers_to_remove = self
@@ -51,7 +64,7 @@ impl NetworkHandler {
                     score
                 );
 
-                if score < ban_peers.ban_threshold {
+                if score < ban_peers.ban_threshold && !protected_peers.contains(peer_id) {
                     return Some(*peer_id);
                 }
 
@@ -59,6 +72,11 @@ impl NetworkHandler {
             })
             .collect::<Vec<_>>();
 
+        // Persist the bans, so that they survive restarts.
+        for peer_id in &peers_to_remove {
+            self.gossip.record_ban(*peer_id, ban_peers.ban_duration);
+        }
+
         // We remove the addresses from the gossip layer.
         let addrs_to_ban = peers_to_remove.into_iter().filter_map(|peer_to_remove| {
                         // In that case, we ban the peer. This means...
@@ -79,7 +97,6 @@ impl NetworkHandler {
                         }
 
                         if let Some(info) = self.gossip.peerstore.remove(&peer_to_remove){
-                            use kona_gossip::ConnectionGate;
                             self.gossip.connection_gate.remove_dial(&peer_to_remove);
                             let score = self.gossip.swarm.behaviour().gossipsub.peer_score(&peer_to_remove).unwrap_or_default();
                             kona_macros::inc!(gauge, kona_gossip::Metrics::BANNED_PEERS, "peer_id" => peer_to_remove.to_string(), "score" => score.to_string());
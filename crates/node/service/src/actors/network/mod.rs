@@ -17,3 +17,6 @@ pub use handler::NetworkHandler;
 
 mod config;
 pub use config::NetworkConfig;
+
+mod payloads;
+pub use payloads::L2PayloadSource;
@@ -0,0 +1,43 @@
+//! Contains the [`L2PayloadSource`], which serves unsafe payloads from the L2 execution layer.
+
+use alloy_provider::{Provider, RootProvider};
+use futures::future::BoxFuture;
+use kona_gossip::PayloadSource;
+use op_alloy_network::Optimism;
+use op_alloy_rpc_types_engine::{OpExecutionPayload, OpExecutionPayloadEnvelope};
+
+/// A [`PayloadSource`] that serves the canonical payloads of the L2 execution layer to peers
+/// over the sync request/response protocol.
+#[derive(Debug, Clone)]
+pub struct L2PayloadSource {
+    /// The L2 EL provider.
+    provider: RootProvider<Optimism>,
+}
+
+impl L2PayloadSource {
+    /// Creates a new [`L2PayloadSource`] from the given L2 EL provider.
+    pub const fn new(provider: RootProvider<Optimism>) -> Self {
+        Self { provider }
+    }
+}
+
+impl PayloadSource for L2PayloadSource {
+    fn payload_by_number(&self, number: u64) -> BoxFuture<'_, Option<OpExecutionPayloadEnvelope>> {
+        Box::pin(async move {
+            let block = self
+                .provider
+                .get_block_by_number(number.into())
+                .full()
+                .await
+                .inspect_err(|err| {
+                    debug!(target: "network", ?err, number, "Failed to fetch payload for sync request")
+                })
+                .ok()??
+                .into_consensus()
+                .map_transactions(|tx| tx.inner.inner.into_inner());
+            let parent_beacon_block_root = block.header.parent_beacon_block_root;
+            let (execution_payload, _) = OpExecutionPayload::from_block_slow(&block);
+            Some(OpExecutionPayloadEnvelope { execution_payload, parent_beacon_block_root })
+        })
+    }
+}
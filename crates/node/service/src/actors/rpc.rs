@@ -4,26 +4,31 @@ use crate::{NodeActor, actors::CancellableContext};
 use async_trait::async_trait;
 use kona_gossip::P2pRpcRequest;
 use kona_rpc::{
-    AdminApiServer, AdminRpc, DevEngineApiServer, DevEngineRpc, HealthzResponse, NetworkAdminQuery,
-    OpP2PApiServer, RollupNodeApiServer, SequencerAdminQuery, WsRPC, WsServer,
+    AdminApiServer, AdminRpc, ConditionalTransaction, DebugApiServer, DebugRpc, DerivationQueries,
+    DevEngineApiServer, DevEngineRpc, HealthzResponse, NetworkAdminQuery, OpP2PApiServer,
+    ReadinessCheck, RollupNodeApiServer, RollupSubscriptionApiServer, SequencerAdminQuery, WsRPC,
+    WsServer,
 };
-use std::time::Duration;
+use std::{collections::HashSet, net::SocketAddr, sync::Arc, time::Duration};
 
 use jsonrpsee::{
     RpcModule,
-    core::RegisterMethodError,
+    core::{RegisterMethodError, middleware::RpcServiceBuilder},
     server::{Server, ServerHandle, middleware::http::ProxyGetRequestLayer},
 };
 use kona_engine::EngineQueries;
-use kona_rpc::{L1WatcherQueries, P2pRpc, RollupRpc, RpcBuilder};
+use kona_rpc::{
+    BearerAuthLayer, L1WatcherQueries, P2pRpc, ProtectedAccess, RollupRpc, RpcAccessGate,
+    RpcBuilder, RpcMetricsService, RpcNamespaceGate, RpcNamespaces,
+};
 use tokio::sync::mpsc;
 use tokio_util::sync::{CancellationToken, WaitForCancellationFuture};
 
 /// An error returned by the [`RpcActor`].
 #[derive(Debug, thiserror::Error)]
 pub enum RpcActorError {
-    /// Failed to register the healthz endpoint.
-    #[error("Failed to register the healthz endpoint")]
+    /// Failed to register the healthz or readyz endpoint.
+    #[error("Failed to register the healthz or readyz endpoint")]
     RegisterHealthz(#[from] RegisterMethodError),
     /// Failed to launch the RPC server.
     #[error(transparent)]
@@ -59,10 +64,14 @@ pub struct RpcContext {
     pub network_admin: mpsc::Sender<NetworkAdminQuery>,
     /// The sequencer admin rpc sender.
     pub sequencer_admin: Option<mpsc::Sender<SequencerAdminQuery>>,
+    /// The sender of conditional transactions to the engine, in sequencer mode.
+    pub conditional_tx: Option<mpsc::Sender<ConditionalTransaction>>,
     /// The l1 watcher queries sender.
     pub l1_watcher_queries: mpsc::Sender<L1WatcherQueries>,
     /// The engine query sender.
     pub engine_query: mpsc::Sender<EngineQueries>,
+    /// The derivation query sender.
+    pub derivation_queries: mpsc::Sender<DerivationQueries>,
     /// The cancellation token, shared between all tasks.
     pub cancellation: CancellationToken,
 }
@@ -73,27 +82,42 @@ impl CancellableContext for RpcContext {
     }
 }
 
-/// Launches the jsonrpsee [`Server`].
+/// Launches the jsonrpsee [`Server`] on the given socket.
 ///
-/// If the RPC server is disabled, this will return `Ok(None)`.
+/// Calls to the methods of the namespaces disabled in the given [`RpcNamespaces`] are rejected,
+/// and calls to the methods of the protected namespaces are handled with the given
+/// [`ProtectedAccess`], authenticating the callers with the admin token of the config.
 ///
 /// ## Errors
 ///
 /// - [`std::io::Error`] if the server fails to start.
 async fn launch(
     config: &RpcBuilder,
+    socket: SocketAddr,
     module: RpcModule<()>,
+    namespaces: RpcNamespaces,
+    access: ProtectedAccess,
 ) -> Result<ServerHandle, std::io::Error> {
     let middleware = tower::ServiceBuilder::new()
+        .layer(BearerAuthLayer::new(config.admin_token.as_deref()))
         .layer(
-            ProxyGetRequestLayer::new([("/healthz", "healthz")])
+            ProxyGetRequestLayer::new([("/healthz", "healthz"), ("/readyz", "readyz")])
                 .expect("Critical: Failed to build GET method proxy"),
         )
         .timeout(Duration::from_secs(2));
-    let server = Server::builder().set_http_middleware(middleware).build(config.socket).await?;
+    let methods = Arc::new(module.method_names().collect::<HashSet<_>>());
+    let rpc_middleware = RpcServiceBuilder::new()
+        .layer_fn(move |service| RpcMetricsService::new(service, methods.clone()))
+        .layer_fn(move |service| RpcNamespaceGate::new(service, namespaces.clone()))
+        .layer_fn(move |service| RpcAccessGate::new(service, access));
+    let server = Server::builder()
+        .set_http_middleware(middleware)
+        .set_rpc_middleware(rpc_middleware)
+        .build(socket)
+        .await?;
 
     if let Ok(addr) = server.local_addr() {
-        info!(target: "rpc", addr = ?addr, "RPC server bound to address");
+        info!(target: "rpc", addr = ?addr, ?access, "RPC server bound to address");
     } else {
         error!(target: "rpc", "Failed to get local address for RPC server");
     }
@@ -119,49 +143,87 @@ impl NodeActor for RpcActor {
             p2p_network,
             l1_watcher_queries,
             engine_query,
+            derivation_queries,
             network_admin,
             sequencer_admin,
+            conditional_tx,
         }: Self::OutboundData,
     ) -> Result<(), Self::Error> {
         let mut modules = RpcModule::new(());
 
-        modules.register_method("healthz", |_, _, _| {
-            let response = HealthzResponse { version: std::env!("CARGO_PKG_VERSION").to_string() };
+        let version = self.config.version.clone();
+        modules.register_method("healthz", move |_, _, _| {
+            let response = HealthzResponse { version: version.clone() };
             jsonrpsee::core::RpcResult::Ok(response)
         })?;
 
+        let mut readiness = RpcModule::new(ReadinessCheck {
+            criteria: self.config.readiness.clone(),
+            p2p_sender: p2p_network.clone(),
+            engine_sender: engine_query.clone(),
+            l1_watcher_sender: l1_watcher_queries.clone(),
+        });
+        readiness
+            .register_async_method("readyz", |_, check, _| async move { check.readyz().await })?;
+        modules.merge(readiness)?;
+
         // Build the p2p rpc module.
         modules.merge(P2pRpc::new(p2p_network).into_rpc())?;
 
         // Build the admin rpc module.
+        let namespaces = RpcNamespaces::default();
         modules.merge(
-            AdminRpc { sequencer_sender: sequencer_admin, network_sender: network_admin }
-                .into_rpc(),
+            AdminRpc {
+                sequencer_sender: sequencer_admin,
+                network_sender: network_admin,
+                conditional_tx_sender: conditional_tx,
+                log_filter: self.config.log_filter.clone(),
+                namespaces: namespaces.clone(),
+            }
+            .into_rpc(),
         )?;
 
         // Create context for communication between actors.
-        let rollup_rpc = RollupRpc::new(engine_query.clone(), l1_watcher_queries);
+        let rollup_rpc =
+            RollupRpc::new(engine_query.clone(), l1_watcher_queries, self.config.version.clone());
         modules.merge(rollup_rpc.into_rpc())?;
 
-        // Add development RPC module for engine state introspection if enabled
+        // Add development RPC modules for engine and derivation state introspection if enabled
         if self.config.dev_enabled() {
             let dev_rpc = DevEngineRpc::new(engine_query.clone());
             modules.merge(dev_rpc.into_rpc())?;
+            modules.merge(DebugRpc::new(derivation_queries).into_rpc())?;
         }
 
         if self.config.ws_enabled() {
-            modules.merge(WsRPC::new(engine_query).into_rpc())?;
+            modules.merge(WsServer::into_rpc(WsRPC::new(engine_query.clone())))?;
+            modules.merge(RollupSubscriptionApiServer::into_rpc(WsRPC::new(engine_query)))?;
         }
 
         let restarts = self.config.restart_count();
 
-        let mut handle = launch(&self.config, modules.clone()).await?;
+        // The main server, and the server of the protected methods if it is on a separate socket.
+        let mut servers = vec![(self.config.socket, self.config.public_access())];
+        if let Some(admin_socket) = self.config.admin_socket {
+            servers.push((admin_socket, self.config.admin_access()));
+        }
+
+        let mut handles = Vec::with_capacity(servers.len());
+        for (socket, access) in &servers {
+            let handle =
+                launch(&self.config, *socket, modules.clone(), namespaces.clone(), *access).await?;
+            handles.push(handle);
+        }
 
         for _ in 0..=restarts {
+            let stopped = handles.iter().cloned().map(|handle| Box::pin(handle.stopped()));
             tokio::select! {
-                _ = handle.clone().stopped() => {
-                    match launch(&self.config, modules.clone()).await {
-                        Ok(h) => handle = h,
+                (_, index, _) = futures::future::select_all(stopped) => {
+                    let (socket, access) = servers[index];
+                    match launch(&self.config, socket, modules.clone(), namespaces.clone(), access)
+                        .await
+                    {
+                        Ok(h) => handles[index] = h,
                         Err(err) => {
                             error!(target: "rpc", ?err, "Failed to launch rpc server");
                             cancellation.cancel();
@@ -170,8 +232,12 @@ impl NodeActor for RpcActor {
                     }
                 }
                 _ = cancellation.cancelled() => {
-                    // The cancellation token has been triggered, so we should stop the server.
-                    handle.stop().map_err(|_| RpcActorError::StopFailed)?;
+                    // The cancellation token has been triggered, so we should stop the servers,
+                    // and wait for the in-flight requests to complete.
+                    for handle in handles {
+                        handle.stop().map_err(|_| RpcActorError::StopFailed)?;
+                        handle.stopped().await;
+                    }
                     // Since the RPC Server didn't originate the error, we should return Ok.
                     return Ok(());
                 }
@@ -186,21 +252,31 @@ impl NodeActor for RpcActor {
 
 #[cfg(test)]
 mod tests {
-    use std::net::SocketAddr;
-
     use super::*;
 
     #[tokio::test]
     async fn test_launch_no_modules() {
         let launcher = RpcBuilder {
             socket: SocketAddr::from(([127, 0, 0, 1], 8080)),
+            admin_socket: None,
+            admin_token: None,
             no_restart: false,
             enable_admin: false,
             admin_persistence: None,
             ws_enabled: false,
             dev_enabled: false,
+            version: String::new(),
+            readiness: Default::default(),
+            log_filter: None,
         };
-        let result = launch(&launcher, RpcModule::new(())).await;
+        let result = launch(
+            &launcher,
+            launcher.socket,
+            RpcModule::new(()),
+            RpcNamespaces::default(),
+            launcher.public_access(),
+        )
+        .await;
         assert!(result.is_ok());
     }
 
@@ -208,11 +284,16 @@ mod tests {
     async fn test_launch_with_modules() {
         let launcher = RpcBuilder {
             socket: SocketAddr::from(([127, 0, 0, 1], 8081)),
+            admin_socket: None,
+            admin_token: None,
             no_restart: false,
             enable_admin: false,
             admin_persistence: None,
             ws_enabled: false,
             dev_enabled: false,
+            version: String::new(),
+            readiness: Default::default(),
+            log_filter: None,
         };
         let mut modules = RpcModule::new(());
 
@@ -220,7 +301,14 @@ mod tests {
         modules.merge(RpcModule::new(())).expect("module merge");
         modules.merge(RpcModule::new(())).expect("module merge");
 
-        let result = launch(&launcher, modules).await;
+        let result = launch(
+            &launcher,
+            launcher.socket,
+            modules,
+            RpcNamespaces::default(),
+            launcher.public_access(),
+        )
+        .await;
         assert!(result.is_ok());
     }
 }
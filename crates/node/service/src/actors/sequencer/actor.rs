@@ -3,10 +3,14 @@
 use super::{
     DelayedL1OriginSelectorProvider, L1OriginSelector, L1OriginSelectorError, SequencerConfig,
 };
-use crate::{CancellableContext, NodeActor, actors::sequencer::conductor::ConductorClient};
+use crate::{
+    BuildRequest, CancellableContext, NodeActor, actors::sequencer::conductor::ConductorClient,
+};
 use alloy_provider::RootProvider;
 use async_trait::async_trait;
+use futures::future::OptionFuture;
 use kona_derive::{AttributesBuilder, PipelineErrorKind, StatefulAttributesBuilder};
+use kona_engine::PayloadCommitRequest;
 use kona_genesis::{L1ChainConfig, RollupConfig};
 use kona_protocol::{BlockInfo, L2BlockInfo, OpAttributesWithParent};
 use kona_providers_alloy::{AlloyChainProvider, AlloyL2ChainProvider};
@@ -80,6 +84,7 @@ impl SequencerActorState<StatefulAttributesBuilder<AlloyChainProvider, AlloyL2Ch
             sequencer_stopped,
             sequencer_recovery_mode,
             conductor_rpc_url,
+            conductor_rpc_timeout,
             l1_conf_delay,
         } = seq_builder.seq_cfg.clone();
 
@@ -89,7 +94,8 @@ impl SequencerActorState<StatefulAttributesBuilder<AlloyChainProvider, AlloyL2Ch
             l1_head_watcher,
             l1_conf_delay,
         );
-        let conductor = conductor_rpc_url.map(ConductorClient::new_http);
+        let conductor = conductor_rpc_url
+            .map(|url| ConductorClient::new_http_with_timeout(url, conductor_rpc_timeout));
 
         let builder = seq_builder.build();
         let build_ticker = tokio::time::interval(Duration::from_secs(cfg.block_time));
@@ -174,8 +180,7 @@ pub struct SequencerContext {
     pub reset_request_tx: mpsc::Sender<()>,
     /// Sender to request the execution layer to build a payload attributes on top of the
     /// current unsafe head.
-    pub build_request_tx:
-        mpsc::Sender<(OpAttributesWithParent, mpsc::Sender<OpExecutionPayloadEnvelope>)>,
+    pub build_request_tx: mpsc::Sender<BuildRequest>,
     /// A sender to asynchronously sign and gossip built [`OpExecutionPayloadEnvelope`]s to the
     /// network actor.
     pub gossip_payload_tx: mpsc::Sender<OpExecutionPayloadEnvelope>,
@@ -220,6 +225,11 @@ impl<AB: AttributesBuilder> SequencerActorState<AB> {
         unsafe_head_rx: &mut watch::Receiver<L2BlockInfo>,
         in_recovery_mode: bool,
     ) -> Result<(), SequencerActorError> {
+        // If the conductor is available, only the leader may build new blocks.
+        if !self.is_conductor_leader().await {
+            return Ok(());
+        }
+
         let unsafe_head = *unsafe_head_rx.borrow();
         let l1_origin = match self
             .origin_selector
@@ -362,15 +372,28 @@ impl<AB: AttributesBuilder> SequencerActorState<AB> {
         // Create a new channel to receive the built payload.
         let (payload_tx, payload_rx) = mpsc::channel(1);
 
+        // If the conductor is available, create a channel to commit the sealed payload to it
+        // before it is imported. Payloads that are not committed to the conductor's log must be
+        // neither imported nor gossipped, as the standby sequencers could not take over from them.
+        let (commit_tx, commit_rx) = if self.conductor.is_some() {
+            let (tx, rx) = mpsc::channel(1);
+            (Some(tx), Some(rx))
+        } else {
+            (None, None)
+        };
+
         // Send the built attributes to the engine to be built.
         let _build_request_start = Instant::now();
-        if let Err(err) = ctx.build_request_tx.send((attrs_with_parent, payload_tx)).await {
+        let request = BuildRequest { attributes: attrs_with_parent, payload_tx, commit_tx };
+        if let Err(err) = ctx.build_request_tx.send(request).await {
             error!(target: "sequencer", ?err, "Failed to send built attributes to engine");
             ctx.cancellation.cancel();
             return Err(SequencerActorError::ChannelClosed);
         }
 
-        let payload = self.try_wait_for_payload(ctx, payload_rx).await?;
+        let Some(payload) = self.try_wait_for_payload(ctx, payload_rx, commit_rx).await? else {
+            return Ok(());
+        };
 
         // Log the block building job duration, if metrics are enabled.
         kona_macros::set!(
@@ -379,20 +402,6 @@ impl<AB: AttributesBuilder> SequencerActorState<AB> {
             _build_request_start.elapsed()
         );
 
-        // If the conductor is available, commit the payload to it.
-        if let Some(conductor) = &self.conductor {
-            let _conductor_commitment_start = Instant::now();
-            if let Err(err) = conductor.commit_unsafe_payload(&payload).await {
-                error!(target: "sequencer", ?err, "Failed to commit unsafe payload to conductor");
-            }
-
-            kona_macros::set!(
-                gauge,
-                crate::Metrics::SEQUENCER_CONDUCTOR_COMMITMENT_DURATION,
-                _conductor_commitment_start.elapsed()
-            );
-        }
-
         let now =
             SystemTime::now().duration_since(UNIX_EPOCH).expect("Time went backwards").as_secs();
         let then = payload.execution_payload.timestamp() + self.cfg.block_time;
@@ -407,18 +416,74 @@ impl<AB: AttributesBuilder> SequencerActorState<AB> {
         self.schedule_gossip(ctx, payload).await
     }
 
-    /// Waits for the next payload to be built and returns it, if there is a payload receiver
-    /// present.
+    /// Returns whether the sequencer is the leader of the conductor. Always returns `true` if
+    /// the conductor is not enabled.
+    async fn is_conductor_leader(&mut self) -> bool {
+        let Some(conductor) = &mut self.conductor else {
+            return true;
+        };
+
+        match conductor.cached_leader().await {
+            Ok(true) => true,
+            Ok(false) => {
+                debug!(target: "sequencer", "Not the conductor leader, skipping block building");
+                false
+            }
+            Err(err) => {
+                warn!(target: "sequencer", ?err, "Failed to check conductor leadership, skipping block building");
+                false
+            }
+        }
+    }
+
+    /// Waits for the next payload to be built and imported, and returns it.
+    ///
+    /// If a commit receiver is given, the sealed payloads are committed to the conductor before
+    /// they are imported. `None` is returned if a payload fails to be committed, in which case the
+    /// engine drops it.
     async fn try_wait_for_payload(
         &mut self,
         ctx: &mut SequencerContext,
         mut payload_rx: mpsc::Receiver<OpExecutionPayloadEnvelope>,
-    ) -> Result<OpExecutionPayloadEnvelope, SequencerActorError> {
-        payload_rx.recv().await.ok_or_else(|| {
-            error!(target: "sequencer", "Failed to receive built payload");
-            ctx.cancellation.cancel();
-            SequencerActorError::ChannelClosed
-        })
+        mut commit_rx: Option<mpsc::Receiver<PayloadCommitRequest>>,
+    ) -> Result<Option<OpExecutionPayloadEnvelope>, SequencerActorError> {
+        loop {
+            select! {
+                biased;
+                Some(Some(request)) = OptionFuture::from(commit_rx.as_mut().map(|rx| rx.recv())) => {
+                    if !self.commit_to_conductor(request).await {
+                        return Ok(None);
+                    }
+                }
+                payload = payload_rx.recv() => {
+                    return payload.map(Some).ok_or_else(|| {
+                        error!(target: "sequencer", "Failed to receive built payload");
+                        ctx.cancellation.cancel();
+                        SequencerActorError::ChannelClosed
+                    });
+                }
+            }
+        }
+    }
+
+    /// Commits a sealed payload to the conductor, if it is enabled, and returns whether it was
+    /// committed.
+    async fn commit_to_conductor(&mut self, request: PayloadCommitRequest) -> bool {
+        let Some(conductor) = &mut self.conductor else {
+            _ = request.1.send(true);
+            return true;
+        };
+
+        let _conductor_commitment_start = Instant::now();
+        let committed = conductor.commit_sealed_payload(request).await;
+        if committed {
+            kona_macros::set!(
+                gauge,
+                crate::Metrics::SEQUENCER_CONDUCTOR_COMMITMENT_DURATION,
+                _conductor_commitment_start.elapsed()
+            );
+        }
+        committed
     }
 
     /// Schedules a built [`OpExecutionPayloadEnvelope`] to be signed and gossipped.
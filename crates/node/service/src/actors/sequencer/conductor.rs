@@ -1,38 +1,88 @@
 use alloy_rpc_client::ReqwestClient;
 use alloy_transport::{RpcError, TransportErrorKind};
+use kona_engine::PayloadCommitRequest;
 use op_alloy_rpc_types_engine::OpExecutionPayloadEnvelope;
+use std::time::{Duration, Instant};
 use url::Url;
 
+/// The default timeout for RPC requests to the conductor service.
+pub(super) const DEFAULT_CONDUCTOR_RPC_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// The duration for which the leadership status of the conductor is cached.
+const LEADERSHIP_CACHE_TTL: Duration = Duration::from_secs(10);
+
 /// A client for communicating with the conductor service via RPC
 #[derive(Debug, Clone)]
 pub struct ConductorClient {
     /// The inner RPC provider
     rpc: ReqwestClient,
+    /// The timeout for each RPC request to the conductor.
+    timeout: Duration,
+    /// The last leadership status returned by the conductor, and when it was queried.
+    leadership: Option<(bool, Instant)>,
 }
 
 impl ConductorClient {
     /// Creates a new conductor client using HTTP transport
     pub fn new_http(url: Url) -> Self {
+        Self::new_http_with_timeout(url, DEFAULT_CONDUCTOR_RPC_TIMEOUT)
+    }
+
+    /// Creates a new conductor client using HTTP transport, with the given request timeout.
+    pub fn new_http_with_timeout(url: Url, timeout: Duration) -> Self {
         let rpc = ReqwestClient::new_http(url);
-        Self { rpc }
+        Self { rpc, timeout, leadership: None }
+    }
+
+    /// Awaits a request to the conductor, failing if no response is received within the timeout.
+    async fn with_timeout<T>(
+        &self,
+        method: &'static str,
+        request: impl Future<Output = Result<T, RpcError<TransportErrorKind>>>,
+    ) -> Result<T, ConductorError> {
+        tokio::time::timeout(self.timeout, request)
+            .await
+            .map_err(|_| ConductorError::Timeout(method))?
+            .map_err(Into::into)
     }
 
     /// Check if the node is a leader of the conductor.
     pub async fn leader(&self) -> Result<bool, ConductorError> {
-        let result: bool = self.rpc.request("conductor_leader", ()).await?;
-        Ok(result)
+        self.with_timeout("conductor_leader", self.rpc.request("conductor_leader", ())).await
+    }
+
+    /// Check if the node is a leader of the conductor, reusing the leadership status returned by
+    /// the conductor within the last [`LEADERSHIP_CACHE_TTL`].
+    pub async fn cached_leader(&mut self) -> Result<bool, ConductorError> {
+        let cached =
+            self.leadership.filter(|(_, queried_at)| queried_at.elapsed() < LEADERSHIP_CACHE_TTL);
+        if let Some((leader, _)) = cached {
+            return Ok(leader);
+        }
+
+        let leader = self.leader().await?;
+        self.leadership = Some((leader, Instant::now()));
+        Ok(leader)
+    }
+
+    /// Clears the cached leadership status, so that the conductor is queried again on the next
+    /// call to [`ConductorClient::cached_leader`].
+    pub const fn clear_cached_leader(&mut self) {
+        self.leadership = None;
     }
 
     /// Check if the conductor is active.
     pub async fn conductor_active(&self) -> Result<bool, ConductorError> {
-        let result: bool = self.rpc.request("conductor_active", ()).await?;
-        Ok(result)
+        self.with_timeout("conductor_active", self.rpc.request("conductor_active", ())).await
     }
 
     /// Override the leader of the conductor.
     pub async fn override_leader(&self) -> Result<(), ConductorError> {
-        let _result: () = self.rpc.request("conductor_overrideLeader", ()).await?;
-        Ok(())
+        self.with_timeout(
+            "conductor_overrideLeader",
+            self.rpc.request("conductor_overrideLeader", ()),
+        )
+        .await
     }
 
     /// Commit an unsafe payload to the conductor.
@@ -40,8 +90,32 @@ impl ConductorClient {
         &self,
         payload: &OpExecutionPayloadEnvelope,
     ) -> Result<(), ConductorError> {
-        let _result: () = self.rpc.request("conductor_commitUnsafePayload", [payload]).await?;
-        Ok(())
+        self.with_timeout(
+            "conductor_commitUnsafePayload",
+            self.rpc.request("conductor_commitUnsafePayload", [payload]),
+        )
+        .await
+    }
+
+    /// Commits the sealed payload of the given [`PayloadCommitRequest`] to the conductor, and
+    /// acknowledges whether it was committed, so that the payload is only imported if it was.
+    ///
+    /// A failed commitment usually means that the node lost the leadership, so the cached
+    /// leadership status is cleared.
+    pub(super) async fn commit_sealed_payload(
+        &mut self,
+        (payload, ack_tx): PayloadCommitRequest,
+    ) -> bool {
+        let committed = match self.commit_unsafe_payload(&payload).await {
+            Ok(()) => true,
+            Err(err) => {
+                error!(target: "sequencer", ?err, "Failed to commit unsafe payload to conductor");
+                self.clear_cached_leader();
+                false
+            }
+        };
+        _ = ack_tx.send(committed);
+        committed
     }
 }
 
@@ -51,4 +125,89 @@ pub enum ConductorError {
     /// An error occurred while making an RPC call to the conductor.
     #[error("RPC error: {0}")]
     Rpc(#[from] RpcError<TransportErrorKind>),
+    /// The conductor did not respond within the timeout.
+    #[error("Conductor request `{0}` timed out")]
+    Timeout(&'static str),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_rpc_client::RpcClient;
+    use alloy_rpc_types_engine::ExecutionPayloadV1;
+    use alloy_transport::mock::{Asserter, MockTransport};
+    use arbitrary::Arbitrary;
+    use op_alloy_rpc_types_engine::OpExecutionPayload;
+    use tokio::sync::oneshot;
+
+    fn client(asserter: &Asserter) -> ConductorClient {
+        ConductorClient {
+            rpc: RpcClient::new(MockTransport::new(asserter.clone()), false),
+            timeout: DEFAULT_CONDUCTOR_RPC_TIMEOUT,
+            leadership: None,
+        }
+    }
+
+    fn payload() -> OpExecutionPayloadEnvelope {
+        let payload =
+            ExecutionPayloadV1::arbitrary(&mut arbitrary::Unstructured::new(&[])).unwrap();
+        OpExecutionPayloadEnvelope {
+            execution_payload: OpExecutionPayload::V1(payload),
+            parent_beacon_block_root: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_cached_leader() {
+        let asserter = Asserter::new();
+        let mut conductor = client(&asserter);
+
+        // The leadership status is only queried once within the cache TTL.
+        asserter.push_success(&true);
+        assert!(conductor.cached_leader().await.unwrap());
+        assert!(conductor.cached_leader().await.unwrap());
+        assert!(asserter.read_q().is_empty());
+
+        // Clearing the cache queries the conductor again.
+        asserter.push_success(&false);
+        conductor.clear_cached_leader();
+        assert!(!conductor.cached_leader().await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_cached_leader_error() {
+        let asserter = Asserter::new();
+        let mut conductor = client(&asserter);
+
+        // Errors are not cached.
+        asserter.push_failure_msg("conductor unavailable");
+        asserter.push_success(&true);
+        assert!(conductor.cached_leader().await.is_err());
+        assert!(conductor.cached_leader().await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_commit_sealed_payload() {
+        let asserter = Asserter::new();
+        let mut conductor = client(&asserter);
+
+        asserter.push_success(&());
+        let (ack_tx, ack_rx) = oneshot::channel();
+        assert!(conductor.commit_sealed_payload((payload(), ack_tx)).await);
+        assert!(ack_rx.await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_commit_sealed_payload_failure() {
+        let asserter = Asserter::new();
+        let mut conductor = client(&asserter);
+        conductor.leadership = Some((true, Instant::now()));
+
+        // A failed commitment is not acknowledged, and clears the cached leadership.
+        asserter.push_failure_msg("not the leader");
+        let (ack_tx, ack_rx) = oneshot::channel();
+        assert!(!conductor.commit_sealed_payload((payload(), ack_tx)).await);
+        assert!(!ack_rx.await.unwrap());
+        assert!(conductor.leadership.is_none());
+    }
 }
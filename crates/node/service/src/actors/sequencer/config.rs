@@ -2,12 +2,14 @@
 //!
 //! [`SequencerActor`]: super::SequencerActor
 
+use super::conductor::DEFAULT_CONDUCTOR_RPC_TIMEOUT;
+use std::time::Duration;
 use url::Url;
 
 /// Configuration for the [`SequencerActor`].
 ///
 /// [`SequencerActor`]: super::SequencerActor
-#[derive(Default, Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct SequencerConfig {
     /// Whether or not the sequencer is enabled at startup.
     pub sequencer_stopped: bool,
@@ -15,6 +17,20 @@ pub struct SequencerConfig {
     pub sequencer_recovery_mode: bool,
     /// The [`Url`] for the conductor RPC endpoint. If [`Some`], enables the conductor service.
     pub conductor_rpc_url: Option<Url>,
+    /// The timeout for RPC requests to the conductor.
+    pub conductor_rpc_timeout: Duration,
     /// The confirmation delay for the sequencer.
     pub l1_conf_delay: u64,
 }
+
+impl Default for SequencerConfig {
+    fn default() -> Self {
+        Self {
+            sequencer_stopped: false,
+            sequencer_recovery_mode: false,
+            conductor_rpc_url: None,
+            conductor_rpc_timeout: DEFAULT_CONDUCTOR_RPC_TIMEOUT,
+            l1_conf_delay: 0,
+        }
+    }
+}
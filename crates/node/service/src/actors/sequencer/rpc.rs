@@ -3,7 +3,7 @@
 
 use kona_derive::AttributesBuilder;
 use kona_protocol::L2BlockInfo;
-use kona_rpc::SequencerAdminQuery;
+use kona_rpc::{SequencerAdminError, SequencerAdminQuery};
 use tokio::sync::watch;
 
 use crate::actors::sequencer::actor::SequencerActorState;
@@ -28,16 +28,25 @@ impl<AB: AttributesBuilder> SequencerActorState<AB> {
             SequencerAdminQuery::SequencerActive(tx) => {
                 tx.send(self.is_active).map_err(|_| SequencerRpcError::SendResponse)?;
             }
-            SequencerAdminQuery::StartSequencer => {
-                info!(target: "sequencer", "Starting sequencer");
-                self.is_active = true;
+            SequencerAdminQuery::StartSequencer(tx) => {
+                let result = if self.is_active {
+                    Err(SequencerAdminError::AlreadyRunning)
+                } else {
+                    info!(target: "sequencer", "Starting sequencer");
+                    self.is_active = true;
+                    Ok(())
+                };
+                tx.send(result).map_err(|_| SequencerRpcError::SendResponse)?;
             }
             SequencerAdminQuery::StopSequencer(tx) => {
-                info!(target: "sequencer", "Stopping sequencer");
-                self.is_active = false;
-
-                tx.send(unsafe_head.borrow().hash())
-                    .map_err(|_| SequencerRpcError::SendResponse)?;
+                let result = if self.is_active {
+                    info!(target: "sequencer", "Stopping sequencer");
+                    self.is_active = false;
+                    Ok(unsafe_head.borrow().hash())
+                } else {
+                    Err(SequencerAdminError::NotRunning)
+                };
+                tx.send(result).map_err(|_| SequencerRpcError::SendResponse)?;
             }
             SequencerAdminQuery::ConductorEnabled(tx) => {
                 tx.send(self.conductor.is_some()).map_err(|_| SequencerRpcError::SendResponse)?;
@@ -46,13 +55,22 @@ impl<AB: AttributesBuilder> SequencerActorState<AB> {
                 self.is_recovery_mode = is_active;
                 info!(target: "sequencer", is_active, "Updated recovery mode");
             }
-            SequencerAdminQuery::OverrideLeader => {
-                if let Some(conductor) = self.conductor.as_mut() {
-                    if let Err(e) = conductor.override_leader().await {
-                        error!(target: "sequencer::rpc", "Failed to override leader: {}", e);
-                    }
-                    info!(target: "sequencer", "Overrode leader via the conductor service");
-                }
+            SequencerAdminQuery::OverrideLeader(tx) => {
+                let result = match self.conductor.as_mut() {
+                    Some(conductor) => match conductor.override_leader().await {
+                        Ok(()) => {
+                            info!(target: "sequencer", "Overrode leader via the conductor service");
+                            conductor.clear_cached_leader();
+                            Ok(())
+                        }
+                        Err(e) => {
+                            error!(target: "sequencer::rpc", "Failed to override leader: {}", e);
+                            Err(SequencerAdminError::Conductor(e.to_string()))
+                        }
+                    },
+                    None => Err(SequencerAdminError::ConductorDisabled),
+                };
+                tx.send(result).map_err(|_| SequencerRpcError::SendResponse)?;
             }
         }
 
@@ -0,0 +1,162 @@
+//! [NodeActor] implementation tracking the unsafe head of a node that runs without an engine.
+
+use crate::{NodeActor, actors::CancellableContext};
+use alloy_eips::BlockNumHash;
+use async_trait::async_trait;
+use kona_engine::Metrics;
+use op_alloy_rpc_types_engine::OpExecutionPayloadEnvelope;
+use thiserror::Error;
+use tokio::{select, sync::mpsc};
+use tokio_util::sync::{CancellationToken, WaitForCancellationFuture};
+
+/// The [NodeActor] tracking the unsafe head from the unsafe blocks received over gossip.
+///
+/// In [`NodeMode::P2pOnly`] mode, the node relays unsafe blocks without an engine to insert them
+/// into. The highest received block is tracked as the unsafe head instead, and reported through
+/// the unsafe block label metric.
+///
+/// [`NodeMode::P2pOnly`]: crate::NodeMode::P2pOnly
+#[derive(Debug)]
+pub struct UnsafeHeadTracker {
+    /// The channel to receive the unsafe blocks from the network actor.
+    unsafe_block_rx: mpsc::Receiver<OpExecutionPayloadEnvelope>,
+    /// The highest unsafe block received.
+    unsafe_head: Option<BlockNumHash>,
+}
+
+/// The communication context used by the [`UnsafeHeadTracker`].
+#[derive(Debug)]
+pub struct UnsafeHeadTrackerContext {
+    /// The cancellation token, shared between all tasks.
+    pub cancellation: CancellationToken,
+}
+
+impl CancellableContext for UnsafeHeadTrackerContext {
+    fn cancelled(&self) -> WaitForCancellationFuture<'_> {
+        self.cancellation.cancelled()
+    }
+}
+
+/// An error returned by the [`UnsafeHeadTracker`].
+#[derive(Error, Debug)]
+pub enum UnsafeHeadTrackerError {
+    /// The unsafe block channel was closed.
+    #[error("The unsafe block channel was closed")]
+    ChannelClosed,
+}
+
+impl UnsafeHeadTracker {
+    /// Creates a new [`UnsafeHeadTracker`], returning the sender of the unsafe blocks to track.
+    pub fn new() -> (mpsc::Sender<OpExecutionPayloadEnvelope>, Self) {
+        let (unsafe_block_tx, unsafe_block_rx) = mpsc::channel(1024);
+        (unsafe_block_tx, Self { unsafe_block_rx, unsafe_head: None })
+    }
+
+    /// Returns the highest unsafe block received.
+    pub const fn unsafe_head(&self) -> Option<BlockNumHash> {
+        self.unsafe_head
+    }
+
+    /// Updates the unsafe head with the given block, returning `true` if it is higher than the
+    /// current unsafe head.
+    fn track(&mut self, envelope: &OpExecutionPayloadEnvelope) -> bool {
+        let block = BlockNumHash::new(
+            envelope.execution_payload.block_number(),
+            envelope.execution_payload.block_hash(),
+        );
+        if self.unsafe_head.is_some_and(|head| head.number >= block.number) {
+            return false;
+        }
+        self.unsafe_head = Some(block);
+        true
+    }
+}
+
+#[async_trait]
+impl NodeActor for UnsafeHeadTracker {
+    type Error = UnsafeHeadTrackerError;
+    type OutboundData = UnsafeHeadTrackerContext;
+    type InboundData = mpsc::Sender<OpExecutionPayloadEnvelope>;
+    type Builder = ();
+
+    fn build(_: Self::Builder) -> (Self::InboundData, Self) {
+        Self::new()
+    }
+
+    async fn start(
+        mut self,
+        UnsafeHeadTrackerContext { cancellation }: Self::OutboundData,
+    ) -> Result<(), Self::Error> {
+        loop {
+            select! {
+                _ = cancellation.cancelled() => {
+                    info!(target: "unsafe_head", "Received shutdown signal. Exiting unsafe head tracker task.");
+                    return Ok(());
+                }
+                envelope = self.unsafe_block_rx.recv() => {
+                    let Some(envelope) = envelope else {
+                        error!(target: "unsafe_head", "Unsafe block channel closed unexpectedly");
+                        cancellation.cancel();
+                        return Err(UnsafeHeadTrackerError::ChannelClosed);
+                    };
+
+                    if !self.track(&envelope) {
+                        debug!(
+                            target: "unsafe_head",
+                            number = envelope.execution_payload.block_number(),
+                            "Ignoring unsafe block at or below the unsafe head"
+                        );
+                        continue;
+                    }
+
+                    let number = envelope.execution_payload.block_number();
+                    info!(
+                        target: "unsafe_head",
+                        number,
+                        hash = %envelope.execution_payload.block_hash(),
+                        "Updated unsafe head"
+                    );
+                    kona_macros::set!(
+                        gauge,
+                        Metrics::BLOCK_LABELS,
+                        "label",
+                        Metrics::UNSAFE_BLOCK_LABEL,
+                        number as f64
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::B256;
+    use alloy_rpc_types_engine::ExecutionPayloadV1;
+    use arbitrary::Arbitrary;
+    use op_alloy_rpc_types_engine::OpExecutionPayload;
+
+    fn payload(number: u64, hash: u8) -> OpExecutionPayloadEnvelope {
+        let mut payload =
+            ExecutionPayloadV1::arbitrary(&mut arbitrary::Unstructured::new(&[])).unwrap();
+        payload.block_number = number;
+        payload.block_hash = B256::with_last_byte(hash);
+        OpExecutionPayloadEnvelope {
+            execution_payload: OpExecutionPayload::V1(payload),
+            parent_beacon_block_root: None,
+        }
+    }
+
+    #[test]
+    fn test_track_unsafe_head() {
+        let (_, mut tracker) = UnsafeHeadTracker::new();
+        assert!(tracker.track(&payload(2, 2)));
+        assert!(!tracker.track(&payload(1, 1)));
+        assert!(!tracker.track(&payload(2, 3)));
+        assert_eq!(tracker.unsafe_head(), Some(BlockNumHash::new(2, B256::with_last_byte(2))));
+
+        assert!(tracker.track(&payload(3, 3)));
+        assert_eq!(tracker.unsafe_head(), Some(BlockNumHash::new(3, B256::with_last_byte(3))));
+    }
+}
@@ -10,20 +10,25 @@
 extern crate tracing;
 
 mod service;
-pub use service::{InteropMode, NodeMode, RollupNode, RollupNodeBuilder, RollupNodeService};
+pub use service::{
+    DEFAULT_SHUTDOWN_TIMEOUT, InteropMode, NodeMode, RollupNode, RollupNodeBuilder,
+    RollupNodeService, SyncMode,
+};
 
 mod actors;
 pub use actors::{
-    AttributesBuilderConfig, CancellableContext, ConductorClient, ConductorError,
+    AttributesBuilderConfig, BuildRequest, CancellableContext, ConductorClient, ConductorError,
     DelayedL1OriginSelectorProvider, DerivationActor, DerivationBuilder, DerivationContext,
     DerivationError, DerivationInboundChannels, DerivationState, EngineActor, EngineBuilder,
     EngineContext, EngineError, EngineInboundData, InboundDerivationMessage, L1OriginSelector,
-    L1OriginSelectorError, L1OriginSelectorProvider, L1WatcherRpc, L1WatcherRpcContext,
-    L1WatcherRpcError, L1WatcherRpcInboundChannels, L1WatcherRpcState, L2Finalizer, NetworkActor,
-    NetworkActorError, NetworkBuilder, NetworkBuilderError, NetworkConfig, NetworkContext,
-    NetworkDriver, NetworkDriverError, NetworkHandler, NetworkInboundData, NodeActor,
-    PipelineBuilder, RpcActor, RpcActorError, RpcContext, SequencerActor, SequencerActorError,
-    SequencerBuilder, SequencerConfig, SequencerContext, SequencerInboundData,
+    L1OriginSelectorError, L1OriginSelectorProvider, L1PrefetchConfig, L1WatcherRpc,
+    L1WatcherRpcContext, L1WatcherRpcError, L1WatcherRpcInboundChannels, L1WatcherRpcState,
+    L2Finalizer, L2PayloadSource, NetworkActor, NetworkActorError, NetworkBuilder,
+    NetworkBuilderError, NetworkConfig, NetworkContext, NetworkDriver, NetworkDriverError,
+    NetworkHandler, NetworkInboundData, NodeActor, PipelineBuilder, RpcActor, RpcActorError,
+    RpcContext, SequencerActor, SequencerActorError, SequencerBuilder, SequencerConfig,
+    SequencerContext, SequencerInboundData, UnsafeHeadTracker, UnsafeHeadTrackerContext,
+    UnsafeHeadTrackerError,
 };
 
 mod metrics;
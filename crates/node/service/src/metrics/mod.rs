@@ -11,6 +11,13 @@ impl Metrics {
     /// Identifier for the counter that tracks the L1 origin of the derivation pipeline.
     pub const DERIVATION_L1_ORIGIN: &str = "kona_node_derivation_l1_origin";
 
+    /// Identifier for the gauge that tracks the number of the latest L1 head block.
+    pub const L1_HEAD: &str = "kona_node_l1_head";
+
+    /// Identifier for the gauge that tracks how many blocks the L1 origin of the derivation
+    /// pipeline lags behind the L1 head.
+    pub const DERIVATION_L1_ORIGIN_LAG: &str = "kona_node_derivation_l1_origin_lag";
+
     /// Identifier for the counter of critical derivation errors (strictly for alerting.)
     pub const DERIVATION_CRITICAL_ERROR: &str = "kona_node_derivation_critical_errors";
 
@@ -49,6 +56,16 @@ impl Metrics {
         // Derivation L1 origin
         metrics::describe_counter!(Self::DERIVATION_L1_ORIGIN, "Derivation pipeline L1 origin");
 
+        // L1 head
+        metrics::describe_gauge!(Self::L1_HEAD, "Latest L1 head block number");
+
+        // Derivation L1 origin lag
+        metrics::describe_gauge!(
+            Self::DERIVATION_L1_ORIGIN_LAG,
+            metrics::Unit::Count,
+            "Number of blocks the derivation pipeline L1 origin lags behind the L1 head"
+        );
+
         // Derivation critical error
         metrics::describe_counter!(
             Self::DERIVATION_CRITICAL_ERROR,
@@ -1,7 +1,8 @@
 //! The core [`RollupNodeService`] trait
 use crate::{
     AttributesBuilderConfig, DerivationContext, EngineContext, L1WatcherRpcContext, NetworkContext,
-    NodeActor, NodeMode, RpcContext, SequencerContext, SequencerInboundData,
+    NodeActor, NodeMode, RpcContext, SequencerContext, SequencerInboundData, UnsafeHeadTracker,
+    UnsafeHeadTrackerContext,
     actors::{
         DerivationInboundChannels, EngineInboundData, L1WatcherRpcInboundChannels,
         NetworkInboundData, PipelineBuilder,
@@ -10,9 +11,12 @@ use crate::{
 };
 use async_trait::async_trait;
 use kona_derive::{AttributesBuilder, Pipeline, SignalReceiver};
-use std::fmt::Display;
+use std::{fmt::Display, time::Duration};
 use tokio_util::sync::CancellationToken;
 
+/// The default time given to the actors to shut down once the node is cancelled.
+pub const DEFAULT_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(30);
+
 /// The [`RollupNodeService`] trait defines the common interface for running a rollup node.
 ///
 /// ## Validator Mode
@@ -34,6 +38,16 @@ use tokio_util::sync::CancellationToken;
 /// the network over p2p gossip. The node also listens for L1 finalized block updates and finalizes
 /// `safe` blocks that it has derived when L1 finalized block updates are received.
 ///
+/// ## P2P-Only Mode
+///
+/// In p2p-only mode, the node relays unsafe L2 blocks over p2p gossip and tracks the unsafe head,
+/// without running the engine or the derivation pipeline.
+///
+/// ## Derive-Only Mode
+///
+/// In derive-only mode, the node derives the L2 chain from the DA layer like a validator, without
+/// joining the p2p network.
+///
 /// ## Types
 ///
 /// - `DataAvailabilityWatcher`: The type of [`NodeActor`] to use for the DA watcher service.
@@ -101,10 +115,19 @@ pub trait RollupNodeService {
     /// Returns the sequencer builder for the node.
     fn sequencer_builder(&self) -> <Self::SequencerActor as NodeActor>::Builder;
 
-    /// Starts the rollup node service.
-    async fn start(&self) -> Result<(), String> {
-        // Create a global cancellation token for graceful shutdown of tasks.
-        let cancellation = CancellationToken::new();
+    /// Returns the time given to the actors to finish their current work and flush their state
+    /// once the node is shut down, after which they are aborted.
+    fn shutdown_timeout(&self) -> Duration {
+        DEFAULT_SHUTDOWN_TIMEOUT
+    }
+
+    /// Starts the rollup node service, running until the given cancellation token is cancelled or
+    /// an actor fails.
+    ///
+    /// The cancellation token is shared by all actors: cancelling it gracefully shuts the node
+    /// down, waiting up to [`Self::shutdown_timeout`] for the actors to stop.
+    async fn start(&self, cancellation: CancellationToken) -> Result<(), String> {
+        let mode = self.mode();
 
         // Create the DA watcher actor.
         let (L1WatcherRpcInboundChannels { inbound_queries: da_watcher_rpc }, da_watcher) =
@@ -117,6 +140,7 @@ pub trait RollupNodeService {
                 l1_head_updates_tx,
                 engine_l2_safe_head_tx,
                 el_sync_complete_tx,
+                inbound_queries: derivation_rpc,
             },
             derivation,
         ) = Self::DerivationActor::build(self.derivation_builder());
@@ -127,9 +151,11 @@ pub trait RollupNodeService {
                 build_request_tx,
                 attributes_tx,
                 unsafe_block_tx,
+                unsafe_import_backpressure,
                 reset_request_tx,
                 inbound_queries_tx: engine_rpc,
                 finalized_l1_block_tx,
+                conditional_tx_tx,
             },
             engine,
         ) = Self::EngineActor::build(self.engine_builder());
@@ -141,6 +167,7 @@ pub trait RollupNodeService {
                 p2p_rpc: network_rpc,
                 gossip_payload_tx,
                 admin_rpc: net_admin_rpc,
+                sync_request_tx,
             },
             network,
         ) = Self::NetworkActor::build(self.network_builder());
@@ -148,14 +175,27 @@ pub trait RollupNodeService {
         // Create the RPC server actor.
         let (_, rpc) = self.rpc_builder().map(Self::RpcActor::build).unzip();
 
-        let (sequencer_inbound_data, sequencer) = self
-            .mode()
+        let (sequencer_inbound_data, sequencer) = mode
             .is_sequencer()
             .then_some(Self::SequencerActor::build(self.sequencer_builder()))
             .unzip();
 
+        // Without an engine, the unsafe blocks received over gossip are tracked instead of being
+        // inserted into the execution layer.
+        let (network_blocks, import_backpressure, unsafe_head_tracker) = if mode.runs_derivation() {
+            (unsafe_block_tx, Some(unsafe_import_backpressure), None)
+        } else {
+            let (blocks, tracker) = UnsafeHeadTracker::build(());
+            (blocks, None, Some(tracker))
+        };
+
+        // In derive-only mode, no network actor feeds the engine with unsafe blocks. The sender is
+        // held until the actors stop, so that the engine does not see its channel as closed.
+        let _unsafe_block_tx = (!mode.runs_p2p()).then(|| network_blocks.clone());
+
         spawn_and_wait!(
             cancellation,
+            shutdown_timeout = self.shutdown_timeout(),
             actors = [
                 rpc.map(|r| (
                     r,
@@ -164,8 +204,10 @@ pub trait RollupNodeService {
                         p2p_network: network_rpc,
                         network_admin: net_admin_rpc,
                         sequencer_admin: sequencer_inbound_data.as_ref().map(|s| s.admin_query_tx.clone()),
+                        conditional_tx: conditional_tx_tx,
                         l1_watcher_queries: da_watcher_rpc,
                         engine_query: engine_rpc,
+                        derivation_queries: derivation_rpc,
                     }
                 )),
                 sequencer.map(|s| (
@@ -180,9 +222,17 @@ pub trait RollupNodeService {
                         cancellation: cancellation.clone(),
                     })
                 ),
-                Some((
+                mode.runs_p2p().then_some((
                     network,
-                    NetworkContext { blocks: unsafe_block_tx, cancellation: cancellation.clone() }
+                    NetworkContext {
+                        blocks: network_blocks,
+                        import_backpressure,
+                        cancellation: cancellation.clone(),
+                    }
+                )),
+                unsafe_head_tracker.map(|t| (
+                    t,
+                    UnsafeHeadTrackerContext { cancellation: cancellation.clone() }
                 )),
                 Some((
                     da_watcher,
@@ -193,20 +243,21 @@ pub trait RollupNodeService {
                         cancellation: cancellation.clone(),
                     })
                 ),
-                Some((
+                mode.runs_derivation().then_some((
                     derivation,
                     DerivationContext {
                         reset_request_tx: reset_request_tx.clone(),
                         derived_attributes_tx: attributes_tx,
                         cancellation: cancellation.clone(),
                 })),
-                Some((engine,
+                mode.runs_derivation().then_some((engine,
                     EngineContext {
                         engine_l2_safe_head_tx,
                         engine_unsafe_head_tx: sequencer_inbound_data
                             .map(|s| s.unsafe_head_tx),
                         sync_complete_tx: el_sync_complete_tx,
                         derivation_signal_tx,
+                        sync_request_tx,
                         cancellation: cancellation.clone(),
                     })
                 ),
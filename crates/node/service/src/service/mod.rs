@@ -4,13 +4,13 @@
 //! [`NodeActor`]: crate::NodeActor
 
 mod core;
-pub use core::RollupNodeService;
+pub use core::{DEFAULT_SHUTDOWN_TIMEOUT, RollupNodeService};
 
 mod standard;
 pub use standard::{RollupNode, RollupNodeBuilder};
 
 mod mode;
-pub use mode::{InteropMode, NodeMode};
+pub use mode::{InteropMode, NodeMode, SyncMode};
 
 pub(crate) mod util;
 pub(crate) use util::spawn_and_wait;
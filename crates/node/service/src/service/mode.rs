@@ -11,9 +11,10 @@
     PartialEq,
     Eq,
     derive_more::Display,
-    derive_more::FromStr,
+    strum::EnumString,
     strum::EnumIter,
 )]
+#[strum(ascii_case_insensitive)]
 pub enum NodeMode {
     /// Validator mode.
     #[display("Validator")]
@@ -22,6 +23,16 @@ pub enum NodeMode {
     /// Sequencer mode.
     #[display("Sequencer")]
     Sequencer,
+    /// P2P-only mode. The node relays unsafe blocks over gossip and tracks the unsafe head,
+    /// without an engine or a derivation pipeline. Used to run lightweight sentry nodes.
+    #[display("p2p-only")]
+    #[strum(serialize = "p2p-only")]
+    P2pOnly,
+    /// Derivation-only mode. The node derives the L2 chain from L1 without joining the P2P
+    /// network. Used to run archival derivers.
+    #[display("derive-only")]
+    #[strum(serialize = "derive-only")]
+    DeriveOnly,
 }
 
 impl NodeMode {
@@ -34,6 +45,26 @@ impl NodeMode {
     pub const fn is_sequencer(&self) -> bool {
         matches!(self, Self::Sequencer)
     }
+
+    /// Returns `true` if [`Self`] is [`Self::P2pOnly`].
+    pub const fn is_p2p_only(&self) -> bool {
+        matches!(self, Self::P2pOnly)
+    }
+
+    /// Returns `true` if [`Self`] is [`Self::DeriveOnly`].
+    pub const fn is_derive_only(&self) -> bool {
+        matches!(self, Self::DeriveOnly)
+    }
+
+    /// Returns `true` if the node runs the engine and the derivation pipeline in this mode.
+    pub const fn runs_derivation(&self) -> bool {
+        !self.is_p2p_only()
+    }
+
+    /// Returns `true` if the node joins the P2P network in this mode.
+    pub const fn runs_p2p(&self) -> bool {
+        !self.is_derive_only()
+    }
 }
 
 /// The [`InteropMode`] enum represents how the node works with interop.
@@ -47,3 +78,42 @@ pub enum InteropMode {
     #[display("Indexed")]
     Indexed,
 }
+
+/// The [`SyncMode`] enum represents how the node brings the execution layer up to the tip of the
+/// L2 chain.
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    derive_more::Display,
+    strum::EnumString,
+    strum::EnumIter,
+)]
+#[strum(serialize_all = "kebab-case")]
+pub enum SyncMode {
+    /// The execution layer syncs to the unsafe head via its own P2P network, as instructed by
+    /// forkchoice updates over the Engine API. Derivation starts once the execution layer has
+    /// finished syncing, and is used to check the consistency of the synced chain.
+    #[display("execution-layer")]
+    #[default]
+    ExecutionLayer,
+    /// The L2 chain is derived from L1, starting from the execution layer's current head. Unsafe
+    /// blocks are only inserted if they extend the unsafe head.
+    #[display("consensus-layer")]
+    ConsensusLayer,
+}
+
+impl SyncMode {
+    /// Returns `true` if [`Self`] is [`Self::ExecutionLayer`].
+    pub const fn is_execution_layer(&self) -> bool {
+        matches!(self, Self::ExecutionLayer)
+    }
+
+    /// Returns `true` if [`Self`] is [`Self::ConsensusLayer`].
+    pub const fn is_consensus_layer(&self) -> bool {
+        matches!(self, Self::ConsensusLayer)
+    }
+}
@@ -1,23 +1,25 @@
 //! Contains the builder for the [`RollupNode`].
 
-use crate::{EngineBuilder, InteropMode, NetworkConfig, NodeMode, RollupNode, SequencerConfig};
-use alloy_primitives::Bytes;
+use crate::{
+    DEFAULT_SHUTDOWN_TIMEOUT, EngineBuilder, InteropMode, L1PrefetchConfig, NetworkConfig,
+    NodeMode, RollupNode, SequencerConfig, SyncMode,
+};
 use alloy_provider::RootProvider;
 use alloy_rpc_client::RpcClient;
 use alloy_rpc_types_engine::JwtSecret;
-use alloy_transport_http::{
-    AuthLayer, Http, HyperClient,
-    hyper_util::{client::legacy::Client, rt::TokioExecutor},
-};
-use http_body_util::Full;
-use op_alloy_network::Optimism;
-use std::sync::Arc;
-use tower::ServiceBuilder;
+use alloy_transport::Transport;
+use alloy_transport_http::Http;
+use std::{num::NonZeroU32, path::PathBuf, sync::Arc, time::Duration};
 use url::Url;
 
+use kona_engine::ReloadableJwtSecret;
 use kona_genesis::{L1ChainConfig, RollupConfig};
-use kona_providers_alloy::OnlineBeaconClient;
+use kona_providers_alloy::{
+    BeaconRedundancy, FailoverConfig, FailoverTransport, RedundantBeaconClient, ThrottleConfig,
+    ThrottledTransport,
+};
 use kona_rpc::RpcBuilder;
+use kona_sources::TrustedCheckpoint;
 
 /// The [`RollupNodeBuilder`] is used to construct a [`RollupNode`] service.
 #[derive(Debug, Default)]
@@ -28,16 +30,36 @@ pub struct RollupNodeBuilder {
     l1_config: L1ChainConfig,
     /// The L1 EL provider RPC URL.
     l1_provider_rpc_url: Option<Url>,
+    /// The L1 EL provider RPC URLs to fail over to when the primary is unavailable.
+    l1_provider_fallback_rpc_urls: Vec<Url>,
+    /// The maximum number of requests per second sent to the L1 EL provider.
+    l1_provider_rate_limit: Option<NonZeroU32>,
+    /// The L1 EL WebSocket or IPC endpoint used to subscribe to new heads.
+    l1_provider_subscription_rpc: Option<String>,
+    /// The number of confirmations after which an L1 block is considered finalized.
+    l1_finality_depth: Option<u64>,
+    /// The number of L1 blocks prefetched ahead of the derivation cursor.
+    l1_prefetch_lookahead: u64,
     /// Whether to trust the L1 RPC.
     l1_trust_rpc: bool,
     /// The L1 beacon API URL.
     l1_beacon_api_url: Option<Url>,
+    /// The L1 beacon API URLs used when the primary beacon API cannot serve valid blobs.
+    l1_beacon_fallback_api_urls: Vec<Url>,
+    /// How the L1 beacon APIs are used.
+    l1_beacon_redundancy: BeaconRedundancy,
     /// The L2 engine RPC URL.
     l2_engine_rpc_url: Option<Url>,
+    /// The standby L2 engine RPC URLs to fail over to when the L2 engine is unavailable.
+    l2_standby_engine_rpc_urls: Vec<Url>,
+    /// The engine RPC URL of a secondary L2 execution layer cross-checking unsafe payloads.
+    l2_verifier_engine_rpc_url: Option<Url>,
     /// Whether to trust the L2 RPC.
     l2_trust_rpc: bool,
     /// The JWT secret.
     jwt_secret: Option<JwtSecret>,
+    /// The file the JWT secret was read from, reloaded whenever it changes.
+    jwt_secret_file: Option<PathBuf>,
     /// The [`NetworkConfig`].
     p2p_config: Option<NetworkConfig>,
     /// An RPC Configuration.
@@ -46,8 +68,24 @@ pub struct RollupNodeBuilder {
     sequencer_config: Option<SequencerConfig>,
     /// The mode to run the node in.
     mode: NodeMode,
+    /// The mode to sync the execution layer in.
+    sync_mode: SyncMode,
+    /// The interval within which forkchoice updates of inserted unsafe blocks are batched.
+    forkchoice_debounce: Option<Duration>,
+    /// The path of the database recording the safe head derived from each L1 block.
+    safe_db_path: Option<PathBuf>,
+    /// The maximum number of unsafe blocks that may be reorged out by the safe chain.
+    max_reorg_depth: Option<u64>,
+    /// The trusted L2 checkpoint to start the sync from.
+    trusted_checkpoint: Option<TrustedCheckpoint>,
+    /// The path of the file persisting the safe and finalized heads.
+    anchor_path: Option<PathBuf>,
+    /// Whether to start on an execution layer that lost the persisted safe and finalized heads.
+    anchor_reorg: bool,
     /// Whether to run the node in interop mode.
     interop_mode: InteropMode,
+    /// The time given to the actors to shut down once the node is cancelled.
+    shutdown_timeout: Option<Duration>,
 }
 
 impl RollupNodeBuilder {
@@ -61,11 +99,90 @@ impl RollupNodeBuilder {
         Self { mode, ..self }
     }
 
+    /// Sets the [`SyncMode`] on the [`RollupNodeBuilder`].
+    pub fn with_sync_mode(self, sync_mode: SyncMode) -> Self {
+        Self { sync_mode, ..self }
+    }
+
+    /// Sets the interval within which forkchoice updates of inserted unsafe blocks are batched.
+    /// If [`None`], a forkchoice update is sent for every inserted unsafe block.
+    pub fn with_forkchoice_debounce(self, forkchoice_debounce: Option<Duration>) -> Self {
+        Self { forkchoice_debounce, ..self }
+    }
+
+    /// Sets the path of the database recording the safe head derived from each L1 block. If
+    /// [`None`], safe heads are not recorded, and `optimism_safeHeadAtL1Block` is unavailable.
+    pub fn with_safe_db_path(self, safe_db_path: Option<PathBuf>) -> Self {
+        Self { safe_db_path, ..self }
+    }
+
+    /// Sets the maximum number of unsafe blocks that may be reorged out when the safe chain
+    /// diverges from the unsafe chain. Deeper reorgs halt the node. If [`None`], reorgs of any
+    /// depth are applied.
+    pub fn with_max_reorg_depth(self, max_reorg_depth: Option<u64>) -> Self {
+        Self { max_reorg_depth, ..self }
+    }
+
+    /// Sets the [`TrustedCheckpoint`] to start the sync from, instead of searching the execution
+    /// layer for a safe block. If [`None`], the sync starts from the forkchoice state of the
+    /// execution layer.
+    pub fn with_trusted_checkpoint(self, trusted_checkpoint: Option<TrustedCheckpoint>) -> Self {
+        Self { trusted_checkpoint, ..self }
+    }
+
+    /// Sets the path of the file persisting the safe and finalized heads. On startup, the
+    /// execution layer must still contain the persisted heads. If [`None`], the heads are not
+    /// persisted or checked.
+    pub fn with_anchor_path(self, anchor_path: Option<PathBuf>) -> Self {
+        Self { anchor_path, ..self }
+    }
+
+    /// Sets whether to start even though the execution layer lost the persisted safe and
+    /// finalized heads, reorging the node to the chain of the execution layer.
+    pub fn with_anchor_reorg(self, anchor_reorg: bool) -> Self {
+        Self { anchor_reorg, ..self }
+    }
+
     /// Appends an L1 EL provider RPC URL to the builder.
     pub fn with_l1_provider_rpc_url(self, l1_provider_rpc_url: Url) -> Self {
         Self { l1_provider_rpc_url: Some(l1_provider_rpc_url), ..self }
     }
 
+    /// Sets the L1 EL provider RPC URLs to fail over to when the primary is unavailable.
+    pub fn with_l1_provider_fallback_rpc_urls(
+        self,
+        l1_provider_fallback_rpc_urls: Vec<Url>,
+    ) -> Self {
+        Self { l1_provider_fallback_rpc_urls, ..self }
+    }
+
+    /// Sets the maximum number of requests per second sent to the L1 EL provider. Identical
+    /// concurrent requests to a rate limited provider are coalesced.
+    pub fn with_l1_provider_rate_limit(self, l1_provider_rate_limit: Option<NonZeroU32>) -> Self {
+        Self { l1_provider_rate_limit, ..self }
+    }
+
+    /// Sets the L1 EL WebSocket or IPC endpoint used to subscribe to new heads and system config
+    /// logs. If unset, or if the subscription fails, the L1 EL provider is polled instead.
+    pub fn with_l1_provider_subscription_rpc(
+        self,
+        l1_provider_subscription_rpc: Option<String>,
+    ) -> Self {
+        Self { l1_provider_subscription_rpc, ..self }
+    }
+
+    /// Sets the number of confirmations after which an L1 block is considered finalized, for L1
+    /// chains without a finality gadget. If [`None`], the L1 `finalized` block tag is used.
+    pub fn with_l1_finality_depth(self, l1_finality_depth: Option<u64>) -> Self {
+        Self { l1_finality_depth, ..self }
+    }
+
+    /// Sets the number of L1 blocks whose data is prefetched ahead of the derivation cursor. A
+    /// lookahead of zero disables prefetching.
+    pub fn with_l1_prefetch_lookahead(self, l1_prefetch_lookahead: u64) -> Self {
+        Self { l1_prefetch_lookahead, ..self }
+    }
+
     /// Sets whether to trust the L1 RPC.
     pub fn with_l1_trust_rpc(self, l1_trust_rpc: bool) -> Self {
         Self { l1_trust_rpc, ..self }
@@ -76,6 +193,16 @@ impl RollupNodeBuilder {
         Self { l1_beacon_api_url: Some(l1_beacon_api_url), ..self }
     }
 
+    /// Sets the L1 beacon API URLs queried when the primary beacon API cannot serve valid blobs.
+    pub fn with_l1_beacon_fallback_api_urls(self, l1_beacon_fallback_api_urls: Vec<Url>) -> Self {
+        Self { l1_beacon_fallback_api_urls, ..self }
+    }
+
+    /// Sets the [`BeaconRedundancy`] mode of the L1 beacon APIs.
+    pub fn with_l1_beacon_redundancy(self, l1_beacon_redundancy: BeaconRedundancy) -> Self {
+        Self { l1_beacon_redundancy, ..self }
+    }
+
     /// Appends an L2 engine RPC URL to the builder.
     pub fn with_l2_engine_rpc_url(self, l2_engine_rpc_url: Url) -> Self {
         Self { l2_engine_rpc_url: Some(l2_engine_rpc_url), ..self }
@@ -91,6 +218,26 @@ impl RollupNodeBuilder {
         Self { jwt_secret: Some(jwt_secret), ..self }
     }
 
+    /// Sets the file the JWT secret was read from. If set, the JWT secret is reloaded whenever
+    /// the file changes, allowing it to be rotated without restarting the node.
+    pub fn with_jwt_secret_file(self, jwt_secret_file: Option<PathBuf>) -> Self {
+        Self { jwt_secret_file, ..self }
+    }
+
+    /// Sets the standby L2 engine RPC URLs, in order of priority, to fail over to when the L2
+    /// engine is unavailable.
+    pub fn with_l2_standby_engine_rpc_urls(self, l2_standby_engine_rpc_urls: Vec<Url>) -> Self {
+        Self { l2_standby_engine_rpc_urls, ..self }
+    }
+
+    /// Sets the engine RPC URL of a secondary L2 execution layer, to which the unsafe payloads
+    /// received by the node are submitted with `engine_newPayload` to cross-check them. The
+    /// secondary execution layer must share the JWT secret of the primary. If [`None`], unsafe
+    /// payloads are not cross-checked.
+    pub fn with_l2_verifier_engine_rpc_url(self, l2_verifier_engine_rpc_url: Option<Url>) -> Self {
+        Self { l2_verifier_engine_rpc_url, ..self }
+    }
+
     /// Appends the P2P [`NetworkConfig`] to the builder.
     pub fn with_p2p_config(self, config: NetworkConfig) -> Self {
         Self { p2p_config: Some(config), ..self }
@@ -106,6 +253,35 @@ impl RollupNodeBuilder {
         Self { sequencer_config: Some(sequencer_config), ..self }
     }
 
+    /// Sets the time given to the actors to shut down, defaulting to
+    /// [`DEFAULT_SHUTDOWN_TIMEOUT`].
+    pub fn with_shutdown_timeout(self, shutdown_timeout: Duration) -> Self {
+        Self { shutdown_timeout: Some(shutdown_timeout), ..self }
+    }
+
+    /// Creates the L1 EL provider, failing over to the fallback RPC URLs and rate limiting
+    /// requests if configured.
+    fn l1_provider(
+        url: Url,
+        fallback_urls: Vec<Url>,
+        rate_limit: Option<NonZeroU32>,
+    ) -> RootProvider {
+        if fallback_urls.is_empty() && rate_limit.is_none() {
+            return RootProvider::new_http(url);
+        }
+
+        let mut transport = if fallback_urls.is_empty() {
+            Http::new(url).boxed()
+        } else {
+            let urls = std::iter::once(url).chain(fallback_urls).collect();
+            FailoverTransport::new_http(urls, FailoverConfig::default()).boxed()
+        };
+        if let Some(rate_limit) = rate_limit {
+            transport = ThrottledTransport::new(transport, ThrottleConfig::new(rate_limit)).boxed();
+        }
+        RootProvider::new(RpcClient::new(transport, false))
+    }
+
     /// Assembles the [`RollupNode`] service.
     ///
     /// ## Panics
@@ -119,22 +295,21 @@ impl RollupNodeBuilder {
     /// - The P2P config is not set.
     pub fn build(self) -> RollupNode {
         let l1_rpc_url = self.l1_provider_rpc_url.expect("l1 provider rpc url not set");
-        let l1_provider = RootProvider::new_http(l1_rpc_url.clone());
-        let l1_beacon = OnlineBeaconClient::new_http(
-            self.l1_beacon_api_url.expect("l1 beacon api url not set").to_string(),
+        let l1_provider = Self::l1_provider(
+            l1_rpc_url.clone(),
+            self.l1_provider_fallback_rpc_urls,
+            self.l1_provider_rate_limit,
         );
+        let l1_beacon_url = self.l1_beacon_api_url.expect("l1 beacon api url not set");
+        let l1_beacon = RedundantBeaconClient::new_http(
+            std::iter::once(l1_beacon_url)
+                .chain(self.l1_beacon_fallback_api_urls)
+                .map(|url| url.to_string()),
+        )
+        .with_redundancy(self.l1_beacon_redundancy);
 
         let engine_url = self.l2_engine_rpc_url.expect("l2 engine rpc url not set");
-        let jwt_secret = self.jwt_secret.expect("jwt secret not set");
-        let hyper_client = Client::builder(TokioExecutor::new()).build_http::<Full<Bytes>>();
-
-        let auth_layer = AuthLayer::new(jwt_secret);
-        let service = ServiceBuilder::new().layer(auth_layer).service(hyper_client);
-
-        let layer_transport = HyperClient::with_service(service);
-        let http_hyper = Http::with_client(layer_transport, engine_url.clone());
-        let rpc_client = RpcClient::new(http_hyper, false);
-        let l2_provider = RootProvider::<Optimism>::new(rpc_client);
+        let jwt_secret = ReloadableJwtSecret::from(self.jwt_secret.expect("jwt secret not set"));
 
         let rollup_config = Arc::new(self.config);
         let l1_config = Arc::new(self.l1_config);
@@ -142,10 +317,23 @@ impl RollupNodeBuilder {
             config: Arc::clone(&rollup_config),
             l1_rpc_url,
             engine_url,
+            standby_engine_urls: self.l2_standby_engine_rpc_urls,
+            verifier_engine_url: self.l2_verifier_engine_rpc_url,
             jwt_secret,
+            jwt_secret_file: self.jwt_secret_file,
             mode: self.mode,
+            sync_mode: self.sync_mode,
+            forkchoice_debounce: self.forkchoice_debounce,
+            safe_db_path: self.safe_db_path,
+            max_reorg_depth: self.max_reorg_depth,
+            trusted_checkpoint: self.trusted_checkpoint,
+            anchor_path: self.anchor_path,
+            anchor_reorg: self.anchor_reorg,
         };
 
+        // The L2 provider shares the engine endpoints and JWT secret of the engine client.
+        let l2_provider = engine_builder.client().l2_engine().clone();
+
         let p2p_config = self.p2p_config.expect("P2P config not set");
         let sequencer_config = self.sequencer_config.unwrap_or_default();
 
@@ -154,6 +342,10 @@ impl RollupNodeBuilder {
             l1_config,
             interop_mode: self.interop_mode,
             l1_provider,
+            l1_subscription_rpc: self.l1_provider_subscription_rpc,
+            l1_finality_depth: self.l1_finality_depth,
+            l1_prefetch: (self.l1_prefetch_lookahead > 0)
+                .then(|| L1PrefetchConfig::new(self.l1_prefetch_lookahead)),
             l1_trust_rpc: self.l1_trust_rpc,
             l1_beacon,
             l2_provider,
@@ -162,6 +354,7 @@ impl RollupNodeBuilder {
             rpc_builder: self.rpc_config,
             p2p_config,
             sequencer_config,
+            shutdown_timeout: self.shutdown_timeout.unwrap_or(DEFAULT_SHUTDOWN_TIMEOUT),
         }
     }
 }
@@ -1,19 +1,19 @@
 //! Contains the [`RollupNode`] implementation.
 use crate::{
-    DerivationActor, DerivationBuilder, EngineActor, EngineBuilder, InteropMode, L1WatcherRpc,
-    L1WatcherRpcState, NetworkActor, NetworkBuilder, NetworkConfig, NodeMode, RollupNodeBuilder,
-    RollupNodeService, RpcActor, SequencerConfig,
+    DerivationActor, DerivationBuilder, EngineActor, EngineBuilder, InteropMode, L1PrefetchConfig,
+    L1WatcherRpc, L1WatcherRpcState, L2PayloadSource, NetworkActor, NetworkBuilder, NetworkConfig,
+    NodeMode, RollupNodeBuilder, RollupNodeService, RpcActor, SequencerConfig,
     actors::{SequencerActor, SequencerBuilder},
 };
 use alloy_provider::RootProvider;
 use async_trait::async_trait;
 use kona_derive::StatefulAttributesBuilder;
 use op_alloy_network::Optimism;
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 
 use kona_genesis::{L1ChainConfig, RollupConfig};
 use kona_providers_alloy::{
-    AlloyChainProvider, AlloyL2ChainProvider, OnlineBeaconClient, OnlinePipeline,
+    AlloyChainProvider, AlloyL2ChainProvider, OnlinePipeline, RedundantBeaconClient,
 };
 use kona_rpc::RpcBuilder;
 
@@ -29,10 +29,17 @@ pub struct RollupNode {
     pub(crate) interop_mode: InteropMode,
     /// The L1 EL provider.
     pub(crate) l1_provider: RootProvider,
+    /// The L1 EL WebSocket or IPC endpoint used to subscribe to new heads and system config logs.
+    pub(crate) l1_subscription_rpc: Option<String>,
+    /// The number of confirmations after which an L1 block is considered finalized, if the L1
+    /// `finalized` block tag is not used.
+    pub(crate) l1_finality_depth: Option<u64>,
+    /// The prefetching of L1 data ahead of the derivation cursor, if enabled.
+    pub(crate) l1_prefetch: Option<L1PrefetchConfig>,
     /// Whether to trust the L1 RPC.
     pub(crate) l1_trust_rpc: bool,
     /// The L1 beacon API.
-    pub(crate) l1_beacon: OnlineBeaconClient,
+    pub(crate) l1_beacon: RedundantBeaconClient,
     /// The L2 EL provider.
     pub(crate) l2_provider: RootProvider<Optimism>,
     /// Whether to trust the L2 RPC.
@@ -45,6 +52,8 @@ pub struct RollupNode {
     pub(crate) p2p_config: NetworkConfig,
     /// The [`SequencerConfig`] for the node.
     pub(crate) sequencer_config: SequencerConfig,
+    /// The time given to the actors to shut down once the node is cancelled.
+    pub(crate) shutdown_timeout: Duration,
 }
 
 impl RollupNode {
@@ -61,7 +70,7 @@ impl RollupNodeService for RollupNode {
     type AttributesBuilder = StatefulAttributesBuilder<AlloyChainProvider, AlloyL2ChainProvider>;
     type SequencerActor = SequencerActor<SequencerBuilder>;
 
-    type DerivationPipeline = OnlinePipeline;
+    type DerivationPipeline = OnlinePipeline<RedundantBeaconClient>;
     type DerivationActor = DerivationActor<DerivationBuilder>;
 
     type RpcActor = RpcActor;
@@ -73,7 +82,14 @@ impl RollupNodeService for RollupNode {
     }
 
     fn da_watcher_builder(&self) -> L1WatcherRpcState {
-        L1WatcherRpcState { rollup: self.config.clone(), l1_provider: self.l1_provider.clone() }
+        L1WatcherRpcState {
+            rollup: self.config.clone(),
+            l1_provider: self.l1_provider.clone(),
+            l1_subscription_rpc: self.l1_subscription_rpc.clone(),
+            l1_finality_depth: self.l1_finality_depth,
+            l1_beacon: self.l1_beacon.clone(),
+            l1_prefetch: self.l1_prefetch.clone(),
+        }
     }
 
     fn engine_builder(&self) -> EngineBuilder {
@@ -92,12 +108,17 @@ impl RollupNodeService for RollupNode {
         }
     }
 
+    fn shutdown_timeout(&self) -> Duration {
+        self.shutdown_timeout
+    }
+
     fn rpc_builder(&self) -> Option<RpcBuilder> {
         self.rpc_builder.clone()
     }
 
     fn network_builder(&self) -> NetworkBuilder {
         NetworkBuilder::from(self.p2p_config.clone())
+            .with_payload_source(Arc::new(L2PayloadSource::new(self.l2_provider.clone())))
     }
 
     fn derivation_builder(&self) -> DerivationBuilder {
@@ -110,6 +131,7 @@ impl RollupNodeService for RollupNode {
             rollup_config: self.config.clone(),
             l1_config: self.l1_config.clone(),
             interop_mode: self.interop_mode,
+            l1_prefetch_cache: self.l1_prefetch.as_ref().map(|p| p.cache.clone()),
         }
     }
 }
@@ -6,11 +6,16 @@
 ///
 /// Actors are passed in as optional arguments, in case a given actor is not needed.
 ///
+/// Once the cancellation token is cancelled, the actors are given `shutdown_timeout` to finish
+/// their current work and flush their state. The actors still running after the timeout are
+/// aborted.
+///
 /// [JoinSet]: tokio::task::JoinSet
 /// [NodeActor]: crate::NodeActor
 macro_rules! spawn_and_wait {
-    ($cancellation:expr, actors = [$($actor:expr$(,)?)*]) => {
+    ($cancellation:expr, shutdown_timeout = $shutdown_timeout:expr, actors = [$($actor:expr$(,)?)*]) => {
         let mut task_handles = tokio::task::JoinSet::new();
+        let shutdown_timeout: std::time::Duration = $shutdown_timeout;
 
         // Check if the actor is present, and spawn it if it is.
         $(
@@ -33,14 +38,43 @@ macro_rules! spawn_and_wait {
             }
         )*
 
-        while let Some(result) = task_handles.join_next().await {
+        // The first actor error, returned once the other actors have shut down.
+        let mut error = None;
+        let mut shutdown_deadline = None;
+        loop {
+            let result = match shutdown_deadline {
+                Some(deadline) => match tokio::time::timeout_at(deadline, task_handles.join_next()).await {
+                    Ok(result) => result,
+                    Err(_) => {
+                        tracing::warn!(
+                            target: "rollup_node",
+                            remaining = task_handles.len(),
+                            "Actors did not shut down within {shutdown_timeout:?}, aborting"
+                        );
+                        task_handles.abort_all();
+                        return Err(error.unwrap_or_else(|| format!("Shutdown timed out after {shutdown_timeout:?}")));
+                    }
+                },
+                None => tokio::select! {
+                    result = task_handles.join_next() => result,
+                    _ = $cancellation.cancelled() => {
+                        tracing::info!(target: "rollup_node", "Shutting down actors");
+                        shutdown_deadline = Some(tokio::time::Instant::now() + shutdown_timeout);
+                        continue;
+                    }
+                },
+            };
+            let Some(result) = result else {
+                break;
+            };
+
             match result {
                 Ok(Ok(())) => { /* Actor completed successfully */ }
                 Ok(Err(e)) => {
                     tracing::error!(target: "rollup_node", "Critical error in sub-routine: {e}");
                     // Cancel all tasks and gracefully shutdown.
                     $cancellation.cancel();
-                    return Err(e);
+                    error.get_or_insert(e);
                 }
                 Err(e) => {
                     let error_msg = format!("Task join error: {e}");
@@ -48,10 +82,14 @@ macro_rules! spawn_and_wait {
                     tracing::error!(target: "rollup_node", "Task join error: {e}");
                     // Cancel all tasks and gracefully shutdown.
                     $cancellation.cancel();
-                    return Err(error_msg);
+                    error.get_or_insert(error_msg);
                 }
             }
         }
+
+        if let Some(e) = error {
+            return Err(e);
+        }
     };
 }
 
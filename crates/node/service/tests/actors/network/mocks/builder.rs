@@ -97,7 +97,7 @@ impl TestNetworkBuilder {
         let (blocks_tx, blocks_rx) = mpsc::channel(1024);
         let cancellation = CancellationToken::new();
 
-        let context = NetworkContext { blocks: blocks_tx, cancellation };
+        let context = NetworkContext { blocks: blocks_tx, import_backpressure: None, cancellation };
 
         let handle = tokio::spawn(async move { actor.start(context).await });
 
@@ -2,3 +2,6 @@
 
 /// Tests for the node actors.
 mod actors;
+
+/// Tests for the rollup node service.
+mod service;
@@ -0,0 +1,244 @@
+//! Integration tests for the rollup node service.
+
+use async_trait::async_trait;
+use kona_derive::test_utils::{TestAttributesBuilder, TestPipeline};
+use kona_node_service::{
+    AttributesBuilderConfig, CancellableContext, DerivationContext, DerivationInboundChannels,
+    DerivationState, EngineContext, EngineInboundData, L1WatcherRpcContext,
+    L1WatcherRpcInboundChannels, NetworkContext, NetworkInboundData, NodeActor, NodeMode,
+    PipelineBuilder, RollupNodeService, RpcContext, SequencerContext, SequencerInboundData,
+};
+use op_alloy_rpc_types_engine::OpExecutionPayloadEnvelope;
+use std::{marker::PhantomData, time::Duration};
+use tokio::sync::{mpsc, oneshot, watch};
+use tokio_util::sync::CancellationToken;
+
+/// Builds the inbound channels of an [`IdleActor`]. The receiving ends are dropped, as the actor
+/// never reads from them.
+trait IdleBuilder: Send + 'static {
+    /// The inbound channels of the actor.
+    type Inbound;
+
+    /// Returns the inbound channels of the actor.
+    fn inbound(self) -> Self::Inbound;
+}
+
+/// An actor that idles until the node is cancelled.
+#[derive(Debug)]
+struct IdleActor<B, C>(PhantomData<fn() -> (B, C)>);
+
+#[async_trait]
+impl<B: IdleBuilder, C: CancellableContext + 'static> NodeActor for IdleActor<B, C> {
+    type Error = String;
+    type OutboundData = C;
+    type InboundData = B::Inbound;
+    type Builder = B;
+
+    fn build(builder: Self::Builder) -> (Self::InboundData, Self) {
+        (builder.inbound(), Self(PhantomData))
+    }
+
+    async fn start(self, context: Self::OutboundData) -> Result<(), Self::Error> {
+        context.cancelled().await;
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+struct DaWatcherBuilder;
+
+impl IdleBuilder for DaWatcherBuilder {
+    type Inbound = L1WatcherRpcInboundChannels;
+
+    fn inbound(self) -> Self::Inbound {
+        L1WatcherRpcInboundChannels { inbound_queries: mpsc::channel(1).0 }
+    }
+}
+
+#[derive(Debug)]
+struct DerivationBuilder;
+
+#[async_trait]
+impl PipelineBuilder for DerivationBuilder {
+    type Pipeline = TestPipeline;
+
+    async fn build(self) -> DerivationState<Self::Pipeline> {
+        unreachable!("the idle derivation actor does not build a pipeline")
+    }
+}
+
+impl IdleBuilder for DerivationBuilder {
+    type Inbound = DerivationInboundChannels;
+
+    fn inbound(self) -> Self::Inbound {
+        DerivationInboundChannels {
+            l1_head_updates_tx: watch::channel(None).0,
+            engine_l2_safe_head_tx: watch::channel(Default::default()).0,
+            el_sync_complete_tx: oneshot::channel().0,
+            derivation_signal_tx: mpsc::channel(1).0,
+            inbound_queries: mpsc::channel(1).0,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct NetworkBuilder;
+
+impl IdleBuilder for NetworkBuilder {
+    type Inbound = NetworkInboundData;
+
+    fn inbound(self) -> Self::Inbound {
+        NetworkInboundData {
+            signer: mpsc::channel(1).0,
+            p2p_rpc: mpsc::channel(1).0,
+            admin_rpc: mpsc::channel(1).0,
+            gossip_payload_tx: mpsc::channel(1).0,
+            sync_request_tx: mpsc::channel(1).0,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct SequencerBuilder;
+
+impl AttributesBuilderConfig for SequencerBuilder {
+    type AB = TestAttributesBuilder;
+
+    fn build(self) -> Self::AB {
+        TestAttributesBuilder::default()
+    }
+}
+
+impl IdleBuilder for SequencerBuilder {
+    type Inbound = SequencerInboundData;
+
+    fn inbound(self) -> Self::Inbound {
+        SequencerInboundData {
+            unsafe_head_tx: watch::channel(Default::default()).0,
+            admin_query_tx: mpsc::channel(1).0,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct RpcBuilder;
+
+impl IdleBuilder for RpcBuilder {
+    type Inbound = ();
+
+    fn inbound(self) -> Self::Inbound {}
+}
+
+/// An engine actor that fails if its unsafe block channel is closed before the node is cancelled,
+/// as the engine actor of the node does.
+#[derive(Debug)]
+struct MockEngine {
+    unsafe_block_rx: mpsc::Receiver<OpExecutionPayloadEnvelope>,
+}
+
+#[async_trait]
+impl NodeActor for MockEngine {
+    type Error = String;
+    type OutboundData = EngineContext;
+    type InboundData = EngineInboundData;
+    type Builder = ();
+
+    fn build(_: Self::Builder) -> (Self::InboundData, Self) {
+        let (unsafe_block_tx, unsafe_block_rx) = mpsc::channel(1);
+        let inbound = EngineInboundData {
+            build_request_tx: None,
+            attributes_tx: mpsc::channel(1).0,
+            unsafe_block_tx,
+            unsafe_import_backpressure: watch::channel(None).1,
+            reset_request_tx: mpsc::channel(1).0,
+            inbound_queries_tx: mpsc::channel(1).0,
+            finalized_l1_block_tx: watch::channel(None).0,
+            conditional_tx_tx: None,
+        };
+        (inbound, Self { unsafe_block_rx })
+    }
+
+    async fn start(mut self, context: Self::OutboundData) -> Result<(), Self::Error> {
+        loop {
+            tokio::select! {
+                _ = context.cancelled() => return Ok(()),
+                block = self.unsafe_block_rx.recv() => {
+                    if block.is_none() {
+                        return Err("unsafe block channel closed".to_string());
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// A rollup node running the [`MockEngine`] alongside [`IdleActor`]s.
+#[derive(Debug)]
+struct MockNode {
+    mode: NodeMode,
+}
+
+#[async_trait]
+impl RollupNodeService for MockNode {
+    type DataAvailabilityWatcher = IdleActor<DaWatcherBuilder, L1WatcherRpcContext>;
+    type DerivationPipeline = TestPipeline;
+    type DerivationActor = IdleActor<DerivationBuilder, DerivationContext>;
+    type EngineActor = MockEngine;
+    type NetworkActor = IdleActor<NetworkBuilder, NetworkContext>;
+    type AttributesBuilder = TestAttributesBuilder;
+    type SequencerActor = IdleActor<SequencerBuilder, SequencerContext>;
+    type RpcActor = IdleActor<RpcBuilder, RpcContext>;
+
+    fn mode(&self) -> NodeMode {
+        self.mode
+    }
+
+    fn da_watcher_builder(&self) -> DaWatcherBuilder {
+        DaWatcherBuilder
+    }
+
+    fn derivation_builder(&self) -> DerivationBuilder {
+        DerivationBuilder
+    }
+
+    fn network_builder(&self) -> NetworkBuilder {
+        NetworkBuilder
+    }
+
+    fn engine_builder(&self) {}
+
+    fn rpc_builder(&self) -> Option<RpcBuilder> {
+        None
+    }
+
+    fn sequencer_builder(&self) -> SequencerBuilder {
+        SequencerBuilder
+    }
+
+    fn shutdown_timeout(&self) -> Duration {
+        Duration::from_secs(1)
+    }
+}
+
+/// Runs a node in the given mode until it is cancelled, or until an actor fails.
+async fn run_node(mode: NodeMode) -> Result<(), String> {
+    let cancellation = CancellationToken::new();
+    let node = tokio::spawn({
+        let cancellation = cancellation.clone();
+        async move { MockNode { mode }.start(cancellation).await }
+    });
+
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    cancellation.cancel();
+    node.await.unwrap()
+}
+
+#[tokio::test]
+async fn test_derive_only_node_runs_until_cancelled() {
+    run_node(NodeMode::DeriveOnly).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_validator_node_runs_until_cancelled() {
+    run_node(NodeMode::Validator).await.unwrap();
+}
@@ -7,11 +7,15 @@
 #![cfg_attr(not(test), warn(unused_crate_dependencies))]
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 
+mod metrics;
+pub use metrics::Metrics;
+
 mod sync;
-pub use sync::{L2ForkchoiceState, SyncStartError, find_starting_forkchoice};
+pub use sync::{L2ForkchoiceState, SyncStartError, TrustedCheckpoint, find_starting_forkchoice};
 
 mod signer;
 pub use signer::{
     BlockSigner, BlockSignerError, BlockSignerHandler, BlockSignerStartError, CertificateError,
     ClientCert, RemoteSigner, RemoteSignerError, RemoteSignerHandler, RemoteSignerStartError,
+    SIGNER_HEALTH_CHECK_INTERVAL, SIGNER_HEALTH_CHECK_TIMEOUT,
 };
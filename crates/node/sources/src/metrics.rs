@@ -0,0 +1,58 @@
+//! Metrics for the node data sources.
+
+/// Container for metrics.
+#[derive(Debug, Clone)]
+pub struct Metrics;
+
+impl Metrics {
+    /// Identifier for the histogram that tracks the duration of remote signer requests.
+    pub const REMOTE_SIGNER_REQUEST_DURATION: &str = "kona_node_remote_signer_request_duration";
+
+    /// Identifier for the counter that tracks failed remote signer requests.
+    pub const REMOTE_SIGNER_ERRORS: &str = "kona_node_remote_signer_errors";
+
+    /// Identifier for the gauge that tracks whether the remote signer is healthy.
+    pub const REMOTE_SIGNER_HEALTHY: &str = "kona_node_remote_signer_healthy";
+
+    /// Health check method label.
+    pub const HEALTH_STATUS_METHOD: &str = "health_status";
+
+    /// Block signing method label.
+    pub const SIGN_BLOCK_PAYLOAD_METHOD: &str = "opsigner_signBlockPayload";
+
+    /// Initializes metrics for the node data sources.
+    ///
+    /// This does two things:
+    /// * Describes various metrics.
+    /// * Initializes metrics to 0 so they can be queried immediately.
+    #[cfg(feature = "metrics")]
+    pub fn init() {
+        Self::describe();
+        Self::zero();
+    }
+
+    /// Describes metrics used in [`kona_sources`][crate].
+    #[cfg(feature = "metrics")]
+    pub fn describe() {
+        metrics::describe_histogram!(
+            Self::REMOTE_SIGNER_REQUEST_DURATION,
+            metrics::Unit::Seconds,
+            "Duration of remote signer requests, by method"
+        );
+        metrics::describe_counter!(
+            Self::REMOTE_SIGNER_ERRORS,
+            "Number of failed remote signer requests, by method"
+        );
+        metrics::describe_gauge!(
+            Self::REMOTE_SIGNER_HEALTHY,
+            "Whether the remote signer answered its last health check"
+        );
+    }
+
+    /// Initializes metrics to `0` so they can be queried immediately by consumers of prometheus
+    /// metrics.
+    #[cfg(feature = "metrics")]
+    pub fn zero() {
+        kona_macros::set!(gauge, Self::REMOTE_SIGNER_HEALTHY, 0);
+    }
+}
@@ -9,7 +9,7 @@ use alloy_primitives::{Address, ChainId};
 use alloy_signer::{Signature, SignerSync};
 use derive_more::From;
 use op_alloy_rpc_types_engine::PayloadHash;
-use std::fmt::Debug;
+use std::{fmt::Debug, time::Duration};
 
 mod remote;
 pub use remote::{
@@ -17,6 +17,12 @@ pub use remote::{
     RemoteSignerStartError,
 };
 
+/// The interval at which the health of a remote block signer is checked.
+pub const SIGNER_HEALTH_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+/// The maximum time to wait for the health check of a remote block signer.
+pub const SIGNER_HEALTH_CHECK_TIMEOUT: Duration = Duration::from_secs(5);
+
 /// A builder for a block signer.
 #[derive(Debug, Clone, From)]
 pub enum BlockSigner {
@@ -68,6 +74,19 @@ impl BlockSigner {
 }
 
 impl BlockSignerHandler {
+    /// Returns whether the signer is a remote signer.
+    pub const fn is_remote(&self) -> bool {
+        matches!(self, Self::Remote(_))
+    }
+
+    /// Checks that the signer is able to sign blocks. Local signers are always healthy.
+    pub async fn health_check(&self) -> Result<(), BlockSignerError> {
+        match self {
+            Self::Local(_) => Ok(()),
+            Self::Remote(signer) => Ok(signer.health_status().await.map(|_| ())?),
+        }
+    }
+
     /// Signs a payload with the signer.
     pub async fn sign_block(
         &self,
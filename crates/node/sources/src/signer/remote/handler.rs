@@ -1,4 +1,4 @@
-use std::sync::Arc;
+use std::{sync::Arc, time::Instant};
 
 use alloy_primitives::{Address, B256, ChainId, SignatureError};
 use alloy_rpc_client::RpcClient;
@@ -9,6 +9,8 @@ use serde::{Deserialize, Serialize};
 use thiserror::Error;
 use tokio::sync::RwLock;
 
+use crate::Metrics;
+
 /// Request parameters for signing a block payload
 #[derive(Debug, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -57,6 +59,14 @@ pub enum RemoteSignerError {
     /// Signature error
     #[error("Signature error: {0}")]
     SignatureError(#[from] SignatureError),
+    /// The signature was not produced by the key of the remote signer.
+    #[error("Remote signer returned a signature of {recovered}, expected {expected}")]
+    UnexpectedSigner {
+        /// The address of the remote signer.
+        expected: Address,
+        /// The address recovered from the signature.
+        recovered: Address,
+    },
     /// Invalid address
     #[error(
         "Unsafe block signer address does not match remote signer address: {unsafe_block_signer} != {remote_signer}"
@@ -75,6 +85,30 @@ impl RemoteSignerHandler {
         self.watcher_handle.is_some()
     }
 
+    /// Checks the health of the remote signer, returning the version reported by the signer.
+    pub async fn health_status(&self) -> Result<String, RemoteSignerError> {
+        let start = Instant::now();
+        let result: Result<String, _> =
+            self.client.read().await.request(Metrics::HEALTH_STATUS_METHOD, ()).await;
+        Self::record_request(Metrics::HEALTH_STATUS_METHOD, start, result.is_ok());
+        kona_macros::set!(gauge, Metrics::REMOTE_SIGNER_HEALTHY, result.is_ok() as u8);
+        result.map_err(RemoteSignerError::PingError)
+    }
+
+    /// Records the duration and the outcome of a request to the remote signer.
+    fn record_request(method: &'static str, start: Instant, success: bool) {
+        kona_macros::record!(
+            histogram,
+            Metrics::REMOTE_SIGNER_REQUEST_DURATION,
+            "method",
+            method,
+            start.elapsed().as_secs_f64()
+        );
+        if !success {
+            kona_macros::inc!(counter, Metrics::REMOTE_SIGNER_ERRORS, "method" => method);
+        }
+    }
+
     /// Signs a block payload hash using the remote signer via JSON-RPC
     pub async fn sign_block_v1(
         &self,
@@ -98,14 +132,11 @@ impl RemoteSignerHandler {
         };
 
         // Make JSON-RPC call to the custom method
-        let response: SignResponse = {
-            self.client
-                .read()
-                .await
-                .request("opsigner_signBlockPayload", &params)
-                .await
-                .map_err(RemoteSignerError::SigningRPCError)?
-        };
+        let start = Instant::now();
+        let result: Result<SignResponse, _> =
+            self.client.read().await.request(Metrics::SIGN_BLOCK_PAYLOAD_METHOD, &params).await;
+        Self::record_request(Metrics::SIGN_BLOCK_PAYLOAD_METHOD, start, result.is_ok());
+        let response = result.map_err(RemoteSignerError::SigningRPCError)?;
 
         // Parse the hex signature
         let signature_bytes =
@@ -119,6 +150,50 @@ impl RemoteSignerHandler {
         let signature = Signature::from_raw(signature_bytes.as_slice())
             .map_err(RemoteSignerError::SignatureError)?;
 
+        // Peers drop blocks that are not signed by the unsafe block signer, so a signature of
+        // another key must not be gossiped.
+        verify_signer(&signature, payload_hash, chain_id, self.address)?;
+
         Ok(signature)
     }
 }
+
+/// Checks that the given signature of the payload hash was produced by the expected address.
+fn verify_signer(
+    signature: &Signature,
+    payload_hash: PayloadHash,
+    chain_id: ChainId,
+    expected: Address,
+) -> Result<(), RemoteSignerError> {
+    let recovered =
+        signature.recover_address_from_prehash(&payload_hash.signature_message(chain_id))?;
+    if recovered != expected {
+        return Err(RemoteSignerError::UnexpectedSigner { expected, recovered });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_signer::SignerSync;
+    use alloy_signer_local::PrivateKeySigner;
+
+    #[test]
+    fn test_verify_signer() {
+        const CHAIN_ID: ChainId = 10;
+        let signer = PrivateKeySigner::random();
+        let payload_hash = PayloadHash(B256::repeat_byte(0xaa));
+        let signature = signer.sign_hash_sync(&payload_hash.signature_message(CHAIN_ID)).unwrap();
+
+        assert!(verify_signer(&signature, payload_hash, CHAIN_ID, signer.address()).is_ok());
+        assert!(matches!(
+            verify_signer(&signature, payload_hash, CHAIN_ID, Address::ZERO),
+            Err(RemoteSignerError::UnexpectedSigner { recovered, .. }) if recovered == signer.address()
+        ));
+        assert!(matches!(
+            verify_signer(&signature, payload_hash, CHAIN_ID + 1, signer.address()),
+            Err(RemoteSignerError::UnexpectedSigner { .. })
+        ));
+    }
+}
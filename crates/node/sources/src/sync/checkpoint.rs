@@ -0,0 +1,323 @@
+//! Contains the [`TrustedCheckpoint`] that the sync can start from.
+
+use crate::{L2ForkchoiceState, SyncStartError, find_starting_forkchoice};
+use alloy_primitives::B256;
+use alloy_provider::{Provider, RootProvider};
+use kona_genesis::RollupConfig;
+use kona_protocol::L2BlockInfo;
+use op_alloy_network::Optimism;
+use tracing::info;
+
+/// An L2 block provided by the operator, that is trusted to be part of the finalized L2 chain.
+///
+/// Starting the sync from a checkpoint skips the search for a safe block in the L2 execution
+/// layer, which only needs to hold the checkpoint block rather than its ancestors. The L2 chain
+/// up to the checkpoint is *not* verified against the L1 chain: this is an explicit trust
+/// assumption made by the operator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TrustedCheckpoint {
+    /// The hash of the checkpoint L2 block.
+    pub l2_hash: B256,
+    /// The hash of the L1 origin of the checkpoint L2 block.
+    pub l1_origin_hash: B256,
+}
+
+impl TrustedCheckpoint {
+    /// Creates a new [`TrustedCheckpoint`].
+    pub const fn new(l2_hash: B256, l1_origin_hash: B256) -> Self {
+        Self { l2_hash, l1_origin_hash }
+    }
+
+    /// Returns the [`L2ForkchoiceState`] to start the sync with.
+    ///
+    /// If the safe head of the L2 execution layer is behind the checkpoint, the checkpoint is
+    /// used as the safe and finalized blocks. The unsafe block is kept if it descends from the
+    /// checkpoint, and is reset to the checkpoint otherwise. If the safe head is not behind the
+    /// checkpoint, the checkpoint is ignored in favour of [`find_starting_forkchoice`].
+    pub async fn starting_forkchoice(
+        &self,
+        cfg: &RollupConfig,
+        l1_provider: &RootProvider,
+        l2_provider: &RootProvider<Optimism>,
+    ) -> Result<L2ForkchoiceState, SyncStartError> {
+        let checkpoint = self.block_info(cfg, l1_provider, l2_provider).await?;
+
+        let current_fc = L2ForkchoiceState::current(cfg, l2_provider).await?;
+        if current_fc.safe.block_info.number >= checkpoint.block_info.number {
+            info!(
+                target: "sync_start",
+                checkpoint = %checkpoint.block_info.number,
+                l2_safe = %current_fc.safe.block_info.number,
+                "L2 safe block is past the trusted checkpoint, ignoring the checkpoint"
+            );
+            return find_starting_forkchoice(cfg, l1_provider, l2_provider).await;
+        }
+
+        let un_safe = if current_fc.un_safe.block_info.number > checkpoint.block_info.number &&
+            l2_provider
+                .get_block(checkpoint.block_info.number.into())
+                .await?
+                .is_some_and(|block| block.header.hash == checkpoint.block_info.hash)
+        {
+            current_fc.un_safe
+        } else {
+            checkpoint
+        };
+
+        info!(
+            target: "sync_start",
+            checkpoint = %checkpoint.block_info.number,
+            l1_origin = %checkpoint.l1_origin.number,
+            l2_unsafe = %un_safe.block_info.number,
+            "Starting sync from trusted L2 checkpoint"
+        );
+        Ok(L2ForkchoiceState { un_safe, safe: checkpoint, finalized: checkpoint })
+    }
+
+    /// Fetches the checkpoint L2 block, and checks that its L1 origin is the expected one and is
+    /// canonical.
+    async fn block_info(
+        &self,
+        cfg: &RollupConfig,
+        l1_provider: &RootProvider,
+        l2_provider: &RootProvider<Optimism>,
+    ) -> Result<L2BlockInfo, SyncStartError> {
+        let block = l2_provider
+            .get_block(self.l2_hash.into())
+            .full()
+            .await?
+            .ok_or(SyncStartError::BlockNotFound(self.l2_hash.into()))?;
+        let checkpoint =
+            L2BlockInfo::from_block_and_genesis(&block.into_consensus(), &cfg.genesis)?;
+        if checkpoint.l1_origin.hash != self.l1_origin_hash {
+            return Err(SyncStartError::L1OriginMismatch);
+        }
+
+        let canonical_origin = l1_provider
+            .get_block(checkpoint.l1_origin.number.into())
+            .await?
+            .ok_or(SyncStartError::BlockNotFound(checkpoint.l1_origin.number.into()))?;
+        if canonical_origin.header.hash != self.l1_origin_hash {
+            return Err(SyncStartError::NonCanonicalL1Origin(self.l1_origin_hash));
+        }
+
+        Ok(checkpoint)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::Header;
+    use alloy_eips::BlockNumHash;
+    use alloy_primitives::Sealed;
+    use alloy_rpc_client::RpcClient;
+    use alloy_rpc_types::{Block, BlockTransactions};
+    use alloy_transport::mock::{Asserter, MockTransport};
+    use kona_genesis::ChainGenesis;
+    use kona_protocol::L1BlockInfoBedrock;
+    use op_alloy_consensus::{OpTxEnvelope, TxDeposit};
+    use op_alloy_rpc_types::Transaction;
+
+    const L1_ORIGIN: BlockNumHash = BlockNumHash { number: 100, hash: B256::repeat_byte(0xaa) };
+
+    fn providers(l1: &Asserter, l2: &Asserter) -> (RootProvider, RootProvider<Optimism>) {
+        (
+            RootProvider::new(RpcClient::new(MockTransport::new(l1.clone()), false)),
+            RootProvider::new(RpcClient::new(MockTransport::new(l2.clone()), false)),
+        )
+    }
+
+    fn config() -> RollupConfig {
+        let genesis = Header::default();
+        RollupConfig {
+            genesis: ChainGenesis {
+                l2: BlockNumHash { number: 0, hash: genesis.hash_slow() },
+                l1: BlockNumHash { number: 1, hash: B256::repeat_byte(1) },
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    /// Returns an RPC block with the given number and parent hash, that starts with the L1 info
+    /// deposit of the given L1 origin.
+    fn l2_block(number: u64, parent_hash: B256, l1_origin: BlockNumHash) -> Block<Transaction> {
+        let l1_info = L1BlockInfoBedrock {
+            number: l1_origin.number,
+            block_hash: l1_origin.hash,
+            ..Default::default()
+        };
+        let deposit = TxDeposit { input: l1_info.encode_calldata(), ..Default::default() };
+        let tx = Transaction {
+            inner: alloy_rpc_types::Transaction {
+                inner: alloy_consensus::transaction::Recovered::new_unchecked(
+                    OpTxEnvelope::Deposit(Sealed::new(deposit)),
+                    Default::default(),
+                ),
+                block_hash: None,
+                block_number: Some(number),
+                effective_gas_price: None,
+                transaction_index: Some(0),
+            },
+            deposit_nonce: None,
+            deposit_receipt_version: None,
+        };
+        Block {
+            header: alloy_rpc_types::Header::new(Header {
+                number,
+                parent_hash,
+                ..Default::default()
+            }),
+            uncles: Vec::new(),
+            transactions: BlockTransactions::Full(vec![tx]),
+            withdrawals: None,
+        }
+    }
+
+    fn l1_block(number: u64, hash: B256) -> Block {
+        Block {
+            header: alloy_rpc_types::Header {
+                hash,
+                inner: Header { number, ..Default::default() },
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    fn info(block: &Block<Transaction>) -> L2BlockInfo {
+        L2BlockInfo::from_block_and_genesis(&block.clone().into_consensus(), &config().genesis)
+            .unwrap()
+    }
+
+    fn checkpoint_block() -> Block<Transaction> {
+        l2_block(10, B256::repeat_byte(9), L1_ORIGIN)
+    }
+
+    fn checkpoint() -> TrustedCheckpoint {
+        TrustedCheckpoint::new(checkpoint_block().header.hash, L1_ORIGIN.hash)
+    }
+
+    #[tokio::test]
+    async fn test_checkpoint_block_info() {
+        let (l1, l2) = (Asserter::new(), Asserter::new());
+        l2.push_success(&checkpoint_block());
+        l1.push_success(&l1_block(L1_ORIGIN.number, L1_ORIGIN.hash));
+
+        let (l1_provider, l2_provider) = providers(&l1, &l2);
+        let checkpoint =
+            checkpoint().block_info(&config(), &l1_provider, &l2_provider).await.unwrap();
+        assert_eq!(checkpoint, info(&checkpoint_block()));
+        assert_eq!(checkpoint.l1_origin, L1_ORIGIN);
+    }
+
+    #[tokio::test]
+    async fn test_checkpoint_block_info_l1_origin_mismatch() {
+        let (l1, l2) = (Asserter::new(), Asserter::new());
+        l2.push_success(&checkpoint_block());
+
+        let (l1_provider, l2_provider) = providers(&l1, &l2);
+        let checkpoint = TrustedCheckpoint::new(checkpoint().l2_hash, B256::repeat_byte(0xbb));
+        let err = checkpoint.block_info(&config(), &l1_provider, &l2_provider).await.unwrap_err();
+        assert!(matches!(err, SyncStartError::L1OriginMismatch));
+    }
+
+    #[tokio::test]
+    async fn test_checkpoint_block_info_non_canonical_l1_origin() {
+        let (l1, l2) = (Asserter::new(), Asserter::new());
+        l2.push_success(&checkpoint_block());
+        l1.push_success(&l1_block(L1_ORIGIN.number, B256::repeat_byte(0xbb)));
+
+        let (l1_provider, l2_provider) = providers(&l1, &l2);
+        let err = checkpoint().block_info(&config(), &l1_provider, &l2_provider).await.unwrap_err();
+        assert!(
+            matches!(err, SyncStartError::NonCanonicalL1Origin(hash) if hash == L1_ORIGIN.hash)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_checkpoint_block_info_missing_block() {
+        let (l1, l2) = (Asserter::new(), Asserter::new());
+        l2.push_success(&Option::<Block<Transaction>>::None);
+
+        let (l1_provider, l2_provider) = providers(&l1, &l2);
+        let err = checkpoint().block_info(&config(), &l1_provider, &l2_provider).await.unwrap_err();
+        assert!(matches!(err, SyncStartError::BlockNotFound(_)));
+    }
+
+    /// Queues the responses to [`TrustedCheckpoint::block_info`] and to the forkchoice state of
+    /// the L2 execution layer.
+    fn push_checkpoint_and_forkchoice(
+        l1: &Asserter,
+        l2: &Asserter,
+        finalized: &Block<Transaction>,
+        safe: &Block<Transaction>,
+        un_safe: &Block<Transaction>,
+    ) {
+        l2.push_success(&checkpoint_block());
+        l1.push_success(&l1_block(L1_ORIGIN.number, L1_ORIGIN.hash));
+        l2.push_success(finalized);
+        l2.push_success(safe);
+        l2.push_success(un_safe);
+    }
+
+    #[tokio::test]
+    async fn test_starting_forkchoice_keeps_descendant_unsafe() {
+        let (l1, l2) = (Asserter::new(), Asserter::new());
+        let finalized = l2_block(3, B256::repeat_byte(2), BlockNumHash::default());
+        let safe = l2_block(5, B256::repeat_byte(4), BlockNumHash::default());
+        let un_safe = l2_block(12, B256::repeat_byte(11), L1_ORIGIN);
+        push_checkpoint_and_forkchoice(&l1, &l2, &finalized, &safe, &un_safe);
+        l2.push_success(&checkpoint_block());
+
+        let (l1_provider, l2_provider) = providers(&l1, &l2);
+        let fc =
+            checkpoint().starting_forkchoice(&config(), &l1_provider, &l2_provider).await.unwrap();
+        let checkpoint = info(&checkpoint_block());
+        assert_eq!(
+            fc,
+            L2ForkchoiceState { un_safe: info(&un_safe), safe: checkpoint, finalized: checkpoint }
+        );
+        assert!(l2.read_q().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_starting_forkchoice_resets_reorged_unsafe() {
+        let (l1, l2) = (Asserter::new(), Asserter::new());
+        let finalized = l2_block(3, B256::repeat_byte(2), BlockNumHash::default());
+        let safe = l2_block(5, B256::repeat_byte(4), BlockNumHash::default());
+        let un_safe = l2_block(12, B256::repeat_byte(11), L1_ORIGIN);
+        push_checkpoint_and_forkchoice(&l1, &l2, &finalized, &safe, &un_safe);
+        // The execution layer holds another block at the height of the checkpoint.
+        l2.push_success(&l2_block(10, B256::repeat_byte(0xff), L1_ORIGIN));
+
+        let (l1_provider, l2_provider) = providers(&l1, &l2);
+        let fc =
+            checkpoint().starting_forkchoice(&config(), &l1_provider, &l2_provider).await.unwrap();
+        let checkpoint = info(&checkpoint_block());
+        assert_eq!(
+            fc,
+            L2ForkchoiceState { un_safe: checkpoint, safe: checkpoint, finalized: checkpoint }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_starting_forkchoice_ignores_checkpoint_behind_safe_head() {
+        let (l1, l2) = (Asserter::new(), Asserter::new());
+        let head = l2_block(12, B256::repeat_byte(11), L1_ORIGIN);
+        push_checkpoint_and_forkchoice(&l1, &l2, &head, &head, &head);
+        // The regular sync start search, which finds the finalized head to be canonical.
+        for _ in 0..3 {
+            l2.push_success(&head);
+        }
+        l1.push_success(&l1_block(L1_ORIGIN.number, L1_ORIGIN.hash));
+
+        let (l1_provider, l2_provider) = providers(&l1, &l2);
+        let fc =
+            checkpoint().starting_forkchoice(&config(), &l1_provider, &l2_provider).await.unwrap();
+        let head = info(&head);
+        assert_eq!(fc, L2ForkchoiceState { un_safe: head, safe: head, finalized: head });
+        assert!(l1.read_q().is_empty() && l2.read_q().is_empty());
+    }
+}
@@ -32,6 +32,9 @@ pub enum SyncStartError {
     /// L1 origin mismatch.
     #[error("L1 origin mismatch")]
     L1OriginMismatch,
+    /// The L1 origin of the trusted checkpoint is not canonical.
+    #[error("L1 origin of the trusted checkpoint is not canonical: {0}")]
+    NonCanonicalL1Origin(B256),
     /// Non-zero sequence number.
     #[error("Non-zero sequence number for block with different L1 origin")]
     NonZeroSequenceNumber,
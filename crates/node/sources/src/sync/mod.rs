@@ -9,6 +9,9 @@ pub use forkchoice::L2ForkchoiceState;
 
 mod error;
 pub use error::SyncStartError;
+
+mod checkpoint;
+pub use checkpoint::TrustedCheckpoint;
 use op_alloy_network::Optimism;
 use tracing::info;
 
@@ -10,7 +10,7 @@ use kona_derive::{Pipeline, PipelineError, PipelineErrorKind, Signal, SignalRece
 use kona_executor::BlockBuildingOutcome;
 use kona_genesis::RollupConfig;
 use kona_protocol::L2BlockInfo;
-use op_alloy_consensus::{OpBlock, OpTxEnvelope, OpTxType};
+use op_alloy_consensus::{OpBlock, OpTxEnvelope};
 use spin::RwLock;
 
 /// The Rollup Driver entrypoint.
@@ -228,7 +228,7 @@ where
 
             let mut attributes = match self.pipeline.produce_payload(tip_cursor.l2_safe_head).await
             {
-                Ok(attrs) => attrs.take_inner(),
+                Ok(attrs) => attrs,
                 Err(PipelineErrorKind::Critical(PipelineError::EndOfSource)) => {
                     warn!(target: "client", "Exhausted data source; Halting derivation and using current safe head.");
 
@@ -253,12 +253,12 @@ where
             };
 
             self.executor.update_safe_head(tip_cursor.l2_safe_head_header.clone());
-            let outcome = match self.executor.execute_payload(attributes.clone()).await {
+            let outcome = match self.executor.execute_payload(attributes.inner().clone()).await {
                 Ok(outcome) => outcome,
                 Err(e) => {
                     error!(target: "client", "Failed to execute L2 block: {}", e);
 
-                    if cfg.is_holocene_active(attributes.payload_attributes.timestamp) {
+                    if cfg.is_holocene_active(attributes.inner().payload_attributes.timestamp) {
                         // Retry with a deposit-only block.
                         warn!(target: "client", "Flushing current channel and retrying deposit only block");
 
@@ -269,15 +269,11 @@ where
                         self.pipeline.signal(Signal::FlushChannel).await?;
 
                         // Strip out all transactions that are not deposits.
-                        attributes.transactions = attributes.transactions.map(|txs| {
-                            txs.into_iter()
-                                .filter(|tx| !tx.is_empty() && tx[0] == OpTxType::Deposit as u8)
-                                .collect::<Vec<_>>()
-                        });
+                        attributes = attributes.as_deposits_only();
 
                         // Retry the execution.
                         self.executor.update_safe_head(tip_cursor.l2_safe_head_header.clone());
-                        match self.executor.execute_payload(attributes.clone()).await {
+                        match self.executor.execute_payload(attributes.inner().clone()).await {
                             Ok(header) => header,
                             Err(e) => {
                                 error!(
@@ -299,6 +295,7 @@ where
                 header: outcome.header.inner().clone(),
                 body: BlockBody {
                     transactions: attributes
+                        .inner()
                         .transactions
                         .as_ref()
                         .unwrap_or(&Vec::new())
@@ -327,7 +324,8 @@ where
             self.cursor.write().advance(origin, tip_cursor);
 
             // Update the latest safe head artifacts.
-            self.safe_head_artifacts = Some((outcome, attributes.transactions.unwrap_or_default()));
+            self.safe_head_artifacts =
+                Some((outcome, attributes.take_inner().transactions.unwrap_or_default()));
         }
     }
 }
@@ -1,18 +1,20 @@
 //! [Header] assembly logic for the [StatelessL2Builder].
 
-use super::StatelessL2Builder;
+use super::{ReceiptAccumulator, StatelessL2Builder};
 use crate::{
-    ExecutorError, ExecutorResult, TrieDBError, TrieDBProvider,
+    ExecutorError, ExecutorResult, TrieDBError, TrieDBProvider, TrieDBResult,
     util::{encode_holocene_eip_1559_params, encode_jovian_eip_1559_params},
 };
-use alloc::vec::Vec;
 use alloy_consensus::{EMPTY_OMMER_ROOT_HASH, Header, Sealed};
-use alloy_eips::{Encodable2718, eip7685::EMPTY_REQUESTS_HASH};
+use alloy_eips::eip7685::EMPTY_REQUESTS_HASH;
 use alloy_evm::{EvmFactory, block::BlockExecutionResult};
-use alloy_primitives::{B256, Sealable, U256, logs_bloom};
-use alloy_trie::EMPTY_ROOT_HASH;
+use alloy_primitives::{B256, Sealable, U256, keccak256};
+use alloy_rlp::Decodable;
+use alloy_trie::{EMPTY_ROOT_HASH, TrieAccount};
 use kona_genesis::RollupConfig;
-use kona_mpt::{TrieHinter, ordered_trie_with_encoder};
+use kona_mpt::{
+    Nibbles, TrieHinter, TrieNode, TrieNodeError, TrieProvider, ordered_trie_with_encoder,
+};
 use kona_protocol::{OutputRoot, Predeploys};
 use op_alloy_consensus::OpReceiptEnvelope;
 use op_alloy_rpc_types_engine::OpPayloadAttributes;
@@ -32,12 +34,13 @@ where
         parent_hash: B256,
         block_env: &BlockEnv,
         ex_result: &BlockExecutionResult<OpReceiptEnvelope>,
+        receipts: ReceiptAccumulator,
         bundle: BundleState,
     ) -> ExecutorResult<Sealed<Header>> {
         let timestamp = block_env.timestamp.saturating_to::<u64>();
 
         // Compute the roots for the block header.
-        let state_root = self.trie_db.state_root(&bundle)?;
+        let state_root = (self.state_root)(&mut self.trie_db, &bundle)?;
         let transactions_root = ordered_trie_with_encoder(
             // SAFETY: The OP Stack protocol will never generate a payload attributes with an empty
             // transactions field. Panicking here is the desired behavior, as it indicates a severe
@@ -46,7 +49,7 @@ where
             |tx, buf| buf.put_slice(tx.as_ref()),
         )
         .root();
-        let receipts_root = compute_receipts_root(&ex_result.receipts, self.config, timestamp);
+        let (receipts_root, logs_bloom) = receipts.finish();
         let withdrawals_root = if self.config.is_isthmus_active(timestamp) {
             Some(self.message_passer_account(block_env.number.saturating_to::<u64>())?)
         } else if self.config.is_canyon_active(timestamp) {
@@ -55,9 +58,6 @@ where
             None
         };
 
-        // Compute the logs bloom from the receipts generated during block execution.
-        let logs_bloom = logs_bloom(ex_result.receipts.iter().flat_map(|r| r.logs()));
-
         // Compute Cancun fields, if active.
         let (blob_gas_used, excess_blob_gas) = if self.config.is_jovian_active(timestamp) {
             (Some(ex_result.blob_gas_used), Some(0))
@@ -154,11 +154,11 @@ where
 
     /// Fetches the L2 to L1 message passer account from the cache or underlying trie.
     fn message_passer_account(&mut self, block_number: u64) -> Result<B256, TrieDBError> {
-        match self.trie_db.storage_roots().get(&Predeploys::L2_TO_L1_MESSAGE_PASSER) {
+        match self.trie_db.storage_roots().get(&self.message_passer) {
             Some(storage_root) => Ok(storage_root.blind()),
             None => Ok(self
                 .trie_db
-                .get_trie_account(&Predeploys::L2_TO_L1_MESSAGE_PASSER, block_number)?
+                .get_trie_account(&self.message_passer, block_number)?
                 .ok_or(TrieDBError::MissingAccountInfo)?
                 .storage_root),
         }
@@ -171,28 +171,106 @@ pub fn compute_receipts_root(
     config: &RollupConfig,
     timestamp: u64,
 ) -> B256 {
-    // There is a minor bug in op-geth and op-erigon where in the Regolith hardfork,
-    // the receipt root calculation does not include the deposit nonce in the
-    // receipt encoding. In the Regolith hardfork, we must strip the deposit nonce
-    // from the receipt encoding to match the receipt root calculation.
-    if config.is_regolith_active(timestamp) && !config.is_canyon_active(timestamp) {
-        let receipts = receipts
-            .iter()
-            .cloned()
-            .map(|receipt| match receipt {
-                OpReceiptEnvelope::Deposit(mut deposit_receipt) => {
-                    deposit_receipt.receipt.deposit_nonce = None;
-                    OpReceiptEnvelope::Deposit(deposit_receipt)
-                }
-                _ => receipt,
-            })
-            .collect::<Vec<_>>();
-
-        ordered_trie_with_encoder(receipts.as_ref(), |receipt, mut buf| {
-            receipt.encode_2718(&mut buf)
-        })
-        .root()
-    } else {
-        ordered_trie_with_encoder(receipts, |receipt, mut buf| receipt.encode_2718(&mut buf)).root()
+    let mut accumulator = ReceiptAccumulator::new(config, timestamp);
+    receipts.iter().for_each(|receipt| accumulator.push(receipt));
+    accumulator.finish().0
+}
+
+/// Computes the withdrawals root of the given L2 block: the storage root of the
+/// `L2ToL1MessagePasser` predeploy of the chain, as resolved by [Predeploys::message_passer], in
+/// the post-state of the block.
+///
+/// Post-Isthmus, block headers commit to this root in their `withdrawals_root` field, and output
+/// roots commit to it at every fork. The account is opened from the state root of the `header`
+/// with the given [TrieProvider], without executing the block, so that the root can be computed
+/// outside of block execution (e.g. to compute output roots).
+pub fn compute_withdrawals_root<P: TrieProvider>(
+    provider: &P,
+    config: &RollupConfig,
+    header: &Header,
+) -> TrieDBResult<B256> {
+    let mut root = TrieNode::new_blinded(header.state_root);
+    let path = Nibbles::unpack(keccak256(Predeploys::message_passer(config)));
+    let account_rlp = root.open(&path, provider)?.ok_or(TrieDBError::MissingAccountInfo)?;
+    let account =
+        TrieAccount::decode(&mut account_rlp.as_ref()).map_err(TrieNodeError::RLPError)?;
+    Ok(account.storage_root)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::NoopTrieDBProvider;
+    use alloc::{string::String, vec::Vec};
+    use alloy_primitives::{Address, Bytes};
+    use alloy_rlp::Encodable;
+
+    /// A [TrieProvider] that serves the preimage of a single trie root.
+    struct RootProvider(TrieNode);
+
+    impl TrieProvider for RootProvider {
+        type Error = String;
+
+        fn trie_node_by_hash(&self, key: B256) -> Result<TrieNode, Self::Error> {
+            (key == self.0.blind()).then(|| self.0.clone()).ok_or_else(|| "unknown node".into())
+        }
+    }
+
+    fn account_rlp(storage_root: B256) -> Bytes {
+        let mut buf = Vec::new();
+        TrieAccount { storage_root, ..Default::default() }.encode(&mut buf);
+        buf.into()
+    }
+
+    #[test]
+    fn test_compute_withdrawals_root() {
+        let storage_root = B256::repeat_byte(0xAA);
+        let mut trie = TrieNode::Empty;
+        trie.insert(
+            &Nibbles::unpack(keccak256(Predeploys::L2_TO_L1_MESSAGE_PASSER)),
+            account_rlp(storage_root),
+            &NoopTrieDBProvider,
+        )
+        .unwrap();
+        trie.insert(
+            &Nibbles::unpack(keccak256(Address::repeat_byte(0x01))),
+            account_rlp(B256::repeat_byte(0xBB)),
+            &NoopTrieDBProvider,
+        )
+        .unwrap();
+
+        let header = Header { state_root: trie.blind(), ..Default::default() };
+        let provider = RootProvider(trie);
+        assert_eq!(
+            compute_withdrawals_root(&provider, &RollupConfig::default(), &header).unwrap(),
+            storage_root
+        );
+
+        let config = RollupConfig {
+            message_passer_address: Some(Address::repeat_byte(0x01)),
+            ..Default::default()
+        };
+        assert_eq!(
+            compute_withdrawals_root(&provider, &config, &header).unwrap(),
+            B256::repeat_byte(0xBB)
+        );
+    }
+
+    #[test]
+    fn test_compute_withdrawals_root_missing_account() {
+        let mut trie = TrieNode::Empty;
+        trie.insert(
+            &Nibbles::unpack(keccak256(Address::repeat_byte(0x01))),
+            account_rlp(B256::repeat_byte(0xBB)),
+            &NoopTrieDBProvider,
+        )
+        .unwrap();
+
+        let header = Header { state_root: trie.blind(), ..Default::default() };
+        let provider = RootProvider(trie);
+        assert!(matches!(
+            compute_withdrawals_root(&provider, &RollupConfig::default(), &header),
+            Err(TrieDBError::MissingAccountInfo)
+        ));
     }
 }
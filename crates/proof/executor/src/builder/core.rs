@@ -4,28 +4,41 @@
 //! for OP Stack L2 chains that operates in a stateless manner, pulling required state
 //! data from a [TrieDB] during execution rather than maintaining full state.
 
-use crate::{ExecutorError, ExecutorResult, TrieDB, TrieDBError, TrieDBProvider};
-use alloc::{string::ToString, vec::Vec};
-use alloy_consensus::{Header, Sealed, crypto::RecoveryError};
+use super::{ReceiptAccumulator, execute_block_incremental};
+use crate::{
+    BlockReportHook, BlockResourceReport, ExecutorError, ExecutorResult, StateAccess, StateDiff,
+    TrieDB, TrieDBError, TrieDBProvider, TrieDBResult, db::SpeculativeTrieDB,
+};
+use alloc::{boxed::Box, string::ToString, vec::Vec};
+use alloy_consensus::{Header, Sealed, crypto::RecoveryError, transaction::Recovered};
+use alloy_eips::eip2718::WithEncoded;
 use alloy_evm::{
-    EvmFactory, FromRecoveredTx, FromTxWithEncoded,
+    EvmEnv, EvmFactory, FromRecoveredTx, FromTxWithEncoded,
     block::{BlockExecutionResult, BlockExecutor, BlockExecutorFactory},
 };
 use alloy_op_evm::{
     OpBlockExecutionCtx, OpBlockExecutorFactory,
     block::{OpAlloyReceiptBuilder, OpTxEnv},
 };
-use core::fmt::Debug;
+use alloy_primitives::{Address, B256};
+use core::{fmt::Debug, time::Duration};
 use kona_genesis::RollupConfig;
 use kona_mpt::TrieHinter;
+use kona_protocol::Predeploys;
 use op_alloy_consensus::{OpReceiptEnvelope, OpTxEnvelope};
 use op_alloy_rpc_types_engine::OpPayloadAttributes;
 use op_revm::OpSpecId;
 use revm::{
+    Inspector,
     context::BlockEnv,
-    database::{State, states::bundle_state::BundleRetention},
+    database::{BundleState, State, states::bundle_state::BundleRetention},
+    inspector::NoOpInspector,
 };
 
+/// The maximum number of speculative execution passes used to discover the state accessed by a
+/// block when state prefetching is enabled.
+const MAX_PREFETCH_PASSES: usize = 3;
+
 /// Stateless OP Stack L2 block builder that derives state from trie proofs during execution.
 ///
 /// The [`StatelessL2Builder`] is a specialized block execution engine designed for fault proof
@@ -97,6 +110,34 @@ where
     /// understand OP-specific transaction types, system calls, and state
     /// management required for proper L2 block execution.
     pub(crate) factory: OpBlockExecutorFactory<OpAlloyReceiptBuilder, RollupConfig, Evm>,
+    /// Whether to speculatively pre-execute blocks to prefetch the state they access.
+    ///
+    /// When enabled, each block is first executed speculatively against the trie nodes that are
+    /// already revealed, and the trie paths of every account and storage slot it touches are
+    /// fetched in batches with [`TrieDB::prefetch`]. The block is then executed
+    /// deterministically on top of the revealed paths.
+    pub(crate) prefetch_state: bool,
+    /// The address of the `L2ToL1MessagePasser` predeploy, committed to in the output root.
+    ///
+    /// Resolved from the [`RollupConfig`] with [`Predeploys::message_passer`], so that alternative
+    /// OP Stack chains that deploy the message passer elsewhere can override it. Custom fee
+    /// handling, such as non-standard fee vault addresses, is configured through the
+    /// [`EvmFactory`] instead.
+    pub(crate) message_passer: Address,
+    /// Whether to compute the [`StateDiff`] of every built block.
+    ///
+    /// Disabled by default, as the diff is not needed to prove a block. Set with
+    /// [`StatelessL2Builder::with_state_diff`].
+    pub(crate) record_state_diff: bool,
+    /// The hook receiving the resource report of every built block, if any.
+    ///
+    /// Set with [`StatelessL2Builder::with_report_hook`]. Without a hook, no report is computed.
+    pub(crate) report_hook: Option<Box<dyn BlockReportHook + Send + 'a>>,
+    /// The function computing the state root of a block when sealing it.
+    ///
+    /// Defaults to [`TrieDB::state_root`]. With the `rayon` feature, it may be switched to the
+    /// parallel storage root computation with [`StatelessL2Builder::with_parallel_state_root`].
+    pub(crate) state_root: fn(&mut TrieDB<P, H>, &BundleState) -> TrieDBResult<B256>,
 }
 
 impl<'a, P, H, Evm> StatelessL2Builder<'a, P, H, Evm>
@@ -145,7 +186,34 @@ where
             config.clone(),
             evm_factory,
         );
-        Self { config, trie_db, factory }
+        Self {
+            config,
+            trie_db,
+            factory,
+            prefetch_state: false,
+            message_passer: Predeploys::message_passer(config),
+            record_state_diff: false,
+            report_hook: None,
+            state_root: TrieDB::state_root,
+        }
+    }
+
+    /// Enables or disables the speculative state prefetching pass prior to block execution.
+    pub const fn with_state_prefetch(mut self, prefetch_state: bool) -> Self {
+        self.prefetch_state = prefetch_state;
+        self
+    }
+
+    /// Enables or disables computing the [`StateDiff`] of every built block.
+    pub const fn with_state_diff(mut self, record_state_diff: bool) -> Self {
+        self.record_state_diff = record_state_diff;
+        self
+    }
+
+    /// Sets the [`BlockReportHook`] receiving the [`BlockResourceReport`] of every built block.
+    pub fn with_report_hook(mut self, hook: impl BlockReportHook + Send + 'a) -> Self {
+        self.report_hook = Some(Box::new(hook));
+        self
     }
 
     /// Builds and executes a new L2 block using the provided payload attributes.
@@ -213,7 +281,95 @@ where
         &mut self,
         attrs: OpPayloadAttributes,
     ) -> ExecutorResult<BlockBuildingOutcome> {
-        // Step 1. Set up the execution environment.
+        self.execute_with_inspector(attrs, NoOpInspector {})
+    }
+
+    /// Returns the sealed header of the block that the next block is built on top of.
+    ///
+    /// This is the parent header passed to [`Self::new`], advanced to the header of every block
+    /// built since.
+    pub const fn parent_header(&self) -> &Sealed<Header> {
+        self.trie_db.parent_block_header()
+    }
+
+    /// Builds and executes a range of consecutive L2 blocks, one per set of payload attributes.
+    ///
+    /// Each block is built on top of the previous one, reusing the warmed trie database and its
+    /// caches across blocks rather than rebuilding them per block. Execution halts at the first
+    /// block that fails to build.
+    ///
+    /// # Arguments
+    /// * `attrs` - Payload attributes of the consecutive blocks, in order of execution
+    ///
+    /// # Returns
+    /// * `Ok(Vec<BlockBuildingOutcome>)` - The outcome of each built block, in order
+    /// * `Err(ExecutorError)` - Block building or execution failure of any block in the range
+    pub fn execute_range(
+        &mut self,
+        attrs: impl IntoIterator<Item = OpPayloadAttributes>,
+    ) -> ExecutorResult<Vec<BlockBuildingOutcome>> {
+        attrs.into_iter().map(|attrs| self.build_block(attrs)).collect()
+    }
+
+    /// Builds and executes a new L2 block using the provided payload attributes, routing all
+    /// EVM execution through the given [`Inspector`].
+    ///
+    /// [`Self::build_block`] runs through this method with a [`NoOpInspector`]. Callers may
+    /// attach call tracers or custom inspectors instead, e.g. to debug a state root mismatch.
+    /// Pass a mutable reference to the inspector to retain access to its results after
+    /// execution.
+    ///
+    /// # Arguments
+    /// * `attrs` - Payload attributes containing transactions and block metadata
+    /// * `inspector` - The [`Inspector`] to attach to the EVM
+    ///
+    /// # Returns
+    /// * `Ok(BlockBuildingOutcome)` - Successfully built and executed block with receipts
+    /// * `Err(ExecutorError)` - Block building or execution failure
+    pub fn execute_with_inspector<I>(
+        &mut self,
+        attrs: OpPayloadAttributes,
+        inspector: I,
+    ) -> ExecutorResult<BlockBuildingOutcome>
+    where
+        I: for<'b> Inspector<<Evm as EvmFactory>::Context<&'b mut State<&'b mut TrieDB<P, H>>>>,
+    {
+        // Step 1. Set up the execution environment, optionally prefetching state.
+        let started = self.report_hook.as_ref().and_then(|hook| hook.now());
+        let PreparedBlock { evm_env, ctx, transactions } = self.prepare_block(&attrs)?;
+        let block_env = evm_env.block_env().clone();
+        let parent_hash = ctx.parent_hash;
+
+        // Step 2. Create the inspecting executor, using the trie database.
+        let mut state = State::builder()
+            .with_database(&mut self.trie_db)
+            .with_bundle_update()
+            .without_state_clear()
+            .build();
+        let evm =
+            self.factory.evm_factory().create_evm_with_inspector(&mut state, evm_env, inspector);
+        let executor = self.factory.create_executor(evm, ctx);
+
+        // Step 3. Execute the block containing the transactions within the payload attributes,
+        // accumulating the receipts root and logs bloom as receipts are produced.
+        let mut receipts = ReceiptAccumulator::new(self.config, attrs.payload_attributes.timestamp);
+        let ex_result = execute_block_incremental(executor, transactions.iter(), &mut receipts)?;
+
+        // Step 4. Merge state transitions and seal the block.
+        state.merge_transitions(BundleRetention::Reverts);
+        let bundle = state.take_bundle();
+        let outcome =
+            self.finalize_block(&attrs, parent_hash, &block_env, ex_result, receipts, bundle)?;
+        self.report_block(&outcome, &transactions, started);
+        Ok(outcome)
+    }
+
+    /// Prepares the execution environment, execution context, and recovered transactions for
+    /// building a block with the given payload attributes.
+    ///
+    /// If state prefetching is enabled, the state accessed by the block is revealed ahead of
+    /// deterministic execution with [`Self::prefetch_block_state`].
+    fn prepare_block(&mut self, attrs: &OpPayloadAttributes) -> ExecutorResult<PreparedBlock> {
         let (base_fee_params, min_base_fee) = Self::active_base_fee_params(
             self.config,
             self.trie_db.parent_block_header(),
@@ -222,11 +378,11 @@ where
         let evm_env = self.evm_env(
             self.config.spec_id(attrs.payload_attributes.timestamp),
             self.trie_db.parent_block_header(),
-            &attrs,
+            attrs,
             &base_fee_params,
             min_base_fee,
         )?;
-        let block_env = evm_env.block_env().clone();
+        let block_env = evm_env.block_env();
         let parent_hash = self.trie_db.parent_block_header().seal();
 
         // Attempt to send a payload witness hint to the host. This hint instructs the host to
@@ -235,7 +391,7 @@ where
         // without it and fall back on on-demand preimage fetching for execution.
         self.trie_db
             .hinter
-            .hint_execution_witness(parent_hash, &attrs)
+            .hint_execution_witness(parent_hash, attrs)
             .map_err(|e| TrieDBError::Provider(e.to_string()))?;
 
         info!(
@@ -247,28 +403,91 @@ where
             "Beginning block building."
         );
 
-        // Step 2. Create the executor, using the trie database.
-        let mut state = State::builder()
-            .with_database(&mut self.trie_db)
-            .with_bundle_update()
-            .without_state_clear()
-            .build();
-        let evm = self.factory.evm_factory().create_evm(&mut state, evm_env);
         let ctx = OpBlockExecutionCtx {
             parent_hash,
             parent_beacon_block_root: attrs.payload_attributes.parent_beacon_block_root,
             // This field is unused for individual block building jobs.
             extra_data: Default::default(),
         };
-        let executor = self.factory.create_executor(evm, ctx);
-
-        // Step 3. Execute the block containing the transactions within the payload attributes.
         let transactions = attrs
             .recovered_transactions_with_encoded()
             .collect::<Result<Vec<_>, RecoveryError>>()
             .map_err(ExecutorError::Recovery)?;
-        let ex_result = executor.execute_block(transactions.iter())?;
 
+        if self.prefetch_state {
+            self.prefetch_block_state(&evm_env, &ctx, &transactions);
+        }
+
+        Ok(PreparedBlock { evm_env, ctx, transactions })
+    }
+
+    /// Reveals the state accessed by a block within the trie database, ahead of its execution.
+    ///
+    /// The block is speculatively executed against a [`SpeculativeTrieDB`], which reads state
+    /// from the revealed trie nodes only, and the accounts and storage slots that it accesses are
+    /// then revealed in batches with [`TrieDB::prefetch`]. State that read as empty may lead to
+    /// further accesses once revealed, so this is repeated until no new state is accessed, for at
+    /// most [`MAX_PREFETCH_PASSES`] passes.
+    ///
+    /// Prefetching is only a hint: failures are logged, and surface during execution instead.
+    fn prefetch_block_state(
+        &mut self,
+        evm_env: &EvmEnv<OpSpecId>,
+        ctx: &OpBlockExecutionCtx,
+        transactions: &[WithEncoded<Recovered<OpTxEnvelope>>],
+    ) {
+        let mut access = StateAccess::default();
+        for pass in 1..=MAX_PREFETCH_PASSES {
+            let pass_access = {
+                let mut db = SpeculativeTrieDB::new(&mut self.trie_db);
+                let mut state = State::builder().with_database(&mut db).build();
+                let evm = self.factory.evm_factory().create_evm(&mut state, evm_env.clone());
+                let mut executor = self.factory.create_executor(evm, ctx.clone());
+
+                // Transactions are executed one by one, so that a transaction failing against
+                // the speculative state does not hide the state accessed by the others.
+                if let Err(e) = executor.apply_pre_execution_changes() {
+                    debug!(target: "block_builder", "Speculative pre-execution failed: {e}");
+                }
+                for tx in transactions {
+                    if let Err(e) = executor.execute_transaction(tx) {
+                        debug!(target: "block_builder", "Speculative transaction failed: {e}");
+                    }
+                }
+                drop(executor);
+                StateAccess::from(&state.cache)
+            };
+
+            let new_access = access.merge(pass_access);
+            if new_access.is_empty() {
+                break;
+            }
+
+            debug!(
+                target: "block_builder",
+                pass,
+                accounts = new_access.account_count(),
+                slots = new_access.slot_count(),
+                "Prefetching state accessed during speculative execution"
+            );
+            if let Err(e) = self.trie_db.prefetch(&new_access) {
+                debug!(target: "block_builder", "State prefetch failed: {e}");
+                break;
+            }
+        }
+    }
+
+    /// Seals an executed block, computing its state root, and advances the parent block header of
+    /// the trie database.
+    fn finalize_block(
+        &mut self,
+        attrs: &OpPayloadAttributes,
+        parent_hash: B256,
+        block_env: &BlockEnv,
+        ex_result: BlockExecutionResult<OpReceiptEnvelope>,
+        receipts: ReceiptAccumulator,
+        bundle: BundleState,
+    ) -> ExecutorResult<BlockBuildingOutcome> {
         info!(
             target: "block_builder",
             gas_used = ex_result.gas_used,
@@ -276,10 +495,9 @@ where
             "Finished block building. Beginning sealing job."
         );
 
-        // Step 4. Merge state transitions and seal the block.
-        state.merge_transitions(BundleRetention::Reverts);
-        let bundle = state.take_bundle();
-        let header = self.seal_block(&attrs, parent_hash, &block_env, &ex_result, bundle)?;
+        let state_diff = self.record_state_diff.then(|| StateDiff::from(&bundle));
+        let header =
+            self.seal_block(attrs, parent_hash, block_env, &ex_result, receipts, bundle)?;
 
         info!(
             target: "block_builder",
@@ -293,34 +511,145 @@ where
 
         // Update the parent block hash in the state database, preparing for the next block.
         self.trie_db.set_parent_block_header(header.clone());
-        Ok((header, ex_result).into())
+        Ok((header, ex_result, state_diff).into())
+    }
+
+    /// Sends the [`BlockResourceReport`] of a built block to the [`BlockReportHook`], if any.
+    fn report_block(
+        &mut self,
+        outcome: &BlockBuildingOutcome,
+        transactions: &[WithEncoded<Recovered<OpTxEnvelope>>],
+        started: Option<Duration>,
+    ) {
+        let Some(hook) = self.report_hook.as_mut() else {
+            return;
+        };
+        let execution_time =
+            started.zip(hook.now()).and_then(|(started, now)| now.checked_sub(started));
+        let report = BlockResourceReport::new(
+            &outcome.header,
+            transactions,
+            &outcome.execution_result.receipts,
+            execution_time,
+        );
+        hook.on_block(&report);
     }
 }
 
-/// The outcome of a block building operation, returning the sealed block [`Header`] and the
-/// [`BlockExecutionResult`].
+#[cfg(feature = "rayon")]
+impl<P, H, Evm> StatelessL2Builder<'_, P, H, Evm>
+where
+    P: TrieDBProvider,
+    H: TrieHinter,
+    Evm: EvmFactory,
+{
+    /// Computes the storage roots of the modified accounts in parallel when sealing a block, with
+    /// [`TrieDB::state_root_parallel`].
+    ///
+    /// The produced blocks are identical. This is meant for native hosts with many cores, and is
+    /// not available on FPVM targets.
+    pub const fn with_parallel_state_root(mut self) -> Self {
+        self.state_root = TrieDB::state_root_parallel;
+        self
+    }
+}
+
+/// The execution inputs of a block, prepared by [`StatelessL2Builder::prepare_block`].
+#[derive(Debug)]
+struct PreparedBlock {
+    /// The EVM environment to execute the block in.
+    evm_env: EvmEnv<OpSpecId>,
+    /// The block execution context.
+    ctx: OpBlockExecutionCtx,
+    /// The recovered transactions of the block, alongside their encoded forms.
+    transactions: Vec<WithEncoded<Recovered<OpTxEnvelope>>>,
+}
+
+/// The outcome of a block building operation, returning the sealed block [`Header`], the
+/// [`BlockExecutionResult`], and the [`StateDiff`] of the block, if recorded.
 #[derive(Debug, Clone)]
 pub struct BlockBuildingOutcome {
     /// The block header.
     pub header: Sealed<Header>,
     /// The block execution result.
     pub execution_result: BlockExecutionResult<OpReceiptEnvelope>,
+    /// The state changes made by executing the block, if enabled with
+    /// [`StatelessL2Builder::with_state_diff`].
+    pub state_diff: Option<StateDiff>,
 }
 
-impl From<(Sealed<Header>, BlockExecutionResult<OpReceiptEnvelope>)> for BlockBuildingOutcome {
+impl From<(Sealed<Header>, BlockExecutionResult<OpReceiptEnvelope>, Option<StateDiff>)>
+    for BlockBuildingOutcome
+{
     fn from(
-        (header, execution_result): (Sealed<Header>, BlockExecutionResult<OpReceiptEnvelope>),
+        (header, execution_result, state_diff): (
+            Sealed<Header>,
+            BlockExecutionResult<OpReceiptEnvelope>,
+            Option<StateDiff>,
+        ),
     ) -> Self {
-        Self { header, execution_result }
+        Self { header, execution_result, state_diff }
     }
 }
 
 #[cfg(test)]
 mod test {
-    use crate::test_utils::run_test_fixture;
+    use super::*;
+    use crate::{
+        NoopTrieDBProvider,
+        test_utils::{
+            DiskTrieNodeProvider, TestTrieNodeProviderError, load_test_fixture, run_test_fixture,
+        },
+    };
+    use alloy_consensus::Sealable;
+    use alloy_op_evm::OpEvmFactory;
+    use alloy_primitives::Bytes;
+    use core::sync::atomic::{AtomicUsize, Ordering};
+    use kona_mpt::{NoopTrieHinter, TrieNode, TrieProvider};
+    use revm::interpreter::{CallInputs, CallOutcome};
     use rstest::rstest;
     use std::path::PathBuf;
 
+    /// An [`Inspector`] counting the call frames entered during execution.
+    #[derive(Debug, Default)]
+    struct CallCounter {
+        calls: usize,
+    }
+
+    impl<CTX> Inspector<CTX> for CallCounter {
+        fn call(&mut self, _: &mut CTX, _: &mut CallInputs) -> Option<CallOutcome> {
+            self.calls += 1;
+            None
+        }
+    }
+
+    #[test]
+    fn test_message_passer_from_rollup_config() {
+        let parent = Header::default().seal_slow();
+        let config = RollupConfig::default();
+        let builder = StatelessL2Builder::new(
+            &config,
+            OpEvmFactory::default(),
+            NoopTrieDBProvider,
+            NoopTrieHinter,
+            parent.clone(),
+        );
+        assert_eq!(builder.message_passer, Predeploys::L2_TO_L1_MESSAGE_PASSER);
+
+        let config = RollupConfig {
+            message_passer_address: Some(Address::repeat_byte(0x16)),
+            ..Default::default()
+        };
+        let builder = StatelessL2Builder::new(
+            &config,
+            OpEvmFactory::default(),
+            NoopTrieDBProvider,
+            NoopTrieHinter,
+            parent,
+        );
+        assert_eq!(builder.message_passer, Address::repeat_byte(0x16));
+    }
+
     #[rstest]
     #[tokio::test]
     async fn test_statelessly_execute_block(
@@ -328,6 +657,203 @@ mod test {
         #[files("*.tar.gz")]
         path: PathBuf,
     ) {
-        run_test_fixture(path).await;
+        run_test_fixture(path, false).await;
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_statelessly_execute_block_with_prefetch(
+        #[base_dir = "./testdata"]
+        #[files("*.tar.gz")]
+        path: PathBuf,
+    ) {
+        run_test_fixture(path, true).await;
+    }
+
+    /// A [`TrieDBProvider`] serving the preimages of several fixtures, so that consecutive
+    /// fixtures can be executed on top of each other.
+    #[derive(Debug)]
+    struct FixturesProvider(Vec<DiskTrieNodeProvider>);
+
+    impl FixturesProvider {
+        fn find<T>(
+            &self,
+            f: impl Fn(&DiskTrieNodeProvider) -> Result<T, TestTrieNodeProviderError>,
+        ) -> Result<T, TestTrieNodeProviderError> {
+            self.0
+                .iter()
+                .find_map(|provider| f(provider).ok())
+                .ok_or(TestTrieNodeProviderError::PreimageNotFound)
+        }
+    }
+
+    impl TrieProvider for FixturesProvider {
+        type Error = TestTrieNodeProviderError;
+
+        fn trie_node_by_hash(&self, key: B256) -> Result<TrieNode, Self::Error> {
+            self.find(|provider| provider.trie_node_by_hash(key))
+        }
+    }
+
+    impl TrieDBProvider for FixturesProvider {
+        fn bytecode_by_hash(&self, code_hash: B256) -> Result<Bytes, Self::Error> {
+            self.find(|provider| provider.bytecode_by_hash(code_hash))
+        }
+
+        fn header_by_hash(&self, hash: B256) -> Result<Header, Self::Error> {
+            self.find(|provider| provider.header_by_hash(hash))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_execute_range() {
+        let mut fixture_dirs = Vec::new();
+        let mut fixtures = Vec::new();
+        let mut providers = Vec::new();
+        for block in 26207960..=26207963 {
+            let path = PathBuf::from(format!("./testdata/block-{block}.tar.gz"));
+            let (dir, fixture, provider) = load_test_fixture(path).await;
+            fixture_dirs.push(dir);
+            fixtures.push(fixture);
+            providers.push(provider);
+        }
+
+        let mut executor = StatelessL2Builder::new(
+            &fixtures[0].rollup_config,
+            OpEvmFactory::default(),
+            FixturesProvider(providers),
+            NoopTrieHinter,
+            fixtures[0].parent_header.clone().seal_slow(),
+        );
+        let outcomes = executor
+            .execute_range(fixtures.iter().map(|fixture| fixture.executing_payload.clone()))
+            .unwrap();
+
+        assert_eq!(outcomes.len(), fixtures.len());
+        for (outcome, fixture) in outcomes.iter().zip(&fixtures) {
+            assert_eq!(outcome.header.hash(), fixture.expected_block_hash);
+        }
+        assert_eq!(executor.parent_header().hash(), fixtures[3].expected_block_hash);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_execute_with_inspector(
+        #[base_dir = "./testdata"]
+        #[files("*.tar.gz")]
+        path: PathBuf,
+    ) {
+        let (_fixture_dir, fixture, provider) = load_test_fixture(path).await;
+
+        let mut executor = StatelessL2Builder::new(
+            &fixture.rollup_config,
+            OpEvmFactory::default(),
+            provider,
+            NoopTrieHinter,
+            fixture.parent_header.seal_slow(),
+        );
+        let mut inspector = CallCounter::default();
+        let outcome =
+            executor.execute_with_inspector(fixture.executing_payload, &mut inspector).unwrap();
+
+        assert_eq!(outcome.header.hash(), fixture.expected_block_hash);
+        assert!(inspector.calls > 0, "The inspector should observe the executed calls");
+    }
+
+    #[rstest]
+    #[tokio::test]
+    #[cfg(feature = "rayon")]
+    async fn test_statelessly_execute_block_with_parallel_state_root(
+        #[base_dir = "./testdata"]
+        #[files("*.tar.gz")]
+        path: PathBuf,
+    ) {
+        let (_fixture_dir, fixture, provider) = load_test_fixture(path).await;
+
+        let mut executor = StatelessL2Builder::new(
+            &fixture.rollup_config,
+            OpEvmFactory::default(),
+            provider,
+            NoopTrieHinter,
+            fixture.parent_header.seal_slow(),
+        )
+        .with_parallel_state_root();
+        let outcome = executor.build_block(fixture.executing_payload).unwrap();
+
+        assert_eq!(outcome.header.hash(), fixture.expected_block_hash);
+    }
+
+    /// A [`TrieDBProvider`] counting the trie node requests made to the inner provider.
+    #[derive(Debug)]
+    struct CountingProvider {
+        inner: DiskTrieNodeProvider,
+        single_requests: AtomicUsize,
+        batch_requests: AtomicUsize,
+    }
+
+    impl TrieProvider for CountingProvider {
+        type Error = TestTrieNodeProviderError;
+
+        fn trie_node_by_hash(&self, key: B256) -> Result<TrieNode, Self::Error> {
+            self.single_requests.fetch_add(1, Ordering::Relaxed);
+            self.inner.trie_node_by_hash(key)
+        }
+
+        fn trie_nodes_by_hash(&self, keys: &[B256]) -> Result<Vec<TrieNode>, Self::Error> {
+            self.batch_requests.fetch_add(1, Ordering::Relaxed);
+            keys.iter().map(|key| self.inner.trie_node_by_hash(*key)).collect()
+        }
+    }
+
+    impl TrieDBProvider for CountingProvider {
+        fn bytecode_by_hash(&self, code_hash: B256) -> Result<Bytes, Self::Error> {
+            self.inner.bytecode_by_hash(code_hash)
+        }
+
+        fn header_by_hash(&self, hash: B256) -> Result<Header, Self::Error> {
+            self.inner.header_by_hash(hash)
+        }
+    }
+
+    /// Builds the block of the given fixture, returning the number of individual and batched trie
+    /// node requests made.
+    async fn count_trie_node_requests(path: PathBuf, prefetch_state: bool) -> (usize, usize) {
+        let (_fixture_dir, fixture, inner) = load_test_fixture(path).await;
+        let provider = CountingProvider {
+            inner,
+            single_requests: AtomicUsize::new(0),
+            batch_requests: AtomicUsize::new(0),
+        };
+
+        let mut executor = StatelessL2Builder::new(
+            &fixture.rollup_config,
+            OpEvmFactory::default(),
+            provider,
+            NoopTrieHinter,
+            fixture.parent_header.seal_slow(),
+        )
+        .with_state_prefetch(prefetch_state);
+        let outcome = executor.build_block(fixture.executing_payload).unwrap();
+        assert_eq!(outcome.header.hash(), fixture.expected_block_hash);
+
+        let provider = &executor.trie_db.fetcher;
+        (
+            provider.single_requests.load(Ordering::Relaxed),
+            provider.batch_requests.load(Ordering::Relaxed),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_prefetch_batches_trie_node_requests() {
+        let path = PathBuf::from("./testdata/block-26207960.tar.gz");
+        let (single, batched) = count_trie_node_requests(path.clone(), false).await;
+        assert_eq!(batched, 0);
+
+        let (prefetch_single, prefetch_batched) = count_trie_node_requests(path, true).await;
+        assert!(prefetch_batched > 0, "The prefetched state should be fetched in batches");
+        assert!(
+            prefetch_single < single,
+            "Prefetching should reveal trie nodes ahead of execution: {prefetch_single} >= {single}"
+        );
     }
 }
@@ -0,0 +1,157 @@
+//! Structured [StateDiff] output for blocks executed by the [StatelessL2Builder].
+//!
+//! [StatelessL2Builder]: crate::StatelessL2Builder
+
+use alloc::collections::BTreeMap;
+use alloy_primitives::{Address, B256, Bytes, U256};
+use revm::{database::BundleState, state::AccountInfo};
+
+/// A structured diff of the state changes made by executing a block.
+///
+/// The [StateDiff] is derived from the [BundleState] produced by block execution, and allows
+/// callers to inspect exactly which accounts, storage slots, and contract code changed without
+/// re-executing the block with a tracer.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StateDiff {
+    /// The accounts modified during execution, keyed by address.
+    pub accounts: BTreeMap<Address, AccountDiff>,
+    /// The contract bytecode deployed during execution, keyed by code hash.
+    pub deployed_code: BTreeMap<B256, Bytes>,
+}
+
+impl StateDiff {
+    /// Returns `true` if the block did not modify any state.
+    pub const fn is_empty(&self) -> bool {
+        self.accounts.is_empty() && self.deployed_code.is_empty()
+    }
+
+    /// Returns the total number of storage slots changed during execution, across all accounts.
+    pub fn changed_slot_count(&self) -> usize {
+        self.accounts.values().map(|account| account.storage.len()).sum()
+    }
+}
+
+impl From<&BundleState> for StateDiff {
+    fn from(bundle: &BundleState) -> Self {
+        let accounts = bundle
+            .state()
+            .iter()
+            .filter(|(_, account)| !account.status.is_not_modified())
+            .map(|(address, account)| {
+                let storage = account
+                    .storage
+                    .iter()
+                    .filter(|(_, slot)| slot.is_changed())
+                    .map(|(key, slot)| {
+                        let diff = StorageDiff {
+                            original: slot.previous_or_original_value,
+                            current: slot.present_value,
+                        };
+                        (*key, diff)
+                    })
+                    .collect();
+                let diff = AccountDiff {
+                    original: account.original_info.as_ref().map(Into::into),
+                    current: account.info.as_ref().map(Into::into),
+                    destroyed: account.was_destroyed(),
+                    storage,
+                };
+                (*address, diff)
+            })
+            .collect();
+
+        let deployed_code =
+            bundle.contracts.iter().map(|(hash, code)| (*hash, code.original_bytes())).collect();
+
+        Self { accounts, deployed_code }
+    }
+}
+
+/// The change to a single account made by executing a block.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AccountDiff {
+    /// The state of the account prior to execution, or [None] if it did not exist.
+    pub original: Option<AccountState>,
+    /// The state of the account after execution, or [None] if it no longer exists.
+    pub current: Option<AccountState>,
+    /// Whether the account was destroyed during execution.
+    pub destroyed: bool,
+    /// The storage slots changed during execution, keyed by slot.
+    pub storage: BTreeMap<U256, StorageDiff>,
+}
+
+/// The trie-relevant state of an account.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AccountState {
+    /// The balance of the account.
+    pub balance: U256,
+    /// The nonce of the account.
+    pub nonce: u64,
+    /// The code hash of the account.
+    pub code_hash: B256,
+}
+
+impl From<&AccountInfo> for AccountState {
+    fn from(info: &AccountInfo) -> Self {
+        Self { balance: info.balance, nonce: info.nonce, code_hash: info.code_hash }
+    }
+}
+
+/// The change to a single storage slot made by executing a block.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StorageDiff {
+    /// The value of the slot prior to execution.
+    pub original: U256,
+    /// The value of the slot after execution.
+    pub current: U256,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use kona_protocol::Predeploys;
+    use revm::{primitives::HashMap, state::Bytecode};
+
+    #[test]
+    fn test_state_diff_from_bundle() {
+        let address = Predeploys::L2_TO_L1_MESSAGE_PASSER;
+        let code = Bytecode::new_raw(Bytes::from_static(&[0x60, 0x00]));
+        let code_hash = code.hash_slow();
+        let original = AccountInfo { nonce: 1, ..Default::default() };
+        let present = AccountInfo { nonce: 2, code_hash, ..Default::default() };
+
+        let bundle = BundleState::builder(0..=0)
+            .state_original_account_info(address, original.clone())
+            .state_present_account_info(address, present.clone())
+            .state_storage(
+                address,
+                HashMap::from_iter([
+                    (U256::from(1), (U256::ZERO, U256::from(7))),
+                    (U256::from(2), (U256::from(3), U256::from(3))),
+                ]),
+            )
+            .contract(code_hash, code.clone())
+            .build();
+
+        let diff = StateDiff::from(&bundle);
+        assert!(!diff.is_empty());
+        assert_eq!(diff.changed_slot_count(), 1);
+
+        let account = &diff.accounts[&address];
+        assert_eq!(account.original, Some(AccountState::from(&original)));
+        assert_eq!(account.current, Some(AccountState::from(&present)));
+        assert!(!account.destroyed);
+        assert_eq!(
+            account.storage[&U256::from(1)],
+            StorageDiff { original: U256::ZERO, current: U256::from(7) }
+        );
+        assert_eq!(diff.deployed_code[&code_hash], code.original_bytes());
+    }
+
+    #[test]
+    fn test_state_diff_empty_bundle() {
+        let diff = StateDiff::from(&BundleState::default());
+        assert!(diff.is_empty());
+        assert_eq!(diff.changed_slot_count(), 0);
+    }
+}
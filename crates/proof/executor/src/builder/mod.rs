@@ -4,6 +4,15 @@ mod core;
 pub use core::{BlockBuildingOutcome, StatelessL2Builder};
 
 mod assemble;
-pub use assemble::compute_receipts_root;
+pub use assemble::{compute_receipts_root, compute_withdrawals_root};
 
 mod env;
+
+mod receipts;
+pub(crate) use receipts::{ReceiptAccumulator, execute_block_incremental};
+
+mod report;
+pub use report::{BlockReportHook, BlockResourceReport, TransactionResources};
+
+mod diff;
+pub use diff::{AccountDiff, AccountState, StateDiff, StorageDiff};
@@ -0,0 +1,165 @@
+//! Incremental receipts root and logs bloom computation for the [StatelessL2Builder].
+//!
+//! [StatelessL2Builder]: crate::StatelessL2Builder
+
+use alloy_eips::Encodable2718;
+use alloy_evm::block::{BlockExecutionError, BlockExecutionResult, BlockExecutor, ExecutableTx};
+use alloy_primitives::{B256, Bloom, logs_bloom};
+use kona_genesis::RollupConfig;
+use kona_mpt::OrderedTrieBuilder;
+use op_alloy_consensus::OpReceiptEnvelope;
+
+/// Accumulates the receipts root and logs bloom of a block, one receipt at a time.
+///
+/// Receipts are encoded into the receipts trie as they are produced, rather than re-encoding the
+/// full list of receipts once the block has been executed. Only one encoded receipt is buffered
+/// at a time, instead of the encodings of the whole block. The decoded receipts themselves are
+/// still held by the block executor, as they are part of the [BlockExecutionResult].
+#[derive(Debug)]
+pub(crate) struct ReceiptAccumulator {
+    /// The incremental receipts trie.
+    trie: OrderedTrieBuilder,
+    /// The running logs bloom of the block.
+    logs_bloom: Bloom,
+    /// Whether to strip the deposit nonce from deposit receipts prior to encoding them.
+    strip_deposit_nonce: bool,
+}
+
+impl ReceiptAccumulator {
+    /// Creates a new [ReceiptAccumulator] for a block with the given timestamp.
+    pub(crate) fn new(config: &RollupConfig, timestamp: u64) -> Self {
+        // There is a minor bug in op-geth and op-erigon where in the Regolith hardfork,
+        // the receipt root calculation does not include the deposit nonce in the
+        // receipt encoding. In the Regolith hardfork, we must strip the deposit nonce
+        // from the receipt encoding to match the receipt root calculation.
+        let strip_deposit_nonce =
+            config.is_regolith_active(timestamp) && !config.is_canyon_active(timestamp);
+        Self { trie: OrderedTrieBuilder::new(), logs_bloom: Bloom::ZERO, strip_deposit_nonce }
+    }
+
+    /// Pushes the next receipt of the block.
+    pub(crate) fn push(&mut self, receipt: &OpReceiptEnvelope) {
+        self.logs_bloom.accrue_bloom(&logs_bloom(receipt.logs()));
+
+        match receipt {
+            OpReceiptEnvelope::Deposit(deposit_receipt) if self.strip_deposit_nonce => {
+                let mut deposit_receipt = deposit_receipt.clone();
+                deposit_receipt.receipt.deposit_nonce = None;
+                self.trie.push_with_encoder(
+                    &OpReceiptEnvelope::Deposit(deposit_receipt),
+                    |receipt, mut buf| receipt.encode_2718(&mut buf),
+                );
+            }
+            _ => self
+                .trie
+                .push_with_encoder(receipt, |receipt, mut buf| receipt.encode_2718(&mut buf)),
+        }
+    }
+
+    /// Consumes the accumulator, returning the receipts root and logs bloom of the block.
+    pub(crate) fn finish(self) -> (B256, Bloom) {
+        (self.trie.root(), self.logs_bloom)
+    }
+}
+
+/// Executes the given transactions with the [BlockExecutor], accumulating each receipt into the
+/// [ReceiptAccumulator] as soon as it is produced.
+pub(crate) fn execute_block_incremental<E>(
+    mut executor: E,
+    transactions: impl IntoIterator<Item = impl ExecutableTx<E>>,
+    receipts: &mut ReceiptAccumulator,
+) -> Result<BlockExecutionResult<OpReceiptEnvelope>, BlockExecutionError>
+where
+    E: BlockExecutor<Receipt = OpReceiptEnvelope>,
+{
+    executor.apply_pre_execution_changes()?;
+    for tx in transactions {
+        executor.execute_transaction(tx)?;
+        if let Some(receipt) = executor.receipts().last() {
+            receipts.push(receipt);
+        }
+    }
+    executor.apply_post_execution_changes()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use alloc::{vec, vec::Vec};
+    use alloy_consensus::{Eip658Value, Receipt, ReceiptWithBloom};
+    use alloy_primitives::{Address, Log, LogData};
+    use kona_genesis::HardForkConfig;
+    use kona_mpt::ordered_trie_with_encoder;
+    use op_alloy_consensus::{OpDepositReceipt, OpDepositReceiptWithBloom};
+
+    fn receipts() -> Vec<OpReceiptEnvelope> {
+        let log = Log {
+            address: Address::repeat_byte(0x42),
+            data: LogData::new_unchecked(vec![B256::repeat_byte(0x01)], Default::default()),
+        };
+        let receipt = Receipt {
+            status: Eip658Value::Eip658(true),
+            cumulative_gas_used: 21_000,
+            logs: vec![log],
+        };
+        let deposit = OpDepositReceiptWithBloom::from(OpDepositReceipt {
+            inner: receipt.clone(),
+            deposit_nonce: Some(7),
+            deposit_receipt_version: None,
+        });
+        (0..200)
+            .map(|i| {
+                if i % 2 == 0 {
+                    OpReceiptEnvelope::Deposit(deposit.clone())
+                } else {
+                    OpReceiptEnvelope::Eip1559(ReceiptWithBloom::from(receipt.clone()))
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_receipt_accumulator_matches_full_computation() {
+        let config = RollupConfig::default();
+        let receipts = receipts();
+
+        let mut accumulator = ReceiptAccumulator::new(&config, 0);
+        receipts.iter().for_each(|r| accumulator.push(r));
+        let (root, bloom) = accumulator.finish();
+
+        let expected_root =
+            ordered_trie_with_encoder(&receipts, |receipt, mut buf| receipt.encode_2718(&mut buf))
+                .root();
+        assert_eq!(root, expected_root);
+        assert_eq!(bloom, logs_bloom(receipts.iter().flat_map(|r| r.logs())));
+    }
+
+    #[test]
+    fn test_receipt_accumulator_strips_regolith_deposit_nonce() {
+        let config = RollupConfig {
+            hardforks: HardForkConfig { regolith_time: Some(0), ..Default::default() },
+            ..Default::default()
+        };
+        let receipts = receipts();
+
+        let mut accumulator = ReceiptAccumulator::new(&config, 0);
+        receipts.iter().for_each(|r| accumulator.push(r));
+        let (root, _) = accumulator.finish();
+
+        let stripped = receipts
+            .iter()
+            .cloned()
+            .map(|receipt| match receipt {
+                OpReceiptEnvelope::Deposit(mut deposit_receipt) => {
+                    deposit_receipt.receipt.deposit_nonce = None;
+                    OpReceiptEnvelope::Deposit(deposit_receipt)
+                }
+                _ => receipt,
+            })
+            .collect::<Vec<_>>();
+        let expected_root =
+            ordered_trie_with_encoder(&stripped, |receipt, mut buf| receipt.encode_2718(&mut buf))
+                .root();
+        assert_eq!(root, expected_root);
+    }
+}
@@ -0,0 +1,157 @@
+//! Per-block resource reporting for the [StatelessL2Builder].
+//!
+//! [StatelessL2Builder]: crate::StatelessL2Builder
+
+use alloc::vec::Vec;
+use alloy_consensus::{Header, Sealed, Transaction, transaction::Recovered};
+use alloy_eips::eip2718::WithEncoded;
+use alloy_primitives::B256;
+use core::{fmt::Debug, time::Duration};
+use op_alloy_consensus::{OpReceiptEnvelope, OpTxEnvelope};
+
+/// A hook receiving the [`BlockResourceReport`] of every block built by the
+/// [`StatelessL2Builder`], e.g. to feed sequencer load-shedding or profiling tooling without
+/// re-tracing the blocks.
+///
+/// [`StatelessL2Builder`]: crate::StatelessL2Builder
+pub trait BlockReportHook: Debug {
+    /// Returns the current time, as a duration since an arbitrary fixed point, used to measure
+    /// the execution time of the blocks.
+    ///
+    /// The executor runs in `no_std` environments, so it has no clock of its own. By default, the
+    /// execution time of the blocks is not measured.
+    fn now(&self) -> Option<Duration> {
+        None
+    }
+
+    /// Called with the resource report of a block, once it is sealed.
+    fn on_block(&mut self, report: &BlockResourceReport);
+}
+
+/// The resources consumed by a transaction of a block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TransactionResources {
+    /// The hash of the transaction.
+    pub hash: B256,
+    /// The gas used by the transaction.
+    pub gas_used: u64,
+    /// The length of the calldata of the transaction, in bytes.
+    pub calldata_bytes: u64,
+}
+
+/// The resources consumed by a block built by the [`StatelessL2Builder`].
+///
+/// [`StatelessL2Builder`]: crate::StatelessL2Builder
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlockResourceReport {
+    /// The number of the block.
+    pub number: u64,
+    /// The hash of the block.
+    pub hash: B256,
+    /// The gas limit of the block.
+    pub gas_limit: u64,
+    /// The total gas used by the block.
+    pub gas_used: u64,
+    /// The total length of the calldata of the transactions of the block, in bytes.
+    pub calldata_bytes: u64,
+    /// The DA footprint gas used by the block, reported in the blob gas used field of the header
+    /// since Jovian. Always `0` before Jovian.
+    pub da_footprint_gas_used: u64,
+    /// The resources consumed by each transaction of the block, in order.
+    pub transactions: Vec<TransactionResources>,
+    /// The time spent building the block, if the [`BlockReportHook`] provides a clock.
+    pub execution_time: Option<Duration>,
+}
+
+impl BlockResourceReport {
+    /// Creates the [`BlockResourceReport`] of a sealed block from its transactions and their
+    /// receipts.
+    pub(crate) fn new(
+        header: &Sealed<Header>,
+        transactions: &[WithEncoded<Recovered<OpTxEnvelope>>],
+        receipts: &[OpReceiptEnvelope],
+        execution_time: Option<Duration>,
+    ) -> Self {
+        let mut cumulative_gas_used = 0;
+        let transactions = transactions
+            .iter()
+            .zip(receipts)
+            .map(|(tx, receipt)| {
+                let gas_used = receipt.cumulative_gas_used().saturating_sub(cumulative_gas_used);
+                cumulative_gas_used = receipt.cumulative_gas_used();
+                TransactionResources {
+                    hash: tx.value().tx_hash(),
+                    gas_used,
+                    calldata_bytes: tx.value().input().len() as u64,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        Self {
+            number: header.number,
+            hash: header.seal(),
+            gas_limit: header.gas_limit,
+            gas_used: header.gas_used,
+            calldata_bytes: transactions.iter().map(|tx| tx.calldata_bytes).sum(),
+            da_footprint_gas_used: header.blob_gas_used.unwrap_or_default(),
+            transactions,
+            execution_time,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use alloc::vec;
+    use alloy_consensus::{Receipt, ReceiptWithBloom};
+    use alloy_primitives::{Address, Bloom, Bytes};
+    use op_alloy_consensus::TxDeposit;
+
+    fn deposit(input: &[u8]) -> WithEncoded<Recovered<OpTxEnvelope>> {
+        let tx = TxDeposit { input: Bytes::copy_from_slice(input), ..Default::default() };
+        let tx = OpTxEnvelope::Deposit(Sealed::new(tx));
+        WithEncoded::new(Bytes::new(), Recovered::new_unchecked(tx, Address::ZERO))
+    }
+
+    fn receipt(cumulative_gas_used: u64) -> OpReceiptEnvelope {
+        let receipt = Receipt { status: true.into(), cumulative_gas_used, logs: vec![] };
+        OpReceiptEnvelope::Legacy(ReceiptWithBloom { receipt, logs_bloom: Bloom::ZERO })
+    }
+
+    #[test]
+    fn test_block_resource_report() {
+        let header = Header {
+            number: 10,
+            gas_limit: 30_000_000,
+            gas_used: 71_000,
+            blob_gas_used: Some(1_600),
+            ..Default::default()
+        }
+        .seal_slow();
+        let transactions = [deposit(&[0; 4]), deposit(&[0; 100])];
+        let receipts = [receipt(50_000), receipt(71_000)];
+
+        let report = BlockResourceReport::new(
+            &header,
+            &transactions,
+            &receipts,
+            Some(Duration::from_millis(5)),
+        );
+        assert_eq!(report.number, 10);
+        assert_eq!(report.hash, header.seal());
+        assert_eq!(report.gas_used, 71_000);
+        assert_eq!(report.calldata_bytes, 104);
+        assert_eq!(report.da_footprint_gas_used, 1_600);
+        assert_eq!(report.execution_time, Some(Duration::from_millis(5)));
+        assert_eq!(
+            report
+                .transactions
+                .iter()
+                .map(|tx| (tx.gas_used, tx.calldata_bytes))
+                .collect::<Vec<_>>(),
+            vec![(50_000, 4), (21_000, 100)]
+        );
+        assert_eq!(report.transactions[0].hash, transactions[0].value().tx_hash());
+    }
+}
@@ -0,0 +1,92 @@
+//! Contains the [StateAccess] type, recording the accounts and storage slots touched during
+//! execution.
+
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloy_primitives::{Address, U256};
+use revm::database::CacheState;
+
+/// The set of accounts and storage slots accessed during (speculative) block execution.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StateAccess {
+    /// The accessed storage slots, keyed by the address of the account that owns them.
+    pub accounts: BTreeMap<Address, BTreeSet<U256>>,
+}
+
+impl StateAccess {
+    /// Returns the number of accessed accounts.
+    pub const fn account_count(&self) -> usize {
+        self.accounts.len()
+    }
+
+    /// Returns the number of accessed storage slots, across all accounts.
+    pub fn slot_count(&self) -> usize {
+        self.accounts.values().map(BTreeSet::len).sum()
+    }
+
+    /// Returns `true` if no accounts were accessed.
+    pub const fn is_empty(&self) -> bool {
+        self.accounts.is_empty()
+    }
+
+    /// Adds the accounts and storage slots of `other` to the set, returning those that were not
+    /// part of it yet.
+    pub fn merge(&mut self, other: Self) -> Self {
+        let mut added = Self::default();
+        for (address, slots) in other.accounts {
+            let is_new_account = !self.accounts.contains_key(&address);
+            let known_slots = self.accounts.entry(address).or_default();
+            let new_slots =
+                slots.into_iter().filter(|slot| known_slots.insert(*slot)).collect::<BTreeSet<_>>();
+            if is_new_account || !new_slots.is_empty() {
+                added.accounts.insert(address, new_slots);
+            }
+        }
+        added
+    }
+}
+
+impl From<&CacheState> for StateAccess {
+    fn from(cache: &CacheState) -> Self {
+        let accounts = cache
+            .accounts
+            .iter()
+            .map(|(address, account)| {
+                let slots = account
+                    .account
+                    .as_ref()
+                    .map(|account| account.storage.keys().copied().collect())
+                    .unwrap_or_default();
+                (*address, slots)
+            })
+            .collect();
+        Self { accounts }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_merge_returns_new_accesses() {
+        let a = Address::repeat_byte(0x0A);
+        let b = Address::repeat_byte(0x0B);
+        let mut access =
+            StateAccess { accounts: BTreeMap::from([(a, BTreeSet::from([U256::from(1)]))]) };
+
+        let added = access.merge(StateAccess {
+            accounts: BTreeMap::from([
+                (a, BTreeSet::from([U256::from(1), U256::from(2)])),
+                (b, BTreeSet::new()),
+            ]),
+        });
+
+        assert_eq!(
+            added.accounts,
+            BTreeMap::from([(a, BTreeSet::from([U256::from(2)])), (b, BTreeSet::new())])
+        );
+        assert_eq!(access.slot_count(), 2);
+        assert_eq!(access.account_count(), 2);
+        assert!(access.merge(added).is_empty());
+    }
+}
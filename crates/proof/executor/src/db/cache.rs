@@ -0,0 +1,236 @@
+//! Contains the [TrieNodeCache], an LRU cache of decoded [TrieNode]s in front of a
+//! [TrieDBProvider].
+
+use super::TrieDBProvider;
+use alloc::{sync::Arc, vec::Vec};
+use alloy_consensus::Header;
+use alloy_primitives::{B256, Bytes};
+use core::num::NonZeroUsize;
+use kona_mpt::{TrieNode, TrieProvider};
+use lru::LruCache;
+use spin::Mutex;
+
+/// The default capacity of the [TrieNodeCache], in number of trie nodes.
+pub const DEFAULT_TRIE_NODE_CACHE_CAPACITY: NonZeroUsize = NonZeroUsize::new(8192).unwrap();
+
+/// Hit and miss counters of a [TrieNodeCache].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TrieNodeCacheStats {
+    /// The number of trie node lookups served from the cache.
+    pub hits: u64,
+    /// The number of trie node lookups that fell through to the inner provider.
+    pub misses: u64,
+}
+
+impl TrieNodeCacheStats {
+    /// Returns the total number of trie node lookups.
+    pub const fn lookups(&self) -> u64 {
+        self.hits + self.misses
+    }
+
+    /// Returns the ratio of lookups served from the cache, in the range `[0, 1]`.
+    ///
+    /// Returns `0` if no lookups have been performed.
+    pub fn hit_rate(&self) -> f64 {
+        match self.lookups() {
+            0 => 0.0,
+            lookups => self.hits as f64 / lookups as f64,
+        }
+    }
+}
+
+/// A [TrieDBProvider] that caches decoded [TrieNode]s in a bounded LRU cache.
+///
+/// The [TrieDB] re-opens the same branch nodes many times while executing a block. Wrapping its
+/// provider in a [TrieNodeCache] avoids re-fetching and re-decoding those nodes, at the cost of
+/// holding up to `capacity` decoded nodes in memory. The capacity may be tuned per chain to trade
+/// off memory against speed, using the exposed [TrieNodeCacheStats].
+///
+/// Bytecode and header lookups are not cached, and are passed through to the inner provider.
+///
+/// Clones of a [TrieNodeCache] share the cached nodes and the counters, so that the cache outlives
+/// the [TrieDB]s built on top of it.
+///
+/// [TrieDB]: crate::TrieDB
+#[derive(Debug, Clone)]
+pub struct TrieNodeCache<F> {
+    /// The inner [TrieDBProvider].
+    inner: F,
+    /// The LRU cache of decoded trie nodes, keyed by their hash.
+    cache: Arc<Mutex<LruCache<B256, TrieNode>>>,
+    /// The hit and miss counters of the cache.
+    stats: Arc<Mutex<TrieNodeCacheStats>>,
+}
+
+impl<F> TrieNodeCache<F> {
+    /// Creates a new [TrieNodeCache] wrapping `inner`, holding at most `capacity` trie nodes.
+    pub fn new(inner: F, capacity: NonZeroUsize) -> Self {
+        Self {
+            inner,
+            cache: Arc::new(Mutex::new(LruCache::new(capacity))),
+            stats: Arc::new(Mutex::new(TrieNodeCacheStats::default())),
+        }
+    }
+
+    /// Returns a reference to the inner provider.
+    pub const fn inner(&self) -> &F {
+        &self.inner
+    }
+
+    /// Consumes the cache, returning the inner provider.
+    pub fn into_inner(self) -> F {
+        self.inner
+    }
+
+    /// Returns the maximum number of trie nodes held by the cache.
+    pub fn capacity(&self) -> NonZeroUsize {
+        self.cache.lock().cap()
+    }
+
+    /// Returns the number of trie nodes currently held by the cache.
+    pub fn len(&self) -> usize {
+        self.cache.lock().len()
+    }
+
+    /// Returns `true` if the cache holds no trie nodes.
+    pub fn is_empty(&self) -> bool {
+        self.cache.lock().is_empty()
+    }
+
+    /// Returns the current hit and miss counters of the cache.
+    pub fn stats(&self) -> TrieNodeCacheStats {
+        *self.stats.lock()
+    }
+
+    /// Resets the hit and miss counters of the cache.
+    pub fn reset_stats(&self) {
+        *self.stats.lock() = TrieNodeCacheStats::default();
+    }
+}
+
+impl<F: TrieProvider> TrieProvider for TrieNodeCache<F> {
+    type Error = F::Error;
+
+    fn trie_node_by_hash(&self, key: B256) -> Result<TrieNode, Self::Error> {
+        if let Some(node) = self.cache.lock().get(&key) {
+            self.stats.lock().hits += 1;
+            return Ok(node.clone());
+        }
+
+        self.stats.lock().misses += 1;
+        let node = self.inner.trie_node_by_hash(key)?;
+        self.cache.lock().put(key, node.clone());
+        Ok(node)
+    }
+
+    fn trie_nodes_by_hash(&self, keys: &[B256]) -> Result<Vec<TrieNode>, Self::Error> {
+        let mut nodes = Vec::with_capacity(keys.len());
+        let mut missing = Vec::new();
+        {
+            let mut cache = self.cache.lock();
+            for (i, key) in keys.iter().enumerate() {
+                let node = cache.get(key).cloned();
+                if node.is_none() {
+                    missing.push((i, *key));
+                }
+                nodes.push(node.unwrap_or(TrieNode::Empty));
+            }
+        }
+
+        let mut stats = self.stats.lock();
+        stats.hits += (keys.len() - missing.len()) as u64;
+        stats.misses += missing.len() as u64;
+        drop(stats);
+
+        if missing.is_empty() {
+            return Ok(nodes);
+        }
+
+        // Fetch all missing nodes from the inner provider in a single batch.
+        let missing_keys = missing.iter().map(|(_, key)| *key).collect::<Vec<_>>();
+        let fetched = self.inner.trie_nodes_by_hash(&missing_keys)?;
+
+        let mut cache = self.cache.lock();
+        for ((i, key), node) in missing.into_iter().zip(fetched) {
+            cache.put(key, node.clone());
+            nodes[i] = node;
+        }
+
+        Ok(nodes)
+    }
+}
+
+impl<F: TrieDBProvider> TrieDBProvider for TrieNodeCache<F> {
+    fn bytecode_by_hash(&self, code_hash: B256) -> Result<Bytes, Self::Error> {
+        self.inner.bytecode_by_hash(code_hash)
+    }
+
+    fn header_by_hash(&self, hash: B256) -> Result<Header, Self::Error> {
+        self.inner.header_by_hash(hash)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::NoopTrieDBProvider;
+
+    #[test]
+    fn test_trie_node_cache_hits_and_misses() {
+        let cache = TrieNodeCache::new(NoopTrieDBProvider, NonZeroUsize::new(2).unwrap());
+        assert!(cache.is_empty());
+
+        let (a, b, c) = (B256::repeat_byte(0xa), B256::repeat_byte(0xb), B256::repeat_byte(0xc));
+        cache.trie_node_by_hash(a).unwrap();
+        cache.trie_node_by_hash(a).unwrap();
+        cache.trie_node_by_hash(b).unwrap();
+        assert_eq!(cache.stats(), TrieNodeCacheStats { hits: 1, misses: 2 });
+        assert_eq!(cache.len(), 2);
+
+        // Inserting a third node evicts the least recently used node, `a`.
+        cache.trie_node_by_hash(c).unwrap();
+        cache.trie_node_by_hash(a).unwrap();
+        assert_eq!(cache.stats(), TrieNodeCacheStats { hits: 1, misses: 4 });
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.capacity().get(), 2);
+    }
+
+    #[test]
+    fn test_trie_node_cache_stats() {
+        let cache = TrieNodeCache::new(NoopTrieDBProvider, DEFAULT_TRIE_NODE_CACHE_CAPACITY);
+        assert_eq!(cache.stats().hit_rate(), 0.0);
+
+        cache.trie_node_by_hash(B256::ZERO).unwrap();
+        cache.trie_node_by_hash(B256::ZERO).unwrap();
+        cache.trie_node_by_hash(B256::ZERO).unwrap();
+        cache.trie_node_by_hash(B256::ZERO).unwrap();
+        assert_eq!(cache.stats().lookups(), 4);
+        assert_eq!(cache.stats().hit_rate(), 0.75);
+
+        cache.reset_stats();
+        assert_eq!(cache.stats(), TrieNodeCacheStats::default());
+    }
+
+    #[test]
+    fn test_trie_node_cache_batched_lookups() {
+        let cache = TrieNodeCache::new(NoopTrieDBProvider, DEFAULT_TRIE_NODE_CACHE_CAPACITY);
+
+        let (a, b) = (B256::repeat_byte(0xa), B256::repeat_byte(0xb));
+        cache.trie_node_by_hash(a).unwrap();
+        assert_eq!(cache.trie_nodes_by_hash(&[a, b, a]).unwrap().len(), 3);
+        assert_eq!(cache.stats(), TrieNodeCacheStats { hits: 2, misses: 2 });
+        assert_eq!(cache.len(), 2);
+    }
+
+    #[test]
+    fn test_trie_node_cache_clones_share_state() {
+        let cache = TrieNodeCache::new(NoopTrieDBProvider, DEFAULT_TRIE_NODE_CACHE_CAPACITY);
+        let clone = cache.clone();
+
+        cache.trie_node_by_hash(B256::ZERO).unwrap();
+        clone.trie_node_by_hash(B256::ZERO).unwrap();
+        assert_eq!(cache.stats(), TrieNodeCacheStats { hits: 1, misses: 1 });
+        assert_eq!(clone.stats(), cache.stats());
+        assert_eq!(clone.len(), 1);
+    }
+}
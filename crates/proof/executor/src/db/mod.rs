@@ -2,12 +2,13 @@
 //! incremental updates through fetching node preimages on the fly during execution.
 
 use crate::errors::{TrieDBError, TrieDBResult};
-use alloc::{string::ToString, vec::Vec};
+use alloc::{format, string::ToString, vec::Vec};
 use alloy_consensus::{EMPTY_ROOT_HASH, Header, Sealed};
 use alloy_primitives::{Address, B256, U256, keccak256};
 use alloy_rlp::{Decodable, Encodable};
 use alloy_trie::TrieAccount;
 use kona_mpt::{Nibbles, TrieHinter, TrieNode, TrieNodeError};
+use kona_protocol::StateStorageProvider;
 use revm::{
     Database,
     database::{BundleState, states::StorageSlot},
@@ -18,6 +19,15 @@ use revm::{
 mod traits;
 pub use traits::{NoopTrieDBProvider, TrieDBProvider};
 
+mod access;
+pub use access::StateAccess;
+
+mod speculative;
+pub(crate) use speculative::SpeculativeTrieDB;
+
+mod cache;
+pub use cache::{DEFAULT_TRIE_NODE_CACHE_CAPACITY, TrieNodeCache, TrieNodeCacheStats};
+
 /// A Trie DB that caches open state in-memory.
 ///
 /// When accounts that don't already exist within the cached [`TrieNode`] are queried, the database
@@ -185,6 +195,11 @@ where
             .hint_account_proof(*address, block_number)
             .map_err(|e| TrieDBError::Provider(e.to_string()))?;
 
+        self.open_trie_account(address)
+    }
+
+    /// Opens the [TrieAccount] of an account within the account trie, without hinting the host.
+    fn open_trie_account(&mut self, address: &Address) -> TrieDBResult<Option<TrieAccount>> {
         // Fetch the account from the trie.
         let hashed_address_nibbles = Nibbles::unpack(keccak256(address.as_slice()));
         let Some(trie_account_rlp) = self.root_node.open(&hashed_address_nibbles, &self.fetcher)?
@@ -199,6 +214,159 @@ where
             .map(Some)
     }
 
+    /// Inserts an account's storage root into the cache, retaining the previously opened storage
+    /// trie if one is present.
+    ///
+    /// Cached storage tries are kept in sync with the account trie by [Self::state_root], so an
+    /// existing entry always commits to the account's current storage root and is not re-hashed.
+    fn insert_storage_root(&mut self, address: Address, storage_root: B256) {
+        self.storage_roots.entry(address).or_insert_with(|| TrieNode::new_blinded(storage_root));
+    }
+
+    /// Prefetches the trie paths of all accounts and storage slots within the [StateAccess].
+    ///
+    /// The account trie is revealed one level at a time, fetching the blinded nodes of all
+    /// account paths at each level in a single batch through
+    /// [TrieProvider::trie_nodes_by_hash]. The storage tries of all existing accounts are then
+    /// revealed in the same manner. Opened paths remain cached for subsequent execution against
+    /// the trie DB.
+    ///
+    /// ## Takes
+    /// - `access`: The [StateAccess] to prefetch.
+    ///
+    /// ## Returns
+    /// - `Ok(())` if all accounts and storage slots were successfully prefetched.
+    /// - `Err(_)` if any trie path could not be opened.
+    ///
+    /// [TrieProvider::trie_nodes_by_hash]: kona_mpt::TrieProvider::trie_nodes_by_hash
+    pub fn prefetch(&mut self, access: &StateAccess) -> TrieDBResult<()> {
+        let block_number = self.parent_block_header.number;
+
+        // Send hints for all account and storage proofs up front, so that the host is able to
+        // serve the batched requests below.
+        for (address, slots) in &access.accounts {
+            self.hinter
+                .hint_account_proof(*address, block_number)
+                .map_err(|e| TrieDBError::Provider(e.to_string()))?;
+            for slot in slots {
+                self.hinter
+                    .hint_storage_proof(*address, *slot, block_number)
+                    .map_err(|e| TrieDBError::Provider(e.to_string()))?;
+            }
+        }
+
+        // Reveal the paths to all accounts within the account trie.
+        let account_paths = access
+            .accounts
+            .keys()
+            .map(|address| Nibbles::unpack(keccak256(address)))
+            .collect::<Vec<_>>();
+        Self::reveal_paths(&mut [(&mut self.root_node, account_paths)], &self.fetcher)?;
+
+        // Load the storage roots of all existing accounts.
+        for address in access.accounts.keys() {
+            if let Some(trie_account) = self.open_trie_account(address)? {
+                self.insert_storage_root(*address, trie_account.storage_root);
+            }
+        }
+
+        // Reveal the paths to all storage slots within the storage tries of existing accounts.
+        let mut storage_paths = self
+            .storage_roots
+            .iter_mut()
+            .filter_map(|(address, storage_root)| {
+                let slots = access.accounts.get(address).filter(|slots| !slots.is_empty())?;
+                let paths = slots
+                    .iter()
+                    .map(|slot| Nibbles::unpack(keccak256(slot.to_be_bytes::<32>())))
+                    .collect::<Vec<_>>();
+                Some((storage_root, paths))
+            })
+            .collect::<Vec<_>>();
+        Self::reveal_paths(&mut storage_paths, &self.fetcher)
+    }
+
+    /// Reveals the given paths within each of the given tries, one level at a time. The blinded
+    /// nodes encountered at each level across all tries are fetched in a single batch.
+    fn reveal_paths(tries: &mut [(&mut TrieNode, Vec<Nibbles>)], fetcher: &F) -> TrieDBResult<()> {
+        loop {
+            let mut commitments = Vec::new();
+            for (root, paths) in tries.iter_mut() {
+                for path in paths.iter() {
+                    if let Some(node) = root.blinded_on_path(path) {
+                        commitments.push(node.blind());
+                    }
+                }
+            }
+            commitments.sort_unstable();
+            commitments.dedup();
+
+            if commitments.is_empty() {
+                return Ok(());
+            }
+
+            let nodes = fetcher
+                .trie_nodes_by_hash(&commitments)
+                .map_err(|e| TrieDBError::Provider(e.to_string()))?;
+            let revealed = commitments.into_iter().zip(nodes).collect::<HashMap<_, _>>();
+
+            for (root, paths) in tries.iter_mut() {
+                for path in paths.iter() {
+                    if let Some(node) = root.blinded_on_path(path) {
+                        let commitment = node.blind();
+                        *node = revealed.get(&commitment).cloned().ok_or_else(|| {
+                            TrieDBError::Provider(format!("Missing trie node {commitment}"))
+                        })?;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Applies a [BundleState] changeset to the [TrieNode] and recomputes the state root hash,
+    /// computing the storage roots of modified accounts in parallel.
+    ///
+    /// The resulting state root is identical to that of [Self::state_root]. This path is intended
+    /// for native environments with many cores, and is not available on FPVM targets.
+    ///
+    /// The storage changes are applied on the calling thread, in the same order as in
+    /// [Self::state_root], and only the hashing of the updated storage tries is spread over the
+    /// thread pool. The trie provider and hinter are never called concurrently: an oracle-backed
+    /// provider shares a single preimage channel, on which concurrent requests and responses would
+    /// interleave, and the host serves a preimage based on the last hint it received.
+    ///
+    /// ## Takes
+    /// - `bundle`: The [BundleState] changeset to apply to the trie DB.
+    ///
+    /// ## Returns
+    /// - `Ok(B256)`: The new state root hash of the trie DB.
+    /// - `Err(_)`: If the state root hash could not be computed.
+    #[cfg(feature = "rayon")]
+    pub fn state_root_parallel(&mut self, bundle: &BundleState) -> TrieDBResult<B256> {
+        use rayon::iter::{IntoParallelRefMutIterator, ParallelIterator};
+
+        debug!(target: "client_executor", "Recomputing state root in parallel");
+
+        // Update the accounts in the trie with the changeset, computing storage roots in parallel.
+        self.update_accounts_with(bundle, |updates, fetcher, hinter| {
+            updates.iter_mut().try_for_each(|update| update.apply(fetcher, hinter))?;
+            updates
+                .par_iter_mut()
+                .for_each(|update| update.root_hash = Some(update.storage_root.blind()));
+            Ok(())
+        })?;
+
+        // Recompute the root hash of the trie.
+        let root = self.root_node.blind();
+
+        debug!(
+            target: "client_executor",
+            "Recomputed state root: {root}",
+        );
+
+        Ok(root)
+    }
+
     /// Modifies the accounts in the storage trie with the given [BundleState] changeset.
     ///
     /// ## Takes
@@ -208,12 +376,59 @@ where
     /// - `Ok(())` if the accounts were successfully updated.
     /// - `Err(_)` if the accounts could not be updated.
     fn update_accounts(&mut self, bundle: &BundleState) -> TrieDBResult<()> {
+        self.update_accounts_with(bundle, |updates, fetcher, hinter| {
+            updates.iter_mut().try_for_each(|update| update.apply(fetcher, hinter))
+        })
+    }
+
+    /// Modifies the accounts in the storage trie with the given [BundleState] changeset, using
+    /// `apply_storage` to compute the storage roots of all modified accounts.
+    ///
+    /// Storage roots are independent of one another, so `apply_storage` is free to compute them
+    /// in any order. The account trie itself is always updated sequentially, in order of the
+    /// hashed account addresses.
+    fn update_accounts_with<S>(
+        &mut self,
+        bundle: &BundleState,
+        apply_storage: S,
+    ) -> TrieDBResult<()>
+    where
+        S: FnOnce(&mut [StorageRootUpdate<'_>], &F, &H) -> TrieDBResult<()>,
+    {
         // Sort the storage keys prior to applying the changeset, to ensure that the order of
         // application is deterministic between runs.
         let mut sorted_state =
             bundle.state().iter().map(|(k, v)| (k, keccak256(*k), v)).collect::<Vec<_>>();
         sorted_state.sort_by_key(|(_, hashed_addr, _)| *hashed_addr);
 
+        // Take the storage tries of all modified, non-destroyed accounts out of the cache, so
+        // that their roots can be recomputed independently.
+        let mut updates = sorted_state
+            .iter()
+            .filter(|(_, _, account)| !account.status.is_not_modified() && !account.was_destroyed())
+            .map(|(address, _, bundle_account)| {
+                let storage_root = self
+                    .storage_roots
+                    .remove(*address)
+                    .unwrap_or_else(|| TrieNode::new_blinded(EMPTY_ROOT_HASH));
+
+                // Sort the hashed storage keys prior to applying the changeset, to ensure that the
+                // order of application is deterministic between runs.
+                let mut storage = bundle_account
+                    .storage
+                    .iter()
+                    .map(|(k, v)| (keccak256(k.to_be_bytes::<32>()), v))
+                    .collect::<Vec<_>>();
+                storage.sort_by_key(|(slot, _)| *slot);
+
+                StorageRootUpdate { address: **address, storage_root, storage, root_hash: None }
+            })
+            .collect::<Vec<_>>();
+
+        // Recompute the storage roots of all modified accounts.
+        apply_storage(&mut updates, &self.fetcher, &self.hinter)?;
+        let mut updates = updates.into_iter();
+
         for (address, hashed_address, bundle_account) in sorted_state {
             if bundle_account.status.is_not_modified() {
                 continue;
@@ -232,41 +447,18 @@ where
             let account_info =
                 bundle_account.account_info().ok_or(TrieDBError::MissingAccountInfo)?;
 
-            let mut trie_account = TrieAccount {
+            // Recompute the account storage root, and return the storage trie to the cache.
+            let StorageRootUpdate { storage_root, root_hash, .. } = updates
+                .next()
+                .filter(|update| update.address == *address)
+                .ok_or(TrieDBError::MissingStorageRootUpdate(*address))?;
+            let trie_account = TrieAccount {
                 balance: account_info.balance,
                 nonce: account_info.nonce,
                 code_hash: account_info.code_hash,
-                ..Default::default()
+                storage_root: root_hash.unwrap_or_else(|| storage_root.blind()),
             };
-
-            // Update the account's storage root
-            let acc_storage_root = self
-                .storage_roots
-                .entry(*address)
-                .or_insert_with(|| TrieNode::new_blinded(EMPTY_ROOT_HASH));
-
-            // Sort the hashed storage keys prior to applying the changeset, to ensure that the
-            // order of application is deterministic between runs.
-            let mut sorted_storage = bundle_account
-                .storage
-                .iter()
-                .map(|(k, v)| (keccak256(k.to_be_bytes::<32>()), v))
-                .collect::<Vec<_>>();
-            sorted_storage.sort_by_key(|(slot, _)| *slot);
-
-            sorted_storage.into_iter().try_for_each(|(hashed_key, value)| {
-                Self::change_storage(
-                    acc_storage_root,
-                    hashed_key,
-                    value,
-                    &self.fetcher,
-                    &self.hinter,
-                )
-            })?;
-
-            // Recompute the account storage root.
-            let root = acc_storage_root.blind();
-            trie_account.storage_root = root;
+            self.storage_roots.insert(*address, storage_root);
 
             // RLP encode the trie account for insertion.
             let mut account_buf = Vec::with_capacity(trie_account.length());
@@ -320,6 +512,39 @@ where
     }
 }
 
+/// A pending update to the storage trie of a single account.
+#[derive(Debug)]
+struct StorageRootUpdate<'a> {
+    /// The address of the account.
+    address: Address,
+    /// The storage trie of the account.
+    storage_root: TrieNode,
+    /// The changed storage slots of the account, sorted by hashed slot key.
+    storage: Vec<(B256, &'a StorageSlot)>,
+    /// The root hash of the updated storage trie, if it was already computed.
+    root_hash: Option<B256>,
+}
+
+impl StorageRootUpdate<'_> {
+    /// Applies the storage slot changes to the storage trie of the account.
+    fn apply<F, H>(&mut self, fetcher: &F, hinter: &H) -> TrieDBResult<()>
+    where
+        F: TrieDBProvider,
+        H: TrieHinter,
+    {
+        trace!(target: "client_executor", address = ?self.address, "Updating storage root");
+        self.storage.iter().try_for_each(|(hashed_key, value)| {
+            TrieDB::<F, H>::change_storage(
+                &mut self.storage_root,
+                *hashed_key,
+                value,
+                fetcher,
+                hinter,
+            )
+        })
+    }
+}
+
 impl<F, H> Database for TrieDB<F, H>
 where
     F: TrieDBProvider,
@@ -337,7 +562,7 @@ where
         };
 
         // Insert the account's storage root into the cache.
-        self.storage_roots.insert(address, TrieNode::new_blinded(trie_account.storage_root));
+        self.insert_storage_root(address, trie_account.storage_root);
 
         // Return a partial DB account. The storage and code are not loaded out-right, and are
         // loaded optimistically in the `Database` + `DatabaseRef` trait implementations.
@@ -412,6 +637,20 @@ where
     }
 }
 
+impl<F, H> StateStorageProvider for TrieDB<F, H>
+where
+    F: TrieDBProvider,
+    H: TrieHinter,
+{
+    type Error = TrieDBError;
+
+    fn storage_at(&mut self, address: Address, slot: U256) -> Result<U256, Self::Error> {
+        // Load the account first, so that its storage root is cached.
+        self.basic(address)?;
+        self.storage(address, slot)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -444,6 +683,21 @@ mod tests {
         assert!(storage_roots.is_empty());
     }
 
+    #[test]
+    fn test_insert_storage_root_retains_opened_trie() {
+        let mut db = new_test_db();
+        let address = Address::repeat_byte(0x01);
+        let opened = TrieNode::Leaf { prefix: Nibbles::unpack([0xAA; 32]), value: [0x01].into() };
+        db.storage_roots.insert(address, opened.clone());
+
+        db.insert_storage_root(address, opened.blind());
+        assert_eq!(db.storage_roots.get(&address), Some(&opened));
+
+        let other = Address::repeat_byte(0x02);
+        db.insert_storage_root(other, EMPTY_ROOT_HASH);
+        assert_eq!(db.storage_roots.get(&other), Some(&TrieNode::new_blinded(EMPTY_ROOT_HASH)));
+    }
+
     #[test]
     fn test_block_hash_above_range() {
         let mut db = new_test_db();
@@ -474,4 +728,150 @@ mod tests {
             b256!("78dec18c6d7da925bbe773c315653cdc70f6444ed6c1de9ac30bdb36cff74c3b")
         );
     }
+
+    #[test]
+    #[cfg(feature = "rayon")]
+    fn test_state_root_parallel_matches_sequential() {
+        use alloy_primitives::{address, map::HashMap};
+
+        let info = AccountInfo { nonce: 1, balance: U256::from(100), ..Default::default() };
+        let storage = HashMap::from_iter([
+            (U256::from(1), (U256::ZERO, U256::from(1))),
+            (U256::from(2), (U256::ZERO, U256::from(2))),
+        ]);
+        let bundle = BundleState::builder(0..=0)
+            .state_present_account_info(
+                address!("0000000000000000000000000000000000000001"),
+                info.clone(),
+            )
+            .state_storage(address!("0000000000000000000000000000000000000001"), storage.clone())
+            .state_present_account_info(address!("0000000000000000000000000000000000000002"), info)
+            .state_storage(address!("0000000000000000000000000000000000000002"), storage)
+            .build();
+
+        let mut sequential = new_test_db();
+        let mut parallel = new_test_db();
+        assert_eq!(
+            sequential.state_root(&bundle).unwrap(),
+            parallel.state_root_parallel(&bundle).unwrap()
+        );
+    }
+
+    /// A [TrieDBProvider] fetching trie nodes through an [kona_preimage::OracleReader].
+    #[cfg(feature = "rayon")]
+    #[derive(Debug)]
+    struct OracleTrieProvider {
+        oracle: kona_preimage::OracleReader<kona_preimage::NativeChannel>,
+        runtime: tokio::runtime::Handle,
+    }
+
+    #[cfg(feature = "rayon")]
+    impl kona_mpt::TrieProvider for OracleTrieProvider {
+        type Error = alloc::string::String;
+
+        fn trie_node_by_hash(&self, key: B256) -> Result<TrieNode, Self::Error> {
+            use kona_preimage::{PreimageKey, PreimageOracleClient};
+
+            let preimage = self
+                .runtime
+                .block_on(self.oracle.get(PreimageKey::new_keccak256(*key)))
+                .map_err(|e| e.to_string())?;
+            TrieNode::decode(&mut preimage.as_slice()).map_err(|e| e.to_string())
+        }
+    }
+
+    #[cfg(feature = "rayon")]
+    impl TrieDBProvider for OracleTrieProvider {
+        fn bytecode_by_hash(&self, _: B256) -> Result<alloy_primitives::Bytes, Self::Error> {
+            Err("bytecode is not served".into())
+        }
+
+        fn header_by_hash(&self, _: B256) -> Result<Header, Self::Error> {
+            Err("headers are not served".into())
+        }
+    }
+
+    /// Collects the preimages of the given trie node and all of its children.
+    #[cfg(feature = "rayon")]
+    fn collect_preimages(
+        node: &TrieNode,
+        preimages: &mut HashMap<kona_preimage::PreimageKey, Vec<u8>>,
+    ) {
+        let encoded = alloy_rlp::encode(node);
+        preimages.insert(kona_preimage::PreimageKey::new_keccak256(*keccak256(&encoded)), encoded);
+        match node {
+            TrieNode::Branch { stack } => {
+                stack.iter().for_each(|child| collect_preimages(child, preimages))
+            }
+            TrieNode::Extension { node, .. } => collect_preimages(node, preimages),
+            _ => {}
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "rayon")]
+    fn test_state_root_parallel_through_oracle() {
+        use kona_preimage::{
+            BidirectionalChannel, OracleReader, OracleServer, PreimageFetcher, PreimageKey,
+            PreimageOracleServer, errors::PreimageOracleResult,
+        };
+
+        struct MapFetcher(HashMap<PreimageKey, Vec<u8>>);
+
+        #[async_trait::async_trait]
+        impl PreimageFetcher for MapFetcher {
+            async fn get_preimage(&self, key: PreimageKey) -> PreimageOracleResult<Vec<u8>> {
+                // Serve unknown keys as empty preimages, so that the test fails to decode them
+                // rather than stalling the client.
+                Ok(self.0.get(&key).cloned().unwrap_or_default())
+            }
+        }
+
+        let addresses = (1..=8).map(Address::with_last_byte).collect::<Vec<_>>();
+        let info = AccountInfo { nonce: 1, balance: U256::from(100), ..Default::default() };
+        let bundle_with = |offset: u64| {
+            let mut builder = BundleState::builder(0..=0);
+            for address in &addresses {
+                let storage = (0..32u64)
+                    .map(|i| (U256::from(i), (U256::ZERO, U256::from(i + offset))))
+                    .collect();
+                builder = builder
+                    .state_present_account_info(*address, info.clone())
+                    .state_storage(*address, storage);
+            }
+            builder.build()
+        };
+
+        // Build the parent state, and serve all of its trie nodes through the oracle.
+        let mut parent = new_test_db();
+        let parent_root = parent.state_root(&bundle_with(1)).unwrap();
+        let mut preimages = HashMap::default();
+        collect_preimages(parent.root(), &mut preimages);
+        for address in &addresses {
+            collect_preimages(parent.storage_roots().get(address).unwrap(), &mut preimages);
+        }
+
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let channel = BidirectionalChannel::new().unwrap();
+        runtime.spawn(async move {
+            let server = OracleServer::new(channel.host);
+            let fetcher = MapFetcher(preimages);
+            while server.next_preimage_request(&fetcher).await.is_ok() {}
+        });
+
+        let provider = OracleTrieProvider {
+            oracle: OracleReader::new(channel.client),
+            runtime: runtime.handle().clone(),
+        };
+        let header = Header { state_root: parent_root, ..Default::default() };
+        let mut db = TrieDB::new(header.seal_slow(), provider, NoopTrieHinter);
+
+        // Load the accounts, so that their storage tries are opened through the oracle.
+        for address in &addresses {
+            db.basic(*address).unwrap();
+        }
+
+        let bundle = bundle_with(2);
+        assert_eq!(parent.state_root(&bundle).unwrap(), db.state_root_parallel(&bundle).unwrap());
+    }
 }
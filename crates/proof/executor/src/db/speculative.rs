@@ -0,0 +1,154 @@
+//! Contains the [SpeculativeTrieDB], a view of a [TrieDB] that reads state without fetching trie
+//! nodes.
+
+use super::{TrieDB, TrieDBProvider};
+use crate::errors::TrieDBError;
+use alloy_primitives::{Address, B256, U256, keccak256};
+use alloy_rlp::Decodable;
+use alloy_trie::TrieAccount;
+use kona_mpt::{Nibbles, TrieHinter, TrieNode, TrieNodeError, TrieProvider};
+use revm::{
+    Database,
+    state::{AccountInfo, Bytecode},
+};
+
+/// A [TrieProvider] that serves no trie nodes, confining trie walks to the revealed nodes.
+#[derive(Debug, Clone, Copy)]
+struct RevealedNodesOnly;
+
+impl TrieProvider for RevealedNodesOnly {
+    type Error = &'static str;
+
+    fn trie_node_by_hash(&self, _key: B256) -> Result<TrieNode, Self::Error> {
+        Err("trie node is not revealed")
+    }
+}
+
+/// A [Database] over a [TrieDB] that reads accounts and storage slots from the revealed trie
+/// nodes only, neither fetching nor hinting.
+///
+/// State behind a blinded trie node reads as a non-existent account or an empty storage slot, so
+/// execution against this database may diverge from the canonical execution. It is used to
+/// discover the state that a block accesses, which is then revealed in batches with
+/// [TrieDB::prefetch]. Bytecode and block hashes are read through the [TrieDB] as usual.
+#[derive(Debug)]
+pub(crate) struct SpeculativeTrieDB<'a, F, H>
+where
+    F: TrieDBProvider,
+    H: TrieHinter,
+{
+    trie_db: &'a mut TrieDB<F, H>,
+}
+
+impl<'a, F, H> SpeculativeTrieDB<'a, F, H>
+where
+    F: TrieDBProvider,
+    H: TrieHinter,
+{
+    /// Creates a new [SpeculativeTrieDB] over the given [TrieDB].
+    pub(crate) const fn new(trie_db: &'a mut TrieDB<F, H>) -> Self {
+        Self { trie_db }
+    }
+}
+
+impl<F, H> Database for SpeculativeTrieDB<'_, F, H>
+where
+    F: TrieDBProvider,
+    H: TrieHinter,
+{
+    type Error = TrieDBError;
+
+    fn basic(&mut self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+        let path = Nibbles::unpack(keccak256(address.as_slice()));
+        let Ok(Some(trie_account_rlp)) = self.trie_db.root_node.open(&path, &RevealedNodesOnly)
+        else {
+            return Ok(None);
+        };
+        let trie_account =
+            TrieAccount::decode(&mut trie_account_rlp.as_ref()).map_err(TrieNodeError::RLPError)?;
+
+        self.trie_db.insert_storage_root(address, trie_account.storage_root);
+
+        Ok(Some(AccountInfo {
+            balance: trie_account.balance,
+            nonce: trie_account.nonce,
+            code_hash: trie_account.code_hash,
+            code: None,
+        }))
+    }
+
+    fn code_by_hash(&mut self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+        self.trie_db.code_by_hash(code_hash)
+    }
+
+    fn storage(&mut self, address: Address, index: U256) -> Result<U256, Self::Error> {
+        let Some(storage_root) = self.trie_db.storage_roots.get_mut(&address) else {
+            return Ok(U256::ZERO);
+        };
+        let path = Nibbles::unpack(keccak256(index.to_be_bytes::<32>().as_slice()));
+        let Ok(Some(slot_value)) = storage_root.open(&path, &RevealedNodesOnly) else {
+            return Ok(U256::ZERO);
+        };
+        Ok(U256::decode(&mut slot_value.as_ref()).map_err(TrieNodeError::RLPError)?)
+    }
+
+    fn block_hash(&mut self, block_number: u64) -> Result<B256, Self::Error> {
+        self.trie_db.block_hash(block_number)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::NoopTrieDBProvider;
+    use alloy_consensus::{EMPTY_ROOT_HASH, Header, Sealable};
+    use alloy_primitives::KECCAK256_EMPTY;
+    use kona_mpt::NoopTrieHinter;
+
+    fn new_test_db(state_root: B256) -> TrieDB<NoopTrieDBProvider, NoopTrieHinter> {
+        let header = Header { state_root, ..Default::default() };
+        TrieDB::new(header.seal_slow(), NoopTrieDBProvider, NoopTrieHinter)
+    }
+
+    #[test]
+    fn test_speculative_reads_blinded_state_as_empty() {
+        let mut trie_db = new_test_db(B256::repeat_byte(0x01));
+        let mut db = SpeculativeTrieDB::new(&mut trie_db);
+
+        let address = Address::repeat_byte(0xAA);
+        assert_eq!(db.basic(address).unwrap(), None);
+        assert_eq!(db.storage(address, U256::from(1)).unwrap(), U256::ZERO);
+
+        // The blinded root is left in place rather than revealed through the provider.
+        assert_eq!(trie_db.root(), &TrieNode::new_blinded(B256::repeat_byte(0x01)));
+        assert!(trie_db.storage_roots().is_empty());
+    }
+
+    #[test]
+    fn test_speculative_reads_revealed_account() {
+        let address = Address::repeat_byte(0xAA);
+        let account = TrieAccount {
+            nonce: 1,
+            balance: U256::from(2),
+            storage_root: EMPTY_ROOT_HASH,
+            code_hash: KECCAK256_EMPTY,
+        };
+        let mut root = TrieNode::Empty;
+        root.insert(
+            &Nibbles::unpack(keccak256(address.as_slice())),
+            alloy_rlp::encode(account).into(),
+            &NoopTrieDBProvider,
+        )
+        .unwrap();
+
+        let mut trie_db = new_test_db(root.blind());
+        trie_db.root_node = root;
+        let mut db = SpeculativeTrieDB::new(&mut trie_db);
+
+        let info = db.basic(address).unwrap().unwrap();
+        assert_eq!(info.nonce, 1);
+        assert_eq!(info.balance, U256::from(2));
+        assert_eq!(db.storage(address, U256::from(1)).unwrap(), U256::ZERO);
+        assert!(trie_db.storage_roots().contains_key(&address));
+    }
+}
@@ -6,6 +6,7 @@
 
 use alloc::string::String;
 use alloy_evm::block::BlockExecutionError;
+use alloy_primitives::Address;
 use kona_mpt::TrieNodeError;
 use op_alloy_consensus::EIP1559ParamError;
 use revm::context::DBErrorMarker;
@@ -255,6 +256,17 @@ pub enum TrieDBError {
     /// - Proof verification failures
     #[error("Missing account info for bundle account.")]
     MissingAccountInfo,
+    /// The recomputed storage trie of a modified account is missing.
+    ///
+    /// This error occurs when the storage root updates of the modified accounts
+    /// do not line up with the accounts of the [`BundleState`] changeset. It
+    /// indicates an internal inconsistency of the [`TrieDB`] rather than
+    /// invalid witness data.
+    ///
+    /// [`BundleState`]: revm::database::BundleState
+    /// [`TrieDB`]: crate::TrieDB
+    #[error("Missing storage root update for account {0}.")]
+    MissingStorageRootUpdate(Address),
     /// Trie node operation failed due to invalid node data or proof.
     ///
     /// This error wraps [`TrieNodeError`] variants that occur during individual
@@ -13,10 +13,20 @@ extern crate alloc;
 extern crate tracing;
 
 mod db;
-pub use db::{NoopTrieDBProvider, TrieDB, TrieDBProvider};
+pub use db::{
+    DEFAULT_TRIE_NODE_CACHE_CAPACITY, NoopTrieDBProvider, StateAccess, TrieDB, TrieDBProvider,
+    TrieNodeCache, TrieNodeCacheStats,
+};
 
 mod builder;
-pub use builder::{BlockBuildingOutcome, StatelessL2Builder, compute_receipts_root};
+pub use builder::{
+    AccountDiff, AccountState, BlockBuildingOutcome, BlockReportHook, BlockResourceReport,
+    StateDiff, StatelessL2Builder, StorageDiff, TransactionResources, compute_receipts_root,
+    compute_withdrawals_root,
+};
+
+mod precompiles;
+pub use precompiles::{PrecompileOverride, PrecompileOverrides};
 
 mod errors;
 pub use errors::{ExecutorError, ExecutorResult, TrieDBError, TrieDBResult};
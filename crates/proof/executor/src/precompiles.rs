@@ -0,0 +1,149 @@
+//! A registry of precompile overrides, allowing hosts to substitute accelerated or oracle-backed
+//! precompile implementations when executing inside of an FPVM.
+
+use alloc::vec::Vec;
+use alloy_primitives::Address;
+use op_revm::OpSpecId;
+use revm::primitives::HashMap;
+
+/// A single precompile override, substituting the precompile at `address` with `precompile` from
+/// the `activation` hardfork onwards.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrecompileOverride<F> {
+    /// The address of the overridden precompile.
+    pub address: Address,
+    /// The first hardfork in which the override is active.
+    pub activation: OpSpecId,
+    /// The overriding precompile implementation.
+    pub precompile: F,
+}
+
+impl<F> PrecompileOverride<F> {
+    /// Creates a new [`PrecompileOverride`].
+    pub const fn new(address: Address, activation: OpSpecId, precompile: F) -> Self {
+        Self { address, activation, precompile }
+    }
+}
+
+/// A registry of [`PrecompileOverride`]s, keyed by address and hardfork.
+///
+/// The registry is generic over the precompile implementation `F`, so that EVM factories may
+/// register overrides with whatever calling convention their precompile provider expects (e.g.
+/// functions that additionally take a preimage oracle client).
+///
+/// When multiple overrides for the same address are active in a given hardfork, the override
+/// registered last takes precedence. Overrides should therefore be registered in order of
+/// activation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrecompileOverrides<F> {
+    /// The registered overrides, in order of registration.
+    overrides: Vec<PrecompileOverride<F>>,
+}
+
+impl<F> Default for PrecompileOverrides<F> {
+    fn default() -> Self {
+        Self { overrides: Vec::new() }
+    }
+}
+
+impl<F> PrecompileOverrides<F> {
+    /// Creates a new, empty [`PrecompileOverrides`] registry.
+    pub const fn new() -> Self {
+        Self { overrides: Vec::new() }
+    }
+
+    /// Registers a precompile override for `address`, active from the `activation` hardfork
+    /// onwards.
+    pub fn register(&mut self, address: Address, activation: OpSpecId, precompile: F) {
+        self.overrides.push(PrecompileOverride::new(address, activation, precompile));
+    }
+
+    /// Registers a precompile override for `address`, active from the `activation` hardfork
+    /// onwards, and returns the updated registry.
+    pub fn with_override(mut self, address: Address, activation: OpSpecId, precompile: F) -> Self {
+        self.register(address, activation, precompile);
+        self
+    }
+
+    /// Returns the number of registered overrides.
+    pub const fn len(&self) -> usize {
+        self.overrides.len()
+    }
+
+    /// Returns `true` if no overrides are registered.
+    pub const fn is_empty(&self) -> bool {
+        self.overrides.is_empty()
+    }
+
+    /// Returns an iterator over all registered overrides, in order of registration.
+    pub fn iter(&self) -> impl Iterator<Item = &PrecompileOverride<F>> {
+        self.overrides.iter()
+    }
+}
+
+impl<F: Clone> PrecompileOverrides<F> {
+    /// Returns the override for `address` that is active in the given hardfork, if any.
+    pub fn get(&self, address: &Address, spec: OpSpecId) -> Option<F> {
+        self.overrides
+            .iter()
+            .rev()
+            .find(|o| o.address == *address && spec.is_enabled_in(o.activation))
+            .map(|o| o.precompile.clone())
+    }
+
+    /// Returns all overrides that are active in the given hardfork, keyed by address.
+    pub fn active(&self, spec: OpSpecId) -> HashMap<Address, F> {
+        let mut active = HashMap::default();
+        for o in self.overrides.iter().filter(|o| spec.is_enabled_in(o.activation)) {
+            active.insert(o.address, o.precompile.clone());
+        }
+        active
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use alloy_primitives::address;
+
+    const ECRECOVER: Address = address!("0000000000000000000000000000000000000001");
+    const PAIRING: Address = address!("0000000000000000000000000000000000000008");
+
+    fn registry() -> PrecompileOverrides<&'static str> {
+        PrecompileOverrides::new()
+            .with_override(ECRECOVER, OpSpecId::BEDROCK, "ecrecover")
+            .with_override(PAIRING, OpSpecId::BEDROCK, "pairing")
+            .with_override(PAIRING, OpSpecId::GRANITE, "pairing_granite")
+    }
+
+    #[test]
+    fn test_precompile_overrides_empty() {
+        let overrides = PrecompileOverrides::<()>::default();
+        assert!(overrides.is_empty());
+        assert!(overrides.active(OpSpecId::ISTHMUS).is_empty());
+        assert_eq!(overrides.get(&ECRECOVER, OpSpecId::ISTHMUS), None);
+    }
+
+    #[test]
+    fn test_precompile_overrides_active_by_hardfork() {
+        let overrides = registry();
+        assert_eq!(overrides.len(), 3);
+
+        let fjord = overrides.active(OpSpecId::FJORD);
+        assert_eq!(fjord.len(), 2);
+        assert_eq!(fjord[&PAIRING], "pairing");
+
+        let granite = overrides.active(OpSpecId::GRANITE);
+        assert_eq!(granite.len(), 2);
+        assert_eq!(granite[&ECRECOVER], "ecrecover");
+        assert_eq!(granite[&PAIRING], "pairing_granite");
+    }
+
+    #[test]
+    fn test_precompile_overrides_get() {
+        let overrides = registry();
+        assert_eq!(overrides.get(&PAIRING, OpSpecId::HOLOCENE), Some("pairing_granite"));
+        assert_eq!(overrides.get(&PAIRING, OpSpecId::ECOTONE), Some("pairing"));
+        assert_eq!(overrides.get(&Address::ZERO, OpSpecId::ECOTONE), None);
+    }
+}
@@ -16,11 +16,42 @@ use op_alloy_rpc_types_engine::OpPayloadAttributes;
 use rocksdb::{DB, Options};
 use serde::{Deserialize, Serialize};
 use std::{path::PathBuf, sync::Arc};
+use tempfile::TempDir;
 use tokio::{fs, runtime::Handle, sync::Mutex};
 
 /// Executes a [ExecutorTestFixture] stored at the passed `fixture_path` and asserts that the
 /// produced block hash matches the expected block hash.
-pub async fn run_test_fixture(fixture_path: PathBuf) {
+///
+/// If `prefetch_state` is set, the speculative state prefetching pass is enabled.
+pub async fn run_test_fixture(fixture_path: PathBuf, prefetch_state: bool) {
+    let (_fixture_dir, fixture, provider) = load_test_fixture(fixture_path).await;
+
+    let mut executor = StatelessL2Builder::new(
+        &fixture.rollup_config,
+        OpEvmFactory::default(),
+        provider,
+        NoopTrieHinter,
+        fixture.parent_header.seal_slow(),
+    )
+    .with_state_prefetch(prefetch_state);
+
+    let outcome = executor.build_block(fixture.executing_payload).unwrap();
+
+    assert_eq!(
+        outcome.header.hash(),
+        fixture.expected_block_hash,
+        "Produced header does not match the expected header"
+    );
+    assert!(outcome.state_diff.is_none(), "State diff should only be computed when enabled");
+}
+
+/// Untars the [ExecutorTestFixture] stored at the passed `fixture_path`, returning the temporary
+/// directory holding it, the fixture, and a [DiskTrieNodeProvider] over its key-value store.
+///
+/// The returned [TempDir] must be kept alive for as long as the provider is used.
+pub async fn load_test_fixture(
+    fixture_path: PathBuf,
+) -> (TempDir, ExecutorTestFixture, DiskTrieNodeProvider) {
     // First, untar the fixture.
     let fixture_dir = tempfile::tempdir().expect("Failed to create temporary directory");
     tokio::process::Command::new("tar")
@@ -43,21 +74,7 @@ pub async fn run_test_fixture(fixture_path: PathBuf) {
         serde_json::from_slice(&fs::read(fixture_dir.path().join("fixture.json")).await.unwrap())
             .expect("Failed to deserialize fixture");
 
-    let mut executor = StatelessL2Builder::new(
-        &fixture.rollup_config,
-        OpEvmFactory::default(),
-        provider,
-        NoopTrieHinter,
-        fixture.parent_header.seal_slow(),
-    );
-
-    let outcome = executor.build_block(fixture.executing_payload).unwrap();
-
-    assert_eq!(
-        outcome.header.hash(),
-        fixture.expected_block_hash,
-        "Produced header does not match the expected header"
-    );
+    (fixture_dir, fixture, provider)
 }
 
 /// The test fixture format for the [`StatelessL2Builder`].
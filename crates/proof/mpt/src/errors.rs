@@ -1,6 +1,7 @@
 //! Errors for the `kona-derive` crate.
 
 use alloc::string::String;
+use alloy_primitives::B256;
 use thiserror::Error;
 
 /// A [Result] type alias where the error is [TrieNodeError].
@@ -28,6 +29,28 @@ pub enum TrieNodeError {
     Provider(String),
 }
 
+/// An error type for the verification of account, storage, and range proofs.
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum ProofVerificationError {
+    /// The proof is incomplete, or does not commit to the trie root.
+    #[error("{0}")]
+    TrieNode(#[from] TrieNodeError),
+    /// Failed to decode the value proven by the proof.
+    #[error("Failed to decode proven value: {0}")]
+    InvalidValue(alloy_rlp::Error),
+    /// The keys of a range are not in strictly ascending order from the first key.
+    #[error("Range keys are not in strictly ascending order from the first key")]
+    InvalidRange,
+    /// The root of the trie rebuilt from a range proof does not match the expected root.
+    #[error("Range proof root mismatch: expected {expected}, computed {computed}")]
+    RootMismatch {
+        /// The expected root.
+        expected: B256,
+        /// The root computed from the range proof.
+        computed: B256,
+    },
+}
+
 /// A [Result] type alias where the error is [OrderedListWalkerError].
 pub type OrderedListWalkerResult<T> = Result<T, OrderedListWalkerError>;
 
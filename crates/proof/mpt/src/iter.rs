@@ -0,0 +1,174 @@
+//! This module contains the [TrieNodeIter] struct, which iterates over the leaves of a trie in
+//! ascending key order.
+
+use crate::{TrieNode, TrieNodeResult, TrieProvider};
+use alloc::{vec, vec::Vec};
+use alloy_primitives::Bytes;
+use alloy_trie::Nibbles;
+
+/// A [TrieNodeIter] iterates over the leaves of a trie in ascending order of their keys, yielding
+/// the full path and value of each leaf.
+///
+/// Blinded nodes are revealed lazily with the [TrieProvider] as the iterator walks over them, so
+/// only the part of the trie that is iterated over has to be available. If a preimage can not be
+/// fetched, the error is yielded and the iterator is exhausted.
+#[derive(Debug)]
+pub struct TrieNodeIter<'a, F> {
+    /// The nodes left to visit with their paths, with the next node on top.
+    stack: Vec<(Nibbles, TrieNode)>,
+    /// The key to start iterating from, if any.
+    start: Option<Nibbles>,
+    /// The preimage fetcher for blinded nodes.
+    fetcher: &'a F,
+}
+
+impl<'a, F> TrieNodeIter<'a, F>
+where
+    F: TrieProvider,
+{
+    /// Creates a new [TrieNodeIter] over the trie rooted at the given node.
+    pub fn new(root: TrieNode, fetcher: &'a F) -> Self {
+        Self { stack: vec![(Nibbles::default(), root)], start: None, fetcher }
+    }
+
+    /// Skips the leaves with keys lower than the given key. Only the nodes on the path to the key
+    /// are revealed to find the first leaf.
+    pub const fn starting_at(mut self, key: Nibbles) -> Self {
+        self.start = Some(key);
+        self
+    }
+
+    /// Returns whether all leaves below the given path have keys lower than the start key.
+    fn is_before_start(&self, path: &Nibbles) -> bool {
+        self.start.is_some_and(|start| {
+            let len = path.len().min(start.len());
+            path.slice(..len) < start.slice(..len)
+        })
+    }
+}
+
+impl<F> Iterator for TrieNodeIter<'_, F>
+where
+    F: TrieProvider,
+{
+    type Item = TrieNodeResult<(Nibbles, Bytes)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some((path, mut node)) = self.stack.pop() {
+            if self.is_before_start(&path) {
+                continue;
+            }
+
+            match node {
+                TrieNode::Empty => {}
+                TrieNode::Blinded { .. } => {
+                    if let Err(e) = node.unblind(self.fetcher) {
+                        self.stack.clear();
+                        return Some(Err(e));
+                    }
+                    self.stack.push((path, node));
+                }
+                TrieNode::Leaf { prefix, value } => {
+                    let key = path.join(&prefix);
+                    if !self.is_before_start(&key) {
+                        return Some(Ok((key, value)));
+                    }
+                }
+                TrieNode::Extension { prefix, node } => {
+                    self.stack.push((path.join(&prefix), *node));
+                }
+                TrieNode::Branch { stack } => {
+                    // Push the children in reverse, so that the lowest nibble is visited first.
+                    // The value slot of the branch is not supported, see [TrieNode].
+                    for (nibble, child) in stack.into_iter().take(16).enumerate().rev() {
+                        if matches!(child, TrieNode::Empty) {
+                            continue;
+                        }
+                        let mut child_path = path;
+                        child_path.push(nibble as u8);
+                        self.stack.push((child_path, child));
+                    }
+                }
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{NoopTrieProvider, TrieNodeError, test_util::TrieNodeProvider};
+    use alloc::collections::BTreeMap;
+    use alloy_primitives::{B256, keccak256};
+    use alloy_trie::{HashBuilder, proof::ProofRetainer};
+
+    /// Returns the sorted leaves of a trie with 64 random keys.
+    fn leaves() -> Vec<(Nibbles, Bytes)> {
+        let mut leaves = (0..64u64)
+            .map(|i| (Nibbles::unpack(keccak256(i.to_be_bytes())), Bytes::from(i.to_be_bytes())))
+            .collect::<Vec<_>>();
+        leaves.sort();
+        leaves
+    }
+
+    /// Returns the root of the trie with the given leaves, and a provider for all of its nodes.
+    fn blinded_trie(leaves: &[(Nibbles, Bytes)]) -> (B256, TrieNodeProvider) {
+        let targets = leaves.iter().map(|(key, _)| *key).collect();
+        let mut hb = HashBuilder::default().with_proof_retainer(ProofRetainer::new(targets));
+        for (key, value) in leaves {
+            hb.add_leaf(*key, value);
+        }
+        let root = hb.root();
+        let preimages = hb
+            .take_proof_nodes()
+            .into_inner()
+            .into_values()
+            .map(|node| (keccak256(&node), node))
+            .collect::<BTreeMap<_, _>>();
+        (root, TrieNodeProvider::new(preimages))
+    }
+
+    #[test]
+    fn test_iter_ordered() {
+        let mut root = TrieNode::Empty;
+        let leaves = leaves();
+        for (key, value) in leaves.iter().rev() {
+            root.insert(key, value.clone(), &NoopTrieProvider).unwrap();
+        }
+
+        let iterated = TrieNodeIter::new(root, &NoopTrieProvider).collect::<Result<Vec<_>, _>>();
+        assert_eq!(iterated.unwrap(), leaves);
+    }
+
+    #[test]
+    fn test_iter_blinded() {
+        let leaves = leaves();
+        let (root, provider) = blinded_trie(&leaves);
+
+        let iter = TrieNodeIter::new(TrieNode::new_blinded(root), &provider);
+        assert_eq!(iter.collect::<Result<Vec<_>, _>>().unwrap(), leaves);
+    }
+
+    #[test]
+    fn test_iter_starting_at() {
+        let leaves = leaves();
+        let (root, provider) = blinded_trie(&leaves);
+
+        let iter =
+            TrieNodeIter::new(TrieNode::new_blinded(root), &provider).starting_at(leaves[40].0);
+        assert_eq!(iter.collect::<Result<Vec<_>, _>>().unwrap(), leaves[40..]);
+
+        let iter = TrieNodeIter::new(TrieNode::new_blinded(root), &provider)
+            .starting_at(Nibbles::unpack(B256::repeat_byte(0xFF)));
+        assert_eq!(iter.count(), 0);
+    }
+
+    #[test]
+    fn test_iter_missing_preimage() {
+        let provider = TrieNodeProvider::new(BTreeMap::default());
+        let mut iter = TrieNodeIter::new(TrieNode::new_blinded(B256::ZERO), &provider);
+        assert!(matches!(iter.next(), Some(Err(TrieNodeError::Provider(_)))));
+        assert!(iter.next().is_none());
+    }
+}
@@ -10,7 +10,10 @@
 extern crate alloc;
 
 mod errors;
-pub use errors::{OrderedListWalkerError, OrderedListWalkerResult, TrieNodeError, TrieNodeResult};
+pub use errors::{
+    OrderedListWalkerError, OrderedListWalkerResult, ProofVerificationError, TrieNodeError,
+    TrieNodeResult,
+};
 
 mod traits;
 pub use traits::{TrieHinter, TrieProvider};
@@ -18,14 +21,20 @@ pub use traits::{TrieHinter, TrieProvider};
 mod node;
 pub use node::TrieNode;
 
+mod iter;
+pub use iter::TrieNodeIter;
+
 mod list_walker;
 pub use list_walker::OrderedListWalker;
 
 mod noop;
 pub use noop::{NoopTrieHinter, NoopTrieProvider};
 
+mod proof;
+pub use proof::{verify_account_proof, verify_range_proof, verify_storage_proof};
+
 mod util;
-pub use util::ordered_trie_with_encoder;
+pub use util::{OrderedTrieBuilder, ordered_trie_with_encoder};
 
 // Re-export [alloy_trie::Nibbles].
 pub use alloy_trie::Nibbles;
@@ -178,6 +178,44 @@ impl TrieNode {
         }
     }
 
+    /// Walks down the trie along the given path without fetching any preimages, returning the
+    /// first [TrieNode::Blinded] node encountered. Blinded nodes committing to the empty root are
+    /// replaced with [TrieNode::Empty] along the way.
+    ///
+    /// This allows callers to collect the blinded nodes along many paths, fetch their preimages
+    /// in a single batch, and reveal them before walking down to the next level of the trie.
+    ///
+    /// ## Takes
+    /// - `self` - The root trie node
+    /// - `path` - The nibbles representation of the path to walk
+    ///
+    /// ## Returns
+    /// - `None` - The path is fully revealed.
+    /// - `Some(_)` - The first blinded node along the path.
+    pub fn blinded_on_path(&mut self, path: &Nibbles) -> Option<&mut Self> {
+        match self {
+            Self::Branch { stack } => {
+                let branch_nibble = path.get(0)? as usize;
+                stack
+                    .get_mut(branch_nibble)
+                    .and_then(|node| node.blinded_on_path(&path.slice(BRANCH_NODE_NIBBLES..)))
+            }
+            Self::Extension { prefix, node } => {
+                if path.len() >= prefix.len() && path.slice(..prefix.len()) == *prefix {
+                    node.blinded_on_path(&path.slice(prefix.len()..))
+                } else {
+                    None
+                }
+            }
+            Self::Blinded { commitment } if *commitment == EMPTY_ROOT_HASH => {
+                *self = Self::Empty;
+                None
+            }
+            Self::Blinded { .. } => Some(self),
+            Self::Leaf { .. } | Self::Empty => None,
+        }
+    }
+
     /// Inserts a [TrieNode] at the given path into the trie rooted at Self.
     ///
     /// ## Takes
@@ -729,6 +767,66 @@ mod test {
         assert_eq!(expected, TrieNode::decode(&mut LEAF_RLP.as_slice()).unwrap());
     }
 
+    #[test]
+    fn test_blinded_on_path_reveals_level_by_level() {
+        let values = (0..=255u8).collect::<Vec<_>>();
+        let mut trie = ordered_trie_with_encoder(&values, |v, buf| v.encode(buf));
+        let root = trie.root();
+        let preimages = trie.take_proof_nodes().into_inner().into_iter().fold(
+            BTreeMap::default(),
+            |mut acc, (_, value)| {
+                acc.insert(keccak256(value.as_ref()), value);
+                acc
+            },
+        );
+        let fetcher = TrieNodeProvider::new(preimages);
+
+        let paths = values
+            .iter()
+            .map(|i| {
+                let mut key = Vec::new();
+                (*i as usize).encode(&mut key);
+                Nibbles::unpack(key)
+            })
+            .collect::<Vec<_>>();
+
+        let mut root_node = TrieNode::new_blinded(root);
+        let mut levels = 0;
+        loop {
+            let mut commitments = paths
+                .iter()
+                .filter_map(|p| root_node.blinded_on_path(p))
+                .map(|n| n.blind())
+                .collect::<Vec<_>>();
+            commitments.sort();
+            commitments.dedup();
+            if commitments.is_empty() {
+                break;
+            }
+
+            let revealed = commitments
+                .iter()
+                .copied()
+                .zip(fetcher.trie_nodes_by_hash(&commitments).unwrap())
+                .collect::<BTreeMap<_, _>>();
+            for path in &paths {
+                if let Some(node) = root_node.blinded_on_path(path) {
+                    *node = revealed[&node.blind()].clone();
+                }
+            }
+            levels += 1;
+        }
+        assert!(levels > 1);
+
+        // All paths are revealed, so no preimages need to be fetched to open them.
+        for (path, value) in paths.iter().zip(values) {
+            let mut encoded_value = Vec::new();
+            value.encode(&mut encoded_value);
+            let v = root_node.open(path, &NoopTrieProvider).unwrap().unwrap();
+            assert_eq!(v, encoded_value.as_slice());
+        }
+    }
+
     #[test]
     fn test_retrieve_from_trie_simple() {
         const VALUES: [&str; 5] = ["yeah", "dog", ", ", "laminar", "flow"];
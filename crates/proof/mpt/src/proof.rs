@@ -0,0 +1,416 @@
+//! Verification of account and storage proofs, as returned by `eth_getProof`, and of range proofs
+//! against a trie root.
+
+use crate::{ProofVerificationError, TrieNode, TrieNodeResult, TrieProvider};
+use alloc::{
+    collections::BTreeMap,
+    format,
+    string::{String, ToString},
+};
+use alloy_primitives::{Address, B256, Bytes, U256, keccak256};
+use alloy_rlp::Decodable;
+use alloy_trie::{Nibbles, TrieAccount};
+use core::cmp::Ordering;
+
+/// Verifies an account proof against the given state root.
+///
+/// ## Takes
+/// - `state_root` - The root of the state trie
+/// - `address` - The address of the account
+/// - `proof` - The RLP encoded trie nodes on the path to the account, in any order
+///
+/// ## Returns
+/// - `Err(_)` - The proof is incomplete, or does not commit to the state root.
+/// - `Ok(None)` - The proof shows that the account does not exist.
+/// - `Ok(Some(_))` - The account.
+pub fn verify_account_proof(
+    state_root: B256,
+    address: Address,
+    proof: &[Bytes],
+) -> Result<Option<TrieAccount>, ProofVerificationError> {
+    let Some(value) = verify_proof(state_root, keccak256(address), proof)? else {
+        return Ok(None);
+    };
+    TrieAccount::decode(&mut value.as_ref()).map(Some).map_err(ProofVerificationError::InvalidValue)
+}
+
+/// Verifies a storage proof against the given storage root of an account.
+///
+/// ## Takes
+/// - `storage_root` - The root of the storage trie of the account
+/// - `slot` - The storage slot
+/// - `proof` - The RLP encoded trie nodes on the path to the slot, in any order
+///
+/// ## Returns
+/// - `Err(_)` - The proof is incomplete, or does not commit to the storage root.
+/// - `Ok(_)` - The value of the slot. Slots that do not exist in the trie hold zero.
+pub fn verify_storage_proof(
+    storage_root: B256,
+    slot: B256,
+    proof: &[Bytes],
+) -> Result<U256, ProofVerificationError> {
+    let Some(value) = verify_proof(storage_root, keccak256(slot), proof)? else {
+        return Ok(U256::ZERO);
+    };
+    U256::decode(&mut value.as_ref()).map_err(ProofVerificationError::InvalidValue)
+}
+
+/// Verifies a range proof against the given trie root.
+///
+/// A range proof proves that the given entries are all entries of the trie with keys from
+/// `first_key` up to the key of the last entry, inclusive. The proof consists of the trie nodes on
+/// the paths to `first_key` and to the last key, which bound the range. If there are no entries,
+/// the proof shows that there are no entries from `first_key` onwards. If the proof is empty, the
+/// entries must make up the whole trie.
+///
+/// ## Takes
+/// - `root` - The root of the trie
+/// - `first_key` - The first key of the range, which may be lower than the first entry
+/// - `entries` - The keys and values of the range, in strictly ascending key order
+/// - `proof` - The RLP encoded trie nodes on the paths to the boundaries, in any order
+///
+/// ## Returns
+/// - `Err(_)` - The entries are not the contents of the range in the trie.
+/// - `Ok(_)` - Whether the trie has entries after the range.
+pub fn verify_range_proof(
+    root: B256,
+    first_key: B256,
+    entries: &[(B256, Bytes)],
+    proof: &[Bytes],
+) -> Result<bool, ProofVerificationError> {
+    let ordered = entries.windows(2).all(|pair| pair[0].0 < pair[1].0);
+    if !ordered || entries.first().is_some_and(|(key, _)| *key < first_key) {
+        return Err(ProofVerificationError::InvalidRange);
+    }
+
+    let provider = ProofNodes(proof.iter().map(|node| (keccak256(node), node)).collect());
+    let mut range = TrieRange {
+        first: Nibbles::unpack(first_key),
+        last: Nibbles::unpack(entries.last().map_or(B256::repeat_byte(0xFF), |(key, _)| *key)),
+        has_more: false,
+    };
+
+    // Remove the contents of the range from the trie, keeping the commitments to the nodes
+    // outside of it, and rebuild the range from the entries.
+    let mut root_node = TrieNode::new_blinded(root);
+    if proof.is_empty() {
+        root_node = TrieNode::Empty;
+    } else {
+        range.prune(&mut root_node, Nibbles::default(), &provider)?;
+    }
+    for (key, value) in entries {
+        root_node.insert(&Nibbles::unpack(key), value.clone(), &provider)?;
+    }
+
+    let computed = root_node.blind();
+    if computed != root {
+        return Err(ProofVerificationError::RootMismatch { expected: root, computed });
+    }
+    Ok(range.has_more)
+}
+
+/// The position of a subtree of the trie relative to a [TrieRange].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RangePosition {
+    /// All keys of the subtree are lower than the range.
+    Before,
+    /// All keys of the subtree are within the range.
+    Within,
+    /// All keys of the subtree are higher than the range.
+    After,
+    /// The subtree contains one of the boundaries of the range.
+    Boundary,
+}
+
+/// An inclusive range of keys of a trie, used to verify range proofs.
+#[derive(Debug)]
+struct TrieRange {
+    /// The first key of the range.
+    first: Nibbles,
+    /// The last key of the range.
+    last: Nibbles,
+    /// Whether a non-empty subtree after the range was encountered.
+    has_more: bool,
+}
+
+impl TrieRange {
+    /// Returns the position of the subtree at the given path relative to the range. The keys of a
+    /// leaf are within the range if they are equal to one of its boundaries.
+    fn position(&self, path: &Nibbles) -> RangePosition {
+        let len = path.len();
+        match (path.cmp(&self.first.slice(..len)), path.cmp(&self.last.slice(..len))) {
+            (Ordering::Less, _) => RangePosition::Before,
+            (_, Ordering::Greater) => RangePosition::After,
+            (Ordering::Greater, Ordering::Less) => RangePosition::Within,
+            _ => RangePosition::Boundary,
+        }
+    }
+
+    /// Removes the subtrees within the range from the trie node at the given path. The nodes on
+    /// the paths to the boundaries of the range are revealed with the `fetcher`.
+    fn prune<F: TrieProvider>(
+        &mut self,
+        node: &mut TrieNode,
+        path: Nibbles,
+        fetcher: &F,
+    ) -> TrieNodeResult<()> {
+        match self.position(&path) {
+            RangePosition::Before => return Ok(()),
+            RangePosition::After => {
+                self.has_more |= !matches!(node, TrieNode::Empty);
+                return Ok(());
+            }
+            RangePosition::Within => {
+                *node = TrieNode::Empty;
+                return Ok(());
+            }
+            RangePosition::Boundary => node.unblind(fetcher)?,
+        }
+
+        match node {
+            TrieNode::Leaf { prefix, .. } => {
+                let key = path.join(prefix);
+                match self.position(&key) {
+                    RangePosition::Before => {}
+                    RangePosition::After => self.has_more = true,
+                    RangePosition::Within | RangePosition::Boundary => *node = TrieNode::Empty,
+                }
+            }
+            TrieNode::Extension { prefix, node: child } => {
+                self.prune(child, path.join(prefix), fetcher)?;
+                if matches!(child.as_ref(), TrieNode::Empty) {
+                    *node = TrieNode::Empty;
+                }
+            }
+            TrieNode::Branch { stack } => {
+                for (nibble, child) in stack.iter_mut().take(16).enumerate() {
+                    let mut child_path = path;
+                    child_path.push(nibble as u8);
+                    self.prune(child, child_path, fetcher)?;
+                }
+            }
+            TrieNode::Empty | TrieNode::Blinded { .. } => {}
+        }
+        Ok(())
+    }
+}
+
+/// Walks down the trie with the given root to the value at the given hashed key, only using the
+/// trie nodes of the proof.
+fn verify_proof(
+    root: B256,
+    hashed_key: B256,
+    proof: &[Bytes],
+) -> Result<Option<Bytes>, ProofVerificationError> {
+    let provider = ProofNodes(proof.iter().map(|node| (keccak256(node), node)).collect());
+    let mut root_node = TrieNode::new_blinded(root);
+    let value = root_node.open(&Nibbles::unpack(hashed_key), &provider)?;
+    Ok(value.cloned())
+}
+
+/// A [TrieProvider] serving the trie nodes of a proof, keyed by their hash.
+#[derive(Debug)]
+struct ProofNodes<'a>(BTreeMap<B256, &'a Bytes>);
+
+impl TrieProvider for ProofNodes<'_> {
+    type Error = String;
+
+    fn trie_node_by_hash(&self, key: B256) -> Result<TrieNode, Self::Error> {
+        let node = self.0.get(&key).ok_or_else(|| format!("Missing proof node {key}"))?;
+        TrieNode::decode(&mut node.as_ref()).map_err(|e| e.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TrieNodeError;
+    use alloc::vec::Vec;
+    use alloy_rlp::Encodable;
+    use alloy_trie::{EMPTY_ROOT_HASH, HashBuilder, KECCAK_EMPTY, proof::ProofRetainer};
+
+    /// Builds a trie with the given values at the hashes of their keys, returning its root and
+    /// the proof nodes for the given target keys.
+    fn trie_with_proof<K: AsRef<[u8]>, V: Encodable>(
+        values: &[(K, V)],
+        targets: &[K],
+    ) -> (B256, Vec<Bytes>) {
+        let mut leaves = values
+            .iter()
+            .map(|(key, value)| (Nibbles::unpack(keccak256(key)), alloy_rlp::encode(value)))
+            .collect::<Vec<_>>();
+        leaves.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let targets = targets.iter().map(|key| Nibbles::unpack(keccak256(key))).collect();
+        let mut hb = HashBuilder::default().with_proof_retainer(ProofRetainer::new(targets));
+        for (path, value) in leaves {
+            hb.add_leaf(path, &value);
+        }
+        let root = hb.root();
+        (root, hb.take_proof_nodes().into_inner().into_values().collect())
+    }
+
+    fn account(nonce: u64) -> TrieAccount {
+        TrieAccount {
+            nonce,
+            balance: U256::from(nonce * 100),
+            storage_root: EMPTY_ROOT_HASH,
+            code_hash: KECCAK_EMPTY,
+        }
+    }
+
+    fn slots() -> Vec<(B256, U256)> {
+        (1..=32u8).map(|i| (B256::with_last_byte(i), U256::from(i as u64 * 1000))).collect()
+    }
+
+    #[test]
+    fn test_verify_account_proof() {
+        let accounts =
+            (1..=32u8).map(|i| (Address::with_last_byte(i), account(i as u64))).collect::<Vec<_>>();
+        let (present, absent) = (Address::with_last_byte(7), Address::with_last_byte(64));
+        let (root, proof) = trie_with_proof(&accounts, &[present, absent]);
+
+        assert_eq!(verify_account_proof(root, present, &proof).unwrap(), Some(account(7)));
+        assert_eq!(verify_account_proof(root, absent, &proof).unwrap(), None);
+    }
+
+    #[test]
+    fn test_verify_account_proof_wrong_root() {
+        let accounts =
+            (1..=32u8).map(|i| (Address::with_last_byte(i), account(i as u64))).collect::<Vec<_>>();
+        let address = Address::with_last_byte(7);
+        let (_, proof) = trie_with_proof(&accounts, &[address]);
+
+        let err = verify_account_proof(B256::repeat_byte(1), address, &proof).unwrap_err();
+        assert!(matches!(err, ProofVerificationError::TrieNode(TrieNodeError::Provider(_))));
+    }
+
+    #[test]
+    fn test_verify_storage_proof() {
+        let (present, absent) = (B256::with_last_byte(5), B256::with_last_byte(64));
+        let (root, proof) = trie_with_proof(&slots(), &[present, absent]);
+
+        assert_eq!(verify_storage_proof(root, present, &proof).unwrap(), U256::from(5000));
+        assert_eq!(verify_storage_proof(root, absent, &proof).unwrap(), U256::ZERO);
+
+        // The empty storage trie does not require any proof nodes.
+        assert_eq!(verify_storage_proof(EMPTY_ROOT_HASH, present, &[]).unwrap(), U256::ZERO);
+    }
+
+    #[test]
+    fn test_verify_storage_proof_missing_node() {
+        let slot = B256::with_last_byte(5);
+        let (root, mut proof) = trie_with_proof(&slots(), &[slot]);
+        proof.retain(|node| keccak256(node) == root);
+
+        let err = verify_storage_proof(root, slot, &proof).unwrap_err();
+        assert!(matches!(err, ProofVerificationError::TrieNode(TrieNodeError::Provider(_))));
+    }
+
+    /// Returns the sorted entries of a trie with 64 random keys.
+    fn range_entries() -> Vec<(B256, Bytes)> {
+        let mut entries = (1..=64u64)
+            .map(|i| (keccak256(i.to_be_bytes()), alloy_rlp::encode(U256::from(i)).into()))
+            .collect::<Vec<_>>();
+        entries.sort();
+        entries
+    }
+
+    /// Returns the root of the trie with the given entries, and the proof nodes for the given
+    /// boundary keys.
+    fn range_proof(entries: &[(B256, Bytes)], boundaries: &[B256]) -> (B256, Vec<Bytes>) {
+        let targets = boundaries.iter().map(Nibbles::unpack).collect();
+        let mut hb = HashBuilder::default().with_proof_retainer(ProofRetainer::new(targets));
+        for (key, value) in entries {
+            hb.add_leaf(Nibbles::unpack(key), value);
+        }
+        let root = hb.root();
+        (root, hb.take_proof_nodes().into_inner().into_values().collect())
+    }
+
+    #[test]
+    fn test_verify_range_proof() {
+        let entries = range_entries();
+        let range = &entries[10..=20];
+        let (root, proof) = range_proof(&entries, &[range[0].0, range[10].0]);
+        assert_eq!(verify_range_proof(root, range[0].0, range, &proof), Ok(true));
+
+        // The first key may be lower than the first entry, as long as there are no entries in
+        // between.
+        let first_key = B256::from(U256::from_be_bytes(entries[9].0.0) + U256::from(1));
+        let (root, proof) = range_proof(&entries, &[first_key, range[10].0]);
+        assert_eq!(verify_range_proof(root, first_key, range, &proof), Ok(true));
+
+        // The last range of the trie has no entries after it.
+        let range = &entries[50..];
+        let (root, proof) = range_proof(&entries, &[range[0].0, entries[63].0]);
+        assert_eq!(verify_range_proof(root, range[0].0, range, &proof), Ok(false));
+    }
+
+    #[test]
+    fn test_verify_range_proof_without_boundaries() {
+        let entries = range_entries();
+        let (root, _) = range_proof(&entries, &[]);
+        assert_eq!(verify_range_proof(root, B256::ZERO, &entries, &[]), Ok(false));
+        assert_eq!(verify_range_proof(EMPTY_ROOT_HASH, B256::ZERO, &[], &[]), Ok(false));
+
+        // Without a proof, the entries must make up the whole trie.
+        assert!(matches!(
+            verify_range_proof(root, B256::ZERO, &entries[1..], &[]),
+            Err(ProofVerificationError::RootMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn test_verify_range_proof_no_entries() {
+        let entries = range_entries();
+        let first_key = B256::from(U256::from_be_bytes(entries[63].0.0) + U256::from(1));
+        let (root, proof) = range_proof(&entries, &[first_key]);
+        assert_eq!(verify_range_proof(root, first_key, &[], &proof), Ok(false));
+
+        let (root, proof) = range_proof(&entries, &[entries[63].0]);
+        assert!(matches!(
+            verify_range_proof(root, entries[63].0, &[], &proof),
+            Err(ProofVerificationError::RootMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn test_verify_range_proof_invalid() {
+        let entries = range_entries();
+        let range = &entries[10..=20];
+        let (root, proof) = range_proof(&entries, &[range[0].0, range[10].0]);
+
+        // A missing entry.
+        let mut missing = range.to_vec();
+        missing.remove(5);
+        assert!(matches!(
+            verify_range_proof(root, range[0].0, &missing, &proof),
+            Err(ProofVerificationError::RootMismatch { .. })
+        ));
+
+        // A modified value.
+        let mut modified = range.to_vec();
+        modified[5].1 = Bytes::from_static(&[0x01]);
+        assert!(matches!(
+            verify_range_proof(root, range[0].0, &modified, &proof),
+            Err(ProofVerificationError::RootMismatch { .. })
+        ));
+
+        // Unordered entries, and entries before the first key.
+        let mut unordered = range.to_vec();
+        unordered.swap(2, 3);
+        assert_eq!(
+            verify_range_proof(root, range[0].0, &unordered, &proof),
+            Err(ProofVerificationError::InvalidRange)
+        );
+        assert_eq!(
+            verify_range_proof(root, range[1].0, range, &proof),
+            Err(ProofVerificationError::InvalidRange)
+        );
+
+        // A proof missing the nodes of the last boundary.
+        let (_, partial) = range_proof(&entries, &[range[0].0]);
+        assert!(verify_range_proof(root, range[0].0, range, &partial).is_err());
+    }
+}
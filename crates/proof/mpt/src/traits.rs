@@ -2,6 +2,7 @@
 //! headers.
 
 use crate::TrieNode;
+use alloc::vec::Vec;
 use alloy_primitives::{Address, B256, U256};
 use core::fmt::Display;
 use op_alloy_rpc_types_engine::OpPayloadAttributes;
@@ -20,6 +21,22 @@ pub trait TrieProvider {
     /// - Ok(TrieNode): The trie node preimage.
     /// - Err(Self::Error): If the trie node preimage could not be fetched.
     fn trie_node_by_hash(&self, key: B256) -> Result<TrieNode, Self::Error>;
+
+    /// Fetches the preimages for each of the given trie node hashes, in order.
+    ///
+    /// The default implementation fetches each preimage individually through
+    /// [Self::trie_node_by_hash]. Providers backed by a transport with a per-request cost should
+    /// override this method to fetch the preimages in a single batch.
+    ///
+    /// ## Takes
+    /// - `keys`: The keys of the trie nodes to fetch.
+    ///
+    /// ## Returns
+    /// - Ok(Vec<TrieNode>): The trie node preimages, in the order of `keys`.
+    /// - Err(Self::Error): If any trie node preimage could not be fetched.
+    fn trie_nodes_by_hash(&self, keys: &[B256]) -> Result<Vec<TrieNode>, Self::Error> {
+        keys.iter().map(|key| self.trie_node_by_hash(*key)).collect()
+    }
 }
 
 /// The [TrieHinter] trait defines the synchronous interface for hinting the host to fetch trie
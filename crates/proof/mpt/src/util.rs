@@ -1,6 +1,7 @@
 //! Utilities for `kona-mpt`
 
 use alloc::vec::Vec;
+use alloy_primitives::B256;
 use alloy_rlp::{Buf, BufMut, Encodable, Header};
 use alloy_trie::{HashBuilder, Nibbles, proof::ProofRetainer};
 
@@ -39,6 +40,89 @@ where
     hb
 }
 
+/// An incremental builder for the root of an ordered list trie, such as the transactions or
+/// receipts trie of a block.
+///
+/// Unlike [ordered_trie_with_encoder], items are pushed one at a time, and only the encoding of
+/// the first item is retained until the trie root is computed. Because RLP encoded indices are
+/// not lexicographically ordered, the item at index `0` is inserted into the trie once the item
+/// at index `0x80` is pushed, or when the root is computed.
+#[derive(Debug, Default)]
+pub struct OrderedTrieBuilder {
+    /// The inner [HashBuilder].
+    hb: HashBuilder,
+    /// The number of items pushed so far.
+    len: usize,
+    /// The encoded item at index `0`, if it has not yet been inserted into the trie.
+    first: Option<Vec<u8>>,
+    /// A scratch buffer for encoding item values.
+    value_buffer: Vec<u8>,
+}
+
+impl OrderedTrieBuilder {
+    /// Creates a new, empty [OrderedTrieBuilder].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of items pushed so far.
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if no items have been pushed.
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Pushes the next item onto the trie, encoding it with the given encoder.
+    pub fn push_with_encoder<T, F>(&mut self, item: &T, encode: F)
+    where
+        F: FnOnce(&T, &mut dyn BufMut),
+    {
+        let mut value_buffer = core::mem::take(&mut self.value_buffer);
+        value_buffer.clear();
+        encode(item, &mut value_buffer);
+        self.push_encoded(&value_buffer);
+        self.value_buffer = value_buffer;
+    }
+
+    /// Pushes the next encoded item onto the trie.
+    pub fn push_encoded(&mut self, value: &[u8]) {
+        let index = self.len;
+        self.len += 1;
+
+        if index == 0 {
+            self.first = Some(value.to_vec());
+            return;
+        }
+        if index == 0x80 {
+            self.flush_first();
+        }
+        Self::add_leaf(&mut self.hb, index, value);
+    }
+
+    /// Consumes the builder and returns the root of the ordered list trie.
+    pub fn root(mut self) -> B256 {
+        self.flush_first();
+        self.hb.root()
+    }
+
+    /// Inserts the item at index `0` into the trie, if it is still pending.
+    fn flush_first(&mut self) {
+        if let Some(first) = self.first.take() {
+            Self::add_leaf(&mut self.hb, 0, &first);
+        }
+    }
+
+    /// Adds a leaf for the item at `index` to the [HashBuilder].
+    fn add_leaf(hb: &mut HashBuilder, index: usize, value: &[u8]) {
+        let mut index_buffer = Vec::with_capacity(4);
+        index.encode(&mut index_buffer);
+        hb.add_leaf(Nibbles::unpack(&index_buffer), value);
+    }
+}
+
 /// Adjust the index of an item for rlp encoding.
 pub(crate) const fn adjust_index_for_rlp(i: usize, len: usize) -> usize {
     if i > 0x7f {
@@ -88,3 +172,33 @@ pub(crate) fn unpack_path_to_nibbles(first: Option<u8>, rest: &[u8]) -> Nibbles
     let rest = Nibbles::unpack(rest);
     Nibbles::from_iter_unchecked(first.into_iter().chain(rest.to_vec()))
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn test_ordered_trie_builder_matches_ordered_trie() {
+        for len in [0usize, 1, 2, 0x7f, 0x80, 0x81, 300] {
+            let items = (0..len).map(|i| vec![i as u8; (i % 40) + 1]).collect::<Vec<_>>();
+            let expected =
+                ordered_trie_with_encoder(&items, |item, buf| buf.put_slice(item)).root();
+
+            let mut builder = OrderedTrieBuilder::new();
+            items.iter().for_each(|item| builder.push_encoded(item));
+            assert_eq!(builder.len(), len);
+            assert_eq!(builder.root(), expected, "root mismatch for {len} items");
+        }
+    }
+
+    #[test]
+    fn test_ordered_trie_builder_push_with_encoder() {
+        let items = vec![1u64, 2, 3];
+        let expected = ordered_trie_with_encoder(&items, |item, buf| item.encode(buf)).root();
+
+        let mut builder = OrderedTrieBuilder::new();
+        items.iter().for_each(|item| builder.push_with_encoder(item, |item, buf| item.encode(buf)));
+        assert_eq!(builder.root(), expected);
+    }
+}
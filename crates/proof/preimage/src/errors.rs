@@ -1,5 +1,6 @@
 //! Errors for the `kona-preimage` crate.
 
+use crate::PreimageKey;
 use alloc::string::String;
 use thiserror::Error;
 
@@ -9,6 +10,7 @@ use thiserror::Error;
 /// [PreimageOracleServer]: crate::PreimageOracleServer
 /// [HintReaderServer]: crate::HintReaderServer
 #[derive(Error, Debug)]
+#[non_exhaustive]
 pub enum PreimageOracleError {
     /// The pipe has been broken.
     #[error(transparent)]
@@ -19,6 +21,9 @@ pub enum PreimageOracleError {
     /// Key not found.
     #[error("Key not found.")]
     KeyNotFound,
+    /// The preimage for the given key is missing from the preimage store.
+    #[error("Missing preimage for key {0}.")]
+    MissingPreimage(PreimageKey),
     /// Timeout while waiting for preimage.
     #[error("Timeout while waiting for preimage.")]
     Timeout,
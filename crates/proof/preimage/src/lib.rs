@@ -18,7 +18,7 @@ mod key;
 pub use key::{PreimageKey, PreimageKeyType};
 
 mod oracle;
-pub use oracle::{OracleReader, OracleServer};
+pub use oracle::{MAX_PREIMAGE_BATCH_SIZE, OracleReader, OracleServer};
 
 mod hint;
 pub use hint::{HintReader, HintWriter};
@@ -33,3 +33,10 @@ pub use traits::{
 mod native_channel;
 #[cfg(feature = "std")]
 pub use native_channel::{BidirectionalChannel, NativeChannel};
+
+#[cfg(feature = "net")]
+mod socket_channel;
+#[cfg(feature = "net")]
+pub use socket_channel::{
+    CHANNEL_KIND_TIMEOUT, ChannelAddr, ChannelKind, SocketChannel, SocketChannelListener,
+};
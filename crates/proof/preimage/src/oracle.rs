@@ -88,8 +88,49 @@ where
 
         Ok(())
     }
+
+    /// Get the data corresponding to each of the given keys from the host, in order.
+    ///
+    /// The keys are written to the host in batches of up to [MAX_PREIMAGE_BATCH_SIZE] before the
+    /// responses are read back, saving a round trip per preimage. This must only be used if the
+    /// host is known to serve batched requests, as an [OracleServer] does. The preimage oracle of
+    /// a fault proof VM serves a single request at a time.
+    async fn get_many(&self, keys: &[PreimageKey]) -> PreimageOracleResult<Vec<Vec<u8>>> {
+        trace!(target: "oracle_client", "Requesting {} preimages from preimage oracle", keys.len());
+
+        let mut preimages = Vec::with_capacity(keys.len());
+        for batch in keys.chunks(MAX_PREIMAGE_BATCH_SIZE) {
+            // Write all keys within the batch to the host.
+            for key in batch {
+                let key_bytes: [u8; 32] = (*key).into();
+                self.channel.write(&key_bytes).await?;
+            }
+
+            // Read back the responses, which the host serves in request order.
+            for _ in batch {
+                let mut length_buffer = [0u8; 8];
+                self.channel.read_exact(&mut length_buffer).await?;
+                let length = u64::from_be_bytes(length_buffer) as usize;
+
+                let mut data_buffer = alloc::vec![0; length];
+                if length > 0 {
+                    self.channel.read_exact(&mut data_buffer).await?;
+                }
+                preimages.push(data_buffer);
+            }
+        }
+
+        trace!(target: "oracle_client", "Successfully read {} preimages from preimage oracle", keys.len());
+
+        Ok(preimages)
+    }
 }
 
+/// The maximum number of preimage keys written to the host before reading back their responses
+/// in [OracleReader::get_many]. Bounding the number of in-flight requests keeps the keys within
+/// the buffer of a pipe-backed [Channel], so that neither side can block the other.
+pub const MAX_PREIMAGE_BATCH_SIZE: usize = 256;
+
 /// An [OracleServer] is a router for the host to serve data back to the client [OracleReader].
 #[derive(Debug, Clone, Copy)]
 pub struct OracleServer<C> {
@@ -245,4 +286,38 @@ mod test {
         assert_eq!(contents_a, MOCK_DATA_A);
         assert_eq!(contents_b, MOCK_DATA_B);
     }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_oracle_reader_get_many() {
+        let data = (0..MAX_PREIMAGE_BATCH_SIZE + 3)
+            .map(|i| alloc::format!("preimage {i}").into_bytes())
+            .collect::<Vec<_>>();
+        let keys = data
+            .iter()
+            .map(|d| PreimageKey::new(*keccak256(d), PreimageKeyType::Keccak256))
+            .collect::<Vec<_>>();
+        let preimages = Arc::new(Mutex::new(keys.iter().copied().zip(data.clone()).collect()));
+
+        let preimage_channel = BidirectionalChannel::new().unwrap();
+
+        let client = tokio::task::spawn(async move {
+            let oracle_reader = OracleReader::new(preimage_channel.client);
+            oracle_reader.get_many(&keys).await.unwrap()
+        });
+        tokio::task::spawn(async move {
+            let oracle_server = OracleServer::new(preimage_channel.host);
+            let test_fetcher = TestFetcher { preimages };
+
+            loop {
+                match oracle_server.next_preimage_request(&test_fetcher).await {
+                    Err(PreimageOracleError::IOError(_)) => break,
+                    Err(e) => panic!("Unexpected error: {e:?}"),
+                    Ok(_) => {}
+                }
+            }
+        });
+
+        let (c,) = tokio::join!(client);
+        assert_eq!(c.unwrap(), data);
+    }
 }
@@ -0,0 +1,383 @@
+//! Implementation of the [Channel] trait over TCP and unix socket connections, allowing the
+//! client program and the host to run on separate machines or in separate containers.
+//!
+//! The client opens one connection per channel to the address that the host listens on, and
+//! identifies the channel by sending its [ChannelKind] as the first byte of the connection.
+
+use crate::{
+    Channel,
+    errors::{ChannelError, ChannelResult},
+};
+use alloc::sync::Arc;
+use async_trait::async_trait;
+use core::{fmt, str::FromStr, time::Duration};
+use std::io;
+#[cfg(unix)]
+use std::path::PathBuf;
+use tokio::{
+    io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt},
+    net::{TcpListener, TcpStream},
+    sync::Mutex,
+};
+
+/// The maximum time a client has to identify the channel of a connection, after which the
+/// connection is dropped.
+pub const CHANNEL_KIND_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The address of a socket serving the hint and preimage channels.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ChannelAddr {
+    /// A TCP address, formatted as `tcp://<host>:<port>`.
+    Tcp(String),
+    /// A unix socket path, formatted as `unix://<path>`.
+    #[cfg(unix)]
+    Unix(PathBuf),
+}
+
+impl FromStr for ChannelAddr {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(addr) = s.strip_prefix("tcp://") {
+            return Ok(Self::Tcp(addr.into()));
+        }
+        #[cfg(unix)]
+        if let Some(path) = s.strip_prefix("unix://") {
+            return Ok(Self::Unix(PathBuf::from(path)));
+        }
+        Err(format!("Expected tcp://<host>:<port> or unix://<path>, got {s}"))
+    }
+}
+
+impl fmt::Display for ChannelAddr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Tcp(addr) => write!(f, "tcp://{addr}"),
+            #[cfg(unix)]
+            Self::Unix(path) => write!(f, "unix://{}", path.display()),
+        }
+    }
+}
+
+/// The channel carried by a socket connection, sent by the client as the first byte of the
+/// connection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum ChannelKind {
+    /// The hint channel.
+    Hint = 1,
+    /// The preimage channel.
+    Preimage = 2,
+}
+
+impl TryFrom<u8> for ChannelKind {
+    type Error = io::Error;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            1 => Ok(Self::Hint),
+            2 => Ok(Self::Preimage),
+            _ => Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("Unknown channel kind: {value}"),
+            )),
+        }
+    }
+}
+
+/// The read half of a socket connection.
+type ReadHalf = Box<dyn AsyncRead + Send + Unpin>;
+
+/// The write half of a socket connection.
+type WriteHalf = Box<dyn AsyncWrite + Send + Unpin>;
+
+/// A [Channel] over a TCP or unix socket connection. Clones share the connection.
+#[derive(Clone)]
+pub struct SocketChannel {
+    /// The read half of the connection.
+    read: Arc<Mutex<ReadHalf>>,
+    /// The write half of the connection.
+    write: Arc<Mutex<WriteHalf>>,
+}
+
+impl fmt::Debug for SocketChannel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SocketChannel").finish_non_exhaustive()
+    }
+}
+
+impl SocketChannel {
+    /// Connects to the host listening on the given [ChannelAddr], opening the given channel.
+    pub async fn connect(addr: &ChannelAddr, kind: ChannelKind) -> io::Result<Self> {
+        let channel = match addr {
+            ChannelAddr::Tcp(addr) => {
+                let stream = TcpStream::connect(addr).await?;
+                stream.set_nodelay(true)?;
+                Self::from_stream(stream)
+            }
+            #[cfg(unix)]
+            ChannelAddr::Unix(path) => {
+                Self::from_stream(tokio::net::UnixStream::connect(path).await?)
+            }
+        };
+
+        let mut write = channel.write.lock().await;
+        write.write_all(&[kind as u8]).await?;
+        write.flush().await?;
+        drop(write);
+
+        Ok(channel)
+    }
+
+    /// Creates a [SocketChannel] from a connected stream.
+    fn from_stream<S>(stream: S) -> Self
+    where
+        S: AsyncRead + AsyncWrite + Send + 'static,
+    {
+        let (read, write) = tokio::io::split(stream);
+        Self {
+            read: Arc::new(Mutex::new(Box::new(read))),
+            write: Arc::new(Mutex::new(Box::new(write))),
+        }
+    }
+
+    /// Reads the [ChannelKind] sent by the client as the first byte of the connection, waiting at
+    /// most [CHANNEL_KIND_TIMEOUT].
+    async fn read_kind(&self) -> io::Result<ChannelKind> {
+        let kind = tokio::time::timeout(CHANNEL_KIND_TIMEOUT, async {
+            self.read.lock().await.read_u8().await
+        })
+        .await
+        .map_err(|_| io::Error::new(io::ErrorKind::TimedOut, "No channel kind received"))??;
+        ChannelKind::try_from(kind)
+    }
+}
+
+#[async_trait]
+impl Channel for SocketChannel {
+    async fn read(&self, buf: &mut [u8]) -> ChannelResult<usize> {
+        self.read.lock().await.read(buf).await.map_err(|_| ChannelError::Closed)
+    }
+
+    async fn read_exact(&self, buf: &mut [u8]) -> ChannelResult<usize> {
+        self.read.lock().await.read_exact(buf).await.map_err(|e| match e.kind() {
+            io::ErrorKind::UnexpectedEof => ChannelError::UnexpectedEOF,
+            _ => ChannelError::Closed,
+        })
+    }
+
+    async fn write(&self, buf: &[u8]) -> ChannelResult<usize> {
+        let mut write = self.write.lock().await;
+        write.write_all(buf).await.map_err(|_| ChannelError::Closed)?;
+        write.flush().await.map_err(|_| ChannelError::Closed)?;
+        Ok(buf.len())
+    }
+}
+
+/// A listener accepting the hint and preimage channels of a client program over a TCP or unix
+/// socket.
+#[derive(Debug)]
+pub enum SocketChannelListener {
+    /// A TCP listener.
+    Tcp(TcpListener),
+    /// A unix socket listener.
+    #[cfg(unix)]
+    Unix(tokio::net::UnixListener),
+}
+
+impl SocketChannelListener {
+    /// Binds a listener to the given [ChannelAddr].
+    pub async fn bind(addr: &ChannelAddr) -> io::Result<Self> {
+        match addr {
+            ChannelAddr::Tcp(addr) => Ok(Self::Tcp(TcpListener::bind(addr).await?)),
+            #[cfg(unix)]
+            ChannelAddr::Unix(path) => Ok(Self::Unix(tokio::net::UnixListener::bind(path)?)),
+        }
+    }
+
+    /// Returns the [ChannelAddr] that the listener is bound to.
+    pub fn local_addr(&self) -> io::Result<ChannelAddr> {
+        match self {
+            Self::Tcp(listener) => Ok(ChannelAddr::Tcp(listener.local_addr()?.to_string())),
+            #[cfg(unix)]
+            Self::Unix(listener) => {
+                let addr = listener.local_addr()?;
+                let path = addr.as_pathname().ok_or_else(|| {
+                    io::Error::new(io::ErrorKind::InvalidInput, "Unnamed unix socket")
+                })?;
+                Ok(ChannelAddr::Unix(path.to_path_buf()))
+            }
+        }
+    }
+
+    /// Accepts the connections of a client program, returning its hint and preimage channels in
+    /// that order. The client may open the channels in any order.
+    ///
+    /// Connections that fail, do not identify their channel within [CHANNEL_KIND_TIMEOUT], or open
+    /// a channel that was already accepted are dropped, and the listener keeps accepting.
+    pub async fn accept(&self) -> (SocketChannel, SocketChannel) {
+        let mut hint = None;
+        let mut preimage = None;
+        while hint.is_none() || preimage.is_none() {
+            let channel = match self.accept_one().await {
+                Ok(channel) => channel,
+                Err(err) => {
+                    warn!(target: "preimage", %err, "Failed to accept a channel connection");
+                    continue;
+                }
+            };
+            let slot = match channel.read_kind().await {
+                Ok(ChannelKind::Hint) => &mut hint,
+                Ok(ChannelKind::Preimage) => &mut preimage,
+                Err(err) => {
+                    warn!(target: "preimage", %err, "Dropping an unidentified channel connection");
+                    continue;
+                }
+            };
+            if slot.is_some() {
+                warn!(target: "preimage", "Dropping a channel connection opened more than once");
+                continue;
+            }
+            *slot = Some(channel);
+        }
+
+        (hint.expect("hint channel accepted"), preimage.expect("preimage channel accepted"))
+    }
+
+    /// Accepts a single connection.
+    async fn accept_one(&self) -> io::Result<SocketChannel> {
+        match self {
+            Self::Tcp(listener) => {
+                let (stream, _) = listener.accept().await?;
+                stream.set_nodelay(true)?;
+                Ok(SocketChannel::from_stream(stream))
+            }
+            #[cfg(unix)]
+            Self::Unix(listener) => Ok(SocketChannel::from_stream(listener.accept().await?.0)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        HintReader, HintWriter, OracleReader, OracleServer, PreimageKey, PreimageOracleClient,
+        PreimageOracleServer,
+        errors::PreimageOracleResult,
+        traits::{HintReaderServer, HintRouter, HintWriterClient, PreimageFetcher},
+    };
+    use alloy_primitives::keccak256;
+    use std::sync::Arc;
+    use tokio::sync::Mutex as AsyncMutex;
+
+    /// A preimage fetcher and hint router serving a single preimage, and recording hints.
+    #[derive(Default)]
+    struct TestBackend {
+        hints: AsyncMutex<Vec<String>>,
+    }
+
+    #[async_trait]
+    impl PreimageFetcher for TestBackend {
+        async fn get_preimage(&self, key: PreimageKey) -> PreimageOracleResult<Vec<u8>> {
+            assert_eq!(key, PreimageKey::new_keccak256(*keccak256(b"preimage")));
+            Ok(b"preimage".to_vec())
+        }
+    }
+
+    #[async_trait]
+    impl HintRouter for TestBackend {
+        async fn route_hint(&self, hint: String) -> PreimageOracleResult<()> {
+            self.hints.lock().await.push(hint);
+            Ok(())
+        }
+    }
+
+    async fn roundtrip(addr: ChannelAddr) {
+        let listener = SocketChannelListener::bind(&addr).await.unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        let backend = Arc::new(TestBackend::default());
+        let server_backend = backend.clone();
+        let server = tokio::spawn(async move {
+            let (hint, preimage) = listener.accept().await;
+            let oracle_server = OracleServer::new(preimage);
+            let hint_reader = HintReader::new(hint);
+            oracle_server.next_preimage_request(server_backend.as_ref()).await.unwrap();
+            hint_reader.next_hint(server_backend.as_ref()).await.unwrap();
+        });
+
+        // Open the channels in the opposite order of the return values of `accept`.
+        let preimage = SocketChannel::connect(&addr, ChannelKind::Preimage).await.unwrap();
+        let hint = SocketChannel::connect(&addr, ChannelKind::Hint).await.unwrap();
+
+        let oracle = OracleReader::new(preimage);
+        let key = PreimageKey::new_keccak256(*keccak256(b"preimage"));
+        assert_eq!(oracle.get(key).await.unwrap(), b"preimage");
+
+        HintWriter::new(hint).write("test-hint 0x01").await.unwrap();
+
+        server.await.unwrap();
+        assert_eq!(*backend.hints.lock().await, vec!["test-hint 0x01".to_string()]);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_tcp_roundtrip() {
+        roundtrip(ChannelAddr::Tcp("127.0.0.1:0".into())).await;
+    }
+
+    #[cfg(unix)]
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_unix_roundtrip() {
+        let dir = std::env::temp_dir().join(format!("kona-preimage-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("channels.sock");
+        let _ = std::fs::remove_file(&path);
+
+        roundtrip(ChannelAddr::Unix(path.clone())).await;
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_accept_drops_stray_connections() {
+        let listener =
+            SocketChannelListener::bind(&ChannelAddr::Tcp("127.0.0.1:0".into())).await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let tcp_addr = addr.to_string().trim_start_matches("tcp://").to_string();
+        let accept = tokio::spawn(async move { listener.accept().await });
+
+        // A connection with an unknown channel kind, a connection closed before identifying its
+        // channel, and a second hint channel are dropped.
+        TcpStream::connect(&tcp_addr).await.unwrap().write_all(&[0xff]).await.unwrap();
+        drop(TcpStream::connect(&tcp_addr).await.unwrap());
+        let hint = SocketChannel::connect(&addr, ChannelKind::Hint).await.unwrap();
+        let _duplicate = SocketChannel::connect(&addr, ChannelKind::Hint).await.unwrap();
+        let preimage = SocketChannel::connect(&addr, ChannelKind::Preimage).await.unwrap();
+
+        let (server_hint, server_preimage) = accept.await.unwrap();
+        hint.write(b"hint").await.unwrap();
+        preimage.write(b"preimage").await.unwrap();
+
+        let mut buf = [0u8; 4];
+        server_hint.read_exact(&mut buf).await.unwrap();
+        assert_eq!(&buf, b"hint");
+        let mut buf = [0u8; 8];
+        server_preimage.read_exact(&mut buf).await.unwrap();
+        assert_eq!(&buf, b"preimage");
+    }
+
+    #[test]
+    fn test_channel_addr_parse() {
+        let addr = "tcp://127.0.0.1:7300".parse::<ChannelAddr>().unwrap();
+        assert_eq!(addr, ChannelAddr::Tcp("127.0.0.1:7300".into()));
+        assert_eq!(addr.to_string(), "tcp://127.0.0.1:7300");
+
+        #[cfg(unix)]
+        assert_eq!(
+            "unix:///tmp/kona.sock".parse::<ChannelAddr>().unwrap(),
+            ChannelAddr::Unix(PathBuf::from("/tmp/kona.sock"))
+        );
+        assert!("127.0.0.1:7300".parse::<ChannelAddr>().is_err());
+    }
+}
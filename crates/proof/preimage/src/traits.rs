@@ -24,6 +24,24 @@ pub trait PreimageOracleClient {
     /// - `Ok(())` if the data was successfully written into the buffer.
     /// - `Err(_)` if the data could not be written into the buffer.
     async fn get_exact(&self, key: PreimageKey, buf: &mut [u8]) -> PreimageOracleResult<()>;
+
+    /// Get the data corresponding to each of the given keys from the host, in order.
+    ///
+    /// The default implementation requests each preimage individually. Implementations may
+    /// instead issue the requests as a single batch, which should only be done if the host is
+    /// known to serve batched requests. The preimage oracle of a fault proof VM serves a single
+    /// request at a time.
+    ///
+    /// # Returns
+    /// - `Ok(Vec<Vec<u8>>)` if the data for all keys was successfully fetched from the host.
+    /// - `Err(_)` if the data for any key could not be fetched from the host.
+    async fn get_many(&self, keys: &[PreimageKey]) -> PreimageOracleResult<Vec<Vec<u8>>> {
+        let mut preimages = Vec::with_capacity(keys.len());
+        for key in keys {
+            preimages.push(self.get(*key).await?);
+        }
+        Ok(preimages)
+    }
 }
 
 /// A [HintWriterClient] is a high-level interface to the hint pipe. It provides a way to write
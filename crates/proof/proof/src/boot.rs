@@ -2,9 +2,10 @@
 //! through the `PreimageOracle` ABI as local keys.
 
 use crate::errors::OracleProviderError;
+use alloc::vec::Vec;
 use alloy_primitives::{B256, U256};
 use kona_genesis::{L1ChainConfig, RollupConfig};
-use kona_preimage::{PreimageKey, PreimageOracleClient};
+use kona_preimage::{PreimageKey, PreimageOracleClient, errors::PreimageOracleError};
 use kona_registry::{L1_CONFIGS, ROLLUP_CONFIGS};
 use serde::{Deserialize, Serialize};
 
@@ -34,6 +35,10 @@ pub const L2_CLAIM_KEY: U256 = U256::from_be_slice(&[3]);
 /// This key retrieves the L2 block number at which the output root disagreement
 /// occurs. The derivation process will produce blocks up to this number to
 /// verify the claim.
+///
+/// The preimage is the 8-byte big-endian block number, optionally followed by a byte of flags
+/// advertising the optional capabilities of the host, such as [INTERMEDIATE_CLAIMS_FLAG] and
+/// [PREIMAGE_BATCHING_FLAG]. The on-chain `PreimageOracle` only serves the block number.
 pub const L2_CLAIM_BLOCK_NUMBER_KEY: U256 = U256::from_be_slice(&[4]);
 
 /// The local key identifier for the L2 chain ID.
@@ -57,6 +62,68 @@ pub const L2_ROLLUP_CONFIG_KEY: U256 = U256::from_be_slice(&[6]);
 /// given chain ID. Oracle-loaded configs require additional validation.
 pub const L1_CONFIG_KEY: U256 = U256::from_be_slice(&[7]);
 
+/// The local key identifier for the intermediate L2 output root claims.
+///
+/// This key retrieves an optional list of output roots claimed at blocks between the agreed
+/// and disputed L2 blocks, encoded as consecutive `(block number (u64 BE), output root)` pairs.
+/// Each intermediate claim is validated as the program derives past its block, allowing a
+/// single program run to prove several consecutive L2 blocks.
+///
+/// The key is only read when the host advertises it with the [INTERMEDIATE_CLAIMS_FLAG], as the
+/// on-chain `PreimageOracle` does not serve it.
+pub const L2_INTERMEDIATE_CLAIMS_KEY: U256 = U256::from_be_slice(&[8]);
+
+/// The flag of the [L2_CLAIM_BLOCK_NUMBER_KEY] preimage advertising that the host serves the
+/// [L2_INTERMEDIATE_CLAIMS_KEY].
+pub const INTERMEDIATE_CLAIMS_FLAG: u8 = 0x01;
+
+/// The flag of the [L2_CLAIM_BLOCK_NUMBER_KEY] preimage advertising that the host serves batched
+/// preimage requests, where several keys are written before the responses are read back.
+///
+/// Only hosts running the program natively serve batched requests, as the preimage oracle of a
+/// fault proof VM serves a single request at a time.
+pub const PREIMAGE_BATCHING_FLAG: u8 = 0x02;
+
+/// An L2 output root claimed at a given L2 block number.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct L2OutputClaim {
+    /// The L2 block number that the output root commits to.
+    pub l2_block_number: u64,
+    /// The claimed output root.
+    pub output_root: B256,
+}
+
+impl L2OutputClaim {
+    /// The length of an encoded [L2OutputClaim], in bytes.
+    pub const ENCODED_LENGTH: usize = 8 + 32;
+
+    /// Encodes a list of [L2OutputClaim]s as consecutive `(block number, output root)` pairs.
+    pub fn encode_list(claims: &[Self]) -> Vec<u8> {
+        claims.iter().fold(Vec::with_capacity(claims.len() * Self::ENCODED_LENGTH), |mut acc, c| {
+            acc.extend_from_slice(&c.l2_block_number.to_be_bytes());
+            acc.extend_from_slice(c.output_root.as_slice());
+            acc
+        })
+    }
+
+    /// Decodes a list of [L2OutputClaim]s encoded with [Self::encode_list].
+    pub fn decode_list(data: &[u8]) -> Result<Vec<Self>, OracleProviderError> {
+        data.chunks(Self::ENCODED_LENGTH)
+            .map(|chunk| {
+                let chunk: [u8; Self::ENCODED_LENGTH] =
+                    chunk.try_into().map_err(OracleProviderError::SliceConversion)?;
+                let (number, root) = chunk.split_at(8);
+                Ok(Self {
+                    l2_block_number: u64::from_be_bytes(
+                        number.try_into().map_err(OracleProviderError::SliceConversion)?,
+                    ),
+                    output_root: B256::from_slice(root),
+                })
+            })
+            .collect()
+    }
+}
+
 /// The boot information for the client program.
 ///
 /// [`BootInfo`] contains all the essential parameters needed to initialize the fault proof
@@ -131,6 +198,20 @@ pub struct BootInfo {
     ///
     /// **Security**: Loaded from registry (secure) or oracle (requires validation).
     pub l1_config: L1ChainConfig,
+    /// The output roots claimed at intermediate L2 blocks, in ascending block order.
+    ///
+    /// Each intermediate claim is validated against the derived output root once the program
+    /// has derived up to its block, before continuing on to the disputed block.
+    ///
+    /// **Security**: User-submitted input requiring verification.
+    #[serde(default)]
+    pub intermediate_claims: Vec<L2OutputClaim>,
+    /// Whether the host serves batched preimage requests, as advertised with the
+    /// [PREIMAGE_BATCHING_FLAG].
+    ///
+    /// **Security**: Only affects how preimages are requested, not the preimages themselves.
+    #[serde(default)]
+    pub preimage_batching: bool,
 }
 
 impl BootInfo {
@@ -161,7 +242,9 @@ impl BootInfo {
     /// 3. **Claimed L2 Output Root** (`L2_CLAIM_KEY`): User's disputed claim
     /// 4. **Claimed Block Number** (`L2_CLAIM_BLOCK_NUMBER_KEY`): Target block height
     /// 5. **Chain ID** (`L2_CHAIN_ID_KEY`): L2 network identifier
-    /// 6. **Rollup Config**: Either from registry (secure) or oracle (fallback)
+    /// 6. **Intermediate Claims** (`L2_INTERMEDIATE_CLAIMS_KEY`): Optional intermediate claims,
+    ///    only read if advertised with the [INTERMEDIATE_CLAIMS_FLAG]
+    /// 7. **Rollup Config**: Either from registry (secure) or oracle (fallback)
     ///
     /// # Rollup Configuration Loading
     /// The rollup configuration is loaded with a security preference:
@@ -204,15 +287,18 @@ impl BootInfo {
             .await
             .map_err(OracleProviderError::Preimage)?;
 
+        let l2_claim_block_preimage = oracle
+            .get(PreimageKey::new_local(L2_CLAIM_BLOCK_NUMBER_KEY.to()))
+            .await
+            .map_err(OracleProviderError::Preimage)?;
+        let (l2_claim_block, flags) =
+            l2_claim_block_preimage.split_at(l2_claim_block_preimage.len().min(8));
         let l2_claim_block = u64::from_be_bytes(
-            oracle
-                .get(PreimageKey::new_local(L2_CLAIM_BLOCK_NUMBER_KEY.to()))
-                .await
-                .map_err(OracleProviderError::Preimage)?
-                .as_slice()
-                .try_into()
-                .map_err(OracleProviderError::SliceConversion)?,
+            l2_claim_block.try_into().map_err(OracleProviderError::SliceConversion)?,
         );
+        let flags = flags.first().copied().unwrap_or_default();
+        let serves_intermediate_claims = flags & INTERMEDIATE_CLAIMS_FLAG != 0;
+        let preimage_batching = flags & PREIMAGE_BATCHING_FLAG != 0;
         let chain_id = u64::from_be_bytes(
             oracle
                 .get(PreimageKey::new_local(L2_CHAIN_ID_KEY.to()))
@@ -223,6 +309,19 @@ impl BootInfo {
                 .map_err(OracleProviderError::SliceConversion)?,
         );
 
+        // The intermediate claims are optional, and only read if the host advertises them.
+        let intermediate_claims = if serves_intermediate_claims {
+            match oracle.get(PreimageKey::new_local(L2_INTERMEDIATE_CLAIMS_KEY.to())).await {
+                Ok(claims) => L2OutputClaim::decode_list(&claims)?,
+                Err(PreimageOracleError::KeyNotFound | PreimageOracleError::MissingPreimage(_)) => {
+                    Vec::new()
+                }
+                Err(err) => return Err(OracleProviderError::Preimage(err)),
+            }
+        } else {
+            Vec::new()
+        };
+
         // Attempt to load the rollup config from the chain ID. If there is no config for the chain,
         // fall back to loading the config from the preimage oracle.
         let rollup_config = if let Some(config) = ROLLUP_CONFIGS.get(&chain_id) {
@@ -263,6 +362,7 @@ impl BootInfo {
             l1_head = %l1_head,
             chain_id = chain_id,
             claimed_l2_block_number = l2_claim_block,
+            intermediate_claims = intermediate_claims.len(),
             "Successfully loaded boot information"
         );
 
@@ -274,6 +374,103 @@ impl BootInfo {
             chain_id,
             rollup_config,
             l1_config,
+            intermediate_claims,
+            preimage_batching,
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::{boxed::Box, collections::BTreeMap, vec};
+    use async_trait::async_trait;
+
+    /// A [PreimageOracleClient] serving the given local keys.
+    struct LocalKeysOracle(BTreeMap<U256, Vec<u8>>);
+
+    #[async_trait]
+    impl PreimageOracleClient for LocalKeysOracle {
+        async fn get(&self, key: PreimageKey) -> Result<Vec<u8>, PreimageOracleError> {
+            self.0.get(&key.key_value()).cloned().ok_or(PreimageOracleError::KeyNotFound)
+        }
+
+        async fn get_exact(
+            &self,
+            key: PreimageKey,
+            buf: &mut [u8],
+        ) -> Result<(), PreimageOracleError> {
+            buf.copy_from_slice(&self.get(key).await?);
+            Ok(())
+        }
+    }
+
+    fn oracle(l2_claim_block: Vec<u8>, intermediate_claims: Option<Vec<u8>>) -> LocalKeysOracle {
+        let mut keys = BTreeMap::from([
+            (L1_HEAD_KEY, B256::repeat_byte(0x01).to_vec()),
+            (L2_OUTPUT_ROOT_KEY, B256::repeat_byte(0x02).to_vec()),
+            (L2_CLAIM_KEY, B256::repeat_byte(0x03).to_vec()),
+            (L2_CLAIM_BLOCK_NUMBER_KEY, l2_claim_block),
+            (L2_CHAIN_ID_KEY, 10u64.to_be_bytes().to_vec()),
+        ]);
+        if let Some(claims) = intermediate_claims {
+            keys.insert(L2_INTERMEDIATE_CLAIMS_KEY, claims);
+        }
+        LocalKeysOracle(keys)
+    }
+
+    #[tokio::test]
+    async fn test_load_without_intermediate_claims() {
+        // The on-chain oracle only serves the block number, and not the intermediate claims.
+        let boot = BootInfo::load(&oracle(100u64.to_be_bytes().to_vec(), None)).await.unwrap();
+        assert_eq!(boot.claimed_l2_block_number, 100);
+        assert!(boot.intermediate_claims.is_empty());
+        assert!(!boot.preimage_batching);
+
+        // Advertised intermediate claims that are missing are treated as absent.
+        let mut l2_claim_block = 100u64.to_be_bytes().to_vec();
+        l2_claim_block.push(INTERMEDIATE_CLAIMS_FLAG);
+        let boot = BootInfo::load(&oracle(l2_claim_block, None)).await.unwrap();
+        assert!(boot.intermediate_claims.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_load_intermediate_claims() {
+        let claims = vec![L2OutputClaim { l2_block_number: 50, output_root: B256::repeat_byte(5) }];
+        let encoded = Some(L2OutputClaim::encode_list(&claims));
+
+        // The intermediate claims are ignored unless advertised.
+        let boot =
+            BootInfo::load(&oracle(100u64.to_be_bytes().to_vec(), encoded.clone())).await.unwrap();
+        assert!(boot.intermediate_claims.is_empty());
+
+        let mut l2_claim_block = 100u64.to_be_bytes().to_vec();
+        l2_claim_block.push(INTERMEDIATE_CLAIMS_FLAG);
+        let boot = BootInfo::load(&oracle(l2_claim_block, encoded)).await.unwrap();
+        assert_eq!(boot.claimed_l2_block_number, 100);
+        assert_eq!(boot.intermediate_claims, claims);
+    }
+
+    #[tokio::test]
+    async fn test_load_preimage_batching() {
+        let mut l2_claim_block = 100u64.to_be_bytes().to_vec();
+        l2_claim_block.push(PREIMAGE_BATCHING_FLAG);
+        let boot = BootInfo::load(&oracle(l2_claim_block, None)).await.unwrap();
+        assert_eq!(boot.claimed_l2_block_number, 100);
+        assert!(boot.preimage_batching);
+        assert!(boot.intermediate_claims.is_empty());
+    }
+
+    #[test]
+    fn test_output_claim_list_roundtrip() {
+        let claims = vec![
+            L2OutputClaim { l2_block_number: 1, output_root: B256::repeat_byte(0x01) },
+            L2OutputClaim { l2_block_number: 2, output_root: B256::repeat_byte(0x02) },
+        ];
+        let encoded = L2OutputClaim::encode_list(&claims);
+        assert_eq!(encoded.len(), 2 * L2OutputClaim::ENCODED_LENGTH);
+        assert_eq!(L2OutputClaim::decode_list(&encoded).unwrap(), claims);
+        assert!(L2OutputClaim::decode_list(&[]).unwrap().is_empty());
+        assert!(L2OutputClaim::decode_list(&encoded[1..]).is_err());
+    }
+}
@@ -6,7 +6,10 @@
 
 use alloc::{boxed::Box, sync::Arc, vec::Vec};
 use async_trait::async_trait;
-use core::num::NonZeroUsize;
+use core::{
+    num::NonZeroUsize,
+    sync::atomic::{AtomicBool, Ordering},
+};
 use kona_preimage::{
     HintWriterClient, PreimageKey, PreimageOracleClient, errors::PreimageOracleResult,
 };
@@ -31,6 +34,8 @@ where
     oracle_reader: OR,
     /// Hint writer type.
     hint_writer: HW,
+    /// Whether the host has advertised support for batched preimage requests.
+    batching: Arc<AtomicBool>,
 }
 
 impl<OR, HW> CachingOracle<OR, HW>
@@ -49,8 +54,22 @@ where
             ))),
             oracle_reader,
             hint_writer,
+            batching: Arc::new(AtomicBool::new(false)),
         }
     }
+
+    /// Enables batched preimage requests. This must only be done once the host has advertised
+    /// support for them, see [PREIMAGE_BATCHING_FLAG].
+    ///
+    /// [PREIMAGE_BATCHING_FLAG]: crate::boot::PREIMAGE_BATCHING_FLAG
+    pub fn enable_batching(&self) {
+        self.batching.store(true, Ordering::Relaxed);
+    }
+
+    /// Returns `true` if batched preimage requests are enabled.
+    pub fn supports_batching(&self) -> bool {
+        self.batching.load(Ordering::Relaxed)
+    }
 }
 
 /// A trait that provides a method to flush a cache.
@@ -98,6 +117,46 @@ where
             Ok(())
         }
     }
+
+    async fn get_many(&self, keys: &[PreimageKey]) -> PreimageOracleResult<Vec<Vec<u8>>> {
+        let mut preimages = Vec::with_capacity(keys.len());
+        let mut missing = Vec::new();
+        {
+            let mut cache = self.cache.lock();
+            for (i, key) in keys.iter().enumerate() {
+                let value = cache.get(key).cloned();
+                if value.is_none() {
+                    missing.push((i, *key));
+                }
+                preimages.push(value.unwrap_or_default());
+            }
+        }
+
+        if missing.is_empty() {
+            return Ok(preimages);
+        }
+
+        // Only batch the requests if the host has advertised support for it; otherwise, fall
+        // back to requesting the preimages individually.
+        let missing_keys = missing.iter().map(|(_, key)| *key).collect::<Vec<_>>();
+        let values = if self.supports_batching() {
+            self.oracle_reader.get_many(&missing_keys).await?
+        } else {
+            let mut values = Vec::with_capacity(missing_keys.len());
+            for key in missing_keys {
+                values.push(self.oracle_reader.get(key).await?);
+            }
+            values
+        };
+
+        let mut cache = self.cache.lock();
+        for ((i, key), value) in missing.into_iter().zip(values) {
+            cache.put(key, value.clone());
+            preimages[i] = value;
+        }
+
+        Ok(preimages)
+    }
 }
 
 #[async_trait]
@@ -110,3 +169,105 @@ where
         self.hint_writer.write(hint).await
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use alloc::{collections::BTreeMap, vec};
+    use alloy_primitives::keccak256;
+    use core::sync::atomic::AtomicUsize;
+    use kona_preimage::{
+        BidirectionalChannel, Channel, HintWriter, OracleReader, OracleServer, PreimageFetcher,
+        PreimageKeyType, PreimageOracleServer,
+        errors::{ChannelResult, PreimageOracleError},
+    };
+
+    /// A [Channel] counting the round trips made over it, i.e. the reads following a write.
+    #[derive(Debug, Clone)]
+    struct RoundTripChannel<C> {
+        inner: C,
+        wrote: Arc<AtomicBool>,
+        round_trips: Arc<AtomicUsize>,
+    }
+
+    impl<C> RoundTripChannel<C> {
+        fn count_round_trip(&self) {
+            if self.wrote.swap(false, Ordering::Relaxed) {
+                self.round_trips.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+    }
+
+    #[async_trait]
+    impl<C: Channel + Send + Sync> Channel for RoundTripChannel<C> {
+        async fn read(&self, buf: &mut [u8]) -> ChannelResult<usize> {
+            self.count_round_trip();
+            self.inner.read(buf).await
+        }
+
+        async fn read_exact(&self, buf: &mut [u8]) -> ChannelResult<usize> {
+            self.count_round_trip();
+            self.inner.read_exact(buf).await
+        }
+
+        async fn write(&self, buf: &[u8]) -> ChannelResult<usize> {
+            self.wrote.store(true, Ordering::Relaxed);
+            self.inner.write(buf).await
+        }
+    }
+
+    struct MapFetcher(BTreeMap<PreimageKey, Vec<u8>>);
+
+    #[async_trait]
+    impl PreimageFetcher for MapFetcher {
+        async fn get_preimage(&self, key: PreimageKey) -> PreimageOracleResult<Vec<u8>> {
+            self.0.get(&key).cloned().ok_or(PreimageOracleError::KeyNotFound)
+        }
+    }
+
+    /// Fetches a set of preimages twice with [CachingOracle::get_many], returning the number of
+    /// round trips made to the host.
+    async fn get_many_round_trips(batching: bool) -> usize {
+        let preimages = (0..8u8).map(|i| vec![i; 40]).collect::<Vec<_>>();
+        let keys = preimages
+            .iter()
+            .map(|preimage| PreimageKey::new(*keccak256(preimage), PreimageKeyType::Keccak256))
+            .collect::<Vec<_>>();
+        let fetcher = MapFetcher(keys.iter().copied().zip(preimages.clone()).collect());
+
+        let preimage_channel = BidirectionalChannel::new().unwrap();
+        let hint_channel = BidirectionalChannel::new().unwrap();
+        let round_trips = Arc::new(AtomicUsize::new(0));
+        let client_channel = RoundTripChannel {
+            inner: preimage_channel.client,
+            wrote: Arc::new(AtomicBool::new(false)),
+            round_trips: round_trips.clone(),
+        };
+
+        tokio::task::spawn(async move {
+            let server = OracleServer::new(preimage_channel.host);
+            while server.next_preimage_request(&fetcher).await.is_ok() {}
+        });
+
+        let oracle = CachingOracle::new(
+            16,
+            OracleReader::new(client_channel),
+            HintWriter::new(hint_channel.client),
+        );
+        if batching {
+            oracle.enable_batching();
+        }
+
+        assert_eq!(oracle.get_many(&keys).await.unwrap(), preimages);
+        // Cached preimages are served without reaching out to the host.
+        assert_eq!(oracle.get_many(&keys).await.unwrap(), preimages);
+
+        round_trips.load(Ordering::Relaxed)
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_get_many_batches_round_trips() {
+        assert_eq!(get_many_round_trips(false).await, 8);
+        assert_eq!(get_many_round_trips(true).await, 1);
+    }
+}
@@ -31,6 +31,9 @@ where
     trie_hinter: H,
     /// The evm factory for the executor.
     evm_factory: Evm,
+    /// Whether the executor prefetches the state accessed by each block, see
+    /// [`StatelessL2Builder::with_state_prefetch`].
+    prefetch_state: bool,
     /// The executor.
     inner: Option<StatelessL2Builder<'a, P, H, Evm>>,
 }
@@ -49,7 +52,26 @@ where
         evm_factory: Evm,
         inner: Option<StatelessL2Builder<'a, P, H, Evm>>,
     ) -> Self {
-        Self { rollup_config, trie_provider, trie_hinter, evm_factory, inner }
+        Self {
+            rollup_config,
+            trie_provider,
+            trie_hinter,
+            evm_factory,
+            prefetch_state: false,
+            inner,
+        }
+    }
+
+    /// Enables or disables prefetching the state accessed by each block before executing it.
+    ///
+    /// The prefetched trie nodes are requested in batches through
+    /// [`TrieProvider::trie_nodes_by_hash`], so this only pays off with a provider that serves
+    /// batches in fewer round trips than individual requests.
+    ///
+    /// [`TrieProvider::trie_nodes_by_hash`]: kona_mpt::TrieProvider::trie_nodes_by_hash
+    pub const fn with_state_prefetch(mut self, prefetch_state: bool) -> Self {
+        self.prefetch_state = prefetch_state;
+        self
     }
 }
 
@@ -73,15 +95,25 @@ where
     /// Updates the safe header.
     ///
     /// Since the L2 block executor is stateless, on an update to the safe head,
-    /// a new executor is created with the updated header.
+    /// a new executor is created with the updated header. If the executor already builds on top
+    /// of the given header, it is kept, so that its warmed trie database and caches are reused
+    /// across consecutive blocks.
     fn update_safe_head(&mut self, header: Sealed<Header>) {
-        self.inner = Some(StatelessL2Builder::new(
+        if self.inner.as_ref().is_some_and(|e| e.parent_header().hash() == header.hash()) {
+            return;
+        }
+
+        let builder = StatelessL2Builder::new(
             self.rollup_config,
             self.evm_factory.clone(),
             self.trie_provider.clone(),
             self.trie_hinter.clone(),
             header,
-        ));
+        )
+        .with_state_prefetch(self.prefetch_state);
+        #[cfg(feature = "rayon")]
+        let builder = builder.with_parallel_state_root();
+        self.inner = Some(builder);
     }
 
     /// Execute the given payload attributes.
@@ -89,10 +121,17 @@ where
         &mut self,
         attributes: OpPayloadAttributes,
     ) -> Result<BlockBuildingOutcome, Self::Error> {
-        self.inner.as_mut().map_or_else(
+        let outcome = self.inner.as_mut().map_or_else(
             || Err(kona_executor::ExecutorError::MissingExecutor),
             |e| e.build_block(attributes),
-        )
+        );
+
+        // A failed block may leave the trie database partially updated. Drop the executor, so
+        // that the next update of the safe head creates a fresh one.
+        if outcome.is_err() {
+            self.inner = None;
+        }
+        outcome
     }
 
     /// Computes the output root.
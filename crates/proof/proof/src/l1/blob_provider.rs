@@ -6,7 +6,7 @@ use alloy_consensus::Blob;
 use alloy_eips::eip4844::{FIELD_ELEMENTS_PER_BLOB, IndexedBlobHash};
 use alloy_primitives::keccak256;
 use ark_bls12_381::Fr;
-use ark_ff::{AdditiveGroup, BigInteger, BigInteger256, Field, PrimeField};
+use ark_ff::{AdditiveGroup, BigInteger256, Field};
 use async_trait::async_trait;
 use core::str::FromStr;
 use kona_derive::BlobProvider;
@@ -60,8 +60,7 @@ impl<T: CommsClient> OracleBlobProvider<T> {
         let mut field_element_key = [0u8; 80];
         field_element_key[..48].copy_from_slice(commitment.as_ref());
         for i in 0..FIELD_ELEMENTS_PER_BLOB {
-            field_element_key[48..]
-                .copy_from_slice(ROOTS_OF_UNITY[i as usize].into_bigint().to_bytes_be().as_ref());
+            field_element_key[48..].copy_from_slice(root_of_unity_be(i as usize));
 
             let mut field_element = [0u8; 32];
             self.oracle
@@ -104,10 +103,33 @@ impl<T: CommsClient + Sync + Send> BlobProvider for OracleBlobProvider<T> {
 
 /// The 4096th bit-reversed roots of unity used in EIP-4844 as predefined evaluation points.
 ///
-/// See `generate_roots_of_unity` for details on how these roots of unity are generated.
+/// See `generate_roots_of_unity` for details on how these roots of unity are generated. Only the
+/// big-endian encoding of the roots is needed to fetch the field elements of blobs, which is
+/// served by [`root_of_unity_be`] without computing them.
 pub static ROOTS_OF_UNITY: Lazy<[Fr; FIELD_ELEMENTS_PER_BLOB as usize]> =
     Lazy::new(generate_roots_of_unity);
 
+/// The big-endian encodings of the [`ROOTS_OF_UNITY`], 32 bytes each, precomputed and embedded in
+/// the binary.
+///
+/// Computing the roots of unity and converting them out of Montgomery form costs the client
+/// program thousands of field operations at startup. Embedding their encodings instead makes
+/// each lookup a slice of static memory.
+static ROOTS_OF_UNITY_BE: &[u8; FIELD_ELEMENTS_PER_BLOB as usize * 32] =
+    include_bytes!("roots_of_unity.bin");
+
+/// Returns the big-endian encoding of the root of unity at the given index of the
+/// [`ROOTS_OF_UNITY`], i.e. the evaluation point of the field element at that index in a blob.
+///
+/// ## Panics
+///
+/// Panics if the index is not lower than [`FIELD_ELEMENTS_PER_BLOB`].
+pub fn root_of_unity_be(index: usize) -> &'static [u8; 32] {
+    ROOTS_OF_UNITY_BE[index << 5..(index + 1) << 5]
+        .try_into()
+        .expect("The slice is exactly 32 bytes long")
+}
+
 /// Generates the 4096th bit-reversed roots of unity used in EIP-4844 as predefined evaluation
 /// points. To compute the field element at index i in a blob, the blob polynomial is evaluated at
 /// the i'th root of unity. Based on go-kzg-4844: <https://github.com/crate-crypto/go-kzg-4844/blob/8bcf6163d3987313a3194595cf1f33fd45d7301a/internal/kzg/domain.go#L44-L98>
@@ -157,13 +179,24 @@ fn generate_roots_of_unity() -> [Fr; FIELD_ELEMENTS_PER_BLOB as usize] {
 
 #[cfg(test)]
 mod test {
-    use super::ROOTS_OF_UNITY;
+    use super::{ROOTS_OF_UNITY, root_of_unity_be};
     use alloy_eips::eip4844::{FIELD_ELEMENTS_PER_BLOB, env_settings::EnvKzgSettings};
     use ark_ff::{BigInteger, PrimeField};
     use c_kzg::{BYTES_PER_BLOB, Blob, Bytes32, Bytes48};
     use rand::Rng;
     use rayon::iter::{IntoParallelIterator, ParallelIterator};
 
+    #[test]
+    fn test_embedded_roots_of_unity() {
+        for (i, root) in ROOTS_OF_UNITY.iter().enumerate() {
+            assert_eq!(
+                root_of_unity_be(i).as_slice(),
+                root.into_bigint().to_bytes_be().as_slice(),
+                "Embedded root of unity {i} does not match the generated one"
+            );
+        }
+    }
+
     #[test]
     fn test_roots_of_unity() {
         // Initiate the default Ethereum KZG settings.
@@ -4,7 +4,7 @@ mod pipeline;
 pub use pipeline::{OraclePipeline, ProviderAttributesBuilder, ProviderDerivationPipeline};
 
 mod blob_provider;
-pub use blob_provider::{OracleBlobProvider, ROOTS_OF_UNITY};
+pub use blob_provider::{OracleBlobProvider, ROOTS_OF_UNITY, root_of_unity_be};
 
 mod chain_provider;
 pub use chain_provider::OracleL1ChainProvider;
@@ -6,8 +6,9 @@ use async_trait::async_trait;
 use core::fmt::Debug;
 use kona_derive::{
     ChainProvider, DataAvailabilityProvider, DerivationPipeline, L2ChainProvider, OriginProvider,
-    Pipeline, PipelineBuilder, PipelineErrorKind, PipelineResult, PolledAttributesQueueStage,
-    ResetSignal, Signal, SignalReceiver, StatefulAttributesBuilder, StepResult,
+    Pipeline, PipelineBuilder, PipelineErrorKind, PipelineResult, PipelineSnapshot,
+    PolledAttributesQueueStage, ResetSignal, Signal, SignalReceiver, StatefulAttributesBuilder,
+    StepResult,
 };
 use kona_driver::{DriverPipeline, PipelineCursor};
 use kona_genesis::{L1ChainConfig, RollupConfig, SystemConfig};
@@ -178,4 +179,9 @@ where
     ) -> Result<SystemConfig, PipelineErrorKind> {
         self.pipeline.system_config_by_number(number).await
     }
+
+    /// Returns a [PipelineSnapshot] of the internal state of the pipeline.
+    fn snapshot(&self) -> PipelineSnapshot {
+        self.pipeline.snapshot()
+    }
 }
@@ -186,6 +186,26 @@ impl<T: CommsClient> TrieProvider for OracleL2ChainProvider<T> {
             .map_err(OracleProviderError::Rlp)
         })
     }
+
+    fn trie_nodes_by_hash(&self, keys: &[B256]) -> Result<Vec<TrieNode>, OracleProviderError> {
+        // Request all trie node preimages in a single batch. The oracle falls back to sequential
+        // requests if the host does not support batching.
+        crate::block_on(async move {
+            let keys = keys
+                .iter()
+                .map(|key| PreimageKey::new(**key, PreimageKeyType::Keccak256))
+                .collect::<Vec<_>>();
+            self.oracle
+                .get_many(&keys)
+                .await
+                .map_err(OracleProviderError::Preimage)?
+                .iter()
+                .map(|preimage| {
+                    TrieNode::decode(&mut preimage.as_ref()).map_err(OracleProviderError::Rlp)
+                })
+                .collect()
+        })
+    }
 }
 
 impl<T: CommsClient> TrieDBProvider for OracleL2ChainProvider<T> {
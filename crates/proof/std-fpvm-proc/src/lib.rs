@@ -13,7 +13,9 @@ pub fn client_entry(_: TokenStream, input: TokenStream) -> TokenStream {
 
     let expanded = quote! {
         fn #fn_name() -> Result<(), String> {
-            match #fn_body {
+            let result = #fn_body;
+            kona_std_fpvm::malloc::report_allocation_stats();
+            match result {
                 Ok(_) => kona_std_fpvm::io::exit(0),
                 Err(e) => {
                     kona_std_fpvm::io::print_err(alloc::format!("Program encountered fatal error: {:?}\n", e).as_ref());
@@ -0,0 +1,273 @@
+//! This module contains a minimal single-threaded async executor, usable inside the FPVM.
+//!
+//! The FPVM runs a single thread, and the kernel I/O it exposes is synchronous. Futures still let
+//! the client structure its preimage fetches as independent tasks: a task issuing a hint can
+//! [yield_now] to let the computation of other tasks make progress, rather than blocking the whole
+//! program until the host has processed it.
+
+use alloc::{boxed::Box, rc::Rc, sync::Arc, task::Wake, vec::Vec};
+use core::{
+    cell::RefCell,
+    fmt,
+    future::Future,
+    pin::{Pin, pin},
+    sync::atomic::{AtomicBool, Ordering},
+    task::{Context, Poll, Waker},
+};
+
+/// A future spawned onto the [LocalExecutor], with its output erased.
+type LocalFuture = Pin<Box<dyn Future<Output = ()>>>;
+
+/// A flag set when the task it belongs to is woken.
+#[derive(Debug)]
+struct WakeFlag(AtomicBool);
+
+impl WakeFlag {
+    /// Creates a new, set, [WakeFlag], so that new tasks are polled at least once.
+    fn new() -> Arc<Self> {
+        Arc::new(Self(AtomicBool::new(true)))
+    }
+
+    /// Clears the flag, returning whether it was set.
+    fn take(&self) -> bool {
+        self.0.swap(false, Ordering::AcqRel)
+    }
+}
+
+impl Wake for WakeFlag {
+    fn wake(self: Arc<Self>) {
+        self.wake_by_ref();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.0.store(true, Ordering::Release);
+    }
+}
+
+/// A task spawned onto the [LocalExecutor].
+struct Task {
+    /// The future driven by the task.
+    future: LocalFuture,
+    /// The wake flag of the task.
+    woken: Arc<WakeFlag>,
+}
+
+/// A minimal single-threaded async executor.
+///
+/// The executor drives a main future with [LocalExecutor::block_on], alongside the tasks
+/// [spawned](LocalExecutor::spawn) onto it. Tasks are polled in the order they were spawned, and
+/// only once woken. Nothing outside of the program wakes tasks in the FPVM, so when no task is
+/// woken, the main future is polled again, as [kona_proof]'s `block_on` does.
+///
+/// [kona_proof]: https://docs.rs/kona-proof
+#[derive(Default)]
+pub struct LocalExecutor {
+    /// The tasks spawned onto the executor that have not completed yet.
+    tasks: RefCell<Vec<Task>>,
+}
+
+impl fmt::Debug for LocalExecutor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LocalExecutor").field("tasks", &self.tasks.borrow().len()).finish()
+    }
+}
+
+impl LocalExecutor {
+    /// Creates a new [LocalExecutor], without any task.
+    pub const fn new() -> Self {
+        Self { tasks: RefCell::new(Vec::new()) }
+    }
+
+    /// Spawns a future onto the executor, returning a [JoinHandle] to await its output.
+    ///
+    /// The future only makes progress while the executor runs a [LocalExecutor::block_on] call.
+    /// Dropping the [JoinHandle] does not cancel the task.
+    pub fn spawn<F>(&self, future: F) -> JoinHandle<F::Output>
+    where
+        F: Future + 'static,
+    {
+        let state = Rc::new(RefCell::new(JoinState { output: None, waker: None }));
+        let task_state = state.clone();
+        let future = async move {
+            let output = future.await;
+            let mut state = task_state.borrow_mut();
+            state.output = Some(output);
+            if let Some(waker) = state.waker.take() {
+                waker.wake();
+            }
+        };
+        self.tasks.borrow_mut().push(Task { future: Box::pin(future), woken: WakeFlag::new() });
+        JoinHandle { state }
+    }
+
+    /// Returns the number of spawned tasks that have not completed yet.
+    pub fn pending_tasks(&self) -> usize {
+        self.tasks.borrow().len()
+    }
+
+    /// Drives the given future to completion, polling the spawned tasks while it is pending.
+    ///
+    /// Spawned tasks that are still pending once the future completes are kept, and resume on the
+    /// next call.
+    pub fn block_on<F: Future>(&self, future: F) -> F::Output {
+        let woken = WakeFlag::new();
+        let waker = Waker::from(woken.clone());
+        let mut context = Context::from_waker(&waker);
+        let mut future = pin!(future);
+
+        loop {
+            let progressed = self.poll_tasks();
+            if !woken.take() && progressed {
+                continue;
+            }
+            if let Poll::Ready(output) = future.as_mut().poll(&mut context) {
+                return output;
+            }
+        }
+    }
+
+    /// Polls the woken tasks once, dropping the completed ones. Returns whether any task was
+    /// polled.
+    fn poll_tasks(&self) -> bool {
+        // Take the tasks out of the executor, so that they can spawn new tasks while polled.
+        let mut tasks = core::mem::take(&mut *self.tasks.borrow_mut());
+        let mut polled = false;
+        tasks.retain_mut(|task| {
+            if !task.woken.take() {
+                return true;
+            }
+            polled = true;
+            let waker = Waker::from(task.woken.clone());
+            task.future.as_mut().poll(&mut Context::from_waker(&waker)).is_pending()
+        });
+
+        let mut spawned = self.tasks.borrow_mut();
+        tasks.append(&mut spawned);
+        *spawned = tasks;
+        polled
+    }
+}
+
+/// The shared state between a task and its [JoinHandle].
+struct JoinState<T> {
+    /// The output of the task, once completed.
+    output: Option<T>,
+    /// The waker of the future awaiting the [JoinHandle].
+    waker: Option<Waker>,
+}
+
+/// A handle to await the output of a task spawned onto the [LocalExecutor].
+pub struct JoinHandle<T> {
+    /// The state shared with the task.
+    state: Rc<RefCell<JoinState<T>>>,
+}
+
+impl<T> fmt::Debug for JoinHandle<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("JoinHandle").field("finished", &self.is_finished()).finish()
+    }
+}
+
+impl<T> JoinHandle<T> {
+    /// Returns whether the task has completed.
+    pub fn is_finished(&self) -> bool {
+        self.state.borrow().output.is_some()
+    }
+}
+
+impl<T> Future for JoinHandle<T> {
+    type Output = T;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut state = self.state.borrow_mut();
+        match state.output.take() {
+            Some(output) => Poll::Ready(output),
+            None => {
+                state.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+/// Yields once to the [LocalExecutor], letting the other woken tasks make progress before the
+/// current one resumes.
+///
+/// Useful after issuing a hint to the host, so that computation proceeds while the host prepares
+/// the preimages.
+pub async fn yield_now() {
+    /// A future that is pending on its first poll only.
+    struct YieldNow(bool);
+
+    impl Future for YieldNow {
+        type Output = ();
+
+        fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+            if self.0 {
+                return Poll::Ready(());
+            }
+            self.0 = true;
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        }
+    }
+
+    YieldNow(false).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn test_block_on() {
+        let executor = LocalExecutor::new();
+        assert_eq!(executor.block_on(async { 42 }), 42);
+    }
+
+    #[test]
+    fn test_spawn_and_join() {
+        let executor = LocalExecutor::new();
+        let handle = executor.spawn(async {
+            yield_now().await;
+            7
+        });
+        assert!(!handle.is_finished());
+        assert_eq!(executor.pending_tasks(), 1);
+
+        assert_eq!(executor.block_on(handle), 7);
+        assert_eq!(executor.pending_tasks(), 0);
+    }
+
+    #[test]
+    fn test_yield_interleaves_tasks() {
+        let executor = LocalExecutor::new();
+        let log = Rc::new(RefCell::new(Vec::new()));
+
+        let handles = ["a", "b"].map(|name| {
+            let log = log.clone();
+            executor.spawn(async move {
+                for i in 0..2 {
+                    log.borrow_mut().push((name, i));
+                    yield_now().await;
+                }
+            })
+        });
+        executor.block_on(async {
+            for handle in handles {
+                handle.await;
+            }
+        });
+
+        assert_eq!(*log.borrow(), vec![("a", 0), ("b", 0), ("a", 1), ("b", 1)]);
+    }
+
+    #[test]
+    fn test_spawn_from_task() {
+        let executor = Rc::new(LocalExecutor::new());
+        let inner = executor.clone();
+        let handle = executor.spawn(async move { inner.spawn(async { 3 }).await * 2 });
+        assert_eq!(executor.block_on(handle), 6);
+    }
+}
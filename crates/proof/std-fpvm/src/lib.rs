@@ -28,6 +28,9 @@ pub use types::FileDescriptor;
 mod channel;
 pub use channel::FileChannel;
 
+mod executor;
+pub use executor::{JoinHandle, LocalExecutor, yield_now};
+
 pub(crate) mod linux;
 
 #[cfg(target_arch = "mips64")]
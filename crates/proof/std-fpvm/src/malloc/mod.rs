@@ -1,22 +1,35 @@
-//! This module contains an implementation of a basic memory allocator for client programs in
-//! running on an embedded device.
+//! This module contains the memory allocator for client programs running on an embedded device.
 //!
-//! The allocator is a linked list allocator based on the `dlmalloc` algorithm, which is a
-//! well-known and widely used allocator software such as OS Kernels.
+//! The global allocator is a buddy system allocator, which returns freed blocks to the heap and
+//! merges them with their free buddies. With the `alloc-stats` feature, it is wrapped in a
+//! [TrackingAllocator], which records the [AllocationStats] used to size the heap of proofs.
+//! Otherwise, allocations go straight to the heap without any bookkeeping.
+
+mod stats;
+pub use stats::{AllocationStats, TrackingAllocator};
 
 /// The global allocator for the program in embedded environments.
 #[cfg(any(target_arch = "mips64", target_arch = "riscv64"))]
 pub mod global_allocator {
+    #[cfg(feature = "alloc-stats")]
+    use super::{AllocationStats, TrackingAllocator};
     use buddy_system_allocator::LockedHeap;
 
     /// The maximum block size, as a power of two, for the buddy system allocator.
     const HEAP_ORDER: usize = 32;
 
+    /// The global allocator for the program, recording its [AllocationStats].
+    #[cfg(feature = "alloc-stats")]
+    #[global_allocator]
+    static ALLOCATOR: TrackingAllocator<LockedHeap<HEAP_ORDER>> =
+        TrackingAllocator::new(LockedHeap::empty());
+
     /// The global allocator for the program.
+    #[cfg(not(feature = "alloc-stats"))]
     #[global_allocator]
     static ALLOCATOR: LockedHeap<HEAP_ORDER> = LockedHeap::empty();
 
-    /// Initialize the [SpinLockedAllocator] with the following parameters:
+    /// Initialize the global allocator with the following parameters:
     /// * `heap_start_addr` is the starting address of the heap memory region,
     /// * `heap_size` is the size of the heap memory region in bytes.
     ///
@@ -27,7 +40,30 @@ pub mod global_allocator {
     /// * After aligning the start and end addresses, the size of the heap must be > 0, or the
     ///   function will panic.
     pub unsafe fn init_allocator(heap_start_addr: usize, heap_size: usize) {
-        unsafe { ALLOCATOR.lock().init(heap_start_addr, heap_size) }
+        #[cfg(feature = "alloc-stats")]
+        let heap = ALLOCATOR.inner();
+        #[cfg(not(feature = "alloc-stats"))]
+        let heap = &ALLOCATOR;
+
+        unsafe { heap.lock().init(heap_start_addr, heap_size) }
+    }
+
+    /// Returns the [AllocationStats] of the global allocator recorded so far.
+    #[cfg(feature = "alloc-stats")]
+    pub fn allocation_stats() -> AllocationStats {
+        ALLOCATOR.stats()
+    }
+}
+
+/// Prints the [AllocationStats] of the global allocator to stderr, if the `alloc-stats` feature is
+/// enabled. Intended to be called at program exit, to help size the heap of proofs.
+///
+/// This function is a no-op outside of the MIPS64 and RISC-V64 profiles.
+pub fn report_allocation_stats() {
+    #[cfg(all(feature = "alloc-stats", any(target_arch = "mips64", target_arch = "riscv64")))]
+    {
+        let stats = global_allocator::allocation_stats();
+        crate::io::print_err(alloc::format!("{stats}\n").as_ref());
     }
 }
 
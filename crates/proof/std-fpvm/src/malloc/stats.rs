@@ -0,0 +1,152 @@
+//! Contains the [TrackingAllocator], which wraps a [GlobalAlloc] and records [AllocationStats]
+//! for sizing the heap of client programs.
+
+use core::{
+    alloc::{GlobalAlloc, Layout},
+    fmt,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+/// A snapshot of the allocation statistics recorded by a [TrackingAllocator].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AllocationStats {
+    /// The total number of successful allocations, including reallocations.
+    pub total_allocs: usize,
+    /// The total number of deallocations.
+    pub total_deallocs: usize,
+    /// The number of bytes currently allocated.
+    pub current_bytes: usize,
+    /// The peak number of bytes allocated at any point in time.
+    pub peak_bytes: usize,
+}
+
+impl fmt::Display for AllocationStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Allocation stats: peak heap = {} bytes, current heap = {} bytes, total allocs = {}, \
+             total deallocs = {}",
+            self.peak_bytes, self.current_bytes, self.total_allocs, self.total_deallocs
+        )
+    }
+}
+
+/// A [GlobalAlloc] wrapper that records [AllocationStats] for every request served by the inner
+/// allocator.
+#[derive(Debug)]
+pub struct TrackingAllocator<A> {
+    /// The inner allocator.
+    inner: A,
+    /// The total number of successful allocations.
+    total_allocs: AtomicUsize,
+    /// The total number of deallocations.
+    total_deallocs: AtomicUsize,
+    /// The number of bytes currently allocated.
+    current_bytes: AtomicUsize,
+    /// The peak number of bytes allocated.
+    peak_bytes: AtomicUsize,
+}
+
+impl<A> TrackingAllocator<A> {
+    /// Creates a new [TrackingAllocator] wrapping the given allocator.
+    pub const fn new(inner: A) -> Self {
+        Self {
+            inner,
+            total_allocs: AtomicUsize::new(0),
+            total_deallocs: AtomicUsize::new(0),
+            current_bytes: AtomicUsize::new(0),
+            peak_bytes: AtomicUsize::new(0),
+        }
+    }
+
+    /// Returns a reference to the inner allocator.
+    pub const fn inner(&self) -> &A {
+        &self.inner
+    }
+
+    /// Returns a snapshot of the [AllocationStats] recorded so far.
+    pub fn stats(&self) -> AllocationStats {
+        AllocationStats {
+            total_allocs: self.total_allocs.load(Ordering::Relaxed),
+            total_deallocs: self.total_deallocs.load(Ordering::Relaxed),
+            current_bytes: self.current_bytes.load(Ordering::Relaxed),
+            peak_bytes: self.peak_bytes.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Records an allocation of `size` bytes.
+    fn record_alloc(&self, size: usize) {
+        self.total_allocs.fetch_add(1, Ordering::Relaxed);
+        let current = self.current_bytes.fetch_add(size, Ordering::Relaxed) + size;
+        self.peak_bytes.fetch_max(current, Ordering::Relaxed);
+    }
+
+    /// Records a deallocation of `size` bytes.
+    fn record_dealloc(&self, size: usize) {
+        self.total_deallocs.fetch_add(1, Ordering::Relaxed);
+        self.current_bytes.fetch_sub(size, Ordering::Relaxed);
+    }
+}
+
+unsafe impl<A: GlobalAlloc> GlobalAlloc for TrackingAllocator<A> {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { self.inner.alloc(layout) };
+        if !ptr.is_null() {
+            self.record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { self.inner.alloc_zeroed(layout) };
+        if !ptr.is_null() {
+            self.record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { self.inner.dealloc(ptr, layout) };
+        self.record_dealloc(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = unsafe { self.inner.realloc(ptr, layout, new_size) };
+        if !new_ptr.is_null() {
+            self.record_dealloc(layout.size());
+            self.record_alloc(new_size);
+        }
+        new_ptr
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::alloc::System;
+
+    #[test]
+    fn test_tracking_allocator_records_stats() {
+        let allocator = TrackingAllocator::new(System);
+        let small = Layout::from_size_align(64, 8).unwrap();
+        let large = Layout::from_size_align(256, 8).unwrap();
+
+        unsafe {
+            let a = allocator.alloc(small);
+            let b = allocator.alloc(large);
+            allocator.dealloc(b, large);
+            let a = allocator.realloc(a, small, 128);
+            allocator.dealloc(a, Layout::from_size_align(128, 8).unwrap());
+        }
+
+        assert_eq!(
+            allocator.stats(),
+            AllocationStats {
+                total_allocs: 3,
+                total_deallocs: 3,
+                current_bytes: 0,
+                peak_bytes: 64 + 256,
+            }
+        );
+    }
+}
@@ -0,0 +1,113 @@
+//! Records of the decisions of the derivation stages, emitted on the `journal` target.
+//!
+//! The records are emitted at `TRACE` level, so that they only cost a level check where the
+//! journal is not written, such as in the fault proof program.
+
+use alloy_primitives::hex;
+use kona_protocol::{BlockInfo, ChannelId, L2BlockInfo};
+
+/// Records a batch accepted on top of the given parent.
+pub(crate) fn batch_accepted(batch_timestamp: u64, inclusion_block: u64, parent: &L2BlockInfo) {
+    trace!(
+        target: "journal",
+        event = "batch_accepted",
+        batch_timestamp,
+        inclusion_block,
+        parent_number = parent.block_info.number,
+        parent_hash = %parent.block_info.hash,
+    );
+}
+
+/// Records a batch dropped for the given reason, while validated on top of the given parent.
+pub(crate) fn batch_dropped(
+    reason: &'static str,
+    batch_timestamp: u64,
+    inclusion_block: u64,
+    parent: &L2BlockInfo,
+) {
+    trace!(
+        target: "journal",
+        event = "batch_dropped",
+        reason,
+        batch_timestamp,
+        inclusion_block,
+        parent_number = parent.block_info.number,
+        parent_hash = %parent.block_info.hash,
+    );
+}
+
+/// Records a span batch of the given number of blocks accepted on top of the given parent.
+pub(crate) fn span_batch_accepted(
+    batch_timestamp: u64,
+    blocks: usize,
+    inclusion_block: u64,
+    parent: &L2BlockInfo,
+) {
+    trace!(
+        target: "journal",
+        event = "span_batch_accepted",
+        batch_timestamp,
+        blocks,
+        inclusion_block,
+        parent_number = parent.block_info.number,
+        parent_hash = %parent.block_info.hash,
+    );
+}
+
+/// Records an invalid span batch of the given number of blocks, dropped while validated on top of
+/// the given parent.
+pub(crate) fn span_batch_dropped(
+    batch_timestamp: u64,
+    blocks: usize,
+    inclusion_block: u64,
+    parent: &L2BlockInfo,
+) {
+    trace!(
+        target: "journal",
+        event = "span_batch_dropped",
+        reason = "invalid",
+        batch_timestamp,
+        blocks,
+        inclusion_block,
+        parent_number = parent.block_info.number,
+        parent_hash = %parent.block_info.hash,
+    );
+}
+
+/// Records a channel opened by a frame of the given L1 origin.
+pub(crate) fn channel_opened(id: ChannelId, origin: &BlockInfo) {
+    trace!(
+        target: "journal",
+        event = "channel_opened",
+        channel = %hex::encode(id),
+        l1_origin = origin.number,
+    );
+}
+
+/// Records a channel opened at the given L1 block, that timed out at the given L1 origin.
+pub(crate) fn channel_timed_out(id: ChannelId, open_block: u64, origin: &BlockInfo) {
+    trace!(
+        target: "journal",
+        event = "channel_timed_out",
+        channel = %hex::encode(id),
+        open_block,
+        l1_origin = origin.number,
+    );
+}
+
+/// Records a channel of the given size dropped for the given reason.
+pub(crate) fn channel_dropped(
+    reason: &'static str,
+    id: ChannelId,
+    size: usize,
+    origin: &BlockInfo,
+) {
+    trace!(
+        target: "journal",
+        event = "channel_dropped",
+        reason,
+        channel = %hex::encode(id),
+        size,
+        l1_origin = origin.number,
+    );
+}
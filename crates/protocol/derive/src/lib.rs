@@ -21,6 +21,8 @@ pub use errors::{
     PipelineEncodingError, PipelineError, PipelineErrorKind, ResetError,
 };
 
+mod journal;
+
 mod pipeline;
 pub use pipeline::{
     AttributesQueueStage, BatchProviderStage, BatchStreamStage, ChannelProviderStage,
@@ -43,11 +45,14 @@ mod traits;
 pub use traits::{
     AttributesBuilder, AttributesProvider, BatchValidationProviderDerive, BlobProvider,
     ChainProvider, DataAvailabilityProvider, L2ChainProvider, NextAttributes, OriginAdvancer,
-    OriginProvider, Pipeline, ResetProvider, SignalReceiver,
+    OriginProvider, Pipeline, ResetProvider, SignalReceiver, StageInspector,
 };
 
 mod types;
-pub use types::{ActivationSignal, PipelineResult, ResetSignal, Signal, StepResult};
+pub use types::{
+    ActivationSignal, ChannelSnapshot, PipelineResult, PipelineSnapshot, ResetSignal, Signal,
+    StepResult,
+};
 
 mod metrics;
 pub use metrics::Metrics;
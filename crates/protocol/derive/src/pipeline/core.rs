@@ -2,8 +2,8 @@
 
 use crate::{
     ActivationSignal, L2ChainProvider, NextAttributes, OriginAdvancer, OriginProvider, Pipeline,
-    PipelineError, PipelineErrorKind, PipelineResult, ResetSignal, Signal, SignalReceiver,
-    StepResult,
+    PipelineError, PipelineErrorKind, PipelineResult, PipelineSnapshot, ResetSignal, Signal,
+    SignalReceiver, StageInspector, StepResult,
 };
 use alloc::{boxed::Box, collections::VecDeque, sync::Arc};
 use async_trait::async_trait;
@@ -135,7 +135,14 @@ where
 #[async_trait]
 impl<S, P> Pipeline for DerivationPipeline<S, P>
 where
-    S: NextAttributes + SignalReceiver + OriginProvider + OriginAdvancer + Debug + Send + Sync,
+    S: NextAttributes
+        + SignalReceiver
+        + OriginProvider
+        + OriginAdvancer
+        + StageInspector
+        + Debug
+        + Send
+        + Sync,
     P: L2ChainProvider + Send + Sync + Debug,
 {
     /// Peeks at the next prepared [`OpAttributesWithParent`] from the pipeline.
@@ -143,6 +150,18 @@ where
         self.prepared.front()
     }
 
+    /// Returns a [`PipelineSnapshot`] of the internal state of the pipeline, recorded by each of
+    /// its stages.
+    fn snapshot(&self) -> PipelineSnapshot {
+        let mut snapshot = PipelineSnapshot {
+            origin: self.origin(),
+            prepared_attributes: self.prepared.len(),
+            ..Default::default()
+        };
+        self.attributes.inspect(&mut snapshot);
+        snapshot
+    }
+
     /// Returns the rollup config.
     fn rollup_config(&self) -> &RollupConfig {
         &self.rollup_config
@@ -303,6 +322,18 @@ mod tests {
         assert_eq!(result, StepResult::PreparedAttributes);
     }
 
+    #[tokio::test]
+    async fn test_derivation_pipeline_snapshot() {
+        let mut pipeline = new_test_pipeline();
+        let snapshot = pipeline.snapshot();
+        assert_eq!(snapshot.origin, Some(BlockInfo::default()));
+        assert_eq!(snapshot.prepared_attributes, 0);
+        assert!(snapshot.channels.is_empty());
+
+        pipeline.prepared.push_back(default_test_payload_attributes());
+        assert_eq!(pipeline.snapshot().prepared_attributes, 1);
+    }
+
     #[tokio::test]
     async fn test_derivation_pipeline_advance_origin() {
         let rollup_config = Arc::new(RollupConfig::default());
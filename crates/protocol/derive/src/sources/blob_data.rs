@@ -23,10 +23,16 @@ pub struct BlobData {
     pub(crate) calldata: Option<Bytes>,
 }
 
+impl From<&Blob> for BlobData {
+    fn from(blob: &Blob) -> Self {
+        Self { data: Some(Bytes::from(*blob)), calldata: None }
+    }
+}
+
 impl BlobData {
     /// Decodes the blob into raw byte data.
     /// Returns a [`BlobDecodingError`] if the blob is invalid.
-    pub(crate) fn decode(&self) -> Result<Bytes, BlobDecodingError> {
+    pub fn decode(&self) -> Result<Bytes, BlobDecodingError> {
         let data = self.data.as_ref().ok_or(BlobDecodingError::MissingData)?;
 
         // Validate the blob encoding version
@@ -243,6 +249,14 @@ mod tests {
         assert_eq!(blob_data.decode(), Ok(Bytes::from(vec![0u8; 1])));
     }
 
+    #[test]
+    fn test_blob_data_from_blob() {
+        let mut blob = Blob::ZERO;
+        blob[4] = 0x01;
+        let blob_data = BlobData::from(&blob);
+        assert_eq!(blob_data.decode(), Ok(Bytes::from(vec![0u8; 1])));
+    }
+
     #[test]
     fn test_blob_data_decode_invalid_field_element() {
         let mut data = vec![0u8; alloy_eips::eip4844::BYTES_PER_BLOB + 10];
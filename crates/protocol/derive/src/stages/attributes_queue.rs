@@ -4,9 +4,9 @@ use crate::{
     errors::{PipelineError, ResetError},
     traits::{
         AttributesBuilder, AttributesProvider, NextAttributes, OriginAdvancer, OriginProvider,
-        SignalReceiver,
+        SignalReceiver, StageInspector,
     },
-    types::{PipelineResult, Signal},
+    types::{PipelineResult, PipelineSnapshot, Signal},
 };
 use alloc::{boxed::Box, sync::Arc};
 use async_trait::async_trait;
@@ -179,6 +179,22 @@ where
     }
 }
 
+impl<P, AB> StageInspector for AttributesQueue<P, AB>
+where
+    P: AttributesProvider
+        + OriginAdvancer
+        + OriginProvider
+        + SignalReceiver
+        + StageInspector
+        + Debug,
+    AB: AttributesBuilder + Debug,
+{
+    fn inspect(&self, snapshot: &mut PipelineSnapshot) {
+        snapshot.pending_batch = self.batch.is_some();
+        self.prev.inspect(snapshot);
+    }
+}
+
 #[async_trait]
 impl<P, AB> SignalReceiver for AttributesQueue<P, AB>
 where
@@ -194,6 +210,7 @@ where
             }
             s @ Signal::FlushChannel => {
                 self.batch = None;
+                self.is_last_in_span = false;
                 self.prev.signal(s).await?;
             }
             s @ Signal::ProvideBlock(_) => {
@@ -248,10 +265,12 @@ mod tests {
     async fn test_attributes_queue_flush() {
         let mut attributes_queue = new_attributes_queue(None, None, vec![]);
         attributes_queue.batch = Some(SingleBatch::default());
+        attributes_queue.is_last_in_span = true;
         assert!(!attributes_queue.prev.flushed);
         attributes_queue.signal(Signal::FlushChannel).await.unwrap();
         assert!(attributes_queue.prev.flushed);
         assert!(attributes_queue.batch.is_none());
+        assert!(!attributes_queue.is_last_in_span);
     }
 
     #[tokio::test]
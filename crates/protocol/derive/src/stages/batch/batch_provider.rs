@@ -3,7 +3,8 @@
 use super::NextBatchProvider;
 use crate::{
     AttributesProvider, BatchQueue, BatchValidator, L2ChainProvider, OriginAdvancer,
-    OriginProvider, PipelineError, PipelineResult, Signal, SignalReceiver,
+    OriginProvider, PipelineError, PipelineResult, PipelineSnapshot, Signal, SignalReceiver,
+    StageInspector,
 };
 use alloc::{boxed::Box, sync::Arc};
 use async_trait::async_trait;
@@ -126,6 +127,27 @@ where
     }
 }
 
+impl<P, F> StageInspector for BatchProvider<P, F>
+where
+    P: NextBatchProvider
+        + OriginAdvancer
+        + OriginProvider
+        + SignalReceiver
+        + StageInspector
+        + Debug,
+    F: L2ChainProvider + Clone + Debug,
+{
+    fn inspect(&self, snapshot: &mut PipelineSnapshot) {
+        if let Some(batch_validator) = &self.batch_validator {
+            batch_validator.inspect(snapshot);
+        } else if let Some(batch_queue) = &self.batch_queue {
+            batch_queue.inspect(snapshot);
+        } else if let Some(prev) = &self.prev {
+            prev.inspect(snapshot);
+        }
+    }
+}
+
 #[async_trait]
 impl<P, F> SignalReceiver for BatchProvider<P, F>
 where
@@ -3,8 +3,11 @@
 use super::NextBatchProvider;
 use crate::{
     errors::{PipelineEncodingError, PipelineError, PipelineErrorKind, ResetError},
-    traits::{AttributesProvider, L2ChainProvider, OriginAdvancer, OriginProvider, SignalReceiver},
-    types::{PipelineResult, ResetSignal, Signal},
+    traits::{
+        AttributesProvider, L2ChainProvider, OriginAdvancer, OriginProvider, SignalReceiver,
+        StageInspector,
+    },
+    types::{PipelineResult, PipelineSnapshot, ResetSignal, Signal},
 };
 use alloc::{boxed::Box, sync::Arc, vec::Vec};
 use async_trait::async_trait;
@@ -48,6 +51,10 @@ where
     /// push it to the list.
     pub l1_blocks: Vec<BlockInfo>,
     /// A set of batches in order from when we've seen them.
+    ///
+    /// Before Holocene, batches ahead of the safe head are retained here until they can be
+    /// applied, and are only pruned once they fall out of the sequencing window. The spec does
+    /// not bound this buffer, so it is not bounded here either.
     pub batches: Vec<BatchWithInclusionBlock>,
     /// A set of cached [`SingleBatch`]es derived from [`SpanBatch`]es.
     ///
@@ -142,6 +149,12 @@ where
                     } else {
                         self.prev.flush();
                         warn!(target: "batch_queue", "[HOLOCENE] Dropping future batch with parent: {}", parent.block_info.number);
+                        crate::journal::batch_dropped(
+                            "future",
+                            batch.batch.timestamp(),
+                            batch.inclusion_block.number,
+                            &parent,
+                        );
                     }
                 }
                 BatchValidity::Drop => {
@@ -149,9 +162,20 @@ where
                     // stage.
                     self.prev.flush();
                     warn!(target: "batch_queue", "Dropping batch with parent: {}", parent.block_info);
+                    crate::journal::batch_dropped(
+                        "invalid",
+                        batch.batch.timestamp(),
+                        batch.inclusion_block.number,
+                        &parent,
+                    );
                     continue;
                 }
                 BatchValidity::Accept => {
+                    crate::journal::batch_accepted(
+                        batch.batch.timestamp(),
+                        batch.inclusion_block.number,
+                        &parent,
+                    );
                     next_batch = Some(batch.clone());
                     // Don't keep the current batch in the remaining items since we are processing
                     // it now, but retain every batch we didn't get to yet.
@@ -170,6 +194,12 @@ where
                     }
 
                     warn!(target: "batch_queue", "[HOLOCENE] Dropping outdated batch with parent: {}", parent.block_info.number);
+                    crate::journal::batch_dropped(
+                        "past",
+                        batch.batch.timestamp(),
+                        batch.inclusion_block.number,
+                        &parent,
+                    );
                     continue;
                 }
             }
@@ -428,6 +458,24 @@ where
     }
 }
 
+impl<P, BF> StageInspector for BatchQueue<P, BF>
+where
+    P: NextBatchProvider
+        + OriginAdvancer
+        + OriginProvider
+        + SignalReceiver
+        + StageInspector
+        + Debug,
+    BF: L2ChainProvider + Debug,
+{
+    fn inspect(&self, snapshot: &mut PipelineSnapshot) {
+        snapshot.batch_queue = self.batches.len();
+        snapshot.span_buffer += self.next_spans.len();
+        snapshot.l1_blocks = self.l1_blocks.len();
+        self.prev.inspect(snapshot);
+    }
+}
+
 #[async_trait]
 impl<P, BF> SignalReceiver for BatchQueue<P, BF>
 where
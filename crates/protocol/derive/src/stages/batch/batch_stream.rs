@@ -2,7 +2,7 @@
 
 use crate::{
     L2ChainProvider, NextBatchProvider, OriginAdvancer, OriginProvider, PipelineEncodingError,
-    PipelineError, PipelineResult, Signal, SignalReceiver,
+    PipelineError, PipelineResult, PipelineSnapshot, Signal, SignalReceiver, StageInspector,
 };
 use alloc::{boxed::Box, collections::VecDeque, sync::Arc};
 use async_trait::async_trait;
@@ -170,8 +170,23 @@ where
                     );
 
                     match validity {
-                        BatchValidity::Accept => self.span = Some(b),
+                        BatchValidity::Accept => {
+                            crate::journal::span_batch_accepted(
+                                b.starting_timestamp(),
+                                b.batches.len(),
+                                batch_with_inclusion.inclusion_block.number,
+                                &parent,
+                            );
+                            self.span = Some(b);
+                        }
                         BatchValidity::Drop => {
+                            crate::journal::span_batch_dropped(
+                                b.starting_timestamp(),
+                                b.batches.len(),
+                                batch_with_inclusion.inclusion_block.number,
+                                &parent,
+                            );
+
                             // Flush the stage.
                             self.flush();
 
@@ -219,6 +234,22 @@ where
     }
 }
 
+impl<P, BF> StageInspector for BatchStream<P, BF>
+where
+    P: BatchStreamProvider
+        + OriginAdvancer
+        + OriginProvider
+        + SignalReceiver
+        + StageInspector
+        + Debug,
+    BF: L2ChainProvider + Debug,
+{
+    fn inspect(&self, snapshot: &mut PipelineSnapshot) {
+        snapshot.span_buffer += self.buffer.len();
+        self.prev.inspect(snapshot);
+    }
+}
+
 #[async_trait]
 impl<P, BF> SignalReceiver for BatchStream<P, BF>
 where
@@ -3,8 +3,8 @@
 use super::NextBatchProvider;
 use crate::{
     errors::{PipelineError, PipelineErrorKind, ResetError},
-    traits::{AttributesProvider, OriginAdvancer, OriginProvider, SignalReceiver},
-    types::{PipelineResult, ResetSignal, Signal},
+    traits::{AttributesProvider, OriginAdvancer, OriginProvider, SignalReceiver, StageInspector},
+    types::{PipelineResult, PipelineSnapshot, ResetSignal, Signal},
 };
 use alloc::{boxed::Box, sync::Arc, vec::Vec};
 use async_trait::async_trait;
@@ -256,14 +256,27 @@ where
         ) {
             BatchValidity::Accept => {
                 info!(target: "batch_validator", "Found next batch (epoch #{})", next_batch.epoch_num);
+                crate::journal::batch_accepted(next_batch.timestamp, stage_origin.number, &parent);
                 Ok(next_batch)
             }
             BatchValidity::Past => {
                 warn!(target: "batch_validator", "Dropping old batch");
+                crate::journal::batch_dropped(
+                    "past",
+                    next_batch.timestamp,
+                    stage_origin.number,
+                    &parent,
+                );
                 Err(PipelineError::NotEnoughData.temp())
             }
             BatchValidity::Drop => {
                 warn!(target: "batch_validator", "Invalid singular batch, flushing current channel.");
+                crate::journal::batch_dropped(
+                    "invalid",
+                    next_batch.timestamp,
+                    stage_origin.number,
+                    &parent,
+                );
                 self.prev.flush();
                 Err(PipelineError::NotEnoughData.temp())
             }
@@ -289,6 +302,21 @@ where
     }
 }
 
+impl<P> StageInspector for BatchValidator<P>
+where
+    P: NextBatchProvider
+        + OriginAdvancer
+        + OriginProvider
+        + SignalReceiver
+        + StageInspector
+        + Debug,
+{
+    fn inspect(&self, snapshot: &mut PipelineSnapshot) {
+        snapshot.l1_blocks = self.l1_blocks.len();
+        self.prev.inspect(snapshot);
+    }
+}
+
 #[async_trait]
 impl<P> OriginAdvancer for BatchValidator<P>
 where
@@ -3,16 +3,14 @@
 use super::{ChannelReaderProvider, NextFrameProvider};
 use crate::{
     errors::PipelineError,
-    traits::{OriginAdvancer, OriginProvider, SignalReceiver},
-    types::{PipelineResult, Signal},
+    traits::{OriginAdvancer, OriginProvider, SignalReceiver, StageInspector},
+    types::{ChannelSnapshot, PipelineResult, PipelineSnapshot, Signal},
 };
 use alloc::{boxed::Box, sync::Arc};
 use alloy_primitives::{Bytes, hex};
 use async_trait::async_trait;
 use core::fmt::Debug;
-use kona_genesis::{
-    MAX_RLP_BYTES_PER_CHANNEL_BEDROCK, MAX_RLP_BYTES_PER_CHANNEL_FJORD, RollupConfig,
-};
+use kona_genesis::RollupConfig;
 use kona_protocol::{BlockInfo, Channel};
 
 /// The [`ChannelAssembler`] stage is responsible for assembling the [`Frame`]s from the
@@ -76,6 +74,11 @@ where
                     origin.number,
                     channel.open_block_number()
                 );
+                crate::journal::channel_timed_out(
+                    channel.id(),
+                    channel.open_block_number(),
+                    &origin,
+                );
                 self.channel = None;
             }
         }
@@ -91,6 +94,7 @@ where
                 hex::encode(next_frame.id),
                 origin.number
             );
+            crate::journal::channel_opened(next_frame.id, &origin);
             self.channel = Some(Channel::new(next_frame.id, origin));
         }
 
@@ -112,12 +116,13 @@ where
                 hex::encode(channel.id()),
                 origin.number
             );
-            if channel.add_frame(next_frame, origin).is_err() {
+            if let Err(e) = channel.add_frame(next_frame, origin) {
                 error!(
                     target: "channel_assembler",
-                    "Failed to add frame to channel (ID: {}) at L1 origin #{}",
+                    "Failed to add frame to channel (ID: {}) at L1 origin #{}: {}",
                     hex::encode(channel.id()),
-                    origin.number
+                    origin.number,
+                    e
                 );
                 return Err(PipelineError::NotEnoughData.temp());
             }
@@ -125,11 +130,7 @@ where
             let size = channel.size() as f64;
             kona_macros::set!(gauge, crate::metrics::Metrics::PIPELINE_CHANNEL_MEM, size);
 
-            let max_rlp_bytes_per_channel = if self.cfg.is_fjord_active(origin.timestamp) {
-                MAX_RLP_BYTES_PER_CHANNEL_FJORD
-            } else {
-                MAX_RLP_BYTES_PER_CHANNEL_BEDROCK
-            };
+            let max_rlp_bytes_per_channel = self.cfg.max_rlp_bytes_per_channel(origin.timestamp);
             kona_macros::set!(
                 gauge,
                 crate::metrics::Metrics::PIPELINE_MAX_RLP_BYTES,
@@ -142,6 +143,7 @@ where
                     hex::encode(channel.id()),
                     channel.size()
                 );
+                crate::journal::channel_dropped("too_large", channel.id(), channel.size(), &origin);
                 self.channel = None;
                 return Err(PipelineError::NotEnoughData.temp());
             }
@@ -188,6 +190,23 @@ where
     }
 }
 
+impl<P> StageInspector for ChannelAssembler<P>
+where
+    P: NextFrameProvider
+        + OriginAdvancer
+        + OriginProvider
+        + SignalReceiver
+        + StageInspector
+        + Debug,
+{
+    fn inspect(&self, snapshot: &mut PipelineSnapshot) {
+        let origin = self.origin();
+        snapshot.channels =
+            self.channel.iter().map(|channel| ChannelSnapshot::new(channel, origin)).collect();
+        self.prev.inspect(snapshot);
+    }
+}
+
 #[async_trait]
 impl<P> SignalReceiver for ChannelAssembler<P>
 where
@@ -1,8 +1,9 @@
 //! This module contains the `ChannelBank` struct.
 
 use crate::{
-    ChannelReaderProvider, NextFrameProvider, OriginAdvancer, OriginProvider, PipelineError,
-    PipelineErrorKind, PipelineResult, Signal, SignalReceiver,
+    ChannelReaderProvider, ChannelSnapshot, NextFrameProvider, OriginAdvancer, OriginProvider,
+    PipelineError, PipelineErrorKind, PipelineResult, PipelineSnapshot, Signal, SignalReceiver,
+    StageInspector,
 };
 use alloc::{boxed::Box, collections::VecDeque, sync::Arc};
 use alloy_primitives::{Bytes, hex, map::HashMap};
@@ -84,6 +85,7 @@ where
         let current_channel = match self.channels.get_mut(&frame.id) {
             Some(c) => c,
             None => {
+                crate::journal::channel_opened(frame.id, &origin);
                 let channel = Channel::new(frame.id, origin);
                 self.channel_queue.push_back(frame.id);
                 self.channels.insert(frame.id, channel);
@@ -104,8 +106,8 @@ where
 
         // Ingest the frame. If it fails, ignore the frame.
         let frame_id = frame.id;
-        if current_channel.add_frame(frame, origin).is_err() {
-            warn!(target: "channel_bank", "Failed to add frame to channel: {:?}", frame_id);
+        if let Err(e) = current_channel.add_frame(frame, origin) {
+            warn!(target: "channel_bank", "Failed to add frame to channel: {:?}: {}", frame_id, e);
             return Ok(());
         }
 
@@ -134,6 +136,7 @@ where
                 target: "channel_bank",
                 "Channel (ID: {}) timed out", hex::encode(first)
             );
+            crate::journal::channel_timed_out(first, channel.open_block_number(), &origin);
             self.channels.remove(&first);
             self.channel_queue.pop_front();
             return Ok(None);
@@ -224,6 +227,27 @@ where
     }
 }
 
+impl<P> StageInspector for ChannelBank<P>
+where
+    P: NextFrameProvider
+        + OriginAdvancer
+        + OriginProvider
+        + SignalReceiver
+        + StageInspector
+        + Debug,
+{
+    fn inspect(&self, snapshot: &mut PipelineSnapshot) {
+        let origin = self.origin();
+        snapshot.channels = self
+            .channel_queue
+            .iter()
+            .filter_map(|id| self.channels.get(id))
+            .map(|channel| ChannelSnapshot::new(channel, origin))
+            .collect();
+        self.prev.inspect(snapshot);
+    }
+}
+
 #[async_trait]
 impl<P> SignalReceiver for ChannelBank<P>
 where
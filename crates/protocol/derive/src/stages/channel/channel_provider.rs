@@ -3,8 +3,8 @@
 use super::{ChannelAssembler, ChannelBank, ChannelReaderProvider, NextFrameProvider};
 use crate::{
     errors::PipelineError,
-    traits::{OriginAdvancer, OriginProvider, SignalReceiver},
-    types::{PipelineResult, Signal},
+    traits::{OriginAdvancer, OriginProvider, SignalReceiver, StageInspector},
+    types::{PipelineResult, PipelineSnapshot, Signal},
 };
 use alloc::{boxed::Box, sync::Arc};
 use alloy_primitives::Bytes;
@@ -117,6 +117,26 @@ where
     }
 }
 
+impl<P> StageInspector for ChannelProvider<P>
+where
+    P: NextFrameProvider
+        + OriginAdvancer
+        + OriginProvider
+        + SignalReceiver
+        + StageInspector
+        + Debug,
+{
+    fn inspect(&self, snapshot: &mut PipelineSnapshot) {
+        if let Some(channel_assembler) = &self.channel_assembler {
+            channel_assembler.inspect(snapshot);
+        } else if let Some(channel_bank) = &self.channel_bank {
+            channel_bank.inspect(snapshot);
+        } else if let Some(prev) = &self.prev {
+            prev.inspect(snapshot);
+        }
+    }
+}
+
 #[async_trait]
 impl<P> SignalReceiver for ChannelProvider<P>
 where
@@ -1,16 +1,14 @@
 //! This module contains the `ChannelReader` struct.
 
 use crate::{
-    BatchStreamProvider, OriginAdvancer, OriginProvider, PipelineError, PipelineResult, Signal,
-    SignalReceiver,
+    BatchStreamProvider, OriginAdvancer, OriginProvider, PipelineError, PipelineResult,
+    PipelineSnapshot, Signal, SignalReceiver, StageInspector,
 };
 use alloc::{boxed::Box, sync::Arc};
 use alloy_primitives::Bytes;
 use async_trait::async_trait;
 use core::fmt::Debug;
-use kona_genesis::{
-    MAX_RLP_BYTES_PER_CHANNEL_BEDROCK, MAX_RLP_BYTES_PER_CHANNEL_FJORD, RollupConfig,
-};
+use kona_genesis::RollupConfig;
 use kona_protocol::{Batch, BatchReader, BlockInfo};
 use tracing::{debug, warn};
 
@@ -62,11 +60,7 @@ where
                 self.prev.next_data().await?.ok_or(PipelineError::ChannelReaderEmpty.temp())?;
 
             let origin = self.prev.origin().ok_or(PipelineError::MissingOrigin.crit())?;
-            let max_rlp_bytes_per_channel = if self.cfg.is_fjord_active(origin.timestamp) {
-                MAX_RLP_BYTES_PER_CHANNEL_FJORD
-            } else {
-                MAX_RLP_BYTES_PER_CHANNEL_BEDROCK
-            };
+            let max_rlp_bytes_per_channel = self.cfg.max_rlp_bytes_per_channel(origin.timestamp);
 
             self.next_batch =
                 Some(BatchReader::new(&channel[..], max_rlp_bytes_per_channel as usize));
@@ -116,6 +110,8 @@ where
             return Err(e);
         }
 
+        let origin = self.prev.origin().ok_or(PipelineError::MissingOrigin.crit())?;
+
         // SAFETY: The batch reader must be set above.
         let next_batch = self.next_batch.as_mut().expect("Batch reader must be set");
         match next_batch.decompress() {
@@ -146,8 +142,8 @@ where
         }
 
         // Read the next batch from the reader's decompressed data
-        match next_batch.next_batch(self.cfg.as_ref()).ok_or(PipelineError::NotEnoughData.temp()) {
-            Ok(batch) => {
+        match next_batch.read_batch(self.cfg.as_ref(), origin.timestamp) {
+            Ok(Some(batch)) => {
                 kona_macros::inc!(
                     gauge,
                     crate::metrics::Metrics::PIPELINE_READ_BATCHES,
@@ -155,9 +151,17 @@ where
                 );
                 Ok(batch)
             }
-            Err(e) => {
+            Ok(None) => {
                 self.next_channel();
-                Err(e)
+                Err(PipelineError::NotEnoughData.temp())
+            }
+            Err(err) => {
+                warn!(target: "channel_reader", %err, "Failed to read batch");
+                // A span batch included before Delta is skipped, the rest of the channel is read.
+                if err.discards_channel() {
+                    self.next_channel();
+                }
+                Err(PipelineError::NotEnoughData.temp())
             }
         }
     }
@@ -172,6 +176,21 @@ where
     }
 }
 
+impl<P> StageInspector for ChannelReader<P>
+where
+    P: ChannelReaderProvider
+        + OriginAdvancer
+        + OriginProvider
+        + SignalReceiver
+        + StageInspector
+        + Debug,
+{
+    fn inspect(&self, snapshot: &mut PipelineSnapshot) {
+        snapshot.reading_channel = self.next_batch.is_some();
+        self.prev.inspect(snapshot);
+    }
+}
+
 #[async_trait]
 impl<P> SignalReceiver for ChannelReader<P>
 where
@@ -201,7 +220,7 @@ mod test {
         errors::PipelineErrorKind, test_utils::TestChannelReaderProvider, types::ResetSignal,
     };
     use alloc::vec;
-    use kona_genesis::HardForkConfig;
+    use kona_genesis::{HardForkConfig, MAX_RLP_BYTES_PER_CHANNEL_FJORD};
 
     fn new_compressed_batch_data() -> Bytes {
         let file_contents =
@@ -269,18 +288,36 @@ mod test {
     #[tokio::test]
     async fn test_next_batch_succeeds() {
         let raw = new_compressed_batch_data();
+        let config = Arc::new(RollupConfig {
+            hardforks: HardForkConfig { delta_time: Some(0), ..Default::default() },
+            ..Default::default()
+        });
         let mock = TestChannelReaderProvider::new(vec![Ok(Some(raw))]);
-        let mut reader = ChannelReader::new(mock, Arc::new(RollupConfig::default()));
+        let mut reader = ChannelReader::new(mock, config);
         let res = reader.next_batch().await.unwrap();
         matches!(res, Batch::Span(_));
         assert!(reader.next_batch.is_some());
     }
 
+    #[tokio::test]
+    async fn test_next_batch_skips_span_batch_before_delta() {
+        let raw = new_compressed_batch_data();
+        let mock = TestChannelReaderProvider::new(vec![Ok(Some(raw))]);
+        let mut reader = ChannelReader::new(mock, Arc::new(RollupConfig::default()));
+        assert_eq!(reader.next_batch().await, Err(PipelineError::NotEnoughData.temp()));
+        // The span batch is skipped, but the rest of the channel is still read.
+        assert_eq!(reader.next_batch.as_ref().map(|r| r.index), Some(1));
+    }
+
     #[tokio::test]
     async fn test_flush_post_holocene() {
         let raw = new_compressed_batch_data();
         let config = Arc::new(RollupConfig {
-            hardforks: HardForkConfig { holocene_time: Some(0), ..Default::default() },
+            hardforks: HardForkConfig {
+                delta_time: Some(0),
+                holocene_time: Some(0),
+                ..Default::default()
+            },
             ..Default::default()
         });
         let mock = TestChannelReaderProvider::new(vec![Ok(Some(raw))]);
@@ -1,8 +1,8 @@
 //! This module contains the [FrameQueue] stage of the derivation pipeline.
 
 use crate::{
-    NextFrameProvider, OriginAdvancer, OriginProvider, PipelineError, PipelineResult, Signal,
-    SignalReceiver,
+    NextFrameProvider, OriginAdvancer, OriginProvider, PipelineError, PipelineResult,
+    PipelineSnapshot, Signal, SignalReceiver, StageInspector,
 };
 use alloc::{boxed::Box, collections::VecDeque, sync::Arc};
 use alloy_primitives::Bytes;
@@ -185,6 +185,21 @@ where
     }
 }
 
+impl<P> StageInspector for FrameQueue<P>
+where
+    P: FrameQueueProvider
+        + OriginAdvancer
+        + OriginProvider
+        + SignalReceiver
+        + StageInspector
+        + Debug,
+{
+    fn inspect(&self, snapshot: &mut PipelineSnapshot) {
+        snapshot.frame_queue = self.queue.len();
+        self.prev.inspect(snapshot);
+    }
+}
+
 #[async_trait]
 impl<P> SignalReceiver for FrameQueue<P>
 where
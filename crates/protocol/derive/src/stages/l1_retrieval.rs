@@ -2,7 +2,8 @@
 
 use crate::{
     ActivationSignal, DataAvailabilityProvider, FrameQueueProvider, OriginAdvancer, OriginProvider,
-    PipelineError, PipelineErrorKind, PipelineResult, ResetSignal, Signal, SignalReceiver,
+    PipelineError, PipelineErrorKind, PipelineResult, PipelineSnapshot, ResetSignal, Signal,
+    SignalReceiver, StageInspector,
 };
 use alloc::boxed::Box;
 use alloy_primitives::Address;
@@ -114,6 +115,16 @@ where
     }
 }
 
+impl<DAP, P> StageInspector for L1Retrieval<DAP, P>
+where
+    DAP: DataAvailabilityProvider,
+    P: L1RetrievalProvider + OriginAdvancer + OriginProvider + SignalReceiver + StageInspector,
+{
+    fn inspect(&self, snapshot: &mut PipelineSnapshot) {
+        self.prev.inspect(snapshot);
+    }
+}
+
 #[async_trait]
 impl<DAP, P> SignalReceiver for L1Retrieval<DAP, P>
 where
@@ -2,7 +2,8 @@
 
 use crate::{
     ActivationSignal, ChainProvider, L1RetrievalProvider, OriginAdvancer, OriginProvider,
-    PipelineError, PipelineResult, ResetError, ResetSignal, Signal, SignalReceiver,
+    PipelineError, PipelineResult, PipelineSnapshot, ResetError, ResetSignal, Signal,
+    SignalReceiver, StageInspector,
 };
 use alloc::{boxed::Box, sync::Arc};
 use alloy_primitives::Address;
@@ -134,6 +135,10 @@ impl<F: ChainProvider> OriginProvider for IndexedTraversal<F> {
     }
 }
 
+impl<F: ChainProvider> StageInspector for IndexedTraversal<F> {
+    fn inspect(&self, _: &mut PipelineSnapshot) {}
+}
+
 #[async_trait]
 impl<F: ChainProvider + Send> SignalReceiver for IndexedTraversal<F> {
     async fn signal(&mut self, signal: Signal) -> PipelineResult<()> {
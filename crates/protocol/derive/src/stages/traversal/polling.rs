@@ -2,7 +2,8 @@
 
 use crate::{
     ActivationSignal, ChainProvider, L1RetrievalProvider, OriginAdvancer, OriginProvider,
-    PipelineError, PipelineResult, ResetError, ResetSignal, Signal, SignalReceiver,
+    PipelineError, PipelineResult, PipelineSnapshot, ResetError, ResetSignal, Signal,
+    SignalReceiver, StageInspector,
 };
 use alloc::{boxed::Box, sync::Arc};
 use alloy_primitives::Address;
@@ -151,6 +152,10 @@ impl<F: ChainProvider> OriginProvider for PollingTraversal<F> {
     }
 }
 
+impl<F: ChainProvider> StageInspector for PollingTraversal<F> {
+    fn inspect(&self, _: &mut PipelineSnapshot) {}
+}
+
 #[async_trait]
 impl<F: ChainProvider + Send> SignalReceiver for PollingTraversal<F> {
     async fn signal(&mut self, signal: Signal) -> PipelineResult<()> {
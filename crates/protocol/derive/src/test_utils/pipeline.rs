@@ -13,7 +13,7 @@ use kona_protocol::{BlockInfo, L2BlockInfo, OpAttributesWithParent};
 use crate::{
     AttributesQueue, BatchStream, ChannelProvider, ChannelReader, DerivationPipeline, FrameQueue,
     L1Retrieval, NextAttributes, OriginAdvancer, OriginProvider, PipelineBuilder, PipelineError,
-    PollingTraversal, Signal, SignalReceiver,
+    PipelineSnapshot, PollingTraversal, Signal, SignalReceiver, StageInspector,
     test_utils::{TestAttributesBuilder, TestDAP},
 };
 
@@ -48,6 +48,10 @@ impl OriginAdvancer for TestNextAttributes {
     }
 }
 
+impl StageInspector for TestNextAttributes {
+    fn inspect(&self, _: &mut PipelineSnapshot) {}
+}
+
 #[async_trait::async_trait]
 impl NextAttributes for TestNextAttributes {
     /// Returns the next valid [`OpAttributesWithParent`].
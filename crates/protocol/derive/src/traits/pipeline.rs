@@ -6,7 +6,7 @@ use core::iter::Iterator;
 use kona_genesis::{RollupConfig, SystemConfig};
 use kona_protocol::{L2BlockInfo, OpAttributesWithParent};
 
-use crate::{OriginProvider, PipelineErrorKind, StepResult};
+use crate::{OriginProvider, PipelineErrorKind, PipelineSnapshot, StepResult};
 
 /// This trait defines the interface for interacting with the derivation pipeline.
 #[async_trait]
@@ -25,4 +25,11 @@ pub trait Pipeline: OriginProvider + Iterator<Item = OpAttributesWithParent> {
         &mut self,
         number: u64,
     ) -> Result<SystemConfig, PipelineErrorKind>;
+
+    /// Returns a [`PipelineSnapshot`] of the internal state of the pipeline.
+    ///
+    /// By default, only the origin of the pipeline is recorded.
+    fn snapshot(&self) -> PipelineSnapshot {
+        PipelineSnapshot { origin: self.origin(), ..Default::default() }
+    }
 }
@@ -4,7 +4,7 @@ use alloc::boxed::Box;
 use async_trait::async_trait;
 use kona_protocol::BlockInfo;
 
-use crate::{PipelineResult, Signal};
+use crate::{PipelineResult, PipelineSnapshot, Signal};
 
 /// Providers a way for the pipeline to accept a signal from the driver.
 #[async_trait]
@@ -19,6 +19,12 @@ pub trait OriginProvider {
     fn origin(&self) -> Option<BlockInfo>;
 }
 
+/// Provides a method for recording the internal state of a stage in a [`PipelineSnapshot`].
+pub trait StageInspector {
+    /// Records the state of the stage, and of the stages before it, in the [`PipelineSnapshot`].
+    fn inspect(&self, snapshot: &mut PipelineSnapshot);
+}
+
 /// Defines a trait for advancing the L1 origin of the pipeline.
 #[async_trait]
 pub trait OriginAdvancer {
@@ -5,3 +5,6 @@ pub use results::{PipelineResult, StepResult};
 
 mod signals;
 pub use signals::{ActivationSignal, ResetSignal, Signal};
+
+mod snapshot;
+pub use snapshot::{ChannelSnapshot, PipelineSnapshot};
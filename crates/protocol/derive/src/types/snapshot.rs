@@ -0,0 +1,91 @@
+//! Snapshot types of the internal state of the `kona-derive` pipeline.
+//!
+//! Snapshots are taken by walking the stages of the pipeline from the top down, each stage
+//! recording its own state through the [`StageInspector`] trait.
+//!
+//! [`StageInspector`]: crate::StageInspector
+
+use alloc::vec::Vec;
+use alloy_primitives::B128;
+use kona_protocol::{BlockInfo, Channel};
+
+/// A snapshot of the internal state of the derivation pipeline, used to find out what the
+/// pipeline is waiting for when the safe head does not progress.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct PipelineSnapshot {
+    /// The L1 origin of the pipeline.
+    pub origin: Option<BlockInfo>,
+    /// The number of frames queued in the frame queue.
+    pub frame_queue: usize,
+    /// The channels being assembled, in the order they were opened.
+    pub channels: Vec<ChannelSnapshot>,
+    /// Whether a channel is being decoded into batches.
+    pub reading_channel: bool,
+    /// The number of single batches buffered from span batches.
+    pub span_buffer: usize,
+    /// The number of batches in the batch queue, waiting for the safe head to reach them.
+    pub batch_queue: usize,
+    /// The number of L1 blocks in the window of L1 origins of the batch stage.
+    pub l1_blocks: usize,
+    /// Whether a batch is waiting to be turned into payload attributes.
+    pub pending_batch: bool,
+    /// The number of payload attributes prepared, waiting to be consumed.
+    pub prepared_attributes: usize,
+}
+
+/// A snapshot of a channel being assembled in the derivation pipeline.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct ChannelSnapshot {
+    /// The ID of the channel.
+    pub id: B128,
+    /// The L1 block in which the channel was opened.
+    pub open_block: BlockInfo,
+    /// The number of L1 blocks between the L1 origin of the pipeline and the opening of the
+    /// channel.
+    pub age: u64,
+    /// The estimated size of the channel, in bytes.
+    pub size: usize,
+    /// The number of frames received.
+    pub frames: usize,
+    /// Whether the last frame of the channel was received.
+    pub closed: bool,
+    /// Whether all frames of the channel were received.
+    pub ready: bool,
+}
+
+impl ChannelSnapshot {
+    /// Creates a [`ChannelSnapshot`] of the given [`Channel`], at the given L1 origin.
+    pub fn new(channel: &Channel, origin: Option<BlockInfo>) -> Self {
+        Self {
+            id: B128::from(channel.id()),
+            open_block: channel.open_block,
+            age: origin.map_or(0, |origin| origin.number.saturating_sub(channel.open_block.number)),
+            size: channel.size(),
+            frames: channel.len(),
+            closed: channel.closed,
+            ready: channel.is_ready(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_channel_snapshot() {
+        let channel = Channel::new([1; 16], BlockInfo { number: 10, ..Default::default() });
+        let origin = BlockInfo { number: 13, ..Default::default() };
+
+        let snapshot = ChannelSnapshot::new(&channel, Some(origin));
+        assert_eq!(snapshot.id, B128::repeat_byte(1));
+        assert_eq!(snapshot.age, 3);
+        assert_eq!(snapshot.frames, 0);
+        assert!(!snapshot.ready);
+        assert_eq!(ChannelSnapshot::new(&channel, None).age, 0);
+    }
+}
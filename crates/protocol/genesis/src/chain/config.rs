@@ -178,6 +178,7 @@ impl ChainConfig {
             channel_timeout: 300,
             granite_channel_timeout: GRANITE_CHANNEL_TIMEOUT,
             interop_message_expiry_window: DEFAULT_INTEROP_MESSAGE_EXPIRY_WINDOW,
+            message_passer_address: None,
             chain_op_config: self.base_fee_config(),
             alt_da_config: self.alt_da.clone(),
         }
@@ -0,0 +1,246 @@
+//! Contains the deploy configuration of a new chain.
+
+use alloy_eips::BlockId;
+use alloy_primitives::{Address, Bytes, U256};
+
+use crate::{BaseFeeConfig, HardForkConfig};
+
+/// The deploy configuration of a new OP Stack chain, in the format of the deploy configurations of
+/// op-deployer and of the contracts-bedrock deploy scripts.
+///
+/// Only the fields used to generate the L2 genesis and the rollup configuration are parsed, the
+/// other fields of the deploy configuration are ignored.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct DeployConfig {
+    /// The L1 chain ID.
+    #[cfg_attr(feature = "serde", serde(rename = "l1ChainID"))]
+    pub l1_chain_id: u64,
+    /// The L2 chain ID.
+    #[cfg_attr(feature = "serde", serde(rename = "l2ChainID"))]
+    pub l2_chain_id: u64,
+    /// The L1 block the L2 chain starts from. Defaults to the latest L1 block.
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub l1_starting_block_tag: Option<BlockId>,
+    /// The block time of the L2, in seconds.
+    pub l2_block_time: u64,
+    /// The maximum sequencer drift, in seconds.
+    pub max_sequencer_drift: u64,
+    /// The sequencer window size, in L1 blocks.
+    pub sequencer_window_size: u64,
+    /// The channel timeout, in L1 blocks.
+    pub channel_timeout: u64,
+    /// The L1 address that batches are sent to.
+    pub batch_inbox_address: Address,
+    /// The initial batcher address.
+    pub batch_sender_address: Address,
+    /// The gas limit of the L2 genesis block. Defaults to 30M gas.
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            default,
+            with = "alloy_serde::quantity::opt",
+            skip_serializing_if = "Option::is_none"
+        )
+    )]
+    pub l2_genesis_block_gas_limit: Option<u64>,
+    /// The base fee of the L2 genesis block. Defaults to 1 gwei.
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub l2_genesis_block_base_fee_per_gas: Option<U256>,
+    /// The timestamp of the L2 genesis block. Defaults to the timestamp of the L1 starting block.
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            default,
+            with = "alloy_serde::quantity::opt",
+            skip_serializing_if = "Option::is_none"
+        )
+    )]
+    pub l2_genesis_block_timestamp: Option<u64>,
+    /// The extra data of the L2 genesis block. Defaults to `BEDROCK`, and is replaced by the
+    /// EIP-1559 parameters if Holocene is active at genesis.
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub l2_genesis_block_extra_data: Option<Bytes>,
+    /// The initial L1 fee overhead, before Ecotone.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub gas_price_oracle_overhead: u64,
+    /// The initial L1 fee scalar, before Ecotone. If zero, the Ecotone scalars are used instead.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub gas_price_oracle_scalar: u64,
+    /// The initial L1 base fee scalar, since Ecotone.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub gas_price_oracle_base_fee_scalar: u32,
+    /// The initial L1 blob base fee scalar, since Ecotone.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub gas_price_oracle_blob_base_fee_scalar: u32,
+    /// The EIP-1559 elasticity multiplier.
+    pub eip1559_elasticity: u64,
+    /// The EIP-1559 base fee max change denominator.
+    pub eip1559_denominator: u64,
+    /// The EIP-1559 base fee max change denominator since Canyon.
+    pub eip1559_denominator_canyon: u64,
+    /// The activation of Regolith, in seconds after the L2 genesis.
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            default,
+            with = "alloy_serde::quantity::opt",
+            skip_serializing_if = "Option::is_none"
+        )
+    )]
+    pub l2_genesis_regolith_time_offset: Option<u64>,
+    /// The activation of Canyon, in seconds after the L2 genesis.
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            default,
+            with = "alloy_serde::quantity::opt",
+            skip_serializing_if = "Option::is_none"
+        )
+    )]
+    pub l2_genesis_canyon_time_offset: Option<u64>,
+    /// The activation of Delta, in seconds after the L2 genesis.
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            default,
+            with = "alloy_serde::quantity::opt",
+            skip_serializing_if = "Option::is_none"
+        )
+    )]
+    pub l2_genesis_delta_time_offset: Option<u64>,
+    /// The activation of Ecotone, in seconds after the L2 genesis.
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            default,
+            with = "alloy_serde::quantity::opt",
+            skip_serializing_if = "Option::is_none"
+        )
+    )]
+    pub l2_genesis_ecotone_time_offset: Option<u64>,
+    /// The activation of Fjord, in seconds after the L2 genesis.
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            default,
+            with = "alloy_serde::quantity::opt",
+            skip_serializing_if = "Option::is_none"
+        )
+    )]
+    pub l2_genesis_fjord_time_offset: Option<u64>,
+    /// The activation of Granite, in seconds after the L2 genesis.
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            default,
+            with = "alloy_serde::quantity::opt",
+            skip_serializing_if = "Option::is_none"
+        )
+    )]
+    pub l2_genesis_granite_time_offset: Option<u64>,
+    /// The activation of Holocene, in seconds after the L2 genesis.
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            default,
+            with = "alloy_serde::quantity::opt",
+            skip_serializing_if = "Option::is_none"
+        )
+    )]
+    pub l2_genesis_holocene_time_offset: Option<u64>,
+    /// The activation of Isthmus, in seconds after the L2 genesis.
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            default,
+            with = "alloy_serde::quantity::opt",
+            skip_serializing_if = "Option::is_none"
+        )
+    )]
+    pub l2_genesis_isthmus_time_offset: Option<u64>,
+    /// The activation of Jovian, in seconds after the L2 genesis.
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            default,
+            with = "alloy_serde::quantity::opt",
+            skip_serializing_if = "Option::is_none"
+        )
+    )]
+    pub l2_genesis_jovian_time_offset: Option<u64>,
+    /// The activation of Interop, in seconds after the L2 genesis.
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            default,
+            with = "alloy_serde::quantity::opt",
+            skip_serializing_if = "Option::is_none"
+        )
+    )]
+    pub l2_genesis_interop_time_offset: Option<u64>,
+}
+
+impl DeployConfig {
+    /// Returns the activation timestamps of the hardforks, for an L2 genesis at the given
+    /// timestamp.
+    pub fn hardforks(&self, l2_genesis_time: u64) -> HardForkConfig {
+        let time =
+            |offset: Option<u64>| offset.map(|offset| l2_genesis_time.saturating_add(offset));
+        HardForkConfig {
+            regolith_time: time(self.l2_genesis_regolith_time_offset),
+            canyon_time: time(self.l2_genesis_canyon_time_offset),
+            delta_time: time(self.l2_genesis_delta_time_offset),
+            ecotone_time: time(self.l2_genesis_ecotone_time_offset),
+            fjord_time: time(self.l2_genesis_fjord_time_offset),
+            granite_time: time(self.l2_genesis_granite_time_offset),
+            holocene_time: time(self.l2_genesis_holocene_time_offset),
+            pectra_blob_schedule_time: None,
+            isthmus_time: time(self.l2_genesis_isthmus_time_offset),
+            jovian_time: time(self.l2_genesis_jovian_time_offset),
+            interop_time: time(self.l2_genesis_interop_time_offset),
+        }
+    }
+
+    /// Returns the EIP-1559 [`BaseFeeConfig`] of the chain.
+    pub const fn base_fee_config(&self) -> BaseFeeConfig {
+        BaseFeeConfig {
+            eip1559_elasticity: self.eip1559_elasticity,
+            eip1559_denominator: self.eip1559_denominator,
+            eip1559_denominator_canyon: self.eip1559_denominator_canyon,
+        }
+    }
+
+    /// Returns the initial L1 fee scalar of the system config. If the legacy scalar is unset, the
+    /// Ecotone scalars are encoded as a version 1 scalar.
+    pub fn fee_scalar(&self) -> U256 {
+        if self.gas_price_oracle_scalar != 0 {
+            return U256::from(self.gas_price_oracle_scalar);
+        }
+
+        let mut scalar = [0u8; 32];
+        scalar[0] = 1;
+        scalar[24..28].copy_from_slice(&self.gas_price_oracle_blob_base_fee_scalar.to_be_bytes());
+        scalar[28..32].copy_from_slice(&self.gas_price_oracle_base_fee_scalar.to_be_bytes());
+        U256::from_be_bytes(scalar)
+    }
+}
+
+/// The addresses of the L1 contracts of a new chain, in the format of the L1 deployments output of
+/// op-deployer.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "PascalCase"))]
+pub struct L1Deployments {
+    /// The `OptimismPortal` proxy, which deposits are sent to.
+    pub optimism_portal_proxy: Address,
+    /// The `SystemConfig` proxy.
+    pub system_config_proxy: Address,
+    /// The `ProtocolVersions` proxy.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub protocol_versions_proxy: Address,
+    /// The `SuperchainConfig` proxy.
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub superchain_config_proxy: Option<Address>,
+}
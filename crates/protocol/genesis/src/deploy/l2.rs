@@ -0,0 +1,409 @@
+//! Contains the generation of the L2 genesis and rollup configuration from a [`DeployConfig`].
+
+use alloc::{
+    collections::BTreeMap,
+    string::{String, ToString},
+    vec::Vec,
+};
+use alloy_chains::Chain;
+use alloy_consensus::{
+    Header,
+    constants::{EMPTY_OMMER_ROOT_HASH, EMPTY_ROOT_HASH, KECCAK_EMPTY},
+};
+use alloy_eips::{BlockNumHash, eip7685::EMPTY_REQUESTS_HASH};
+use alloy_genesis::{ChainConfig as GenesisChainConfig, Genesis, GenesisAccount};
+use alloy_primitives::{Address, B256, Bytes, U256, keccak256};
+use alloy_trie::{
+    TrieAccount,
+    root::{state_root_unhashed, storage_root_unhashed},
+};
+
+use crate::{
+    ChainGenesis, DeployConfig, HardForkConfig, L1Deployments, Predeploys, RollupConfig,
+    SystemConfig,
+};
+
+/// The default gas limit of the L2 genesis block.
+const DEFAULT_GAS_LIMIT: u64 = 30_000_000;
+
+/// The default base fee of the L2 genesis block, 1 gwei.
+const DEFAULT_BASE_FEE: u128 = 1_000_000_000;
+
+/// The default extra data of the L2 genesis block.
+const DEFAULT_EXTRA_DATA: &[u8] = b"BEDROCK";
+
+/// An error generating the L2 genesis from a [`DeployConfig`].
+#[derive(Debug, thiserror::Error, Clone, PartialEq, Eq)]
+pub enum DeployConfigError {
+    /// The EIP-1559 parameters do not fit the Holocene extra data.
+    #[error("EIP-1559 parameters out of range: denominator {0}, elasticity {1}")]
+    Eip1559Params(u64, u64),
+    /// The chain configuration could not be serialized.
+    #[error("Failed to serialize the chain configuration: {0}")]
+    ChainConfig(String),
+}
+
+impl DeployConfig {
+    /// Generates the L2 genesis of the chain, starting from the given L1 block, with the given
+    /// allocations of the predeploys and prefunded accounts, such as the state dump of the
+    /// contracts-bedrock `L2Genesis` script.
+    ///
+    /// The L2 genesis block has the timestamp of the L1 starting block, unless overridden by
+    /// [`DeployConfig::l2_genesis_block_timestamp`].
+    pub fn l2_genesis(
+        &self,
+        l1_start: &Header,
+        alloc: BTreeMap<Address, GenesisAccount>,
+    ) -> Result<Genesis, DeployConfigError> {
+        let timestamp = self.l2_genesis_block_timestamp.unwrap_or(l1_start.timestamp);
+        let hardforks = self.hardforks(timestamp);
+        let rollup_config = RollupConfig { hardforks, ..Default::default() };
+
+        let extra_data = if rollup_config.is_holocene_active(timestamp) {
+            self.eip1559_extra_data(rollup_config.is_jovian_active(timestamp))?
+        } else {
+            self.l2_genesis_block_extra_data
+                .clone()
+                .unwrap_or_else(|| Bytes::from_static(DEFAULT_EXTRA_DATA))
+        };
+        let ecotone = rollup_config.is_ecotone_active(timestamp);
+
+        Ok(Genesis {
+            config: self.l2_chain_config(&hardforks)?,
+            timestamp,
+            extra_data,
+            gas_limit: self.l2_genesis_block_gas_limit.unwrap_or(DEFAULT_GAS_LIMIT),
+            difficulty: U256::ZERO,
+            coinbase: Predeploys::SEQUENCER_FEE_VAULT,
+            alloc,
+            base_fee_per_gas: Some(
+                self.l2_genesis_block_base_fee_per_gas
+                    .map_or(DEFAULT_BASE_FEE, |base_fee| base_fee.saturating_to()),
+            ),
+            excess_blob_gas: ecotone.then_some(0),
+            blob_gas_used: ecotone.then_some(0),
+            ..Default::default()
+        })
+    }
+
+    /// Generates the rollup configuration of the chain, for the given L1 starting block, L2
+    /// genesis and L1 contract deployments.
+    pub fn rollup_config(
+        &self,
+        l1_start: &Header,
+        l2_genesis: &Genesis,
+        deployments: &L1Deployments,
+    ) -> RollupConfig {
+        let hardforks = self.hardforks(l2_genesis.timestamp);
+        let l2_genesis_header = l2_genesis_header(l2_genesis, &hardforks);
+
+        RollupConfig {
+            genesis: ChainGenesis {
+                l1: BlockNumHash { number: l1_start.number, hash: l1_start.hash_slow() },
+                l2: BlockNumHash { number: 0, hash: l2_genesis_header.hash_slow() },
+                l2_time: l2_genesis.timestamp,
+                system_config: Some(SystemConfig {
+                    batcher_address: self.batch_sender_address,
+                    overhead: U256::from(self.gas_price_oracle_overhead),
+                    scalar: self.fee_scalar(),
+                    gas_limit: l2_genesis.gas_limit,
+                    ..Default::default()
+                }),
+            },
+            block_time: self.l2_block_time,
+            max_sequencer_drift: self.max_sequencer_drift,
+            seq_window_size: self.sequencer_window_size,
+            channel_timeout: self.channel_timeout,
+            l1_chain_id: self.l1_chain_id,
+            l2_chain_id: Chain::from_id(self.l2_chain_id),
+            hardforks,
+            batch_inbox_address: self.batch_inbox_address,
+            deposit_contract_address: deployments.optimism_portal_proxy,
+            l1_system_config_address: deployments.system_config_proxy,
+            protocol_versions_address: deployments.protocol_versions_proxy,
+            superchain_config_address: deployments.superchain_config_proxy,
+            chain_op_config: self.base_fee_config(),
+            ..Default::default()
+        }
+    }
+
+    /// Returns the chain configuration of the L2 execution layer, with the OP Stack hardforks and
+    /// EIP-1559 parameters in its extra fields, as read by op-geth and op-reth.
+    fn l2_chain_config(
+        &self,
+        hardforks: &HardForkConfig,
+    ) -> Result<GenesisChainConfig, DeployConfigError> {
+        let mut config = GenesisChainConfig {
+            chain_id: self.l2_chain_id,
+            homestead_block: Some(0),
+            eip150_block: Some(0),
+            eip155_block: Some(0),
+            eip158_block: Some(0),
+            byzantium_block: Some(0),
+            constantinople_block: Some(0),
+            petersburg_block: Some(0),
+            istanbul_block: Some(0),
+            muir_glacier_block: Some(0),
+            berlin_block: Some(0),
+            london_block: Some(0),
+            arrow_glacier_block: Some(0),
+            gray_glacier_block: Some(0),
+            merge_netsplit_block: Some(0),
+            shanghai_time: hardforks.canyon_time,
+            cancun_time: hardforks.ecotone_time,
+            prague_time: hardforks.isthmus_time,
+            terminal_total_difficulty: Some(U256::ZERO),
+            terminal_total_difficulty_passed: true,
+            ..Default::default()
+        };
+
+        let fields = [
+            ("bedrockBlock", Some(0)),
+            ("regolithTime", hardforks.regolith_time),
+            ("canyonTime", hardforks.canyon_time),
+            ("deltaTime", hardforks.delta_time),
+            ("ecotoneTime", hardforks.ecotone_time),
+            ("fjordTime", hardforks.fjord_time),
+            ("graniteTime", hardforks.granite_time),
+            ("holoceneTime", hardforks.holocene_time),
+            ("isthmusTime", hardforks.isthmus_time),
+            ("jovianTime", hardforks.jovian_time),
+            ("interopTime", hardforks.interop_time),
+        ];
+        for (key, value) in fields {
+            if let Some(value) = value {
+                config
+                    .extra_fields
+                    .insert_value(key.into(), value)
+                    .map_err(|e| DeployConfigError::ChainConfig(e.to_string()))?;
+            }
+        }
+        config
+            .extra_fields
+            .insert_value("optimism".into(), self.base_fee_config())
+            .map_err(|e| DeployConfigError::ChainConfig(e.to_string()))?;
+
+        Ok(config)
+    }
+
+    /// Returns the extra data of the L2 genesis block since Holocene, which holds the EIP-1559
+    /// parameters of the chain, and since Jovian the minimum base fee, which is zero at genesis.
+    fn eip1559_extra_data(&self, jovian: bool) -> Result<Bytes, DeployConfigError> {
+        let (Ok(denominator), Ok(elasticity)) = (
+            u32::try_from(self.eip1559_denominator_canyon),
+            u32::try_from(self.eip1559_elasticity),
+        ) else {
+            return Err(DeployConfigError::Eip1559Params(
+                self.eip1559_denominator_canyon,
+                self.eip1559_elasticity,
+            ));
+        };
+
+        let mut extra_data = Vec::with_capacity(17);
+        extra_data.push(u8::from(jovian));
+        extra_data.extend_from_slice(&denominator.to_be_bytes());
+        extra_data.extend_from_slice(&elasticity.to_be_bytes());
+        if jovian {
+            extra_data.extend_from_slice(&0u64.to_be_bytes());
+        }
+        Ok(extra_data.into())
+    }
+}
+
+/// Returns the header of the given L2 genesis block, with the fields of the hardforks active at
+/// genesis.
+pub fn l2_genesis_header(genesis: &Genesis, hardforks: &HardForkConfig) -> Header {
+    let rollup_config = RollupConfig { hardforks: *hardforks, ..Default::default() };
+    let timestamp = genesis.timestamp;
+
+    // The storage root of the `L2ToL1MessagePasser` is the withdrawals root since Isthmus.
+    let withdrawals_root = if rollup_config.is_isthmus_active(timestamp) {
+        Some(
+            genesis
+                .alloc
+                .get(&Predeploys::L2_TO_L1_MESSAGE_PASSER)
+                .map_or(EMPTY_ROOT_HASH, |account| trie_account(account).storage_root),
+        )
+    } else {
+        rollup_config.is_canyon_active(timestamp).then_some(EMPTY_ROOT_HASH)
+    };
+    let ecotone = rollup_config.is_ecotone_active(timestamp);
+
+    Header {
+        parent_hash: genesis.parent_hash.unwrap_or_default(),
+        ommers_hash: EMPTY_OMMER_ROOT_HASH,
+        beneficiary: genesis.coinbase,
+        state_root: state_root_unhashed(
+            genesis.alloc.iter().map(|(address, account)| (*address, trie_account(account))),
+        ),
+        transactions_root: EMPTY_ROOT_HASH,
+        receipts_root: EMPTY_ROOT_HASH,
+        difficulty: genesis.difficulty,
+        number: genesis.number.unwrap_or_default(),
+        gas_limit: genesis.gas_limit,
+        gas_used: genesis.gas_used.unwrap_or_default(),
+        timestamp,
+        extra_data: genesis.extra_data.clone(),
+        mix_hash: genesis.mix_hash,
+        nonce: genesis.nonce.into(),
+        base_fee_per_gas: genesis.base_fee_per_gas.map(|base_fee| base_fee as u64),
+        withdrawals_root,
+        blob_gas_used: ecotone.then_some(genesis.blob_gas_used.unwrap_or_default()),
+        excess_blob_gas: ecotone.then_some(genesis.excess_blob_gas.unwrap_or_default()),
+        parent_beacon_block_root: ecotone.then_some(B256::ZERO),
+        requests_hash: rollup_config.is_isthmus_active(timestamp).then_some(EMPTY_REQUESTS_HASH),
+        ..Default::default()
+    }
+}
+
+/// Returns the [`TrieAccount`] of the given genesis account.
+fn trie_account(account: &GenesisAccount) -> TrieAccount {
+    let storage = account.storage.iter().flatten().filter(|(_, value)| !value.is_zero());
+    TrieAccount {
+        nonce: account.nonce.unwrap_or_default(),
+        balance: account.balance,
+        storage_root: storage_root_unhashed(
+            storage.map(|(slot, value)| (*slot, U256::from_be_bytes(value.0))),
+        ),
+        code_hash: account.code.as_ref().map_or(KECCAK_EMPTY, keccak256),
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "serde")]
+mod tests {
+    use super::*;
+
+    const DEPLOY_CONFIG: &str = r#"{
+        "l1ChainID": 900,
+        "l2ChainID": 901,
+        "l2BlockTime": 2,
+        "maxSequencerDrift": 600,
+        "sequencerWindowSize": 3600,
+        "channelTimeout": 300,
+        "p2pSequencerAddress": "0x9965507d1a55bcc2695c58ba16fb37d819b0a4dc",
+        "batchInboxAddress": "0xff00000000000000000000000000000000000901",
+        "batchSenderAddress": "0x3c44cdddb6a900fa2b585dd299e03d12fa4293bc",
+        "l2GenesisBlockGasLimit": "0x2faf080",
+        "l2GenesisBlockBaseFeePerGas": "0x3b9aca00",
+        "gasPriceOracleBaseFeeScalar": 1368,
+        "gasPriceOracleBlobBaseFeeScalar": 810949,
+        "eip1559Denominator": 50,
+        "eip1559DenominatorCanyon": 250,
+        "eip1559Elasticity": 6,
+        "l2GenesisRegolithTimeOffset": "0x0",
+        "l2GenesisCanyonTimeOffset": "0x0",
+        "l2GenesisDeltaTimeOffset": "0x0",
+        "l2GenesisEcotoneTimeOffset": "0x0",
+        "l2GenesisFjordTimeOffset": "0x0",
+        "l2GenesisGraniteTimeOffset": "0x0",
+        "l2GenesisHoloceneTimeOffset": "0x0",
+        "l2GenesisIsthmusTimeOffset": "0x3c"
+    }"#;
+
+    fn l1_start() -> Header {
+        Header { number: 100, timestamp: 1_700_000_000, ..Default::default() }
+    }
+
+    fn alloc() -> BTreeMap<Address, GenesisAccount> {
+        let storage = BTreeMap::from([(B256::ZERO, B256::with_last_byte(1))]);
+        BTreeMap::from([
+            (
+                Predeploys::L2_TO_L1_MESSAGE_PASSER,
+                GenesisAccount::default()
+                    .with_code(Some(Bytes::from_static(&[0x60, 0x00])))
+                    .with_storage(Some(storage)),
+            ),
+            (Address::repeat_byte(1), GenesisAccount::default().with_balance(U256::from(10))),
+        ])
+    }
+
+    #[test]
+    fn test_deploy_config_serde() {
+        let config: DeployConfig = serde_json::from_str(DEPLOY_CONFIG).unwrap();
+        assert_eq!(config.l2_chain_id, 901);
+        assert_eq!(config.l2_genesis_block_gas_limit, Some(50_000_000));
+        assert_eq!(config.l2_genesis_block_base_fee_per_gas, Some(U256::from(1_000_000_000)));
+        assert_eq!(config.l2_genesis_isthmus_time_offset, Some(60));
+        assert_eq!(config.l2_genesis_jovian_time_offset, None);
+        assert_eq!(config.hardforks(1000).isthmus_time, Some(1060));
+    }
+
+    #[test]
+    fn test_l2_genesis() {
+        let config: DeployConfig = serde_json::from_str(DEPLOY_CONFIG).unwrap();
+        let genesis = config.l2_genesis(&l1_start(), alloc()).unwrap();
+
+        assert_eq!(genesis.timestamp, l1_start().timestamp);
+        assert_eq!(genesis.gas_limit, 50_000_000);
+        assert_eq!(genesis.coinbase, Predeploys::SEQUENCER_FEE_VAULT);
+        assert_eq!(genesis.config.chain_id, 901);
+        assert_eq!(genesis.config.prague_time, Some(l1_start().timestamp + 60));
+        assert_eq!(
+            genesis.config.extra_fields.get("ecotoneTime"),
+            Some(&serde_json::json!(l1_start().timestamp))
+        );
+        assert_eq!(genesis.config.extra_fields.get("jovianTime"), None);
+        // Holocene is active at genesis, so the extra data holds the EIP-1559 parameters.
+        assert_eq!(genesis.extra_data.as_ref(), &[0, 0, 0, 0, 250, 0, 0, 0, 6]);
+
+        let header = l2_genesis_header(&genesis, &config.hardforks(genesis.timestamp));
+        assert_eq!(header.withdrawals_root, Some(EMPTY_ROOT_HASH));
+        assert_eq!(header.parent_beacon_block_root, Some(B256::ZERO));
+        assert_eq!(header.requests_hash, None);
+        assert_ne!(header.state_root, EMPTY_ROOT_HASH);
+    }
+
+    #[test]
+    fn test_l2_genesis_timestamp_override() {
+        let mut config: DeployConfig = serde_json::from_str(DEPLOY_CONFIG).unwrap();
+        config.l2_genesis_block_timestamp = Some(l1_start().timestamp + 100);
+        let genesis = config.l2_genesis(&l1_start(), alloc()).unwrap();
+
+        assert_eq!(genesis.timestamp, l1_start().timestamp + 100);
+        assert_eq!(genesis.config.prague_time, Some(l1_start().timestamp + 160));
+
+        let rollup_config = config.rollup_config(&l1_start(), &genesis, &L1Deployments::default());
+        assert_eq!(rollup_config.genesis.l2_time, l1_start().timestamp + 100);
+        assert_eq!(rollup_config.hardforks.isthmus_time, Some(l1_start().timestamp + 160));
+    }
+
+    #[test]
+    fn test_l2_genesis_header_isthmus() {
+        let mut config: DeployConfig = serde_json::from_str(DEPLOY_CONFIG).unwrap();
+        config.l2_genesis_isthmus_time_offset = Some(0);
+        let genesis = config.l2_genesis(&l1_start(), alloc()).unwrap();
+
+        let header = l2_genesis_header(&genesis, &config.hardforks(genesis.timestamp));
+        let message_passer = trie_account(&genesis.alloc[&Predeploys::L2_TO_L1_MESSAGE_PASSER]);
+        assert_eq!(header.withdrawals_root, Some(message_passer.storage_root));
+        assert_ne!(message_passer.storage_root, EMPTY_ROOT_HASH);
+        assert_eq!(header.requests_hash, Some(EMPTY_REQUESTS_HASH));
+    }
+
+    #[test]
+    fn test_rollup_config() {
+        let config: DeployConfig = serde_json::from_str(DEPLOY_CONFIG).unwrap();
+        let genesis = config.l2_genesis(&l1_start(), alloc()).unwrap();
+        let deployments = L1Deployments {
+            optimism_portal_proxy: Address::repeat_byte(2),
+            system_config_proxy: Address::repeat_byte(3),
+            ..Default::default()
+        };
+
+        let rollup_config = config.rollup_config(&l1_start(), &genesis, &deployments);
+        let header = l2_genesis_header(&genesis, &rollup_config.hardforks);
+        assert_eq!(rollup_config.genesis.l1.hash, l1_start().hash_slow());
+        assert_eq!(rollup_config.genesis.l2.hash, header.hash_slow());
+        assert_eq!(rollup_config.genesis.l2_time, genesis.timestamp);
+        assert_eq!(rollup_config.deposit_contract_address, deployments.optimism_portal_proxy);
+        assert_eq!(rollup_config.chain_op_config.eip1559_denominator_canyon, 250);
+
+        let system_config = rollup_config.genesis.system_config.unwrap();
+        assert_eq!(system_config.gas_limit, 50_000_000);
+        let scalar = system_config.scalar.to_be_bytes::<32>();
+        assert_eq!(scalar[0], 1);
+        assert_eq!(scalar[24..28], 810949u32.to_be_bytes());
+        assert_eq!(scalar[28..32], 1368u32.to_be_bytes());
+    }
+}
@@ -0,0 +1,8 @@
+//! Module containing the generation of the L2 genesis and rollup configuration of a new chain from
+//! its deploy configuration.
+
+mod config;
+pub use config::{DeployConfig, L1Deployments};
+
+mod l2;
+pub use l2::{DeployConfigError, l2_genesis_header};
@@ -44,9 +44,9 @@ mod system;
 pub use system::{
     BatcherUpdateError, CONFIG_UPDATE_EVENT_VERSION_0, CONFIG_UPDATE_TOPIC,
     DaFootprintGasScalarUpdateError, EIP1559UpdateError, GasConfigUpdateError, GasLimitUpdateError,
-    LogProcessingError, MinBaseFeeUpdateError, OperatorFeeUpdateError, SystemConfig,
-    SystemConfigLog, SystemConfigUpdate, SystemConfigUpdateError, SystemConfigUpdateKind,
-    UnsafeBlockSignerUpdateError,
+    L1StorageProvider, LogProcessingError, MinBaseFeeUpdateError, OperatorFeeUpdateError,
+    SystemConfig, SystemConfigLog, SystemConfigSlots, SystemConfigUpdate, SystemConfigUpdateError,
+    SystemConfigUpdateKind, UnsafeBlockSignerUpdateError,
 };
 
 mod chain;
@@ -58,6 +58,12 @@ pub use chain::{
 mod genesis;
 pub use genesis::ChainGenesis;
 
+mod predeploys;
+pub use predeploys::Predeploys;
+
+mod deploy;
+pub use deploy::{DeployConfig, DeployConfigError, L1Deployments, l2_genesis_header};
+
 mod rollup;
 pub use rollup::{
     DEFAULT_INTEROP_MESSAGE_EXPIRY_WINDOW, FJORD_MAX_SEQUENCER_DRIFT, GRANITE_CHANNEL_TIMEOUT,
@@ -31,6 +31,16 @@ const fn default_interop_message_expiry_window() -> u64 {
     DEFAULT_INTEROP_MESSAGE_EXPIRY_WINDOW
 }
 
+/// Serializes a [`Chain`] as its numeric id, as op-node does, rather than as the name of a known
+/// chain.
+#[cfg(feature = "serde")]
+fn serialize_chain_id<S: serde::Serializer>(
+    chain: &Chain,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.serialize_u64(chain.id())
+}
+
 /// The Rollup configuration.
 #[derive(Debug, Clone, Eq, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -58,6 +68,7 @@ pub struct RollupConfig {
     /// The L1 chain ID
     pub l1_chain_id: u64,
     /// The L2 chain ID
+    #[cfg_attr(feature = "serde", serde(serialize_with = "serialize_chain_id"))]
     pub l2_chain_id: Chain,
     /// Hardfork timestamps.
     #[cfg_attr(feature = "serde", serde(flatten))]
@@ -88,8 +99,16 @@ pub struct RollupConfig {
     /// can be referenced on a remote chain before it expires.
     #[cfg_attr(feature = "serde", serde(default = "default_interop_message_expiry_window"))]
     pub interop_message_expiry_window: u64,
+    /// `message_passer_address` overrides the L2 address of the `L2ToL1MessagePasser` predeploy,
+    /// whose storage root is committed to in output roots. Only set for OP Stack chains that
+    /// deploy the message passer outside of the standard predeploy address.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub message_passer_address: Option<Address>,
     /// `alt_da_config` is the chain-specific DA config for the rollup.
-    #[cfg_attr(feature = "serde", serde(rename = "alt_da"))]
+    #[cfg_attr(
+        feature = "serde",
+        serde(rename = "alt_da", skip_serializing_if = "Option::is_none")
+    )]
     pub alt_da_config: Option<AltDAConfig>,
     /// `chain_op_config` is the chain-specific EIP1559 config for the rollup.
     #[cfg_attr(feature = "serde", serde(default = "BaseFeeConfig::optimism"))]
@@ -126,6 +145,7 @@ impl<'a> arbitrary::Arbitrary<'a> for RollupConfig {
             blobs_enabled_l1_timestamp: Option::<u64>::arbitrary(u)?,
             da_challenge_address: Option::<Address>::arbitrary(u)?,
             interop_message_expiry_window: u.arbitrary()?,
+            message_passer_address: Option::<Address>::arbitrary(u)?,
             chain_op_config,
             alt_da_config: Option::<AltDAConfig>::arbitrary(u)?,
         })
@@ -153,6 +173,7 @@ impl Default for RollupConfig {
             blobs_enabled_l1_timestamp: None,
             da_challenge_address: None,
             interop_message_expiry_window: DEFAULT_INTEROP_MESSAGE_EXPIRY_WINDOW,
+            message_passer_address: None,
             alt_da_config: None,
             chain_op_config: OP_MAINNET_BASE_FEE_CONFIG,
         }
@@ -879,6 +900,7 @@ mod tests {
             blobs_enabled_l1_timestamp: None,
             da_challenge_address: None,
             interop_message_expiry_window: DEFAULT_INTEROP_MESSAGE_EXPIRY_WINDOW,
+            message_passer_address: None,
             chain_op_config: OP_MAINNET_BASE_FEE_CONFIG,
             alt_da_config: None,
         };
@@ -887,6 +909,55 @@ mod tests {
         assert_eq!(deserialized, expected);
     }
 
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_serialize_rollup_config_op_node_format() {
+        let raw: &str = r#"
+        {
+          "genesis": {
+            "l1": {
+              "hash": "0x438335a20d98863a4c0c97999eb2481921ccd28553eac6f913af7c12aec04108",
+              "number": 17422590
+            },
+            "l2": {
+              "hash": "0xdbf6a80fef073de06add9b0d14026d6e5a86c85f6d102c36d3d8e9cf89c2afd3",
+              "number": 105235063
+            },
+            "l2_time": 1686068903,
+            "system_config": {
+              "batcherAddr": "0x6887246668a3b87f54deb3b94ba47a6f63f32985",
+              "overhead": "0x00000000000000000000000000000000000000000000000000000000000000bc",
+              "scalar": "0x00000000000000000000000000000000000000000000000000000000000a6fe0",
+              "gasLimit": 30000000
+            }
+          },
+          "block_time": 2,
+          "max_sequencer_drift": 600,
+          "seq_window_size": 3600,
+          "channel_timeout": 300,
+          "granite_channel_timeout": 50,
+          "l1_chain_id": 1,
+          "l2_chain_id": 10,
+          "regolith_time": 0,
+          "canyon_time": 1704992401,
+          "batch_inbox_address": "0xff00000000000000000000000000000000000010",
+          "deposit_contract_address": "0xbeb5fc579115071764c7423a4f12edde41f106ed",
+          "l1_system_config_address": "0x229047fed2591dbec1ef1118d64f7af3db9eb290",
+          "protocol_versions_address": "0x8062abc286f5e7d9428a0ccb9abd71e50d93b935",
+          "interop_message_expiry_window": 3600,
+          "chain_op_config": {
+            "eip1559Elasticity": 6,
+            "eip1559Denominator": 50,
+            "eip1559DenominatorCanyon": 250
+          }
+        }
+        "#;
+
+        let config: RollupConfig = serde_json::from_str(raw).unwrap();
+        let serialized = serde_json::to_value(&config).unwrap();
+        assert_eq!(serialized, serde_json::from_str::<serde_json::Value>(raw).unwrap());
+    }
+
     #[test]
     fn test_rollup_config_unknown_field() {
         let raw: &str = r#"
@@ -1,8 +1,8 @@
 //! Contains the [`SystemConfig`] type.
 
 use crate::{
-    CONFIG_UPDATE_TOPIC, RollupConfig, SystemConfigLog, SystemConfigUpdateError,
-    SystemConfigUpdateKind,
+    CONFIG_UPDATE_TOPIC, DaFootprintGasScalarUpdate, RollupConfig, SystemConfigLog,
+    SystemConfigUpdateError, SystemConfigUpdateKind,
 };
 use alloy_consensus::{Eip658Value, Receipt};
 use alloy_primitives::{Address, B64, Log, U256};
@@ -116,6 +116,24 @@ impl<'a> serde::Deserialize<'a> for SystemConfig {
 }
 
 impl SystemConfig {
+    /// Returns the min base fee, defaulting to `0` while it is unset (Jovian hardfork).
+    pub const fn min_base_fee_or_default(&self) -> u64 {
+        match self.min_base_fee {
+            Some(min_base_fee) => min_base_fee,
+            None => 0,
+        }
+    }
+
+    /// Returns the DA footprint gas scalar, defaulting to
+    /// [`DaFootprintGasScalarUpdate::DEFAULT_DA_FOOTPRINT_GAS_SCALAR`] while it is unset or zero
+    /// (Jovian hardfork).
+    pub const fn da_footprint_gas_scalar_or_default(&self) -> u16 {
+        match self.da_footprint_gas_scalar {
+            Some(scalar) if scalar != 0 => scalar,
+            _ => DaFootprintGasScalarUpdate::DEFAULT_DA_FOOTPRINT_GAS_SCALAR,
+        }
+    }
+
     /// Filters all L1 receipts to find config updates and applies the config updates.
     ///
     /// Returns `true` if any config updates were applied, `false` otherwise.
@@ -238,6 +256,41 @@ mod test {
         assert_eq!(system_config.da_footprint_gas_scalar, Some(10), "da_footprint_gas_scalar");
     }
 
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_system_config_pre_holocene_backcompat() {
+        let raw = r#"{
+          "batcherAddress": "0x6887246668a3b87F54DeB3b94Ba47a6f63F32985",
+          "overhead": "0x00000000000000000000000000000000000000000000000000000000000000bc",
+          "scalar": "0x00000000000000000000000000000000000000000000000000000000000a6fe0",
+          "gasLimit": 30000000
+        }"#;
+        let system_config: SystemConfig = serde_json::from_str(raw).unwrap();
+        assert_eq!(system_config.eip1559_denominator, None);
+        assert_eq!(system_config.eip1559_elasticity, None);
+        assert_eq!(system_config.min_base_fee, None);
+        assert_eq!(system_config.da_footprint_gas_scalar, None);
+
+        assert_eq!(system_config.min_base_fee_or_default(), 0);
+        assert_eq!(
+            system_config.da_footprint_gas_scalar_or_default(),
+            DaFootprintGasScalarUpdate::DEFAULT_DA_FOOTPRINT_GAS_SCALAR
+        );
+    }
+
+    #[test]
+    fn test_system_config_da_footprint_gas_scalar_or_default() {
+        let mut system_config =
+            SystemConfig { da_footprint_gas_scalar: Some(0), ..Default::default() };
+        assert_eq!(
+            system_config.da_footprint_gas_scalar_or_default(),
+            DaFootprintGasScalarUpdate::DEFAULT_DA_FOOTPRINT_GAS_SCALAR
+        );
+
+        system_config.da_footprint_gas_scalar = Some(10);
+        assert_eq!(system_config.da_footprint_gas_scalar_or_default(), 10);
+    }
+
     #[test]
     #[cfg(feature = "serde")]
     fn test_system_config_eip1559_params() {
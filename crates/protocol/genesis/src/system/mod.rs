@@ -12,6 +12,9 @@ pub const CONFIG_UPDATE_EVENT_VERSION_0: B256 = B256::ZERO;
 mod config;
 pub use config::SystemConfig;
 
+mod storage;
+pub use storage::{L1StorageProvider, SystemConfigSlots};
+
 mod log;
 pub use log::SystemConfigLog;
 
@@ -0,0 +1,220 @@
+//! Reconstruction of the [`SystemConfig`] from the storage of the L1 `SystemConfig` contract.
+
+use crate::SystemConfig;
+use alloc::boxed::Box;
+use alloy_primitives::{Address, B256, U256};
+use async_trait::async_trait;
+
+/// A provider of the storage of L1 contracts at a given L1 block.
+#[async_trait]
+pub trait L1StorageProvider {
+    /// The error type of the provider.
+    type Error;
+
+    /// Returns the value of the given storage slot of the given account at the L1 block with the
+    /// given hash, which is zero if the slot or the account does not exist.
+    async fn storage_at(
+        &mut self,
+        address: Address,
+        slot: U256,
+        block_hash: B256,
+    ) -> Result<U256, Self::Error>;
+}
+
+/// The storage slots of the fields of the L1 `SystemConfig` contract.
+///
+/// Several fields are packed into a single slot; the byte offsets of the packed fields are given
+/// from the low-order end of the slot, as in the Solidity storage layout.
+/// <https://github.com/ethereum-optimism/optimism/blob/develop/packages/contracts-bedrock/snapshots/storageLayout/SystemConfig.json>
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct SystemConfigSlots;
+
+impl SystemConfigSlots {
+    /// The slot of the `overhead` (`uint256`).
+    pub const OVERHEAD: U256 = U256::from_limbs([101, 0, 0, 0]);
+
+    /// The slot of the `scalar` (`uint256`).
+    pub const SCALAR: U256 = U256::from_limbs([102, 0, 0, 0]);
+
+    /// The slot of the `batcherHash` (`bytes32`).
+    pub const BATCHER_HASH: U256 = U256::from_limbs([103, 0, 0, 0]);
+
+    /// The slot packing the `gasLimit` (`uint64`, offset 0), and the `basefeeScalar` (`uint32`,
+    /// offset 8) and `blobbasefeeScalar` (`uint32`, offset 12) since Ecotone.
+    pub const GAS_LIMIT_FEE_SCALARS: U256 = U256::from_limbs([104, 0, 0, 0]);
+
+    /// The slot packing the `eip1559Denominator` (`uint32`, offset 0) and `eip1559Elasticity`
+    /// (`uint32`, offset 4) since Holocene, and the `operatorFeeScalar` (`uint32`, offset 8) and
+    /// `operatorFeeConstant` (`uint64`, offset 12) since Isthmus.
+    pub const EIP1559_OPERATOR_FEE_PARAMS: U256 = U256::from_limbs([106, 0, 0, 0]);
+
+    /// The slot packing the `minBaseFee` (`uint64`, offset 20) and `daFootprintGasScalar`
+    /// (`uint16`, offset 28), since Jovian.
+    pub const MIN_BASE_FEE_DA_FOOTPRINT: U256 = U256::from_limbs([108, 0, 0, 0]);
+}
+
+impl SystemConfig {
+    /// Reconstructs the [`SystemConfig`] from the storage of the L1 `SystemConfig` contract at the
+    /// given address, at the L1 block with the given hash.
+    ///
+    /// The fields introduced by hardforks are left unset while they are zero in storage, as they
+    /// are for contracts that were not upgraded yet. A zero value of these fields selects the same
+    /// defaults as an unset one, so the result can be compared against the system config of the
+    /// rollup config, or used to initialize the derivation pipeline.
+    pub async fn from_l1_storage<P: L1StorageProvider + Send>(
+        address: Address,
+        block_hash: B256,
+        provider: &mut P,
+    ) -> Result<Self, P::Error> {
+        let overhead =
+            provider.storage_at(address, SystemConfigSlots::OVERHEAD, block_hash).await?;
+        let scalar = provider.storage_at(address, SystemConfigSlots::SCALAR, block_hash).await?;
+        let batcher_hash =
+            provider.storage_at(address, SystemConfigSlots::BATCHER_HASH, block_hash).await?;
+        let gas_config = provider
+            .storage_at(address, SystemConfigSlots::GAS_LIMIT_FEE_SCALARS, block_hash)
+            .await?;
+        let fee_params = provider
+            .storage_at(address, SystemConfigSlots::EIP1559_OPERATOR_FEE_PARAMS, block_hash)
+            .await?;
+        let jovian_params = provider
+            .storage_at(address, SystemConfigSlots::MIN_BASE_FEE_DA_FOOTPRINT, block_hash)
+            .await?;
+
+        let non_zero_u32 = |value: u32| (value != 0).then_some(value);
+        let non_zero_u64 = |value: u64| (value != 0).then_some(value);
+        Ok(Self {
+            batcher_address: Address::from(packed::<20>(batcher_hash, 0)),
+            overhead,
+            scalar,
+            gas_limit: u64::from_be_bytes(packed(gas_config, 0)),
+            base_fee_scalar: non_zero_u32(u32::from_be_bytes(packed(gas_config, 8)))
+                .map(Into::into),
+            blob_base_fee_scalar: non_zero_u32(u32::from_be_bytes(packed(gas_config, 12)))
+                .map(Into::into),
+            eip1559_denominator: non_zero_u32(u32::from_be_bytes(packed(fee_params, 0))),
+            eip1559_elasticity: non_zero_u32(u32::from_be_bytes(packed(fee_params, 4))),
+            operator_fee_scalar: non_zero_u32(u32::from_be_bytes(packed(fee_params, 8))),
+            operator_fee_constant: non_zero_u64(u64::from_be_bytes(packed(fee_params, 12))),
+            min_base_fee: non_zero_u64(u64::from_be_bytes(packed(jovian_params, 20))),
+            da_footprint_gas_scalar: Some(u16::from_be_bytes(packed(jovian_params, 28)))
+                .filter(|scalar| *scalar != 0),
+        })
+    }
+}
+
+/// Returns the `N` bytes of the field packed at the given byte offset of a slot value, the offset
+/// being counted from the low-order end of the slot as in the Solidity storage layout.
+fn packed<const N: usize>(value: U256, offset: usize) -> [u8; N] {
+    let bytes = value.to_be_bytes::<32>();
+    let end = 32 - offset;
+    let mut field = [0u8; N];
+    field.copy_from_slice(&bytes[end - N..end]);
+    field
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{address, b256, map::HashMap};
+    use core::convert::Infallible;
+
+    const SYSTEM_CONFIG: Address = address!("229047fed2591dbec1eF1118d64F7aF3dB9EB290");
+    const BLOCK_HASH: B256 =
+        b256!("0x1111111111111111111111111111111111111111111111111111111111111111");
+
+    /// The storage of the L1 `SystemConfig` contract at [`BLOCK_HASH`].
+    #[derive(Default)]
+    struct MockStorage(HashMap<U256, U256>);
+
+    #[async_trait]
+    impl L1StorageProvider for MockStorage {
+        type Error = Infallible;
+
+        async fn storage_at(
+            &mut self,
+            address: Address,
+            slot: U256,
+            block_hash: B256,
+        ) -> Result<U256, Self::Error> {
+            assert_eq!(address, SYSTEM_CONFIG);
+            assert_eq!(block_hash, BLOCK_HASH);
+            Ok(self.0.get(&slot).copied().unwrap_or_default())
+        }
+    }
+
+    /// Returns the slot value packing the given fields, each at its byte offset from the low-order
+    /// end of the slot.
+    fn pack(fields: &[(&[u8], usize)]) -> U256 {
+        let mut bytes = [0u8; 32];
+        for (field, offset) in fields {
+            let end = 32 - offset;
+            bytes[end - field.len()..end].copy_from_slice(field);
+        }
+        U256::from_be_bytes(bytes)
+    }
+
+    #[tokio::test]
+    async fn test_from_l1_storage() {
+        let batcher = address!("6887246668a3b87F54DeB3b94Ba47a6f63F32985");
+        let mut storage = MockStorage(HashMap::from_iter([
+            (SystemConfigSlots::OVERHEAD, U256::from(188)),
+            (SystemConfigSlots::SCALAR, U256::from(684_000)),
+            (SystemConfigSlots::BATCHER_HASH, U256::from_be_slice(batcher.as_slice())),
+            (
+                SystemConfigSlots::GAS_LIMIT_FEE_SCALARS,
+                pack(&[
+                    (&30_000_000u64.to_be_bytes(), 0),
+                    (&1368u32.to_be_bytes(), 8),
+                    (&810_949u32.to_be_bytes(), 12),
+                ]),
+            ),
+            (
+                SystemConfigSlots::EIP1559_OPERATOR_FEE_PARAMS,
+                pack(&[
+                    (&250u32.to_be_bytes(), 0),
+                    (&6u32.to_be_bytes(), 4),
+                    (&7u32.to_be_bytes(), 8),
+                    (&8u64.to_be_bytes(), 12),
+                ]),
+            ),
+            (
+                SystemConfigSlots::MIN_BASE_FEE_DA_FOOTPRINT,
+                pack(&[(&[0xAA; 20], 0), (&9u64.to_be_bytes(), 20), (&10u16.to_be_bytes(), 28)]),
+            ),
+        ]));
+
+        let config =
+            SystemConfig::from_l1_storage(SYSTEM_CONFIG, BLOCK_HASH, &mut storage).await.unwrap();
+        assert_eq!(
+            config,
+            SystemConfig {
+                batcher_address: batcher,
+                overhead: U256::from(188),
+                scalar: U256::from(684_000),
+                gas_limit: 30_000_000,
+                base_fee_scalar: Some(1368),
+                blob_base_fee_scalar: Some(810_949),
+                eip1559_denominator: Some(250),
+                eip1559_elasticity: Some(6),
+                operator_fee_scalar: Some(7),
+                operator_fee_constant: Some(8),
+                min_base_fee: Some(9),
+                da_footprint_gas_scalar: Some(10),
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_from_l1_storage_unset_fields() {
+        let mut storage = MockStorage(HashMap::from_iter([(
+            SystemConfigSlots::GAS_LIMIT_FEE_SCALARS,
+            U256::from(30_000_000),
+        )]));
+
+        let config =
+            SystemConfig::from_l1_storage(SYSTEM_CONFIG, BLOCK_HASH, &mut storage).await.unwrap();
+        assert_eq!(config, SystemConfig { gas_limit: 30_000_000, ..Default::default() });
+    }
+}
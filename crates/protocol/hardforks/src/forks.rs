@@ -1,6 +1,9 @@
 //! Contains all hardforks represented in the [crate::Hardfork] type.
 
-use crate::{Ecotone, Fjord, Interop, Isthmus, Jovian};
+use crate::{Ecotone, Fjord, Hardfork, Interop, Isthmus, Jovian};
+use alloc::vec::Vec;
+use alloy_primitives::Bytes;
+use kona_genesis::RollupConfig;
 
 /// Optimism Hardforks
 ///
@@ -56,13 +59,46 @@ impl Hardforks {
 
     /// The Interop hardfork upgrade transactions.
     pub const INTEROP: Interop = Interop;
+
+    /// Returns the network upgrade transactions to include in the L2 block at `next_timestamp`,
+    /// given the timestamp of its parent block.
+    ///
+    /// A hardfork's upgrade transactions are only included in its activation block, and multiple
+    /// hardforks activating in the same block have their transactions included in activation
+    /// order. The Granite and Holocene hardforks do not have any upgrade transactions.
+    pub fn upgrade_txs(
+        rollup_config: &RollupConfig,
+        parent_timestamp: u64,
+        next_timestamp: u64,
+    ) -> Vec<Bytes> {
+        let activates = |is_active: fn(&RollupConfig, u64) -> bool| {
+            is_active(rollup_config, next_timestamp) && !is_active(rollup_config, parent_timestamp)
+        };
+
+        let mut txs = Vec::new();
+        if activates(RollupConfig::is_ecotone_active) {
+            txs.extend(Self::ECOTONE.txs());
+        }
+        if activates(RollupConfig::is_fjord_active) {
+            txs.extend(Self::FJORD.txs());
+        }
+        if activates(RollupConfig::is_isthmus_active) {
+            txs.extend(Self::ISTHMUS.txs());
+        }
+        if activates(RollupConfig::is_jovian_active) {
+            txs.extend(Self::JOVIAN.txs());
+        }
+        if activates(RollupConfig::is_interop_active) {
+            txs.extend(Self::INTEROP.txs());
+        }
+        txs
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::Hardfork;
-    use alloc::vec::Vec;
+    use kona_genesis::HardForkConfig;
 
     #[test]
     fn test_hardforks() {
@@ -81,4 +117,29 @@ mod tests {
         let interop_upgrade_tx = Hardforks::INTEROP.txs();
         assert_eq!(interop_upgrade_tx.collect::<Vec<_>>().len(), 4);
     }
+
+    #[test]
+    fn test_upgrade_txs() {
+        let rollup_config = RollupConfig {
+            hardforks: HardForkConfig {
+                ecotone_time: Some(2),
+                fjord_time: Some(2),
+                granite_time: Some(4),
+                holocene_time: Some(4),
+                isthmus_time: Some(6),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let expected = Hardforks::ECOTONE.txs().chain(Hardforks::FJORD.txs()).collect::<Vec<_>>();
+        assert_eq!(Hardforks::upgrade_txs(&rollup_config, 0, 2), expected);
+
+        // Already active, or no upgrade transactions.
+        assert!(Hardforks::upgrade_txs(&rollup_config, 2, 4).is_empty());
+
+        let expected = Hardforks::ISTHMUS.txs().collect::<Vec<_>>();
+        assert_eq!(Hardforks::upgrade_txs(&rollup_config, 4, 6), expected);
+        assert!(Hardforks::upgrade_txs(&rollup_config, 6, 8).is_empty());
+    }
 }
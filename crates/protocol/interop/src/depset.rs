@@ -1,12 +1,24 @@
-use crate::MESSAGE_EXPIRY_WINDOW;
+use crate::{DependencySetError, MESSAGE_EXPIRY_WINDOW};
+#[cfg(feature = "json")]
+use alloc::string::ToString;
+use alloc::vec::Vec;
 use alloy_primitives::ChainId;
 use kona_registry::HashMap;
 
 /// Configuration for a dependency of a chain
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
-pub struct ChainDependency {}
+pub struct ChainDependency {
+    /// The index of the chain in the dependency set, if configured.
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub chain_index: Option<u32>,
+
+    /// The timestamp at which the chain joins the dependency set. If set, it must match the
+    /// interop activation time of the rollup config of the chain.
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub activation_time: Option<u64>,
+}
 
 /// Configuration for the dependency set
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -14,6 +26,7 @@ pub struct ChainDependency {}
 #[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
 pub struct DependencySet {
     /// Dependencies information per chain.
+    #[cfg_attr(feature = "serde", serde(deserialize_with = "deserialize_dependencies"))]
     pub dependencies: HashMap<ChainId, ChainDependency>,
 
     /// Override message expiry window to use for this dependency set.
@@ -28,11 +41,138 @@ impl DependencySet {
             _ => MESSAGE_EXPIRY_WINDOW,
         }
     }
+
+    /// Returns whether the chain is part of the dependency set.
+    pub fn has_chain(&self, chain_id: ChainId) -> bool {
+        self.dependencies.contains_key(&chain_id)
+    }
+
+    /// Returns the IDs of the chains in the dependency set, in ascending order.
+    pub fn chain_ids(&self) -> Vec<ChainId> {
+        let mut chain_ids = self.dependencies.keys().copied().collect::<Vec<_>>();
+        chain_ids.sort_unstable();
+        chain_ids
+    }
+
+    /// Returns the timestamp at which the chain joins the dependency set, if configured.
+    pub fn activation_time(&self, chain_id: ChainId) -> Option<u64> {
+        self.dependencies.get(&chain_id).and_then(|dependency| dependency.activation_time)
+    }
+
+    /// Returns the configured index of the chain in the dependency set, or [`None`] if the chain
+    /// is not part of it or has no configured index.
+    pub fn chain_index(&self, chain_id: ChainId) -> Option<u32> {
+        self.dependencies.get(&chain_id).and_then(|dependency| dependency.chain_index)
+    }
+
+    /// Returns the ID of the chain with the given configured index in the dependency set.
+    pub fn chain_id_by_index(&self, index: u32) -> Option<ChainId> {
+        self.dependencies
+            .iter()
+            .find(|(_, dependency)| dependency.chain_index == Some(index))
+            .map(|(chain_id, _)| *chain_id)
+    }
+
+    /// Validates the dependency set against the interop activation times of the rollup configs
+    /// of the chains, keyed by chain ID.
+    ///
+    /// The dependency set must not be empty, every chain must have a rollup config, explicit chain
+    /// indexes must be unique, and configured activation times must match the rollup configs.
+    pub fn validate(
+        &self,
+        interop_times: &HashMap<ChainId, Option<u64>>,
+    ) -> Result<(), DependencySetError> {
+        if self.dependencies.is_empty() {
+            return Err(DependencySetError::Empty);
+        }
+
+        let mut indexes = HashMap::<u32, ChainId>::default();
+        for chain_id in self.chain_ids() {
+            let dependency = &self.dependencies[&chain_id];
+            let interop_time = *interop_times
+                .get(&chain_id)
+                .ok_or(DependencySetError::MissingRollupConfig(chain_id))?;
+
+            let mismatch = dependency.activation_time.filter(|time| interop_time != Some(*time));
+            if let Some(activation_time) = mismatch {
+                return Err(DependencySetError::ActivationTimeMismatch {
+                    chain_id,
+                    activation_time,
+                    interop_time,
+                });
+            }
+
+            let duplicate = dependency
+                .chain_index
+                .and_then(|index| indexes.insert(index, chain_id).map(|other| (index, other)));
+            if let Some((index, other)) = duplicate {
+                return Err(DependencySetError::DuplicateChainIndex {
+                    index,
+                    chain_ids: (other, chain_id),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Parses a dependency set from its JSON representation.
+    #[cfg(feature = "json")]
+    pub fn from_json(json: &str) -> Result<Self, DependencySetError> {
+        serde_json::from_str(json).map_err(|err| DependencySetError::Parse(err.to_string()))
+    }
+
+    /// Parses a dependency set from its TOML representation.
+    #[cfg(feature = "toml")]
+    pub fn from_toml(toml: &str) -> Result<Self, DependencySetError> {
+        toml::from_str(toml).map_err(|err| DependencySetError::Parse(err.to_string()))
+    }
+}
+
+/// Deserializes the dependencies of a [`DependencySet`], keyed by chain ID. Formats such as TOML
+/// only support string keys, so chain IDs are accepted both as integers and as strings.
+#[cfg(feature = "serde")]
+fn deserialize_dependencies<'de, D>(
+    deserializer: D,
+) -> Result<HashMap<ChainId, ChainDependency>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(PartialEq, Eq, PartialOrd, Ord)]
+    struct ChainIdKey(ChainId);
+
+    impl<'de> serde::Deserialize<'de> for ChainIdKey {
+        fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            struct KeyVisitor;
+
+            impl serde::de::Visitor<'_> for KeyVisitor {
+                type Value = ChainIdKey;
+
+                fn expecting(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                    f.write_str("a chain ID")
+                }
+
+                fn visit_u64<E: serde::de::Error>(self, v: u64) -> Result<Self::Value, E> {
+                    Ok(ChainIdKey(v))
+                }
+
+                fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<Self::Value, E> {
+                    v.parse().map(ChainIdKey).map_err(E::custom)
+                }
+            }
+
+            deserializer.deserialize_any(KeyVisitor)
+        }
+    }
+
+    let dependencies: alloc::collections::BTreeMap<ChainIdKey, ChainDependency> =
+        serde::Deserialize::deserialize(deserializer)?;
+    Ok(dependencies.into_iter().map(|(key, dependency)| (key.0, dependency)).collect())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use alloc::vec;
     use alloy_primitives::ChainId;
     use kona_registry::HashMap;
 
@@ -43,6 +183,10 @@ mod tests {
         DependencySet { dependencies, override_message_expiry_window: Some(override_expiry) }
     }
 
+    fn dependency(chain_index: Option<u32>, activation_time: Option<u64>) -> ChainDependency {
+        ChainDependency { chain_index, activation_time }
+    }
+
     #[test]
     fn test_get_message_expiry_window_default() {
         let deps = HashMap::default();
@@ -66,4 +210,103 @@ mod tests {
             "Should return override expiry window when it's non-zero"
         );
     }
+
+    #[test]
+    fn test_chain_indexes() {
+        let deps = HashMap::from_iter([
+            (30, dependency(None, None)),
+            (10, dependency(Some(0), None)),
+            (20, dependency(None, None)),
+            (40, dependency(Some(2), None)),
+        ]);
+        let ds = create_dependency_set(deps, 0);
+
+        assert_eq!(ds.chain_ids(), vec![10, 20, 30, 40]);
+        assert_eq!(ds.chain_index(10), Some(0));
+        assert_eq!(ds.chain_index(40), Some(2));
+        // Chains without a configured index are not assigned one.
+        assert_eq!(ds.chain_index(20), None);
+        assert_eq!(ds.chain_index(30), None);
+        assert_eq!(ds.chain_index(50), None);
+        assert_eq!(ds.chain_id_by_index(2), Some(40));
+        assert_eq!(ds.chain_id_by_index(1), None);
+    }
+
+    #[test]
+    fn test_validate() {
+        let interop_times = HashMap::from_iter([(1, Some(100)), (2, None)]);
+
+        let ds = create_dependency_set(
+            HashMap::from_iter([(1, dependency(Some(0), Some(100))), (2, dependency(None, None))]),
+            0,
+        );
+        assert_eq!(ds.validate(&interop_times), Ok(()));
+
+        let ds = create_dependency_set(HashMap::default(), 0);
+        assert_eq!(ds.validate(&interop_times), Err(DependencySetError::Empty));
+
+        let ds = create_dependency_set(HashMap::from_iter([(3, dependency(None, None))]), 0);
+        assert_eq!(ds.validate(&interop_times), Err(DependencySetError::MissingRollupConfig(3)));
+
+        let ds = create_dependency_set(
+            HashMap::from_iter([(1, dependency(Some(1), None)), (2, dependency(Some(1), None))]),
+            0,
+        );
+        assert_eq!(
+            ds.validate(&interop_times),
+            Err(DependencySetError::DuplicateChainIndex { index: 1, chain_ids: (1, 2) })
+        );
+
+        let ds = create_dependency_set(HashMap::from_iter([(2, dependency(None, Some(100)))]), 0);
+        assert_eq!(
+            ds.validate(&interop_times),
+            Err(DependencySetError::ActivationTimeMismatch {
+                chain_id: 2,
+                activation_time: 100,
+                interop_time: None,
+            })
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "json")]
+    fn test_from_json() {
+        let ds = DependencySet::from_json(
+            r#"{
+                "dependencies": {
+                    "1": {},
+                    "2": { "chainIndex": 1, "activationTime": 100 }
+                },
+                "overrideMessageExpiryWindow": 3600
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(ds.chain_ids(), vec![1, 2]);
+        assert_eq!(ds.chain_index(2), Some(1));
+        assert_eq!(ds.activation_time(2), Some(100));
+        assert_eq!(ds.activation_time(1), None);
+        assert!(matches!(DependencySet::from_json("{"), Err(DependencySetError::Parse(_))));
+    }
+
+    #[test]
+    #[cfg(feature = "toml")]
+    fn test_from_toml() {
+        let ds = DependencySet::from_toml(
+            r#"
+            overrideMessageExpiryWindow = 3600
+
+            [dependencies.1]
+
+            [dependencies.2]
+            chainIndex = 1
+            activationTime = 100
+            "#,
+        )
+        .unwrap();
+        assert_eq!(ds.chain_ids(), vec![1, 2]);
+        assert_eq!(ds.chain_index(1), None);
+        assert_eq!(ds.chain_index(2), Some(1));
+        assert_eq!(ds.activation_time(2), Some(100));
+        assert_eq!(ds.get_message_expiry_window(), 3600);
+    }
 }
@@ -1,7 +1,8 @@
 //! Error types for the `kona-interop` crate.
 
 use crate::InteropProvider;
-use alloy_primitives::{Address, B256};
+use alloc::string::String;
+use alloy_primitives::{Address, B256, ChainId};
 use core::fmt::Debug;
 use kona_registry::HashMap;
 use thiserror::Error;
@@ -132,4 +133,60 @@ pub enum InteropValidationError {
     /// Timestamp is outside the allowed interop expiry window.
     #[error("timestamp outside allowed interop window, timestamp: {0}")]
     InvalidInteropTimestamp(u64),
+
+    /// The chain is not part of the dependency set.
+    #[error("chain {0} is not in the dependency set")]
+    ChainNotInDependencySet(ChainId),
+
+    /// An executing message references a log at or after itself within the same block.
+    #[error(
+        "intra-block cycle, initiating log index: {initiating_log_index}, executing log index: {executing_log_index}"
+    )]
+    IntraBlockCycle {
+        /// Log index of the initiating message
+        initiating_log_index: u32,
+        /// Log index of the executing message
+        executing_log_index: u32,
+    },
+}
+
+/// Errors that can occur when loading or validating a [DependencySet].
+///
+/// [DependencySet]: crate::DependencySet
+#[derive(Debug, Clone, Error, PartialEq, Eq)]
+pub enum DependencySetError {
+    /// The dependency set contains no chains.
+    #[error("dependency set is empty")]
+    Empty,
+
+    /// A chain of the dependency set has no rollup config.
+    #[error("missing rollup config for chain {0} of the dependency set")]
+    MissingRollupConfig(ChainId),
+
+    /// Two chains of the dependency set have the same index.
+    #[error("chains {} and {} have the same index {index}", chain_ids.0, chain_ids.1)]
+    DuplicateChainIndex {
+        /// The duplicate index.
+        index: u32,
+        /// The chains with the duplicate index.
+        chain_ids: (ChainId, ChainId),
+    },
+
+    /// The activation time of a chain does not match the interop activation time of its rollup
+    /// config.
+    #[error(
+        "activation time {activation_time} of chain {chain_id} does not match its interop time {interop_time:?}"
+    )]
+    ActivationTimeMismatch {
+        /// The chain ID.
+        chain_id: ChainId,
+        /// The activation time of the chain in the dependency set.
+        activation_time: u64,
+        /// The interop activation time of the rollup config of the chain.
+        interop_time: Option<u64>,
+    },
+
+    /// The dependency set could not be parsed.
+    #[error("failed to parse dependency set: {0}")]
+    Parse(String),
 }
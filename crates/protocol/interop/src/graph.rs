@@ -1,7 +1,7 @@
 //! Interop [`MessageGraph`].
 
 use crate::{
-    MESSAGE_EXPIRY_WINDOW, RawMessagePayload,
+    InteropValidationError, MessageValidator, RawMessagePayload,
     errors::{MessageGraphError, MessageGraphResult},
     message::{EnrichedExecutingMessage, extract_executing_messages},
     traits::InteropProvider,
@@ -160,12 +160,23 @@ where
         // Timestamp invariant: The timestamp at the time of inclusion of the initiating message
         // MUST be less than or equal to the timestamp of the executing message as well as greater
         // than the Interop activation block's timestamp.
-        if initiating_timestamp > message.executing_timestamp {
-            return Err(MessageGraphError::MessageInFuture {
-                max: message.executing_timestamp,
-                actual: initiating_timestamp,
-            });
-        } else if initiating_timestamp <
+        //
+        // Message expiry invariant: The timestamp of the initiating message must be no more than
+        // `MESSAGE_EXPIRY_WINDOW` seconds in the past, relative to the timestamp of the executing
+        // message.
+        MessageValidator::default()
+            .validate_timestamps(initiating_timestamp, message.executing_timestamp, None)
+            .map_err(|e| match e {
+                InteropValidationError::InvalidTimestampInvariant { executing, initiating } => {
+                    MessageGraphError::MessageInFuture { max: executing, actual: initiating }
+                }
+                _ => MessageGraphError::MessageExpired {
+                    initiating_timestamp,
+                    executing_timestamp: message.executing_timestamp,
+                },
+            })?;
+
+        if initiating_timestamp <
             rollup_config.hardforks.interop_time.unwrap_or_default() + rollup_config.block_time
         {
             return Err(MessageGraphError::InitiatedTooEarly {
@@ -174,17 +185,6 @@ where
             });
         }
 
-        // Message expiry invariant: The timestamp of the initiating message must be no more than
-        // `MESSAGE_EXPIRY_WINDOW` seconds in the past, relative to the timestamp of the executing
-        // message.
-        if initiating_timestamp < message.executing_timestamp.saturating_sub(MESSAGE_EXPIRY_WINDOW)
-        {
-            return Err(MessageGraphError::MessageExpired {
-                initiating_timestamp,
-                executing_timestamp: message.executing_timestamp,
-            });
-        }
-
         // Fetch the header & receipts for the message's claimed origin block on the remote chain.
         let remote_header = self
             .provider
@@ -245,9 +245,9 @@ where
 
 #[cfg(test)]
 mod test {
-    use super::{MESSAGE_EXPIRY_WINDOW, MessageGraph};
+    use super::MessageGraph;
     use crate::{
-        MessageGraphError,
+        MESSAGE_EXPIRY_WINDOW, MessageGraphError,
         test_util::{ExecutingMessageBuilder, SuperchainBuilder},
     };
     use alloy_primitives::{Address, hex, keccak256};
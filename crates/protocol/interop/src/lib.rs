@@ -20,15 +20,19 @@ pub use control::ControlEvent;
 mod replacement;
 pub use replacement::BlockReplacement;
 
+mod validator;
+pub use validator::MessageValidator;
+
 mod traits;
-pub use traits::{InteropProvider, InteropValidator};
+pub use traits::{InteropProvider, InteropValidator, SuperRootProvider};
 
 mod safety;
 pub use safety::SafetyLevelParseError;
 
 mod errors;
 pub use errors::{
-    InteropValidationError, MessageGraphError, MessageGraphResult, SuperRootError, SuperRootResult,
+    DependencySetError, InteropValidationError, MessageGraphError, MessageGraphResult,
+    SuperRootError, SuperRootResult,
 };
 
 mod root;
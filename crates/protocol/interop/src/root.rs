@@ -2,7 +2,7 @@
 //!
 //! Represents a snapshot of the state of the superchain at a given integer timestamp.
 
-use crate::{SUPER_ROOT_VERSION, SuperRootError, SuperRootResult};
+use crate::{SUPER_ROOT_VERSION, SuperRootError, SuperRootProvider, SuperRootResult};
 use alloc::vec::Vec;
 use alloy_eips::BlockNumHash;
 use alloy_primitives::{B256, Bytes, U256, keccak256};
@@ -27,6 +27,28 @@ impl SuperRoot {
         Self { timestamp, output_roots }
     }
 
+    /// Computes the [SuperRoot] at the given timestamp by fetching the output root of every chain
+    /// in `chain_ids` from the [SuperRootProvider].
+    ///
+    /// Duplicate chain IDs are ignored, and the output roots are ordered by chain ID.
+    pub async fn compute<P: SuperRootProvider>(
+        timestamp: u64,
+        chain_ids: impl IntoIterator<Item = u64>,
+        provider: &P,
+    ) -> Result<Self, P::Error> {
+        let mut chain_ids = chain_ids.into_iter().collect::<Vec<_>>();
+        chain_ids.sort_unstable();
+        chain_ids.dedup();
+
+        let mut output_roots = Vec::with_capacity(chain_ids.len());
+        for chain_id in chain_ids {
+            let output_root = provider.output_root_at_timestamp(chain_id, timestamp).await?;
+            output_roots.push(OutputRootWithChain::new(chain_id, output_root));
+        }
+
+        Ok(Self { timestamp, output_roots })
+    }
+
     /// Decodes a [SuperRoot] from the given buffer.
     pub fn decode(buf: &mut &[u8]) -> SuperRootResult<Self> {
         if buf.is_empty() {
@@ -143,7 +165,7 @@ impl OutputRootWithChain {
 
 #[cfg(test)]
 mod test {
-    use crate::{SUPER_ROOT_VERSION, errors::SuperRootError};
+    use crate::{SUPER_ROOT_VERSION, SuperRootProvider, errors::SuperRootError};
 
     use super::{OutputRootWithChain, SuperRoot};
     use alloy_primitives::{B256, b256};
@@ -162,6 +184,35 @@ mod test {
         assert!(super_root.output_roots.windows(2).all(|w| w[0].chain_id <= w[1].chain_id));
     }
 
+    #[tokio::test]
+    async fn test_compute_super_root() {
+        struct MockProvider;
+
+        #[async_trait::async_trait]
+        impl SuperRootProvider for MockProvider {
+            type Error = core::convert::Infallible;
+
+            async fn output_root_at_timestamp(
+                &self,
+                chain_id: u64,
+                timestamp: u64,
+            ) -> Result<B256, Self::Error> {
+                Ok(B256::left_padding_from(&[chain_id as u8, timestamp as u8]))
+            }
+        }
+
+        let super_root = SuperRoot::compute(10, [3, 1, 2, 1], &MockProvider).await.unwrap();
+        let expected = SuperRoot::new(
+            10,
+            vec![
+                OutputRootWithChain::new(1, B256::left_padding_from(&[1, 10])),
+                OutputRootWithChain::new(2, B256::left_padding_from(&[2, 10])),
+                OutputRootWithChain::new(3, B256::left_padding_from(&[3, 10])),
+            ],
+        );
+        assert_eq!(super_root, expected);
+    }
+
     #[test]
     fn test_super_root_empty_buf() {
         let buf: Vec<u8> = Vec::new();
@@ -34,6 +34,23 @@ pub trait InteropProvider {
     ) -> Result<Vec<OpReceiptEnvelope>, Self::Error>;
 }
 
+/// Describes the interface of a provider for the per-chain output roots that make up a
+/// [`SuperRoot`].
+///
+/// [`SuperRoot`]: crate::SuperRoot
+#[async_trait]
+pub trait SuperRootProvider {
+    /// The error type for the provider.
+    type Error: Error;
+
+    /// Fetch the canonical output root of the given chain at the given timestamp.
+    async fn output_root_at_timestamp(
+        &self,
+        chain_id: ChainId,
+        timestamp: u64,
+    ) -> Result<B256, Self::Error>;
+}
+
 /// Trait for validating interop-related timestamps and blocks.
 pub trait InteropValidator: Send + Sync {
     /// Validates that the provided timestamps and chain IDs are eligible for interop execution.
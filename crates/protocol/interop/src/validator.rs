@@ -0,0 +1,195 @@
+//! Interop [`MessageValidator`].
+
+use crate::{DependencySet, InteropValidationError, MESSAGE_EXPIRY_WINDOW};
+use alloy_primitives::ChainId;
+
+/// The [`MessageValidator`] enforces the stateless [message validity invariants] that every
+/// executing message must satisfy, independent of how the initiating message is looked up.
+///
+/// It is shared by the supervisor and the proof program so that both agree on which messages are
+/// invalid. Checks that require access to chain data (the existence and integrity of the
+/// initiating log) are left to the caller.
+///
+/// [message validity invariants]: https://specs.optimism.io/interop/messaging.html#invalid-messages
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MessageValidator {
+    /// The message expiry window, in seconds.
+    message_expiry_window: u64,
+}
+
+impl Default for MessageValidator {
+    fn default() -> Self {
+        Self::new(MESSAGE_EXPIRY_WINDOW)
+    }
+}
+
+impl From<&DependencySet> for MessageValidator {
+    fn from(dependency_set: &DependencySet) -> Self {
+        Self::new(dependency_set.get_message_expiry_window())
+    }
+}
+
+impl MessageValidator {
+    /// Creates a new [`MessageValidator`] with the given message expiry window, in seconds.
+    pub const fn new(message_expiry_window: u64) -> Self {
+        Self { message_expiry_window }
+    }
+
+    /// Returns the message expiry window, in seconds.
+    pub const fn message_expiry_window(&self) -> u64 {
+        self.message_expiry_window
+    }
+
+    /// Validates the timestamp and expiry invariants of a message.
+    ///
+    /// The initiating timestamp must be less than or equal to the executing timestamp, and the
+    /// message must not expire before the executing timestamp plus the optional `timeout`.
+    pub const fn validate_timestamps(
+        &self,
+        initiating_timestamp: u64,
+        executing_timestamp: u64,
+        timeout: Option<u64>,
+    ) -> Result<(), InteropValidationError> {
+        if initiating_timestamp > executing_timestamp {
+            return Err(InteropValidationError::InvalidTimestampInvariant {
+                initiating: initiating_timestamp,
+                executing: executing_timestamp,
+            });
+        }
+
+        let timeout = match timeout {
+            Some(timeout) => timeout,
+            None => 0,
+        };
+        let expires_at = initiating_timestamp.saturating_add(self.message_expiry_window);
+        let execution_deadline = executing_timestamp.saturating_add(timeout);
+        if expires_at < execution_deadline {
+            return Err(InteropValidationError::InvalidInteropTimestamp(executing_timestamp));
+        }
+
+        Ok(())
+    }
+
+    /// Validates that the given chain is part of the dependency set.
+    pub fn validate_dependency(
+        &self,
+        dependency_set: &DependencySet,
+        chain_id: ChainId,
+    ) -> Result<(), InteropValidationError> {
+        if !dependency_set.dependencies.contains_key(&chain_id) {
+            return Err(InteropValidationError::ChainNotInDependencySet(chain_id));
+        }
+        Ok(())
+    }
+
+    /// Validates the ordering of a message whose initiating log lives in the same block as the
+    /// executing log.
+    ///
+    /// The only allowed form of an intra-block dependency is an executing message referencing an
+    /// initiating log that was emitted earlier in the same block. Messages initiated in a different
+    /// block are not checked.
+    pub const fn validate_intra_block_ordering(
+        &self,
+        initiating: (ChainId, u64, u32),
+        executing: (ChainId, u64, u32),
+    ) -> Result<(), InteropValidationError> {
+        let (initiating_chain_id, initiating_block, initiating_log_index) = initiating;
+        let (executing_chain_id, executing_block, executing_log_index) = executing;
+
+        if initiating_chain_id == executing_chain_id &&
+            initiating_block == executing_block &&
+            initiating_log_index >= executing_log_index
+        {
+            return Err(InteropValidationError::IntraBlockCycle {
+                initiating_log_index,
+                executing_log_index,
+            });
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ChainDependency;
+    use kona_registry::HashMap;
+
+    fn dependency_set(chain_ids: &[ChainId], expiry: Option<u64>) -> DependencySet {
+        let mut dependencies = HashMap::default();
+        for chain_id in chain_ids {
+            dependencies.insert(*chain_id, ChainDependency::default());
+        }
+        DependencySet { dependencies, override_message_expiry_window: expiry }
+    }
+
+    #[test]
+    fn test_default_expiry_window() {
+        assert_eq!(MessageValidator::default().message_expiry_window(), MESSAGE_EXPIRY_WINDOW);
+        assert_eq!(
+            MessageValidator::from(&dependency_set(&[], Some(10))).message_expiry_window(),
+            10
+        );
+    }
+
+    #[test]
+    fn test_validate_timestamps() {
+        let validator = MessageValidator::new(10);
+
+        assert_eq!(validator.validate_timestamps(200, 200, None), Ok(()));
+        assert_eq!(validator.validate_timestamps(200, 210, None), Ok(()));
+        assert_eq!(
+            validator.validate_timestamps(201, 200, None),
+            Err(InteropValidationError::InvalidTimestampInvariant {
+                initiating: 201,
+                executing: 200
+            })
+        );
+        assert_eq!(
+            validator.validate_timestamps(200, 211, None),
+            Err(InteropValidationError::InvalidInteropTimestamp(211))
+        );
+        assert_eq!(
+            validator.validate_timestamps(200, 205, Some(6)),
+            Err(InteropValidationError::InvalidInteropTimestamp(205))
+        );
+    }
+
+    #[test]
+    fn test_validate_dependency() {
+        let validator = MessageValidator::default();
+        let depset = dependency_set(&[1, 2], None);
+
+        assert_eq!(validator.validate_dependency(&depset, 1), Ok(()));
+        assert_eq!(
+            validator.validate_dependency(&depset, 3),
+            Err(InteropValidationError::ChainNotInDependencySet(3))
+        );
+    }
+
+    #[test]
+    fn test_validate_intra_block_ordering() {
+        let validator = MessageValidator::default();
+
+        // Earlier log in the same block.
+        assert_eq!(validator.validate_intra_block_ordering((1, 5, 0), (1, 5, 1)), Ok(()));
+        // Different block or chain.
+        assert_eq!(validator.validate_intra_block_ordering((1, 4, 3), (1, 5, 1)), Ok(()));
+        assert_eq!(validator.validate_intra_block_ordering((2, 5, 3), (1, 5, 1)), Ok(()));
+        // Self-reference and forward reference within the same block.
+        assert_eq!(
+            validator.validate_intra_block_ordering((1, 5, 1), (1, 5, 1)),
+            Err(InteropValidationError::IntraBlockCycle {
+                initiating_log_index: 1,
+                executing_log_index: 1
+            })
+        );
+        assert_eq!(
+            validator.validate_intra_block_ordering((1, 5, 2), (1, 5, 1)),
+            Err(InteropValidationError::IntraBlockCycle {
+                initiating_log_index: 2,
+                executing_log_index: 1
+            })
+        );
+    }
+}
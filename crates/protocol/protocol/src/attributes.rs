@@ -1,7 +1,6 @@
 //! Optimism Payload attributes that reference the parent L2 block.
 
 use crate::{BlockInfo, L2BlockInfo};
-use alloc::vec;
 use op_alloy_consensus::OpTxType;
 use op_alloy_rpc_types_engine::OpPayloadAttributes;
 
@@ -63,21 +62,24 @@ impl OpAttributesWithParent {
 
     /// Returns `true` if all transactions in the payload are deposits.
     pub fn is_deposits_only(&self) -> bool {
-        self.inner
-            .transactions
-            .iter()
-            .all(|tx| tx.first().is_some_and(|tx| tx[0] == OpTxType::Deposit as u8))
+        self.inner.transactions.iter().flatten().all(|tx| Self::is_deposit(tx))
     }
 
-    /// Converts the [`OpAttributesWithParent`] into a deposits-only payload.
+    /// Converts the [`OpAttributesWithParent`] into a deposits-only payload, stripping all
+    /// transactions that are not deposits.
+    ///
+    /// Post-Holocene, a block that fails to execute is replaced by the deposits-only block with
+    /// the same attributes, and the remainder of the span batch it was derived from is dropped:
+    /// the blocks of the span that were already applied are kept. As in op-node, the deposits-only
+    /// block keeps the `is_last_in_span` flag of the original attributes.
     pub fn as_deposits_only(&self) -> Self {
         Self {
             inner: OpPayloadAttributes {
-                transactions: self.inner.transactions.as_ref().map(|txs| {
-                    txs.iter()
-                        .map(|_| alloy_primitives::Bytes::from(vec![OpTxType::Deposit as u8]))
-                        .collect()
-                }),
+                transactions: self
+                    .inner
+                    .transactions
+                    .as_ref()
+                    .map(|txs| txs.iter().filter(|tx| Self::is_deposit(tx)).cloned().collect()),
                 ..self.inner.clone()
             },
             parent: self.parent,
@@ -85,11 +87,18 @@ impl OpAttributesWithParent {
             is_last_in_span: self.is_last_in_span,
         }
     }
+
+    /// Returns `true` if the given encoded transaction is a deposit.
+    fn is_deposit(tx: &[u8]) -> bool {
+        tx.first().is_some_and(|ty| *ty == OpTxType::Deposit as u8)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use alloc::vec;
+    use alloy_primitives::Bytes;
 
     #[test]
     fn test_op_attributes_with_parent() {
@@ -104,4 +113,45 @@ mod tests {
         assert_eq!(op_attributes_with_parent.is_last_in_span(), is_last_in_span);
         assert_eq!(op_attributes_with_parent.derived_from(), None);
     }
+
+    #[test]
+    fn test_as_deposits_only() {
+        let deposit = Bytes::from(vec![OpTxType::Deposit as u8, 0x01]);
+        let user_tx = Bytes::from(vec![OpTxType::Eip1559 as u8, 0x02]);
+        let attributes = OpPayloadAttributes {
+            transactions: Some(vec![deposit.clone(), user_tx, deposit.clone()]),
+            ..Default::default()
+        };
+        let with_parent =
+            OpAttributesWithParent::new(attributes, L2BlockInfo::default(), None, false);
+        assert!(!with_parent.is_deposits_only());
+
+        let deposits_only = with_parent.as_deposits_only();
+        assert!(deposits_only.is_deposits_only());
+        assert!(!deposits_only.is_last_in_span());
+        assert_eq!(deposits_only.inner().transactions, Some(vec![deposit.clone(), deposit]));
+        assert_eq!(deposits_only.parent(), with_parent.parent());
+    }
+
+    #[test]
+    fn test_as_deposits_only_keeps_last_in_span() {
+        let with_parent = OpAttributesWithParent::new(
+            OpPayloadAttributes::default(),
+            L2BlockInfo::default(),
+            None,
+            true,
+        );
+        assert!(with_parent.as_deposits_only().is_last_in_span());
+    }
+
+    #[test]
+    fn test_is_deposits_only_without_transactions() {
+        let with_parent = OpAttributesWithParent::new(
+            OpPayloadAttributes::default(),
+            L2BlockInfo::default(),
+            None,
+            false,
+        );
+        assert!(with_parent.is_deposits_only());
+    }
 }
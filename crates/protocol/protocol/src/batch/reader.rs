@@ -1,11 +1,11 @@
 //! Contains the [`BatchReader`] which is used to iteratively consume batches from raw data.
 
-use crate::{Batch, BrotliDecompressionError, decompress_brotli};
+use crate::{Batch, BatchDecodingError, BrotliDecompressionError, decompress_brotli};
 use alloc::vec::Vec;
 use alloy_primitives::Bytes;
 use alloy_rlp::Decodable;
 use kona_genesis::RollupConfig;
-use miniz_oxide::inflate::decompress_to_vec_zlib;
+use miniz_oxide::inflate::{TINFLStatus, decompress_to_vec_zlib_with_limit};
 
 /// Error type for decompression failures.
 #[derive(Debug, thiserror::Error)]
@@ -27,6 +27,47 @@ pub enum DecompressionError {
     RlpTooLarge(usize, usize),
 }
 
+/// Error type for the failures to read a batch from a channel.
+///
+/// The batch index is the position of the offending batch in the channel, starting at 0.
+#[derive(Debug, thiserror::Error)]
+pub enum BatchReaderError {
+    /// The channel data could not be decompressed.
+    #[error("failed to decompress channel: {0}")]
+    Decompression(#[from] DecompressionError),
+    /// The batch could not be decoded.
+    #[error("failed to decode batch #{index}: {source}")]
+    Decoding {
+        /// The index of the batch in the channel.
+        index: usize,
+        /// The decoding error.
+        source: BatchDecodingError,
+    },
+    /// The channel is brotli-compressed, but the batch predates the Fjord hardfork.
+    #[error("batch #{index} is brotli-compressed before the Fjord hardfork")]
+    BrotliBeforeFjord {
+        /// The index of the batch in the channel.
+        index: usize,
+    },
+    /// The batch is a span batch, but the channel was included before the Delta hardfork.
+    #[error("span batch #{index} was included before the Delta hardfork")]
+    SpanBatchBeforeDelta {
+        /// The index of the batch in the channel.
+        index: usize,
+    },
+}
+
+impl BatchReaderError {
+    /// Returns whether the rest of the channel must be discarded.
+    ///
+    /// A span batch included before the Delta hardfork is skipped on its own, and the batches
+    /// that follow it in the channel can still be read. All other errors invalidate the rest of
+    /// the channel.
+    pub const fn discards_channel(&self) -> bool {
+        !matches!(self, Self::SpanBatchBeforeDelta { .. })
+    }
+}
+
 /// Batch Reader provides a function that iteratively consumes batches from the reader.
 /// The L1Inclusion block is also provided at creation time.
 /// Warning: the batch reader can read every batch-type.
@@ -43,6 +84,8 @@ pub struct BatchReader {
     pub max_rlp_bytes_per_channel: usize,
     /// Whether brotli decompression was used.
     pub brotli_used: bool,
+    /// The number of batches read from the `decompressed` data.
+    pub index: usize,
 }
 
 impl BatchReader {
@@ -67,6 +110,7 @@ impl BatchReader {
             cursor: 0,
             max_rlp_bytes_per_channel,
             brotli_used: false,
+            index: 0,
         }
     }
 
@@ -82,16 +126,17 @@ impl BatchReader {
             if (compression_type & 0x0F) == Self::ZLIB_DEFLATE_COMPRESSION_METHOD ||
                 (compression_type & 0x0F) == Self::ZLIB_RESERVED_COMPRESSION_METHOD
             {
-                self.decompressed =
-                    decompress_to_vec_zlib(&data).map_err(|_| DecompressionError::ZlibError)?;
-
-                // Check the size of the decompressed channel RLP.
-                if self.decompressed.len() > self.max_rlp_bytes_per_channel {
-                    return Err(DecompressionError::RlpTooLarge(
-                        self.decompressed.len(),
-                        self.max_rlp_bytes_per_channel,
-                    ));
-                }
+                // Inflation stops as soon as the output reaches the maximum channel RLP size, so
+                // that a highly compressed channel cannot exhaust the memory. As in the reference
+                // implementation, the channel is truncated at that size rather than rejected.
+                self.decompressed = match decompress_to_vec_zlib_with_limit(
+                    &data,
+                    self.max_rlp_bytes_per_channel,
+                ) {
+                    Ok(decompressed) => decompressed,
+                    Err(err) if err.status == TINFLStatus::HasMoreOutput => err.output,
+                    Err(_) => return Err(DecompressionError::ZlibError),
+                };
             } else if compression_type == Self::CHANNEL_VERSION_BROTLI {
                 self.brotli_used = true;
                 self.decompressed = decompress_brotli(&data[1..], self.max_rlp_bytes_per_channel)?;
@@ -103,25 +148,105 @@ impl BatchReader {
     }
 
     /// Pulls out the next batch from the reader.
+    ///
+    /// Returns `None` if there are no more batches or if the next batch is invalid. Span batches
+    /// are returned regardless of the Delta hardfork, see [`BatchReader::read_batch`].
     pub fn next_batch(&mut self, cfg: &RollupConfig) -> Option<Batch> {
+        self.decode_next(cfg).ok().flatten()
+    }
+
+    /// Reads the next batch of the channel, which was included in an L1 block with the given
+    /// timestamp, enforcing the hardfork rules on the batch types.
+    ///
+    /// Returns `Ok(None)` once all batches have been read. On error, the batch is consumed if
+    /// [`BatchReaderError::discards_channel`] is `false`, so that reading can resume with the
+    /// next batch.
+    pub fn read_batch(
+        &mut self,
+        cfg: &RollupConfig,
+        origin_timestamp: u64,
+    ) -> Result<Option<Batch>, BatchReaderError> {
+        let index = self.index;
+        match self.decode_next(cfg)? {
+            Some(Batch::Span(_)) if !cfg.is_delta_active(origin_timestamp) => {
+                Err(BatchReaderError::SpanBatchBeforeDelta { index })
+            }
+            batch => Ok(batch),
+        }
+    }
+
+    /// Returns an iterator over the remaining batches of the channel, which was included in an L1
+    /// block with the given timestamp. See [`BatchReader::read_batch`].
+    ///
+    /// The iterator ends after the first error that discards the rest of the channel.
+    pub const fn batches<'a>(
+        &'a mut self,
+        cfg: &'a RollupConfig,
+        origin_timestamp: u64,
+    ) -> Batches<'a> {
+        Batches { reader: self, cfg, origin_timestamp, done: false }
+    }
+
+    /// Decodes the next batch from the decompressed data, advancing the cursor.
+    fn decode_next(&mut self, cfg: &RollupConfig) -> Result<Option<Batch>, BatchReaderError> {
         // Ensure the data is decompressed.
-        self.decompress().ok()?;
+        self.decompress()?;
+        if self.cursor >= self.decompressed.len() {
+            return Ok(None);
+        }
 
-        // Decompress and RLP decode the batch data, before finally decoding the batch itself.
+        // RLP decode the batch data, before finally decoding the batch itself.
+        let index = self.index;
         let decompressed_reader = &mut self.decompressed.as_slice()[self.cursor..].as_ref();
-        let bytes = Bytes::decode(decompressed_reader).ok()?;
-        let Ok(batch) = Batch::decode(&mut bytes.as_ref(), cfg) else {
-            return None;
-        };
+        let bytes = Bytes::decode(decompressed_reader).map_err(|e| BatchReaderError::Decoding {
+            index,
+            source: BatchDecodingError::AlloyRlpError(e),
+        })?;
+        let batch = Batch::decode(&mut bytes.as_ref(), cfg)
+            .map_err(|source| BatchReaderError::Decoding { index, source })?;
 
         // Confirm that brotli decompression was performed *after* the Fjord hardfork.
         if self.brotli_used && !cfg.is_fjord_active(batch.timestamp()) {
-            return None;
+            return Err(BatchReaderError::BrotliBeforeFjord { index });
         }
 
         // Advance the cursor on the reader.
         self.cursor = self.decompressed.len() - decompressed_reader.len();
-        Some(batch)
+        self.index += 1;
+        Ok(Some(batch))
+    }
+}
+
+/// An iterator over the batches of a channel, returned by [`BatchReader::batches`].
+#[derive(Debug)]
+pub struct Batches<'a> {
+    /// The batch reader.
+    reader: &'a mut BatchReader,
+    /// The rollup configuration.
+    cfg: &'a RollupConfig,
+    /// The timestamp of the L1 block that included the channel.
+    origin_timestamp: u64,
+    /// Whether the iterator is exhausted.
+    done: bool,
+}
+
+impl Iterator for Batches<'_> {
+    type Item = Result<Batch, BatchReaderError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        match self.reader.read_batch(self.cfg, self.origin_timestamp) {
+            Ok(batch) => {
+                self.done = batch.is_none();
+                batch.map(Ok)
+            }
+            Err(err) => {
+                self.done = err.discards_channel();
+                Some(Err(err))
+            }
+        }
     }
 }
 
@@ -131,6 +256,7 @@ mod test {
     use kona_genesis::{
         HardForkConfig, MAX_RLP_BYTES_PER_CHANNEL_BEDROCK, MAX_RLP_BYTES_PER_CHANNEL_FJORD,
     };
+    use miniz_oxide::{deflate::compress_to_vec_zlib, inflate::decompress_to_vec_zlib};
 
     fn new_compressed_batch_data() -> Bytes {
         let file_contents =
@@ -162,4 +288,67 @@ mod test {
             .unwrap();
         assert_eq!(reader.cursor, decompressed_len);
     }
+
+    #[test]
+    fn test_read_batch_span_before_delta() {
+        let raw = new_compressed_batch_data();
+        let decompressed_len = decompress_to_vec_zlib(&raw).unwrap().len();
+        let cfg = RollupConfig {
+            hardforks: HardForkConfig { delta_time: Some(10), ..Default::default() },
+            ..Default::default()
+        };
+
+        let mut reader = BatchReader::new(raw.clone(), MAX_RLP_BYTES_PER_CHANNEL_BEDROCK as usize);
+        let err = reader.read_batch(&cfg, 9).unwrap_err();
+        assert!(matches!(err, BatchReaderError::SpanBatchBeforeDelta { index: 0 }));
+        assert!(!err.discards_channel());
+        assert_eq!(reader.cursor, decompressed_len);
+        assert!(reader.read_batch(&cfg, 9).unwrap().is_none());
+
+        let mut reader = BatchReader::new(raw, MAX_RLP_BYTES_PER_CHANNEL_BEDROCK as usize);
+        assert!(matches!(reader.read_batch(&cfg, 10), Ok(Some(Batch::Span(_)))));
+        assert_eq!(reader.index, 1);
+    }
+
+    #[test]
+    fn test_batches_stops_on_decoding_error() {
+        let raw = new_compressed_batch_data();
+        let mut decompressed = decompress_to_vec_zlib(&raw).unwrap();
+        decompressed.truncate(decompressed.len() / 2);
+        let mut reader = BatchReader::new(Vec::new(), MAX_RLP_BYTES_PER_CHANNEL_BEDROCK as usize);
+        reader.data = None;
+        reader.decompressed = decompressed;
+
+        let cfg = RollupConfig::default();
+        let batches = reader.batches(&cfg, 0).collect::<Vec<_>>();
+        assert_eq!(batches.len(), 1);
+        assert!(matches!(batches[0], Err(BatchReaderError::Decoding { index: 0, .. })));
+        assert_eq!(reader.cursor, 0);
+    }
+
+    #[test]
+    fn test_batches() {
+        let raw = new_compressed_batch_data();
+        let cfg = RollupConfig {
+            hardforks: HardForkConfig { delta_time: Some(0), ..Default::default() },
+            ..Default::default()
+        };
+        let mut reader = BatchReader::new(raw, MAX_RLP_BYTES_PER_CHANNEL_BEDROCK as usize);
+        let batches = reader.batches(&cfg, 0).collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(batches.len(), 1);
+        assert!(matches!(batches[0], Batch::Span(_)));
+    }
+
+    #[test]
+    fn test_decompress_zlib_output_limit() {
+        let compressed = compress_to_vec_zlib(&[0; 64 * 1024], 9);
+
+        let mut reader = BatchReader::new(compressed.clone(), 64 * 1024);
+        reader.decompress().unwrap();
+        assert_eq!(reader.decompressed.len(), 64 * 1024);
+
+        let mut reader = BatchReader::new(compressed, 64 * 1024 - 1);
+        reader.decompress().unwrap();
+        assert_eq!(reader.decompressed.len(), 64 * 1024 - 1);
+    }
 }
@@ -1,12 +1,13 @@
 //! This module contains the [`SingleBatch`] type.
 
-use crate::{BatchValidity, BlockInfo, L2BlockInfo};
+use crate::{BatchValidity, BlockInfo, L1BlockInfoTx, L2BlockInfo, OpBlockConversionError};
 use alloc::vec::Vec;
-use alloy_eips::BlockNumHash;
+use alloy_consensus::{Transaction, Typed2718};
+use alloy_eips::{BlockNumHash, eip2718::Encodable2718};
 use alloy_primitives::{BlockHash, Bytes};
 use alloy_rlp::{RlpDecodable, RlpEncodable};
 use kona_genesis::RollupConfig;
-use op_alloy_consensus::OpTxType;
+use op_alloy_consensus::{OpBlock, OpTxType};
 use tracing::warn;
 
 /// Represents a single batch: a single encoded L2 block
@@ -26,6 +27,35 @@ pub struct SingleBatch {
 }
 
 impl SingleBatch {
+    /// Converts an L2 [`OpBlock`] into the [`SingleBatch`] it is derived from.
+    ///
+    /// The epoch of the batch is read from the L1 info deposit, which must be the first
+    /// transaction of the block. Deposits are left out of the batch, since they are derived from
+    /// L1 rather than submitted by the batcher.
+    pub fn from_block(block: &OpBlock) -> Result<Self, OpBlockConversionError> {
+        let Some(first) = block.body.transactions.first() else {
+            return Err(OpBlockConversionError::EmptyTransactions(block.header.hash_slow()));
+        };
+        let Some(deposit) = first.as_deposit() else {
+            return Err(OpBlockConversionError::InvalidTxType(first.ty()));
+        };
+        let epoch = L1BlockInfoTx::decode_calldata(deposit.input().as_ref())?.id();
+
+        Ok(Self {
+            parent_hash: block.header.parent_hash,
+            epoch_num: epoch.number,
+            epoch_hash: epoch.hash,
+            timestamp: block.header.timestamp,
+            transactions: block
+                .body
+                .transactions
+                .iter()
+                .filter(|tx| !tx.is_deposit())
+                .map(|tx| tx.encoded_2718().into())
+                .collect(),
+        })
+    }
+
     /// If any transactions are empty or deposited transaction types.
     pub fn has_invalid_transactions(&self) -> bool {
         self.transactions.iter().any(|tx| tx.0.is_empty() || tx.0[0] == OpTxType::Deposit as u8)
@@ -202,6 +232,43 @@ mod tests {
     use tracing::Level;
     use tracing_subscriber::layer::SubscriberExt;
 
+    #[test]
+    fn test_from_block() {
+        use crate::test_utils::RAW_BEDROCK_INFO_TX;
+
+        let deposit = OpTxEnvelope::Deposit(Sealed::new(TxDeposit {
+            input: Bytes::from(&RAW_BEDROCK_INFO_TX),
+            ..Default::default()
+        }));
+        let tx = TxEip1559 { chain_id: 10, nonce: 2, ..Default::default() };
+        let tx = OpTxEnvelope::Eip1559(tx.into_signed(Signature::test_signature()));
+        let block = OpBlock {
+            header: alloy_consensus::Header {
+                parent_hash: BlockHash::from([0x02; 32]),
+                timestamp: 10,
+                ..Default::default()
+            },
+            body: alloy_consensus::BlockBody {
+                transactions: vec![deposit, tx.clone()],
+                ..Default::default()
+            },
+        };
+
+        let batch = SingleBatch::from_block(&block).unwrap();
+        let epoch = L1BlockInfoTx::decode_calldata(&RAW_BEDROCK_INFO_TX).unwrap().id();
+        assert_eq!(batch.parent_hash, BlockHash::from([0x02; 32]));
+        assert_eq!(batch.epoch(), epoch);
+        assert_eq!(batch.timestamp, 10);
+        assert_eq!(batch.transactions, vec![Bytes::from(tx.encoded_2718())]);
+        assert!(!batch.has_invalid_transactions());
+
+        let block = OpBlock { body: alloy_consensus::BlockBody::default(), ..block };
+        assert!(matches!(
+            SingleBatch::from_block(&block),
+            Err(OpBlockConversionError::EmptyTransactions(_))
+        ));
+    }
+
     #[test]
     fn test_empty_l1_blocks() {
         let cfg = RollupConfig::default();
@@ -17,6 +17,10 @@ pub enum BrotliDecompressionError {
 
 /// Decompresses the given bytes data using the Brotli decompressor implemented
 /// in the [`brotli`](https://crates.io/crates/brotli) crate.
+///
+/// The output buffer grows as the stream is decoded, up to `max_rlp_bytes_per_channel` bytes. As
+/// in the reference implementation, the output is truncated at that size, and the data decoded
+/// before an invalid or truncated part of the stream is returned.
 pub fn decompress_brotli(
     data: &[u8],
     max_rlp_bytes_per_channel: usize,
@@ -32,7 +36,7 @@ pub fn decompress_brotli(
     let mut brotli_state = BrotliState::new(u8_allocator, u32_allocator, hc_allocator);
 
     // Setup the decompressor inputs and outputs
-    let mut output = vec![0; data.len()];
+    let mut output = vec![0; data.len().min(max_rlp_bytes_per_channel)];
     let mut available_in = data.len();
     let mut input_offset = 0;
     let mut available_out = output.len();
@@ -54,18 +58,18 @@ pub fn decompress_brotli(
             brotli::BrotliResult::ResultSuccess => break,
             brotli::BrotliResult::NeedsMoreOutput => {
                 // Resize the output buffer to double the size, following standard
-                // practice for buffer resizing in streams.
+                // practice for buffer resizing in streams, up to the maximum size.
                 let old_len = output.len();
-                let new_len = old_len * 2;
-
-                if new_len > max_rlp_bytes_per_channel {
-                    return Err(BrotliDecompressionError::BatchTooLarge);
+                if old_len >= max_rlp_bytes_per_channel {
+                    break;
                 }
 
+                let new_len = (old_len * 2).max(1).min(max_rlp_bytes_per_channel);
                 output.resize(new_len, 0);
-                available_out += old_len;
+                available_out += new_len - old_len;
             }
-            _ => break,
+            // The stream is invalid or truncated, the data decompressed so far is returned.
+            brotli::BrotliResult::ResultFailure | brotli::BrotliResult::NeedsMoreInput => break,
         }
     }
 
@@ -104,4 +108,51 @@ mod test {
             decompress_brotli(&raw_batch, MAX_RLP_BYTES_PER_CHANNEL_FJORD as usize).unwrap();
         assert_eq!(decompressed, raw_batch_decompressed);
     }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_decompress_brotli_output_limit() {
+        let mut compressed = Vec::new();
+        {
+            let mut writer = brotli::CompressorWriter::new(&mut compressed, 4096, 11, 22);
+            std::io::Write::write_all(&mut writer, &[0; 64 * 1024]).unwrap();
+        }
+
+        let decompressed = decompress_brotli(&compressed, 64 * 1024).unwrap();
+        assert_eq!(decompressed.len(), 64 * 1024);
+        let truncated = decompress_brotli(&compressed, 64 * 1024 - 1).unwrap();
+        assert_eq!(truncated.len(), 64 * 1024 - 1);
+    }
+
+    #[test]
+    fn test_decompress_brotli_invalid_stream() {
+        let decompressed =
+            decompress_brotli(&[0xFF; 32], MAX_RLP_BYTES_PER_CHANNEL_FJORD as usize).unwrap();
+        assert!(decompressed.is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_decompress_brotli_corrupt_tail() {
+        let data = (0..16 * 1024u32).flat_map(|i| (i * i).to_le_bytes()).collect::<Vec<_>>();
+        let (head, tail) = data.split_at(data.len() / 2);
+
+        // The head is flushed into its own meta-block, which decodes before the corrupt tail.
+        let mut compressed = Vec::new();
+        let head_len = {
+            let mut writer = brotli::CompressorWriter::new(&mut compressed, 4096, 11, 22);
+            std::io::Write::write_all(&mut writer, head).unwrap();
+            std::io::Write::flush(&mut writer).unwrap();
+            let head_len = writer.get_ref().len();
+            std::io::Write::write_all(&mut writer, tail).unwrap();
+            head_len
+        };
+        compressed.truncate(head_len);
+        compressed.extend_from_slice(&[0xFF; 64]);
+
+        let decompressed =
+            decompress_brotli(&compressed, MAX_RLP_BYTES_PER_CHANNEL_FJORD as usize).unwrap();
+        assert!(decompressed.len() >= head.len());
+        assert!(data.starts_with(&decompressed));
+    }
 }
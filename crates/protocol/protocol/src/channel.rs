@@ -1,7 +1,7 @@
 //! Channel Types
 
 use alloc::vec::Vec;
-use alloy_primitives::{Bytes, map::HashMap};
+use alloy_primitives::{B256, Bytes, keccak256, map::HashMap};
 
 use crate::{BlockInfo, Frame};
 
@@ -11,6 +11,25 @@ pub const CHANNEL_ID_LENGTH: usize = 16;
 /// [`ChannelId`] is an opaque identifier for a channel.
 pub type ChannelId = [u8; CHANNEL_ID_LENGTH];
 
+/// Generates a random [`ChannelId`] from the given random number generator.
+///
+/// Channel IDs must be unique among the channels that are open at the same time, so batchers
+/// should use a cryptographically secure generator when one is available.
+#[cfg(feature = "rand")]
+pub fn random_channel_id<R: rand::RngCore + ?Sized>(rng: &mut R) -> ChannelId {
+    let mut id = ChannelId::default();
+    rng.fill_bytes(&mut id);
+    id
+}
+
+/// Derives a [`ChannelId`] from the given seed: the first [`CHANNEL_ID_LENGTH`] bytes of its
+/// keccak256 hash. Useful to derive reproducible channel IDs, e.g. in tests or tooling.
+pub fn derive_channel_id(seed: &[u8]) -> ChannelId {
+    let mut id = ChannelId::default();
+    id.copy_from_slice(&keccak256(seed)[..CHANNEL_ID_LENGTH]);
+    id
+}
+
 /// [`MAX_RLP_BYTES_PER_CHANNEL`] is the maximum amount of bytes that will be read from
 /// a channel. This limit is set when decoding the RLP.
 pub const MAX_RLP_BYTES_PER_CHANNEL: u64 = 10_000_000;
@@ -28,9 +47,12 @@ pub enum ChannelError {
     /// The channel is closed.
     #[error("Channel is closed")]
     ChannelClosed,
-    /// The frame number is already in the channel.
+    /// The frame number is already in the channel, with the same content.
     #[error("Frame number {0} already exists")]
     FrameNumberExists(usize),
+    /// The frame number is already in the channel, with a different content.
+    #[error("Frame number {0} conflicts with an existing frame")]
+    ConflictingFrame(usize),
     /// The frame number is beyond the end frame.
     #[error("Frame number {0} is beyond end frame")]
     FrameBeyondEndFrame(usize),
@@ -104,8 +126,11 @@ impl Channel {
         if frame.is_last && self.closed {
             return Err(ChannelError::ChannelClosed);
         }
-        if self.inputs.contains_key(&frame.number) {
-            return Err(ChannelError::FrameNumberExists(frame.number as usize));
+        if let Some(existing) = self.inputs.get(&frame.number) {
+            if *existing == frame {
+                return Err(ChannelError::FrameNumberExists(frame.number as usize));
+            }
+            return Err(ChannelError::ConflictingFrame(frame.number as usize));
         }
         if self.closed && frame.number >= self.last_frame_number {
             return Err(ChannelError::FrameBeyondEndFrame(frame.number as usize));
@@ -193,6 +218,101 @@ impl Channel {
     }
 }
 
+/// An error returned by a [`FrameSequence`] when a frame is out of sequence.
+#[derive(Debug, thiserror::Error, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FrameSequenceError {
+    /// The frame id does not match the channel id.
+    #[error("Frame id does not match channel id")]
+    FrameIdMismatch,
+    /// The frame follows the last frame of the channel.
+    #[error("Frame number {0} follows the last frame")]
+    FrameAfterLast(u16),
+    /// The frame was already seen, with the same content.
+    #[error("Frame number {0} is a duplicate")]
+    DuplicateFrame(u16),
+    /// A frame with the same number but a different content was already seen.
+    #[error("Frame number {0} conflicts with an earlier frame")]
+    ConflictingFrame(u16),
+    /// The frame skips frame numbers.
+    #[error("Expected frame number {expected}, got {got}")]
+    FrameNumberGap {
+        /// The next expected frame number.
+        expected: u16,
+        /// The frame number that was received.
+        got: u16,
+    },
+}
+
+/// Tracks the frames of a single channel in the order they are emitted, checking that frame
+/// numbers are contiguous and increasing, and detecting duplicate and conflicting frames.
+///
+/// Unlike a [`Channel`], which buffers frames received in any order for reading, a
+/// [`FrameSequence`] only keeps the hash of each frame. It is meant for batchers and tooling that
+/// produce or audit the frames of a channel, which must be submitted in order.
+#[derive(Debug, Clone)]
+pub struct FrameSequence {
+    /// The channel id.
+    id: ChannelId,
+    /// The hash of each frame, indexed by frame number.
+    frames: Vec<B256>,
+    /// Whether the last frame was seen.
+    closed: bool,
+}
+
+impl FrameSequence {
+    /// Creates a new [`FrameSequence`] for the channel with the given [`ChannelId`].
+    pub const fn new(id: ChannelId) -> Self {
+        Self { id, frames: Vec::new(), closed: false }
+    }
+
+    /// Returns the [`ChannelId`] of the tracked channel.
+    pub const fn id(&self) -> ChannelId {
+        self.id
+    }
+
+    /// Returns the number of the next expected frame.
+    pub const fn next_frame_number(&self) -> u16 {
+        self.frames.len() as u16
+    }
+
+    /// Returns whether the last frame of the channel was seen.
+    pub const fn is_closed(&self) -> bool {
+        self.closed
+    }
+
+    /// Checks the given [`Frame`] against the frames seen so far, and records it if it is the
+    /// next frame of the channel.
+    pub fn push(&mut self, frame: &Frame) -> Result<(), FrameSequenceError> {
+        if frame.id != self.id {
+            return Err(FrameSequenceError::FrameIdMismatch);
+        }
+
+        let hash = Self::frame_hash(frame);
+        if let Some(seen) = self.frames.get(frame.number as usize) {
+            if *seen == hash {
+                return Err(FrameSequenceError::DuplicateFrame(frame.number));
+            }
+            return Err(FrameSequenceError::ConflictingFrame(frame.number));
+        }
+        if self.closed {
+            return Err(FrameSequenceError::FrameAfterLast(frame.number));
+        }
+        let expected = self.next_frame_number();
+        if frame.number != expected {
+            return Err(FrameSequenceError::FrameNumberGap { expected, got: frame.number });
+        }
+
+        self.frames.push(hash);
+        self.closed = frame.is_last;
+        Ok(())
+    }
+
+    /// Hashes the content of the given frame.
+    fn frame_hash(frame: &Frame) -> B256 {
+        keccak256(frame.encode())
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -239,6 +359,62 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_derive_channel_id() {
+        assert_eq!(derive_channel_id(b"kona"), derive_channel_id(b"kona"));
+        assert_ne!(derive_channel_id(b"kona"), derive_channel_id(b"kona2"));
+    }
+
+    #[test]
+    fn test_conflicting_frame() {
+        let id = [0xFF; 16];
+        let block = BlockInfo::default();
+        let mut channel = Channel::new(id, block);
+        let frame = Frame { id, number: 0, data: b"seven__".to_vec(), is_last: false };
+
+        channel.add_frame(frame.clone(), block).unwrap();
+        assert_eq!(
+            channel.add_frame(frame.clone(), block),
+            Err(ChannelError::FrameNumberExists(0))
+        );
+        let conflicting = Frame { data: b"eight___".to_vec(), ..frame };
+        assert_eq!(channel.add_frame(conflicting, block), Err(ChannelError::ConflictingFrame(0)));
+    }
+
+    #[test]
+    fn test_frame_sequence() {
+        let id = [0xFF; 16];
+        let frame =
+            |number, data: &[u8], is_last| Frame { id, number, data: data.to_vec(), is_last };
+        let mut sequence = FrameSequence::new(id);
+
+        sequence.push(&frame(0, b"zero", false)).unwrap();
+        assert_eq!(
+            sequence.push(&frame(2, b"two", false)),
+            Err(FrameSequenceError::FrameNumberGap { expected: 1, got: 2 })
+        );
+        assert_eq!(
+            sequence.push(&frame(0, b"zero", false)),
+            Err(FrameSequenceError::DuplicateFrame(0))
+        );
+        assert_eq!(
+            sequence.push(&frame(0, b"other", false)),
+            Err(FrameSequenceError::ConflictingFrame(0))
+        );
+        assert_eq!(
+            sequence.push(&Frame { id: [0; 16], ..frame(1, b"one", false) }),
+            Err(FrameSequenceError::FrameIdMismatch)
+        );
+
+        sequence.push(&frame(1, b"one", true)).unwrap();
+        assert!(sequence.is_closed());
+        assert_eq!(
+            sequence.push(&frame(2, b"two", false)),
+            Err(FrameSequenceError::FrameAfterLast(2))
+        );
+        assert_eq!(sequence.next_frame_number(), 2);
+    }
+
     #[test]
     fn test_channel_accessors() {
         let id = [0xFF; 16];
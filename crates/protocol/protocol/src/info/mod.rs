@@ -15,6 +15,9 @@ pub use ecotone::L1BlockInfoEcotone;
 mod jovian;
 pub use jovian::L1BlockInfoJovian;
 
+mod storage;
+pub use storage::StateStorageProvider;
+
 mod errors;
 pub use errors::{BlockInfoError, DecodeError};
 
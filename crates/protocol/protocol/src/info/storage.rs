@@ -0,0 +1,265 @@
+//! Reconstruction of the [`L1BlockInfoTx`] from the storage of the L1Block predeploy.
+
+use alloy_primitives::{Address, U256};
+use kona_genesis::RollupConfig;
+
+use crate::{
+    L1BlockInfoBedrock, L1BlockInfoEcotone, L1BlockInfoIsthmus, L1BlockInfoJovian, L1BlockInfoTx,
+    L1BlockSlots, Predeploys,
+};
+
+/// A provider of the storage of the L2 state, such as the state after the execution of a block.
+pub trait StateStorageProvider {
+    /// The error type of the provider.
+    type Error;
+
+    /// Returns the value of the given storage slot of the given account, which is zero if the
+    /// slot or the account does not exist.
+    fn storage_at(&mut self, address: Address, slot: U256) -> Result<U256, Self::Error>;
+}
+
+impl L1BlockInfoTx {
+    /// Reconstructs the [`L1BlockInfoTx`] from the storage of the [`Predeploys::L1_BLOCK_INFO`]
+    /// contract, after the execution of the L2 block with the given timestamp.
+    ///
+    /// The variant is selected from the hardforks active at the timestamp, as in
+    /// [`L1BlockInfoTx::try_new`], so that the result can be compared against the L1 info
+    /// transaction of the derived attributes. As with [`L1BlockInfoTx::decode_calldata`], the
+    /// fields that are not part of the calldata of the variant are left to their default.
+    pub fn from_l1_block_storage<P: StateStorageProvider>(
+        rollup_config: &RollupConfig,
+        l2_block_time: u64,
+        provider: &mut P,
+    ) -> Result<Self, P::Error> {
+        let mut read = |slot| provider.storage_at(Predeploys::L1_BLOCK_INFO, slot);
+
+        let number_time = read(L1BlockSlots::NUMBER_TIMESTAMP)?;
+        let number = u64::from_be_bytes(packed(number_time, 0));
+        let time = u64::from_be_bytes(packed(number_time, 8));
+        let base_fee = read(L1BlockSlots::BASE_FEE)?.saturating_to();
+        let block_hash = read(L1BlockSlots::HASH)?.into();
+        let scalars = read(L1BlockSlots::SEQUENCE_NUMBER_FEE_SCALARS)?;
+        let sequence_number = u64::from_be_bytes(packed(scalars, 0));
+        let batcher_address = Address::from(packed::<20>(read(L1BlockSlots::BATCHER_HASH)?, 0));
+
+        if !rollup_config.is_ecotone_active(l2_block_time) ||
+            rollup_config.is_first_ecotone_block(l2_block_time)
+        {
+            return Ok(Self::Bedrock(L1BlockInfoBedrock {
+                number,
+                time,
+                base_fee,
+                block_hash,
+                sequence_number,
+                batcher_address,
+                l1_fee_overhead: read(L1BlockSlots::L1_FEE_OVERHEAD)?,
+                l1_fee_scalar: read(L1BlockSlots::L1_FEE_SCALAR)?,
+            }));
+        }
+
+        let blob_base_fee = read(L1BlockSlots::BLOB_BASE_FEE)?.saturating_to();
+        let blob_base_fee_scalar = u32::from_be_bytes(packed(scalars, 8));
+        let base_fee_scalar = u32::from_be_bytes(packed(scalars, 12));
+
+        if rollup_config.is_isthmus_active(l2_block_time) &&
+            !rollup_config.is_first_isthmus_block(l2_block_time)
+        {
+            let operator_fee_params = read(L1BlockSlots::OPERATOR_FEE_PARAMS)?;
+            let operator_fee_constant = u64::from_be_bytes(packed(operator_fee_params, 0));
+            let operator_fee_scalar = u32::from_be_bytes(packed(operator_fee_params, 8));
+
+            if rollup_config.is_jovian_active(l2_block_time) &&
+                !rollup_config.is_first_jovian_block(l2_block_time)
+            {
+                return Ok(Self::Jovian(L1BlockInfoJovian {
+                    number,
+                    time,
+                    base_fee,
+                    block_hash,
+                    sequence_number,
+                    batcher_address,
+                    blob_base_fee,
+                    blob_base_fee_scalar,
+                    base_fee_scalar,
+                    operator_fee_scalar,
+                    operator_fee_constant,
+                    da_footprint_gas_scalar: u16::from_be_bytes(packed(operator_fee_params, 12)),
+                }));
+            }
+
+            return Ok(Self::Isthmus(L1BlockInfoIsthmus {
+                number,
+                time,
+                base_fee,
+                block_hash,
+                sequence_number,
+                batcher_address,
+                blob_base_fee,
+                blob_base_fee_scalar,
+                base_fee_scalar,
+                operator_fee_scalar,
+                operator_fee_constant,
+            }));
+        }
+
+        Ok(Self::Ecotone(L1BlockInfoEcotone {
+            number,
+            time,
+            base_fee,
+            block_hash,
+            sequence_number,
+            batcher_address,
+            blob_base_fee,
+            blob_base_fee_scalar,
+            base_fee_scalar,
+            empty_scalars: false,
+            l1_fee_overhead: U256::ZERO,
+        }))
+    }
+}
+
+/// Returns the `N` bytes of the field packed at the given byte offset of a slot value, the offset
+/// being counted from the low-order end of the slot as in the Solidity storage layout.
+fn packed<const N: usize>(value: U256, offset: usize) -> [u8; N] {
+    let bytes = value.to_be_bytes::<32>();
+    let end = 32 - offset;
+    let mut field = [0u8; N];
+    field.copy_from_slice(&bytes[end - N..end]);
+    field
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{B256, map::HashMap};
+    use core::convert::Infallible;
+    use kona_genesis::HardForkConfig;
+
+    /// The storage of the L1Block predeploy.
+    #[derive(Default)]
+    struct MockStorage(HashMap<U256, U256>);
+
+    impl StateStorageProvider for MockStorage {
+        type Error = Infallible;
+
+        fn storage_at(&mut self, address: Address, slot: U256) -> Result<U256, Self::Error> {
+            assert_eq!(address, Predeploys::L1_BLOCK_INFO);
+            Ok(self.0.get(&slot).copied().unwrap_or_default())
+        }
+    }
+
+    /// Returns the storage written by the `setL1BlockValuesJovian` call of the given info.
+    fn jovian_storage(info: &L1BlockInfoJovian) -> MockStorage {
+        let word = |fields: &[(u64, usize)]| {
+            fields.iter().fold(U256::ZERO, |word, (value, offset)| {
+                word | (U256::from(*value) << (offset * 8))
+            })
+        };
+        MockStorage(HashMap::from_iter([
+            (L1BlockSlots::NUMBER_TIMESTAMP, word(&[(info.number, 0), (info.time, 8)])),
+            (L1BlockSlots::BASE_FEE, U256::from(info.base_fee)),
+            (L1BlockSlots::HASH, info.block_hash.into()),
+            (
+                L1BlockSlots::SEQUENCE_NUMBER_FEE_SCALARS,
+                word(&[
+                    (info.sequence_number, 0),
+                    (info.blob_base_fee_scalar as u64, 8),
+                    (info.base_fee_scalar as u64, 12),
+                ]),
+            ),
+            (
+                L1BlockSlots::BATCHER_HASH,
+                B256::left_padding_from(info.batcher_address.as_slice()).into(),
+            ),
+            (L1BlockSlots::BLOB_BASE_FEE, U256::from(info.blob_base_fee)),
+            (
+                L1BlockSlots::OPERATOR_FEE_PARAMS,
+                word(&[
+                    (info.operator_fee_constant, 0),
+                    (info.operator_fee_scalar as u64, 8),
+                    (info.da_footprint_gas_scalar as u64, 12),
+                ]),
+            ),
+        ]))
+    }
+
+    fn jovian_info() -> L1BlockInfoJovian {
+        L1BlockInfoJovian {
+            number: 0x1234,
+            time: 0x5678,
+            base_fee: 7,
+            block_hash: B256::repeat_byte(0xab),
+            sequence_number: 3,
+            batcher_address: Address::repeat_byte(0xcd),
+            blob_base_fee: 11,
+            blob_base_fee_scalar: 13,
+            base_fee_scalar: 17,
+            operator_fee_scalar: 19,
+            operator_fee_constant: 23,
+            da_footprint_gas_scalar: 29,
+        }
+    }
+
+    #[test]
+    fn test_from_l1_block_storage_jovian() {
+        let rollup_config = RollupConfig {
+            hardforks: HardForkConfig { jovian_time: Some(0), ..Default::default() },
+            block_time: 2,
+            ..Default::default()
+        };
+        let info = jovian_info();
+        let mut storage = jovian_storage(&info);
+
+        let reconstructed =
+            L1BlockInfoTx::from_l1_block_storage(&rollup_config, 2, &mut storage).unwrap();
+        assert_eq!(reconstructed, L1BlockInfoTx::Jovian(info));
+    }
+
+    #[test]
+    fn test_from_l1_block_storage_first_jovian_block() {
+        let rollup_config = RollupConfig {
+            hardforks: HardForkConfig {
+                isthmus_time: Some(0),
+                jovian_time: Some(2),
+                ..Default::default()
+            },
+            block_time: 2,
+            ..Default::default()
+        };
+        let info = jovian_info();
+        let mut storage = jovian_storage(&info);
+
+        let reconstructed =
+            L1BlockInfoTx::from_l1_block_storage(&rollup_config, 2, &mut storage).unwrap();
+        let L1BlockInfoTx::Isthmus(isthmus) = reconstructed else {
+            panic!("expected an Isthmus L1 info, got {reconstructed:?}");
+        };
+        assert_eq!(isthmus.operator_fee_scalar, info.operator_fee_scalar);
+        assert_eq!(isthmus.operator_fee_constant, info.operator_fee_constant);
+    }
+
+    #[test]
+    fn test_from_l1_block_storage_bedrock() {
+        let info = jovian_info();
+        let mut storage = jovian_storage(&info);
+        storage.0.insert(L1BlockSlots::L1_FEE_OVERHEAD, U256::from(0xbc));
+        storage.0.insert(L1BlockSlots::L1_FEE_SCALAR, U256::from(0xa6fe0));
+
+        let reconstructed =
+            L1BlockInfoTx::from_l1_block_storage(&RollupConfig::default(), 2, &mut storage)
+                .unwrap();
+        assert_eq!(
+            reconstructed,
+            L1BlockInfoTx::Bedrock(L1BlockInfoBedrock {
+                number: info.number,
+                time: info.time,
+                base_fee: info.base_fee,
+                block_hash: info.block_hash,
+                sequence_number: info.sequence_number,
+                batcher_address: info.batcher_address,
+                l1_fee_overhead: U256::from(0xbc),
+                l1_fee_scalar: U256::from(0xa6fe0),
+            })
+        );
+    }
+}
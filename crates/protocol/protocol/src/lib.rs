@@ -11,13 +11,13 @@ extern crate alloc;
 
 mod batch;
 pub use batch::{
-    Batch, BatchDecodingError, BatchEncodingError, BatchReader, BatchTransaction, BatchType,
-    BatchValidationProvider, BatchValidity, BatchWithInclusionBlock, DecompressionError,
-    MAX_SPAN_BATCH_ELEMENTS, RawSpanBatch, SINGLE_BATCH_TYPE, SPAN_BATCH_TYPE, SingleBatch,
-    SpanBatch, SpanBatchBits, SpanBatchEip1559TransactionData, SpanBatchEip2930TransactionData,
-    SpanBatchEip7702TransactionData, SpanBatchElement, SpanBatchError,
-    SpanBatchLegacyTransactionData, SpanBatchPayload, SpanBatchPrefix, SpanBatchTransactionData,
-    SpanBatchTransactions, SpanDecodingError,
+    Batch, BatchDecodingError, BatchEncodingError, BatchReader, BatchReaderError, BatchTransaction,
+    BatchType, BatchValidationProvider, BatchValidity, BatchWithInclusionBlock, Batches,
+    DecompressionError, MAX_SPAN_BATCH_ELEMENTS, RawSpanBatch, SINGLE_BATCH_TYPE, SPAN_BATCH_TYPE,
+    SingleBatch, SpanBatch, SpanBatchBits, SpanBatchEip1559TransactionData,
+    SpanBatchEip2930TransactionData, SpanBatchEip7702TransactionData, SpanBatchElement,
+    SpanBatchError, SpanBatchLegacyTransactionData, SpanBatchPayload, SpanBatchPrefix,
+    SpanBatchTransactionData, SpanBatchTransactions, SpanDecodingError,
 };
 
 mod brotli;
@@ -44,9 +44,11 @@ mod utils;
 pub use utils::{read_tx_data, to_system_config};
 
 mod channel;
+#[cfg(feature = "rand")]
+pub use channel::random_channel_id;
 pub use channel::{
     CHANNEL_ID_LENGTH, Channel, ChannelError, ChannelId, FJORD_MAX_RLP_BYTES_PER_CHANNEL,
-    MAX_RLP_BYTES_PER_CHANNEL,
+    FrameSequence, FrameSequenceError, MAX_RLP_BYTES_PER_CHANNEL, derive_channel_id,
 };
 
 mod deposits;
@@ -55,14 +57,23 @@ pub use deposits::{
     decode_deposit,
 };
 
+mod source;
+pub use source::{
+    AFTER_FORCE_INCLUDE_SOURCE_DOMAIN, AfterForceIncludeSource, DepositSourceDomain,
+    L1InfoDepositSource, UpgradeDepositSource, UserDepositSource,
+};
+
 mod info;
 pub use info::{
     BlockInfoError, DecodeError, L1BlockInfoBedrock, L1BlockInfoEcotone, L1BlockInfoIsthmus,
-    L1BlockInfoJovian, L1BlockInfoTx,
+    L1BlockInfoJovian, L1BlockInfoTx, StateStorageProvider,
 };
 
 mod predeploys;
-pub use predeploys::Predeploys;
+pub use predeploys::{
+    FeeVaultSlots, L1BlockSlots, MessagePasserSlots, Predeploys, ProxySlots, mapping_slot,
+    namespaced_slot,
+};
 
 mod output_root;
 pub use output_root::OutputRoot;
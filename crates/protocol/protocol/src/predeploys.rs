@@ -1,141 +1,138 @@
-//! Addresses of OP pre-deploys.
+//! Addresses and storage slots of OP pre-deploys.
 //!
-//! This module contains the addresses of various predeploy contracts in the OP Stack.
+//! This module contains the storage slots of the predeploys that are read directly from the
+//! state, and re-exports the [`Predeploys`] addresses defined in [`kona_genesis`].
 //! See the complete set of predeploys at <https://specs.optimism.io/protocol/predeploys.html#predeploys>
 
-use alloy_primitives::{Address, address};
+use alloy_primitives::{B256, U256, b256, keccak256};
 
-/// Container for all predeploy contract addresses
+pub use kona_genesis::Predeploys;
+
+/// Storage slots of the [`Predeploys::L1_BLOCK_INFO`] contract.
+///
+/// Several fields are packed into a single slot; the byte offsets of the packed fields are given
+/// from the low-order end of the slot, as in the Solidity storage layout.
+/// <https://github.com/ethereum-optimism/optimism/blob/develop/packages/contracts-bedrock/src/L2/L1Block.sol>
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct L1BlockSlots;
+
+impl L1BlockSlots {
+    /// The slot packing the L1 block `number` (`uint64`, offset 0) and `timestamp` (`uint64`,
+    /// offset 8).
+    pub const NUMBER_TIMESTAMP: U256 = U256::from_limbs([0, 0, 0, 0]);
+
+    /// The slot of the L1 `basefee` (`uint256`).
+    pub const BASE_FEE: U256 = U256::from_limbs([1, 0, 0, 0]);
+
+    /// The slot of the L1 block `hash` (`bytes32`).
+    pub const HASH: U256 = U256::from_limbs([2, 0, 0, 0]);
+
+    /// The slot packing the `sequenceNumber` (`uint64`, offset 0), `blobBaseFeeScalar`
+    /// (`uint32`, offset 8) and `baseFeeScalar` (`uint32`, offset 12), since Ecotone.
+    pub const SEQUENCE_NUMBER_FEE_SCALARS: U256 = U256::from_limbs([3, 0, 0, 0]);
+
+    /// The slot of the `batcherHash` (`bytes32`).
+    pub const BATCHER_HASH: U256 = U256::from_limbs([4, 0, 0, 0]);
+
+    /// The slot of the `l1FeeOverhead` (`uint256`), before Ecotone.
+    pub const L1_FEE_OVERHEAD: U256 = U256::from_limbs([5, 0, 0, 0]);
+
+    /// The slot of the `l1FeeScalar` (`uint256`), before Ecotone.
+    pub const L1_FEE_SCALAR: U256 = U256::from_limbs([6, 0, 0, 0]);
+
+    /// The slot of the L1 `blobBaseFee` (`uint256`), since Ecotone.
+    pub const BLOB_BASE_FEE: U256 = U256::from_limbs([7, 0, 0, 0]);
+
+    /// The slot packing the `operatorFeeConstant` (`uint64`, offset 0) and `operatorFeeScalar`
+    /// (`uint32`, offset 8) since Isthmus, and the `daFootprintGasScalar` (`uint16`, offset 12)
+    /// since Jovian.
+    pub const OPERATOR_FEE_PARAMS: U256 = U256::from_limbs([8, 0, 0, 0]);
+}
+
+/// Storage slots of the [`Predeploys::L2_TO_L1_MESSAGE_PASSER`] contract.
+/// <https://specs.optimism.io/protocol/withdrawals.html#the-l2tol1messagepasser-contract>
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct MessagePasserSlots;
+
+impl MessagePasserSlots {
+    /// The slot of the `sentMessages` mapping, from withdrawal hashes to whether they were sent.
+    pub const SENT_MESSAGES: U256 = U256::from_limbs([0, 0, 0, 0]);
+
+    /// The slot of the `msgNonce` (`uint240`).
+    pub const MESSAGE_NONCE: U256 = U256::from_limbs([1, 0, 0, 0]);
+
+    /// Returns the slot of the `sentMessages` entry of the given withdrawal hash, which is set to
+    /// `1` once the withdrawal is initiated.
+    pub fn sent_message(withdrawal_hash: B256) -> B256 {
+        mapping_slot(withdrawal_hash, Self::SENT_MESSAGES)
+    }
+}
+
+/// Storage slots of the fee vault predeploys: [`Predeploys::SEQUENCER_FEE_VAULT`],
+/// [`Predeploys::BASE_FEE_VAULT`], [`Predeploys::L1_FEE_VAULT`] and
+/// [`Predeploys::OPERATOR_FEE_VAULT`].
+///
+/// The recipient, minimum withdrawal amount and withdrawal network of the vaults are immutables
+/// of their implementations, and are not held in storage.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct FeeVaultSlots;
+
+impl FeeVaultSlots {
+    /// The slot of the `totalProcessed` amount of fees withdrawn (`uint256`).
+    pub const TOTAL_PROCESSED: U256 = U256::from_limbs([0, 0, 0, 0]);
+}
+
+/// Storage slots of the EIP-1967 proxies that the predeploys are deployed behind.
+/// <https://eips.ethereum.org/EIPS/eip-1967>
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 #[non_exhaustive]
-pub struct Predeploys;
-
-impl Predeploys {
-    /// List of all predeploys.
-    pub const ALL: [Address; 24] = [
-        Self::LEGACY_MESSAGE_PASSER,
-        Self::DEPLOYER_WHITELIST,
-        Self::LEGACY_ERC20_ETH,
-        Self::WETH9,
-        Self::L2_CROSS_DOMAIN_MESSENGER,
-        Self::L2_STANDARD_BRIDGE,
-        Self::SEQUENCER_FEE_VAULT,
-        Self::OP_MINTABLE_ERC20_FACTORY,
-        Self::L1_BLOCK_NUMBER,
-        Self::GAS_PRICE_ORACLE,
-        Self::GOVERNANCE_TOKEN,
-        Self::L1_BLOCK_INFO,
-        Self::L2_TO_L1_MESSAGE_PASSER,
-        Self::L2_ERC721_BRIDGE,
-        Self::OP_MINTABLE_ERC721_FACTORY,
-        Self::PROXY_ADMIN,
-        Self::BASE_FEE_VAULT,
-        Self::L1_FEE_VAULT,
-        Self::SCHEMA_REGISTRY,
-        Self::EAS,
-        Self::BEACON_BLOCK_ROOT,
-        Self::OPERATOR_FEE_VAULT,
-        Self::CROSS_L2_INBOX,
-        Self::L2_TO_L2_XDM,
-    ];
-
-    /// The LegacyMessagePasser contract stores commitments to withdrawal transactions before the
-    /// Bedrock upgrade.
-    /// <https://specs.optimism.io/protocol/predeploys.html#legacymessagepasser>
-    pub const LEGACY_MESSAGE_PASSER: Address =
-        address!("0x4200000000000000000000000000000000000000");
-
-    /// The DeployerWhitelist was used to provide additional safety during initial phases of
-    /// Optimism.
-    /// <https://specs.optimism.io/protocol/predeploys.html#deployerwhitelist>
-    pub const DEPLOYER_WHITELIST: Address = address!("0x4200000000000000000000000000000000000002");
-
-    /// The LegacyERC20ETH predeploy represented all ether in the system before the Bedrock upgrade.
-    /// <https://specs.optimism.io/protocol/predeploys.html#legacyerc20eth>
-    pub const LEGACY_ERC20_ETH: Address = address!("0xDeadDeAddeAddEAddeadDEaDDEAdDeaDDeAD0000");
-
-    /// The WETH9 predeploy address.
-    /// <https://specs.optimism.io/protocol/predeploys.html#weth9>
-    pub const WETH9: Address = address!("0x4200000000000000000000000000000000000006");
-
-    /// Higher level API for sending cross domain messages.
-    /// <https://specs.optimism.io/protocol/predeploys.html#l2crossdomainmessenger>
-    pub const L2_CROSS_DOMAIN_MESSENGER: Address =
-        address!("0x4200000000000000000000000000000000000007");
-
-    /// The L2 cross-domain messenger proxy address.
-    /// <https://specs.optimism.io/protocol/predeploys.html#l2standardbridge>
-    pub const L2_STANDARD_BRIDGE: Address = address!("0x4200000000000000000000000000000000000010");
-
-    /// The sequencer fee vault proxy address.
-    /// <https://specs.optimism.io/protocol/predeploys.html#sequencerfeevault>
-    pub const SEQUENCER_FEE_VAULT: Address = address!("0x4200000000000000000000000000000000000011");
-
-    /// The Optimism mintable ERC20 factory proxy address.
-    /// <https://specs.optimism.io/protocol/predeploys.html#optimismmintableerc20factory>
-    pub const OP_MINTABLE_ERC20_FACTORY: Address =
-        address!("0x4200000000000000000000000000000000000012");
-
-    /// Returns the last known L1 block number (legacy system).
-    /// <https://specs.optimism.io/protocol/predeploys.html#l1blocknumber>
-    pub const L1_BLOCK_NUMBER: Address = address!("0x4200000000000000000000000000000000000013");
-
-    /// The gas price oracle proxy address.
-    /// <https://specs.optimism.io/protocol/predeploys.html#gaspriceoracle>
-    pub const GAS_PRICE_ORACLE: Address = address!("0x420000000000000000000000000000000000000F");
-
-    /// The governance token proxy address.
-    /// <https://specs.optimism.io/governance/gov-token.html>
-    pub const GOVERNANCE_TOKEN: Address = address!("0x4200000000000000000000000000000000000042");
-
-    /// The L1 block information proxy address.
-    /// <https://specs.optimism.io/protocol/predeploys.html#l1block>
-    pub const L1_BLOCK_INFO: Address = address!("0x4200000000000000000000000000000000000015");
-
-    /// The L2 contract `L2ToL1MessagePasser`, stores commitments to withdrawal transactions.
-    /// <https://specs.optimism.io/protocol/predeploys.html#l2tol1messagepasser>
-    pub const L2_TO_L1_MESSAGE_PASSER: Address =
-        address!("0x4200000000000000000000000000000000000016");
-
-    /// The L2 ERC721 bridge proxy address.
-    /// <https://specs.optimism.io/protocol/predeploys.html>
-    pub const L2_ERC721_BRIDGE: Address = address!("0x4200000000000000000000000000000000000014");
-
-    /// The Optimism mintable ERC721 proxy address.
-    /// <https://specs.optimism.io/protocol/predeploys.html#optimismmintableerc721factory>
-    pub const OP_MINTABLE_ERC721_FACTORY: Address =
-        address!("0x4200000000000000000000000000000000000017");
-
-    /// The L2 proxy admin address.
-    /// <https://specs.optimism.io/protocol/predeploys.html#proxyadmin>
-    pub const PROXY_ADMIN: Address = address!("0x4200000000000000000000000000000000000018");
-
-    /// The base fee vault address.
-    /// <https://specs.optimism.io/protocol/predeploys.html#basefeevault>
-    pub const BASE_FEE_VAULT: Address = address!("0x4200000000000000000000000000000000000019");
-
-    /// The L1 fee vault address.
-    /// <https://specs.optimism.io/protocol/predeploys.html#l1feevault>
-    pub const L1_FEE_VAULT: Address = address!("0x420000000000000000000000000000000000001a");
-
-    /// The schema registry proxy address.
-    /// <https://specs.optimism.io/protocol/predeploys.html#schemaregistry>
-    pub const SCHEMA_REGISTRY: Address = address!("0x4200000000000000000000000000000000000020");
-
-    /// The EAS proxy address.
-    /// <https://specs.optimism.io/protocol/predeploys.html#eas>
-    pub const EAS: Address = address!("0x4200000000000000000000000000000000000021");
-
-    /// Provides access to L1 beacon block roots (EIP-4788).
-    /// <https://specs.optimism.io/protocol/predeploys.html#beacon-block-root>
-    pub const BEACON_BLOCK_ROOT: Address = address!("0x000F3df6D732807Ef1319fB7B8bB8522d0Beac02");
-
-    /// The Operator Fee Vault proxy address.
-    pub const OPERATOR_FEE_VAULT: Address = address!("0x420000000000000000000000000000000000001B");
-
-    /// The CrossL2Inbox proxy address.
-    pub const CROSS_L2_INBOX: Address = address!("0x4200000000000000000000000000000000000022");
-
-    /// The L2ToL2CrossDomainMessenger proxy address.
-    pub const L2_TO_L2_XDM: Address = address!("0x4200000000000000000000000000000000000023");
+pub struct ProxySlots;
+
+impl ProxySlots {
+    /// The slot of the implementation address, computed as
+    /// `bytes32(uint256(keccak256("eip1967.proxy.implementation")) - 1)`.
+    pub const IMPLEMENTATION: B256 =
+        b256!("0x360894a13ba1a3210667c828492db98dca3e2076cc3735a920a3ca505d382bbc");
+
+    /// The slot of the admin address, computed as
+    /// `bytes32(uint256(keccak256("eip1967.proxy.admin")) - 1)`.
+    pub const ADMIN: B256 =
+        b256!("0xb53127684a568b3173ae13b9f8a6016e243e63b6e8ee1178d6a717850b5d6103");
+}
+
+/// Returns the storage slot of the entry of the given key in a Solidity mapping stored at the
+/// given slot, computed as `keccak256(key . slot)`.
+pub fn mapping_slot(key: B256, slot: U256) -> B256 {
+    let mut preimage = [0u8; 64];
+    preimage[..32].copy_from_slice(key.as_slice());
+    preimage[32..].copy_from_slice(&slot.to_be_bytes::<32>());
+    keccak256(preimage)
+}
+
+/// Returns the storage slot derived from the given namespace, as used by the OP Stack contracts
+/// and EIP-1967 proxies, computed as `bytes32(uint256(keccak256(namespace)) - 1)`.
+pub fn namespaced_slot(namespace: &str) -> B256 {
+    (U256::from_be_bytes(keccak256(namespace).0) - U256::from(1)).into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_proxy_slots() {
+        assert_eq!(ProxySlots::IMPLEMENTATION, namespaced_slot("eip1967.proxy.implementation"));
+        assert_eq!(ProxySlots::ADMIN, namespaced_slot("eip1967.proxy.admin"));
+    }
+
+    #[test]
+    fn test_sent_message_slot() {
+        assert_eq!(
+            MessagePasserSlots::sent_message(B256::ZERO),
+            b256!("0xad3228b676f7d3cd4284a5443f17f1962b36e491b30a40b2405849e597ba5fb5")
+        );
+    }
 }
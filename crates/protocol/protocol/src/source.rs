@@ -0,0 +1,67 @@
+//! Source hashes of deposit transactions.
+//!
+//! The source hash of a deposit transaction uniquely identifies the origin of the deposit, so that
+//! two deposits never share a transaction hash. The user, L1 info and upgrade deposit sources are
+//! provided by [`op_alloy_consensus`], and are re-exported here alongside the interop
+//! [`AfterForceIncludeSource`].
+//!
+//! See: <https://specs.optimism.io/protocol/deposits.html#source-hash-computation>
+
+use alloy_primitives::{B256, keccak256};
+
+pub use op_alloy_consensus::{
+    DepositSourceDomain, L1InfoDepositSource, UpgradeDepositSource, UserDepositSource,
+};
+
+/// The source domain of the interop deposits closing the deposit context of an L2 block, after
+/// the force-included user deposits.
+pub const AFTER_FORCE_INCLUDE_SOURCE_DOMAIN: u64 = 3;
+
+/// The source of the interop deposit closing the deposit context of an L2 block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct AfterForceIncludeSource {
+    /// The hash of the L1 origin of the L2 block.
+    pub l1_block_hash: B256,
+    /// The sequence number of the L2 block in its epoch.
+    pub seq_number: u64,
+}
+
+impl AfterForceIncludeSource {
+    /// Creates a new [`AfterForceIncludeSource`].
+    pub const fn new(l1_block_hash: B256, seq_number: u64) -> Self {
+        Self { l1_block_hash, seq_number }
+    }
+
+    /// Returns the source hash of the deposit.
+    pub fn source_hash(&self) -> B256 {
+        let mut input = [0u8; 64];
+        input[..32].copy_from_slice(self.l1_block_hash.as_slice());
+        input[56..].copy_from_slice(&self.seq_number.to_be_bytes());
+        let deposit_id_hash = keccak256(input);
+
+        let mut domain_input = [0u8; 64];
+        domain_input[24..32].copy_from_slice(&AFTER_FORCE_INCLUDE_SOURCE_DOMAIN.to_be_bytes());
+        domain_input[32..].copy_from_slice(deposit_id_hash.as_slice());
+        keccak256(domain_input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::b256;
+
+    const L1_BLOCK_HASH: B256 =
+        b256!("c0c9f8c5a4ec5f4d3a0c8e5d3d2b1a09f8e7d6c5b4a392817060504030201000");
+
+    #[test]
+    fn test_after_force_include_source_hash() {
+        let expected = b256!("983141dade99364fc3538a17b9bb41febf768871321631e1e49c3140016c746c");
+        let source = AfterForceIncludeSource::new(L1_BLOCK_HASH, 2);
+        assert_eq!(source.source_hash(), expected);
+
+        // The same L1 block and sequence number yield a distinct L1 info deposit source hash.
+        let l1_info = L1InfoDepositSource { l1_block_hash: L1_BLOCK_HASH, seq_number: 2 };
+        assert_ne!(DepositSourceDomain::L1Info(l1_info).source_hash(), expected);
+    }
+}
@@ -55,6 +55,12 @@ pub struct SyncStatus {
     /// This points to the L2 block that was derived fully from finalized L1 information, thus
     /// irreversible.
     pub finalized_l2: L2BlockInfo,
+    /// The pending safe L2 block ref.
+    ///
+    /// This points to the last L2 block that was processed from L1 data, which may be part of a
+    /// span batch that has not been fully derived yet, and therefore is not safe.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub pending_safe_l2: L2BlockInfo,
     /// Cross unsafe L2 block ref.
     ///
     /// This is an unsafe L2 block, that has been verified to match cross-L2 dependencies.
@@ -64,4 +70,10 @@ pub struct SyncStatus {
     ///
     /// This is an L2 block derived from L1, not yet verified to have valid cross-L2 dependencies.
     pub local_safe_l2: L2BlockInfo,
+    /// The first queued unsafe L2 block ref.
+    ///
+    /// This is the next unsafe payload, received via p2p or other offchain sources, that is
+    /// awaiting insertion into the unsafe chain. Zeroed out if no payload is queued.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub queued_unsafe_l2: L2BlockInfo,
 }
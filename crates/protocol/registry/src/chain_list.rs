@@ -1,6 +1,6 @@
 //! List of OP Stack chains.
 
-use alloc::{string::String, vec::Vec};
+use alloc::{format, string::String, vec::Vec};
 use alloy_chains::Chain as AlloyChain;
 
 /// List of Chains.
@@ -17,6 +17,13 @@ impl ChainList {
         self.chains.iter().find(|c| c.identifier.eq_ignore_ascii_case(identifier))
     }
 
+    /// Fetch a [Chain] by its network name, e.g. `op-mainnet` or `base-sepolia`.
+    ///
+    /// See [`Chain::network_name`].
+    pub fn get_chain_by_network_name(&self, name: &str) -> Option<&Chain> {
+        self.chains.iter().find(|c| c.network_name().is_some_and(|n| n.eq_ignore_ascii_case(name)))
+    }
+
     /// Returns all available [Chain] identifiers.
     pub fn chain_idents(&self) -> Vec<String> {
         self.chains.iter().map(|c| c.identifier.clone()).collect()
@@ -69,6 +76,17 @@ pub struct Chain {
     pub parent: SuperchainParent,
 }
 
+impl Chain {
+    /// Returns the network name of the chain.
+    ///
+    /// The network name is the chain's [identifier](Self::identifier) with its superchain and
+    /// chain parts swapped and joined by a dash, so `mainnet/op` is named `op-mainnet`.
+    pub fn network_name(&self) -> Option<String> {
+        let (superchain, chain) = self.identifier.split_once('/')?;
+        Some(format!("{chain}-{superchain}"))
+    }
+}
+
 /// A Chain Parent
 #[derive(Debug, Clone, Default, Hash, Eq, PartialEq, serde::Serialize, serde::Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -88,6 +88,18 @@ mod tests {
         assert_eq!(chain_by_alloy_ident, chain_by_id);
     }
 
+    #[test]
+    fn test_chain_by_network_name() {
+        let op_mainnet = CHAINS.get_chain_by_network_name("op-mainnet").unwrap();
+        assert_eq!(op_mainnet.chain_id, 10);
+        assert_eq!(op_mainnet.network_name().as_deref(), Some("op-mainnet"));
+
+        let base_sepolia = CHAINS.get_chain_by_network_name("Base-Sepolia").unwrap();
+        assert_eq!(base_sepolia.chain_id, 84532);
+
+        assert!(CHAINS.get_chain_by_network_name("mainnet/op").is_none());
+    }
+
     #[test]
     fn test_rollup_config_by_ident() {
         const ALLOY_BASE: AlloyChain = AlloyChain::base_mainnet();
@@ -68,6 +68,7 @@ pub const BASE_MAINNET_CONFIG: RollupConfig = RollupConfig {
     da_challenge_address: None,
     blobs_enabled_l1_timestamp: None,
     interop_message_expiry_window: DEFAULT_INTEROP_MESSAGE_EXPIRY_WINDOW,
+    message_passer_address: None,
     alt_da_config: None,
     chain_op_config: BASE_MAINNET_BASE_FEE_CONFIG,
 };
@@ -132,7 +132,7 @@ impl OnlineBeaconClient {
                             let index = index as u64;
                             blob_indexes
                                 .contains(&index)
-                                .then_some(BoxedBlobWithIndex { index, blob: Box::new(blob) })
+                                .then_some(BoxedBlobWithIndex::new(index, Box::new(blob)))
                         })
                         .collect::<Vec<_>>()
                 }
@@ -149,7 +149,7 @@ impl OnlineBeaconClient {
                     .filter_map(|blob| {
                         blob_indexes
                             .contains(&blob.index)
-                            .then_some(BoxedBlobWithIndex { index: blob.index, blob: blob.blob })
+                            .then_some(BoxedBlobWithIndex::new(blob.index, blob.blob))
                     })
                     .collect::<Vec<_>>(),
             },
@@ -0,0 +1,425 @@
+//! Contains a [BeaconClient] that serves blobs from multiple beacon endpoints, verifying their
+//! responses against the versioned hashes of the requested blobs.
+
+#[cfg(feature = "metrics")]
+use crate::Metrics;
+use crate::{
+    APIConfigResponse, APIGenesisResponse, BeaconClient, OnlineBeaconClient,
+    blobs::BoxedBlobWithIndex,
+};
+use alloy_eips::eip4844::{
+    Blob, IndexedBlobHash, env_settings::EnvKzgSettings, kzg_to_versioned_hash,
+};
+use alloy_primitives::FixedBytes;
+use async_trait::async_trait;
+use std::{
+    boxed::Box,
+    string::{String, ToString},
+    sync::{
+        Arc,
+        atomic::{AtomicU64, Ordering},
+    },
+    vec::Vec,
+};
+
+/// How a [RedundantBeaconClient] uses its beacon endpoints.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum BeaconRedundancy {
+    /// Queries the endpoints one after the other, until one of them serves the requested blobs.
+    #[default]
+    Failover,
+    /// Queries all endpoints concurrently, so that every endpoint serving blobs that do not match
+    /// their versioned hashes is flagged, even when another endpoint served valid blobs.
+    CrossCheck,
+}
+
+/// An error returned by the [RedundantBeaconClient].
+#[derive(Debug, thiserror::Error)]
+pub enum RedundantBeaconClientError {
+    /// No beacon endpoint is configured.
+    #[error("No beacon endpoint configured")]
+    NoEndpoints,
+    /// No beacon endpoint served a valid response.
+    #[error("No beacon endpoint served a valid response: {0}")]
+    Unavailable(String),
+}
+
+/// The outcome of checking the blobs served by a beacon endpoint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BlobCheck {
+    /// The endpoint served all requested blobs, matching their versioned hashes.
+    Valid,
+    /// The endpoint did not serve all requested blobs, for example because they expired.
+    Incomplete,
+    /// The endpoint served a blob that does not match its versioned hash.
+    Mismatch,
+}
+
+/// A [BeaconClient] that serves blobs from multiple beacon endpoints.
+///
+/// Every blob is checked against the versioned hash of the requested blob, which commits to the
+/// blob through its KZG commitment. Endpoints serving mismatched blobs are flagged: the mismatch
+/// is counted, and flagged endpoints are queried after the other endpoints in
+/// [BeaconRedundancy::Failover] mode. This protects the node against faulty or malicious
+/// third-party beacon providers. The KZG commitments computed by the checks are returned with the
+/// blobs, so that they are not recomputed.
+///
+/// In [BeaconRedundancy::Failover] mode, the last endpoints may be blob archives, which retain
+/// the blobs that expired from the beacon nodes.
+///
+/// The chain configuration is loaded from the first endpoint that serves it.
+#[derive(Debug, Clone)]
+pub struct RedundantBeaconClient<B = OnlineBeaconClient> {
+    /// The beacon endpoints, in order of preference.
+    endpoints: Vec<B>,
+    /// How the endpoints are used.
+    redundancy: BeaconRedundancy,
+    /// The number of mismatched blobs served by each endpoint, shared between clones.
+    mismatches: Arc<[AtomicU64]>,
+}
+
+impl RedundantBeaconClient {
+    /// Creates a new [RedundantBeaconClient] from the given beacon API URLs, in order of
+    /// preference.
+    pub fn new_http(urls: impl IntoIterator<Item = String>) -> Self {
+        Self::new(urls.into_iter().map(OnlineBeaconClient::new_http).collect())
+    }
+}
+
+impl<B> RedundantBeaconClient<B> {
+    /// Creates a new [RedundantBeaconClient] from the given beacon endpoints, in order of
+    /// preference, in [BeaconRedundancy::Failover] mode.
+    pub fn new(endpoints: Vec<B>) -> Self {
+        let mismatches = endpoints.iter().map(|_| AtomicU64::new(0)).collect();
+        Self { endpoints, redundancy: BeaconRedundancy::default(), mismatches }
+    }
+
+    /// Sets the [BeaconRedundancy] mode.
+    pub const fn with_redundancy(mut self, redundancy: BeaconRedundancy) -> Self {
+        self.redundancy = redundancy;
+        self
+    }
+
+    /// Returns the beacon endpoints, in order of preference.
+    pub fn endpoints(&self) -> &[B] {
+        &self.endpoints
+    }
+
+    /// Returns the number of mismatched blobs served by each endpoint.
+    pub fn mismatches(&self) -> Vec<u64> {
+        self.mismatches.iter().map(|count| count.load(Ordering::Relaxed)).collect()
+    }
+
+    /// Flags the endpoint at the given index for serving a mismatched blob.
+    fn flag(&self, index: usize) {
+        self.mismatches[index].fetch_add(1, Ordering::Relaxed);
+        kona_macros::inc!(
+            counter,
+            Metrics::BEACON_BLOB_MISMATCH_COUNT,
+            "endpoint" => index.to_string()
+        );
+    }
+
+    /// Returns the indices of the endpoints, with the flagged endpoints last.
+    fn ordered(&self) -> Vec<usize> {
+        let mut order = (0..self.endpoints.len()).collect::<Vec<_>>();
+        order.sort_by_key(|&index| self.mismatches[index].load(Ordering::Relaxed) > 0);
+        order
+    }
+}
+
+impl<B> RedundantBeaconClient<B>
+where
+    B: BeaconClient + Send + Sync,
+{
+    /// Returns the error reporting the failures of all endpoints.
+    fn unavailable(errors: Vec<String>) -> RedundantBeaconClientError {
+        if errors.is_empty() {
+            return RedundantBeaconClientError::NoEndpoints;
+        }
+        RedundantBeaconClientError::Unavailable(errors.join("; "))
+    }
+
+    /// Fetches the blobs from the endpoint at the given index.
+    async fn fetch(
+        &self,
+        index: usize,
+        slot: u64,
+        blob_hashes: &[IndexedBlobHash],
+    ) -> Result<Vec<BoxedBlobWithIndex>, String> {
+        self.endpoints[index]
+            .filtered_beacon_blobs(slot, blob_hashes)
+            .await
+            .map_err(|e| e.to_string())
+    }
+
+    /// Checks the blobs served by the endpoint at the given index, flagging the endpoint if they
+    /// do not match. Blobs identical to the given already checked blobs are not checked again.
+    fn check(
+        &self,
+        index: usize,
+        mut blobs: Vec<BoxedBlobWithIndex>,
+        blob_hashes: &[IndexedBlobHash],
+        checked: &[BoxedBlobWithIndex],
+    ) -> Result<Vec<BoxedBlobWithIndex>, String> {
+        match check_blobs(&mut blobs, blob_hashes, checked) {
+            BlobCheck::Valid => Ok(blobs),
+            BlobCheck::Incomplete => Err(format!("endpoint {index} is missing blobs")),
+            BlobCheck::Mismatch => {
+                self.flag(index);
+                Err(format!("endpoint {index} served mismatched blobs"))
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl<B> BeaconClient for RedundantBeaconClient<B>
+where
+    B: BeaconClient + Send + Sync,
+{
+    type Error = RedundantBeaconClientError;
+
+    async fn config_spec(&self) -> Result<APIConfigResponse, Self::Error> {
+        let mut errors = Vec::new();
+        for endpoint in &self.endpoints {
+            match endpoint.config_spec().await {
+                Ok(config) => return Ok(config),
+                Err(err) => errors.push(err.to_string()),
+            }
+        }
+        Err(Self::unavailable(errors))
+    }
+
+    async fn beacon_genesis(&self) -> Result<APIGenesisResponse, Self::Error> {
+        let mut errors = Vec::new();
+        for endpoint in &self.endpoints {
+            match endpoint.beacon_genesis().await {
+                Ok(genesis) => return Ok(genesis),
+                Err(err) => errors.push(err.to_string()),
+            }
+        }
+        Err(Self::unavailable(errors))
+    }
+
+    async fn filtered_beacon_blobs(
+        &self,
+        slot: u64,
+        blob_hashes: &[IndexedBlobHash],
+    ) -> Result<Vec<BoxedBlobWithIndex>, Self::Error> {
+        let mut errors = Vec::new();
+        match self.redundancy {
+            BeaconRedundancy::Failover => {
+                for index in self.ordered() {
+                    let response = self.fetch(index, slot, blob_hashes).await;
+                    match response.and_then(|blobs| self.check(index, blobs, blob_hashes, &[])) {
+                        Ok(blobs) => return Ok(blobs),
+                        Err(err) => errors.push(err),
+                    }
+                }
+            }
+            BeaconRedundancy::CrossCheck => {
+                let order = self.ordered();
+                let responses = futures::future::join_all(
+                    order.iter().map(|&index| self.fetch(index, slot, blob_hashes)),
+                )
+                .await;
+                // The responses are checked one after the other, so that the blobs served by
+                // several endpoints are only committed to once.
+                let mut valid: Option<Vec<BoxedBlobWithIndex>> = None;
+                for (index, response) in order.into_iter().zip(responses) {
+                    let checked = valid.as_deref().unwrap_or_default();
+                    match response.and_then(|blobs| self.check(index, blobs, blob_hashes, checked))
+                    {
+                        Ok(blobs) => valid = valid.or(Some(blobs)),
+                        Err(err) => errors.push(err),
+                    }
+                }
+                if let Some(blobs) = valid {
+                    return Ok(blobs);
+                }
+            }
+        }
+        Err(Self::unavailable(errors))
+    }
+}
+
+/// Checks that the blobs contain every requested blob, and that each of them matches the
+/// versioned hash of the request. The KZG commitment of each checked blob is set on the blob.
+///
+/// Blobs identical to one of the `checked` blobs, which already matched their versioned hashes,
+/// take its KZG commitment instead of being committed to again.
+fn check_blobs(
+    blobs: &mut [BoxedBlobWithIndex],
+    blob_hashes: &[IndexedBlobHash],
+    checked: &[BoxedBlobWithIndex],
+) -> BlobCheck {
+    let kzg_settings = EnvKzgSettings::Default;
+    for requested in blob_hashes {
+        let Some(blob) = blobs.iter_mut().find(|blob| blob.index == requested.index) else {
+            return BlobCheck::Incomplete;
+        };
+        let known = checked
+            .iter()
+            .find(|known| known.index == blob.index && known.blob == blob.blob)
+            .and_then(|known| known.kzg_commitment);
+        if known.is_some() {
+            blob.kzg_commitment = known;
+            continue;
+        }
+
+        // SAFETY: all types have the same size and alignment
+        let kzg_blob = unsafe { &*(blob.blob.as_ref() as *const Blob).cast::<c_kzg::Blob>() };
+        let Ok(commitment) = kzg_settings.get().blob_to_kzg_commitment(kzg_blob) else {
+            return BlobCheck::Mismatch;
+        };
+        let commitment = FixedBytes::from(*commitment.to_bytes());
+        if kzg_to_versioned_hash(commitment.as_slice()) != requested.hash {
+            return BlobCheck::Mismatch;
+        }
+        blob.kzg_commitment = Some(commitment);
+    }
+    BlobCheck::Valid
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::B256;
+
+    /// A [BeaconClient] serving fixed blobs.
+    #[derive(Debug, Clone)]
+    struct MockBeaconClient(Option<Vec<BoxedBlobWithIndex>>);
+
+    #[async_trait]
+    impl BeaconClient for MockBeaconClient {
+        type Error = String;
+
+        async fn config_spec(&self) -> Result<APIConfigResponse, Self::Error> {
+            Err("unavailable".to_string())
+        }
+
+        async fn beacon_genesis(&self) -> Result<APIGenesisResponse, Self::Error> {
+            Err("unavailable".to_string())
+        }
+
+        async fn filtered_beacon_blobs(
+            &self,
+            _slot: u64,
+            _blob_hashes: &[IndexedBlobHash],
+        ) -> Result<Vec<BoxedBlobWithIndex>, Self::Error> {
+            self.0.clone().ok_or_else(|| "unavailable".to_string())
+        }
+    }
+
+    fn blob(index: u64, byte: u8) -> (BoxedBlobWithIndex, IndexedBlobHash) {
+        let mut blob = Box::<Blob>::default();
+        blob[0] = byte;
+        // SAFETY: all types have the same size and alignment
+        let kzg_blob = unsafe { &*(blob.as_ref() as *const Blob).cast::<c_kzg::Blob>() };
+        let commitment = EnvKzgSettings::Default.get().blob_to_kzg_commitment(kzg_blob).unwrap();
+        let hash = kzg_to_versioned_hash(commitment.to_bytes().as_slice());
+        (BoxedBlobWithIndex::new(index, blob), IndexedBlobHash { index, hash })
+    }
+
+    #[test]
+    fn test_check_blobs() {
+        let (valid, hash) = blob(1, 1);
+        let mut blobs = [valid.clone()];
+        assert_eq!(check_blobs(&mut blobs, &[hash], &[]), BlobCheck::Valid);
+        assert!(blobs[0].kzg_commitment.is_some());
+        assert_eq!(check_blobs(&mut [], &[hash], &[]), BlobCheck::Incomplete);
+
+        let mismatched = IndexedBlobHash { index: 1, hash: B256::with_last_byte(1) };
+        assert_eq!(check_blobs(&mut [valid], &[mismatched], &[]), BlobCheck::Mismatch);
+    }
+
+    #[test]
+    fn test_check_blobs_reuses_checked_commitments() {
+        let (valid, hash) = blob(1, 1);
+        // The commitment of an identical checked blob is taken as is.
+        let checked = BoxedBlobWithIndex {
+            kzg_commitment: Some(FixedBytes::repeat_byte(7)),
+            ..valid.clone()
+        };
+        let mut blobs = [valid];
+        assert_eq!(check_blobs(&mut blobs, &[hash], &[checked]), BlobCheck::Valid);
+        assert_eq!(blobs[0].kzg_commitment, Some(FixedBytes::repeat_byte(7)));
+
+        // A different blob at the same index is committed to.
+        let (other, _) = blob(1, 2);
+        let checked =
+            BoxedBlobWithIndex { kzg_commitment: Some(FixedBytes::repeat_byte(7)), ..other };
+        let (valid, _) = blob(1, 1);
+        let mut blobs = [valid];
+        assert_eq!(check_blobs(&mut blobs, &[hash], &[checked]), BlobCheck::Valid);
+        assert_ne!(blobs[0].kzg_commitment, Some(FixedBytes::repeat_byte(7)));
+    }
+
+    #[tokio::test]
+    async fn test_failover_flags_mismatched_endpoint() {
+        let (valid, hash) = blob(0, 1);
+        let (other, _) = blob(0, 2);
+        let client = RedundantBeaconClient::new(vec![
+            MockBeaconClient(None),
+            MockBeaconClient(Some(vec![other])),
+            MockBeaconClient(Some(vec![valid.clone()])),
+        ]);
+
+        let blobs = client.filtered_beacon_blobs(0, &[hash]).await.unwrap();
+        assert_eq!(blobs.len(), 1);
+        assert_eq!(blobs[0].blob, valid.blob);
+        assert!(blobs[0].kzg_commitment.is_some());
+        assert_eq!(client.mismatches(), vec![0, 1, 0]);
+
+        // The flagged endpoint is queried last.
+        assert_eq!(client.ordered(), vec![0, 2, 1]);
+    }
+
+    #[tokio::test]
+    async fn test_failover_to_blob_archive() {
+        let (valid, hash) = blob(0, 1);
+        // The beacon node pruned the expired blob, which the trailing archive retains.
+        let client = RedundantBeaconClient::new(vec![
+            MockBeaconClient(Some(Vec::new())),
+            MockBeaconClient(Some(vec![valid.clone()])),
+        ]);
+
+        let blobs = client.filtered_beacon_blobs(0, &[hash]).await.unwrap();
+        assert_eq!(blobs.len(), 1);
+        assert_eq!(blobs[0].blob, valid.blob);
+        // An incomplete response is not a mismatch.
+        assert_eq!(client.mismatches(), vec![0, 0]);
+        assert_eq!(client.ordered(), vec![0, 1]);
+    }
+
+    #[tokio::test]
+    async fn test_cross_check_flags_all_mismatched_endpoints() {
+        let (valid, hash) = blob(0, 1);
+        let (other, _) = blob(0, 2);
+        let client = RedundantBeaconClient::new(vec![
+            MockBeaconClient(Some(vec![valid.clone()])),
+            MockBeaconClient(Some(vec![other])),
+        ])
+        .with_redundancy(BeaconRedundancy::CrossCheck);
+
+        let blobs = client.filtered_beacon_blobs(0, &[hash]).await.unwrap();
+        assert_eq!(blobs.len(), 1);
+        assert_eq!(blobs[0].blob, valid.blob);
+        assert!(blobs[0].kzg_commitment.is_some());
+        assert_eq!(client.mismatches(), vec![0, 1]);
+    }
+
+    #[tokio::test]
+    async fn test_no_valid_endpoint() {
+        let (_, hash) = blob(0, 1);
+        let client = RedundantBeaconClient::new(vec![MockBeaconClient(None)]);
+        assert!(matches!(
+            client.filtered_beacon_blobs(0, &[hash]).await,
+            Err(RedundantBeaconClientError::Unavailable(_))
+        ));
+
+        let client = RedundantBeaconClient::<MockBeaconClient>::new(Vec::new());
+        assert!(matches!(client.config_spec().await, Err(RedundantBeaconClientError::NoEndpoints)));
+    }
+}
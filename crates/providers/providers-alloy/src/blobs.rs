@@ -1,8 +1,8 @@
 //! Contains an online implementation of the `BlobProvider` trait.
 
-use crate::BeaconClient;
 #[cfg(feature = "metrics")]
 use crate::Metrics;
+use crate::{BeaconClient, L1PrefetchCache};
 use alloy_eips::eip4844::{
     Blob, BlobTransactionSidecarItem, IndexedBlobHash, env_settings::EnvKzgSettings,
 };
@@ -19,6 +19,16 @@ pub struct BoxedBlobWithIndex {
     pub index: u64,
     /// The blob data.
     pub blob: Box<Blob>,
+    /// The KZG commitment of the blob, if it was already computed, for example when the blob was
+    /// checked against its versioned hash.
+    pub kzg_commitment: Option<FixedBytes<48>>,
+}
+
+impl BoxedBlobWithIndex {
+    /// Creates a new [BoxedBlobWithIndex] with the given index and blob, without a KZG commitment.
+    pub const fn new(index: u64, blob: Box<Blob>) -> Self {
+        Self { index, blob, kzg_commitment: None }
+    }
 }
 
 /// An online implementation of the [BlobProvider] trait.
@@ -30,6 +40,8 @@ pub struct OnlineBlobProvider<B: BeaconClient> {
     pub genesis_time: u64,
     /// Slot interval used for the time to slot conversion.
     pub slot_interval: u64,
+    /// The blobs prefetched ahead of the pipeline, consulted before fetching blobs.
+    pub prefetched: Option<L1PrefetchCache>,
 }
 
 impl<B: BeaconClient> OnlineBlobProvider<B> {
@@ -42,19 +54,34 @@ impl<B: BeaconClient> OnlineBlobProvider<B> {
     /// ## Panics
     /// Panics if the genesis time or slot interval cannot be loaded from the beacon client.
     pub async fn init(beacon_client: B) -> Self {
+        Self::try_init(beacon_client)
+            .await
+            .expect("Failed to load beacon config from beacon client")
+    }
+
+    /// Creates a new instance of the [OnlineBlobProvider], loading the genesis time and slot
+    /// interval from the beacon client, or returns the error of the beacon client.
+    pub async fn try_init(beacon_client: B) -> Result<Self, BlobProviderError> {
         let genesis_time = beacon_client
             .beacon_genesis()
             .await
             .map(|r| r.data.genesis_time)
-            .map_err(|e| BlobProviderError::Backend(e.to_string()))
-            .expect("Failed to load genesis time from beacon client");
+            .map_err(|e| BlobProviderError::Backend(e.to_string()))?;
         let slot_interval = beacon_client
             .config_spec()
             .await
             .map(|r| r.data.seconds_per_slot)
-            .map_err(|e| BlobProviderError::Backend(e.to_string()))
-            .expect("Failed to load slot interval from beacon client");
-        Self { beacon_client, genesis_time, slot_interval }
+            .map_err(|e| BlobProviderError::Backend(e.to_string()))?;
+        Ok(Self { beacon_client, genesis_time, slot_interval, prefetched: None })
+    }
+
+    /// Sets the [L1PrefetchCache] filled by an [L1Prefetcher], which is consulted before fetching
+    /// blobs from the beacon node.
+    ///
+    /// [L1Prefetcher]: crate::L1Prefetcher
+    pub fn with_prefetch_cache(mut self, prefetched: L1PrefetchCache) -> Self {
+        self.prefetched = Some(prefetched);
+        self
     }
 
     /// Computes the slot for the given timestamp.
@@ -92,6 +119,7 @@ impl<B: BeaconClient> OnlineBlobProvider<B> {
     }
 
     /// Converts a vector of boxed blobs with index to a vector of blob transaction sidecar items.
+    /// The KZG commitments of the blobs are only computed if they were not already.
     ///
     /// Note: for performance reasons, we need to transmute the blobs to the c_kzg::Blob type to
     /// avoid the overhead of moving the blobs around or reallocating the memory.
@@ -107,10 +135,13 @@ impl<B: BeaconClient> OnlineBlobProvider<B> {
                 let kzg_blob =
                     unsafe { Box::from_raw(Box::<Blob>::into_raw(blob.blob) as *mut c_kzg::Blob) };
 
-                let commitment = kzg_settings
-                    .get()
-                    .blob_to_kzg_commitment(&kzg_blob)
-                    .map(|blob| blob.to_bytes())?;
+                let commitment = match blob.kzg_commitment {
+                    Some(commitment) => c_kzg::Bytes48::from(commitment.0),
+                    None => kzg_settings
+                        .get()
+                        .blob_to_kzg_commitment(&kzg_blob)
+                        .map(|blob| blob.to_bytes())?,
+                };
                 let proof = kzg_settings
                     .get()
                     .compute_blob_kzg_proof(&kzg_blob, &commitment)
@@ -170,6 +201,11 @@ where
         block_ref: &BlockInfo,
         blob_hashes: &[IndexedBlobHash],
     ) -> Result<Vec<Box<Blob>>, Self::Error> {
+        // Prefetched blobs were validated against their versioned hashes when they were fetched.
+        if let Some(blobs) = self.prefetched.as_ref().and_then(|p| p.blobs(blob_hashes)) {
+            return Ok(blobs);
+        }
+
         // Fetch the blob sidecars for the given block reference and blob hashes.
         let blobs = self.fetch_filtered_blob_sidecars(block_ref, blob_hashes).await?;
 
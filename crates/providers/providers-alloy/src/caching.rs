@@ -0,0 +1,92 @@
+//! Contains the on-disk persistence of the LRU caches of the [AlloyChainProvider], so that
+//! repeated derivation of overlapping ranges does not refetch the same L1 data from the RPC
+//! endpoint across restarts.
+//!
+//! [AlloyChainProvider]: crate::AlloyChainProvider
+
+use lru::LruCache;
+use serde::{Serialize, de::DeserializeOwned};
+use std::{
+    fs,
+    hash::Hash,
+    io::{BufReader, BufWriter},
+    path::Path,
+    vec::Vec,
+};
+
+/// An error that can occur while loading or persisting a provider cache.
+#[derive(Debug, thiserror::Error)]
+pub enum CacheError {
+    /// An I/O error occurred while reading or writing the cache.
+    #[error("Cache I/O error: {0}")]
+    Io(#[from] std::io::Error),
+    /// The cache could not be serialized or deserialized.
+    #[error("Cache serialization error: {0}")]
+    Serde(#[from] serde_json::Error),
+}
+
+/// Loads the entries persisted at the given path into the [LruCache], if the file exists. The
+/// most recently used entries are loaded last, so that the recency order of the cache is retained.
+pub(crate) fn load_lru<K, V>(path: &Path, cache: &mut LruCache<K, V>) -> Result<(), CacheError>
+where
+    K: Hash + Eq + DeserializeOwned,
+    V: DeserializeOwned,
+{
+    if !path.exists() {
+        return Ok(());
+    }
+
+    let entries: Vec<(K, V)> = serde_json::from_reader(BufReader::new(fs::File::open(path)?))?;
+    for (key, value) in entries {
+        cache.put(key, value);
+    }
+    Ok(())
+}
+
+/// Persists the entries of the [LruCache] to the given path, from least to most recently used.
+pub(crate) fn persist_lru<K, V>(path: &Path, cache: &LruCache<K, V>) -> Result<(), CacheError>
+where
+    K: Hash + Eq + Serialize,
+    V: Serialize,
+{
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+
+    let entries = cache.iter().rev().collect::<Vec<_>>();
+    serde_json::to_writer(BufWriter::new(fs::File::create(path)?), &entries)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::num::NonZeroUsize;
+
+    #[test]
+    fn test_persist_and_load_lru() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("cache").join("entries.json");
+
+        let mut cache = LruCache::new(NonZeroUsize::new(3).unwrap());
+        cache.put(1u64, "one".to_string());
+        cache.put(2, "two".to_string());
+        cache.put(3, "three".to_string());
+        cache.get(&1);
+        persist_lru(&path, &cache).unwrap();
+
+        // Only the most recently used entries fit into a smaller cache.
+        let mut loaded = LruCache::new(NonZeroUsize::new(2).unwrap());
+        load_lru(&path, &mut loaded).unwrap();
+        assert_eq!(loaded.iter().map(|(k, _)| *k).collect::<Vec<_>>(), vec![1, 3]);
+        assert_eq!(loaded.peek(&1).map(String::as_str), Some("one"));
+    }
+
+    #[test]
+    fn test_load_lru_missing_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut cache = LruCache::<u64, u64>::new(NonZeroUsize::new(1).unwrap());
+        load_lru(&dir.path().join("missing.json"), &mut cache).unwrap();
+        assert!(cache.is_empty());
+    }
+}
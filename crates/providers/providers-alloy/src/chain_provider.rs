@@ -2,16 +2,29 @@
 
 #[cfg(feature = "metrics")]
 use crate::Metrics;
+use crate::{
+    CacheError, L1PrefetchCache, ReceiptsStrategy,
+    caching::{load_lru, persist_lru},
+    receipts::is_method_not_found,
+};
 use alloy_consensus::{Header, Receipt, TxEnvelope};
-use alloy_eips::BlockId;
-use alloy_primitives::B256;
+use alloy_eips::{BlockId, BlockNumberOrTag};
+use alloy_primitives::{Address, B256, U256};
 use alloy_provider::{Provider, RootProvider};
 use alloy_transport::{RpcError, TransportErrorKind};
 use async_trait::async_trait;
 use kona_derive::{ChainProvider, PipelineError, PipelineErrorKind};
+use kona_genesis::L1StorageProvider;
 use kona_protocol::BlockInfo;
 use lru::LruCache;
-use std::{boxed::Box, num::NonZeroUsize, vec::Vec};
+use std::{boxed::Box, num::NonZeroUsize, path::PathBuf, vec::Vec};
+
+/// The file name of the persisted `header_by_hash` cache.
+const HEADERS_CACHE_FILE: &str = "l1_headers_by_hash.json";
+/// The file name of the persisted `receipts_by_hash` cache.
+const RECEIPTS_CACHE_FILE: &str = "l1_receipts_by_hash.json";
+/// The file name of the persisted `block_info_and_transactions_by_hash` cache.
+const BLOCKS_CACHE_FILE: &str = "l1_blocks_by_hash.json";
 
 /// The [AlloyChainProvider] is a concrete implementation of the [ChainProvider] trait, providing
 /// data over Ethereum JSON-RPC using an alloy provider as the backend.
@@ -27,6 +40,12 @@ pub struct AlloyChainProvider {
     receipts_by_hash_cache: LruCache<B256, Vec<Receipt>>,
     /// `block_info_and_transactions_by_hash` LRU cache.
     block_info_and_transactions_by_hash_cache: LruCache<B256, (BlockInfo, Vec<TxEnvelope>)>,
+    /// The L1 data prefetched ahead of the pipeline, consulted on cache misses.
+    prefetched: Option<L1PrefetchCache>,
+    /// The directory that the LRU caches are persisted to, if any.
+    cache_dir: Option<PathBuf>,
+    /// The method used to fetch receipts, or [None] if it has not been probed yet.
+    receipts_strategy: Option<ReceiptsStrategy>,
 }
 
 impl AlloyChainProvider {
@@ -51,7 +70,112 @@ impl AlloyChainProvider {
             block_info_and_transactions_by_hash_cache: LruCache::new(
                 NonZeroUsize::new(cache_size).unwrap(),
             ),
+            prefetched: None,
+            cache_dir: None,
+            receipts_strategy: None,
+        }
+    }
+
+    /// Loads the LRU caches persisted in the given directory, and sets it as the directory that
+    /// [Self::persist_caches] persists the caches to.
+    ///
+    /// The caches are keyed by block hash, so persisted entries remain valid across L1 reorgs.
+    pub fn with_cache_dir(mut self, dir: impl Into<PathBuf>) -> Result<Self, CacheError> {
+        let dir = dir.into();
+        load_lru(&dir.join(HEADERS_CACHE_FILE), &mut self.header_by_hash_cache)?;
+        load_lru(&dir.join(RECEIPTS_CACHE_FILE), &mut self.receipts_by_hash_cache)?;
+        load_lru(
+            &dir.join(BLOCKS_CACHE_FILE),
+            &mut self.block_info_and_transactions_by_hash_cache,
+        )?;
+        self.cache_dir = Some(dir);
+        Ok(self)
+    }
+
+    /// Persists the LRU caches to the directory set with [Self::with_cache_dir]. This is a no-op
+    /// if no cache directory is set.
+    pub fn persist_caches(&self) -> Result<(), CacheError> {
+        let Some(dir) = self.cache_dir.as_ref() else {
+            return Ok(());
+        };
+        persist_lru(&dir.join(HEADERS_CACHE_FILE), &self.header_by_hash_cache)?;
+        persist_lru(&dir.join(RECEIPTS_CACHE_FILE), &self.receipts_by_hash_cache)?;
+        persist_lru(&dir.join(BLOCKS_CACHE_FILE), &self.block_info_and_transactions_by_hash_cache)
+    }
+
+    /// Sets the [L1PrefetchCache] filled by an [L1Prefetcher], which is consulted before
+    /// fetching blocks and receipts over RPC. The L1 blocks requested by number are reported to
+    /// the cache as the derivation cursor.
+    ///
+    /// [L1Prefetcher]: crate::L1Prefetcher
+    pub fn with_prefetch_cache(mut self, prefetched: L1PrefetchCache) -> Self {
+        self.prefetched = Some(prefetched);
+        self
+    }
+
+    /// Sets the [ReceiptsStrategy] used to fetch receipts, instead of probing the methods
+    /// supported by the endpoint. The methods are still probed again if the endpoint reports
+    /// that the method is not found.
+    pub const fn with_receipts_strategy(mut self, strategy: ReceiptsStrategy) -> Self {
+        self.receipts_strategy = Some(strategy);
+        self
+    }
+
+    /// Returns the [ReceiptsStrategy] used to fetch receipts, or [None] if it has not been
+    /// probed yet.
+    pub const fn receipts_strategy(&self) -> Option<ReceiptsStrategy> {
+        self.receipts_strategy
+    }
+
+    /// Probes the methods supported by the endpoint to fetch receipts, using the latest block,
+    /// and selects the most efficient one. Without an explicit probe, the methods are probed
+    /// on the first fetch.
+    pub async fn probe_receipts_strategy(
+        &mut self,
+    ) -> Result<ReceiptsStrategy, AlloyChainProviderError> {
+        let latest = BlockId::Number(BlockNumberOrTag::Latest);
+        let hash = self
+            .inner
+            .get_block_by_number(BlockNumberOrTag::Latest)
+            .await?
+            .ok_or(AlloyChainProviderError::BlockNotFound(latest))?
+            .header
+            .hash;
+
+        self.receipts_strategy = None;
+        self.fetch_receipts(hash).await?;
+        Ok(self.receipts_strategy.unwrap_or(ReceiptsStrategy::TransactionReceipts))
+    }
+
+    /// Fetches the receipts of the block with the given hash with the selected
+    /// [ReceiptsStrategy]. If no strategy is selected, or if the endpoint reports that the
+    /// selected method is not found, the strategies are probed with the requested block.
+    async fn fetch_receipts(
+        &mut self,
+        hash: B256,
+    ) -> Result<Vec<Receipt>, AlloyChainProviderError> {
+        if let Some(strategy) = self.receipts_strategy {
+            match strategy.fetch(&self.inner, hash).await {
+                Err(err) if is_method_not_found(&err) => self.receipts_strategy = None,
+                result => return result,
+            }
         }
+
+        for strategy in ReceiptsStrategy::PROBED {
+            match strategy.fetch(&self.inner, hash).await {
+                Err(err) if is_method_not_found(&err) => continue,
+                Err(err) => return Err(err),
+                Ok(receipts) => {
+                    self.receipts_strategy = Some(strategy);
+                    return Ok(receipts);
+                }
+            }
+        }
+
+        let strategy = ReceiptsStrategy::TransactionReceipts;
+        let receipts = strategy.fetch(&self.inner, hash).await?;
+        self.receipts_strategy = Some(strategy);
+        Ok(receipts)
     }
 
     /// Creates a new [AlloyChainProvider] from the provided [reqwest::Url].
@@ -118,6 +242,9 @@ pub enum AlloyChainProviderError {
     /// Failed to convert RPC receipts into consensus receipts.
     #[error("Failed to convert RPC receipts into consensus receipts: {0}")]
     ReceiptsConversion(B256),
+    /// A transaction receipt does not belong to the requested block.
+    #[error("Receipt of transaction {0} is not in block {1}")]
+    ReceiptNotInBlock(B256, B256),
 }
 
 impl From<AlloyChainProviderError> for PipelineErrorKind {
@@ -134,6 +261,11 @@ impl From<AlloyChainProviderError> for PipelineErrorKind {
                     "Failed to convert RPC receipts into consensus receipts".to_string(),
                 ))
             }
+            AlloyChainProviderError::ReceiptNotInBlock(_, hash) => {
+                Self::Temporary(PipelineError::Provider(format!(
+                    "L1 block {hash} was reorged while fetching receipts"
+                )))
+            }
         }
     }
 }
@@ -173,6 +305,10 @@ impl ChainProvider for AlloyChainProvider {
     }
 
     async fn block_info_by_number(&mut self, number: u64) -> Result<BlockInfo, Self::Error> {
+        if let Some(prefetched) = &self.prefetched {
+            prefetched.observe_cursor(number);
+        }
+
         kona_macros::inc!(gauge, Metrics::CHAIN_PROVIDER_RPC_CALLS, "method" => "block_by_number");
 
         let block = self
@@ -202,21 +338,16 @@ impl ChainProvider for AlloyChainProvider {
 
         kona_macros::inc!(gauge, Metrics::CHAIN_PROVIDER_CACHE_MISSES, "cache" => "receipts_by_hash");
 
+        if let Some(receipts) = self.prefetched.as_ref().and_then(|p| p.receipts(&hash)) {
+            self.receipts_by_hash_cache.put(hash, receipts.clone());
+            return Ok(receipts);
+        }
+
         kona_macros::inc!(gauge, Metrics::CHAIN_PROVIDER_RPC_CALLS, "method" => "receipts_by_hash");
 
-        let receipts = self
-            .inner
-            .get_block_receipts(hash.into())
-            .await
-            .inspect_err(|_e| {
-                kona_macros::inc!(gauge, Metrics::CHAIN_PROVIDER_RPC_ERRORS, "method" => "receipts_by_hash");
-            })?
-            .ok_or(AlloyChainProviderError::BlockNotFound(hash.into()))?;
-        let consensus_receipts = receipts
-            .into_iter()
-            .map(|r| r.inner.into_primitives_receipt().as_receipt().cloned())
-            .collect::<Option<Vec<_>>>()
-            .ok_or(AlloyChainProviderError::ReceiptsConversion(hash))?;
+        let consensus_receipts = self.fetch_receipts(hash).await.inspect_err(|_e| {
+            kona_macros::inc!(gauge, Metrics::CHAIN_PROVIDER_RPC_ERRORS, "method" => "receipts_by_hash");
+        })?;
 
         self.receipts_by_hash_cache.put(hash, consensus_receipts.clone());
 
@@ -237,6 +368,11 @@ impl ChainProvider for AlloyChainProvider {
 
         kona_macros::inc!(gauge, Metrics::CHAIN_PROVIDER_CACHE_MISSES, "cache" => "block_info_and_tx");
 
+        if let Some(block) = self.prefetched.as_ref().and_then(|p| p.block(&hash)) {
+            self.block_info_and_transactions_by_hash_cache.put(hash, block.clone());
+            return Ok(block);
+        }
+
         kona_macros::inc!(gauge, Metrics::CHAIN_PROVIDER_RPC_CALLS, "method" => "block_by_hash");
 
         let block = self
@@ -269,3 +405,67 @@ impl ChainProvider for AlloyChainProvider {
         Ok((block_info, block.body.transactions))
     }
 }
+
+#[async_trait]
+impl L1StorageProvider for AlloyChainProvider {
+    type Error = AlloyChainProviderError;
+
+    async fn storage_at(
+        &mut self,
+        address: Address,
+        slot: U256,
+        block_hash: B256,
+    ) -> Result<U256, Self::Error> {
+        kona_macros::inc!(gauge, Metrics::CHAIN_PROVIDER_RPC_CALLS, "method" => "storage_at");
+
+        let value = self
+            .inner
+            .get_storage_at(address, slot)
+            .block_id(BlockId::hash(block_hash))
+            .await
+            .inspect_err(|_e| {
+                kona_macros::inc!(gauge, Metrics::CHAIN_PROVIDER_RPC_ERRORS, "method" => "storage_at");
+            })?;
+        Ok(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_rpc_client::RpcClient;
+    use alloy_rpc_types_eth::{Block, BlockTransactions};
+    use alloy_transport::mock::{Asserter, MockTransport};
+
+    fn provider(asserter: &Asserter) -> AlloyChainProvider {
+        let inner = RootProvider::new(RpcClient::new(MockTransport::new(asserter.clone()), false));
+        AlloyChainProvider::new(inner, 4)
+    }
+
+    #[tokio::test]
+    async fn test_persisted_caches_are_reloaded() {
+        let dir = tempfile::tempdir().unwrap();
+        let header = Header { number: 1, ..Default::default() };
+        let hash = header.hash_slow();
+
+        let asserter = Asserter::new();
+        asserter.push_success(&Block::<()> {
+            header: alloy_rpc_types_eth::Header::new(header.clone()),
+            uncles: Vec::new(),
+            transactions: BlockTransactions::Hashes(Vec::new()),
+            withdrawals: None,
+        });
+        let mut l1 = provider(&asserter).with_cache_dir(dir.path()).unwrap();
+        assert_eq!(l1.header_by_hash(hash).await.unwrap(), header);
+        l1.persist_caches().unwrap();
+
+        // The reloaded header is served without a request to the endpoint.
+        let mut reloaded = provider(&Asserter::new()).with_cache_dir(dir.path()).unwrap();
+        assert_eq!(reloaded.header_by_hash(hash).await.unwrap(), header);
+    }
+
+    #[test]
+    fn test_persist_caches_without_dir() {
+        assert!(provider(&Asserter::new()).persist_caches().is_ok());
+    }
+}
@@ -0,0 +1,407 @@
+//! Contains the [FailoverTransport], which fails over across multiple RPC endpoints.
+
+use alloy_json_rpc::{RequestPacket, ResponsePacket};
+use alloy_transport::{Transport, TransportError, TransportFut};
+use alloy_transport_http::Http;
+use std::{
+    boxed::Box,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicUsize, Ordering},
+    },
+    task::{Context, Poll},
+    time::{Duration, Instant},
+    vec::Vec,
+};
+use tower::Service;
+
+/// The configuration of the failover and of the backoff applied to failing endpoints of a
+/// [FailoverTransport].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FailoverConfig {
+    /// The number of consecutive failed requests to the active endpoint after which requests fail
+    /// over to the standby endpoints.
+    pub failover_threshold: u32,
+    /// The backoff applied to an endpoint after its first consecutive failure.
+    pub base_backoff: Duration,
+    /// The maximum backoff applied to an endpoint.
+    pub max_backoff: Duration,
+}
+
+impl Default for FailoverConfig {
+    fn default() -> Self {
+        Self {
+            failover_threshold: 3,
+            base_backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(60),
+        }
+    }
+}
+
+impl FailoverConfig {
+    /// Returns the backoff for an endpoint after the given number of consecutive failures.
+    fn backoff(&self, failures: u32) -> Duration {
+        let factor = 1u32.checked_shl(failures.saturating_sub(1)).unwrap_or(u32::MAX);
+        self.base_backoff.saturating_mul(factor).min(self.max_backoff)
+    }
+}
+
+/// The health of a single endpoint.
+#[derive(Debug, Default, Clone, Copy)]
+struct EndpointHealth {
+    /// The number of consecutive failures of the endpoint.
+    failures: u32,
+    /// The instant before which the endpoint is not retried, if it is backing off.
+    retry_at: Option<Instant>,
+}
+
+impl EndpointHealth {
+    /// Returns `true` if the endpoint is not backing off at the given instant.
+    fn is_available(&self, now: Instant) -> bool {
+        self.retry_at.is_none_or(|retry_at| retry_at <= now)
+    }
+}
+
+/// A single endpoint of a [FailoverTransport].
+#[derive(Debug)]
+struct Endpoint<T> {
+    /// The transport of the endpoint.
+    transport: T,
+    /// The health of the endpoint.
+    health: Mutex<EndpointHealth>,
+}
+
+/// The state shared between clones of a [FailoverTransport].
+#[derive(Debug)]
+struct FailoverState<T> {
+    /// The endpoints, in order of priority. The first endpoint is the primary.
+    endpoints: Vec<Endpoint<T>>,
+    /// The index of the endpoint that requests are currently routed to.
+    active: AtomicUsize,
+    /// The failover configuration.
+    config: FailoverConfig,
+}
+
+/// A [Transport] that routes requests across multiple RPC endpoints, failing over to the next
+/// healthy endpoint when a request fails at the transport level.
+///
+/// Endpoint selection is sticky: requests are routed to the active endpoint only, and its failures
+/// are returned as-is until it fails [`FailoverConfig::failover_threshold`] requests in a row. The
+/// request that reaches the threshold is retried on the standby endpoints, and the first one that
+/// succeeds becomes active. Requests then stay on the new active endpoint, and never switch back to
+/// the primary on their own, so that a flapping endpoint does not make requests bounce between
+/// endpoints.
+///
+/// Standby endpoints that fail are backed off exponentially, and are attempted after the available
+/// ones on the next failover.
+///
+/// JSON-RPC error responses are returned as-is, and do not trigger a failover.
+#[derive(Debug)]
+pub struct FailoverTransport<T> {
+    /// The shared failover state.
+    state: Arc<FailoverState<T>>,
+}
+
+impl<T> Clone for FailoverTransport<T> {
+    fn clone(&self) -> Self {
+        Self { state: Arc::clone(&self.state) }
+    }
+}
+
+impl<T> FailoverTransport<T> {
+    /// Creates a new [FailoverTransport] over the given transports, in order of priority.
+    ///
+    /// ## Panics
+    /// Panics if no transports are provided.
+    pub fn new(transports: Vec<T>, config: FailoverConfig) -> Self {
+        assert!(!transports.is_empty(), "At least one transport must be provided");
+        let endpoints = transports
+            .into_iter()
+            .map(|transport| Endpoint { transport, health: Mutex::new(EndpointHealth::default()) })
+            .collect();
+        Self { state: Arc::new(FailoverState { endpoints, active: AtomicUsize::new(0), config }) }
+    }
+
+    /// Returns the index of the endpoint that requests are currently routed to.
+    pub fn active(&self) -> usize {
+        self.state.active.load(Ordering::Relaxed)
+    }
+}
+
+impl FailoverTransport<Http<reqwest::Client>> {
+    /// Creates a new [FailoverTransport] over HTTP endpoints at the given URLs, in order of
+    /// priority.
+    ///
+    /// ## Panics
+    /// Panics if no URLs are provided.
+    pub fn new_http(urls: Vec<reqwest::Url>, config: FailoverConfig) -> Self {
+        Self::new(urls.into_iter().map(Http::new).collect(), config)
+    }
+}
+
+impl<T> FailoverState<T> {
+    /// Returns the health of the endpoint at the given index.
+    fn health(&self, index: usize) -> EndpointHealth {
+        *self.endpoints[index].health.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Returns the order in which the standby endpoints are attempted when failing over from the
+    /// active endpoint at the given index.
+    ///
+    /// The available endpoints are attempted in order of priority, followed by the endpoints that
+    /// are backing off, so that a failover is never abandoned without trying every endpoint.
+    fn failover_order(&self, active: usize) -> Vec<usize> {
+        let now = Instant::now();
+        let (mut order, backing_off): (Vec<_>, Vec<_>) = (0..self.endpoints.len())
+            .filter(|&i| i != active)
+            .partition(|&i| self.health(i).is_available(now));
+        order.extend(backing_off);
+        order
+    }
+
+    /// Records a successful request to the endpoint at the given index, making it active.
+    fn record_success(&self, index: usize) {
+        *self.endpoints[index].health.lock().unwrap_or_else(|e| e.into_inner()) =
+            EndpointHealth::default();
+        self.active.store(index, Ordering::Relaxed);
+    }
+
+    /// Records a failed request to the endpoint at the given index, backing it off, and returns
+    /// its number of consecutive failures.
+    fn record_failure(&self, index: usize) -> u32 {
+        let mut health = self.endpoints[index].health.lock().unwrap_or_else(|e| e.into_inner());
+        health.failures = health.failures.saturating_add(1);
+        health.retry_at = Some(Instant::now() + self.config.backoff(health.failures));
+        health.failures
+    }
+}
+
+impl<T> FailoverState<T>
+where
+    T: Transport,
+{
+    /// Dispatches the request to the active endpoint, failing over to the standby endpoints in
+    /// order once the active endpoint reaches the failover threshold.
+    async fn dispatch(&self, request: RequestPacket) -> Result<ResponsePacket, TransportError> {
+        let active = self.active.load(Ordering::Relaxed);
+        let mut last_error =
+            match self.endpoints[active].transport.clone().call(request.clone()).await {
+                Ok(response) => {
+                    self.record_success(active);
+                    return Ok(response);
+                }
+                Err(err) => err,
+            };
+        if self.record_failure(active) < self.config.failover_threshold {
+            return Err(last_error);
+        }
+
+        for index in self.failover_order(active) {
+            let mut transport = self.endpoints[index].transport.clone();
+            match transport.call(request.clone()).await {
+                Ok(response) => {
+                    self.record_success(index);
+                    return Ok(response);
+                }
+                Err(err) => {
+                    self.record_failure(index);
+                    last_error = err;
+                }
+            }
+        }
+        Err(last_error)
+    }
+}
+
+impl<T> Service<RequestPacket> for FailoverTransport<T>
+where
+    T: Transport,
+{
+    type Response = ResponsePacket;
+    type Error = TransportError;
+    type Future = TransportFut<'static>;
+
+    fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: RequestPacket) -> Self::Future {
+        let state = Arc::clone(&self.state);
+        Box::pin(async move { state.dispatch(request).await })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_json_rpc::{Id, Request};
+    use alloy_transport::TransportErrorKind;
+    use std::sync::atomic::AtomicBool;
+
+    /// An endpoint that succeeds while it is healthy, and fails at the transport level otherwise.
+    #[derive(Debug, Clone)]
+    struct MockEndpoint {
+        healthy: Arc<AtomicBool>,
+        calls: Arc<AtomicUsize>,
+    }
+
+    impl MockEndpoint {
+        fn new(healthy: bool) -> Self {
+            Self { healthy: Arc::new(AtomicBool::new(healthy)), calls: Arc::default() }
+        }
+
+        fn set_healthy(&self, healthy: bool) {
+            self.healthy.store(healthy, Ordering::Relaxed);
+        }
+
+        fn calls(&self) -> usize {
+            self.calls.load(Ordering::Relaxed)
+        }
+    }
+
+    impl Service<RequestPacket> for MockEndpoint {
+        type Response = ResponsePacket;
+        type Error = TransportError;
+        type Future = TransportFut<'static>;
+
+        fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn call(&mut self, _: RequestPacket) -> Self::Future {
+            self.calls.fetch_add(1, Ordering::Relaxed);
+            let healthy = self.healthy.load(Ordering::Relaxed);
+            Box::pin(async move {
+                if healthy {
+                    Ok(serde_json::from_str(r#"{"jsonrpc":"2.0","id":0,"result":null}"#).unwrap())
+                } else {
+                    Err(TransportErrorKind::custom_str("endpoint unavailable"))
+                }
+            })
+        }
+    }
+
+    fn request() -> RequestPacket {
+        Request::new("eth_chainId", Id::Number(0), ()).serialize().unwrap().into()
+    }
+
+    #[tokio::test]
+    async fn test_failover_after_threshold() {
+        let primary = MockEndpoint::new(false);
+        let standby = MockEndpoint::new(true);
+        let mut transport = FailoverTransport::new(
+            vec![primary.clone(), standby.clone()],
+            FailoverConfig::default(),
+        );
+
+        // Failures below the threshold are returned without failing over.
+        for _ in 1..FailoverConfig::default().failover_threshold {
+            assert!(transport.call(request()).await.is_err());
+            assert_eq!(transport.active(), 0);
+        }
+        assert_eq!(standby.calls(), 0);
+
+        // The request reaching the threshold is retried on the standby endpoint.
+        assert!(transport.call(request()).await.is_ok());
+        assert_eq!(transport.active(), 1);
+        assert_eq!(standby.calls(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_failover_resets_on_success() {
+        let primary = MockEndpoint::new(false);
+        let standby = MockEndpoint::new(true);
+        let mut transport = FailoverTransport::new(
+            vec![primary.clone(), standby.clone()],
+            FailoverConfig { failover_threshold: 2, ..Default::default() },
+        );
+
+        // Intermittent failures of the active endpoint do not add up to a failover.
+        for _ in 0..3 {
+            primary.set_healthy(false);
+            assert!(transport.call(request()).await.is_err());
+            primary.set_healthy(true);
+            assert!(transport.call(request()).await.is_ok());
+        }
+        assert_eq!(transport.active(), 0);
+        assert_eq!(standby.calls(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_failover_is_sticky() {
+        let primary = MockEndpoint::new(false);
+        let standby = MockEndpoint::new(true);
+        let mut transport = FailoverTransport::new(
+            vec![primary.clone(), standby.clone()],
+            FailoverConfig {
+                failover_threshold: 1,
+                base_backoff: Duration::ZERO,
+                ..Default::default()
+            },
+        );
+        assert!(transport.call(request()).await.is_ok());
+        assert_eq!(transport.active(), 1);
+
+        // Requests stay on the standby endpoint once the primary recovers.
+        primary.set_healthy(true);
+        let primary_calls = primary.calls();
+        for _ in 0..3 {
+            assert!(transport.call(request()).await.is_ok());
+        }
+        assert_eq!(transport.active(), 1);
+        assert_eq!(primary.calls(), primary_calls);
+
+        // Requests only switch back once the standby endpoint fails in turn.
+        standby.set_healthy(false);
+        assert!(transport.call(request()).await.is_ok());
+        assert_eq!(transport.active(), 0);
+    }
+
+    #[test]
+    fn test_backoff() {
+        let config = FailoverConfig {
+            base_backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(10),
+            ..Default::default()
+        };
+        assert_eq!(config.backoff(1), Duration::from_secs(1));
+        assert_eq!(config.backoff(2), Duration::from_secs(2));
+        assert_eq!(config.backoff(4), Duration::from_secs(8));
+        assert_eq!(config.backoff(5), Duration::from_secs(10));
+        assert_eq!(config.backoff(u32::MAX), Duration::from_secs(10));
+    }
+
+    #[tokio::test]
+    async fn test_failover_prefers_available_endpoints() {
+        let primary = MockEndpoint::new(false);
+        let backing_off = MockEndpoint::new(true);
+        let available = MockEndpoint::new(true);
+        let mut transport = FailoverTransport::new(
+            vec![primary.clone(), backing_off.clone(), available.clone()],
+            FailoverConfig { failover_threshold: 1, ..Default::default() },
+        );
+        transport.state.record_failure(1);
+        assert_eq!(transport.state.failover_order(0), vec![2, 1]);
+
+        // The endpoint that is backing off is skipped in favour of the available one.
+        assert!(transport.call(request()).await.is_ok());
+        assert_eq!(transport.active(), 2);
+        assert_eq!(backing_off.calls(), 0);
+        assert_eq!(available.calls(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_failover_all_endpoints_failing() {
+        let primary = MockEndpoint::new(false);
+        let standby = MockEndpoint::new(false);
+        let mut transport = FailoverTransport::new(
+            vec![primary.clone(), standby.clone()],
+            FailoverConfig { failover_threshold: 1, ..Default::default() },
+        );
+
+        assert!(transport.call(request()).await.is_err());
+        assert_eq!(transport.active(), 0);
+        assert_eq!(primary.calls(), 1);
+        assert_eq!(standby.calls(), 1);
+    }
+}
@@ -20,7 +20,7 @@ use kona_protocol::{BatchValidationProvider, L2BlockInfo, to_system_config};
 use lru::LruCache;
 use op_alloy_consensus::OpBlock;
 use op_alloy_network::Optimism;
-use std::{num::NonZeroUsize, sync::Arc};
+use std::{num::NonZeroUsize, sync::Arc, vec::Vec};
 use tower::ServiceBuilder;
 
 /// The [AlloyL2ChainProvider] is a concrete implementation of the [L2ChainProvider] trait,
@@ -33,8 +33,10 @@ pub struct AlloyL2ChainProvider {
     trust_rpc: bool,
     /// The rollup configuration.
     rollup_config: Arc<RollupConfig>,
-    /// The `block_by_number` LRU cache.
-    block_by_number_cache: LruCache<u64, OpBlock>,
+    /// The `block_by_number` LRU cache, keyed by block hash.
+    block_by_hash_cache: LruCache<B256, OpBlock>,
+    /// The hashes of the cached blocks, keyed by block number.
+    block_hash_by_number_cache: LruCache<u64, B256>,
 }
 
 impl AlloyL2ChainProvider {
@@ -65,10 +67,50 @@ impl AlloyL2ChainProvider {
             inner,
             trust_rpc,
             rollup_config,
-            block_by_number_cache: LruCache::new(NonZeroUsize::new(cache_size).unwrap()),
+            block_by_hash_cache: LruCache::new(NonZeroUsize::new(cache_size).unwrap()),
+            block_hash_by_number_cache: LruCache::new(NonZeroUsize::new(cache_size).unwrap()),
         }
     }
 
+    /// Drops the cached blocks at and above the given number, e.g. after they were reorged out.
+    pub fn invalidate_from(&mut self, number: u64) {
+        let stale = self
+            .block_hash_by_number_cache
+            .iter()
+            .filter(|(cached, _)| **cached >= number)
+            .map(|(cached, hash)| (*cached, *hash))
+            .collect::<Vec<_>>();
+        for (cached, hash) in stale {
+            self.block_hash_by_number_cache.pop(&cached);
+            self.block_by_hash_cache.pop(&hash);
+        }
+    }
+
+    /// Caches a block fetched by number. If the block does not extend its cached parent, or is
+    /// not the parent of its cached child, the cached chain was reorged and the conflicting
+    /// blocks are dropped first.
+    fn cache_block(&mut self, number: u64, hash: B256, block: OpBlock) {
+        let parent_reorged = number
+            .checked_sub(1)
+            .and_then(|parent| self.block_hash_by_number_cache.peek(&parent))
+            .is_some_and(|parent_hash| *parent_hash != block.header.parent_hash);
+        if parent_reorged {
+            self.invalidate_from(number - 1);
+        }
+
+        let child_reorged = self
+            .block_hash_by_number_cache
+            .peek(&(number + 1))
+            .and_then(|child_hash| self.block_by_hash_cache.peek(child_hash))
+            .is_some_and(|child| child.header.parent_hash != hash);
+        if child_reorged {
+            self.invalidate_from(number + 1);
+        }
+
+        self.block_hash_by_number_cache.put(number, hash);
+        self.block_by_hash_cache.put(hash, block);
+    }
+
     /// Returns the chain ID.
     pub async fn chain_id(&mut self) -> Result<u64, RpcError<TransportErrorKind>> {
         self.inner.get_chain_id().await
@@ -227,7 +269,8 @@ impl BatchValidationProvider for AlloyL2ChainProvider {
     }
 
     async fn block_by_number(&mut self, number: u64) -> Result<OpBlock, Self::Error> {
-        if let Some(block) = self.block_by_number_cache.get(&number) {
+        let cached = self.block_hash_by_number_cache.get(&number).copied();
+        if let Some(block) = cached.and_then(|hash| self.block_by_hash_cache.get(&hash)) {
             return Ok(block.clone());
         }
 
@@ -242,11 +285,11 @@ impl BatchValidationProvider for AlloyL2ChainProvider {
                 kona_macros::inc!(gauge, Metrics::L2_CHAIN_PROVIDER_ERRORS, "method" => "l2_block_ref_by_number");
                 AlloyL2ChainProviderError::Transport(e)
             })?
-            .ok_or(AlloyL2ChainProviderError::BlockNotFound(number))?
-            .into_consensus()
-            .map_transactions(|t| t.inner.inner.into_inner());
+            .ok_or(AlloyL2ChainProviderError::BlockNotFound(number))?;
+        let hash = block.header.hash;
+        let block = block.into_consensus().map_transactions(|t| t.inner.inner.into_inner());
 
-        self.block_by_number_cache.put(number, block.clone());
+        self.cache_block(number, hash, block.clone());
         Ok(block)
     }
 }
@@ -268,3 +311,96 @@ impl L2ChainProvider for AlloyL2ChainProvider {
             .map_err(|_| AlloyL2ChainProviderError::SystemConfigConversion(number))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::Header;
+    use alloy_rpc_types_eth::{Block, BlockTransactions};
+    use alloy_transport::mock::{Asserter, MockTransport};
+
+    fn provider(asserter: &Asserter) -> AlloyL2ChainProvider {
+        let inner = RootProvider::new(RpcClient::new(MockTransport::new(asserter.clone()), false));
+        AlloyL2ChainProvider::new(inner, Arc::new(RollupConfig::default()), 8)
+    }
+
+    /// Returns the RPC block with the given number and parent hash, and its hash.
+    fn block(number: u64, parent_hash: B256) -> (Block<()>, B256) {
+        let header = Header { number, parent_hash, ..Default::default() };
+        let hash = header.hash_slow();
+        let block = Block {
+            header: alloy_rpc_types_eth::Header::new(header),
+            uncles: Vec::new(),
+            transactions: BlockTransactions::Hashes(Vec::new()),
+            withdrawals: None,
+        };
+        (block, hash)
+    }
+
+    #[tokio::test]
+    async fn test_block_by_number_is_cached() {
+        let asserter = Asserter::new();
+        let mut l2 = provider(&asserter);
+        let (one, one_hash) = block(1, B256::ZERO);
+        asserter.push_success(&one);
+
+        assert_eq!(l2.block_by_number(1).await.unwrap().header.hash_slow(), one_hash);
+        assert_eq!(l2.block_by_number(1).await.unwrap().header.hash_slow(), one_hash);
+        assert!(asserter.read_q().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_reorged_child_is_invalidated() {
+        let asserter = Asserter::new();
+        let mut l2 = provider(&asserter);
+        let (stale_two, _) = block(2, B256::repeat_byte(0xAA));
+        let (one, one_hash) = block(1, B256::ZERO);
+        let (two, two_hash) = block(2, one_hash);
+        asserter.push_success(&stale_two);
+        asserter.push_success(&one);
+        asserter.push_success(&two);
+
+        l2.block_by_number(2).await.unwrap();
+        l2.block_by_number(1).await.unwrap();
+
+        // Block 1 does not match the parent of the cached block 2, which is refetched.
+        assert_eq!(l2.block_by_number(2).await.unwrap().header.hash_slow(), two_hash);
+        assert!(asserter.read_q().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_reorged_parent_is_invalidated() {
+        let asserter = Asserter::new();
+        let mut l2 = provider(&asserter);
+        let (stale_one, _) = block(1, B256::repeat_byte(0xAA));
+        let (one, one_hash) = block(1, B256::ZERO);
+        let (two, _) = block(2, one_hash);
+        asserter.push_success(&stale_one);
+        asserter.push_success(&two);
+        asserter.push_success(&one);
+
+        l2.block_by_number(1).await.unwrap();
+        l2.block_by_number(2).await.unwrap();
+
+        // Block 2 does not extend the cached block 1, which is refetched.
+        assert_eq!(l2.block_by_number(1).await.unwrap().header.hash_slow(), one_hash);
+        assert!(asserter.read_q().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_invalidate_from() {
+        let asserter = Asserter::new();
+        let mut l2 = provider(&asserter);
+        let (one, one_hash) = block(1, B256::ZERO);
+        let (two, _) = block(2, one_hash);
+        asserter.push_success(&one);
+        asserter.push_success(&two);
+        l2.block_by_number(1).await.unwrap();
+        l2.block_by_number(2).await.unwrap();
+
+        l2.invalidate_from(2);
+        assert!(l2.block_hash_by_number_cache.contains(&1));
+        assert!(!l2.block_hash_by_number_cache.contains(&2));
+        assert_eq!(l2.block_by_hash_cache.len(), 1);
+    }
+}
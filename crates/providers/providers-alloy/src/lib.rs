@@ -15,14 +15,32 @@ pub use beacon_client::{
     ReducedGenesisData,
 };
 
+mod beacon_redundant;
+pub use beacon_redundant::{BeaconRedundancy, RedundantBeaconClient, RedundantBeaconClientError};
+
 mod blobs;
 pub use blobs::{BoxedBlobWithIndex, OnlineBlobProvider};
 
+mod caching;
+pub use caching::CacheError;
+
 mod chain_provider;
 pub use chain_provider::{AlloyChainProvider, AlloyChainProviderError};
 
+mod receipts;
+pub use receipts::ReceiptsStrategy;
+
+mod failover;
+pub use failover::{FailoverConfig, FailoverTransport};
+
 mod l2_chain_provider;
 pub use l2_chain_provider::{AlloyL2ChainProvider, AlloyL2ChainProviderError};
 
+mod throttle;
+pub use throttle::{ThrottleConfig, ThrottleLayer, ThrottledTransport};
+
 mod pipeline;
 pub use pipeline::OnlinePipeline;
+
+mod prefetch;
+pub use prefetch::{L1PrefetchCache, L1Prefetcher, PrefetchError};
@@ -23,6 +23,9 @@ impl Metrics {
     /// Identifier for the gauge that tracks beacon client errors.
     pub const BEACON_CLIENT_ERRORS: &str = "kona_providers_beacon_errors";
 
+    /// Identifier for the counter of mismatched blobs served by beacon endpoints.
+    pub const BEACON_BLOB_MISMATCH_COUNT: &str = "kona_providers_beacon_blob_mismatch_count";
+
     /// Identifier for the gauge that tracks L2 chain provider requests.
     pub const L2_CHAIN_PROVIDER_REQUESTS: &str = "kona_providers_l2_chain_requests";
 
@@ -82,6 +85,10 @@ impl Metrics {
             Self::BEACON_CLIENT_ERRORS,
             "Number of errors in beacon client requests"
         );
+        metrics::describe_counter!(
+            Self::BEACON_BLOB_MISMATCH_COUNT,
+            "Number of blobs served by beacon endpoints that do not match their versioned hashes"
+        );
         metrics::describe_gauge!(
             Self::L2_CHAIN_PROVIDER_REQUESTS,
             "Number of requests made to L2 chain provider"
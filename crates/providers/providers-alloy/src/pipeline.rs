@@ -1,11 +1,13 @@
 //! Contains an online derivation pipeline.
 
-use crate::{AlloyChainProvider, AlloyL2ChainProvider, OnlineBeaconClient, OnlineBlobProvider};
+use crate::{
+    AlloyChainProvider, AlloyL2ChainProvider, BeaconClient, OnlineBeaconClient, OnlineBlobProvider,
+};
 use async_trait::async_trait;
 use core::fmt::Debug;
 use kona_derive::{
     DerivationPipeline, EthereumDataSource, IndexedAttributesQueueStage, L2ChainProvider,
-    OriginProvider, Pipeline, PipelineBuilder, PipelineErrorKind, PipelineResult,
+    OriginProvider, Pipeline, PipelineBuilder, PipelineErrorKind, PipelineResult, PipelineSnapshot,
     PolledAttributesQueueStage, ResetSignal, Signal, SignalReceiver, StatefulAttributesBuilder,
     StepResult,
 };
@@ -14,9 +16,9 @@ use kona_protocol::{BlockInfo, L2BlockInfo, OpAttributesWithParent};
 use std::sync::Arc;
 
 /// An online polled derivation pipeline.
-type OnlinePolledDerivationPipeline = DerivationPipeline<
+type OnlinePolledDerivationPipeline<B> = DerivationPipeline<
     PolledAttributesQueueStage<
-        OnlineDataProvider,
+        OnlineDataProvider<B>,
         AlloyChainProvider,
         AlloyL2ChainProvider,
         OnlineAttributesBuilder,
@@ -25,9 +27,9 @@ type OnlinePolledDerivationPipeline = DerivationPipeline<
 >;
 
 /// An online managed derivation pipeline.
-type OnlineManagedDerivationPipeline = DerivationPipeline<
+type OnlineManagedDerivationPipeline<B> = DerivationPipeline<
     IndexedAttributesQueueStage<
-        OnlineDataProvider,
+        OnlineDataProvider<B>,
         AlloyChainProvider,
         AlloyL2ChainProvider,
         OnlineAttributesBuilder,
@@ -36,30 +38,35 @@ type OnlineManagedDerivationPipeline = DerivationPipeline<
 >;
 
 /// An RPC-backed Ethereum data source.
-type OnlineDataProvider =
-    EthereumDataSource<AlloyChainProvider, OnlineBlobProvider<OnlineBeaconClient>>;
+type OnlineDataProvider<B> = EthereumDataSource<AlloyChainProvider, OnlineBlobProvider<B>>;
 
 /// An RPC-backed payload attributes builder for the `AttributesQueue` stage of the derivation
 /// pipeline.
 type OnlineAttributesBuilder = StatefulAttributesBuilder<AlloyChainProvider, AlloyL2ChainProvider>;
 
-/// An online derivation pipeline.
+/// An online derivation pipeline, fetching blobs through the [BeaconClient] `B`.
 #[derive(Debug)]
-pub enum OnlinePipeline {
+pub enum OnlinePipeline<B = OnlineBeaconClient>
+where
+    B: BeaconClient + Send + Sync + Clone + Debug,
+{
     /// An online derivation pipeline that uses a polled traversal stage.
-    Polled(OnlinePolledDerivationPipeline),
+    Polled(OnlinePolledDerivationPipeline<B>),
     /// An online derivation pipeline that uses a managed traversal stage.
-    Managed(OnlineManagedDerivationPipeline),
+    Managed(OnlineManagedDerivationPipeline<B>),
 }
 
-impl OnlinePipeline {
+impl<B> OnlinePipeline<B>
+where
+    B: BeaconClient + Send + Sync + Clone + Debug,
+{
     /// Constructs a new polled derivation pipeline that is initialized.
     pub async fn new(
         cfg: Arc<RollupConfig>,
         l1_cfg: Arc<L1ChainConfig>,
         l2_safe_head: L2BlockInfo,
         l1_origin: BlockInfo,
-        blob_provider: OnlineBlobProvider<OnlineBeaconClient>,
+        blob_provider: OnlineBlobProvider<B>,
         chain_provider: AlloyChainProvider,
         mut l2_chain_provider: AlloyL2ChainProvider,
     ) -> PipelineResult<Self> {
@@ -100,7 +107,7 @@ impl OnlinePipeline {
     pub fn new_polled(
         cfg: Arc<RollupConfig>,
         l1_cfg: Arc<L1ChainConfig>,
-        blob_provider: OnlineBlobProvider<OnlineBeaconClient>,
+        blob_provider: OnlineBlobProvider<B>,
         chain_provider: AlloyChainProvider,
         l2_chain_provider: AlloyL2ChainProvider,
     ) -> Self {
@@ -134,7 +141,7 @@ impl OnlinePipeline {
     pub fn new_indexed(
         cfg: Arc<RollupConfig>,
         l1_cfg: Arc<L1ChainConfig>,
-        blob_provider: OnlineBlobProvider<OnlineBeaconClient>,
+        blob_provider: OnlineBlobProvider<B>,
         chain_provider: AlloyChainProvider,
         l2_chain_provider: AlloyL2ChainProvider,
     ) -> Self {
@@ -160,7 +167,10 @@ impl OnlinePipeline {
 }
 
 #[async_trait]
-impl SignalReceiver for OnlinePipeline {
+impl<B> SignalReceiver for OnlinePipeline<B>
+where
+    B: BeaconClient + Send + Sync + Clone + Debug,
+{
     /// Receives a signal from the driver.
     async fn signal(&mut self, signal: Signal) -> PipelineResult<()> {
         match self {
@@ -170,7 +180,10 @@ impl SignalReceiver for OnlinePipeline {
     }
 }
 
-impl OriginProvider for OnlinePipeline {
+impl<B> OriginProvider for OnlinePipeline<B>
+where
+    B: BeaconClient + Send + Sync + Clone + Debug,
+{
     /// Returns the optional L1 [BlockInfo] origin.
     fn origin(&self) -> Option<BlockInfo> {
         match self {
@@ -180,7 +193,10 @@ impl OriginProvider for OnlinePipeline {
     }
 }
 
-impl Iterator for OnlinePipeline {
+impl<B> Iterator for OnlinePipeline<B>
+where
+    B: BeaconClient + Send + Sync + Clone + Debug,
+{
     type Item = OpAttributesWithParent;
 
     fn next(&mut self) -> Option<Self::Item> {
@@ -192,7 +208,10 @@ impl Iterator for OnlinePipeline {
 }
 
 #[async_trait]
-impl Pipeline for OnlinePipeline {
+impl<B> Pipeline for OnlinePipeline<B>
+where
+    B: BeaconClient + Send + Sync + Clone + Debug,
+{
     /// Peeks at the next [OpAttributesWithParent] from the pipeline.
     fn peek(&self) -> Option<&OpAttributesWithParent> {
         match self {
@@ -227,4 +246,12 @@ impl Pipeline for OnlinePipeline {
             Self::Managed(pipeline) => pipeline.system_config_by_number(number).await,
         }
     }
+
+    /// Returns a [PipelineSnapshot] of the internal state of the pipeline.
+    fn snapshot(&self) -> PipelineSnapshot {
+        match self {
+            Self::Polled(pipeline) => pipeline.snapshot(),
+            Self::Managed(pipeline) => pipeline.snapshot(),
+        }
+    }
 }
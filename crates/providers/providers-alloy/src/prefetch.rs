@@ -0,0 +1,298 @@
+//! Contains the [L1Prefetcher], which fetches L1 data ahead of the derivation pipeline, and the
+//! [L1PrefetchCache] it shares with the providers of the pipeline.
+
+use crate::{AlloyChainProvider, AlloyChainProviderError, BeaconClient, OnlineBlobProvider};
+use alloy_consensus::{Receipt, Transaction, TxEnvelope};
+use alloy_eips::eip4844::{Blob, IndexedBlobHash};
+use alloy_primitives::{Address, B256};
+use kona_derive::{BlobProviderError, ChainProvider};
+use kona_protocol::BlockInfo;
+use lru::LruCache;
+use std::{
+    boxed::Box,
+    num::NonZeroUsize,
+    ops::RangeInclusive,
+    sync::{Arc, Mutex, MutexGuard},
+    vec::Vec,
+};
+use tokio::sync::watch;
+
+/// The maximum number of blobs per L1 block, since the Prague hardfork.
+const MAX_BLOBS_PER_BLOCK: usize = 9;
+
+/// The L1 data fetched ahead of the derivation pipeline.
+#[derive(Debug)]
+struct PrefetchedData {
+    /// The blocks and their transactions, by block hash.
+    blocks: LruCache<B256, (BlockInfo, Vec<TxEnvelope>)>,
+    /// The block receipts, by block hash.
+    receipts: LruCache<B256, Vec<Receipt>>,
+    /// The blobs, by versioned hash.
+    blobs: LruCache<B256, Box<Blob>>,
+}
+
+/// L1 data fetched ahead of the derivation pipeline, shared between the [L1Prefetcher] that fills
+/// it and the [AlloyChainProvider] and [OnlineBlobProvider] of the pipeline that consume it.
+///
+/// The cache also tracks the derivation cursor: the number of the last L1 block that the pipeline
+/// requested by number, which is the next L1 origin of the pipeline.
+#[derive(Debug, Clone)]
+pub struct L1PrefetchCache {
+    /// The prefetched data.
+    data: Arc<Mutex<PrefetchedData>>,
+    /// The derivation cursor.
+    cursor: Arc<watch::Sender<Option<u64>>>,
+}
+
+impl L1PrefetchCache {
+    /// Creates a new [L1PrefetchCache], holding the data of up to `capacity` blocks.
+    pub fn new(capacity: NonZeroUsize) -> Self {
+        let blobs_capacity = NonZeroUsize::new(capacity.get().saturating_mul(MAX_BLOBS_PER_BLOCK))
+            .unwrap_or(capacity);
+        let data = PrefetchedData {
+            blocks: LruCache::new(capacity),
+            receipts: LruCache::new(capacity),
+            blobs: LruCache::new(blobs_capacity),
+        };
+        Self { data: Arc::new(Mutex::new(data)), cursor: Arc::new(watch::Sender::new(None)) }
+    }
+
+    /// Returns a receiver of the derivation cursor updates.
+    pub fn subscribe_cursor(&self) -> watch::Receiver<Option<u64>> {
+        self.cursor.subscribe()
+    }
+
+    /// Records that the pipeline requested the L1 block with the given number.
+    pub(crate) fn observe_cursor(&self, number: u64) {
+        self.cursor.send_if_modified(|cursor| {
+            let modified = *cursor != Some(number);
+            *cursor = Some(number);
+            modified
+        });
+    }
+
+    /// Returns the prefetched block and transactions with the given hash.
+    pub(crate) fn block(&self, hash: &B256) -> Option<(BlockInfo, Vec<TxEnvelope>)> {
+        self.data().blocks.get(hash).cloned()
+    }
+
+    /// Returns the prefetched receipts of the block with the given hash.
+    pub(crate) fn receipts(&self, hash: &B256) -> Option<Vec<Receipt>> {
+        self.data().receipts.get(hash).cloned()
+    }
+
+    /// Returns the prefetched blobs with the given hashes, if all of them were prefetched.
+    pub(crate) fn blobs(&self, hashes: &[IndexedBlobHash]) -> Option<Vec<Box<Blob>>> {
+        let mut data = self.data();
+        hashes.iter().map(|hash| data.blobs.get(&hash.hash).cloned()).collect()
+    }
+
+    /// Locks the prefetched data.
+    fn data(&self) -> MutexGuard<'_, PrefetchedData> {
+        self.data.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Fetches the blocks, receipts and batcher blobs of the L1 blocks following the derivation
+/// cursor into an [L1PrefetchCache], so that the network latency overlaps with the processing of
+/// the current L1 origin by the pipeline.
+#[derive(Debug)]
+pub struct L1Prefetcher<B: BeaconClient> {
+    /// The provider used to fetch blocks and receipts.
+    chain_provider: AlloyChainProvider,
+    /// The provider used to fetch blobs.
+    blob_provider: OnlineBlobProvider<B>,
+    /// The batch inbox address, whose blob transactions have their blobs prefetched.
+    batch_inbox_address: Address,
+    /// The cache filled with the prefetched data.
+    cache: L1PrefetchCache,
+    /// The maximum number of blocks prefetched ahead of the derivation cursor.
+    lookahead: u64,
+    /// The number of the next block to prefetch.
+    next: u64,
+}
+
+impl<B> L1Prefetcher<B>
+where
+    B: BeaconClient + Send + Sync,
+{
+    /// Creates a new [L1Prefetcher].
+    pub fn new(
+        chain_provider: AlloyChainProvider,
+        blob_provider: OnlineBlobProvider<B>,
+        batch_inbox_address: Address,
+        cache: L1PrefetchCache,
+        lookahead: u64,
+    ) -> Self {
+        Self { chain_provider, blob_provider, batch_inbox_address, cache, lookahead, next: 0 }
+    }
+
+    /// Creates a new [L1Prefetcher], with a blob provider initialized from the given beacon
+    /// client.
+    pub async fn init(
+        chain_provider: AlloyChainProvider,
+        beacon_client: B,
+        batch_inbox_address: Address,
+        cache: L1PrefetchCache,
+        lookahead: u64,
+    ) -> Result<Self, PrefetchError> {
+        let blob_provider = OnlineBlobProvider::try_init(beacon_client).await?;
+        Ok(Self::new(chain_provider, blob_provider, batch_inbox_address, cache, lookahead))
+    }
+
+    /// Prefetches the blocks following the given derivation cursor, up to the lookahead and the
+    /// given L1 head. Blocks prefetched by a previous call are skipped, unless the cursor moved
+    /// backwards, e.g. after a reset of the pipeline.
+    pub async fn prefetch(&mut self, cursor: u64, head: u64) -> Result<(), PrefetchError> {
+        let window = self.window(cursor, head);
+        self.next = *window.start();
+        for number in window {
+            self.prefetch_block(number).await?;
+            self.next = number + 1;
+        }
+        Ok(())
+    }
+
+    /// Returns the numbers of the blocks to prefetch for the given derivation cursor and L1 head.
+    fn window(&self, cursor: u64, head: u64) -> RangeInclusive<u64> {
+        let first = if self.next <= cursor || self.next > cursor.saturating_add(self.lookahead) + 1
+        {
+            cursor + 1
+        } else {
+            self.next
+        };
+        first..=cursor.saturating_add(self.lookahead).min(head)
+    }
+
+    /// Prefetches the block with the given number, its receipts and its batcher blobs.
+    async fn prefetch_block(&mut self, number: u64) -> Result<(), PrefetchError> {
+        let hash = self.chain_provider.block_info_by_number(number).await?.hash;
+        let (block_info, txs) =
+            self.chain_provider.block_info_and_transactions_by_hash(hash).await?;
+        let receipts = self.chain_provider.receipts_by_hash(hash).await?;
+
+        let blob_hashes = self.batcher_blob_hashes(&txs);
+        let blobs =
+            self.blob_provider.fetch_filtered_blob_sidecars(&block_info, &blob_hashes).await?;
+
+        let mut data = self.cache.data();
+        data.blocks.put(hash, (block_info, txs));
+        data.receipts.put(hash, receipts);
+        for sidecar in blobs {
+            data.blobs.put(sidecar.to_kzg_versioned_hash().into(), sidecar.blob);
+        }
+        Ok(())
+    }
+
+    /// Returns the indexed hashes of the blobs sent to the batch inbox by the given transactions.
+    /// The batcher address is not checked, which is left to the derivation pipeline.
+    fn batcher_blob_hashes(&self, txs: &[TxEnvelope]) -> Vec<IndexedBlobHash> {
+        let mut index = 0;
+        let mut hashes = Vec::new();
+        for tx in txs {
+            let blob_hashes = tx.blob_versioned_hashes().unwrap_or_default();
+            if tx.to() == Some(self.batch_inbox_address) {
+                hashes.extend(
+                    blob_hashes
+                        .iter()
+                        .enumerate()
+                        .map(|(i, hash)| IndexedBlobHash { hash: *hash, index: index + i as u64 }),
+                );
+            }
+            index += blob_hashes.len() as u64;
+        }
+        hashes
+    }
+}
+
+/// An error returned by the [L1Prefetcher].
+#[derive(Debug, thiserror::Error)]
+pub enum PrefetchError {
+    /// Failed to fetch a block or its receipts.
+    #[error(transparent)]
+    ChainProvider(#[from] AlloyChainProviderError),
+    /// Failed to fetch blobs.
+    #[error(transparent)]
+    BlobProvider(#[from] BlobProviderError),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::OnlineBeaconClient;
+    use alloy_provider::RootProvider;
+    use alloy_rpc_client::RpcClient;
+    use alloy_transport::mock::{Asserter, MockTransport};
+
+    fn prefetcher(lookahead: u64, next: u64) -> L1Prefetcher<OnlineBeaconClient> {
+        let inner = RootProvider::new(RpcClient::new(MockTransport::new(Asserter::new()), false));
+        let blob_provider = OnlineBlobProvider {
+            beacon_client: OnlineBeaconClient::new_http("http://localhost".into()),
+            genesis_time: 0,
+            slot_interval: 12,
+            prefetched: None,
+        };
+        let cache = L1PrefetchCache::new(NonZeroUsize::new(4).unwrap());
+        let mut prefetcher = L1Prefetcher::new(
+            AlloyChainProvider::new(inner, 1),
+            blob_provider,
+            Address::ZERO,
+            cache,
+            lookahead,
+        );
+        prefetcher.next = next;
+        prefetcher
+    }
+
+    #[test]
+    fn test_prefetch_window() {
+        // Nothing was prefetched yet: the lookahead after the cursor, bounded by the head.
+        assert_eq!(prefetcher(3, 0).window(10, 100), 11..=13);
+        assert_eq!(prefetcher(3, 0).window(10, 11), 11..=11);
+
+        // Blocks prefetched by a previous call are skipped.
+        assert_eq!(prefetcher(3, 14).window(11, 100), 14..=14);
+        assert_eq!(prefetcher(3, 14).window(13, 100), 14..=16);
+        assert!(prefetcher(3, 14).window(10, 100).is_empty());
+        assert!(prefetcher(3, 14).window(12, 13).is_empty());
+
+        // The cursor moved backwards past the lookahead, or past the prefetched blocks.
+        assert_eq!(prefetcher(3, 14).window(5, 100), 6..=8);
+        assert_eq!(prefetcher(3, 14).window(20, 100), 21..=23);
+    }
+
+    #[test]
+    fn test_observe_cursor() {
+        let cache = L1PrefetchCache::new(NonZeroUsize::new(4).unwrap());
+        let mut cursor = cache.subscribe_cursor();
+        assert_eq!(*cursor.borrow(), None);
+
+        cache.observe_cursor(10);
+        assert!(cursor.has_changed().unwrap());
+        assert_eq!(*cursor.borrow_and_update(), Some(10));
+
+        cache.observe_cursor(10);
+        assert!(!cursor.has_changed().unwrap());
+    }
+
+    #[test]
+    fn test_blobs_requires_all_hashes() {
+        let cache = L1PrefetchCache::new(NonZeroUsize::new(4).unwrap());
+        let first = IndexedBlobHash { hash: B256::repeat_byte(1), index: 0 };
+        let second = IndexedBlobHash { hash: B256::repeat_byte(2), index: 1 };
+        cache.data().blobs.put(first.hash, Box::new(Blob::repeat_byte(1)));
+
+        assert_eq!(cache.blobs(&[first]).unwrap().len(), 1);
+        assert!(cache.blobs(&[first, second]).is_none());
+    }
+
+    #[test]
+    fn test_clones_share_data() {
+        let cache = L1PrefetchCache::new(NonZeroUsize::new(4).unwrap());
+        let clone = cache.clone();
+        let hash = B256::repeat_byte(1);
+        cache.data().receipts.put(hash, Vec::new());
+        assert_eq!(clone.receipts(&hash), Some(Vec::new()));
+        assert!(clone.block(&hash).is_none());
+    }
+}
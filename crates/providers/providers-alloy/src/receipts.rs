@@ -0,0 +1,174 @@
+//! Contains the [ReceiptsStrategy], the JSON-RPC method used to fetch the receipts of L1 blocks.
+
+use crate::AlloyChainProviderError;
+use alloy_consensus::{Receipt, ReceiptEnvelope};
+use alloy_eips::eip2718::Decodable2718;
+use alloy_primitives::{B256, Bytes};
+use alloy_provider::{Provider, RootProvider};
+use alloy_transport::RpcError;
+use core::fmt;
+use std::vec::Vec;
+
+/// The JSON-RPC error code of a method that is not found or not available.
+const METHOD_NOT_FOUND_CODE: i64 = -32601;
+
+/// The JSON-RPC method used to fetch the receipts of an L1 block.
+///
+/// Endpoints support different methods: `eth_getBlockReceipts` is not implemented by every
+/// client, and `debug_getRawReceipts` requires the `debug` namespace to be enabled. The
+/// [AlloyChainProvider] probes the methods supported by its endpoint, and uses the most
+/// efficient one.
+///
+/// [AlloyChainProvider]: crate::AlloyChainProvider
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ReceiptsStrategy {
+    /// Fetches all receipts of the block in a single `eth_getBlockReceipts` request.
+    BlockReceipts,
+    /// Fetches all EIP-2718 encoded receipts of the block in a single `debug_getRawReceipts`
+    /// request.
+    RawReceipts,
+    /// Fetches the receipt of every transaction of the block with `eth_getTransactionReceipt`.
+    /// Supported by every endpoint, but requires a request per transaction.
+    TransactionReceipts,
+}
+
+impl ReceiptsStrategy {
+    /// The strategies that fetch all receipts of a block in a single request, from the most to
+    /// the least efficient. If the endpoint supports none of them,
+    /// [ReceiptsStrategy::TransactionReceipts] is used.
+    pub const PROBED: [Self; 2] = [Self::BlockReceipts, Self::RawReceipts];
+
+    /// Returns the JSON-RPC method of the strategy.
+    pub const fn method(&self) -> &'static str {
+        match self {
+            Self::BlockReceipts => "eth_getBlockReceipts",
+            Self::RawReceipts => "debug_getRawReceipts",
+            Self::TransactionReceipts => "eth_getTransactionReceipt",
+        }
+    }
+
+    /// Fetches the receipts of the block with the given hash.
+    pub(crate) async fn fetch(
+        &self,
+        provider: &RootProvider,
+        hash: B256,
+    ) -> Result<Vec<Receipt>, AlloyChainProviderError> {
+        match self {
+            Self::BlockReceipts => provider
+                .get_block_receipts(hash.into())
+                .await?
+                .ok_or(AlloyChainProviderError::BlockNotFound(hash.into()))?
+                .into_iter()
+                .map(|r| r.inner.into_primitives_receipt().as_receipt().cloned())
+                .collect::<Option<Vec<_>>>()
+                .ok_or(AlloyChainProviderError::ReceiptsConversion(hash)),
+            Self::RawReceipts => {
+                let raw_receipts: Vec<Bytes> =
+                    provider.client().request(self.method(), [hash]).await?;
+
+                // Unknown blocks have no receipts, like empty blocks.
+                if raw_receipts.is_empty() && provider.get_block_by_hash(hash).await?.is_none() {
+                    return Err(AlloyChainProviderError::BlockNotFound(hash.into()));
+                }
+
+                raw_receipts
+                    .iter()
+                    .map(|raw| {
+                        ReceiptEnvelope::decode_2718(&mut raw.as_ref())
+                            .ok()
+                            .and_then(|envelope| envelope.as_receipt().cloned())
+                    })
+                    .collect::<Option<Vec<_>>>()
+                    .ok_or(AlloyChainProviderError::ReceiptsConversion(hash))
+            }
+            Self::TransactionReceipts => {
+                let block = provider
+                    .get_block_by_hash(hash)
+                    .await?
+                    .ok_or(AlloyChainProviderError::BlockNotFound(hash.into()))?;
+
+                let mut receipts = Vec::with_capacity(block.transactions.len());
+                for tx_hash in block.transactions.hashes() {
+                    let receipt = provider
+                        .get_transaction_receipt(tx_hash)
+                        .await?
+                        .ok_or(AlloyChainProviderError::ReceiptsConversion(hash))?;
+
+                    // The block may have been reorged out between the requests.
+                    if receipt.block_hash != Some(hash) {
+                        return Err(AlloyChainProviderError::ReceiptNotInBlock(tx_hash, hash));
+                    }
+
+                    let receipt = receipt
+                        .inner
+                        .into_primitives_receipt()
+                        .as_receipt()
+                        .cloned()
+                        .ok_or(AlloyChainProviderError::ReceiptsConversion(hash))?;
+                    receipts.push(receipt);
+                }
+                Ok(receipts)
+            }
+        }
+    }
+}
+
+impl fmt::Display for ReceiptsStrategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.method())
+    }
+}
+
+/// Returns whether the error reports that the requested method is not supported by the endpoint,
+/// either with the JSON-RPC method not found code, or with one of geth's method not found
+/// messages. Other errors, such as a missing block, must not downgrade the receipts method.
+pub(crate) fn is_method_not_found(err: &AlloyChainProviderError) -> bool {
+    let AlloyChainProviderError::Transport(RpcError::ErrorResp(payload)) = err else {
+        return false;
+    };
+    let message = payload.message.as_ref();
+    payload.code == METHOD_NOT_FOUND_CODE ||
+        message.eq_ignore_ascii_case("method not found") ||
+        (message.starts_with("the method ") &&
+            message.ends_with(" does not exist/is not available"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_json_rpc::ErrorPayload;
+    use alloy_transport::TransportErrorKind;
+    use std::borrow::Cow;
+
+    fn error_resp(code: i64, message: &'static str) -> AlloyChainProviderError {
+        AlloyChainProviderError::Transport(RpcError::ErrorResp(ErrorPayload {
+            code,
+            message: Cow::Borrowed(message),
+            data: None,
+        }))
+    }
+
+    #[test]
+    fn test_is_method_not_found() {
+        assert!(is_method_not_found(&error_resp(-32601, "Method not found")));
+        assert!(is_method_not_found(&error_resp(
+            -32000,
+            "the method debug_getRawReceipts does not exist/is not available"
+        )));
+        assert!(is_method_not_found(&error_resp(-32000, "method not found")));
+        assert!(!is_method_not_found(&error_resp(-32000, "header not found")));
+        assert!(!is_method_not_found(&error_resp(-32000, "block 0x1234 does not exist")));
+        assert!(!is_method_not_found(&error_resp(-32000, "receipts not found: method not found")));
+        assert!(!is_method_not_found(&AlloyChainProviderError::BlockNotFound(B256::ZERO.into())));
+        assert!(!is_method_not_found(&AlloyChainProviderError::Transport(RpcError::Transport(
+            TransportErrorKind::BackendGone
+        ))));
+    }
+
+    #[test]
+    fn test_receipts_strategy_method() {
+        assert_eq!(ReceiptsStrategy::BlockReceipts.to_string(), "eth_getBlockReceipts");
+        assert_eq!(ReceiptsStrategy::RawReceipts.to_string(), "debug_getRawReceipts");
+        assert_eq!(ReceiptsStrategy::TransactionReceipts.to_string(), "eth_getTransactionReceipt");
+    }
+}
@@ -0,0 +1,277 @@
+//! Contains the [ThrottledTransport], a middleware that enforces a requests-per-second budget and
+//! coalesces identical concurrent requests into a single upstream call.
+
+use alloy_json_rpc::{RequestPacket, Response, ResponsePacket, ResponsePayload};
+use alloy_transport::{Transport, TransportError, TransportErrorKind, TransportFut};
+use std::{
+    boxed::Box,
+    collections::{HashMap, hash_map::Entry},
+    format,
+    num::NonZeroU32,
+    string::{String, ToString},
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+    time::{Duration, Instant},
+    vec::Vec,
+};
+use tokio::sync::oneshot;
+use tower::{Layer, Service};
+
+/// The prefixes of the JSON-RPC methods whose identical concurrent requests are coalesced. Only
+/// read-only methods are coalesced.
+const COALESCED_METHOD_PREFIXES: &[&str] = &["eth_get", "eth_call", "debug_getRaw"];
+
+/// The configuration of a [ThrottledTransport].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ThrottleConfig {
+    /// The maximum number of upstream requests per second. If [None], requests are not rate
+    /// limited.
+    pub requests_per_second: Option<NonZeroU32>,
+    /// Whether identical concurrent requests are coalesced into a single upstream request.
+    pub coalesce: bool,
+}
+
+impl Default for ThrottleConfig {
+    fn default() -> Self {
+        Self { requests_per_second: None, coalesce: true }
+    }
+}
+
+impl ThrottleConfig {
+    /// Creates a new [ThrottleConfig] with the given requests-per-second budget, coalescing
+    /// identical concurrent requests.
+    pub const fn new(requests_per_second: NonZeroU32) -> Self {
+        Self { requests_per_second: Some(requests_per_second), coalesce: true }
+    }
+
+    /// Sets whether identical concurrent requests are coalesced.
+    pub const fn with_coalescing(mut self, coalesce: bool) -> Self {
+        self.coalesce = coalesce;
+        self
+    }
+}
+
+/// A token bucket rate limiter, allowing bursts of up to one second's worth of requests.
+#[derive(Debug)]
+struct RateLimiter {
+    /// The number of tokens added to the bucket per second, and the capacity of the bucket.
+    rate: f64,
+    /// The number of tokens in the bucket, and the instant at which it was last refilled.
+    bucket: Mutex<(f64, Instant)>,
+}
+
+impl RateLimiter {
+    /// Creates a new [RateLimiter] with a full bucket.
+    fn new(requests_per_second: NonZeroU32) -> Self {
+        let rate = requests_per_second.get() as f64;
+        Self { rate, bucket: Mutex::new((rate, Instant::now())) }
+    }
+
+    /// Waits until a request may be sent upstream.
+    async fn acquire(&self) {
+        loop {
+            let wait = {
+                let mut bucket = self.bucket.lock().unwrap_or_else(|e| e.into_inner());
+                let now = Instant::now();
+                let (tokens, last) = *bucket;
+                let tokens =
+                    (tokens + now.duration_since(last).as_secs_f64() * self.rate).min(self.rate);
+                if tokens >= 1.0 {
+                    *bucket = (tokens - 1.0, now);
+                    return;
+                }
+                *bucket = (tokens, now);
+                Duration::from_secs_f64((1.0 - tokens) / self.rate)
+            };
+            tokio::time::sleep(wait).await;
+        }
+    }
+}
+
+/// The waiters of an in-flight coalesced request, which receive a copy of its response payload.
+type Waiters = Vec<oneshot::Sender<Result<ResponsePayload, String>>>;
+
+/// The state shared between clones of a [ThrottledTransport].
+#[derive(Debug)]
+struct ThrottleState {
+    /// The rate limiter, if requests are rate limited.
+    limiter: Option<RateLimiter>,
+    /// The in-flight coalesced requests, keyed by method and parameters, if requests are
+    /// coalesced.
+    in_flight: Option<Mutex<HashMap<String, Waiters>>>,
+}
+
+/// A [Layer] that wraps a transport in a [ThrottledTransport].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ThrottleLayer {
+    /// The [ThrottleConfig].
+    config: ThrottleConfig,
+}
+
+impl ThrottleLayer {
+    /// Creates a new [ThrottleLayer] with the given [ThrottleConfig].
+    pub const fn new(config: ThrottleConfig) -> Self {
+        Self { config }
+    }
+}
+
+impl<T> Layer<T> for ThrottleLayer {
+    type Service = ThrottledTransport<T>;
+
+    fn layer(&self, inner: T) -> Self::Service {
+        ThrottledTransport::new(inner, self.config)
+    }
+}
+
+/// A [Transport] middleware that enforces a requests-per-second budget on the inner transport, and
+/// coalesces identical concurrent read-only requests into a single upstream request.
+///
+/// Coalesced requests share the response of the upstream request, with the JSON-RPC ID of each
+/// caller's own request. Batch requests are rate limited as a single request, and are never
+/// coalesced.
+#[derive(Debug)]
+pub struct ThrottledTransport<T> {
+    /// The inner transport.
+    inner: T,
+    /// The shared throttling state.
+    state: Arc<ThrottleState>,
+}
+
+impl<T: Clone> Clone for ThrottledTransport<T> {
+    fn clone(&self) -> Self {
+        Self { inner: self.inner.clone(), state: Arc::clone(&self.state) }
+    }
+}
+
+impl<T> ThrottledTransport<T> {
+    /// Creates a new [ThrottledTransport] wrapping the given transport.
+    pub fn new(inner: T, config: ThrottleConfig) -> Self {
+        let state = ThrottleState {
+            limiter: config.requests_per_second.map(RateLimiter::new),
+            in_flight: config.coalesce.then(Default::default),
+        };
+        Self { inner, state: Arc::new(state) }
+    }
+}
+
+impl<T> ThrottledTransport<T>
+where
+    T: Transport,
+{
+    /// Sends the request upstream once the rate limiter permits it.
+    async fn send(
+        mut inner: T,
+        state: &ThrottleState,
+        request: RequestPacket,
+    ) -> Result<ResponsePacket, TransportError> {
+        if let Some(limiter) = state.limiter.as_ref() {
+            limiter.acquire().await;
+        }
+        inner.call(request).await
+    }
+
+    /// Sends the request upstream, or joins an identical in-flight request if there is one.
+    async fn send_coalesced(
+        inner: T,
+        state: Arc<ThrottleState>,
+        request: RequestPacket,
+    ) -> Result<ResponsePacket, TransportError> {
+        let (Some(in_flight), RequestPacket::Single(single)) = (state.in_flight.as_ref(), &request)
+        else {
+            return Self::send(inner, &state, request).await;
+        };
+        if !COALESCED_METHOD_PREFIXES.iter().any(|prefix| single.method().starts_with(prefix)) {
+            return Self::send(inner, &state, request).await;
+        }
+
+        let id = single.id().clone();
+        let key = format!("{}:{}", single.method(), single.params().map_or("", |p| p.get()));
+
+        // Join the in-flight request, if there is one.
+        let receiver = {
+            let mut in_flight = in_flight.lock().unwrap_or_else(|e| e.into_inner());
+            match in_flight.entry(key.clone()) {
+                Entry::Occupied(mut waiters) => {
+                    let (sender, receiver) = oneshot::channel();
+                    waiters.get_mut().push(sender);
+                    Some(receiver)
+                }
+                Entry::Vacant(entry) => {
+                    entry.insert(Vec::new());
+                    None
+                }
+            }
+        };
+        if let Some(receiver) = receiver {
+            return match receiver.await {
+                Ok(Ok(payload)) => Ok(ResponsePacket::Single(Response { id, payload })),
+                Ok(Err(err)) => Err(TransportErrorKind::custom_str(&err)),
+                Err(_) => Err(TransportErrorKind::custom_str("coalesced request was dropped")),
+            };
+        }
+
+        // Otherwise, send the request upstream and share the response with any waiters.
+        let guard = InFlightGuard { in_flight, key: Some(key) };
+        let result = Self::send(inner, &state, request).await;
+        for waiter in guard.complete() {
+            let shared = match &result {
+                Ok(ResponsePacket::Single(response)) => Ok(response.payload.clone()),
+                Ok(ResponsePacket::Batch(_)) => Err("unexpected batch response".to_string()),
+                Err(err) => Err(err.to_string()),
+            };
+            let _ = waiter.send(shared);
+        }
+        result
+    }
+}
+
+/// Removes an in-flight coalesced request when it completes. If the request is dropped before
+/// completing, its waiters are dropped along with it, failing their requests rather than leaving
+/// them waiting indefinitely.
+#[derive(Debug)]
+struct InFlightGuard<'a> {
+    /// The in-flight coalesced requests.
+    in_flight: &'a Mutex<HashMap<String, Waiters>>,
+    /// The key of the in-flight request, taken once it has been removed.
+    key: Option<String>,
+}
+
+impl InFlightGuard<'_> {
+    /// Removes the in-flight request, returning its waiters.
+    fn complete(mut self) -> Waiters {
+        self.remove()
+    }
+
+    /// Removes the in-flight request, if it has not already been removed.
+    fn remove(&mut self) -> Waiters {
+        self.key
+            .take()
+            .and_then(|key| self.in_flight.lock().unwrap_or_else(|e| e.into_inner()).remove(&key))
+            .unwrap_or_default()
+    }
+}
+
+impl Drop for InFlightGuard<'_> {
+    fn drop(&mut self) {
+        self.remove();
+    }
+}
+
+impl<T> Service<RequestPacket> for ThrottledTransport<T>
+where
+    T: Transport,
+{
+    type Response = ResponsePacket;
+    type Error = TransportError;
+    type Future = TransportFut<'static>;
+
+    fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: RequestPacket) -> Self::Future {
+        let inner = self.inner.clone();
+        let state = Arc::clone(&self.state);
+        Box::pin(Self::send_coalesced(inner, state, request))
+    }
+}
@@ -2,7 +2,7 @@ use super::RollupConfigSet;
 use crate::syncnode::ClientConfig;
 use alloy_primitives::ChainId;
 use derive_more::Constructor;
-use kona_interop::{DependencySet, InteropValidationError, InteropValidator};
+use kona_interop::{DependencySet, InteropValidationError, InteropValidator, MessageValidator};
 use kona_protocol::BlockInfo;
 use std::{net::SocketAddr, path::PathBuf};
 
@@ -47,24 +47,14 @@ impl InteropValidator for Config {
             return Err(InteropValidationError::InteropNotEnabled);
         }
 
-        // Executing timestamp must not be earlier than the initiating timestamp
-        if initiating_timestamp > executing_timestamp {
-            return Err(InteropValidationError::InvalidTimestampInvariant {
-                initiating: initiating_timestamp,
-                executing: executing_timestamp,
-            });
-        }
-
-        // Ensure the message has not expired by the time of execution
-        let expiry_window = self.dependency_set.get_message_expiry_window();
-        let expires_at = initiating_timestamp.saturating_add(expiry_window);
-        let execution_deadline = executing_timestamp.saturating_add(timeout.unwrap_or(0));
-
-        if expires_at < execution_deadline {
-            return Err(InteropValidationError::InvalidInteropTimestamp(executing_timestamp));
-        }
+        // Both chains must be part of the dependency set
+        let validator = MessageValidator::from(&self.dependency_set);
+        validator.validate_dependency(&self.dependency_set, initiating_chain_id)?;
+        validator.validate_dependency(&self.dependency_set, executing_chain_id)?;
 
-        Ok(())
+        // Executing timestamp must not be earlier than the initiating timestamp, and the message
+        // must not have expired by the time of execution
+        validator.validate_timestamps(initiating_timestamp, executing_timestamp, timeout)
     }
 
     fn is_post_interop(&self, chain_id: ChainId, timestamp: u64) -> bool {
@@ -80,7 +70,7 @@ impl InteropValidator for Config {
 mod tests {
     use super::*;
     use crate::config::RollupConfig;
-    use kona_interop::DependencySet;
+    use kona_interop::{ChainDependency, DependencySet};
     use std::{collections::HashMap, net::SocketAddr, path::PathBuf};
 
     fn mock_rollup_config_set() -> RollupConfigSet {
@@ -103,7 +93,9 @@ mod tests {
             rpc_addr: SocketAddr::from(([127, 0, 0, 1], 8545)),
             enable_admin_api: false,
             dependency_set: DependencySet {
-                dependencies: Default::default(),
+                dependencies: [(1, ChainDependency::default()), (2, ChainDependency::default())]
+                    .into_iter()
+                    .collect(),
                 override_message_expiry_window: Some(10),
             },
             rollup_config_set: mock_rollup_config_set(),
@@ -136,6 +128,14 @@ mod tests {
         assert_eq!(res, Err(InteropValidationError::InteropNotEnabled));
     }
 
+    #[test]
+    fn test_chain_not_in_dependency_set() {
+        let mut cfg = mock_config();
+        cfg.dependency_set.dependencies.remove(&2);
+        let res = cfg.validate_interop_timestamps(1, 200, 2, 202, None);
+        assert_eq!(res, Err(InteropValidationError::ChainNotInDependencySet(2)));
+    }
+
     #[test]
     fn test_invalid_timestamp_invariant() {
         let cfg = mock_config();
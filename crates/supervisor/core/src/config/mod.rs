@@ -5,3 +5,6 @@ pub use rollup_config_set::{Genesis, RollupConfig, RollupConfigSet};
 
 mod core_config;
 pub use core_config::Config;
+
+mod shared_config;
+pub use shared_config::SharedConfig;
@@ -0,0 +1,110 @@
+use super::Config;
+use alloy_primitives::ChainId;
+use kona_interop::{InteropValidationError, InteropValidator};
+use kona_protocol::BlockInfo;
+use std::sync::{Arc, PoisonError, RwLock};
+
+/// A [`Config`] shared between the supervisor components that can be swapped at runtime.
+///
+/// Every clone points at the same underlying [`Config`], so a reloaded dependency set becomes
+/// visible to the RPC server, chain processors and safety checkers at once.
+#[derive(Debug, Clone)]
+pub struct SharedConfig(Arc<RwLock<Arc<Config>>>);
+
+impl SharedConfig {
+    /// Creates a new [`SharedConfig`] holding the given [`Config`].
+    pub fn new(config: Config) -> Self {
+        Self(Arc::new(RwLock::new(Arc::new(config))))
+    }
+
+    /// Returns a snapshot of the current [`Config`].
+    pub fn load(&self) -> Arc<Config> {
+        self.0.read().unwrap_or_else(PoisonError::into_inner).clone()
+    }
+
+    /// Replaces the current [`Config`].
+    pub fn store(&self, config: Config) {
+        *self.0.write().unwrap_or_else(PoisonError::into_inner) = Arc::new(config);
+    }
+}
+
+impl From<Config> for SharedConfig {
+    fn from(config: Config) -> Self {
+        Self::new(config)
+    }
+}
+
+impl InteropValidator for SharedConfig {
+    fn validate_interop_timestamps(
+        &self,
+        initiating_chain_id: ChainId,
+        initiating_timestamp: u64,
+        executing_chain_id: ChainId,
+        executing_timestamp: u64,
+        timeout: Option<u64>,
+    ) -> Result<(), InteropValidationError> {
+        self.load().validate_interop_timestamps(
+            initiating_chain_id,
+            initiating_timestamp,
+            executing_chain_id,
+            executing_timestamp,
+            timeout,
+        )
+    }
+
+    fn is_post_interop(&self, chain_id: ChainId, timestamp: u64) -> bool {
+        self.load().is_post_interop(chain_id, timestamp)
+    }
+
+    fn is_interop_activation_block(&self, chain_id: ChainId, block: BlockInfo) -> bool {
+        self.load().is_interop_activation_block(chain_id, block)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{RollupConfig, RollupConfigSet};
+    use kona_interop::{ChainDependency, DependencySet};
+    use std::{collections::HashMap, net::SocketAddr, path::PathBuf};
+
+    fn config_with_chains(chain_ids: &[ChainId]) -> Config {
+        let rollup =
+            RollupConfig { genesis: Default::default(), block_time: 2, interop_time: Some(0) };
+        Config {
+            l1_rpc: Default::default(),
+            l2_consensus_nodes_config: vec![],
+            datadir: PathBuf::new(),
+            rpc_addr: SocketAddr::from(([127, 0, 0, 1], 8545)),
+            enable_admin_api: false,
+            dependency_set: DependencySet {
+                dependencies: chain_ids
+                    .iter()
+                    .map(|id| (*id, ChainDependency::default()))
+                    .collect(),
+                override_message_expiry_window: None,
+            },
+            rollup_config_set: RollupConfigSet {
+                rollups: chain_ids
+                    .iter()
+                    .map(|id| (*id, rollup.clone()))
+                    .collect::<HashMap<_, _>>(),
+            },
+        }
+    }
+
+    #[test]
+    fn test_store_is_visible_to_all_clones() {
+        let shared = SharedConfig::new(config_with_chains(&[1]));
+        let other = shared.clone();
+        assert_eq!(
+            other.validate_interop_timestamps(1, 10, 2, 10, None),
+            Err(InteropValidationError::InteropNotEnabled)
+        );
+
+        shared.store(config_with_chains(&[1, 2]));
+
+        assert!(other.load().dependency_set.has_chain(2));
+        assert_eq!(other.validate_interop_timestamps(1, 10, 2, 10, None), Ok(()));
+    }
+}
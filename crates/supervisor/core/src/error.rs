@@ -127,9 +127,11 @@ impl From<SupervisorError> for ErrorObjectOwned {
             SupervisorError::ManagedNodeMissing(_) |
             SupervisorError::ManagedNodeError(_) |
             SupervisorError::StorageError(_) |
-            SupervisorError::AccessListError(_) |
-            SupervisorError::ChainIdParseError() |
             SupervisorError::SerdeJson(_) => ErrorObjectOwned::from(ErrorCode::InternalError),
+            // malformed access lists are rejected as invalid requests
+            SupervisorError::AccessListError(_) | SupervisorError::ChainIdParseError() => {
+                ErrorObjectOwned::from(ErrorCode::InvalidParams)
+            }
             SupervisorError::SpecError(err) => err.into(),
         }
     }
@@ -204,5 +206,8 @@ mod test {
         let expected_err = SpecError::SuperchainDAError(SuperchainDAError::FutureData);
 
         assert_eq!(supervisor_err, expected_err.into());
+
+        let supervisor_err = ErrorObjectOwned::from(SupervisorError::ChainIdParseError());
+        assert_eq!(supervisor_err, ErrorObjectOwned::from(ErrorCode::InvalidParams));
     }
 }
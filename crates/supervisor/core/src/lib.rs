@@ -26,4 +26,4 @@ pub mod safety_checker;
 pub use safety_checker::{CrossSafetyCheckerJob, CrossSafetyError};
 
 mod reorg;
-pub use reorg::{ReorgHandler, ReorgHandlerError};
+pub use reorg::{ReorgHandler, ReorgHandlerError, RewindCause, RewindDecision, RewindJournal};
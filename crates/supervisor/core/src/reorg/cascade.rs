@@ -0,0 +1,300 @@
+//! Cross-chain rewind cascades.
+//!
+//! When the storage of a chain is rewound below its cross-safe head, the cross-safe blocks of the
+//! other chains may execute messages initiated in the removed blocks. These blocks are no longer
+//! valid, so the other chains must be rewound too, which may in turn invalidate the cross-safe
+//! blocks of further chains.
+
+use alloy_primitives::ChainId;
+use kona_interop::SafetyLevel;
+use kona_protocol::BlockInfo;
+use kona_supervisor_storage::{DbReader, StorageError};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, HashMap, VecDeque},
+    sync::Arc,
+};
+use tracing::info;
+
+/// The reason for a rewind of the storage of a chain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case", rename_all_fields = "camelCase")]
+pub enum RewindCause {
+    /// The L1 blocks that the removed blocks were derived from were reorged out.
+    L1Reorg,
+    /// The first removed block executes a message initiated in a removed block of another chain.
+    Dependency {
+        /// The chain the message was initiated on.
+        chain_id: ChainId,
+        /// The number of the block the message was initiated in.
+        block_number: u64,
+    },
+}
+
+/// A rewind of the storage of a chain, decided by the supervisor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RewindDecision {
+    /// The chain that is rewound.
+    pub chain_id: ChainId,
+    /// The first block removed from the storage of the chain. Rewinds are inclusive.
+    pub first_removed: BlockInfo,
+    /// The reason for the rewind.
+    pub cause: RewindCause,
+}
+
+/// Computes the dependency closure of the blocks removed from the storage of a set of chains.
+#[derive(Debug)]
+pub(crate) struct RewindCascade<'a, DB> {
+    chain_dbs: &'a HashMap<ChainId, Arc<DB>>,
+}
+
+impl<'a, DB> RewindCascade<'a, DB>
+where
+    DB: DbReader,
+{
+    /// Creates a new [`RewindCascade`] over the storage of the given chains.
+    pub(crate) const fn new(chain_dbs: &'a HashMap<ChainId, Arc<DB>>) -> Self {
+        Self { chain_dbs }
+    }
+
+    /// Returns the rewinds of the chains whose cross-safe blocks depend, transitively, on the
+    /// given removed blocks. At most one rewind is returned per chain, at its earliest dependent
+    /// block, and chains are never rewound to a later block than one of the given removals.
+    pub(crate) fn plan(
+        &self,
+        removed: &[RewindDecision],
+    ) -> Result<Vec<RewindDecision>, StorageError> {
+        let mut earliest = HashMap::<ChainId, u64>::new();
+        for removal in removed {
+            earliest
+                .entry(removal.chain_id)
+                .and_modify(|number| *number = (*number).min(removal.first_removed.number))
+                .or_insert(removal.first_removed.number);
+        }
+
+        let mut queue = removed.iter().copied().collect::<VecDeque<_>>();
+        let mut decisions = BTreeMap::<ChainId, RewindDecision>::new();
+        while let Some(removal) = queue.pop_front() {
+            for (&chain_id, db) in self.chain_dbs {
+                if chain_id == removal.chain_id {
+                    continue;
+                }
+                let Some((dependent, block_number)) = Self::first_dependent(db, &removal)? else {
+                    continue;
+                };
+                if earliest.get(&chain_id).is_some_and(|number| *number <= dependent.number) {
+                    continue;
+                }
+
+                info!(
+                    target: "supervisor::reorg_handler",
+                    chain_id,
+                    dependent = dependent.number,
+                    initiating_chain_id = removal.chain_id,
+                    initiating_block = block_number,
+                    "Cross-safe block depends on a rewound block"
+                );
+                earliest.insert(chain_id, dependent.number);
+                let decision = RewindDecision {
+                    chain_id,
+                    first_removed: dependent,
+                    cause: RewindCause::Dependency { chain_id: removal.chain_id, block_number },
+                };
+                decisions.insert(chain_id, decision);
+                queue.push_back(decision);
+            }
+        }
+
+        Ok(decisions.into_values().collect())
+    }
+
+    /// Returns the first cross-safe block of the chain that executes a message initiated in one
+    /// of the removed blocks, with the number of the block that initiated the message.
+    fn first_dependent(
+        db: &DB,
+        removal: &RewindDecision,
+    ) -> Result<Option<(BlockInfo, u64)>, StorageError> {
+        let cross_safe = match db.get_safety_head_ref(SafetyLevel::CrossSafe) {
+            Ok(cross_safe) => cross_safe,
+            Err(StorageError::FutureData | StorageError::DatabaseNotInitialised) => {
+                return Ok(None);
+            }
+            Err(err) => return Err(err),
+        };
+
+        // Messages can only be executed at or after the timestamp of the block that initiated them.
+        let timestamp = removal.first_removed.timestamp;
+        if cross_safe.timestamp < timestamp {
+            return Ok(None);
+        }
+
+        let start = Self::first_block_at(db, timestamp, cross_safe.number)?;
+        for number in start..=cross_safe.number {
+            let dependency = db.get_logs(number)?.into_iter().find_map(|log| {
+                log.executing_message
+                    .filter(|message| {
+                        message.chain_id == removal.chain_id &&
+                            message.block_number >= removal.first_removed.number
+                    })
+                    .map(|message| message.block_number)
+            });
+            if let Some(block_number) = dependency {
+                return Ok(Some((db.get_block(number)?, block_number)));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Returns the number of the first block of the chain with a timestamp at or after the given
+    /// one, searching up to the given block.
+    fn first_block_at(db: &DB, timestamp: u64, last: u64) -> Result<u64, StorageError> {
+        let (mut low, mut high) = (db.get_activation_block()?.number, last);
+        while low < high {
+            let mid = low + (high - low) / 2;
+            if db.get_block(mid)?.timestamp < timestamp {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+        Ok(low)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_eips::BlockNumHash;
+    use alloy_primitives::B256;
+    use kona_interop::DerivedRefPair;
+    use kona_supervisor_storage::{
+        DerivationStorageReader, HeadRefStorageReader, LogStorageReader,
+    };
+    use kona_supervisor_types::{ExecutingMessage, Log, SuperHead};
+    use mockall::mock;
+
+    mock!(
+        #[derive(Debug)]
+        pub Db {}
+
+        impl LogStorageReader for Db {
+            fn get_block(&self, block_number: u64) -> Result<BlockInfo, StorageError>;
+            fn get_latest_block(&self) -> Result<BlockInfo, StorageError>;
+            fn get_log(&self, block_number: u64,log_index: u32) -> Result<Log, StorageError>;
+            fn get_logs(&self, block_number: u64) -> Result<Vec<Log>, StorageError>;
+        }
+
+        impl DerivationStorageReader for Db {
+            fn derived_to_source(&self, derived_block_id: BlockNumHash) -> Result<BlockInfo, StorageError>;
+            fn latest_derived_block_at_source(&self, source_block_id: BlockNumHash) -> Result<BlockInfo, StorageError>;
+            fn latest_derivation_state(&self) -> Result<DerivedRefPair, StorageError>;
+            fn get_source_block(&self, source_block_number: u64) -> Result<BlockInfo, StorageError>;
+            fn get_activation_block(&self) -> Result<BlockInfo, StorageError>;
+        }
+
+        impl HeadRefStorageReader for Db {
+            fn get_safety_head_ref(&self, safety_level: SafetyLevel) -> Result<BlockInfo, StorageError>;
+            fn get_super_head(&self) -> Result<SuperHead, StorageError>;
+        }
+    );
+
+    /// Returns the block of a chain with a block time of 2 seconds from timestamp 0.
+    fn block(number: u64) -> BlockInfo {
+        BlockInfo::new(B256::with_last_byte(number as u8), number, B256::ZERO, number * 2)
+    }
+
+    /// Returns a mock chain with the given cross-safe head, whose blocks execute the given
+    /// messages, by block number.
+    fn chain(cross_safe: Option<u64>, messages: Vec<(u64, ExecutingMessage)>) -> Arc<MockDb> {
+        let mut db = MockDb::new();
+        db.expect_get_safety_head_ref()
+            .returning(move |_| cross_safe.map(block).ok_or(StorageError::FutureData));
+        db.expect_get_activation_block().returning(|| Ok(block(0)));
+        db.expect_get_block().returning(|number| Ok(block(number)));
+        db.expect_get_logs().returning(move |number| {
+            Ok(messages
+                .iter()
+                .filter(|(block_number, _)| *block_number == number)
+                .map(|(_, message)| Log {
+                    index: 0,
+                    hash: B256::ZERO,
+                    executing_message: Some(*message),
+                })
+                .collect())
+        });
+        Arc::new(db)
+    }
+
+    fn message(chain_id: ChainId, block_number: u64) -> ExecutingMessage {
+        ExecutingMessage {
+            chain_id,
+            block_number,
+            log_index: 0,
+            timestamp: block_number * 2,
+            hash: B256::ZERO,
+        }
+    }
+
+    fn l1_reorg(chain_id: ChainId, number: u64) -> RewindDecision {
+        RewindDecision { chain_id, first_removed: block(number), cause: RewindCause::L1Reorg }
+    }
+
+    #[test]
+    fn test_plan_cascade() {
+        // Chain 2 executes a message of block 10 of chain 1 at block 12, and chain 3 executes a
+        // message of block 12 of chain 2 at block 15. Chain 4 only depends on kept blocks.
+        let chain_dbs = HashMap::from([
+            (1, chain(Some(20), vec![])),
+            (2, chain(Some(20), vec![(12, message(1, 10)), (14, message(1, 11))])),
+            (3, chain(Some(20), vec![(15, message(2, 12))])),
+            (4, chain(Some(20), vec![(16, message(1, 9))])),
+        ]);
+
+        let decisions = RewindCascade::new(&chain_dbs).plan(&[l1_reorg(1, 10)]).unwrap();
+        assert_eq!(
+            decisions,
+            vec![
+                RewindDecision {
+                    chain_id: 2,
+                    first_removed: block(12),
+                    cause: RewindCause::Dependency { chain_id: 1, block_number: 10 },
+                },
+                RewindDecision {
+                    chain_id: 3,
+                    first_removed: block(15),
+                    cause: RewindCause::Dependency { chain_id: 2, block_number: 12 },
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_plan_cascade_ignores_later_dependents() {
+        // Chain 2 is already rewound below its dependent block, and the dependent block of chain 3
+        // is above its cross-safe head.
+        let chain_dbs = HashMap::from([
+            (1, chain(Some(20), vec![])),
+            (2, chain(Some(20), vec![(12, message(1, 10))])),
+            (3, chain(Some(11), vec![(12, message(1, 10))])),
+            (4, chain(None, vec![(12, message(1, 10))])),
+        ]);
+
+        let decisions =
+            RewindCascade::new(&chain_dbs).plan(&[l1_reorg(1, 10), l1_reorg(2, 8)]).unwrap();
+        assert!(decisions.is_empty());
+    }
+
+    #[test]
+    fn test_rewind_decision_serde() {
+        let decision = RewindDecision {
+            chain_id: 2,
+            first_removed: block(12),
+            cause: RewindCause::Dependency { chain_id: 1, block_number: 10 },
+        };
+        let json = serde_json::to_value(decision).unwrap();
+        assert_eq!(json["chainId"], 2);
+        assert_eq!(json["cause"]["type"], "dependency");
+        assert_eq!(serde_json::from_value::<RewindDecision>(json).unwrap(), decision);
+    }
+}
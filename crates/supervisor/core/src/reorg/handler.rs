@@ -1,28 +1,54 @@
-use super::metrics::Metrics;
-use crate::{ReorgHandlerError, reorg::task::ReorgTask};
+use super::{RewindCause, RewindDecision, RewindJournal, cascade::RewindCascade, metrics::Metrics};
+use crate::{ReorgHandlerError, reorg::task::ReorgTask, syncnode::ManagedNodeCommand};
 use alloy_primitives::ChainId;
 use alloy_rpc_client::RpcClient;
-use derive_more::Constructor;
 use futures::future;
+use kona_interop::SafetyLevel;
 use kona_protocol::BlockInfo;
 use kona_supervisor_metrics::observe_metrics_for_result_async;
 use kona_supervisor_storage::{DbReader, StorageRewinder};
 use std::{collections::HashMap, sync::Arc};
-use tracing::{error, info, trace};
+use tokio::sync::mpsc;
+use tracing::{error, info, trace, warn};
 
 /// Handles L1 reorg operations for multiple chains
-#[derive(Debug, Constructor)]
+#[derive(Debug)]
 pub struct ReorgHandler<DB> {
     /// The Alloy RPC client for L1.
     rpc_client: RpcClient,
     /// Per chain dbs.
     chain_dbs: HashMap<ChainId, Arc<DB>>,
+    /// Per chain senders of commands to the managed nodes, reset after a cross-chain rewind.
+    managed_nodes: HashMap<ChainId, mpsc::Sender<ManagedNodeCommand>>,
+    /// The journal the rewinds are recorded to, if any.
+    journal: Option<RewindJournal>,
 }
 
 impl<DB> ReorgHandler<DB>
 where
     DB: DbReader + StorageRewinder + Send + Sync + 'static,
 {
+    /// Creates a new [`ReorgHandler`] for the given chains.
+    pub fn new(rpc_client: RpcClient, chain_dbs: HashMap<ChainId, Arc<DB>>) -> Self {
+        Self { rpc_client, chain_dbs, managed_nodes: HashMap::new(), journal: None }
+    }
+
+    /// Sets the senders of commands to the managed nodes, which are reset when their chain is
+    /// rewound because of a reorg on another chain.
+    pub fn with_managed_node_senders(
+        mut self,
+        managed_nodes: HashMap<ChainId, mpsc::Sender<ManagedNodeCommand>>,
+    ) -> Self {
+        self.managed_nodes = managed_nodes;
+        self
+    }
+
+    /// Sets the journal the rewinds are recorded to.
+    pub fn with_rewind_journal(mut self, journal: RewindJournal) -> Self {
+        self.journal = Some(journal);
+        self
+    }
+
     /// Initializes the metrics for the reorg handler
     pub fn with_metrics(self) -> Self {
         // Initialize metrics for all chains
@@ -55,7 +81,19 @@ where
     }
 
     /// Verifies the consistency of each chain with the L1 chain and handles any reorgs, if any.
+    ///
+    /// Chains rewound below their cross-safe head may invalidate the cross-safe blocks of other
+    /// chains, which are rewound in turn. See [`Self::cascade_rewinds`].
     async fn verify_and_handle_chain_reorg(&self) -> Result<(), ReorgHandlerError> {
+        // Snapshot the cross-safe heads before the rewinds reset them.
+        let cross_safe_heads = self
+            .chain_dbs
+            .iter()
+            .filter_map(|(chain_id, db)| {
+                db.get_safety_head_ref(SafetyLevel::CrossSafe).ok().map(|head| (*chain_id, head))
+            })
+            .collect::<HashMap<_, _>>();
+
         let mut handles = Vec::with_capacity(self.chain_dbs.len());
 
         for (chain_id, chain_db) in &self.chain_dbs {
@@ -76,16 +114,207 @@ where
                     "chain_id" => chain_id.to_string()
                 )
             });
-            handles.push(handle);
+            handles.push((chain_id, handle));
         }
 
+        let (chain_ids, handles): (Vec<_>, Vec<_>) = handles.into_iter().unzip();
         let results = future::join_all(handles).await;
-        for result in results {
-            if let Err(err) = result {
-                error!(target: "supervisor::reorg_handler", %err, "Reorg task failed");
+
+        let mut removed = Vec::new();
+        for (chain_id, result) in chain_ids.into_iter().zip(results) {
+            match result {
+                Ok(Ok(Some(first_removed))) => {
+                    let decision =
+                        RewindDecision { chain_id, first_removed, cause: RewindCause::L1Reorg };
+                    self.record(&decision);
+
+                    // Only rewinds of cross-safe blocks can invalidate blocks of other chains.
+                    if cross_safe_heads
+                        .get(&chain_id)
+                        .is_some_and(|head| first_removed.number <= head.number)
+                    {
+                        removed.push(decision);
+                    }
+                }
+                Ok(_) => {}
+                Err(err) => {
+                    error!(target: "supervisor::reorg_handler", %err, "Reorg task failed");
+                }
+            }
+        }
+
+        if !removed.is_empty() {
+            self.cascade_rewinds(&removed).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Rewinds the chains whose cross-safe blocks depend on the removed blocks of other chains,
+    /// and resets their managed nodes.
+    ///
+    /// A failed rewind is logged, and does not prevent the rewinds of the other chains.
+    async fn cascade_rewinds(&self, removed: &[RewindDecision]) -> Result<(), ReorgHandlerError> {
+        let decisions = RewindCascade::new(&self.chain_dbs).plan(removed)?;
+
+        for decision in decisions {
+            let Some(db) = self.chain_dbs.get(&decision.chain_id) else {
+                continue;
+            };
+
+            info!(
+                target: "supervisor::reorg_handler",
+                chain_id = decision.chain_id,
+                first_removed = %decision.first_removed,
+                cause = ?decision.cause,
+                "Rewinding chain depending on rewound blocks"
+            );
+            if let Err(err) = db.rewind(&decision.first_removed.id()) {
+                error!(
+                    target: "supervisor::reorg_handler",
+                    chain_id = decision.chain_id,
+                    %err,
+                    "Failed to rewind chain depending on rewound blocks"
+                );
+                continue;
+            }
+            self.record(&decision);
+
+            let Some(sender) = self.managed_nodes.get(&decision.chain_id) else {
+                warn!(
+                    target: "supervisor::reorg_handler",
+                    chain_id = decision.chain_id,
+                    "No managed node to reset after rewind"
+                );
+                continue;
+            };
+            if let Err(err) = sender.send(ManagedNodeCommand::Reset {}).await {
+                error!(
+                    target: "supervisor::reorg_handler",
+                    chain_id = decision.chain_id,
+                    %err,
+                    "Failed to reset managed node after rewind"
+                );
             }
         }
 
         Ok(())
     }
+
+    /// Records the rewind to the journal, if any.
+    fn record(&self, decision: &RewindDecision) {
+        let Some(journal) = &self.journal else {
+            return;
+        };
+        if let Err(err) = journal.record(decision) {
+            error!(
+                target: "supervisor::reorg_handler",
+                chain_id = decision.chain_id,
+                %err,
+                "Failed to record rewind"
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_eips::BlockNumHash;
+    use alloy_primitives::B256;
+    use alloy_transport::mock::{Asserter, MockTransport};
+    use kona_interop::DerivedRefPair;
+    use kona_supervisor_storage::{
+        DerivationStorageReader, HeadRefStorageReader, LogStorageReader, StorageError,
+    };
+    use kona_supervisor_types::{ExecutingMessage, Log, SuperHead};
+    use mockall::mock;
+
+    mock!(
+        #[derive(Debug)]
+        pub Db {}
+
+        impl LogStorageReader for Db {
+            fn get_block(&self, block_number: u64) -> Result<BlockInfo, StorageError>;
+            fn get_latest_block(&self) -> Result<BlockInfo, StorageError>;
+            fn get_log(&self, block_number: u64,log_index: u32) -> Result<Log, StorageError>;
+            fn get_logs(&self, block_number: u64) -> Result<Vec<Log>, StorageError>;
+        }
+
+        impl DerivationStorageReader for Db {
+            fn derived_to_source(&self, derived_block_id: BlockNumHash) -> Result<BlockInfo, StorageError>;
+            fn latest_derived_block_at_source(&self, source_block_id: BlockNumHash) -> Result<BlockInfo, StorageError>;
+            fn latest_derivation_state(&self) -> Result<DerivedRefPair, StorageError>;
+            fn get_source_block(&self, source_block_number: u64) -> Result<BlockInfo, StorageError>;
+            fn get_activation_block(&self) -> Result<BlockInfo, StorageError>;
+        }
+
+        impl HeadRefStorageReader for Db {
+            fn get_safety_head_ref(&self, safety_level: SafetyLevel) -> Result<BlockInfo, StorageError>;
+            fn get_super_head(&self) -> Result<SuperHead, StorageError>;
+        }
+
+        impl StorageRewinder for Db {
+            fn rewind(&self, to: &BlockNumHash) -> Result<(), StorageError>;
+            fn rewind_log_storage(&self, to: &BlockNumHash) -> Result<(), StorageError>;
+            fn rewind_to_source(&self, to: &BlockNumHash) -> Result<Option<BlockInfo>, StorageError>;
+        }
+    );
+
+    /// Returns the block of a chain with a block time of 2 seconds from timestamp 0.
+    fn block(number: u64) -> BlockInfo {
+        BlockInfo::new(B256::with_last_byte(number as u8), number, B256::ZERO, number * 2)
+    }
+
+    /// Returns a mock chain with a cross-safe head at block 20, whose block 12 executes a message
+    /// of block 10 of chain 1, and whose rewind fails if `fail_rewind` is set.
+    fn dependent_chain(fail_rewind: bool) -> MockDb {
+        let mut db = MockDb::new();
+        db.expect_get_safety_head_ref().returning(|_| Ok(block(20)));
+        db.expect_get_activation_block().returning(|| Ok(block(0)));
+        db.expect_get_block().returning(|number| Ok(block(number)));
+        db.expect_get_logs().returning(|number| {
+            let executing_message = (number == 12).then_some(ExecutingMessage {
+                chain_id: 1,
+                block_number: 10,
+                log_index: 0,
+                timestamp: 20,
+                hash: B256::ZERO,
+            });
+            Ok(vec![Log { index: 0, hash: B256::ZERO, executing_message }])
+        });
+        db.expect_rewind().times(1).returning(move |to| {
+            assert_eq!(*to, block(12).id());
+            if fail_rewind { Err(StorageError::DatabaseNotInitialised) } else { Ok(()) }
+        });
+        db
+    }
+
+    #[tokio::test]
+    async fn test_cascade_rewinds_continues_after_failed_rewind() {
+        let mut initiating = MockDb::new();
+        initiating.expect_get_safety_head_ref().returning(|_| Ok(block(20)));
+        initiating.expect_get_activation_block().returning(|| Ok(block(0)));
+        initiating.expect_get_block().returning(|number| Ok(block(number)));
+        initiating.expect_get_logs().returning(|_| Ok(vec![]));
+
+        let chain_dbs = HashMap::from([
+            (1, Arc::new(initiating)),
+            (2, Arc::new(dependent_chain(true))),
+            (3, Arc::new(dependent_chain(false))),
+        ]);
+        let (tx_2, mut rx_2) = mpsc::channel(1);
+        let (tx_3, mut rx_3) = mpsc::channel(1);
+        let rpc_client = RpcClient::new(MockTransport::new(Asserter::new()), false);
+        let handler = ReorgHandler::new(rpc_client, chain_dbs)
+            .with_managed_node_senders(HashMap::from([(2, tx_2), (3, tx_3)]));
+
+        let removed =
+            RewindDecision { chain_id: 1, first_removed: block(10), cause: RewindCause::L1Reorg };
+        handler.cascade_rewinds(&[removed]).await.unwrap();
+
+        // The chain that failed to rewind keeps its managed node, the other one is reset.
+        assert!(rx_2.try_recv().is_err());
+        assert!(matches!(rx_3.try_recv(), Ok(ManagedNodeCommand::Reset {})));
+    }
 }
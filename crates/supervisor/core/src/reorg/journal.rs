@@ -0,0 +1,83 @@
+use super::RewindDecision;
+use serde::Serialize;
+use std::{
+    fs::OpenOptions,
+    io::{self, Write},
+    path::PathBuf,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// An append-only journal of the rewinds decided by the supervisor, kept for audit.
+///
+/// Each rewind is recorded as a JSON object on its own line, with the unix timestamp at which it
+/// was decided.
+#[derive(Debug, Clone)]
+pub struct RewindJournal {
+    path: PathBuf,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct JournalEntry<'a> {
+    recorded_at: u64,
+    #[serde(flatten)]
+    decision: &'a RewindDecision,
+}
+
+impl RewindJournal {
+    /// The name of the journal file in the data directory of the supervisor.
+    pub const FILE_NAME: &'static str = "rewinds.jsonl";
+
+    /// Creates a new [`RewindJournal`] appending to the file at the given path.
+    pub const fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    /// Appends the given rewind to the journal.
+    pub fn record(&self, decision: &RewindDecision) -> io::Result<()> {
+        let recorded_at =
+            SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or_default();
+        let mut line = serde_json::to_vec(&JournalEntry { recorded_at, decision })?;
+        line.push(b'\n');
+
+        let mut file = OpenOptions::new().create(true).append(true).open(&self.path)?;
+        file.write_all(&line)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reorg::RewindCause;
+    use kona_protocol::BlockInfo;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_record_rewinds() {
+        let dir = TempDir::new().unwrap();
+        let journal = RewindJournal::new(dir.path().join(RewindJournal::FILE_NAME));
+
+        let first_removed = BlockInfo { number: 12, ..Default::default() };
+        journal
+            .record(&RewindDecision { chain_id: 1, first_removed, cause: RewindCause::L1Reorg })
+            .unwrap();
+        journal
+            .record(&RewindDecision {
+                chain_id: 2,
+                first_removed,
+                cause: RewindCause::Dependency { chain_id: 1, block_number: 12 },
+            })
+            .unwrap();
+
+        let contents = std::fs::read_to_string(dir.path().join(RewindJournal::FILE_NAME)).unwrap();
+        let entries = contents
+            .lines()
+            .map(|line| serde_json::from_str::<serde_json::Value>(line).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0]["chainId"], 1);
+        assert_eq!(entries[0]["cause"]["type"], "l1_reorg");
+        assert_eq!(entries[1]["cause"]["blockNumber"], 12);
+        assert!(entries[1]["recordedAt"].as_u64().unwrap() > 0);
+    }
+}
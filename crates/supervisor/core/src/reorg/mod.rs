@@ -7,3 +7,9 @@ mod error;
 pub use error::ReorgHandlerError;
 
 mod metrics;
+
+mod cascade;
+pub use cascade::{RewindCause, RewindDecision};
+
+mod journal;
+pub use journal::RewindJournal;
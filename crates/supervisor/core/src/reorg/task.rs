@@ -31,7 +31,9 @@ where
 {
     /// Processes reorg for a single chain. If the chain is consistent with the L1 chain,
     /// does nothing.
-    pub(crate) async fn process_chain_reorg(&self) -> Result<(), ReorgHandlerError> {
+    ///
+    /// Returns the first derived block removed from the storage of the chain, if any.
+    pub(crate) async fn process_chain_reorg(&self) -> Result<Option<BlockInfo>, ReorgHandlerError> {
         trace!(
             target: "supervisor::reorg_handler",
             chain_id = %self.chain_id,
@@ -47,7 +49,7 @@ where
             }
             Ok(None) => {
                 // No reorg needed, latest source block is still canonical
-                return Ok(());
+                return Ok(None);
             }
             Err(ReorgHandlerError::RewindTargetPreInterop) => {
                 self.rewind_to_activation_block().await?
@@ -58,6 +60,7 @@ where
         };
 
         // record metrics
+        let mut first_removed = None;
         if let Some(rewound_state) = rewound_state {
             let l1_depth = latest_state.source.number - rewound_state.source.number;
             let mut l2_depth = 0;
@@ -65,13 +68,14 @@ where
                 l2_depth = latest_state.derived.number - derived.number;
             }
             Metrics::record_block_depth(self.chain_id, l1_depth, l2_depth);
+            first_removed = rewound_state.derived;
         }
         info!(
             target: "supervisor::reorg_handler",
             chain_id = %self.chain_id,
             "Processed reorged successfully"
         );
-        Ok(())
+        Ok(first_removed)
     }
 
     async fn rewind_to_target_source(
@@ -360,7 +364,7 @@ mod tests {
 
         let result = reorg_task.process_chain_reorg().await;
 
-        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), None);
     }
 
     #[tokio::test]
@@ -457,7 +461,7 @@ mod tests {
 
         let result = reorg_task.process_chain_reorg().await;
 
-        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), Some(rewind_target_derived));
     }
 
     #[tokio::test]
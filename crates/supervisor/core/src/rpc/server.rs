@@ -115,7 +115,7 @@ where
                     "Received the dependency set"
                 );
 
-                Ok(self.supervisor.dependency_set().to_owned())
+                Ok(self.supervisor.dependency_set())
             }
             .await
         )
@@ -346,7 +346,7 @@ mod tests {
         #[async_trait]
         impl SupervisorService for SupervisorService {
             fn chain_ids(&self) -> impl Iterator<Item = ChainId>;
-            fn dependency_set(&self) -> &DependencySet;
+            fn dependency_set(&self) -> DependencySet;
             fn super_head(&self, chain: ChainId) -> Result<SuperHead, SupervisorError>;
             fn latest_block_from(&self, l1_block: BlockNumHash, chain: ChainId) -> Result<BlockInfo, SupervisorError>;
             fn derived_to_source_block(&self, chain: ChainId, derived: BlockNumHash) -> Result<BlockInfo, SupervisorError>;
@@ -517,4 +517,125 @@ mod tests {
         assert_eq!(status.finalized_timestamp, 50);
         assert_eq!(status.chains.len(), 2);
     }
+
+    #[tokio::test]
+    async fn test_safety_heads() {
+        let chain_id = ChainId::from(1u64);
+        let block = |number| BlockInfo {
+            number,
+            hash: B256::with_last_byte(number as u8),
+            ..Default::default()
+        };
+
+        let mut mock_service = MockSupervisorService::new();
+        mock_service
+            .expect_local_unsafe()
+            .with(predicate::eq(chain_id))
+            .returning(move |_| Ok(block(30)));
+        mock_service
+            .expect_cross_safe()
+            .with(predicate::eq(chain_id))
+            .returning(move |_| Ok(block(20)));
+        mock_service
+            .expect_finalized()
+            .with(predicate::eq(chain_id))
+            .returning(move |_| Ok(block(10)));
+        mock_service
+            .expect_derived_to_source_block()
+            .with(predicate::eq(chain_id), predicate::eq(block(20).id()))
+            .returning(move |_, _| Ok(block(5)));
+
+        let rpc = SupervisorRpc::new(Arc::new(mock_service));
+        assert_eq!(rpc.local_unsafe(HexStringU64(chain_id)).await.unwrap(), block(30).id());
+        assert_eq!(
+            rpc.cross_safe(HexStringU64(chain_id)).await.unwrap(),
+            DerivedIdPair { source: block(5).id(), derived: block(20).id() }
+        );
+        assert_eq!(rpc.finalized(HexStringU64(chain_id)).await.unwrap(), block(10).id());
+    }
+
+    #[tokio::test]
+    async fn test_safety_head_uninitialized_chain_db() {
+        let mut mock_service = MockSupervisorService::new();
+        mock_service
+            .expect_cross_safe()
+            .returning(|_| Err(SupervisorError::SpecError(SpecError::ErrorNotInSpec)));
+
+        let rpc = SupervisorRpc::new(Arc::new(mock_service));
+        assert_eq!(
+            rpc.cross_safe(HexStringU64(1)).await.unwrap_err(),
+            ErrorObject::from(SupervisorError::SpecError(SpecError::ErrorNotInSpec))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_all_safe_derived_at() {
+        let source = BlockNumHash::new(100, B256::with_last_byte(100));
+
+        let mut mock_service = MockSupervisorService::new();
+        mock_service.expect_chain_ids().returning(|| Box::new(vec![1, 2].into_iter()));
+        mock_service
+            .expect_latest_block_from()
+            .with(predicate::eq(source), predicate::always())
+            .returning(|_, chain_id| {
+                Ok(BlockInfo {
+                    number: chain_id * 10,
+                    hash: B256::with_last_byte(chain_id as u8),
+                    ..Default::default()
+                })
+            });
+
+        let rpc = SupervisorRpc::new(Arc::new(mock_service));
+        let derived = rpc.all_safe_derived_at(source).await.unwrap();
+        assert_eq!(
+            derived,
+            HashMap::from_iter([
+                (1, BlockNumHash::new(10, B256::with_last_byte(1))),
+                (2, BlockNumHash::new(20, B256::with_last_byte(2))),
+            ])
+        );
+
+        // A chain without a block derived at the source fails the whole query.
+        let mut mock_service = MockSupervisorService::new();
+        mock_service.expect_chain_ids().returning(|| Box::new(vec![1, 2].into_iter()));
+        mock_service.expect_latest_block_from().returning(|_, chain_id| {
+            if chain_id == 1 {
+                Ok(BlockInfo::default())
+            } else {
+                Err(SupervisorError::SpecError(SpecError::ErrorNotInSpec))
+            }
+        });
+
+        let rpc = SupervisorRpc::new(Arc::new(mock_service));
+        assert!(rpc.all_safe_derived_at(source).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_super_root_at_timestamp() {
+        let output = SuperRootOutputRpc {
+            cross_safe_derived_from: BlockNumHash::new(100, B256::with_last_byte(100)),
+            timestamp: 1000,
+            super_root: B256::with_last_byte(1),
+            version: 1,
+            chains: vec![],
+        };
+
+        let mut mock_service = MockSupervisorService::new();
+        let expected = output.clone();
+        mock_service
+            .expect_super_root_at_timestamp()
+            .with(predicate::eq(1000))
+            .returning(move |_| Ok(expected.clone()));
+        mock_service
+            .expect_super_root_at_timestamp()
+            .with(predicate::eq(2000))
+            .returning(|_| Err(SupervisorError::ManagedNodeMissing(1)));
+
+        let rpc = SupervisorRpc::new(Arc::new(mock_service));
+        assert_eq!(rpc.super_root_at_timestamp(HexStringU64(1000)).await.unwrap(), output);
+        assert_eq!(
+            rpc.super_root_at_timestamp(HexStringU64(2000)).await.unwrap_err(),
+            ErrorObject::from(SupervisorError::ManagedNodeMissing(1))
+        );
+    }
 }
@@ -4,7 +4,7 @@ use crate::{
 };
 use alloy_primitives::{BlockHash, ChainId};
 use derive_more::Constructor;
-use kona_interop::InteropValidator;
+use kona_interop::{InteropValidator, MessageValidator};
 use kona_protocol::BlockInfo;
 use kona_supervisor_storage::{CrossChainSafetyProvider, StorageError};
 use kona_supervisor_types::ExecutingMessage;
@@ -28,40 +28,52 @@ where
     /// Verifies that all executing messages in the given block are valid based on the validity
     /// checks
     pub fn validate_block(&self, block: BlockInfo) -> Result<(), CrossSafetyError> {
-        self.map_dependent_block(&block, self.chain_id, |message, initiating_block_fetcher| {
-            // Step 1: Validate interop timestamps before any dependency checks
-            self.validator
-                .validate_interop_timestamps(
-                    message.chain_id,  // initiating chain id
-                    message.timestamp, // initiating block timestamp
-                    self.chain_id,     // executing chain id
-                    block.timestamp,   // executing block timestamp
-                    None,
+        self.map_dependent_block(
+            &block,
+            self.chain_id,
+            |idx, message, initiating_block_fetcher| {
+                // Step 1: Validate interop timestamps before any dependency checks
+                self.validator
+                    .validate_interop_timestamps(
+                        message.chain_id,  // initiating chain id
+                        message.timestamp, // initiating block timestamp
+                        self.chain_id,     // executing chain id
+                        block.timestamp,   // executing block timestamp
+                        None,
+                    )
+                    .map_err(ValidationError::InteropValidationError)?;
+
+                // A message initiated within the same block must reference an earlier log.
+                MessageValidator::default()
+                    .validate_intra_block_ordering(
+                        (message.chain_id, message.block_number, message.log_index),
+                        (self.chain_id, block.number, idx),
+                    )
+                    .map_err(ValidationError::InteropValidationError)?;
+
+                // Step 2: Verify message dependency without fetching the initiating block.
+                // This avoids unnecessary I/O and ensures we skip validation when:
+                //  - The current target head of the chain is behind the initiating block (must wait
+                //    for that chain to process further)
+                // Only if the target head is ahead but the initiating block is missing, we return a
+                // validation error.
+                self.verify_message_dependency(&message)?;
+
+                // Step 3: Lazily fetch the initiating block only after dependency checks pass.
+                let initiating_block = initiating_block_fetcher()?;
+
+                // Step 4: Validate message existence and integrity.
+                self.validate_executing_message(initiating_block, &message)?;
+
+                // Step 5: Perform cyclic dependency detection starting from the dependent block.
+                self.check_cyclic_dependency(
+                    &block,
+                    &initiating_block,
+                    message.chain_id,
+                    &mut HashSet::new(),
                 )
-                .map_err(ValidationError::InteropValidationError)?;
-
-            // Step 2: Verify message dependency without fetching the initiating block.
-            // This avoids unnecessary I/O and ensures we skip validation when:
-            //  - The current target head of the chain is behind the initiating block (must wait for
-            //    that chain to process further)
-            // Only if the target head is ahead but the initiating block is missing, we return a
-            // validation error.
-            self.verify_message_dependency(&message)?;
-
-            // Step 3: Lazily fetch the initiating block only after dependency checks pass.
-            let initiating_block = initiating_block_fetcher()?;
-
-            // Step 4: Validate message existence and integrity.
-            self.validate_executing_message(initiating_block, &message)?;
-
-            // Step 5: Perform cyclic dependency detection starting from the dependent block.
-            self.check_cyclic_dependency(
-                &block,
-                &initiating_block,
-                message.chain_id,
-                &mut HashSet::new(),
-            )
-        })?;
+            },
+        )?;
 
         Ok(())
     }
@@ -131,7 +143,7 @@ where
             return Ok(()); // Already at target safety level - cannot form a cycle
         }
 
-        self.map_dependent_block(current, chain_id, |message, origin_block_fetcher| {
+        self.map_dependent_block(current, chain_id, |_, message, origin_block_fetcher| {
             let origin_block = origin_block_fetcher()?;
             self.check_cyclic_dependency(candidate, &origin_block, message.chain_id, visited)
         })
@@ -175,8 +187,9 @@ where
         Ok(())
     }
 
-    /// For each executing log in the block, provide a lazy fetcher for the initiating block.
-    /// The callback decides if/when to fetch the initiating block.
+    /// For each executing log in the block, provide its log index, the executing message and a lazy
+    /// fetcher for the initiating block. The callback decides if/when to fetch the initiating
+    /// block.
     fn map_dependent_block<F>(
         &self,
         exec_block: &BlockInfo,
@@ -185,6 +198,7 @@ where
     ) -> Result<(), CrossSafetyError>
     where
         F: for<'a> FnMut(
+            u32,
             ExecutingMessage,
             &'a dyn Fn() -> Result<BlockInfo, CrossSafetyError>,
         ) -> Result<(), CrossSafetyError>,
@@ -202,7 +216,7 @@ where
                     || provider.get_block(chain, number).map_err(CrossSafetyError::Storage);
 
                 // Pass the message and the reference to the fetcher.
-                f(msg, &fetcher)?;
+                f(log.index, msg, &fetcher)?;
             }
         }
         Ok(())
@@ -382,6 +396,49 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn validate_block_rejects_intra_block_cycle() {
+        let chain_id = 1;
+        let block =
+            BlockInfo { number: 101, hash: b256(101), parent_hash: b256(100), timestamp: 200 };
+
+        // Two executing messages in the same block, each referencing the other.
+        let message = |log_index| ExecutingMessage {
+            chain_id,
+            block_number: 101,
+            log_index,
+            timestamp: 200,
+            hash: b256(999),
+        };
+        let logs = vec![
+            Log { index: 0, hash: b256(1), executing_message: Some(message(1)) },
+            Log { index: 1, hash: b256(2), executing_message: Some(message(0)) },
+        ];
+
+        let mut provider = MockProvider::default();
+        let mut validator = MockValidator::default();
+
+        provider
+            .expect_get_block_logs()
+            .withf(move |cid, num| *cid == chain_id && *num == 101)
+            .returning(move |_, _| Ok(logs.clone()));
+
+        validator.expect_validate_interop_timestamps().returning(move |_, _, _, _, _| Ok(()));
+
+        let checker =
+            CrossSafetyChecker::new(chain_id, &validator, &provider, SafetyLevel::CrossSafe);
+        let result = checker.validate_block(block);
+        assert!(matches!(
+            result,
+            Err(CrossSafetyError::ValidationError(ValidationError::InteropValidationError(
+                InteropValidationError::IntraBlockCycle {
+                    initiating_log_index: 1,
+                    executing_log_index: 0
+                }
+            )))
+        ));
+    }
+
     #[test]
     fn validate_executing_message_timestamp_violation() {
         let chain_id = 1;
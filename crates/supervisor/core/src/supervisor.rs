@@ -1,12 +1,12 @@
 use alloy_eips::BlockNumHash;
-use alloy_primitives::{B256, Bytes, ChainId, keccak256};
+use alloy_primitives::{B256, Bytes, ChainId};
 use async_trait::async_trait;
 use core::fmt::Debug;
 use kona_interop::{
     DependencySet, ExecutingDescriptor, InteropValidator, OutputRootWithChain, SUPER_ROOT_VERSION,
     SafetyLevel, SuperRoot,
 };
-use kona_protocol::BlockInfo;
+use kona_protocol::{BlockInfo, OutputRoot};
 use kona_supervisor_rpc::{ChainRootInfoRpc, SuperRootOutputRpc};
 use kona_supervisor_storage::{
     ChainDb, ChainDbFactory, DerivationStorageReader, FinalizedL1Storage, HeadRefStorageReader,
@@ -20,7 +20,7 @@ use tracing::{error, warn};
 
 use crate::{
     SpecError, SupervisorError,
-    config::Config,
+    config::SharedConfig,
     syncnode::{BlockProvider, ManagedNodeDataProvider},
 };
 
@@ -34,7 +34,7 @@ pub trait SupervisorService: Debug + Send + Sync {
     /// Returns mapping of supervised [`ChainId`]s to their [`ChainDependency`] config.
     ///
     /// [`ChainDependency`]: kona_interop::ChainDependency
-    fn dependency_set(&self) -> &DependencySet;
+    fn dependency_set(&self) -> DependencySet;
 
     /// Returns [`SuperHead`] of given supervised chain.
     fn super_head(&self, chain: ChainId) -> Result<SuperHead, SupervisorError>;
@@ -98,7 +98,7 @@ pub trait SupervisorService: Debug + Send + Sync {
 /// The core Supervisor component responsible for monitoring and coordinating chain states.
 #[derive(Debug)]
 pub struct Supervisor<M> {
-    config: Arc<Config>,
+    config: SharedConfig,
     database_factory: Arc<ChainDbFactory>,
 
     // As of now supervisor only supports a single managed node per chain.
@@ -112,7 +112,7 @@ where
 {
     /// Creates a new [`Supervisor`] instance.
     #[allow(clippy::new_without_default, clippy::missing_const_for_fn)]
-    pub fn new(config: Arc<Config>, database_factory: Arc<ChainDbFactory>) -> Self {
+    pub fn new(config: SharedConfig, database_factory: Arc<ChainDbFactory>) -> Self {
         Self { config, database_factory, managed_nodes: RwLock::new(HashMap::new()) }
     }
 
@@ -123,7 +123,7 @@ where
         managed_node: Arc<M>,
     ) -> Result<(), SupervisorError> {
         // todo: instead of passing the chain ID, we should get it from the managed node
-        if !self.config.dependency_set.dependencies.contains_key(&chain_id) {
+        if !self.config.load().dependency_set.has_chain(chain_id) {
             warn!(target: "supervisor::service", %chain_id, "Unsupported chain ID");
             return Err(SupervisorError::UnsupportedChainId);
         }
@@ -167,11 +167,11 @@ where
     M: ManagedNodeDataProvider + BlockProvider + Send + Sync + Debug,
 {
     fn chain_ids(&self) -> impl Iterator<Item = ChainId> {
-        self.config.dependency_set.dependencies.keys().copied()
+        self.config.load().dependency_set.chain_ids().into_iter()
     }
 
-    fn dependency_set(&self) -> &DependencySet {
-        &self.config.dependency_set
+    fn dependency_set(&self) -> DependencySet {
+        self.config.load().dependency_set.clone()
     }
 
     fn super_head(&self, chain: ChainId) -> Result<SuperHead, SupervisorError> {
@@ -246,15 +246,14 @@ where
         &self,
         timestamp: u64,
     ) -> Result<SuperRootOutputRpc, SupervisorError> {
-        let mut chain_ids = self.config.dependency_set.dependencies.keys().collect::<Vec<_>>();
-        // Sorting chain ids for deterministic super root hash
-        chain_ids.sort();
+        // Chain ids are sorted for a deterministic super root hash
+        let chain_ids = self.config.load().dependency_set.chain_ids();
 
         let mut chain_infos = Vec::<ChainRootInfoRpc>::with_capacity(chain_ids.len());
         let mut super_root_chains = Vec::<OutputRootWithChain>::with_capacity(chain_ids.len());
         let mut cross_safe_source = BlockNumHash::default();
 
-        for id in chain_ids {
+        for id in &chain_ids {
             let managed_node = {
                 let guard = self.managed_nodes.read().await;
                 match guard.get(id) {
@@ -266,19 +265,23 @@ where
                 }
             };
             let output_v0 = managed_node.output_v0_at_timestamp(timestamp).await?;
-            let output_v0_string = serde_json::to_string(&output_v0)
-                .inspect_err(|err| {
-                    error!(target: "supervisor::service", chain_id = %id, %err, "Failed to serialize output_v0 for chain");
-                })?;
-            let canonical_root = keccak256(output_v0_string.as_bytes());
-
+            let canonical_root = OutputRoot::from_parts(
+                output_v0.state_root,
+                output_v0.message_passer_storage_root,
+                output_v0.block_hash,
+            )
+            .hash();
+
+            // The pending output is committed to as the encoded output root preimage.
             let pending_output_v0 = managed_node.pending_output_v0_at_timestamp(timestamp).await?;
-            let pending_output_v0_string = serde_json::to_string(&pending_output_v0)
-                .inspect_err(|err| {
-                    error!(target: "supervisor::service", chain_id = %id, %err, "Failed to serialize pending_output_v0 for chain");
-                })?;
-            let pending_output_v0_bytes =
-                Bytes::copy_from_slice(pending_output_v0_string.as_bytes());
+            let pending_output_v0_bytes = Bytes::copy_from_slice(
+                &OutputRoot::from_parts(
+                    pending_output_v0.state_root,
+                    pending_output_v0.message_passer_storage_root,
+                    pending_output_v0.block_hash,
+                )
+                .encode(),
+            );
 
             chain_infos.push(ChainRootInfoRpc {
                 chain_id: *id,
@@ -286,8 +289,7 @@ where
                 pending: pending_output_v0_bytes,
             });
 
-            super_root_chains
-                .push(OutputRootWithChain { chain_id: *id, output_root: canonical_root });
+            super_root_chains.push(OutputRootWithChain::new(*id, canonical_root));
 
             let l2_block = managed_node.l2_block_ref_by_timestamp(timestamp).await?;
             let source = self
@@ -301,7 +303,7 @@ where
             }
         }
 
-        let super_root = SuperRoot { timestamp, output_roots: super_root_chains };
+        let super_root = SuperRoot::new(timestamp, super_root_chains);
         let super_root_hash = super_root.hash();
 
         Ok(SuperRootOutputRpc {
@@ -327,7 +329,12 @@ where
 
             // TODO: support 32 bytes chain id and convert to u64 via dependency set to be usable
             // across services
-            let initiating_chain_id = access.chain_id[24..32]
+            let (chain_id_high, chain_id_low) = access.chain_id.split_at(24);
+            if chain_id_high.iter().any(|byte| *byte != 0) {
+                warn!(target: "supervisor::service", "Initiating chain id in access list exceeds 64 bits");
+                return Err(SupervisorError::ChainIdParseError());
+            }
+            let initiating_chain_id = chain_id_low
                 .try_into()
                 .map(u64::from_be_bytes)
                 .map_err(|err| {
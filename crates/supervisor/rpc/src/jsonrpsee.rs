@@ -128,6 +128,9 @@ pub trait SupervisorApi {
 #[cfg_attr(feature = "client", rpc(server, client, namespace = "admin"))]
 pub trait SupervisorAdminApi {
     /// Adds L2RPC to the supervisor.
+    ///
+    /// If the chain of the node is not part of the running dependency set, the supervisor reloads
+    /// the dependency set and joins the chain when it has been added there.
     #[method(name = "addL2RPC")]
     async fn add_l2_rpc(&self, url: String, jwt_secret: String) -> RpcResult<()>;
 }
@@ -101,7 +101,7 @@ mod tests {
         #[async_trait]
         impl SupervisorService for SupervisorService {
             fn chain_ids(&self) -> impl Iterator<Item = ChainId>;
-            fn dependency_set(&self) -> &DependencySet;
+            fn dependency_set(&self) -> DependencySet;
             fn super_head(&self, chain: ChainId) -> Result<SuperHead, SupervisorError>;
             fn latest_block_from(&self, l1_block: BlockNumHash, chain: ChainId) -> Result<BlockInfo, SupervisorError>;
             fn derived_to_source_block(&self, chain: ChainId, derived: BlockNumHash) -> Result<BlockInfo, SupervisorError>;
@@ -3,7 +3,7 @@
 
 mod service;
 
-pub use service::Service;
+pub use service::{ConfigLoader, Service};
 
 mod actors;
 pub use actors::SupervisorActor;
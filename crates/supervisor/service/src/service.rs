@@ -4,11 +4,13 @@ use alloy_primitives::ChainId;
 use alloy_provider::{RootProvider, network::Ethereum};
 use alloy_rpc_client::RpcClient;
 use anyhow::Result;
+use async_trait::async_trait;
+use core::fmt::Debug;
 use futures::future;
 use jsonrpsee::client_transport::ws::Url;
 use kona_supervisor_core::{
-    ChainProcessor, CrossSafetyCheckerJob, LogIndexer, ReorgHandler, Supervisor,
-    config::Config,
+    ChainProcessor, CrossSafetyCheckerJob, LogIndexer, ReorgHandler, RewindJournal, Supervisor,
+    config::{Config, RollupConfig, SharedConfig},
     event::ChainEvent,
     l1_watcher::L1Watcher,
     rpc::{AdminError, AdminRequest, AdminRpc, SupervisorRpc},
@@ -29,12 +31,23 @@ use crate::actors::{
 // simplify long type signature
 type ManagedLogIndexer = LogIndexer<ManagedNode<ChainDb, Client>, ChainDb>;
 
+/// Loads the supervisor [`Config`] from its sources.
+///
+/// Used by the [`Service`] to reload the dependency set when a managed node of a chain that is
+/// not part of the running dependency set is added.
+#[async_trait]
+pub trait ConfigLoader: Debug + Send + Sync {
+    /// Loads a fresh [`Config`].
+    async fn load_config(&self) -> Result<Config>;
+}
+
 /// The main service structure for the Kona
 /// [`SupervisorService`](`kona_supervisor_core::SupervisorService`). Orchestrates the various
 /// components of the supervisor.
 #[derive(Debug)]
 pub struct Service {
-    config: Arc<Config>,
+    config: SharedConfig,
+    config_loader: Option<Arc<dyn ConfigLoader>>,
 
     supervisor: Arc<Supervisor<ManagedNode<ChainDb, Client>>>,
     database_factory: Arc<ChainDbFactory>,
@@ -49,18 +62,21 @@ pub struct Service {
     admin_receiver: Option<mpsc::Receiver<AdminRequest>>,
 
     cancel_token: CancellationToken,
+    l1_watcher_cancel_token: CancellationToken,
     join_set: JoinSet<Result<(), anyhow::Error>>,
 }
 
 impl Service {
     /// Creates a new Supervisor service instance.
     pub fn new(cfg: Config) -> Self {
-        let config = Arc::new(cfg);
-        let database_factory = Arc::new(ChainDbFactory::new(config.datadir.clone()).with_metrics());
+        let database_factory = Arc::new(ChainDbFactory::new(cfg.datadir.clone()).with_metrics());
+        let config = SharedConfig::new(cfg);
         let supervisor = Arc::new(Supervisor::new(config.clone(), database_factory.clone()));
+        let cancel_token = CancellationToken::new();
 
         Self {
             config,
+            config_loader: None,
 
             supervisor,
             database_factory,
@@ -73,22 +89,27 @@ impl Service {
             managed_node_receivers: HashMap::new(),
             admin_receiver: None,
 
-            cancel_token: CancellationToken::new(),
+            l1_watcher_cancel_token: cancel_token.child_token(),
+            cancel_token,
             join_set: JoinSet::new(),
         }
     }
 
+    /// Sets the [`ConfigLoader`] used to reload the dependency set at runtime.
+    ///
+    /// Without a loader, managed nodes can only be added for chains of the dependency set the
+    /// service was created with.
+    pub fn with_config_loader(mut self, loader: Arc<dyn ConfigLoader>) -> Self {
+        self.config_loader = Some(loader);
+        self
+    }
+
     /// Initialises the Supervisor service.
     pub async fn initialise(&mut self) -> Result<()> {
         // create sender and receiver channels for each chain
-        for chain_id in self.config.rollup_config_set.rollups.keys() {
-            let (chain_tx, chain_rx) = mpsc::channel::<ChainEvent>(1000);
-            self.chain_event_senders.insert(*chain_id, chain_tx);
-            self.chain_event_receivers.insert(*chain_id, chain_rx);
-
-            let (managed_node_tx, managed_node_rx) = mpsc::channel::<ManagedNodeCommand>(1000);
-            self.managed_node_senders.insert(*chain_id, managed_node_tx);
-            self.managed_node_receivers.insert(*chain_id, managed_node_rx);
+        let config = self.config.load();
+        for chain_id in config.rollup_config_set.rollups.keys() {
+            self.init_chain_channels(*chain_id);
         }
 
         self.init_database().await?;
@@ -103,27 +124,41 @@ impl Service {
         Ok(())
     }
 
+    fn init_chain_channels(&mut self, chain_id: ChainId) {
+        let (chain_tx, chain_rx) = mpsc::channel::<ChainEvent>(1000);
+        self.chain_event_senders.insert(chain_id, chain_tx);
+        self.chain_event_receivers.insert(chain_id, chain_rx);
+
+        let (managed_node_tx, managed_node_rx) = mpsc::channel::<ManagedNodeCommand>(1000);
+        self.managed_node_senders.insert(chain_id, managed_node_tx);
+        self.managed_node_receivers.insert(chain_id, managed_node_rx);
+    }
+
     async fn init_database(&self) -> Result<()> {
         info!(target: "supervisor::service", "Initialising databases for all chains...");
 
-        for (chain_id, config) in self.config.rollup_config_set.rollups.iter() {
-            // Initialise the database for each chain.
-            let db = self.database_factory.get_or_create_db(*chain_id)?;
-            let interop_time = config.interop_time;
-            let derived_pair = config.genesis.get_derived_pair();
-            if config.is_interop(derived_pair.derived.timestamp) {
-                info!(target: "supervisor::service", chain_id, interop_time, %derived_pair, "Initialising database for interop activation block");
-                db.initialise_log_storage(derived_pair.derived)?;
-                db.initialise_derivation_storage(derived_pair)?;
-            }
-            info!(target: "supervisor::service", chain_id, "Database initialized successfully");
+        for (chain_id, config) in self.config.load().rollup_config_set.rollups.iter() {
+            self.init_chain_database(*chain_id, config)?;
         }
         Ok(())
     }
 
+    fn init_chain_database(&self, chain_id: ChainId, config: &RollupConfig) -> Result<()> {
+        let db = self.database_factory.get_or_create_db(chain_id)?;
+        let interop_time = config.interop_time;
+        let derived_pair = config.genesis.get_derived_pair();
+        if config.is_interop(derived_pair.derived.timestamp) {
+            info!(target: "supervisor::service", chain_id, interop_time, %derived_pair, "Initialising database for interop activation block");
+            db.initialise_log_storage(derived_pair.derived)?;
+            db.initialise_derivation_storage(derived_pair)?;
+        }
+        info!(target: "supervisor::service", chain_id, "Database initialized successfully");
+        Ok(())
+    }
+
     async fn init_managed_node(&mut self, config: &ClientConfig) -> Result<()> {
         info!(target: "supervisor::service", node = %config.url, "Initialising managed node...");
-        let url = Url::parse(&self.config.l1_rpc).map_err(|err| {
+        let url = Url::parse(&self.config.load().l1_rpc).map_err(|err| {
             error!(target: "supervisor::service", %err, "Failed to parse L1 RPC URL");
             anyhow::anyhow!("failed to parse L1 RPC URL: {err}")
         })?;
@@ -135,6 +170,20 @@ impl Service {
             anyhow::anyhow!("failed to get chain ID from client: {err}")
         })?;
 
+        if self.config_loader.is_some() && !self.config.load().dependency_set.has_chain(chain_id) {
+            info!(target: "supervisor::service", %chain_id, "Managed node chain is not in the dependency set, reloading it");
+            self.reload_dependency_set().await?;
+        }
+
+        self.ensure_chain_in_dependency_set(chain_id).inspect_err(|_| {
+            warn!(target: "supervisor::service", %chain_id, node = %config.url, "Managed node chain is not in the dependency set");
+        })?;
+
+        if self.managed_nodes.contains_key(&chain_id) {
+            warn!(target: "supervisor::service", %chain_id, "Managed node for chain already exists, skipping initialization");
+            return Ok(());
+        }
+
         let db = self.database_factory.get_db(chain_id)?;
 
         let chain_event_sender = self
@@ -146,11 +195,6 @@ impl Service {
         let managed_node =
             ManagedNode::<ChainDb, Client>::new(client.clone(), db, provider, chain_event_sender);
 
-        if self.managed_nodes.contains_key(&chain_id) {
-            warn!(target: "supervisor::service", %chain_id, "Managed node for chain already exists, skipping initialization");
-            return Ok(());
-        }
-
         let managed_node = Arc::new(managed_node);
         // add the managed node to the supervisor service
         // also checks if the chain ID is supported
@@ -191,8 +235,82 @@ impl Service {
         Ok(())
     }
 
+    /// Ensures that a managed node's chain is part of the dependency set.
+    fn ensure_chain_in_dependency_set(&self, chain_id: ChainId) -> Result<()> {
+        if !self.config.load().dependency_set.has_chain(chain_id) {
+            return Err(anyhow::anyhow!("chain {chain_id} is not in the dependency set"));
+        }
+        Ok(())
+    }
+
+    /// Reloads the dependency set and joins every chain that it newly contains.
+    ///
+    /// The new chains get their own database, chain processor and cross safety checkers, and the
+    /// L1 watcher is restarted to include them. Chains that are already supervised keep running
+    /// and see the new chains through the [`SharedConfig`].
+    async fn reload_dependency_set(&mut self) -> Result<()> {
+        let new_chains = self.reload_config().await?;
+        if new_chains.is_empty() {
+            return Ok(());
+        }
+
+        for chain_id in new_chains {
+            self.join_chain(chain_id)?;
+        }
+        self.restart_l1_watcher()
+    }
+
+    /// Loads a fresh [`Config`] and stores it, returning the chains it newly contains.
+    async fn reload_config(&self) -> Result<Vec<ChainId>> {
+        let loader = self
+            .config_loader
+            .as_ref()
+            .ok_or(anyhow::anyhow!("no config loader set, adding chains requires a restart"))?;
+        let config = loader.load_config().await?;
+
+        // Components of running chains cannot be torn down, so the reloaded config must keep them.
+        let current = self.config.load();
+        if let Some(chain_id) = current.rollup_config_set.rollups.keys().find(|chain_id| {
+            !config.dependency_set.has_chain(**chain_id) ||
+                !config.rollup_config_set.rollups.contains_key(*chain_id)
+        }) {
+            return Err(anyhow::anyhow!(
+                "reloaded config drops chain {chain_id}, removing chains requires a restart"
+            ));
+        }
+
+        let mut new_chains = config
+            .rollup_config_set
+            .rollups
+            .keys()
+            .filter(|chain_id| !current.rollup_config_set.rollups.contains_key(*chain_id))
+            .copied()
+            .collect::<Vec<_>>();
+        new_chains.sort_unstable();
+
+        info!(target: "supervisor::service", ?new_chains, "Reloaded dependency set");
+        self.config.store(config);
+        Ok(new_chains)
+    }
+
+    fn join_chain(&mut self, chain_id: ChainId) -> Result<()> {
+        info!(target: "supervisor::service", chain_id, "Joining chain from reloaded dependency set");
+
+        let config = self.config.load();
+        let rollup_config = config
+            .rollup_config_set
+            .rollups
+            .get(&chain_id)
+            .ok_or(anyhow::anyhow!("no rollup config found for chain {chain_id}"))?;
+
+        self.init_chain_channels(chain_id);
+        self.init_chain_database(chain_id, rollup_config)?;
+        self.start_chain_processor(chain_id)?;
+        self.start_cross_safety_checkers(chain_id, rollup_config.block_time)
+    }
+
     async fn init_managed_nodes(&mut self) -> Result<()> {
-        let configs = self.config.l2_consensus_nodes_config.clone();
+        let configs = self.config.load().l2_consensus_nodes_config.clone();
         for config in configs.iter() {
             self.init_managed_node(config).await?;
         }
@@ -202,63 +320,69 @@ impl Service {
     async fn init_chain_processor(&mut self) -> Result<()> {
         info!(target: "supervisor::service", "Initialising chain processors for all chains...");
 
-        for (chain_id, _) in self.config.rollup_config_set.rollups.iter() {
-            let db = self.database_factory.get_db(*chain_id)?;
-
-            let managed_node_sender = self
-                .managed_node_senders
-                .get(chain_id)
-                .ok_or(anyhow::anyhow!("no managed node sender found for chain {chain_id}"))?
-                .clone();
-
-            let log_indexer = Arc::new(LogIndexer::new(*chain_id, None, db.clone()));
-            self.log_indexers.insert(*chain_id, log_indexer.clone());
-
-            // initialise chain processor for the chain.
-            let mut processor = ChainProcessor::new(
-                self.config.clone(),
-                *chain_id,
-                log_indexer,
-                db,
-                managed_node_sender,
-            );
-
-            // todo: enable metrics only if configured
-            processor = processor.with_metrics();
-
-            // Start the chain processor actor.
-            let chain_event_receiver = self
-                .chain_event_receivers
-                .remove(chain_id)
-                .ok_or(anyhow::anyhow!("no chain event receiver found for chain {chain_id}"))?;
-
-            let cancel_token = self.cancel_token.clone();
-            self.join_set.spawn(async move {
-                if let Err(err) =
-                    ChainProcessorActor::new(processor, cancel_token, chain_event_receiver)
-                        .start()
-                        .await
-                {
-                    Err(anyhow::anyhow!(err))
-                } else {
-                    Ok(())
-                }
-            });
+        let config = self.config.load();
+        for chain_id in config.rollup_config_set.rollups.keys() {
+            self.start_chain_processor(*chain_id)?;
         }
         Ok(())
     }
 
+    fn start_chain_processor(&mut self, chain_id: ChainId) -> Result<()> {
+        let db = self.database_factory.get_db(chain_id)?;
+
+        let managed_node_sender = self
+            .managed_node_senders
+            .get(&chain_id)
+            .ok_or(anyhow::anyhow!("no managed node sender found for chain {chain_id}"))?
+            .clone();
+
+        let log_indexer = Arc::new(LogIndexer::new(chain_id, None, db.clone()));
+        self.log_indexers.insert(chain_id, log_indexer.clone());
+
+        // initialise chain processor for the chain.
+        let mut processor = ChainProcessor::new(
+            Arc::new(self.config.clone()),
+            chain_id,
+            log_indexer,
+            db,
+            managed_node_sender,
+        );
+
+        // todo: enable metrics only if configured
+        processor = processor.with_metrics();
+
+        // Start the chain processor actor.
+        let chain_event_receiver = self
+            .chain_event_receivers
+            .remove(&chain_id)
+            .ok_or(anyhow::anyhow!("no chain event receiver found for chain {chain_id}"))?;
+
+        let cancel_token = self.cancel_token.clone();
+        self.join_set.spawn(async move {
+            if let Err(err) =
+                ChainProcessorActor::new(processor, cancel_token, chain_event_receiver)
+                    .start()
+                    .await
+            {
+                Err(anyhow::anyhow!(err))
+            } else {
+                Ok(())
+            }
+        });
+        Ok(())
+    }
+
     fn init_l1_watcher(&mut self) -> Result<()> {
         info!(target: "supervisor::service", "Initialising L1 watcher...");
 
-        let l1_rpc_url = Url::parse(&self.config.l1_rpc).map_err(|err| {
+        let config = self.config.load();
+        let l1_rpc_url = Url::parse(&config.l1_rpc).map_err(|err| {
             error!(target: "supervisor::service", %err, "Failed to parse L1 RPC URL");
             anyhow::anyhow!("failed to parse L1 RPC URL: {err}")
         })?;
         let l1_rpc = RpcClient::new_http(l1_rpc_url);
 
-        let chain_dbs_map: HashMap<ChainId, Arc<ChainDb>> = self
-            .config
+        let chain_dbs_map: HashMap<ChainId, Arc<ChainDb>> = config
             .rollup_config_set
             .rollups
             .keys()
@@ -273,11 +397,15 @@ impl Service {
             .collect::<Result<HashMap<ChainId, Arc<ChainDb>>>>()?;
 
         let database_factory = self.database_factory.clone();
-        let cancel_token = self.cancel_token.clone();
+        let cancel_token = self.l1_watcher_cancel_token.clone();
         let event_senders = self.chain_event_senders.clone();
+        let managed_node_senders = self.managed_node_senders.clone();
+        let rewind_journal = RewindJournal::new(config.datadir.join(RewindJournal::FILE_NAME));
         self.join_set.spawn(async move {
-            let reorg_handler =
-                ReorgHandler::new(l1_rpc.clone(), chain_dbs_map.clone()).with_metrics();
+            let reorg_handler = ReorgHandler::new(l1_rpc.clone(), chain_dbs_map.clone())
+                .with_managed_node_senders(managed_node_senders)
+                .with_rewind_journal(rewind_journal)
+                .with_metrics();
 
             // Start the L1 watcher streaming loop.
             let l1_watcher = L1Watcher::new(
@@ -294,49 +422,63 @@ impl Service {
         Ok(())
     }
 
+    /// Restarts the L1 watcher so that it covers every chain the service currently supervises.
+    fn restart_l1_watcher(&mut self) -> Result<()> {
+        self.l1_watcher_cancel_token.cancel();
+        self.l1_watcher_cancel_token = self.cancel_token.child_token();
+        self.init_l1_watcher()
+    }
+
     async fn init_cross_safety_checker(&mut self) -> Result<()> {
         info!(target: "supervisor::service", "Initialising cross safety checker...");
 
-        for (&chain_id, config) in &self.config.rollup_config_set.rollups {
-            let db = Arc::clone(&self.database_factory);
-            let cancel = self.cancel_token.clone();
-
-            let chain_event_sender = self
-                .chain_event_senders
-                .get(&chain_id)
-                .ok_or(anyhow::anyhow!("no chain event sender found for chain {chain_id}"))?
-                .clone();
-
-            let cross_safe_job = CrossSafetyCheckerJob::new(
-                chain_id,
-                db.clone(),
-                cancel.clone(),
-                Duration::from_secs(config.block_time),
-                CrossSafePromoter,
-                chain_event_sender.clone(),
-                self.config.clone(),
-            );
+        let config = self.config.load();
+        for (&chain_id, rollup_config) in &config.rollup_config_set.rollups {
+            self.start_cross_safety_checkers(chain_id, rollup_config.block_time)?;
+        }
+        Ok(())
+    }
 
-            self.join_set.spawn(async move {
-                cross_safe_job.run().await;
-                Ok(())
-            });
-
-            let cross_unsafe_job = CrossSafetyCheckerJob::new(
-                chain_id,
-                db,
-                cancel,
-                Duration::from_secs(config.block_time),
-                CrossUnsafePromoter,
-                chain_event_sender,
-                self.config.clone(),
-            );
+    fn start_cross_safety_checkers(&mut self, chain_id: ChainId, block_time: u64) -> Result<()> {
+        let db = Arc::clone(&self.database_factory);
+        let cancel = self.cancel_token.clone();
+        let validator = Arc::new(self.config.clone());
 
-            self.join_set.spawn(async move {
-                cross_unsafe_job.run().await;
-                Ok(())
-            });
-        }
+        let chain_event_sender = self
+            .chain_event_senders
+            .get(&chain_id)
+            .ok_or(anyhow::anyhow!("no chain event sender found for chain {chain_id}"))?
+            .clone();
+
+        let cross_safe_job = CrossSafetyCheckerJob::new(
+            chain_id,
+            db.clone(),
+            cancel.clone(),
+            Duration::from_secs(block_time),
+            CrossSafePromoter,
+            chain_event_sender.clone(),
+            validator.clone(),
+        );
+
+        self.join_set.spawn(async move {
+            cross_safe_job.run().await;
+            Ok(())
+        });
+
+        let cross_unsafe_job = CrossSafetyCheckerJob::new(
+            chain_id,
+            db,
+            cancel,
+            Duration::from_secs(block_time),
+            CrossUnsafePromoter,
+            chain_event_sender,
+            validator,
+        );
+
+        self.join_set.spawn(async move {
+            cross_unsafe_job.run().await;
+            Ok(())
+        });
         Ok(())
     }
 
@@ -362,7 +504,7 @@ impl Service {
 
         let mut rpc_module = supervisor_rpc.into_rpc();
 
-        if self.config.enable_admin_api {
+        if self.config.load().enable_admin_api {
             info!(target: "supervisor::service", "Enabling Supervisor Admin API");
 
             let (admin_tx, admin_rx) = mpsc::channel::<AdminRequest>(100);
@@ -373,7 +515,7 @@ impl Service {
             self.admin_receiver = Some(admin_rx);
         }
 
-        let rpc_addr = self.config.rpc_addr;
+        let rpc_addr = self.config.load().rpc_addr;
         let cancel_token = self.cancel_token.clone();
         self.join_set.spawn(async move {
             if let Err(err) =
@@ -475,11 +617,30 @@ impl Service {
 mod tests {
     use std::{net::SocketAddr, path::PathBuf};
 
-    use kona_interop::DependencySet;
-    use kona_supervisor_core::config::RollupConfigSet;
+    use kona_interop::{ChainDependency, DependencySet};
+    use kona_supervisor_core::{SupervisorService, config::RollupConfigSet};
 
     use super::*;
 
+    #[derive(Debug)]
+    struct StaticConfigLoader(Config);
+
+    #[async_trait]
+    impl ConfigLoader for StaticConfigLoader {
+        async fn load_config(&self) -> Result<Config> {
+            Ok(self.0.clone())
+        }
+    }
+
+    fn make_config_with_chains(chain_ids: &[ChainId]) -> Config {
+        let mut cfg = make_test_config(true);
+        for chain_id in chain_ids {
+            cfg.dependency_set.dependencies.insert(*chain_id, ChainDependency::default());
+            cfg.rollup_config_set.rollups.insert(*chain_id, RollupConfig::default());
+        }
+        cfg
+    }
+
     fn make_test_config(enable_admin: bool) -> Config {
         let mut cfg = Config::new(
             "http://localhost:8545".to_string(),
@@ -502,8 +663,53 @@ mod tests {
         let cfg = Arc::new(make_test_config(true));
         let mut svc = Service::new((*cfg).clone());
 
-        svc.config = cfg.clone();
+        svc.config = SharedConfig::new((*cfg).clone());
         svc.init_rpc_server().await.expect("init_rpc_server failed");
         assert!(svc.admin_receiver.is_some(), "admin_receiver must be set when admin enabled");
     }
+
+    #[test]
+    fn test_ensure_chain_in_dependency_set() {
+        let mut cfg = make_test_config(true);
+        cfg.dependency_set.dependencies.insert(10, ChainDependency::default());
+        let svc = Service::new(cfg);
+
+        assert!(svc.ensure_chain_in_dependency_set(10).is_ok());
+        let err = svc.ensure_chain_in_dependency_set(11).unwrap_err();
+        assert!(err.to_string().contains("chain 11 is not in the dependency set"));
+    }
+
+    #[tokio::test]
+    async fn test_reload_config_returns_new_chains() {
+        let loader = StaticConfigLoader(make_config_with_chains(&[10, 11, 12]));
+        let svc = Service::new(make_config_with_chains(&[10])).with_config_loader(Arc::new(loader));
+        assert!(svc.ensure_chain_in_dependency_set(11).is_err());
+
+        let new_chains = svc.reload_config().await.expect("reload failed");
+
+        assert_eq!(new_chains, vec![11, 12]);
+        assert!(svc.ensure_chain_in_dependency_set(11).is_ok());
+        // the supervisor shares the reloaded config
+        assert_eq!(svc.supervisor.chain_ids().collect::<Vec<_>>(), vec![10, 11, 12]);
+    }
+
+    #[tokio::test]
+    async fn test_reload_config_rejects_dropped_chain() {
+        let loader = StaticConfigLoader(make_config_with_chains(&[11]));
+        let svc = Service::new(make_config_with_chains(&[10])).with_config_loader(Arc::new(loader));
+
+        let err = svc.reload_config().await.unwrap_err();
+
+        assert!(err.to_string().contains("drops chain 10"));
+        assert!(svc.ensure_chain_in_dependency_set(10).is_ok());
+        assert!(svc.ensure_chain_in_dependency_set(11).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_reload_config_requires_loader() {
+        let svc = Service::new(make_config_with_chains(&[10]));
+
+        let err = svc.reload_config().await.unwrap_err();
+        assert!(err.to_string().contains("no config loader set"));
+    }
 }
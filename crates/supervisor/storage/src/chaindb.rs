@@ -393,6 +393,16 @@ impl HeadRefStorageWriter for ChainDb {
                 let derived_pair = dp.get_derived_block_pair(block.id())?;
                 sp.update_safety_head_ref(SafetyLevel::CrossSafe, block)?;
 
+                // A cross-safe block is cross-unsafe too, so keep the CrossUnsafe head from
+                // falling behind the CrossSafe head.
+                match sp.get_safety_head_ref(SafetyLevel::CrossUnsafe) {
+                    Ok(cross_unsafe) if cross_unsafe.number >= block.number => {}
+                    Ok(_) | Err(StorageError::FutureData) => {
+                        sp.update_safety_head_ref(SafetyLevel::CrossUnsafe, block)?;
+                    }
+                    Err(err) => return Err(err),
+                }
+
                 Ok(derived_pair.into())
             })?
         })
@@ -916,6 +926,10 @@ mod tests {
 
         let cross_safe_block = db.get_safety_head_ref(SafetyLevel::CrossSafe).unwrap();
         assert_eq!(cross_safe_block, block2);
+
+        // the CrossUnsafe head was behind, so it's advanced along with the CrossSafe head
+        let cross_unsafe_block = db.get_safety_head_ref(SafetyLevel::CrossUnsafe).unwrap();
+        assert_eq!(cross_unsafe_block, block2);
     }
 
     #[test]
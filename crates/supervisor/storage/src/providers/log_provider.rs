@@ -355,7 +355,9 @@ where
             );
         })?;
 
-        let log_entry = result.ok_or_else(|| {
+        // The cursor seeks to the first log at or after the given index, so the match must be
+        // exact for the log to be found.
+        let log_entry = result.filter(|entry| entry.index == log_index).ok_or_else(|| {
             warn!(
                 target: "supervisor::storage",
                 chain_id = %self.chain_id,
@@ -636,6 +638,27 @@ mod tests {
         assert!(matches!(result, Err(StorageError::EntryNotFound(_))));
     }
 
+    #[test]
+    fn test_get_log_requires_exact_index() {
+        let db = setup_db();
+
+        let genesis = genesis_block();
+        initialize_db(&db, &genesis).expect("Failed to initialize DB with genesis block");
+
+        let block1 = sample_block_info(1, genesis.hash);
+        let logs = vec![sample_log(0, false), sample_log(2, true)];
+        insert_block_logs(&db, &block1, logs.clone()).expect("Failed to insert logs");
+
+        let tx = db.tx().expect("Failed to start RO tx");
+        let log_reader = LogProvider::new(&tx, CHAIN_ID);
+
+        assert_eq!(log_reader.get_log(1, 2).expect("Failed to get log"), logs[1]);
+
+        // should not resolve to the next log in the block
+        let result = log_reader.get_log(1, 1);
+        assert!(matches!(result, Err(StorageError::EntryNotFound(_))));
+    }
+
     #[test]
     fn test_block_append_failed_on_order_mismatch() {
         let db = setup_db();
@@ -20,6 +20,18 @@ pub enum CliError {
     /// Error initializing metrics.
     #[error("Failed to initialize metrics")]
     MetricsInitialization(#[from] metrics_exporter_prometheus::BuildError),
+
+    /// Error when log filter directives cannot be parsed.
+    #[error("Invalid log filter: {0}")]
+    InvalidLogFilter(#[from] tracing_subscriber::filter::ParseError),
+
+    /// Error when the log filter is reloaded before the tracing subscriber is initialized.
+    #[error("The log filter cannot be reloaded before the tracing subscriber is initialized")]
+    LogFilterNotReloadable,
+
+    /// Error when the log filter fails to reload.
+    #[error("Failed to reload the log filter: {0}")]
+    LogFilterReload(#[from] tracing_subscriber::reload::Error),
 }
 
 /// Type alias for CLI results.
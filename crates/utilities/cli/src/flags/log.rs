@@ -55,6 +55,13 @@ pub struct LogArgs {
     /// If set, new log files will be created every interval.
     #[arg(long = "logs.file.rotation", default_value = "never", env = "KONA_LOG_FILE_ROTATION")]
     pub file_rotation: LogRotation,
+    /// The path of the file to write the event journal to.
+    /// If not set, no event journal is written.
+    ///
+    /// The event journal records significant decisions of the node, such as accepted and dropped
+    /// batches, channel timeouts, L1 reorgs and forkchoice updates, as JSON lines.
+    #[arg(long = "logs.journal.path", global = true, env = "KONA_LOG_JOURNAL_PATH")]
+    pub journal_path: Option<PathBuf>,
 }
 
 #[cfg(test)]
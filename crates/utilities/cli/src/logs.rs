@@ -51,6 +51,10 @@ pub struct LogConfig {
     pub stdout_logs: Option<StdoutLogConfig>,
     /// The configuration for file logging.
     pub file_logs: Option<FileLogConfig>,
+    /// The path of the JSONL event journal, if enabled.
+    ///
+    /// See [`JOURNAL_TARGET`](crate::JOURNAL_TARGET).
+    pub journal_path: Option<PathBuf>,
 }
 
 impl Default for LogConfig {
@@ -59,6 +63,7 @@ impl Default for LogConfig {
             global_level: LevelFilter::INFO,
             stdout_logs: Some(StdoutLogConfig { format: LogFormat::Full }),
             file_logs: None,
+            journal_path: None,
         }
     }
 }
@@ -92,6 +97,6 @@ impl LogConfig {
             rotation: args.file_rotation,
         });
 
-        Self { global_level: level, stdout_logs, file_logs }
+        Self { global_level: level, stdout_logs, file_logs, journal_path: args.journal_path }
     }
 }
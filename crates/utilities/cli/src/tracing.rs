@@ -1,15 +1,33 @@
 //! [tracing_subscriber] utilities.
 
 use tracing_subscriber::{
-    Layer,
+    Layer, Registry,
+    filter::{Targets, filter_fn},
     prelude::__tracing_subscriber_SubscriberExt,
+    registry::LookupSpan,
+    reload,
     util::{SubscriberInitExt, TryInitError},
 };
 
 use serde::{Deserialize, Serialize};
+use std::{ffi::OsStr, path::Path, sync::OnceLock};
+use tracing::{Subscriber, level_filters::LevelFilter};
 use tracing_subscriber::EnvFilter;
 
-use crate::{LogConfig, LogRotation};
+use crate::{CliError, CliResult, LogConfig, LogRotation};
+
+/// The handle used to swap the filter of the regular logs at runtime.
+///
+/// Set once, when the tracing subscriber is initialized.
+static LOG_FILTER_HANDLE: OnceLock<reload::Handle<EnvFilter, Registry>> = OnceLock::new();
+
+/// The target of the events recorded in the event journal.
+///
+/// Events emitted with this target are structured records of significant decisions, identified by
+/// their `event` field, emitted at `TRACE` level. They are written as JSON lines to the journal
+/// file configured in [`LogConfig::journal_path`] regardless of the log verbosity, and are
+/// excluded from the regular logs.
+pub const JOURNAL_TARGET: &str = "journal";
 
 /// The format of the logs.
 #[derive(
@@ -77,20 +95,60 @@ impl LogConfig {
             LogFormat::Compact => tracing_subscriber::fmt::layer().compact().boxed(),
         });
 
+        let journal_layer = self.journal_path.as_deref().map(journal_layer);
+
         let env_filter = env_filter
             .unwrap_or(EnvFilter::from_default_env())
             .add_directive(self.global_level.into());
+        let (env_filter, handle) = reload::Layer::new(env_filter);
+        let log_layers = file_layer
+            .into_iter()
+            .chain(stdout_layer)
+            .collect::<Vec<_>>()
+            .with_filter(env_filter)
+            .with_filter(filter_fn(|metadata| metadata.target() != JOURNAL_TARGET));
 
-        tracing_subscriber::registry()
-            .with(env_filter)
-            .with(file_layer)
-            .with(stdout_layer)
-            .try_init()?;
+        tracing_subscriber::registry().with(log_layers).with(journal_layer).try_init()?;
+        let _ = LOG_FILTER_HANDLE.set(handle);
 
         Ok(())
     }
 }
 
+/// Returns the layer writing the events of the [`JOURNAL_TARGET`] to the journal file at the given
+/// path, as JSON lines.
+fn journal_layer<S>(path: &Path) -> impl Layer<S>
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    let directory = path.parent().unwrap_or(path);
+    let file_name = path.file_name().unwrap_or(OsStr::new(JOURNAL_TARGET));
+    let appender = tracing_appender::rolling::never(directory, file_name);
+
+    tracing_subscriber::fmt::layer()
+        .json()
+        .flatten_event(true)
+        .with_current_span(false)
+        .with_span_list(false)
+        .with_writer(appender)
+        .with_filter(Targets::new().with_target(JOURNAL_TARGET, LevelFilter::TRACE))
+}
+
+/// Replaces the filter of the regular logs with the given directives, without restarting the
+/// process.
+///
+/// The directives use the same syntax as the `RUST_LOG` environment variable, e.g.
+/// `info,engine=debug`. The event journal is not affected.
+///
+/// Returns an error if the directives are invalid, or if the tracing subscriber was not
+/// initialized through [`LogConfig::init_tracing_subscriber`].
+pub fn reload_log_filter(directives: &str) -> CliResult<()> {
+    let filter = EnvFilter::builder().parse(directives)?;
+    let handle = LOG_FILTER_HANDLE.get().ok_or(CliError::LogFilterNotReloadable)?;
+    handle.reload(filter)?;
+    Ok(())
+}
+
 /// This provides function for init tracing in testing
 ///
 /// # Functions
@@ -100,3 +158,28 @@ impl LogConfig {
 pub fn init_test_tracing() {
     let _ = LogConfig::default().init_tracing_subscriber(None::<EnvFilter>);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_journal_layer() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("journal.jsonl");
+        let subscriber = tracing_subscriber::registry().with(journal_layer(&path));
+
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::trace!(target: "journal", event = "batch_accepted", batch_timestamp = 2);
+            tracing::info!(target: "derivation", "Not a journal event");
+        });
+
+        let journal = std::fs::read_to_string(&path).unwrap();
+        let lines = journal.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 1);
+        let record = serde_json::from_str::<serde_json::Value>(lines[0]).unwrap();
+        assert_eq!(record["event"], "batch_accepted");
+        assert_eq!(record["batch_timestamp"], 2);
+        assert_eq!(record["level"], "TRACE");
+    }
+}
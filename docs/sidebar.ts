@@ -75,6 +75,10 @@ export const sidebar: SidebarItem[] = [
               {
                   text: "admin",
                   link: "/node/rpc/admin",
+              },
+              {
+                  text: "debug",
+                  link: "/node/rpc/debug",
               }
           ]
       },
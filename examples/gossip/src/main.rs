@@ -97,13 +97,20 @@ impl GossipCommand {
                 gossip_config: Default::default(),
                 scoring: Default::default(),
                 topic_scoring: Default::default(),
+                topic_score_weights: Default::default(),
                 monitor_peers: Default::default(),
                 bootstore: None,
+                ban_list: None,
                 gater_config: Default::default(),
                 bootnodes: Default::default(),
+                static_peers: Default::default(),
+                trusted_peers: Default::default(),
                 rollup_config: rollup_config.clone(),
                 gossip_signer: None,
                 enr_update: true,
+                nat: Default::default(),
+                publish_rate_limit: Default::default(),
+                signer_rotation_grace: Default::default(),
             }
             .into(),
         );